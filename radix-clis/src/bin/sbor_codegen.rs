@@ -0,0 +1,13 @@
+#[cfg(windows)]
+use colored::*;
+use radix_clis::error::exit_with_error;
+use radix_clis::sbor_codegen;
+
+pub fn main() {
+    #[cfg(windows)]
+    control::set_virtual_terminal(true).unwrap();
+    match sbor_codegen::run() {
+        Err(msg) => exit_with_error(msg, 1),
+        _ => {}
+    }
+}
@@ -6,6 +6,10 @@ pub mod resim;
 pub mod rtmc;
 /// Radix transaction manifest decompiler CLI.
 pub mod rtmd;
+/// Radix offline transaction signing CLI.
+pub mod rtsign;
+/// Rust types from SBOR schemas generator CLI.
+pub mod sbor_codegen;
 /// Scrypto CLI.
 pub mod scrypto;
 /// Stubs Generator CLI.
@@ -0,0 +1,203 @@
+use super::ledger_transaction_execution::execute_ledger_transaction;
+use super::txn_reader::TxnReader;
+use super::Error;
+use clap::{ArgEnum, Parser};
+use flate2::read::GzDecoder;
+use radix_common::prelude::*;
+use radix_engine::vm::wasm::*;
+use radix_engine::vm::ScryptoVm;
+use radix_rust::ContextualSerialize;
+use radix_substate_store_impls::memory_db::InMemorySubstateDatabase;
+use radix_substate_store_queries::query::{ChangeFeed, ChangeRecord};
+use sbor::representations::*;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::thread;
+use tar::Archive;
+
+/// Run transactions in archive from genesis, using in-memory database, and write the typed state
+/// changes made by each of them to a file
+#[derive(Parser, Debug)]
+pub struct TxnChangeFeed {
+    /// The transaction file, in `.tar.gz` format, with entries sorted
+    pub source: PathBuf,
+    /// Path to the output file
+    pub output_file: PathBuf,
+
+    /// The network to use, [mainnet | stokenet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+    /// The max version to execute
+    #[clap(short, long)]
+    pub max_version: Option<u64>,
+    /// The format of the output file
+    #[clap(short, long, arg_enum, default_value = "json")]
+    pub format: ChangeFeedFormat,
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeFeedFormat {
+    /// One JSON object per transaction and line, in the natural JSON representation of SBOR.
+    Json,
+    /// The SBOR encoding of each transaction, prefixed with its length as a little-endian `u32`.
+    Sbor,
+}
+
+/// The changes made by the transaction committed at a state version.
+#[derive(Debug, Clone, ScryptoSbor)]
+pub struct TransactionChanges {
+    pub state_version: u64,
+    pub changes: Vec<ChangeRecord>,
+}
+
+impl TxnChangeFeed {
+    pub fn run(&self) -> Result<(), String> {
+        let network = match &self.network {
+            Some(n) => NetworkDefinition::from_str(n).map_err(Error::ParseNetworkError)?,
+            None => NetworkDefinition::mainnet(),
+        };
+
+        let cur_version = 0;
+        let to_version = self.max_version;
+
+        let (tx, rx) = flume::bounded(10);
+
+        // txn reader
+        let mut txn_reader = if self.source.is_file() {
+            let tar_gz = File::open(&self.source).map_err(Error::IOError)?;
+            let tar = GzDecoder::new(tar_gz);
+            let archive = Archive::new(tar);
+            TxnReader::TransactionFile(archive)
+        } else if self.source.is_dir() {
+            TxnReader::StateManagerDatabaseDir(self.source.clone())
+        } else {
+            return Err(Error::InvalidTransactionSource.into());
+        };
+        let txn_read_thread_handle =
+            thread::spawn(move || txn_reader.read(cur_version, to_version, tx));
+
+        // txn executor
+        let mut output = BufWriter::new(File::create(&self.output_file).map_err(Error::IOError)?);
+        let (type_id, schema) =
+            generate_full_schema_from_single_type::<TransactionChanges, ScryptoCustomSchema>();
+        let address_bech32_encoder = AddressBech32Encoder::new(&network);
+        let mut database = InMemorySubstateDatabase::standard();
+        let scrypto_vm = ScryptoVm::<DefaultWasmEngine>::default();
+        let change_feed = ChangeFeed::new().replay(&mut database, |database| {
+            let tx_payload = rx.recv().ok()?;
+            Some(execute_ledger_transaction(
+                database,
+                &scrypto_vm,
+                &network,
+                &tx_payload,
+                false,
+            ))
+        });
+        for (state_version, changes) in (cur_version + 1..).zip(change_feed) {
+            let payload = encode_transaction_changes(state_version, changes);
+            match self.format {
+                ChangeFeedFormat::Json => {
+                    let raw_payload = ScryptoRawPayload::new_from_valid_slice(&payload);
+                    let serializable =
+                        raw_payload.serializable(SerializationParameters::WithSchema {
+                            mode: SerializationMode::Natural,
+                            custom_context: ScryptoValueDisplayContext::with_optional_bech32(Some(
+                                &address_bech32_encoder,
+                            )),
+                            schema: schema.v1(),
+                            type_id,
+                            depth_limit: SCRYPTO_SBOR_V1_MAX_DEPTH,
+                        });
+                    serde_json::to_writer(&mut output, &serializable)
+                        .map_err(|err| Error::IOError(err.into()))?;
+                    output.write_all(b"\n").map_err(Error::IOError)?;
+                }
+                ChangeFeedFormat::Sbor => {
+                    output
+                        .write_all(&(payload.len() as u32).to_le_bytes())
+                        .map_err(Error::IOError)?;
+                    output.write_all(&payload).map_err(Error::IOError)?;
+                }
+            }
+        }
+        output.flush().map_err(Error::IOError)?;
+
+        txn_read_thread_handle.join().unwrap()?;
+
+        Ok(())
+    }
+}
+
+/// Encodes the changes made by a transaction. The raw values of the changes are nested a few
+/// levels deeper than in their substates, so a change of a deep enough value can't be encoded
+/// within the max depth - such changes are reported and skipped.
+fn encode_transaction_changes(state_version: u64, changes: Vec<ChangeRecord>) -> Vec<u8> {
+    let transaction_changes = TransactionChanges {
+        state_version,
+        changes,
+    };
+    if let Ok(payload) = scrypto_encode(&transaction_changes) {
+        return payload;
+    }
+
+    let changes = transaction_changes
+        .changes
+        .into_iter()
+        .filter(|change| {
+            match scrypto_encode(&TransactionChanges {
+                state_version,
+                changes: vec![change.clone()],
+            }) {
+                Ok(_) => true,
+                Err(err) => {
+                    eprintln!(
+                        "Skipping a change of {:?} at state version {} which can't be encoded: {:?}",
+                        change.node_id, state_version, err
+                    );
+                    false
+                }
+            }
+        })
+        .collect();
+    scrypto_encode(&TransactionChanges {
+        state_version,
+        changes,
+    })
+    .expect("Changes which can be encoded on their own can be encoded together")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use radix_substate_store_queries::query::TypedChange;
+
+    fn non_fungible_data_change(depth: usize) -> ChangeRecord {
+        let mut data = ScryptoValue::Tuple { fields: vec![] };
+        for _ in 1..depth {
+            data = ScryptoValue::Tuple { fields: vec![data] };
+        }
+        ChangeRecord {
+            node_id: XRD.into_node_id(),
+            owner: None,
+            change: TypedChange::NonFungibleDataChanged {
+                local_id: NonFungibleLocalId::integer(1),
+                previous_data: None,
+                new_data: Some(data),
+            },
+        }
+    }
+
+    #[test]
+    fn changes_of_too_deep_values_are_skipped() {
+        let shallow_change = non_fungible_data_change(2);
+        let deep_change = non_fungible_data_change(SCRYPTO_SBOR_V1_MAX_DEPTH);
+
+        let payload =
+            encode_transaction_changes(1, vec![shallow_change.clone(), deep_change.clone()]);
+
+        let transaction_changes = scrypto_decode::<TransactionChanges>(&payload).unwrap();
+        assert_eq!(transaction_changes.state_version, 1);
+        assert_eq!(transaction_changes.changes, vec![shallow_change]);
+    }
+}
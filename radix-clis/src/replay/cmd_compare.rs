@@ -0,0 +1,282 @@
+use super::ledger_transaction::{PreparedLedgerTransaction, PreparedLedgerTransactionInner};
+use super::ledger_transaction_execution::*;
+use super::receipt_comparison::*;
+use super::txn_reader::TxnReader;
+use super::Error;
+use clap::Parser;
+use flate2::read::GzDecoder;
+use flume;
+use radix_common::prelude::NetworkDefinition;
+use radix_common::prelude::*;
+use radix_engine::updates::{ProtocolBuilder, ProtocolVersion};
+use radix_engine::vm::wasm::*;
+use radix_engine::vm::{NativeVmExtension, NoExtension, ScryptoVm, VmInit};
+use radix_substate_store_impls::memory_db::InMemorySubstateDatabase;
+use radix_substate_store_impls::state_tree_support::StateTreeUpdatingDatabase;
+use radix_substate_store_interface::db_key_mapper::SpreadPrefixKeyMapper;
+use radix_substate_store_interface::interface::CommittableSubstateDatabase;
+use std::fs::File;
+use std::path::PathBuf;
+use std::thread;
+use tar::Archive;
+
+/// Run transactions in archive against two in-memory ledgers with different configurations,
+/// stopping at the first transaction whose receipts diverge
+#[derive(Parser, Debug)]
+pub struct TxnCompare {
+    /// The transaction file, in `.tar.gz` format, with entries sorted
+    pub source: PathBuf,
+
+    /// The network to use, [mainnet | stokenet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+    /// The max version to execute
+    #[clap(short, long)]
+    pub max_version: Option<u64>,
+
+    /// The protocol version the left ledger is updated to, [babylon | anemone | bottlenose | cuttlefish]
+    #[clap(long)]
+    pub left_protocol_version: Option<String>,
+    /// The protocol version the right ledger is updated to, [babylon | anemone | bottlenose | cuttlefish]
+    #[clap(long)]
+    pub right_protocol_version: Option<String>,
+    /// The version after which protocol updates are enacted; defaults to the end of genesis
+    #[clap(long)]
+    pub protocol_update_version: Option<u64>,
+
+    /// The WASM engine the left ledger executes Scrypto code with, [wasmi | wasmer]; defaults to
+    /// the default engine of the build. `wasmer` requires the `wasmer` feature.
+    #[clap(long)]
+    pub left_wasm_engine: Option<String>,
+    /// The WASM engine the right ledger executes Scrypto code with, [wasmi | wasmer]; defaults to
+    /// the default engine of the build. `wasmer` requires the `wasmer` feature.
+    #[clap(long)]
+    pub right_wasm_engine: Option<String>,
+
+    /// Trace transaction execution
+    #[clap(long)]
+    pub trace: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum WasmEngineKind {
+    Wasmi,
+    #[cfg(feature = "wasmer")]
+    Wasmer,
+}
+
+impl WasmEngineKind {
+    /// The kind of `DefaultWasmEngine`
+    #[cfg(not(feature = "wasmer"))]
+    const DEFAULT: Self = Self::Wasmi;
+    #[cfg(feature = "wasmer")]
+    const DEFAULT: Self = Self::Wasmer;
+
+    fn parse(name: &Option<String>) -> Result<Self, Error> {
+        match name.as_deref() {
+            None => Ok(Self::DEFAULT),
+            Some("wasmi") => Ok(Self::Wasmi),
+            #[cfg(feature = "wasmer")]
+            Some("wasmer") => Ok(Self::Wasmer),
+            Some(name) => Err(Error::InvalidWasmEngine(name.to_string())),
+        }
+    }
+}
+
+struct ComparedLedger<'v, W: WasmEngine, E: NativeVmExtension> {
+    database: StateTreeUpdatingDatabase<InMemorySubstateDatabase>,
+    protocol_version: ProtocolVersion,
+    pending_protocol_version: Option<ProtocolVersion>,
+    scrypto_vm: &'v ScryptoVm<W>,
+    native_vm_extension: E,
+}
+
+impl<'v, W: WasmEngine, E: NativeVmExtension> ComparedLedger<'v, W, E> {
+    fn new(
+        protocol_version: Option<ProtocolVersion>,
+        scrypto_vm: &'v ScryptoVm<W>,
+        native_vm_extension: E,
+    ) -> Self {
+        Self {
+            database: StateTreeUpdatingDatabase::new(InMemorySubstateDatabase::standard()),
+            protocol_version: ProtocolVersion::Babylon,
+            pending_protocol_version: protocol_version,
+            scrypto_vm,
+            native_vm_extension,
+        }
+    }
+
+    fn enact_pending_protocol_updates(&mut self, network: &NetworkDefinition) {
+        let Some(target) = self.pending_protocol_version.take() else {
+            return;
+        };
+        let protocol_executor = ProtocolBuilder::for_network(network).until(target);
+        for update_executor in protocol_executor.each_protocol_update_executor() {
+            if update_executor.protocol_update.protocol_version() > self.protocol_version {
+                update_executor.run_and_commit(&mut self.database);
+            }
+        }
+        self.protocol_version = target;
+    }
+
+    fn execute_and_commit(
+        &mut self,
+        network: &NetworkDefinition,
+        prepared: &PreparedLedgerTransaction,
+        trace: bool,
+    ) -> ComparableReceipt {
+        let receipt = execute_prepared_ledger_transaction_with_vm(
+            &self.database,
+            VmInit::new(self.scrypto_vm, self.native_vm_extension.clone()),
+            network,
+            prepared,
+            trace,
+        );
+        let comparable = ComparableReceipt::from(&receipt);
+        let database_updates = receipt
+            .into_state_updates()
+            .create_database_updates::<SpreadPrefixKeyMapper>();
+        self.database.commit(&database_updates);
+        comparable
+    }
+}
+
+impl TxnCompare {
+    pub fn run(&self) -> Result<(), String> {
+        let left_wasm_engine = WasmEngineKind::parse(&self.left_wasm_engine)?;
+        let right_wasm_engine = WasmEngineKind::parse(&self.right_wasm_engine)?;
+        let wasmi_vm = ScryptoVm::<WasmiEngine>::default();
+        #[cfg(feature = "wasmer")]
+        let wasmer_vm = ScryptoVm::<WasmerEngine>::default();
+        match (left_wasm_engine, right_wasm_engine) {
+            (WasmEngineKind::Wasmi, WasmEngineKind::Wasmi) => {
+                self.run_with_vms(&wasmi_vm, NoExtension, &wasmi_vm, NoExtension)
+            }
+            #[cfg(feature = "wasmer")]
+            (WasmEngineKind::Wasmi, WasmEngineKind::Wasmer) => {
+                self.run_with_vms(&wasmi_vm, NoExtension, &wasmer_vm, NoExtension)
+            }
+            #[cfg(feature = "wasmer")]
+            (WasmEngineKind::Wasmer, WasmEngineKind::Wasmi) => {
+                self.run_with_vms(&wasmer_vm, NoExtension, &wasmi_vm, NoExtension)
+            }
+            #[cfg(feature = "wasmer")]
+            (WasmEngineKind::Wasmer, WasmEngineKind::Wasmer) => {
+                self.run_with_vms(&wasmer_vm, NoExtension, &wasmer_vm, NoExtension)
+            }
+        }
+    }
+
+    /// Runs the comparison with the given Scrypto VM and native VM extension on each side, e.g.
+    /// to compare a native code override against the native code on ledger.
+    pub fn run_with_vms<WL, EL, WR, ER>(
+        &self,
+        left_scrypto_vm: &ScryptoVm<WL>,
+        left_native_vm_extension: EL,
+        right_scrypto_vm: &ScryptoVm<WR>,
+        right_native_vm_extension: ER,
+    ) -> Result<(), String>
+    where
+        WL: WasmEngine,
+        EL: NativeVmExtension,
+        WR: WasmEngine,
+        ER: NativeVmExtension,
+    {
+        let network = match &self.network {
+            Some(n) => NetworkDefinition::from_str(n).map_err(Error::ParseNetworkError)?,
+            None => NetworkDefinition::mainnet(),
+        };
+        let parse_protocol_version = |name: &Option<String>| match name {
+            Some(name) => ProtocolVersion::try_from_logical_name(name)
+                .map(Some)
+                .ok_or_else(|| Error::InvalidProtocolVersion(name.clone())),
+            None => Ok(None),
+        };
+        let mut left = ComparedLedger::new(
+            parse_protocol_version(&self.left_protocol_version)?,
+            left_scrypto_vm,
+            left_native_vm_extension,
+        );
+        let mut right = ComparedLedger::new(
+            parse_protocol_version(&self.right_protocol_version)?,
+            right_scrypto_vm,
+            right_native_vm_extension,
+        );
+
+        let cur_version = 0;
+        let to_version = self.max_version.clone();
+
+        let start = std::time::Instant::now();
+        let (tx, rx) = flume::bounded(10);
+
+        // txn reader
+        let mut txn_reader = if self.source.is_file() {
+            let tar_gz = File::open(&self.source).map_err(Error::IOError)?;
+            let tar = GzDecoder::new(tar_gz);
+            let archive = Archive::new(tar);
+            TxnReader::TransactionFile(archive)
+        } else if self.source.is_dir() {
+            TxnReader::StateManagerDatabaseDir(self.source.clone())
+        } else {
+            return Err(Error::InvalidTransactionSource.into());
+        };
+        let txn_read_thread_handle =
+            thread::spawn(move || txn_reader.read(cur_version, to_version, tx));
+
+        // txn executor
+        let address_encoder = AddressBech32Encoder::new(&network);
+        let mut version = cur_version;
+        let mut divergence = None;
+        for tx_payload in rx.iter() {
+            let prepared = prepare_ledger_transaction(&tx_payload);
+
+            let protocol_update_due = match self.protocol_update_version {
+                Some(protocol_update_version) => version >= protocol_update_version,
+                None => !matches!(prepared.inner, PreparedLedgerTransactionInner::Genesis(_)),
+            };
+            if protocol_update_due {
+                left.enact_pending_protocol_updates(&network);
+                right.enact_pending_protocol_updates(&network);
+            }
+
+            version += 1;
+            let left_receipt = left.execute_and_commit(&network, &prepared, self.trace);
+            let right_receipt = right.execute_and_commit(&network, &prepared, self.trace);
+
+            let diff = compare_receipts(&left_receipt, &right_receipt);
+            if !diff.is_empty() {
+                divergence = Some((version, diff));
+                break;
+            }
+
+            if version < 1000 || version % 1000 == 0 {
+                println!(
+                    "Compared version: {}, left = {}, right = {}",
+                    version,
+                    left.database.get_current_root_hash(),
+                    right.database.get_current_root_hash()
+                );
+            }
+        }
+        // Dropping the receiver lets the reader thread finish early on divergence
+        drop(rx);
+        txn_read_thread_handle.join().unwrap()?;
+
+        let duration = start.elapsed();
+        println!("Time elapsed: {:?}", duration);
+        match divergence {
+            Some((version, diff)) => {
+                println!(
+                    "Receipts diverged at version {} (< left, > right):{}",
+                    version,
+                    diff.display(&address_encoder)
+                );
+                Err(Error::ReceiptsDiverged(version).into())
+            }
+            None => {
+                println!("No divergence up to version {}", version);
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use super::Error;
+use crate::utils::*;
+use clap::Parser;
+use radix_common::prelude::*;
+use radix_engine::updates::{ProtocolBuilder, ProtocolVersion};
+use radix_engine::vm::wasm::*;
+use radix_engine::vm::{NoExtension, ScryptoVm, VmInit};
+use radix_substate_store_impls::rocks_db_with_merkle_tree::RocksDBWithMerkleTreeSubstateStore;
+use radix_substate_store_queries::query::dry_run_protocol_updates;
+use std::path::PathBuf;
+
+/// Report the changes protocol updates would make to a ledger database, without committing them
+#[derive(Parser, Debug)]
+pub struct TxnDryRunProtocolUpdate {
+    /// Path to a folder storing state, as populated by `execute` or `sync`
+    pub database_dir: PathBuf,
+
+    /// The protocol version the database is currently at
+    #[clap(long)]
+    pub from: String,
+    /// The protocol version to update to, defaults to the latest
+    #[clap(long)]
+    pub until: Option<String>,
+    /// The network to use, [mainnet | stokenet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+}
+
+impl TxnDryRunProtocolUpdate {
+    pub fn run(&self) -> Result<(), String> {
+        let network = match &self.network {
+            Some(n) => NetworkDefinition::from_str(n).map_err(Error::ParseNetworkError)?,
+            None => NetworkDefinition::mainnet(),
+        };
+        let parse_protocol_version = |name: &str| {
+            ProtocolVersion::try_from_logical_name(name)
+                .ok_or_else(|| Error::InvalidProtocolVersion(name.to_owned()))
+        };
+        let from = parse_protocol_version(&self.from)?;
+        let until = match &self.until {
+            Some(until) => parse_protocol_version(until)?,
+            None => ProtocolVersion::LATEST,
+        };
+
+        let database = RocksDBWithMerkleTreeSubstateStore::standard(self.database_dir.clone());
+        let scrypto_vm = ScryptoVm::<DefaultWasmEngine>::default();
+        let protocol_executor = ProtocolBuilder::for_network(&network).until(until);
+        let dry_runs = dry_run_protocol_updates(
+            &database,
+            protocol_executor
+                .each_protocol_update_executor()
+                .filter(|executor| executor.protocol_update.protocol_version() > from),
+            VmInit::new(&scrypto_vm, NoExtension),
+        );
+
+        write_protocol_update_dry_run_report(
+            &mut std::io::stdout(),
+            &dry_runs,
+            &AddressBech32Encoder::new(&network),
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
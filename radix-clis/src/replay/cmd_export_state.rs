@@ -0,0 +1,200 @@
+use super::ledger_transaction_execution::execute_ledger_transaction;
+use super::txn_reader::TxnReader;
+use super::Error;
+use clap::Parser;
+use flate2::read::GzDecoder;
+use radix_common::prelude::*;
+use radix_engine::vm::wasm::*;
+use radix_engine::vm::ScryptoVm;
+use radix_engine_interface::types::IndexedScryptoValue;
+use radix_substate_store_impls::memory_db::InMemorySubstateDatabase;
+use radix_substate_store_impls::rocks_db_with_merkle_tree::RocksDBWithMerkleTreeSubstateStore;
+use radix_substate_store_impls::state_tree_support::StateTreeUpdatingDatabase;
+use radix_substate_store_impls::substate_snapshot::*;
+use radix_substate_store_interface::db_key_mapper::{DatabaseKeyMapper, SpreadPrefixKeyMapper};
+use radix_substate_store_interface::interface::{
+    CommittableSubstateDatabase, ListableSubstateDatabase, SubstateDatabase,
+};
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::PathBuf;
+use std::thread;
+use tar::Archive;
+
+/// Export the state of a ledger database to a portable substate snapshot
+#[derive(Parser, Debug)]
+pub struct TxnExportState {
+    /// Path to a folder storing state, as populated by `execute` or `sync`, or with `--at`, the
+    /// transaction source to execute, either a transaction file in `.tar.gz` format or a node
+    /// database folder
+    pub source: PathBuf,
+    /// Path to the output snapshot file
+    pub output_file: PathBuf,
+
+    /// Exports the state at this version, by executing the transactions of the source up to it
+    /// into an in-memory database, as state databases don't keep historical state
+    #[clap(long)]
+    pub at: Option<u64>,
+    /// The network to use, [mainnet | stokenet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+    /// Only export the entities reachable from these components, comma separated
+    #[clap(long)]
+    pub components: Option<String>,
+}
+
+impl TxnExportState {
+    pub fn run(&self) -> Result<(), String> {
+        let network = match &self.network {
+            Some(n) => NetworkDefinition::from_str(n).map_err(Error::ParseNetworkError)?,
+            None => NetworkDefinition::mainnet(),
+        };
+
+        match self.at {
+            Some(state_version) => {
+                let database = self.execute_in_memory(&network, state_version)?;
+                self.export(
+                    &database,
+                    &network,
+                    database.get_current_version(),
+                    database.get_current_root_hash(),
+                )
+            }
+            None => {
+                let database = RocksDBWithMerkleTreeSubstateStore::standard(self.source.clone());
+                self.export(
+                    &database,
+                    &network,
+                    database.get_current_version(),
+                    database.get_current_root_hash(),
+                )
+            }
+        }
+    }
+
+    /// Executes the transactions of the source into an in-memory database, stopping at the given
+    /// state version.
+    fn execute_in_memory(
+        &self,
+        network: &NetworkDefinition,
+        state_version: u64,
+    ) -> Result<StateTreeUpdatingDatabase<InMemorySubstateDatabase>, Error> {
+        let (tx, rx) = flume::bounded(10);
+
+        // txn reader
+        let mut txn_reader = if self.source.is_file() {
+            let tar_gz = File::open(&self.source).map_err(Error::IOError)?;
+            let tar = GzDecoder::new(tar_gz);
+            let archive = Archive::new(tar);
+            TxnReader::TransactionFile(archive)
+        } else if self.source.is_dir() {
+            TxnReader::StateManagerDatabaseDir(self.source.clone())
+        } else {
+            return Err(Error::InvalidTransactionSource);
+        };
+        let txn_read_thread_handle =
+            thread::spawn(move || txn_reader.read(0, Some(state_version), tx));
+
+        // txn executor
+        let mut database = StateTreeUpdatingDatabase::new(InMemorySubstateDatabase::standard());
+        let scrypto_vm = ScryptoVm::<DefaultWasmEngine>::default();
+        while database.get_current_version() < state_version {
+            let Ok(tx_payload) = rx.recv() else {
+                break;
+            };
+            let state_updates =
+                execute_ledger_transaction(&database, &scrypto_vm, network, &tx_payload, false);
+            database.commit(&state_updates.create_database_updates::<SpreadPrefixKeyMapper>());
+        }
+        // Lets the reader stop, were it to have more transactions
+        drop(rx);
+        txn_read_thread_handle.join().unwrap()?;
+
+        if database.get_current_version() != state_version {
+            return Err(Error::UnexpectedStateVersion {
+                expected: state_version,
+                actual: database.get_current_version(),
+            });
+        }
+        Ok(database)
+    }
+
+    fn export<S: SubstateDatabase + ListableSubstateDatabase>(
+        &self,
+        database: &S,
+        network: &NetworkDefinition,
+        state_version: u64,
+        state_root_hash: Hash,
+    ) -> Result<(), String> {
+        let reachable_nodes = match &self.components {
+            Some(components) => {
+                let decoder = AddressBech32Decoder::new(network);
+                let mut roots = Vec::new();
+                for component in components.split(",") {
+                    let address = ComponentAddress::try_from_bech32(&decoder, component.trim())
+                        .ok_or_else(|| Error::InvalidComponentAddress(component.to_owned()))?;
+                    roots.push(address.into_node_id());
+                }
+                Some(find_reachable_nodes(database, roots))
+            }
+            None => None,
+        };
+
+        let header = SubstateSnapshotHeader {
+            network_id: network.id,
+            state_version,
+            state_root_hash: Some(state_root_hash),
+            is_partial: reachable_nodes.is_some(),
+        };
+        let file = File::create(&self.output_file).map_err(Error::IOError)?;
+        let (_, partition_count) = write_substate_snapshot(
+            database,
+            &header,
+            |partition_key| match &reachable_nodes {
+                Some(reachable_nodes) => reachable_nodes
+                    .contains(&SpreadPrefixKeyMapper::from_db_partition_key(partition_key).0),
+                None => true,
+            },
+            BufWriter::new(file),
+        )
+        .map_err(Error::SubstateSnapshotError)?;
+
+        println!(
+            "Exported {} partitions at state version {} to {}",
+            partition_count,
+            state_version,
+            self.output_file.display()
+        );
+        Ok(())
+    }
+}
+
+/// Finds all nodes which are transitively owned or referenced by the given root nodes, including
+/// the roots themselves.
+pub fn find_reachable_nodes<S: SubstateDatabase>(
+    database: &S,
+    roots: Vec<NodeId>,
+) -> IndexSet<NodeId> {
+    let mut reachable_nodes = roots.iter().cloned().collect::<IndexSet<_>>();
+    let mut queue = roots.into_iter().collect::<VecDeque<_>>();
+    while let Some(node_id) = queue.pop_front() {
+        for partition_num in 0..=u8::MAX {
+            let partition_key = SpreadPrefixKeyMapper::to_db_partition_key(
+                &node_id,
+                PartitionNumber(partition_num),
+            );
+            for (_, value) in database.list_entries(&partition_key) {
+                let Ok(value) = IndexedScryptoValue::from_vec(value) else {
+                    continue;
+                };
+                for child in value.references().iter().chain(value.owned_nodes()) {
+                    if reachable_nodes.insert(*child) {
+                        queue.push_back(*child);
+                    }
+                }
+            }
+        }
+    }
+    reachable_nodes
+}
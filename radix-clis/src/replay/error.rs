@@ -1,4 +1,5 @@
 use radix_common::prelude::ParseNetworkError;
+use radix_substate_store_impls::substate_snapshot::SubstateSnapshotError;
 use std::fmt;
 
 #[derive(Debug)]
@@ -9,6 +10,12 @@ pub enum Error {
     InvalidTransactionArchive,
     InvalidTransactionSource,
     InvalidBreakpoints(String),
+    InvalidProtocolVersion(String),
+    InvalidWasmEngine(String),
+    ReceiptsDiverged(u64),
+    InvalidComponentAddress(String),
+    UnexpectedStateVersion { expected: u64, actual: u64 },
+    SubstateSnapshotError(SubstateSnapshotError),
 }
 
 impl fmt::Display for Error {
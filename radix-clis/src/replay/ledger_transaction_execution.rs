@@ -8,7 +8,7 @@ use radix_engine::transaction::{
     execute_transaction, ExecutionConfig, TransactionFeeSummary, TransactionReceipt,
 };
 use radix_engine::vm::wasm::*;
-use radix_engine::vm::{NoExtension, ScryptoVm, VmInit};
+use radix_engine::vm::{NativeVmExtension, NoExtension, ScryptoVm, VmInit};
 use radix_substate_store_interface::interface::SubstateDatabase;
 use radix_transactions::validation::{
     NotarizedTransactionValidator, TransactionValidator, ValidationConfig,
@@ -58,9 +58,32 @@ pub fn prepare_ledger_transaction(tx_payload: &[u8]) -> PreparedLedgerTransactio
     prepared
 }
 
-pub fn execute_prepared_ledger_transaction<S: SubstateDatabase>(
+pub fn execute_prepared_ledger_transaction<S: SubstateDatabase, W: WasmEngine>(
     database: &S,
-    scrypto_vm: &ScryptoVm<DefaultWasmEngine>,
+    scrypto_vm: &ScryptoVm<W>,
+    network: &NetworkDefinition,
+    prepared: &PreparedLedgerTransaction,
+    trace: bool,
+) -> LedgerTransactionReceipt {
+    execute_prepared_ledger_transaction_with_vm(
+        database,
+        VmInit {
+            scrypto_vm,
+            native_vm_extension: NoExtension,
+        },
+        network,
+        prepared,
+        trace,
+    )
+}
+
+pub fn execute_prepared_ledger_transaction_with_vm<
+    S: SubstateDatabase,
+    W: WasmEngine,
+    E: NativeVmExtension,
+>(
+    database: &S,
+    vm_init: VmInit<W, E>,
     network: &NetworkDefinition,
     prepared: &PreparedLedgerTransaction,
     trace: bool,
@@ -75,10 +98,7 @@ pub fn execute_prepared_ledger_transaction<S: SubstateDatabase>(
                 PreparedGenesisTransaction::Transaction(tx) => {
                     let receipt = execute_transaction(
                         database,
-                        VmInit {
-                            scrypto_vm,
-                            native_vm_extension: NoExtension,
-                        },
+                        vm_init,
                         &ExecutionConfig::for_genesis_transaction(network.clone())
                             .with_kernel_trace(trace)
                             .with_cost_breakdown(trace),
@@ -91,10 +111,7 @@ pub fn execute_prepared_ledger_transaction<S: SubstateDatabase>(
         PreparedLedgerTransactionInner::UserV1(tx) => {
             let receipt = execute_transaction(
                 database,
-                VmInit {
-                    scrypto_vm,
-                    native_vm_extension: NoExtension,
-                },
+                vm_init,
                 &ExecutionConfig::for_notarized_transaction(network.clone())
                     .with_kernel_trace(trace)
                     .with_cost_breakdown(trace),
@@ -108,10 +125,7 @@ pub fn execute_prepared_ledger_transaction<S: SubstateDatabase>(
         PreparedLedgerTransactionInner::RoundUpdateV1(tx) => {
             let receipt = execute_transaction(
                 database,
-                VmInit {
-                    scrypto_vm,
-                    native_vm_extension: NoExtension,
-                },
+                vm_init,
                 &ExecutionConfig::for_system_transaction(network.clone())
                     .with_kernel_trace(trace)
                     .with_cost_breakdown(trace),
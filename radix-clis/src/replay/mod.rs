@@ -1,18 +1,27 @@
 pub mod ledger_transaction;
 pub mod ledger_transaction_execution;
+pub mod receipt_comparison;
 pub mod txn_reader;
 
 mod cmd_alloc_dump;
+mod cmd_change_feed;
+mod cmd_compare;
+mod cmd_dry_run_protocol_update;
 mod cmd_execute;
 mod cmd_execute_in_memory;
+mod cmd_export_state;
 mod cmd_measure;
 mod cmd_prepare;
 mod cmd_sync;
 mod error;
 
 pub use cmd_alloc_dump::*;
+pub use cmd_change_feed::*;
+pub use cmd_compare::*;
+pub use cmd_dry_run_protocol_update::*;
 pub use cmd_execute::*;
 pub use cmd_execute_in_memory::*;
+pub use cmd_export_state::*;
 pub use cmd_measure::*;
 pub use cmd_prepare::*;
 pub use cmd_sync::*;
@@ -36,6 +45,10 @@ pub enum Command {
     Sync(TxnSync),
     Measure(TxnMeasure),
     AllocDump(TxnAllocDump),
+    Compare(TxnCompare),
+    ExportState(TxnExportState),
+    DryRunProtocolUpdate(TxnDryRunProtocolUpdate),
+    ChangeFeed(TxnChangeFeed),
 }
 
 pub fn run() -> Result<(), String> {
@@ -48,5 +61,9 @@ pub fn run() -> Result<(), String> {
         Command::Sync(cmd) => cmd.sync(),
         Command::Measure(cmd) => cmd.run(),
         Command::AllocDump(cmd) => cmd.run(),
+        Command::Compare(cmd) => cmd.run(),
+        Command::ExportState(cmd) => cmd.run(),
+        Command::DryRunProtocolUpdate(cmd) => cmd.run(),
+        Command::ChangeFeed(cmd) => cmd.run(),
     }
 }
@@ -0,0 +1,523 @@
+use super::ledger_transaction_execution::LedgerTransactionReceipt;
+use radix_common::prelude::*;
+use radix_engine::track::LegacyStateUpdates;
+use radix_engine::transaction::{TransactionFeeSummary, TransactionResult};
+use radix_engine_interface::types::EventTypeIdentifier;
+use radix_substate_store_interface::interface::DatabaseUpdate;
+use sbor::representations::*;
+use std::fmt;
+
+/// The parts of a ledger transaction receipt which are expected to be identical when the same
+/// transaction is executed against the same state by two engine configurations.
+pub struct ComparableReceipt {
+    pub result_kind: &'static str,
+    pub outcome: Option<String>,
+    pub fee_summary: Option<TransactionFeeSummary>,
+    pub state_updates: LegacyStateUpdates,
+    pub events: Vec<(EventTypeIdentifier, Vec<u8>)>,
+}
+
+impl From<&LedgerTransactionReceipt> for ComparableReceipt {
+    fn from(receipt: &LedgerTransactionReceipt) -> Self {
+        match receipt {
+            LedgerTransactionReceipt::Flash(receipt) => Self {
+                result_kind: "Flash",
+                outcome: None,
+                fee_summary: None,
+                state_updates: receipt.state_updates.clone().into_legacy(),
+                events: vec![],
+            },
+            LedgerTransactionReceipt::Standard(receipt) => match &receipt.result {
+                TransactionResult::Commit(commit) => Self {
+                    result_kind: "Commit",
+                    outcome: Some(format!("{:?}", commit.outcome)),
+                    fee_summary: Some(receipt.fee_summary.clone()),
+                    state_updates: commit.state_updates.clone().into_legacy(),
+                    events: commit.application_events.clone(),
+                },
+                TransactionResult::Reject(reject) => Self {
+                    result_kind: "Reject",
+                    outcome: Some(format!("{:?}", reject.reason)),
+                    fee_summary: Some(receipt.fee_summary.clone()),
+                    state_updates: LegacyStateUpdates::default(),
+                    events: vec![],
+                },
+                TransactionResult::Abort(abort) => Self {
+                    result_kind: "Abort",
+                    outcome: Some(format!("{:?}", abort.reason)),
+                    fee_summary: Some(receipt.fee_summary.clone()),
+                    state_updates: LegacyStateUpdates::default(),
+                    events: vec![],
+                },
+            },
+        }
+    }
+}
+
+/// A single difference between two receipts of the same transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReceiptDifference {
+    ResultKind {
+        left: &'static str,
+        right: &'static str,
+    },
+    Outcome {
+        left: Option<String>,
+        right: Option<String>,
+    },
+    FeeSummary {
+        left: Option<TransactionFeeSummary>,
+        right: Option<TransactionFeeSummary>,
+    },
+    PartitionDeletion {
+        node_id: NodeId,
+        partition_number: PartitionNumber,
+        left: bool,
+        right: bool,
+    },
+    SubstateUpdate {
+        node_id: NodeId,
+        partition_number: PartitionNumber,
+        substate_key: SubstateKey,
+        left: Option<DatabaseUpdate>,
+        right: Option<DatabaseUpdate>,
+    },
+    Event {
+        index: usize,
+        left: Option<(EventTypeIdentifier, Vec<u8>)>,
+        right: Option<(EventTypeIdentifier, Vec<u8>)>,
+    },
+}
+
+/// All differences between two receipts of the same transaction, in a stable order.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ReceiptDiff {
+    pub differences: Vec<ReceiptDifference>,
+}
+
+impl ReceiptDiff {
+    pub fn is_empty(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+pub fn compare_receipts(left: &ComparableReceipt, right: &ComparableReceipt) -> ReceiptDiff {
+    let mut differences = Vec::new();
+
+    if left.result_kind != right.result_kind {
+        differences.push(ReceiptDifference::ResultKind {
+            left: left.result_kind,
+            right: right.result_kind,
+        });
+    }
+    if left.outcome != right.outcome {
+        differences.push(ReceiptDifference::Outcome {
+            left: left.outcome.clone(),
+            right: right.outcome.clone(),
+        });
+    }
+    if left.fee_summary != right.fee_summary {
+        differences.push(ReceiptDifference::FeeSummary {
+            left: left.fee_summary.clone(),
+            right: right.fee_summary.clone(),
+        });
+    }
+
+    let all_partition_deletions = left
+        .state_updates
+        .partition_deletions
+        .iter()
+        .chain(right.state_updates.partition_deletions.iter())
+        .collect::<BTreeSet<_>>();
+    for (node_id, partition_number) in all_partition_deletions {
+        let key = (*node_id, *partition_number);
+        let in_left = left.state_updates.partition_deletions.contains(&key);
+        let in_right = right.state_updates.partition_deletions.contains(&key);
+        if in_left != in_right {
+            differences.push(ReceiptDifference::PartitionDeletion {
+                node_id: *node_id,
+                partition_number: *partition_number,
+                left: in_left,
+                right: in_right,
+            });
+        }
+    }
+
+    let all_substates = left
+        .state_updates
+        .system_updates
+        .iter()
+        .chain(right.state_updates.system_updates.iter())
+        .flat_map(|(partition, by_key)| by_key.keys().map(move |key| (*partition, key.clone())))
+        .collect::<BTreeSet<_>>();
+    for ((node_id, partition_number), substate_key) in all_substates {
+        let lookup = |state_updates: &LegacyStateUpdates| {
+            state_updates
+                .system_updates
+                .get(&(node_id, partition_number))
+                .and_then(|by_key| by_key.get(&substate_key))
+                .cloned()
+        };
+        let left_update = lookup(&left.state_updates);
+        let right_update = lookup(&right.state_updates);
+        if left_update != right_update {
+            differences.push(ReceiptDifference::SubstateUpdate {
+                node_id,
+                partition_number,
+                substate_key,
+                left: left_update,
+                right: right_update,
+            });
+        }
+    }
+
+    for index in 0..left.events.len().max(right.events.len()) {
+        let left_event = left.events.get(index);
+        let right_event = right.events.get(index);
+        if left_event != right_event {
+            differences.push(ReceiptDifference::Event {
+                index,
+                left: left_event.cloned(),
+                right: right_event.cloned(),
+            });
+        }
+    }
+
+    ReceiptDiff { differences }
+}
+
+impl<'a> ContextualDisplay<AddressDisplayContext<'a>> for ReceiptDiff {
+    type Error = fmt::Error;
+
+    fn contextual_format<F: fmt::Write>(
+        &self,
+        f: &mut F,
+        context: &AddressDisplayContext<'a>,
+    ) -> Result<(), Self::Error> {
+        for difference in &self.differences {
+            match difference {
+                ReceiptDifference::ResultKind { left, right } => {
+                    write!(f, "\nResult kind:")?;
+                    write!(f, "\n  < {}", left)?;
+                    write!(f, "\n  > {}", right)?;
+                }
+                ReceiptDifference::Outcome { left, right } => {
+                    write!(f, "\nOutcome:")?;
+                    write!(f, "\n  < {}", left.as_deref().unwrap_or("None"))?;
+                    write!(f, "\n  > {}", right.as_deref().unwrap_or("None"))?;
+                }
+                ReceiptDifference::FeeSummary { left, right } => {
+                    write!(f, "\nFee summary:")?;
+                    write!(f, "\n  < {:?}", left)?;
+                    write!(f, "\n  > {:?}", right)?;
+                }
+                ReceiptDifference::PartitionDeletion {
+                    node_id,
+                    partition_number,
+                    left,
+                    right,
+                } => {
+                    write!(
+                        f,
+                        "\nPartition deletion: {} {:?}",
+                        node_id.display(*context),
+                        partition_number
+                    )?;
+                    write!(f, "\n  < {}", if *left { "Deleted" } else { "None" })?;
+                    write!(f, "\n  > {}", if *right { "Deleted" } else { "None" })?;
+                }
+                ReceiptDifference::SubstateUpdate {
+                    node_id,
+                    partition_number,
+                    substate_key,
+                    left,
+                    right,
+                } => {
+                    write!(
+                        f,
+                        "\nSubstate: {} {:?} {:?}",
+                        node_id.display(*context),
+                        partition_number,
+                        substate_key
+                    )?;
+                    write!(f, "\n  < ")?;
+                    format_database_update(f, left.as_ref(), context)?;
+                    write!(f, "\n  > ")?;
+                    format_database_update(f, right.as_ref(), context)?;
+                }
+                ReceiptDifference::Event { index, left, right } => {
+                    write!(f, "\nEvent #{}:", index)?;
+                    write!(f, "\n  < ")?;
+                    format_event(f, left.as_ref(), context)?;
+                    write!(f, "\n  > ")?;
+                    format_event(f, right.as_ref(), context)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn format_database_update<F: fmt::Write>(
+    f: &mut F,
+    update: Option<&DatabaseUpdate>,
+    context: &AddressDisplayContext,
+) -> fmt::Result {
+    match update {
+        Some(DatabaseUpdate::Set(value)) => {
+            write!(f, "Set: ")?;
+            format_scrypto_payload(f, value, context)
+        }
+        Some(DatabaseUpdate::Delete) => write!(f, "Delete"),
+        None => write!(f, "None"),
+    }
+}
+
+fn format_event<F: fmt::Write>(
+    f: &mut F,
+    event: Option<&(EventTypeIdentifier, Vec<u8>)>,
+    context: &AddressDisplayContext,
+) -> fmt::Result {
+    match event {
+        Some((EventTypeIdentifier(emitter, name), data)) => {
+            write!(f, "{:?} {:?}: ", emitter, name)?;
+            format_scrypto_payload(f, data, context)
+        }
+        None => write!(f, "None"),
+    }
+}
+
+fn format_scrypto_payload<F: fmt::Write>(
+    f: &mut F,
+    payload: &[u8],
+    context: &AddressDisplayContext,
+) -> fmt::Result {
+    match ScryptoRawPayload::new_from_valid_slice_with_checks(payload) {
+        Some(raw_payload) => write!(
+            f,
+            "{}",
+            raw_payload.to_string(ValueDisplayParameters::Schemaless {
+                display_mode: DisplayMode::RustLike,
+                print_mode: PrintMode::SingleLine,
+                custom_context: ScryptoValueDisplayContext::with_optional_bech32(context.encoder),
+                depth_limit: SCRYPTO_SBOR_V1_MAX_DEPTH,
+            })
+        ),
+        None => write!(f, "{}", hex::encode(payload)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use radix_engine_interface::api::ModuleId;
+    use radix_engine_interface::types::{Emitter, MAIN_BASE_PARTITION, METADATA_BASE_PARTITION};
+
+    fn commit_receipt() -> ComparableReceipt {
+        ComparableReceipt {
+            result_kind: "Commit",
+            outcome: Some("Success([])".to_string()),
+            fee_summary: Some(TransactionFeeSummary {
+                total_execution_cost_units_consumed: 100,
+                ..Default::default()
+            }),
+            state_updates: LegacyStateUpdates {
+                partition_deletions: indexset!(),
+                system_updates: indexmap!(
+                    (XRD.into_node_id(), MAIN_BASE_PARTITION) => indexmap!(
+                        SubstateKey::Field(0u8) => DatabaseUpdate::Set(scrypto_encode(&1u32).unwrap())
+                    )
+                ),
+            },
+            events: vec![(
+                EventTypeIdentifier(
+                    Emitter::Method(XRD.into_node_id(), ModuleId::Main),
+                    "MintFungibleResourceEvent".to_string(),
+                ),
+                scrypto_encode(&1u32).unwrap(),
+            )],
+        }
+    }
+
+    #[test]
+    pub fn comparing_identical_receipts_finds_no_differences() {
+        // Arrange
+        let left = commit_receipt();
+        let right = commit_receipt();
+
+        // Act
+        let diff = compare_receipts(&left, &right);
+
+        // Assert
+        assert!(diff.is_empty());
+    }
+
+    #[test]
+    pub fn comparing_receipts_with_different_results_finds_result_and_outcome_differences() {
+        // Arrange
+        let left = commit_receipt();
+        let right = ComparableReceipt {
+            result_kind: "Reject",
+            outcome: Some("SuccessButFeeLoanNotRepaid".to_string()),
+            ..commit_receipt()
+        };
+
+        // Act
+        let diff = compare_receipts(&left, &right);
+
+        // Assert
+        assert_eq!(
+            diff.differences,
+            vec![
+                ReceiptDifference::ResultKind {
+                    left: "Commit",
+                    right: "Reject",
+                },
+                ReceiptDifference::Outcome {
+                    left: left.outcome.clone(),
+                    right: right.outcome.clone(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    pub fn comparing_receipts_with_different_fees_finds_fee_summary_difference() {
+        // Arrange
+        let left = commit_receipt();
+        let right = ComparableReceipt {
+            fee_summary: Some(TransactionFeeSummary {
+                total_execution_cost_units_consumed: 101,
+                ..Default::default()
+            }),
+            ..commit_receipt()
+        };
+
+        // Act
+        let diff = compare_receipts(&left, &right);
+
+        // Assert
+        assert_eq!(
+            diff.differences,
+            vec![ReceiptDifference::FeeSummary {
+                left: left.fee_summary.clone(),
+                right: right.fee_summary.clone(),
+            }]
+        );
+    }
+
+    #[test]
+    pub fn comparing_receipts_with_different_partition_deletions_finds_partition_deletion_difference(
+    ) {
+        // Arrange
+        let left = commit_receipt();
+        let mut right = commit_receipt();
+        right
+            .state_updates
+            .partition_deletions
+            .insert((XRD.into_node_id(), METADATA_BASE_PARTITION));
+
+        // Act
+        let diff = compare_receipts(&left, &right);
+
+        // Assert
+        assert_eq!(
+            diff.differences,
+            vec![ReceiptDifference::PartitionDeletion {
+                node_id: XRD.into_node_id(),
+                partition_number: METADATA_BASE_PARTITION,
+                left: false,
+                right: true,
+            }]
+        );
+    }
+
+    #[test]
+    pub fn comparing_receipts_with_different_substate_updates_finds_substate_update_differences() {
+        // Arrange
+        let left = commit_receipt();
+        let mut right = commit_receipt();
+        let by_key = right
+            .state_updates
+            .system_updates
+            .get_mut(&(XRD.into_node_id(), MAIN_BASE_PARTITION))
+            .unwrap();
+        by_key.insert(SubstateKey::Field(0u8), DatabaseUpdate::Delete);
+        by_key.insert(
+            SubstateKey::Field(1u8),
+            DatabaseUpdate::Set(scrypto_encode(&2u32).unwrap()),
+        );
+
+        // Act
+        let diff = compare_receipts(&left, &right);
+
+        // Assert
+        assert_eq!(
+            diff.differences,
+            vec![
+                ReceiptDifference::SubstateUpdate {
+                    node_id: XRD.into_node_id(),
+                    partition_number: MAIN_BASE_PARTITION,
+                    substate_key: SubstateKey::Field(0u8),
+                    left: Some(DatabaseUpdate::Set(scrypto_encode(&1u32).unwrap())),
+                    right: Some(DatabaseUpdate::Delete),
+                },
+                ReceiptDifference::SubstateUpdate {
+                    node_id: XRD.into_node_id(),
+                    partition_number: MAIN_BASE_PARTITION,
+                    substate_key: SubstateKey::Field(1u8),
+                    left: None,
+                    right: Some(DatabaseUpdate::Set(scrypto_encode(&2u32).unwrap())),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    pub fn comparing_receipts_with_different_events_finds_event_differences() {
+        // Arrange
+        let left = commit_receipt();
+        let mut right = commit_receipt();
+        right.events[0].1 = scrypto_encode(&2u32).unwrap();
+        right.events.push(left.events[0].clone());
+
+        // Act
+        let diff = compare_receipts(&left, &right);
+
+        // Assert
+        assert_eq!(
+            diff.differences,
+            vec![
+                ReceiptDifference::Event {
+                    index: 0,
+                    left: Some(left.events[0].clone()),
+                    right: Some(right.events[0].clone()),
+                },
+                ReceiptDifference::Event {
+                    index: 1,
+                    left: None,
+                    right: Some(left.events[0].clone()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    pub fn receipt_diff_is_displayed_with_both_sides() {
+        // Arrange
+        let left = commit_receipt();
+        let right = ComparableReceipt {
+            result_kind: "Reject",
+            ..commit_receipt()
+        };
+        let address_encoder = AddressBech32Encoder::for_simulator();
+
+        // Act
+        let display = compare_receipts(&left, &right)
+            .display(&address_encoder)
+            .to_string();
+
+        // Assert
+        assert_eq!(display, "\nResult kind:\n  < Commit\n  > Reject");
+    }
+}
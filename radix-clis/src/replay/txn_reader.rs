@@ -49,7 +49,10 @@ impl TxnReader {
                         }
                     }
 
-                    tx.send(tx_payload).unwrap();
+                    // The receiving end is dropped when the consumer stops early
+                    if tx.send(tx_payload).is_err() {
+                        break;
+                    }
                 }
             }
             TxnReader::StateManagerDatabaseDir(db_dir) => {
@@ -80,7 +83,16 @@ impl TxnReader {
                     );
                     while let Some(next_txn) = txn_iter.next() {
                         let next_txn = next_txn.unwrap();
-                        tx.send(next_txn.1.to_vec()).unwrap();
+                        if let Some(to_version) = to_version {
+                            let tx_version =
+                                u64::from_be_bytes(next_txn.0[..8].try_into().unwrap());
+                            if tx_version > to_version {
+                                return Ok(());
+                            }
+                        }
+                        if tx.send(next_txn.1.to_vec()).is_err() {
+                            return Ok(());
+                        }
                     }
                     thread::sleep(Duration::from_secs(1));
                 }
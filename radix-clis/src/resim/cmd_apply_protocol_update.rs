@@ -0,0 +1,57 @@
+use clap::Parser;
+use radix_engine::updates::*;
+use std::path::PathBuf;
+
+use crate::resim::*;
+
+/// Apply a custom flash protocol update to the simulator ledger
+#[derive(Parser, Debug)]
+pub struct ApplyProtocolUpdate {
+    /// The path to the SBOR-encoded flash update (a `FlashBatchGenerator`)
+    pub path: PathBuf,
+
+    /// The logical name of the update, defaults to the file name
+    #[clap(short, long)]
+    pub name: Option<String>,
+}
+
+impl ApplyProtocolUpdate {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let update = load_flash_protocol_update(&self.path, &self.name)?;
+        let name = update.logical_name.clone();
+        let flash_count = update.batch_generator.batch_count();
+
+        let mut env = SimulatorEnvironment::new()?;
+        env.enact_custom_protocol_update(update);
+
+        writeln!(
+            out,
+            "Applied protocol update {} ({} flash transactions).",
+            name, flash_count
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
+
+/// Loads an SBOR-encoded [`FlashBatchGenerator`] as a custom protocol update, to be enacted on top
+/// of the latest protocol version. The update is named after the file unless a name is given.
+pub fn load_flash_protocol_update(
+    path: &PathBuf,
+    name: &Option<String>,
+) -> Result<CustomProtocolUpdate, Error> {
+    let bytes = std::fs::read(path).map_err(|err| Error::IOErrorAtPath(err, path.clone()))?;
+    let generator: FlashBatchGenerator = scrypto_decode(&bytes).map_err(Error::SborDecodeError)?;
+    let name = match name {
+        Some(name) => name.clone(),
+        None => path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default(),
+    };
+    Ok(CustomProtocolUpdate::new(
+        &name,
+        ProtocolVersion::LATEST,
+        generator,
+    ))
+}
@@ -34,6 +34,10 @@ pub struct CallMethod {
     #[clap(short, long)]
     pub signing_keys: Option<String>,
 
+    /// The shell command of an external signer to also sign with, which is spoken to over stdio
+    #[clap(long)]
+    pub signer_command: Option<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     pub trace: bool,
@@ -71,6 +75,7 @@ impl CallMethod {
         handle_manifest(
             manifest,
             &self.signing_keys,
+            &self.signer_command,
             &self.network,
             &self.manifest,
             self.trace,
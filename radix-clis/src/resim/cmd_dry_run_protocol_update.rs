@@ -0,0 +1,38 @@
+use crate::utils::*;
+use clap::Parser;
+use radix_substate_store_queries::query::dry_run_protocol_updates;
+use std::path::PathBuf;
+
+use crate::resim::*;
+
+/// Report the changes a custom flash protocol update would make to the simulator ledger
+#[derive(Parser, Debug)]
+pub struct DryRunProtocolUpdate {
+    /// The path to the SBOR-encoded flash update (a `FlashBatchGenerator`)
+    pub path: PathBuf,
+
+    /// The logical name of the update, defaults to the file name
+    #[clap(short, long)]
+    pub name: Option<String>,
+}
+
+impl DryRunProtocolUpdate {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let update = load_flash_protocol_update(&self.path, &self.name)?;
+
+        let env = SimulatorEnvironment::new()?;
+        let dry_runs = dry_run_protocol_updates(
+            &env.db,
+            [env.custom_protocol_update_executor(&update)],
+            VmInit::new(&env.scrypto_vm, NoExtension),
+        );
+
+        write_protocol_update_dry_run_report(
+            out,
+            &dry_runs,
+            &AddressBech32Encoder::new(&env.network_definition),
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
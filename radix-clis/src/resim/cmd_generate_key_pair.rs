@@ -4,20 +4,86 @@ use colored::*;
 use radix_common::prelude::*;
 use rand::Rng;
 
-/// Generate a key pair
+/// Generate a key pair, either randomly or derived from a mnemonic
 #[derive(Parser, Debug)]
-pub struct GenerateKeyPair {}
+pub struct GenerateKeyPair {
+    /// The BIP-39 mnemonic to derive the key pair from, instead of generating a random one
+    #[clap(long)]
+    pub mnemonic: Option<String>,
+
+    /// The BIP-39 passphrase of the mnemonic
+    #[clap(long, default_value = "")]
+    pub passphrase: String,
+
+    /// The index of the account to derive the key pair of
+    #[clap(long, default_value = "0")]
+    pub index: u32,
+
+    /// Derive the secp256k1 key of an Olympia account, instead of the Ed25519 key of a Babylon account
+    #[clap(long)]
+    pub olympia: bool,
+
+    /// The network of the derived account, [simulator | adapanet | nebunet | mainnet]
+    #[clap(short, long)]
+    pub network: Option<String>,
+}
 
 impl GenerateKeyPair {
     pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
-        let secret = rand::thread_rng().gen::<[u8; 32]>();
-        let private_key = Secp256k1PrivateKey::from_bytes(&secret).unwrap();
+        let Some(mnemonic) = &self.mnemonic else {
+            let secret = rand::thread_rng().gen::<[u8; 32]>();
+            let private_key = Secp256k1PrivateKey::from_bytes(&secret).unwrap();
+            let public_key = private_key.public_key();
+            writeln!(out, "Public key: {}", public_key.to_string().green())
+                .map_err(Error::IOError)?;
+            writeln!(
+                out,
+                "Private key: {}",
+                hex::encode(private_key.to_bytes()).green()
+            )
+            .map_err(Error::IOError)?;
+            return Ok(());
+        };
+
+        let network = match &self.network {
+            Some(n) => NetworkDefinition::from_str(n).map_err(Error::ParseNetworkError)?,
+            None => NetworkDefinition::simulator(),
+        };
+        let seed = bip39_seed_from_mnemonic(mnemonic, &self.passphrase)
+            .map_err(Error::HdDerivationError)?;
+        let (path, private_key) = if self.olympia {
+            let path = DerivationPath::radix_olympia_account(self.index);
+            let private_key =
+                derive_secp256k1_private_key(&seed, &path).map_err(Error::HdDerivationError)?;
+            (path, PrivateKey::Secp256k1(private_key))
+        } else {
+            let path = DerivationPath::radix_account(network.id, self.index);
+            let private_key =
+                derive_ed25519_private_key(&seed, &path).map_err(Error::HdDerivationError)?;
+            (path, PrivateKey::Ed25519(private_key))
+        };
         let public_key = private_key.public_key();
-        writeln!(out, "Public key: {}", public_key.to_string().green()).map_err(Error::IOError)?;
+        let account = ComponentAddress::virtual_account_from_public_key(&public_key);
+
+        writeln!(out, "Derivation path: {}", path.to_string().green()).map_err(Error::IOError)?;
+        writeln!(
+            out,
+            "Account component address: {}",
+            account
+                .display(&AddressBech32Encoder::new(&network))
+                .to_string()
+                .green()
+        )
+        .map_err(Error::IOError)?;
+        let public_key_hex = match &public_key {
+            PublicKey::Secp256k1(public_key) => public_key.to_string(),
+            PublicKey::Ed25519(public_key) => public_key.to_string(),
+        };
+        writeln!(out, "Public key: {}", public_key_hex.green()).map_err(Error::IOError)?;
         writeln!(
             out,
             "Private key: {}",
-            hex::encode(private_key.to_bytes()).green()
+            private_key_to_string(&private_key).green()
         )
         .map_err(Error::IOError)?;
         Ok(())
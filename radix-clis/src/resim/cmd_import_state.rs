@@ -0,0 +1,47 @@
+use clap::Parser;
+use radix_common::prelude::NetworkDefinition;
+use radix_substate_store_impls::substate_snapshot::SubstateSnapshotReader;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use crate::resim::*;
+
+/// Replace the simulator ledger with a substate snapshot (e.g. from `replay export-state`)
+#[derive(Parser, Debug)]
+pub struct ImportState {
+    /// The path to the snapshot file
+    pub snapshot: PathBuf,
+}
+
+impl ImportState {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let file = File::open(&self.snapshot)
+            .map_err(|err| Error::IOErrorAtPath(err, self.snapshot.clone()))?;
+        let reader = SubstateSnapshotReader::new(BufReader::new(file))
+            .map_err(Error::SubstateSnapshotError)?;
+        let header = reader.header().clone();
+        let network = NetworkDefinition::simulator();
+        if header.network_id != network.id {
+            // Node ids don't depend on the network, so the snapshot can be used as is, but its
+            // addresses are displayed as simulator addresses from now on.
+            writeln!(
+                out,
+                "Warning: the snapshot was taken on network {:#04x}, its addresses are displayed as {} addresses.",
+                header.network_id, network.logical_name
+            )
+            .map_err(Error::IOError)?;
+        }
+
+        SimulatorEnvironment::new().and_then(|env| env.import_substate_snapshot(reader))?;
+
+        writeln!(
+            out,
+            "Imported {} snapshot taken at state version {}.",
+            if header.is_partial { "partial" } else { "full" },
+            header.state_version
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use clap::Parser;
+use colored::*;
+use radix_engine::blueprints::transaction_tracker::TransactionStatusV1;
+use radix_substate_store_queries::query::TransactionTrackerReader;
+use radix_transactions::prelude::*;
+
+use crate::resim::*;
+
+/// Show the status of a transaction intent tracked by the transaction tracker
+#[derive(Parser, Debug)]
+pub struct IntentStatus {
+    /// The intent hash, either Bech32 encoded (e.g. `txid_sim1...`) or hex encoded
+    pub intent_hash: String,
+}
+
+impl IntentStatus {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), String> {
+        let SimulatorEnvironment {
+            db,
+            network_definition,
+            ..
+        } = SimulatorEnvironment::new()?;
+
+        let intent_hash = TransactionHashBech32Decoder::new(&network_definition)
+            .validate_and_decode::<IntentHash>(&self.intent_hash)
+            .ok()
+            .or_else(|| {
+                Hash::from_str(&self.intent_hash)
+                    .ok()
+                    .map(IntentHash::from_hash)
+            })
+            .ok_or(Error::InvalidId(self.intent_hash.clone()))?;
+
+        match TransactionTrackerReader::new(&db).intent_status(&intent_hash) {
+            Some(tracked_intent) => {
+                let status = match tracked_intent.status {
+                    TransactionStatusV1::CommittedSuccess => "Committed Success".green(),
+                    TransactionStatusV1::CommittedFailure => "Committed Failure".red(),
+                    TransactionStatusV1::Cancelled => "Cancelled".yellow(),
+                };
+                writeln!(out, "{}: {}", "Status".green().bold(), status).map_err(Error::IOError)?;
+                writeln!(
+                    out,
+                    "{}: {} (inclusive) to {} (exclusive)",
+                    "Expiry Epochs".green().bold(),
+                    tracked_intent.expiry_epoch_start_inclusive.number(),
+                    tracked_intent.expiry_epoch_end_exclusive.number()
+                )
+                .map_err(Error::IOError)?;
+            }
+            None => {
+                writeln!(
+                    out,
+                    "{}: Not tracked. The intent was either never committed, or its expiry epoch has passed. \
+                    Note that transactions run by resim itself aren't notarized, and so are never tracked.",
+                    "Status".green().bold()
+                )
+                .map_err(Error::IOError)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -29,6 +29,10 @@ pub struct Mint {
     #[clap(short, long)]
     pub signing_keys: Option<String>,
 
+    /// The shell command of an external signer to also sign with, which is spoken to over stdio
+    #[clap(long)]
+    pub signer_command: Option<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     pub trace: bool,
@@ -58,6 +62,7 @@ impl Mint {
         handle_manifest(
             manifest,
             &self.signing_keys,
+            &self.signer_command,
             &self.network,
             &self.manifest,
             self.trace,
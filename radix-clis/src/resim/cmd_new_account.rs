@@ -45,6 +45,7 @@ impl NewAccount {
         let receipt = handle_manifest(
             manifest,
             &Some("".to_string()), // explicit empty signer public keys
+            &None,
             &self.network,
             &self.manifest,
             self.trace,
@@ -83,6 +84,7 @@ impl NewAccount {
             let receipt = handle_manifest(
                 manifest,
                 &Some("".to_string()), // explicit empty signer public keys
+                &None,
                 &self.network,
                 &None,
                 self.trace,
@@ -44,6 +44,10 @@ pub struct NewBadgeFixed {
     #[clap(short, long)]
     pub signing_keys: Option<String>,
 
+    /// The shell command of an external signer to also sign with, which is spoken to over stdio
+    #[clap(long)]
+    pub signer_command: Option<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     pub trace: bool,
@@ -91,6 +95,7 @@ impl NewBadgeFixed {
         handle_manifest(
             manifest,
             &self.signing_keys,
+            &self.signer_command,
             &self.network,
             &self.manifest,
             self.trace,
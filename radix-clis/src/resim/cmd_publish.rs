@@ -335,6 +335,7 @@ impl Publish {
             let receipt = handle_manifest(
                 manifest,
                 &None,
+                &None,
                 &self.network,
                 &self.manifest,
                 self.trace,
@@ -27,6 +27,10 @@ pub struct Run {
     #[clap(short, long)]
     pub signing_keys: Option<String>,
 
+    /// The shell command of an external signer to also sign with, which is spoken to over stdio
+    #[clap(long)]
+    pub signer_command: Option<String>,
+
     /// Turn on tracing
     #[clap(short, long)]
     pub trace: bool,
@@ -73,6 +77,7 @@ impl Run {
         handle_manifest(
             compiled_manifest,
             &self.signing_keys,
+            &self.signer_command,
             &self.network,
             &None,
             self.trace,
@@ -8,6 +8,11 @@ pub struct Show {
     /// The address of a package, component or resource manager, if no
     /// address is provided, then we default to `show <DEFAULT_ACCOUNT_ADDRESS>`.
     pub address: Option<String>,
+
+    /// Also validate the metadata of the entity against the metadata standards, e.g. the
+    /// two-way linking between dApp definitions and the entities they claim
+    #[clap(long)]
+    pub check_metadata: bool,
 }
 
 impl Show {
@@ -17,11 +22,17 @@ impl Show {
         let result = match &self.address {
             Some(address) => {
                 if let Ok(a) = SimulatorPackageAddress::from_str(address) {
-                    dump_package(a.0, &db, out).map_err(Error::LedgerDumpError)
+                    dump_package(a.0, &db, out)
+                        .map(|_| GlobalAddress::from(a.0))
+                        .map_err(Error::LedgerDumpError)
                 } else if let Ok(a) = SimulatorComponentAddress::from_str(address) {
-                    dump_component(a.0, &db, out).map_err(Error::LedgerDumpError)
+                    dump_component(a.0, &db, out)
+                        .map(|_| GlobalAddress::from(a.0))
+                        .map_err(Error::LedgerDumpError)
                 } else if let Ok(a) = SimulatorResourceAddress::from_str(address) {
-                    dump_resource_manager(a.0, &db, out).map_err(Error::LedgerDumpError)
+                    dump_resource_manager(a.0, &db, out)
+                        .map(|_| GlobalAddress::from(a.0))
+                        .map_err(Error::LedgerDumpError)
                 } else {
                     Err(Error::InvalidId(address.clone()))
                 }
@@ -32,8 +43,17 @@ impl Show {
                         EntityDumpError::NoAddressProvidedAndNotDefaultAccountSet,
                     ))
                 })
-                .and_then(|x| dump_component(x, &db, out).map_err(Error::LedgerDumpError)),
+                .and_then(|x| {
+                    dump_component(x, &db, out)
+                        .map(|_| GlobalAddress::from(x))
+                        .map_err(Error::LedgerDumpError)
+                }),
         };
-        result.map_err(|err| err.into())
+        let address = result.map_err(|err| -> String { err.into() })?;
+
+        if self.check_metadata {
+            dump_metadata_standards_report(address, &db, out);
+        }
+        Ok(())
     }
 }
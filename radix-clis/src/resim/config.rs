@@ -1,6 +1,13 @@
 use crate::resim::*;
 use radix_common::prelude::*;
+use radix_engine::system::system_callback::*;
 use radix_engine::updates::*;
+use radix_engine::vm::*;
+use radix_substate_store_impls::substate_snapshot::SubstateSnapshotReader;
+use radix_substate_store_interface::{
+    db_key_mapper::{MappedSubstateDatabase, SpreadPrefixKeyMapper},
+    interface::SubstateDatabase,
+};
 use std::env;
 use std::fs;
 use std::path::PathBuf;
@@ -27,7 +34,7 @@ impl SimulatorEnvironment {
             scrypto_vm,
             network_definition: NetworkDefinition::simulator(),
         };
-        env.bootstrap();
+        env.bootstrap()?;
 
         Ok(env)
     }
@@ -41,18 +48,87 @@ impl SimulatorEnvironment {
         Self::new()
     }
 
-    fn bootstrap(&mut self) {
+    /// Replaces the ledger with the content of the given substate snapshot. A partial snapshot is
+    /// applied on top of a freshly bootstrapped ledger.
+    ///
+    /// The snapshot may have been taken on any network, as node ids don't depend on it - only the
+    /// way the addresses are displayed does.
+    pub fn import_substate_snapshot<R: std::io::Read>(
+        self,
+        reader: SubstateSnapshotReader<R>,
+    ) -> Result<Self, Error> {
+        if reader.header().is_partial {
+            // The fresh ledger is already at the latest protocol version.
+            let mut env = self.reset()?;
+            reader
+                .import_into(&mut env.db)
+                .map_err(Error::SubstateSnapshotError)?;
+            Ok(env)
+        } else {
+            drop(self);
+
+            let dir = get_data_dir()?;
+            std::fs::remove_dir_all(dir).map_err(Error::IOError)?;
+
+            let mut env = Self {
+                db: RocksdbSubstateStore::standard(get_data_dir()?),
+                scrypto_vm: ScryptoVm::<DefaultWasmEngine>::default(),
+                network_definition: NetworkDefinition::simulator(),
+            };
+            reader
+                .import_into(&mut env.db)
+                .map_err(Error::SubstateSnapshotError)?;
+
+            // Genesis is skipped for an already bootstrapped ledger, and as no protocol version is
+            // recorded for the imported state, only the protocol updates after the one it was
+            // taken at get enacted.
+            env.bootstrap()?;
+            Ok(env)
+        }
+    }
+
+    fn bootstrap(&mut self) -> Result<(), Error> {
         let vm = VmInit::new(&self.scrypto_vm, NoExtension);
 
         // Bootstrap
         Bootstrapper::new(self.network_definition.clone(), &mut self.db, vm, false)
             .bootstrap_test_default();
 
-        // Run the protocol updates - for now, unlike the test runner, the user has no way in whether they
-        // get these protocol updates or not.
-        ProtocolBuilder::for_network(&self.network_definition)
+        // Run the protocol updates which the ledger hasn't seen yet, so that ledgers created by an
+        // older resim catch up, while custom protocol updates applied since are kept - for now,
+        // unlike the test runner, the user has no way in whether they get these protocol updates
+        // or not.
+        let protocol_version = match get_protocol_version()? {
+            Some(protocol_version) => protocol_version,
+            None => infer_protocol_version(&self.db),
+        };
+        for executor in ProtocolBuilder::for_network(&self.network_definition)
             .until_latest_protocol_version()
-            .commit_each_protocol_update(&mut self.db);
+            .each_protocol_update_executor()
+            .filter(|executor| executor.protocol_update.protocol_version() > protocol_version)
+        {
+            executor.run_and_commit_with_vm_init(
+                &mut self.db,
+                VmInit::new(&self.scrypto_vm, NoExtension),
+            );
+        }
+        set_protocol_version(ProtocolVersion::LATEST)
+    }
+
+    /// Runs and commits the given custom protocol update against the ledger.
+    pub fn enact_custom_protocol_update(&mut self, update: CustomProtocolUpdate) {
+        let executor = self.custom_protocol_update_executor(&update);
+        executor
+            .run_and_commit_with_vm_init(&mut self.db, VmInit::new(&self.scrypto_vm, NoExtension));
+    }
+
+    pub fn custom_protocol_update_executor(
+        &self,
+        update: &CustomProtocolUpdate,
+    ) -> ProtocolUpdateExecutor {
+        ProtocolBuilder::for_network(&self.network_definition)
+            .until_babylon()
+            .create_executor_for_custom_update(update)
     }
 }
 
@@ -86,6 +162,65 @@ pub fn get_configs_path() -> Result<PathBuf, Error> {
     Ok(path.with_extension("sbor"))
 }
 
+/// The path of the file recording the protocol version that the simulator ledger is at, which
+/// lives next to the configs rather than in them, so that configs of older data dirs still decode.
+pub fn get_protocol_version_path() -> Result<PathBuf, Error> {
+    let mut path = get_data_dir()?;
+    path.push("protocol_version");
+    Ok(path)
+}
+
+/// Returns the recorded protocol version of the simulator ledger, if any.
+pub fn get_protocol_version() -> Result<Option<ProtocolVersion>, Error> {
+    let path = get_protocol_version_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let logical_name = fs::read_to_string(&path).map_err(Error::IOError)?;
+    ProtocolVersion::try_from_logical_name(logical_name.trim())
+        .map(Some)
+        .ok_or_else(|| Error::InvalidProtocolVersion(logical_name))
+}
+
+pub fn set_protocol_version(protocol_version: ProtocolVersion) -> Result<(), Error> {
+    fs::write(
+        get_protocol_version_path()?,
+        protocol_version.logical_name(),
+    )
+    .map_err(Error::IOError)
+}
+
+/// Infers the protocol version of a ledger which has none recorded (i.e. one bootstrapped by an
+/// older resim, or imported from a snapshot) from the boot loader substates written by the
+/// built-in protocol updates, assuming they were enacted with their default settings.
+fn infer_protocol_version<S: SubstateDatabase>(db: &S) -> ProtocolVersion {
+    let scrypto_vm_version = db
+        .get_mapped::<SpreadPrefixKeyMapper, VmBoot>(
+            TRANSACTION_TRACKER.as_node_id(),
+            BOOT_LOADER_PARTITION,
+            &SubstateKey::Field(BOOT_LOADER_VM_BOOT_FIELD_KEY),
+        )
+        .unwrap_or(VmBoot::babylon())
+        .get_scrypto_version();
+    let system_boot = db.get_mapped::<SpreadPrefixKeyMapper, SystemBoot>(
+        TRANSACTION_TRACKER.as_node_id(),
+        BOOT_LOADER_PARTITION,
+        &SubstateKey::Field(BOOT_LOADER_SYSTEM_SUBSTATE_FIELD_KEY),
+    );
+
+    if scrypto_vm_version >= ScryptoVmVersion::V1_2
+        || matches!(system_boot, Some(SystemBoot::V2(..)))
+    {
+        ProtocolVersion::Cuttlefish
+    } else if system_boot.is_some() {
+        ProtocolVersion::Bottlenose
+    } else if scrypto_vm_version >= ScryptoVmVersion::V1_1 {
+        ProtocolVersion::Anemone
+    } else {
+        ProtocolVersion::Babylon
+    }
+}
+
 pub fn get_configs() -> Result<Configs, Error> {
     let path = get_configs_path()?;
     if path.exists() {
@@ -106,10 +241,10 @@ pub fn get_default_account() -> Result<ComponentAddress, Error> {
         .ok_or(Error::NoDefaultAccount)
 }
 
-pub fn get_default_private_key() -> Result<Secp256k1PrivateKey, Error> {
+pub fn get_default_private_key() -> Result<PrivateKey, Error> {
     get_configs()?
         .default_private_key
-        .map(|v| Secp256k1PrivateKey::from_hex(&v).unwrap())
+        .map(|v| parse_private_key_from_str(&v).unwrap())
         .ok_or(Error::NoDefaultPrivateKey)
 }
 
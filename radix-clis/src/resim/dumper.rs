@@ -16,7 +16,9 @@ use radix_substate_store_interface::{
     db_key_mapper::{MappedSubstateDatabase, SpreadPrefixKeyMapper},
     interface::SubstateDatabase,
 };
-use radix_substate_store_queries::query::ResourceAccounter;
+use radix_substate_store_queries::query::{
+    MetadataStandardsIssue, MetadataStandardsValidator, ResourceAccounter,
+};
 use radix_substate_store_queries::typed_substate_layout::*;
 
 /// Represents an error when displaying an entity.
@@ -315,3 +317,73 @@ fn get_entity_metadata<T: SubstateDatabase>(
         })
         .collect()
 }
+
+/// Dump the violations of the metadata standards by an entity into console.
+pub fn dump_metadata_standards_report<T: SubstateDatabase, O: std::io::Write>(
+    address: GlobalAddress,
+    substate_db: &T,
+    output: &mut O,
+) {
+    let address_bech32_encoder = AddressBech32Encoder::new(&NetworkDefinition::simulator());
+    let report = MetadataStandardsValidator::new(substate_db).validate(address);
+    if report.is_valid() {
+        writeln!(
+            output,
+            "{}: {}",
+            "Metadata Standards".green().bold(),
+            "OK".green()
+        );
+        return;
+    }
+
+    writeln!(
+        output,
+        "{}: {} issue(s)",
+        "Metadata Standards".green().bold(),
+        report.issues.len()
+    );
+    for (last, issue) in report.issues.iter().identify_last() {
+        let description = match issue {
+            MetadataStandardsIssue::EntityNotFound => "Entity has no metadata".to_string(),
+            MetadataStandardsIssue::UnexpectedType {
+                key,
+                expected_type_id,
+                actual_type_id,
+            } => format!(
+                "{}: expected value type {}, but found {}",
+                key, expected_type_id, actual_type_id
+            ),
+            MetadataStandardsIssue::UnknownAccountType(account_type) => {
+                format!("Unknown account_type: {:?}", account_type)
+            }
+            MetadataStandardsIssue::NotADappDefinition { key } => {
+                format!("{}: only allowed on dApp definitions", key)
+            }
+            MetadataStandardsIssue::LinkedEntityIsNotADappDefinition { dapp_definition } => {
+                format!(
+                    "Linked entity {} is not a dApp definition",
+                    dapp_definition.display(&address_bech32_encoder)
+                )
+            }
+            MetadataStandardsIssue::DappDefinitionDoesNotClaimEntity { dapp_definition } => {
+                format!(
+                    "dApp definition {} does not claim this entity",
+                    dapp_definition.display(&address_bech32_encoder)
+                )
+            }
+            MetadataStandardsIssue::ClaimedEntityDoesNotLinkBack { claimed_entity } => {
+                format!(
+                    "Claimed entity {} does not link back to this dApp definition",
+                    claimed_entity.display(&address_bech32_encoder)
+                )
+            }
+            MetadataStandardsIssue::DappDefinitionDoesNotLinkBack { dapp_definition } => {
+                format!(
+                    "dApp definition {} does not link back to this dApp definition",
+                    dapp_definition.display(&address_bech32_encoder)
+                )
+            }
+        };
+        writeln!(output, "{} {}", list_item_prefix(last), description.red());
+    }
+}
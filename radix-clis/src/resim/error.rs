@@ -3,16 +3,18 @@ use std::io;
 use std::path::PathBuf;
 
 use radix_common::network::ParseNetworkError;
-use radix_common::prelude::{ComponentAddress, NodeId, PackageAddress};
+use radix_common::prelude::{ComponentAddress, HdDerivationError, NodeId, PackageAddress};
 use radix_engine::errors::{RejectionReason, RuntimeError};
 use radix_engine::transaction::AbortReason;
 use radix_engine::utils::ExtractSchemaError;
 use radix_engine::vm::wasm::PrepareError;
 use radix_engine_interface::types::ParseNonFungibleGlobalIdError;
 use radix_engine_interface::types::SchemaHash;
+use radix_substate_store_impls::substate_snapshot::SubstateSnapshotError;
 use radix_transactions::errors::*;
 use radix_transactions::manifest::DecompileError;
 use radix_transactions::model::PrepareError as TransactionPrepareError;
+use radix_transactions::signing::ExternalSignerError;
 use sbor::*;
 
 use crate::resim::EntityDumpError;
@@ -67,6 +69,10 @@ pub enum Error {
 
     InvalidPrivateKey,
 
+    HdDerivationError(HdDerivationError),
+
+    ExternalSignerError(ExternalSignerError),
+
     /// e.g. if you accidentally pass in a public key in `set_default_account` command.
     GotPublicKeyExpectedPrivateKey,
 
@@ -83,6 +89,10 @@ pub enum Error {
     InvalidResourceSpecifier(String),
 
     RemoteGenericSubstitutionNotSupported,
+
+    SubstateSnapshotError(SubstateSnapshotError),
+
+    InvalidProtocolVersion(String),
 }
 
 impl fmt::Display for Error {
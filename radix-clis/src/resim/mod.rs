@@ -1,8 +1,12 @@
 mod addressing;
+mod cmd_apply_protocol_update;
 mod cmd_call_function;
 mod cmd_call_method;
+mod cmd_dry_run_protocol_update;
 mod cmd_export_package_definition;
 mod cmd_generate_key_pair;
+mod cmd_import_state;
+mod cmd_intent_status;
 mod cmd_mint;
 mod cmd_new_account;
 mod cmd_new_badge_fixed;
@@ -25,10 +29,14 @@ mod dumper;
 mod error;
 
 pub use addressing::*;
+pub use cmd_apply_protocol_update::*;
 pub use cmd_call_function::*;
 pub use cmd_call_method::*;
+pub use cmd_dry_run_protocol_update::*;
 pub use cmd_export_package_definition::*;
 pub use cmd_generate_key_pair::*;
+pub use cmd_import_state::*;
+pub use cmd_intent_status::*;
 pub use cmd_new_account::*;
 pub use cmd_new_badge_fixed::*;
 pub use cmd_new_badge_mutable::*;
@@ -105,10 +113,14 @@ impl ResimCli {
 
 #[derive(Subcommand, Debug)]
 pub enum Command {
+    ApplyProtocolUpdate(ApplyProtocolUpdate),
     CallFunction(CallFunction),
     CallMethod(CallMethod),
+    DryRunProtocolUpdate(DryRunProtocolUpdate),
     ExportPackageDefinition(ExportPackageDefinition),
     GenerateKeyPair(GenerateKeyPair),
+    ImportState(ImportState),
+    IntentStatus(IntentStatus),
     Mint(crate::resim::cmd_mint::Mint),
     NewAccount(NewAccount),
     NewSimpleBadge(NewSimpleBadge),
@@ -134,10 +146,14 @@ pub fn run() -> Result<(), String> {
     let mut out = std::io::stdout();
 
     match cli.command {
+        Command::ApplyProtocolUpdate(cmd) => cmd.run(&mut out),
         Command::CallFunction(cmd) => cmd.run(&mut out),
         Command::CallMethod(cmd) => cmd.run(&mut out),
+        Command::DryRunProtocolUpdate(cmd) => cmd.run(&mut out),
         Command::ExportPackageDefinition(cmd) => cmd.run(&mut out),
         Command::GenerateKeyPair(cmd) => cmd.run(&mut out),
+        Command::ImportState(cmd) => cmd.run(&mut out),
+        Command::IntentStatus(cmd) => cmd.run(&mut out),
         Command::Mint(cmd) => cmd.run(&mut out),
         Command::NewAccount(cmd) => cmd.run(&mut out),
         Command::NewSimpleBadge(cmd) => cmd.run(&mut out).map(|_| ()),
@@ -208,6 +224,7 @@ pub fn handle_system_transaction<O: std::io::Write>(
 pub fn handle_manifest<O: std::io::Write>(
     manifest: TransactionManifestV1,
     signing_keys: &Option<String>,
+    signer_command: &Option<String>,
     network: &Option<String>,
     write_manifest: &Option<PathBuf>,
     trace: bool,
@@ -242,22 +259,34 @@ pub fn handle_manifest<O: std::io::Write>(
             } = SimulatorEnvironment::new()?;
             let vm_init = VmInit::new(&scrypto_vm, NoExtension);
 
-            let sks = get_signing_keys(signing_keys)?;
-            let initial_proofs = sks
+            // The default key is only used if neither signing keys nor an external signer are given
+            let sks = match (signing_keys, signer_command) {
+                (None, Some(_)) => vec![],
+                _ => get_signing_keys(signing_keys)?,
+            };
+            let mut initial_proofs = sks
                 .into_iter()
                 .map(|e| NonFungibleGlobalId::from_public_key(&e.public_key()))
                 .collect::<BTreeSet<NonFungibleGlobalId>>();
             let nonce = get_nonce()?;
-            let transaction = TestTransaction::new_from_nonce(manifest, nonce);
+            let transaction = TestTransaction::new_from_nonce(manifest, nonce)
+                .prepare()
+                .map_err(Error::TransactionPrepareError)?;
+            if let Some(command) = signer_command {
+                let signer = ExternalSigner::spawn(command, ExternalSigner::DEFAULT_READ_TIMEOUT)
+                    .map_err(Error::ExternalSignerError)?;
+                // Test transactions aren't signed, but this checks the signer controls its key
+                signer
+                    .try_sign_with_public_key(&transaction.hash)
+                    .map_err(Error::ExternalSignerError)?;
+                initial_proofs.insert(NonFungibleGlobalId::from_public_key(&signer.public_key()));
+            }
 
             let receipt = execute_and_commit_transaction(
                 &mut db,
                 vm_init,
                 &ExecutionConfig::for_test_transaction().with_kernel_trace(trace),
-                &transaction
-                    .prepare()
-                    .map_err(Error::TransactionPrepareError)?
-                    .get_executable(initial_proofs),
+                &transaction.get_executable(initial_proofs),
             );
 
             if print_receipt {
@@ -296,23 +325,47 @@ pub fn process_receipt(receipt: TransactionReceipt) -> Result<TransactionReceipt
     }
 }
 
+/// The prefix of Ed25519 private keys, as unprefixed private keys are secp256k1 keys.
+pub const ED25519_PRIVATE_KEY_PREFIX: &'static str = "ed25519:";
+
 pub fn parse_private_key_from_bytes(slice: &[u8]) -> Result<Secp256k1PrivateKey, Error> {
     Secp256k1PrivateKey::from_bytes(slice).map_err(|_| Error::InvalidPrivateKey)
 }
 
-pub fn parse_private_key_from_str(key: &str) -> Result<Secp256k1PrivateKey, Error> {
-    hex::decode(key)
-        .map_err(|_| Error::InvalidPrivateKey)
-        .and_then(|bytes| parse_private_key_from_bytes(&bytes))
+pub fn parse_private_key_from_str(key: &str) -> Result<PrivateKey, Error> {
+    match key.strip_prefix(ED25519_PRIVATE_KEY_PREFIX) {
+        Some(key) => hex::decode(key)
+            .map_err(|_| Error::InvalidPrivateKey)
+            .and_then(|bytes| {
+                Ed25519PrivateKey::from_bytes(&bytes).map_err(|_| Error::InvalidPrivateKey)
+            })
+            .map(PrivateKey::Ed25519),
+        None => hex::decode(key)
+            .map_err(|_| Error::InvalidPrivateKey)
+            .and_then(|bytes| parse_private_key_from_bytes(&bytes))
+            .map(PrivateKey::Secp256k1),
+    }
+}
+
+/// Formats the private key the way [`parse_private_key_from_str`] parses it.
+pub fn private_key_to_string(private_key: &PrivateKey) -> String {
+    match private_key {
+        PrivateKey::Secp256k1(private_key) => private_key.to_hex(),
+        PrivateKey::Ed25519(private_key) => format!(
+            "{}{}",
+            ED25519_PRIVATE_KEY_PREFIX,
+            hex::encode(private_key.to_bytes())
+        ),
+    }
 }
 
-pub fn get_signing_keys(signing_keys: &Option<String>) -> Result<Vec<Secp256k1PrivateKey>, Error> {
+pub fn get_signing_keys(signing_keys: &Option<String>) -> Result<Vec<PrivateKey>, Error> {
     let private_keys = if let Some(keys) = signing_keys {
         keys.split(",")
             .map(str::trim)
             .filter(|s: &&str| !s.is_empty())
             .map(parse_private_key_from_str)
-            .collect::<Result<Vec<Secp256k1PrivateKey>, Error>>()?
+            .collect::<Result<Vec<PrivateKey>, Error>>()?
     } else {
         vec![get_default_private_key()?]
     };
@@ -464,7 +517,15 @@ mod tests {
             trace: false,
         };
         assert!(new_account.run(&mut out).is_ok());
-        let cmd = Show { address: None };
+        let cmd = Show {
+            address: None,
+            check_metadata: false,
+        };
+        assert!(cmd.run(&mut out).is_ok());
+        let cmd = Show {
+            address: None,
+            check_metadata: true,
+        };
         assert!(cmd.run(&mut out).is_ok());
     }
 
@@ -512,6 +573,13 @@ mod tests {
 
         assert!(make_cmd(private_key.to_hex()).run(&mut out).is_ok());
         assert!(make_cmd(public_key.to_string()).run(&mut out).is_err());
+
+        let ed25519_private_key = Ed25519PrivateKey::from_u64(1).unwrap();
+        let ed25519_public_key = ed25519_private_key.public_key().to_string();
+        assert!(make_cmd(private_key_to_string(&ed25519_private_key.into()))
+            .run(&mut out)
+            .is_ok());
+        assert!(make_cmd(ed25519_public_key).run(&mut out).is_err());
     }
 
     #[test]
@@ -0,0 +1,129 @@
+use clap::Parser;
+use radix_common::prelude::*;
+use radix_transactions::manifest::{
+    compile, compiler::compile_error_diagnostics, compiler::CompileErrorDiagnosticsStyle,
+    BlobProvider,
+};
+use radix_transactions::prelude::*;
+use rand::Rng;
+use std::path::PathBuf;
+
+use super::*;
+
+/// Create a partially signed transaction from a manifest
+#[derive(Parser, Debug)]
+pub struct Create {
+    /// The path to the transaction manifest
+    manifest: PathBuf,
+
+    /// The path to write the partially signed transaction to
+    #[clap(short, long)]
+    output: PathBuf,
+
+    /// Network to Use [Simulator | Alphanet | Mainnet]
+    #[clap(short, long)]
+    network: Option<String>,
+
+    /// The paths to blobs
+    #[clap(short, long, multiple = true)]
+    blobs: Option<Vec<String>>,
+
+    /// The hex-encoded public key of the notary
+    #[clap(long)]
+    notary_public_key: String,
+
+    /// Whether the notary also counts as a signatory of the intent
+    #[clap(long, action)]
+    notary_is_signatory: bool,
+
+    /// The first epoch in which the transaction can be committed
+    #[clap(long)]
+    start_epoch: u64,
+
+    /// The epoch from which the transaction can no longer be committed
+    #[clap(long)]
+    end_epoch: u64,
+
+    /// The intent nonce, random if not provided
+    #[clap(long)]
+    nonce: Option<u32>,
+
+    /// The tip percentage
+    #[clap(long, default_value = "0")]
+    tip_percentage: u16,
+
+    /// A plaintext message to attach to the transaction
+    #[clap(long)]
+    message: Option<String>,
+
+    /// The hex-encoded public keys of signers required in addition to those derived from the manifest
+    #[clap(long, multiple = true)]
+    required_signers: Option<Vec<String>>,
+}
+
+impl Create {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let network = parse_network(&self.network)?;
+        let content = std::fs::read_to_string(&self.manifest).map_err(Error::IOError)?;
+        let mut blobs = Vec::new();
+        if let Some(paths) = &self.blobs {
+            for path in paths {
+                blobs.push(std::fs::read(path).map_err(Error::IOError)?);
+            }
+        }
+        let manifest =
+            compile(&content, &network, BlobProvider::new_with_blobs(blobs)).map_err(|err| {
+                Error::CompileError(compile_error_diagnostics(
+                    &content,
+                    err,
+                    CompileErrorDiagnosticsStyle::TextTerminalColors,
+                ))
+            })?;
+        let (instructions, blobs) = manifest.for_intent();
+
+        let intent = IntentV1 {
+            header: TransactionHeaderV1 {
+                network_id: network.id,
+                start_epoch_inclusive: Epoch::of(self.start_epoch),
+                end_epoch_exclusive: Epoch::of(self.end_epoch),
+                nonce: self.nonce.unwrap_or_else(|| rand::thread_rng().gen()),
+                notary_public_key: parse_public_key(&self.notary_public_key)?,
+                notary_is_signatory: self.notary_is_signatory,
+                tip_percentage: self.tip_percentage,
+            },
+            instructions,
+            blobs,
+            message: match &self.message {
+                Some(message) => MessageV1::Plaintext(PlaintextMessageV1 {
+                    mime_type: "text/plain".to_string(),
+                    message: MessageContentsV1::String(message.clone()),
+                }),
+                None => MessageV1::None,
+            },
+        };
+        let mut transaction = PartiallySignedTransactionV1::new(intent);
+        for public_key in self.required_signers.iter().flatten() {
+            transaction
+                .required_signers
+                .insert(parse_public_key(public_key)?.get_hash());
+        }
+        write_partially_signed_transaction(&self.output, &transaction)?;
+
+        let intent_hash = transaction.intent_hash().map_err(Error::PrepareError)?;
+        writeln!(
+            out,
+            "Intent hash: {}",
+            TransactionHashBech32Encoder::new(&network)
+                .encode(&intent_hash)
+                .unwrap()
+        )
+        .map_err(Error::IOError)?;
+        writeln!(
+            out,
+            "Required signers: {}",
+            transaction.required_signers.len()
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use clap::Parser;
+use radix_transactions::prelude::*;
+use radix_transactions::validation::{
+    NotarizedTransactionValidator, TransactionValidator, ValidationConfig,
+};
+use std::path::PathBuf;
+
+use super::*;
+
+/// Notarize a partially signed transaction into a transaction which can be submitted
+#[derive(Parser, Debug)]
+pub struct Finalize {
+    /// The path to the partially signed transaction
+    input: PathBuf,
+
+    /// The hex-encoded private key of the notary
+    #[clap(long)]
+    notary_private_key: Option<String>,
+
+    /// The curve of the notary private key [Secp256k1 | Ed25519]
+    #[clap(short, long, default_value = "secp256k1")]
+    curve: String,
+
+    /// The shell command of an external signer to notarize with, instead of a private key
+    #[clap(long)]
+    signer_command: Option<String>,
+
+    /// Network to Use [Simulator | Alphanet | Mainnet]
+    #[clap(short, long)]
+    network: Option<String>,
+
+    /// Notarize even if some of the required signers haven't signed
+    #[clap(long, action)]
+    allow_missing_signers: bool,
+
+    /// The path to write the notarized transaction to
+    #[clap(short, long)]
+    output: PathBuf,
+}
+
+impl Finalize {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let network = parse_network(&self.network)?;
+        let notary =
+            parse_signing_key(&self.notary_private_key, &self.curve, &self.signer_command)?;
+        let transaction = read_partially_signed_transaction(&self.input)?;
+        let notarized = if self.allow_missing_signers {
+            transaction.notarize_allowing_missing_signers(&notary)
+        } else {
+            transaction.notarize(&notary)
+        }
+        .map_err(Error::PartialSigningError)?;
+
+        let prepared = notarized.prepare().map_err(Error::PrepareError)?;
+        let notarized_transaction_hash = prepared.notarized_transaction_hash();
+        NotarizedTransactionValidator::new(ValidationConfig::default(network.id))
+            .validate(prepared)
+            .map_err(Error::TransactionValidationError)?;
+        let raw = notarized.to_raw().map_err(Error::EncodeError)?;
+        std::fs::write(&self.output, raw.as_slice()).map_err(Error::IOError)?;
+
+        writeln!(
+            out,
+            "Transaction hash: {}",
+            TransactionHashBech32Encoder::new(&network)
+                .encode(&notarized_transaction_hash)
+                .unwrap()
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use clap::Parser;
+use radix_common::prelude::*;
+use radix_transactions::manifest::decompile;
+use radix_transactions::prelude::*;
+use std::path::PathBuf;
+
+use super::*;
+
+/// Show the intent, collected signatures and missing signers of a partially signed transaction
+#[derive(Parser, Debug)]
+pub struct Inspect {
+    /// The path to the partially signed transaction
+    input: PathBuf,
+
+    /// Network to Use [Simulator | Alphanet | Mainnet]
+    #[clap(short, long)]
+    network: Option<String>,
+}
+
+impl Inspect {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let network = parse_network(&self.network)?;
+        let transaction = read_partially_signed_transaction(&self.input)?;
+        let intent_hash = transaction.intent_hash().map_err(Error::PrepareError)?;
+        let header = &transaction.intent.header;
+        let signers = transaction.signers().map_err(Error::PartialSigningError)?;
+        let missing_signers = transaction
+            .missing_signers()
+            .map_err(Error::PartialSigningError)?;
+
+        writeln!(
+            out,
+            "Intent hash: {}",
+            TransactionHashBech32Encoder::new(&network)
+                .encode(&intent_hash)
+                .unwrap()
+        )
+        .map_err(Error::IOError)?;
+        writeln!(out, "Network id: {}", header.network_id).map_err(Error::IOError)?;
+        writeln!(
+            out,
+            "Epochs: [{}, {})",
+            header.start_epoch_inclusive.number(),
+            header.end_epoch_exclusive.number()
+        )
+        .map_err(Error::IOError)?;
+        writeln!(out, "Nonce: {}", header.nonce).map_err(Error::IOError)?;
+        writeln!(
+            out,
+            "Notary: {:?} (signatory: {})",
+            header.notary_public_key, header.notary_is_signatory
+        )
+        .map_err(Error::IOError)?;
+        writeln!(out, "Tip percentage: {}", header.tip_percentage).map_err(Error::IOError)?;
+
+        writeln!(out, "Signatures: {}", signers.len()).map_err(Error::IOError)?;
+        for signer in &signers {
+            writeln!(out, "├─ {:?}", signer).map_err(Error::IOError)?;
+        }
+        writeln!(
+            out,
+            "Required signers: {}",
+            transaction.required_signers.len()
+        )
+        .map_err(Error::IOError)?;
+        for required_signer in &transaction.required_signers {
+            let status = if missing_signers.contains(required_signer) {
+                "missing"
+            } else {
+                "signed"
+            };
+            writeln!(
+                out,
+                "├─ {} [{}]",
+                format_public_key_hash(required_signer),
+                status
+            )
+            .map_err(Error::IOError)?;
+        }
+
+        let manifest = decompile(&transaction.intent.instructions.0, &network)
+            .map_err(Error::DecompileError)?;
+        writeln!(out, "Manifest:\n{}", manifest).map_err(Error::IOError)?;
+        Ok(())
+    }
+}
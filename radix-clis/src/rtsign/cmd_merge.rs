@@ -0,0 +1,40 @@
+use clap::Parser;
+use std::path::PathBuf;
+
+use super::*;
+
+/// Merge the signatures of copies of the same partially signed transaction
+#[derive(Parser, Debug)]
+pub struct Merge {
+    /// The paths to the copies of the partially signed transaction
+    #[clap(multiple = true)]
+    inputs: Vec<PathBuf>,
+
+    /// The path to write the merged transaction to
+    #[clap(short, long)]
+    output: PathBuf,
+}
+
+impl Merge {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        if self.inputs.len() < 2 {
+            return Err(Error::NotEnoughInputs);
+        }
+        let mut transaction = read_partially_signed_transaction(&self.inputs[0])?;
+        for input in &self.inputs[1..] {
+            transaction
+                .merge(&read_partially_signed_transaction(input)?)
+                .map_err(Error::PartialSigningError)?;
+        }
+        write_partially_signed_transaction(&self.output, &transaction)?;
+
+        writeln!(
+            out,
+            "Merged {} signature(s) from {} file(s)",
+            transaction.intent_signatures.signatures.len(),
+            self.inputs.len()
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use clap::Parser;
+use radix_transactions::prelude::*;
+use std::path::PathBuf;
+
+use super::*;
+
+/// Add a signature to a partially signed transaction
+#[derive(Parser, Debug)]
+pub struct Sign {
+    /// The path to the partially signed transaction
+    input: PathBuf,
+
+    /// The hex-encoded private key to sign with
+    #[clap(short, long)]
+    private_key: Option<String>,
+
+    /// The curve of the private key [Secp256k1 | Ed25519]
+    #[clap(short, long, default_value = "secp256k1")]
+    curve: String,
+
+    /// The shell command of an external signer to sign with, instead of a private key
+    #[clap(long)]
+    signer_command: Option<String>,
+
+    /// The path to write the signed transaction to, if not the input file
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+impl Sign {
+    pub fn run<O: std::io::Write>(&self, out: &mut O) -> Result<(), Error> {
+        let signing_key = parse_signing_key(&self.private_key, &self.curve, &self.signer_command)?;
+        let mut transaction = read_partially_signed_transaction(&self.input)?;
+        transaction
+            .sign(&signing_key)
+            .map_err(Error::PartialSigningError)?;
+        write_partially_signed_transaction(
+            self.output.as_ref().unwrap_or(&self.input),
+            &transaction,
+        )?;
+
+        let missing_signers = transaction
+            .missing_signers()
+            .map_err(Error::PartialSigningError)?;
+        writeln!(
+            out,
+            "Signed by {:?}, {} required signer(s) missing",
+            signing_key.public_key(),
+            missing_signers.len()
+        )
+        .map_err(Error::IOError)?;
+        Ok(())
+    }
+}
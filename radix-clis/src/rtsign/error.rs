@@ -0,0 +1,36 @@
+use radix_common::prelude::*;
+use radix_transactions::errors::TransactionValidationError;
+use radix_transactions::manifest::DecompileError;
+use radix_transactions::prelude::*;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum Error {
+    IOError(std::io::Error),
+    ParseNetworkError(ParseNetworkError),
+    CompileError(String),
+    DecompileError(DecompileError),
+    InvalidPrivateKey,
+    InvalidPublicKey(String),
+    InvalidCurve(String),
+    ExpectedPrivateKeyOrSignerCommand,
+    ExternalSignerError(ExternalSignerError),
+    PrepareError(PrepareError),
+    PartialSigningError(PartialSigningError),
+    TransactionValidationError(TransactionValidationError),
+    EncodeError(EncodeError),
+    NotEnoughInputs,
+}
+
+impl fmt::Display for Error {
+    // TODO Implement pretty error printing
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl From<Error> for String {
+    fn from(err: Error) -> String {
+        err.to_string()
+    }
+}
@@ -0,0 +1,165 @@
+mod cmd_create;
+mod cmd_finalize;
+mod cmd_inspect;
+mod cmd_merge;
+mod cmd_sign;
+mod error;
+
+pub use cmd_create::*;
+pub use cmd_finalize::*;
+pub use cmd_inspect::*;
+pub use cmd_merge::*;
+pub use cmd_sign::*;
+pub use error::*;
+
+use clap::{Parser, Subcommand};
+use radix_common::prelude::IsHash;
+use radix_common::prelude::*;
+use radix_transactions::prelude::*;
+use std::path::Path;
+
+/// Offline multi-party transaction signing
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None, name = "rtsign")]
+pub struct RtsignCli {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    Create(Create),
+    Inspect(Inspect),
+    Sign(Sign),
+    Merge(Merge),
+    Finalize(Finalize),
+}
+
+pub fn run() -> Result<(), String> {
+    let cli = RtsignCli::parse();
+
+    let mut out = std::io::stdout();
+
+    match cli.command {
+        Command::Create(cmd) => cmd.run(&mut out),
+        Command::Inspect(cmd) => cmd.run(&mut out),
+        Command::Sign(cmd) => cmd.run(&mut out),
+        Command::Merge(cmd) => cmd.run(&mut out),
+        Command::Finalize(cmd) => cmd.run(&mut out),
+    }
+    .map_err(|err| err.into())
+}
+
+pub fn parse_network(network: &Option<String>) -> Result<NetworkDefinition, Error> {
+    match network {
+        Some(n) => NetworkDefinition::from_str(n).map_err(Error::ParseNetworkError),
+        None => Ok(NetworkDefinition::simulator()),
+    }
+}
+
+/// Parses a hex-encoded private key of the given curve (`secp256k1` or `ed25519`).
+pub fn parse_private_key(key: &str, curve: &str) -> Result<PrivateKey, Error> {
+    let bytes = hex::decode(key).map_err(|_| Error::InvalidPrivateKey)?;
+    match curve.to_ascii_lowercase().as_str() {
+        "secp256k1" => Secp256k1PrivateKey::from_bytes(&bytes)
+            .map(PrivateKey::Secp256k1)
+            .map_err(|_| Error::InvalidPrivateKey),
+        "ed25519" => Ed25519PrivateKey::from_bytes(&bytes)
+            .map(PrivateKey::Ed25519)
+            .map_err(|_| Error::InvalidPrivateKey),
+        _ => Err(Error::InvalidCurve(curve.to_string())),
+    }
+}
+
+/// A key to sign with, which is either held in memory or by an external signer.
+pub enum SigningKey {
+    PrivateKey(PrivateKey),
+    External(ExternalSigner),
+}
+
+impl Signer for SigningKey {
+    fn public_key(&self) -> PublicKey {
+        match self {
+            SigningKey::PrivateKey(key) => key.public_key(),
+            SigningKey::External(signer) => signer.public_key(),
+        }
+    }
+
+    fn sign_without_public_key(&self, message_hash: &impl IsHash) -> SignatureV1 {
+        match self {
+            SigningKey::PrivateKey(key) => key.sign_without_public_key(message_hash),
+            SigningKey::External(signer) => signer.sign_without_public_key(message_hash),
+        }
+    }
+
+    fn sign_with_public_key(&self, message_hash: &impl IsHash) -> SignatureWithPublicKeyV1 {
+        match self {
+            SigningKey::PrivateKey(key) => key.sign_with_public_key(message_hash),
+            SigningKey::External(signer) => signer.sign_with_public_key(message_hash),
+        }
+    }
+
+    fn try_sign_with_public_key(
+        &self,
+        message_hash: &impl IsHash,
+    ) -> Result<SignatureWithPublicKeyV1, SigningError> {
+        match self {
+            SigningKey::PrivateKey(key) => key.try_sign_with_public_key(message_hash),
+            SigningKey::External(signer) => Signer::try_sign_with_public_key(signer, message_hash),
+        }
+    }
+}
+
+/// Loads either the given private key, or the external signer run by the given command.
+pub fn parse_signing_key(
+    private_key: &Option<String>,
+    curve: &str,
+    signer_command: &Option<String>,
+) -> Result<SigningKey, Error> {
+    match (private_key, signer_command) {
+        (Some(key), None) => parse_private_key(key, curve).map(SigningKey::PrivateKey),
+        (None, Some(command)) => {
+            ExternalSigner::spawn(command, ExternalSigner::DEFAULT_READ_TIMEOUT)
+                .map(SigningKey::External)
+                .map_err(Error::ExternalSignerError)
+        }
+        _ => Err(Error::ExpectedPrivateKeyOrSignerCommand),
+    }
+}
+
+/// Parses a hex-encoded public key - the curve is determined by the key length.
+pub fn parse_public_key(key: &str) -> Result<PublicKey, Error> {
+    match key.len() / 2 {
+        Secp256k1PublicKey::LENGTH => Secp256k1PublicKey::from_str(key)
+            .map(PublicKey::Secp256k1)
+            .map_err(|_| Error::InvalidPublicKey(key.to_string())),
+        Ed25519PublicKey::LENGTH => Ed25519PublicKey::from_str(key)
+            .map(PublicKey::Ed25519)
+            .map_err(|_| Error::InvalidPublicKey(key.to_string())),
+        _ => Err(Error::InvalidPublicKey(key.to_string())),
+    }
+}
+
+pub fn format_public_key_hash(public_key_hash: &PublicKeyHash) -> String {
+    match public_key_hash {
+        PublicKeyHash::Secp256k1(hash) => format!("secp256k1:{}", hex::encode(hash.0)),
+        PublicKeyHash::Ed25519(hash) => format!("ed25519:{}", hex::encode(hash.0)),
+    }
+}
+
+pub fn read_partially_signed_transaction<P: AsRef<Path>>(
+    path: P,
+) -> Result<PartiallySignedTransactionV1, Error> {
+    let bytes = std::fs::read(path).map_err(Error::IOError)?;
+    PartiallySignedTransactionV1::from_payload_bytes(&bytes).map_err(Error::PartialSigningError)
+}
+
+pub fn write_partially_signed_transaction<P: AsRef<Path>>(
+    path: P,
+    transaction: &PartiallySignedTransactionV1,
+) -> Result<(), Error> {
+    let bytes = transaction
+        .to_payload_bytes()
+        .map_err(Error::PartialSigningError)?;
+    std::fs::write(path, bytes).map_err(Error::IOError)
+}
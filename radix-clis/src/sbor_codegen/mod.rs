@@ -0,0 +1,89 @@
+use clap::Parser;
+use radix_common::prelude::*;
+use std::fmt;
+use std::path::PathBuf;
+
+/// Generates Rust types with SBOR derives from a Scrypto schema
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None, name = "sbor-codegen")]
+pub struct Args {
+    /// Path to the SBOR-encoded versioned Scrypto schema
+    #[clap(required = true)]
+    input: PathBuf,
+
+    /// Whether the input file contains the schema as hex rather than raw bytes
+    #[clap(long, action)]
+    hex: bool,
+
+    /// The schema local indices of the types to generate, along with the types they contain.
+    /// Defaults to all types in the schema
+    #[clap(short, long, multiple = true)]
+    type_index: Vec<usize>,
+
+    /// The derives to add to each type, besides ScryptoSbor
+    #[clap(
+        short,
+        long,
+        multiple = true,
+        default_values = &["Debug", "Clone", "PartialEq", "Eq"]
+    )]
+    derive: Vec<String>,
+
+    /// Path to the output file, instead of the standard output
+    #[clap(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    IoError(std::io::Error),
+    HexDecodeError(hex::FromHexError),
+    DecodeError(sbor::DecodeError),
+    CodegenError(RustCodegenError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl From<Error> for String {
+    fn from(err: Error) -> String {
+        err.to_string()
+    }
+}
+
+pub fn run() -> Result<(), String> {
+    let args = Args::parse();
+
+    let mut content = std::fs::read(&args.input).map_err(Error::IoError)?;
+    if args.hex {
+        content =
+            hex::decode(String::from_utf8_lossy(&content).trim()).map_err(Error::HexDecodeError)?;
+    }
+    let schema: VersionedScryptoSchema = scrypto_decode(&content).map_err(Error::DecodeError)?;
+
+    let root_type_ids: Vec<LocalTypeId> = if args.type_index.is_empty() {
+        (0..schema.v1().type_kinds.len())
+            .map(LocalTypeId::SchemaLocalIndex)
+            .collect()
+    } else {
+        args.type_index
+            .iter()
+            .map(|index| LocalTypeId::SchemaLocalIndex(*index))
+            .collect()
+    };
+    let types = RustCodeGenerator::new(&schema)
+        .with_derives(args.derive)
+        .generate(&root_type_ids)
+        .map_err(Error::CodegenError)?;
+    let result = format!("use scrypto::prelude::*;\n\n{}", types);
+
+    match args.output {
+        Some(output) => std::fs::write(output, result).map_err(Error::IoError)?,
+        None => print!("{}", result),
+    }
+
+    Ok(())
+}
@@ -22,8 +22,19 @@ pub struct Coverage {
     /// The package directory
     #[clap(long)]
     path: Option<PathBuf>,
+
+    /// Keep the profile data of previous runs, so that the report covers all of them
+    #[clap(long)]
+    keep_data: bool,
+
+    /// Fail if the percentage of covered lines of the package is below this threshold
+    #[clap(long)]
+    fail_under: Option<f64>,
 }
 
+/// Source files which are not part of the package and are excluded from the report
+const IGNORED_FILENAME_REGEX: &str = r"(\.cargo/registry|\.cargo/git|/rustc/)";
+
 impl Coverage {
     fn check_wasm_target(nightly: bool) -> Result<(), Error> {
         let output = Command::new("rustup")
@@ -79,6 +90,31 @@ impl Coverage {
         }
     }
 
+    fn run_llvm_cov(llvm_major_version: &str, args: &[&str]) -> Result<Vec<u8>, Error> {
+        let output = Command::new(format!("llvm-cov-{}", llvm_major_version))
+            .args(args)
+            .output()
+            .expect("Failed to execute llvm-cov command");
+
+        if !output.status.success() {
+            eprintln!(
+                "llvm-cov failed: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+            return Err(Error::CoverageError(CoverageError::LlvmCovFailed));
+        }
+        Ok(output.stdout)
+    }
+
+    /// Reads the percentage of covered lines from the output of `llvm-cov export --summary-only`
+    fn parse_line_coverage(summary: &[u8]) -> Result<f64, Error> {
+        let summary: serde_json::Value = serde_json::from_slice(summary)
+            .map_err(|_| Error::CoverageError(CoverageError::InvalidCoverageSummary))?;
+        summary["data"][0]["totals"]["lines"]["percent"]
+            .as_f64()
+            .ok_or(Error::CoverageError(CoverageError::InvalidCoverageSummary))
+    }
+
     pub fn run(&self) -> Result<(), String> {
         // Verify rust version and wasm target
         Self::check_wasm_target(false)?;
@@ -113,7 +149,7 @@ impl Coverage {
             true,
             &[(
                 "CARGO_ENCODED_RUSTFLAGS".to_owned(),
-                "-Clto=off\x1f-Cinstrument-coverage\x1f-Zno-profiler-runtime\x1f--emit=llvm-ir"
+                "-Clto=off\x1f-Cinstrument-coverage\x1f-Zcoverage-options=branch\x1f-Zno-profiler-runtime\x1f--emit=llvm-ir"
                     .to_owned(),
             )],
         )
@@ -140,9 +176,10 @@ impl Coverage {
         assert!(coverage_path.ends_with("coverage"));
         assert!(coverage_path.is_dir());
 
-        // Remove "data" directory from coverage directory if it exists, then create it
+        // Remove "data" directory from coverage directory if it exists, then create it.
+        // When keeping data, the .profraw files of previous runs are merged into the report.
         let data_path = coverage_path.join("data");
-        if data_path.exists() {
+        if data_path.exists() && !self.keep_data {
             fs::remove_dir_all(&data_path).unwrap();
         }
         fs::create_dir_all(&data_path).unwrap();
@@ -242,31 +279,73 @@ impl Coverage {
         if coverage_report_path.exists() {
             fs::remove_dir_all(&coverage_report_path).unwrap();
         }
+        fs::create_dir_all(&coverage_report_path).unwrap();
 
-        let output = Command::new(format!("llvm-cov-{}", llvm_major_version))
-            .args(&[
+        let profdata_path = profdata_path.to_str().unwrap();
+        let object_file_path = object_file_path.to_str().unwrap();
+        let ignore_filename_regex = format!("--ignore-filename-regex={}", IGNORED_FILENAME_REGEX);
+
+        Self::run_llvm_cov(
+            &llvm_major_version,
+            &[
                 "show",
                 "--instr-profile",
-                profdata_path.to_str().unwrap(),
-                object_file_path.to_str().unwrap(),
+                profdata_path,
+                object_file_path,
+                &ignore_filename_regex,
                 "--show-instantiations=false",
+                "--show-branches=count",
+                "--show-regions",
                 "--format=html",
                 "--output-dir",
                 coverage_report_path.to_str().unwrap(),
-            ])
-            .output()
-            .expect("Failed to execute llvm-cov command");
+            ],
+        )?;
 
-        if !output.status.success() {
-            eprintln!(
-                "llvm-cov failed: {}",
-                String::from_utf8_lossy(&output.stderr)
-            );
-            return Err(Error::CoverageError(CoverageError::LlvmCovFailed).into());
-        }
+        let lcov = Self::run_llvm_cov(
+            &llvm_major_version,
+            &[
+                "export",
+                "--instr-profile",
+                profdata_path,
+                object_file_path,
+                &ignore_filename_regex,
+                "--format=lcov",
+            ],
+        )?;
+        let lcov_path = coverage_report_path.join("lcov.info");
+        fs::write(&lcov_path, lcov).expect("Failed to write lcov file");
 
         println!("Coverage report was succesfully generated, it is available in {coverage_report_path:?} directory.");
 
+        // Check coverage threshold
+        if let Some(fail_under) = self.fail_under {
+            let summary = Self::run_llvm_cov(
+                &llvm_major_version,
+                &[
+                    "export",
+                    "--instr-profile",
+                    profdata_path,
+                    object_file_path,
+                    &ignore_filename_regex,
+                    "--summary-only",
+                ],
+            )?;
+            let line_coverage = Self::parse_line_coverage(&summary)?;
+            println!("Line coverage: {:.2}%", line_coverage);
+            if line_coverage < fail_under {
+                eprintln!(
+                    "Line coverage {:.2}% is below the required {:.2}%",
+                    line_coverage, fail_under
+                );
+                return Err(Error::CoverageError(CoverageError::BelowThreshold {
+                    line_coverage,
+                    fail_under,
+                })
+                .into());
+            }
+        }
+
         Ok(())
     }
 }
@@ -26,12 +26,20 @@ impl ToTokens for PackageStub {
 pub struct BlueprintStub {
     pub blueprint_name: String,
     pub fn_signatures: Vec<FnSignature>,
-    pub package_address: PackageAddress,
+    /// The address of the package, or `None` if it isn't published yet. The stubs then refer to a
+    /// `PACKAGE_ADDRESS` constant which must be in scope where they are included.
+    pub package_address: Option<PackageAddress>,
 }
 
 impl ToTokens for BlueprintStub {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let package_address_bytes = self.package_address.to_vec();
+        let package_address = match self.package_address {
+            Some(package_address) => {
+                let package_address_bytes = package_address.to_vec();
+                quote! { PackageAddress::new_or_panic([ #(#package_address_bytes),* ]) }
+            }
+            None => quote! { PACKAGE_ADDRESS },
+        };
         let blueprint_name = self.blueprint_name.clone();
         let owned_blueprint_name = format!("Owned{}", self.blueprint_name);
         let global_blueprint_name = format!("Global{}", self.blueprint_name);
@@ -55,7 +63,7 @@ impl ToTokens for BlueprintStub {
 
         quote! {
             extern_blueprint_internal! {
-                PackageAddress::new_or_panic([ #(#package_address_bytes),* ]),
+                #package_address,
                 #blueprint_name_ident,
                 #blueprint_name,
                 #owned_blueprint_name,
@@ -119,6 +127,123 @@ impl ToTokens for FnSignature {
     }
 }
 
+/// The `scrypto-test` counterpart of [`PackageStub`]: typed wrappers around the invocations of the
+/// `TestEnvironment` which allow tests to call the package without encoding arguments by hand.
+pub struct TestEnvironmentPackageStub {
+    pub blueprints: Vec<TestEnvironmentBlueprintStub>,
+    pub auxiliary_types: Vec<AuxiliaryType>,
+}
+
+impl ToTokens for TestEnvironmentPackageStub {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let blueprints = &self.blueprints;
+        quote! {
+            #(#blueprints)*
+        }
+        .to_tokens(tokens);
+        for auxiliary_type in self.auxiliary_types.iter() {
+            auxiliary_type.to_tokens_with_derive(quote! { ScryptoSbor }, tokens);
+        }
+    }
+}
+
+/// Generates a `{Blueprint}Blueprint` wrapper around the package address for calling the functions
+/// of the blueprint and a `{Blueprint}Component` wrapper around the node id of an instance for
+/// calling its methods.
+pub struct TestEnvironmentBlueprintStub {
+    pub blueprint_name: String,
+    pub fn_signatures: Vec<FnSignature>,
+}
+
+impl ToTokens for TestEnvironmentBlueprintStub {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let blueprint_name = &self.blueprint_name;
+        let blueprint_ident = Ident::new(
+            format!("{}Blueprint", self.blueprint_name).as_str(),
+            Span::call_site(),
+        );
+        let component_ident = Ident::new(
+            format!("{}Component", self.blueprint_name).as_str(),
+            Span::call_site(),
+        );
+
+        let functions = self
+            .fn_signatures
+            .iter()
+            .filter(|func| matches!(func.fn_type, FnType::Function))
+            .map(|func| {
+                let ident = &func.ident;
+                let function_name = func.ident.to_string();
+                let input_names = func.inputs.iter().map(|(k, _)| k).collect::<Vec<_>>();
+                let input_types = func.inputs.iter().map(|(_, v)| v).collect::<Vec<_>>();
+                let output = &func.output;
+                quote! {
+                    pub fn #ident<D>(
+                        &self,
+                        #( #input_names: #input_types, )*
+                        env: &mut TestEnvironment<D>
+                    ) -> Result<#output, RuntimeError>
+                    where
+                        D: SubstateDatabase + CommittableSubstateDatabase + 'static,
+                    {
+                        env.call_function_typed::<_, #output>(
+                            self.0,
+                            #blueprint_name,
+                            #function_name,
+                            &( #( #input_names, )* ),
+                        )
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+        let methods = self
+            .fn_signatures
+            .iter()
+            .filter(|func| matches!(func.fn_type, FnType::Method { .. }))
+            .map(|func| {
+                let ident = &func.ident;
+                let method_name = func.ident.to_string();
+                let input_names = func.inputs.iter().map(|(k, _)| k).collect::<Vec<_>>();
+                let input_types = func.inputs.iter().map(|(_, v)| v).collect::<Vec<_>>();
+                let output = &func.output;
+                quote! {
+                    pub fn #ident<D>(
+                        &self,
+                        #( #input_names: #input_types, )*
+                        env: &mut TestEnvironment<D>
+                    ) -> Result<#output, RuntimeError>
+                    where
+                        D: SubstateDatabase + CommittableSubstateDatabase + 'static,
+                    {
+                        env.call_method_typed::<_, _, #output>(
+                            self.0,
+                            #method_name,
+                            &( #( #input_names, )* ),
+                        )
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        quote! {
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+            pub struct #blueprint_ident(pub PackageAddress);
+
+            impl #blueprint_ident {
+                #(#functions)*
+            }
+
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+            pub struct #component_ident(pub NodeId);
+
+            impl #component_ident {
+                #(#methods)*
+            }
+        }
+        .to_tokens(tokens)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AuxiliaryType {
     TupleStruct {
@@ -137,6 +262,14 @@ pub enum AuxiliaryType {
 
 impl ToTokens for AuxiliaryType {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        self.to_tokens_with_derive(quote! { ::scrypto::prelude::ScryptoSbor }, tokens)
+    }
+}
+
+impl AuxiliaryType {
+    /// Generates the type with the given path of the `ScryptoSbor` derive macro, since that depends
+    /// on the crate that the bindings are used from.
+    pub fn to_tokens_with_derive(&self, derive: TokenStream, tokens: &mut TokenStream) {
         match self {
             Self::TupleStruct {
                 struct_name,
@@ -148,7 +281,7 @@ impl ToTokens for AuxiliaryType {
                     .map(|string| token_stream_from_str!(string));
 
                 quote! {
-                    #[derive(::scrypto::prelude::ScryptoSbor)]
+                    #[derive(#derive)]
                     pub struct #struct_name(
                         #(
                             #field_types
@@ -166,7 +299,7 @@ impl ToTokens for AuxiliaryType {
                 let field_types = fields.values().map(|string| token_stream_from_str!(string));
 
                 quote! {
-                    #[derive(::scrypto::prelude::ScryptoSbor)]
+                    #[derive(#derive)]
                     pub struct #struct_name {
                         #(
                             #field_names: #field_types
@@ -182,7 +315,7 @@ impl ToTokens for AuxiliaryType {
                 let enum_name = token_stream_from_str!(enum_name);
 
                 quote! {
-                    #[derive(::scrypto::prelude::ScryptoSbor)]
+                    #[derive(#derive)]
                     pub enum #enum_name {
                         #(
                             #variants
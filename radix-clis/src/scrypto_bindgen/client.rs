@@ -0,0 +1,468 @@
+//! This module converts the models from `schema.rs` to a language-agnostic model of a package's
+//! client interface, which the `typescript.rs` and `python.rs` generators are based on.
+
+use super::schema;
+use radix_common::prelude::*;
+
+/// A description of a package's interface as seen by an off-ledger client that builds manifests
+/// and decodes the programmatic JSON representation of values.
+#[derive(Clone, Debug)]
+pub struct ClientPackage {
+    /// The address of the package, if it is known at generation time.
+    pub package_address: Option<String>,
+    pub blueprints: Vec<ClientBlueprint>,
+    /// The definitions of all named structs and enums used in the interface, ordered by name.
+    pub type_definitions: Vec<ClientTypeDefinition>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ClientBlueprint {
+    pub blueprint_name: String,
+    pub functions: Vec<ClientFunction>,
+    pub events: Vec<(String, ClientType)>,
+    pub registered_types: Vec<(String, ClientType)>,
+}
+
+impl ClientBlueprint {
+    pub fn functions(&self) -> impl Iterator<Item = &ClientFunction> {
+        self.functions.iter().filter(|function| !function.is_method)
+    }
+
+    pub fn methods(&self) -> impl Iterator<Item = &ClientFunction> {
+        self.functions.iter().filter(|function| function.is_method)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ClientFunction {
+    pub ident: String,
+    pub is_method: bool,
+    pub arguments: Vec<(String, ClientType)>,
+    pub returns: ClientType,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IntegerKind {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+}
+
+impl IntegerKind {
+    /// The name of the value kind, which is also used as the manifest value kind.
+    pub fn value_kind(&self) -> &'static str {
+        match self {
+            Self::I8 => "I8",
+            Self::I16 => "I16",
+            Self::I32 => "I32",
+            Self::I64 => "I64",
+            Self::I128 => "I128",
+            Self::U8 => "U8",
+            Self::U16 => "U16",
+            Self::U32 => "U32",
+            Self::U64 => "U64",
+            Self::U128 => "U128",
+        }
+    }
+
+    /// Whether the values of this kind may not fit into a double precision float, in which case
+    /// the programmatic JSON representation encodes them as strings.
+    pub fn is_large(&self) -> bool {
+        matches!(self, Self::I64 | Self::I128 | Self::U64 | Self::U128)
+    }
+}
+
+/// The type of a value in the interface of a package.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClientType {
+    Any,
+    Bool,
+    Integer(IntegerKind),
+    String,
+    Decimal,
+    PreciseDecimal,
+    NonFungibleLocalId,
+    Address,
+    Bucket,
+    Proof,
+    AddressReservation,
+    /// An owned object which can't be passed in a manifest, such as a vault.
+    Own,
+    Bytes,
+    Array(Box<ClientType>),
+    Map(Box<ClientType>, Box<ClientType>),
+    Tuple(Vec<ClientType>),
+    Option(Box<ClientType>),
+    Result(Box<ClientType>, Box<ClientType>),
+    /// A struct or an enum with a definition in [`ClientPackage::type_definitions`].
+    Named(String),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClientTypeDefinition {
+    Struct {
+        name: String,
+        fields: ClientFields,
+    },
+    Enum {
+        name: String,
+        variants: Vec<ClientEnumVariant>,
+    },
+}
+
+impl ClientTypeDefinition {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Struct { name, .. } | Self::Enum { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientEnumVariant {
+    pub variant_name: String,
+    pub variant_index: u8,
+    pub fields: ClientFields,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClientFields {
+    Unnamed(Vec<ClientType>),
+    Named(IndexMap<String, ClientType>),
+}
+
+impl ClientFields {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Unnamed(field_types) => field_types.len(),
+            Self::Named(fields) => fields.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ClientPackage {
+    /// The manifest value kind of the given type, or `None` if it can only be determined from the
+    /// value itself.
+    pub fn manifest_value_kind(&self, client_type: &ClientType) -> Option<&'static str> {
+        match client_type {
+            ClientType::Any | ClientType::Own => None,
+            ClientType::Bool => Some("Bool"),
+            ClientType::Integer(integer_kind) => Some(integer_kind.value_kind()),
+            ClientType::String => Some("String"),
+            ClientType::Decimal => Some("Decimal"),
+            ClientType::PreciseDecimal => Some("PreciseDecimal"),
+            ClientType::NonFungibleLocalId => Some("NonFungibleLocalId"),
+            ClientType::Address => Some("Address"),
+            ClientType::Bucket => Some("Bucket"),
+            ClientType::Proof => Some("Proof"),
+            ClientType::AddressReservation => Some("AddressReservation"),
+            ClientType::Bytes | ClientType::Array(..) => Some("Array"),
+            ClientType::Map(..) => Some("Map"),
+            ClientType::Tuple(..) => Some("Tuple"),
+            ClientType::Option(..) | ClientType::Result(..) => Some("Enum"),
+            ClientType::Named(name) => self
+                .type_definitions
+                .iter()
+                .find(|definition| definition.name() == name)
+                .map(|definition| match definition {
+                    ClientTypeDefinition::Struct { .. } => "Tuple",
+                    ClientTypeDefinition::Enum { .. } => "Enum",
+                }),
+        }
+    }
+}
+
+pub fn package_schema_interface_to_client_interface<S>(
+    schema_interface: schema::PackageInterface,
+    package_address: Option<String>,
+    schema_resolver: &S,
+) -> Result<ClientPackage, schema::SchemaError>
+where
+    S: schema::PackageSchemaResolver,
+{
+    // Unlike the Rust bindings, the client bindings also describe the events and registered types
+    // of the blueprints, so the types they refer to need to be defined too.
+    let mut auxiliary_types = schema_interface.auxiliary_types;
+    for blueprint_interface in schema_interface.blueprints.values() {
+        for type_identifier in blueprint_interface
+            .events
+            .values()
+            .chain(blueprint_interface.registered_types.values())
+        {
+            schema::get_scoped_type_ids_in_path(
+                type_identifier,
+                schema_resolver,
+                &mut auxiliary_types,
+            )?;
+        }
+    }
+
+    let blueprints = schema_interface
+        .blueprints
+        .into_iter()
+        .map(|(blueprint_name, blueprint_interface)| {
+            blueprint_schema_interface_to_client_interface(
+                blueprint_interface,
+                blueprint_name,
+                schema_resolver,
+            )
+        })
+        .collect::<Result<_, _>>()?;
+
+    let mut type_definitions = Vec::new();
+    for scoped_type_id in auxiliary_types {
+        if let Some(type_definition) = client_type_definition(&scoped_type_id, schema_resolver)? {
+            type_definitions.push(type_definition);
+        }
+    }
+    // Types are identified by their name in the generated code, so a name is only defined once.
+    type_definitions.sort_by(|a, b| a.name().cmp(b.name()));
+    type_definitions.dedup_by(|a, b| a.name() == b.name());
+
+    Ok(ClientPackage {
+        package_address,
+        blueprints,
+        type_definitions,
+    })
+}
+
+fn blueprint_schema_interface_to_client_interface<S>(
+    schema_interface: schema::BlueprintInterface,
+    blueprint_name: String,
+    schema_resolver: &S,
+) -> Result<ClientBlueprint, schema::SchemaError>
+where
+    S: schema::PackageSchemaResolver,
+{
+    let named_client_types = |types: IndexMap<String, ScopedTypeId>| {
+        types
+            .into_iter()
+            .map(|(name, type_identifier)| {
+                client_type(&type_identifier, schema_resolver)
+                    .map(|client_type| (name, client_type))
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    Ok(ClientBlueprint {
+        functions: schema_interface
+            .functions
+            .into_iter()
+            .map(|function| {
+                Ok::<_, schema::SchemaError>(ClientFunction {
+                    ident: function.ident,
+                    is_method: function.receiver.is_some(),
+                    arguments: named_client_types(function.arguments)?,
+                    returns: client_type(&function.returns, schema_resolver)?,
+                })
+            })
+            .collect::<Result<_, _>>()?,
+        events: named_client_types(schema_interface.events)?,
+        registered_types: named_client_types(schema_interface.registered_types)?,
+        blueprint_name,
+    })
+}
+
+pub fn client_type<S>(
+    type_identifier: &ScopedTypeId,
+    schema_resolver: &S,
+) -> Result<ClientType, schema::SchemaError>
+where
+    S: schema::PackageSchemaResolver,
+{
+    let type_kind = schema_resolver.resolve_type_kind(type_identifier)?;
+    let type_metadata = schema_resolver.resolve_type_metadata(type_identifier)?;
+    let type_validation = schema_resolver.resolve_type_validation(type_identifier)?;
+    let child_type = |local_type_id: LocalTypeId| {
+        client_type(
+            &ScopedTypeId(type_identifier.0, local_type_id),
+            schema_resolver,
+        )
+        .map(Box::new)
+    };
+
+    let client_type = match type_kind {
+        TypeKind::Any => ClientType::Any,
+        TypeKind::Bool => ClientType::Bool,
+        TypeKind::I8 => ClientType::Integer(IntegerKind::I8),
+        TypeKind::I16 => ClientType::Integer(IntegerKind::I16),
+        TypeKind::I32 => ClientType::Integer(IntegerKind::I32),
+        TypeKind::I64 => ClientType::Integer(IntegerKind::I64),
+        TypeKind::I128 => ClientType::Integer(IntegerKind::I128),
+        TypeKind::U8 => ClientType::Integer(IntegerKind::U8),
+        TypeKind::U16 => ClientType::Integer(IntegerKind::U16),
+        TypeKind::U32 => ClientType::Integer(IntegerKind::U32),
+        TypeKind::U64 => ClientType::Integer(IntegerKind::U64),
+        TypeKind::U128 => ClientType::Integer(IntegerKind::U128),
+        TypeKind::String => ClientType::String,
+        TypeKind::Array { element_type } => {
+            let element_type_kind = schema_resolver
+                .resolve_type_kind(&ScopedTypeId(type_identifier.0, element_type))?;
+            match element_type_kind {
+                TypeKind::U8 => ClientType::Bytes,
+                _ => ClientType::Array(child_type(element_type)?),
+            }
+        }
+        TypeKind::Tuple { field_types } => match type_metadata.get_name_string() {
+            Some(name) => ClientType::Named(name),
+            None => ClientType::Tuple(
+                field_types
+                    .into_iter()
+                    .map(|field_type| child_type(field_type).map(|field_type| *field_type))
+                    .collect::<Result<_, _>>()?,
+            ),
+        },
+        TypeKind::Enum { variants } => {
+            // Same as for the Rust bindings, `Option` and `Result` are recognized by their name
+            // and shape as there is no way to know if a type has generics.
+            match (
+                type_metadata.get_name(),
+                variants.len(),
+                variants.get(&0).as_ref().map(|vec| vec.as_slice()),
+                variants.get(&1).as_ref().map(|vec| vec.as_slice()),
+            ) {
+                (Some("Option"), 2usize, Some([]), Some([some_type_index])) => {
+                    ClientType::Option(child_type(*some_type_index)?)
+                }
+                (Some("Result"), 2usize, Some([ok_type_index]), Some([err_type_index])) => {
+                    ClientType::Result(child_type(*ok_type_index)?, child_type(*err_type_index)?)
+                }
+                (Some(name), ..) => ClientType::Named(name.to_owned()),
+                (None, ..) => return Err(schema::SchemaError::NoNameFound),
+            }
+        }
+        TypeKind::Map {
+            key_type,
+            value_type,
+        } => ClientType::Map(child_type(key_type)?, child_type(value_type)?),
+        TypeKind::Custom(custom_type_kind) => match custom_type_kind {
+            ScryptoCustomTypeKind::Reference => ClientType::Address,
+            ScryptoCustomTypeKind::Own => match type_validation {
+                TypeValidation::Custom(ScryptoCustomTypeValidation::Own(
+                    OwnValidation::IsBucket,
+                )) => ClientType::Bucket,
+                TypeValidation::Custom(ScryptoCustomTypeValidation::Own(
+                    OwnValidation::IsProof,
+                )) => ClientType::Proof,
+                TypeValidation::Custom(ScryptoCustomTypeValidation::Own(
+                    OwnValidation::IsGlobalAddressReservation,
+                )) => ClientType::AddressReservation,
+                _ => ClientType::Own,
+            },
+            ScryptoCustomTypeKind::Decimal => ClientType::Decimal,
+            ScryptoCustomTypeKind::PreciseDecimal => ClientType::PreciseDecimal,
+            ScryptoCustomTypeKind::NonFungibleLocalId => ClientType::NonFungibleLocalId,
+        },
+    };
+
+    Ok(client_type)
+}
+
+/// Returns the definition of the given type if it is referenced by name in the client interface.
+fn client_type_definition<S>(
+    type_identifier: &ScopedTypeId,
+    schema_resolver: &S,
+) -> Result<Option<ClientTypeDefinition>, schema::SchemaError>
+where
+    S: schema::PackageSchemaResolver,
+{
+    let ClientType::Named(name) = client_type(type_identifier, schema_resolver)? else {
+        return Ok(None);
+    };
+    let type_kind = schema_resolver.resolve_type_kind(type_identifier)?;
+    let type_metadata = schema_resolver.resolve_type_metadata(type_identifier)?;
+    let fields = |field_types: &[LocalTypeId],
+                  metadata: &TypeMetadata|
+     -> Result<ClientFields, schema::SchemaError> {
+        let field_types = field_types
+            .iter()
+            .map(|field_type| {
+                client_type(
+                    &ScopedTypeId(type_identifier.0, *field_type),
+                    schema_resolver,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(match metadata.get_field_names() {
+            Some(field_names) => ClientFields::Named(
+                field_names
+                    .iter()
+                    .map(|field_name| field_name.as_ref().to_owned())
+                    .zip(field_types)
+                    .collect(),
+            ),
+            None => ClientFields::Unnamed(field_types),
+        })
+    };
+
+    let type_definition = match type_kind {
+        TypeKind::Tuple { field_types } => ClientTypeDefinition::Struct {
+            fields: fields(&field_types, &type_metadata)?,
+            name,
+        },
+        TypeKind::Enum { variants } => {
+            let Some(ChildNames::EnumVariants(variant_metadata)) = &type_metadata.child_names
+            else {
+                panic!("Unexpected state: the child names of an enum must be enum-variants")
+            };
+            let variants = variants
+                .iter()
+                .map(|(variant_index, field_types)| {
+                    let metadata = variant_metadata
+                        .get(variant_index)
+                        .expect("Unexpected state: variant id can not be found!");
+                    let variant_name = metadata
+                        .get_name_string()
+                        .expect("Unexpected state: an enum variant with no name!");
+                    Ok::<_, schema::SchemaError>(ClientEnumVariant {
+                        variant_name,
+                        variant_index: *variant_index,
+                        fields: fields(field_types, metadata)?,
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+            ClientTypeDefinition::Enum { name, variants }
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(type_definition))
+}
+
+/// Converts a `snake_case` identifier to `PascalCase`.
+pub fn to_pascal_case(ident: &str) -> String {
+    ident
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Converts a `PascalCase` identifier to `UPPER_SNAKE_CASE`.
+pub fn to_upper_snake_case(ident: &str) -> String {
+    let mut upper_snake_case = String::new();
+    for (index, c) in ident.chars().enumerate() {
+        if c.is_uppercase() && index != 0 {
+            upper_snake_case.push('_');
+        }
+        upper_snake_case.extend(c.to_uppercase());
+    }
+    upper_snake_case
+}
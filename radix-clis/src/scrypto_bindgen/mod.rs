@@ -1,13 +1,19 @@
 pub mod ast;
+pub mod client;
 pub mod macros;
+pub mod python;
 pub mod schema;
 pub mod translation;
+pub mod typescript;
 
-use clap::Parser;
+use clap::{ArgEnum, Parser};
 use radix_common::prelude::*;
 use radix_engine::system::system_db_reader::SystemDatabaseReader;
+use radix_engine_interface::blueprints::package::*;
 use radix_substate_store_interface::interface::SubstateDatabase;
+use std::collections::BTreeMap;
 use std::io::Write;
+use std::path::PathBuf;
 
 use crate::resim::*;
 
@@ -17,13 +23,41 @@ use self::schema::*;
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None, name = "scrypto-bindgen")]
 pub struct Args {
-    /// The address of the package to generate the bindings for.
-    package_address: String,
+    /// The address of the package to generate the bindings for. This is optional when the package
+    /// is read from its compiled WASM and RPD files, in which case the Rust bindings refer to a
+    /// `PACKAGE_ADDRESS` constant which must be in scope where they are included.
+    #[clap(required_unless_present = "wasm")]
+    package_address: Option<String>,
+
+    /// The path to the compiled WASM of the package. When provided together with its RPD file, the
+    /// bindings are generated from the compiled package rather than from the ledger.
+    #[clap(long, requires = "rpd")]
+    wasm: Option<PathBuf>,
+
+    /// The path to the package definition (RPD) produced alongside the compiled WASM.
+    #[clap(long, requires = "wasm")]
+    rpd: Option<PathBuf>,
 
     /// When enabled, the ledger will be cleared and bootstrapped again before being used to obtain
     /// the bindings.
     #[clap(short, long)]
     reset_ledger: bool,
+
+    /// The language to generate the bindings in.
+    #[clap(short, long, arg_enum, default_value = "rust")]
+    language: Language,
+}
+
+#[derive(ArgEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Language {
+    /// Scrypto stubs for calling the package from other blueprints.
+    Rust,
+    /// Typed wrappers for calling the package from `scrypto-test` through the `TestEnvironment`.
+    TestEnvironment,
+    /// Client bindings producing manifest instructions and decoding programmatic JSON values.
+    Typescript,
+    /// Client bindings producing manifest instructions and decoding programmatic JSON values.
+    Python,
 }
 
 #[derive(Debug)]
@@ -33,6 +67,9 @@ pub enum Error {
     ResimError(crate::resim::Error),
     SchemaError(SchemaError),
     IOError(std::io::Error),
+    IOErrorAtPath(std::io::Error, PathBuf),
+    SborDecodeError(DecodeError),
+    FormatError(std::fmt::Error),
 }
 
 pub fn run() -> Result<(), Error> {
@@ -41,47 +78,152 @@ pub fn run() -> Result<(), Error> {
     // Everything will be written to the std-out
     let mut out = std::io::stdout();
 
-    let mut env = SimulatorEnvironment::new().map_err(Error::ResimError)?;
-    if args.reset_ledger {
-        env = env.reset().map_err(Error::ResimError)?;
+    // Decode the package address without network context.
+    let package_address = args
+        .package_address
+        .as_ref()
+        .map(|package_address| {
+            let (_, _, bytes) =
+                AddressBech32Decoder::validate_and_decode_ignore_hrp(package_address)
+                    .map_err(Error::Bech32DecodeError)?;
+            PackageAddress::try_from(bytes.as_slice()).map_err(Error::PackageAddressError)
+        })
+        .transpose()?;
+
+    match (&args.wasm, &args.rpd) {
+        (Some(wasm_path), Some(rpd_path)) => {
+            let code = std::fs::read(wasm_path)
+                .map_err(|err| Error::IOErrorAtPath(err, wasm_path.clone()))?;
+            let package_definition: PackageDefinition = manifest_decode(
+                &std::fs::read(rpd_path)
+                    .map_err(|err| Error::IOErrorAtPath(err, rpd_path.clone()))?,
+            )
+            .map_err(Error::SborDecodeError)?;
+
+            generate_from_compiled_package(
+                &mut out,
+                &args,
+                package_address,
+                &code,
+                package_definition,
+            )
+        }
+        _ => {
+            let mut env = SimulatorEnvironment::new().map_err(Error::ResimError)?;
+            if args.reset_ledger {
+                env = env.reset().map_err(Error::ResimError)?;
+            }
+            let db = env.db;
+
+            let package_address =
+                package_address.expect("Clap requires the package address without a WASM file");
+            let reader = SystemDatabaseReader::new(&db);
+            let definition = reader.get_package_definition(package_address);
+            let schema_resolver = SchemaResolver::new(package_address, &db);
+
+            generate(
+                &mut out,
+                &args,
+                Some(package_address),
+                definition,
+                &schema_resolver,
+            )
+        }
     }
-    let db = env.db;
+}
 
-    // Decode the package address without network context.
-    let package_address = {
-        let (_, _, bytes) =
-            AddressBech32Decoder::validate_and_decode_ignore_hrp(&args.package_address)
-                .map_err(Error::Bech32DecodeError)?;
-        PackageAddress::try_from(bytes.as_slice()).map_err(Error::PackageAddressError)?
-    };
-
-    // Generating the bindings
-    let bindings = {
-        let reader = SystemDatabaseReader::new(&db);
-        let definition = reader.get_package_definition(package_address);
-        let schema_resolver = SchemaResolver::new(package_address, &db);
-
-        let package_interface =
-            schema::package_interface_from_package_definition(definition, &schema_resolver)
-                .map_err(Error::SchemaError)?;
-        let mut ast_package_interface = translation::package_schema_interface_to_ast_interface(
-            package_interface,
-            package_address,
-            &schema_resolver,
-        )
-        .map_err(Error::SchemaError)?;
+fn generate_from_compiled_package<O>(
+    out: &mut O,
+    args: &Args,
+    package_address: Option<PackageAddress>,
+    code: &[u8],
+    package_definition: PackageDefinition,
+) -> Result<(), Error>
+where
+    O: std::io::Write,
+{
+    let code_hash = CodeHash::from(hash(code));
+    let (definition, schemas) =
+        blueprint_definitions_from_package_definition(package_definition, code_hash);
+    let schema_resolver = LocalSchemaResolver::new(
+        package_address.unwrap_or_else(|| local_package_address(code_hash)),
+        schemas,
+    );
 
-        // Scrypto-bindgen does not generate the aux-types. Only ledger-tools does.
-        ast_package_interface.auxiliary_types = Default::default();
+    generate(out, args, package_address, definition, &schema_resolver)
+}
 
-        ast_package_interface
-    };
+fn generate<O, S>(
+    out: &mut O,
+    args: &Args,
+    package_address: Option<PackageAddress>,
+    definition: BTreeMap<BlueprintVersionKey, BlueprintDefinition>,
+    schema_resolver: &S,
+) -> Result<(), Error>
+where
+    O: std::io::Write,
+    S: PackageSchemaResolver,
+{
+    let package_interface =
+        schema::package_interface_from_package_definition(definition, schema_resolver)
+            .map_err(Error::SchemaError)?;
+
+    match args.language {
+        Language::Rust => {
+            let mut ast_package_interface = translation::package_schema_interface_to_ast_interface(
+                package_interface,
+                package_address,
+                schema_resolver,
+            )
+            .map_err(Error::SchemaError)?;
 
-    writeln!(&mut out, "{}", quote::quote!(#bindings)).map_err(Error::IOError)?;
+            // Scrypto-bindgen does not generate the aux-types. Only ledger-tools does.
+            ast_package_interface.auxiliary_types = Default::default();
+
+            writeln!(out, "{}", quote::quote!(#ast_package_interface)).map_err(Error::IOError)?;
+        }
+        Language::TestEnvironment => {
+            // The package address is only known once the package is published in the test, so it
+            // is provided to the wrappers at runtime rather than embedded in them.
+            let test_environment_interface =
+                translation::package_schema_interface_to_test_environment_interface(
+                    package_interface,
+                    schema_resolver,
+                )
+                .map_err(Error::SchemaError)?;
+
+            writeln!(out, "{}", quote::quote!(#test_environment_interface))
+                .map_err(Error::IOError)?;
+        }
+        Language::Typescript | Language::Python => {
+            let client_package = client::package_schema_interface_to_client_interface(
+                package_interface,
+                args.package_address.clone(),
+                schema_resolver,
+            )
+            .map_err(Error::SchemaError)?;
+            let bindings = match args.language {
+                Language::Typescript => typescript::generate(&client_package),
+                _ => python::generate(&client_package),
+            }
+            .map_err(Error::FormatError)?;
+
+            write!(out, "{}", bindings).map_err(Error::IOError)?;
+        }
+    }
 
     Ok(())
 }
 
+/// An address standing in for a package which was not published yet, derived from its code hash.
+/// It only serves to tell references to the package's own blueprints apart from others.
+fn local_package_address(code_hash: CodeHash) -> PackageAddress {
+    let mut bytes = [0u8; NodeId::LENGTH];
+    bytes[0] = EntityType::GlobalPackage as u8;
+    bytes[1..].copy_from_slice(&code_hash.0.as_slice()[..NodeId::LENGTH - 1]);
+    PackageAddress::new_or_panic(bytes)
+}
+
 pub struct SchemaResolver<'s, S>(PackageAddress, SystemDatabaseReader<'s, S>)
 where
     S: SubstateDatabase;
@@ -104,52 +246,118 @@ where
         self.1.get_schema(self.0.as_node_id(), schema_hash).ok()
     }
 
-    fn resolve_type_kind(
-        &self,
-        type_identifier: &ScopedTypeId,
-    ) -> Result<SchemaTypeKind<ScryptoCustomSchema>, schema::SchemaError> {
-        self.lookup_schema(&type_identifier.0)
-            .ok_or(schema::SchemaError::FailedToGetSchemaFromSchemaHash)?
-            .as_latest_version()
-            .ok_or(schema::SchemaError::FailedToGetSchemaFromSchemaHash)?
-            .resolve_type_kind(type_identifier.1)
-            .ok_or(schema::SchemaError::NonExistentLocalTypeIndex(
-                type_identifier.1,
-            ))
-            .cloned()
+    fn package_address(&self) -> PackageAddress {
+        self.0
     }
+}
 
-    fn resolve_type_metadata(
-        &self,
-        type_identifier: &ScopedTypeId,
-    ) -> Result<TypeMetadata, schema::SchemaError> {
-        self.lookup_schema(&type_identifier.0)
-            .ok_or(schema::SchemaError::FailedToGetSchemaFromSchemaHash)?
-            .as_latest_version()
-            .ok_or(schema::SchemaError::FailedToGetSchemaFromSchemaHash)?
-            .resolve_type_metadata(type_identifier.1)
-            .ok_or(schema::SchemaError::NonExistentLocalTypeIndex(
-                type_identifier.1,
-            ))
-            .cloned()
+/// Resolves the schemas of a package which was compiled but not published, from its RPD file.
+pub struct LocalSchemaResolver {
+    package_address: PackageAddress,
+    schemas: IndexMap<SchemaHash, Rc<VersionedScryptoSchema>>,
+}
+
+impl LocalSchemaResolver {
+    pub fn new(
+        package_address: PackageAddress,
+        schemas: IndexMap<SchemaHash, VersionedScryptoSchema>,
+    ) -> Self {
+        Self {
+            package_address,
+            schemas: schemas
+                .into_iter()
+                .map(|(schema_hash, schema)| (schema_hash, Rc::new(schema)))
+                .collect(),
+        }
     }
+}
 
-    fn resolve_type_validation(
-        &self,
-        type_identifier: &ScopedTypeId,
-    ) -> Result<TypeValidation<ScryptoCustomTypeValidation>, schema::SchemaError> {
-        self.lookup_schema(&type_identifier.0)
-            .ok_or(schema::SchemaError::FailedToGetSchemaFromSchemaHash)?
-            .as_latest_version()
-            .ok_or(schema::SchemaError::FailedToGetSchemaFromSchemaHash)?
-            .resolve_type_validation(type_identifier.1)
-            .ok_or(schema::SchemaError::NonExistentLocalTypeIndex(
-                type_identifier.1,
-            ))
-            .cloned()
+impl PackageSchemaResolver for LocalSchemaResolver {
+    fn lookup_schema(&self, schema_hash: &SchemaHash) -> Option<Rc<VersionedScryptoSchema>> {
+        self.schemas.get(schema_hash).cloned()
     }
 
     fn package_address(&self) -> PackageAddress {
-        self.0
+        self.package_address
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Generates the bindings of the Radiswap scenario package, as done by
+    /// `scrypto-bindgen [package_address] --wasm radiswap.wasm --rpd radiswap.rpd --language <language>`.
+    fn generate_radiswap_bindings(
+        language: Language,
+        package_address: Option<PackageAddress>,
+    ) -> String {
+        let args = Args {
+            package_address: None,
+            wasm: None,
+            rpd: None,
+            reset_ledger: false,
+            language,
+        };
+        let code = include_bytes!("../../../radix-transaction-scenarios/assets/radiswap.wasm");
+        let package_definition = manifest_decode(include_bytes!(
+            "../../../radix-transaction-scenarios/assets/radiswap.rpd"
+        ))
+        .unwrap();
+
+        let mut out = Vec::new();
+        generate_from_compiled_package(&mut out, &args, package_address, code, package_definition)
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    /// Compares the generated bindings against the expected ones. Setting the `UPDATE_BINDINGS`
+    /// environment variable overwrites the expected bindings instead.
+    fn assert_bindings_match(bindings: String, expected_path: &str) {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(expected_path);
+        if std::env::var("UPDATE_BINDINGS").is_ok() {
+            std::fs::write(&path, &bindings).unwrap();
+        }
+        let expected = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(bindings, expected);
+    }
+
+    #[test]
+    pub fn rust_bindings_match_expected() {
+        let package_address = local_package_address(CodeHash(Hash([1u8; Hash::LENGTH])));
+        let bindings = generate_radiswap_bindings(Language::Rust, Some(package_address));
+        syn::parse_file(&bindings).unwrap();
+        assert_bindings_match(bindings, "tests/bindgen/radiswap.rs");
+    }
+
+    #[test]
+    pub fn rust_bindings_without_package_address_match_expected() {
+        let bindings = generate_radiswap_bindings(Language::Rust, None);
+        syn::parse_file(&bindings).unwrap();
+        assert!(bindings.contains("PACKAGE_ADDRESS"));
+        assert_bindings_match(bindings, "tests/bindgen/radiswap_unpublished.rs");
+    }
+
+    #[test]
+    pub fn test_environment_bindings_match_expected() {
+        let bindings = generate_radiswap_bindings(Language::TestEnvironment, None);
+        syn::parse_file(&bindings).unwrap();
+        assert_bindings_match(bindings, "tests/bindgen/radiswap_test_environment.rs");
+    }
+
+    #[test]
+    pub fn typescript_bindings_match_expected() {
+        assert_bindings_match(
+            generate_radiswap_bindings(Language::Typescript, None),
+            "tests/bindgen/radiswap.ts",
+        );
+    }
+
+    #[test]
+    pub fn python_bindings_match_expected() {
+        assert_bindings_match(
+            generate_radiswap_bindings(Language::Python, None),
+            "tests/bindgen/radiswap.py",
+        );
     }
 }
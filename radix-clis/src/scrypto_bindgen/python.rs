@@ -0,0 +1,697 @@
+//! Generates Python bindings from the client model in `client.rs`.
+
+use super::client::*;
+use std::fmt::Write;
+
+const PRELUDE: &str = r#"#===================================================================================================
+# This file has been autogenerated by scrypto-bindgen and none of the contents here are
+# hand-written. Regenerate it whenever the interface of the package changes.
+#===================================================================================================
+
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, Generic, List, Optional, Tuple, TypeVar, Union
+
+T = TypeVar("T")
+E = TypeVar("E")
+
+# A value in the programmatic JSON representation, as returned by the Gateway and Core APIs.
+ProgrammaticValue = Dict[str, Any]
+
+
+@dataclass(frozen=True)
+class ManifestValue:
+    """A value in the textual manifest representation, along with its manifest value kind."""
+
+    kind: str
+    text: str
+
+
+@dataclass(frozen=True)
+class Ok(Generic[T]):
+    value: T
+
+
+@dataclass(frozen=True)
+class Err(Generic[E]):
+    value: E
+
+
+def _join_texts(values: List[ManifestValue]) -> str:
+    return ", ".join(value.text for value in values)
+
+
+def encode_any(value: Any) -> ManifestValue:
+    if not isinstance(value, ManifestValue):
+        raise TypeError("Only manifest values can be encoded")
+    return value
+
+
+def encode_bool(value: bool) -> ManifestValue:
+    return ManifestValue("Bool", "true" if value else "false")
+
+
+def encode_integer(kind: str, value: int) -> ManifestValue:
+    return ManifestValue(kind, f"{value}{kind.lower()}")
+
+
+def encode_string(value: str) -> ManifestValue:
+    return ManifestValue("String", json.dumps(value, ensure_ascii=False))
+
+
+def encode_decimal(value: str) -> ManifestValue:
+    return ManifestValue("Decimal", f'Decimal("{value}")')
+
+
+def encode_precise_decimal(value: str) -> ManifestValue:
+    return ManifestValue("PreciseDecimal", f'PreciseDecimal("{value}")')
+
+
+def encode_non_fungible_local_id(value: str) -> ManifestValue:
+    return ManifestValue("NonFungibleLocalId", f'NonFungibleLocalId("{value}")')
+
+
+def encode_address(value: str) -> ManifestValue:
+    return ManifestValue("Address", f'Address("{value}")')
+
+
+def encode_bucket(name: str) -> ManifestValue:
+    return ManifestValue("Bucket", f'Bucket("{name}")')
+
+
+def encode_proof(name: str) -> ManifestValue:
+    return ManifestValue("Proof", f'Proof("{name}")')
+
+
+def encode_address_reservation(name: str) -> ManifestValue:
+    return ManifestValue("AddressReservation", f'AddressReservation("{name}")')
+
+
+def encode_own(value: str) -> ManifestValue:
+    raise TypeError("Only buckets, proofs and address reservations can be passed by a manifest")
+
+
+def encode_bytes(value: bytes) -> ManifestValue:
+    return ManifestValue("Array", f'Bytes("{value.hex()}")')
+
+
+def encode_array(element_kind: Optional[str], elements: List[ManifestValue]) -> ManifestValue:
+    if element_kind is None:
+        element_kind = elements[0].kind if elements else "Tuple"
+    return ManifestValue("Array", f"Array<{element_kind}>({_join_texts(elements)})")
+
+
+def encode_map(
+    key_kind: Optional[str],
+    value_kind: Optional[str],
+    entries: List[Tuple[ManifestValue, ManifestValue]],
+) -> ManifestValue:
+    if key_kind is None:
+        key_kind = entries[0][0].kind if entries else "Tuple"
+    if value_kind is None:
+        value_kind = entries[0][1].kind if entries else "Tuple"
+    texts = ", ".join(f"{key.text} => {value.text}" for key, value in entries)
+    return ManifestValue("Map", f"Map<{key_kind}, {value_kind}>({texts})")
+
+
+def encode_tuple(fields: List[ManifestValue]) -> ManifestValue:
+    return ManifestValue("Tuple", f"Tuple({_join_texts(fields)})")
+
+
+def encode_enum(discriminator: int, fields: List[ManifestValue]) -> ManifestValue:
+    return ManifestValue("Enum", f"Enum<{discriminator}u8>({_join_texts(fields)})")
+
+
+def encode_option(value: Optional[T], encode: Callable[[T], ManifestValue]) -> ManifestValue:
+    return encode_enum(0, []) if value is None else encode_enum(1, [encode(value)])
+
+
+def encode_result(
+    value: Union[Ok[T], Err[E]],
+    encode_ok: Callable[[T], ManifestValue],
+    encode_err: Callable[[E], ManifestValue],
+) -> ManifestValue:
+    if isinstance(value, Ok):
+        return encode_enum(0, [encode_ok(value.value)])
+    return encode_enum(1, [encode_err(value.value)])
+
+
+def _expect_kind(value: ProgrammaticValue, kind: str) -> ProgrammaticValue:
+    if value["kind"] != kind:
+        raise ValueError(f"Expected a value of kind {kind} but found {value['kind']}")
+    return value
+
+
+def decode_any(value: ProgrammaticValue) -> Any:
+    return value
+
+
+def decode_bool(value: ProgrammaticValue) -> bool:
+    return _expect_kind(value, "Bool")["value"]
+
+
+def decode_integer(kind: str, value: ProgrammaticValue) -> int:
+    return int(_expect_kind(value, kind)["value"])
+
+
+def decode_string(value: ProgrammaticValue) -> str:
+    return _expect_kind(value, "String")["value"]
+
+
+def decode_decimal(value: ProgrammaticValue) -> str:
+    return _expect_kind(value, "Decimal")["value"]
+
+
+def decode_precise_decimal(value: ProgrammaticValue) -> str:
+    return _expect_kind(value, "PreciseDecimal")["value"]
+
+
+def decode_non_fungible_local_id(value: ProgrammaticValue) -> str:
+    return _expect_kind(value, "NonFungibleLocalId")["value"]
+
+
+def decode_reference(value: ProgrammaticValue) -> str:
+    return _expect_kind(value, "Reference")["value"]
+
+
+def decode_own(value: ProgrammaticValue) -> str:
+    return _expect_kind(value, "Own")["value"]
+
+
+def decode_bytes(value: ProgrammaticValue) -> bytes:
+    return bytes.fromhex(_expect_kind(value, "Bytes")["hex"])
+
+
+def decode_array(value: ProgrammaticValue) -> List[ProgrammaticValue]:
+    return _expect_kind(value, "Array")["elements"]
+
+
+def decode_map(value: ProgrammaticValue) -> List[Tuple[ProgrammaticValue, ProgrammaticValue]]:
+    return [(entry["key"], entry["value"]) for entry in _expect_kind(value, "Map")["entries"]]
+
+
+def decode_tuple(value: ProgrammaticValue) -> List[ProgrammaticValue]:
+    return _expect_kind(value, "Tuple")["fields"]
+
+
+def decode_enum(value: ProgrammaticValue) -> Tuple[int, List[ProgrammaticValue]]:
+    enum_value = _expect_kind(value, "Enum")
+    return int(enum_value["variant_id"]), enum_value["fields"]
+
+
+def decode_option(value: ProgrammaticValue, decode: Callable[[ProgrammaticValue], T]) -> Optional[T]:
+    variant_id, fields = decode_enum(value)
+    return None if variant_id == 0 else decode(fields[0])
+
+
+def decode_result(
+    value: ProgrammaticValue,
+    decode_ok: Callable[[ProgrammaticValue], T],
+    decode_err: Callable[[ProgrammaticValue], E],
+) -> Union[Ok[T], Err[E]]:
+    variant_id, fields = decode_enum(value)
+    return Ok(decode_ok(fields[0])) if variant_id == 0 else Err(decode_err(fields[0]))
+
+
+def _instruction(name: str, args: List[str]) -> str:
+    return "\n".join([name] + [f"    {arg}" for arg in args]) + "\n;\n"
+
+
+def call_method(address: str, method: str, args: List[ManifestValue]) -> str:
+    """Returns a `CALL_METHOD` manifest instruction."""
+    return _instruction(
+        "CALL_METHOD",
+        [f'Address("{address}")', json.dumps(method)] + [arg.text for arg in args],
+    )
+
+
+def call_function(
+    package_address: str, blueprint_name: str, function_name: str, args: List[ManifestValue]
+) -> str:
+    """Returns a `CALL_FUNCTION` manifest instruction."""
+    return _instruction(
+        "CALL_FUNCTION",
+        [f'Address("{package_address}")', json.dumps(blueprint_name), json.dumps(function_name)]
+        + [arg.text for arg in args],
+    )
+"#;
+
+/// Words which can't be used as identifiers in Python.
+const RESERVED_WORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "self", "try",
+    "while", "with", "yield",
+];
+
+pub fn generate(package: &ClientPackage) -> Result<String, std::fmt::Error> {
+    let mut out = String::from(PRELUDE);
+
+    for type_definition in &package.type_definitions {
+        writeln!(out)?;
+        writeln!(out)?;
+        write_type_definition(&mut out, package, type_definition)?;
+    }
+    for blueprint in &package.blueprints {
+        writeln!(out)?;
+        writeln!(out)?;
+        write_blueprint(&mut out, package, blueprint)?;
+    }
+
+    Ok(out)
+}
+
+fn write_type_definition(
+    out: &mut String,
+    package: &ClientPackage,
+    type_definition: &ClientTypeDefinition,
+) -> std::fmt::Result {
+    match type_definition {
+        ClientTypeDefinition::Struct { name, fields } => {
+            writeln!(out, "@dataclass(frozen=True)")?;
+            writeln!(out, "class {name}:")?;
+            write_fields_class_body(out, package, name, fields, None)?;
+        }
+        ClientTypeDefinition::Enum { name, variants } => {
+            writeln!(out, "class {name}:")?;
+            writeln!(
+                out,
+                "    \"\"\"The base class of the variants of `{name}`.\"\"\""
+            )?;
+            writeln!(out)?;
+            writeln!(out, "    def encode(self) -> ManifestValue:")?;
+            writeln!(out, "        raise NotImplementedError")?;
+            writeln!(out)?;
+            writeln!(out, "    @staticmethod")?;
+            writeln!(out, "    def decode(value: ProgrammaticValue) -> {name}:")?;
+            writeln!(out, "        variant_id, fields = decode_enum(value)")?;
+            for variant in variants {
+                writeln!(out, "        if variant_id == {}:", variant.variant_index)?;
+                writeln!(
+                    out,
+                    "            return {}",
+                    construct_expression(
+                        &variant_class_name(name, variant),
+                        &variant.fields,
+                        "fields"
+                    )
+                )?;
+            }
+            writeln!(
+                out,
+                "        raise ValueError(f\"Unknown variant {{variant_id}} of {name}\")"
+            )?;
+
+            for variant in variants {
+                let variant_class_name = variant_class_name(name, variant);
+                writeln!(out)?;
+                writeln!(out)?;
+                writeln!(out, "@dataclass(frozen=True)")?;
+                writeln!(out, "class {variant_class_name}({name}):")?;
+                write_fields_class_body(
+                    out,
+                    package,
+                    &variant_class_name,
+                    &variant.fields,
+                    Some(variant.variant_index),
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Writes the fields and the `encode` and `decode` methods of a struct or an enum variant.
+fn write_fields_class_body(
+    out: &mut String,
+    package: &ClientPackage,
+    class_name: &str,
+    fields: &ClientFields,
+    variant_index: Option<u8>,
+) -> std::fmt::Result {
+    let fields = python_fields(fields);
+    for (field_name, field_type) in &fields {
+        writeln!(out, "    {field_name}: {}", type_name(field_type))?;
+    }
+    if !fields.is_empty() {
+        writeln!(out)?;
+    }
+
+    let encoded_fields = fields
+        .iter()
+        .map(|(field_name, field_type)| {
+            encode_expression(package, field_type, &format!("self.{field_name}"))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    writeln!(out, "    def encode(self) -> ManifestValue:")?;
+    match variant_index {
+        Some(variant_index) => writeln!(
+            out,
+            "        return encode_enum({variant_index}, [{encoded_fields}])"
+        )?,
+        None => writeln!(out, "        return encode_tuple([{encoded_fields}])")?,
+    }
+
+    // Variants are decoded by the base class of the enum
+    if variant_index.is_none() {
+        let decoded_fields = fields
+            .iter()
+            .enumerate()
+            .map(|(index, (_, field_type))| {
+                decode_expression(field_type, &format!("fields[{index}]"))
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(out)?;
+        writeln!(out, "    @staticmethod")?;
+        writeln!(
+            out,
+            "    def decode(value: ProgrammaticValue) -> {class_name}:"
+        )?;
+        writeln!(out, "        fields = decode_tuple(value)")?;
+        writeln!(out, "        return {class_name}({decoded_fields})")?;
+    }
+    Ok(())
+}
+
+fn write_blueprint(
+    out: &mut String,
+    package: &ClientPackage,
+    blueprint: &ClientBlueprint,
+) -> std::fmt::Result {
+    let blueprint_name = &blueprint.blueprint_name;
+
+    writeln!(out, "class {blueprint_name}Functions:")?;
+    writeln!(
+        out,
+        "    \"\"\"The functions of the `{blueprint_name}` blueprint.\"\"\""
+    )?;
+    writeln!(out)?;
+    match &package.package_address {
+        Some(package_address) => writeln!(
+            out,
+            "    def __init__(self, package_address: str = \"{package_address}\"):"
+        )?,
+        None => writeln!(out, "    def __init__(self, package_address: str):")?,
+    }
+    writeln!(out, "        self.package_address = package_address")?;
+    for function in blueprint.functions() {
+        writeln!(out)?;
+        write_function(
+            out,
+            package,
+            function,
+            &format!(
+                "call_function(self.package_address, \"{blueprint_name}\", \"{}\"",
+                function.ident
+            ),
+        )?;
+    }
+
+    writeln!(out)?;
+    writeln!(out)?;
+    writeln!(out, "class {blueprint_name}Component:")?;
+    writeln!(
+        out,
+        "    \"\"\"The methods of a `{blueprint_name}` component.\"\"\""
+    )?;
+    writeln!(out)?;
+    writeln!(out, "    def __init__(self, address: str):")?;
+    writeln!(out, "        self.address = address")?;
+    for method in blueprint.methods() {
+        writeln!(out)?;
+        write_function(
+            out,
+            package,
+            method,
+            &format!("call_method(self.address, \"{}\"", method.ident),
+        )?;
+    }
+
+    for (kind, named_types) in [
+        ("EVENTS", &blueprint.events),
+        ("REGISTERED_TYPES", &blueprint.registered_types),
+    ] {
+        if named_types.is_empty() {
+            continue;
+        }
+        writeln!(out)?;
+        writeln!(out)?;
+        writeln!(
+            out,
+            "{}_{kind}: Dict[str, Callable[[ProgrammaticValue], Any]] = {{",
+            to_upper_snake_case(blueprint_name)
+        )?;
+        for (name, client_type) in named_types {
+            writeln!(
+                out,
+                "    \"{name}\": lambda value: {},",
+                decode_expression(client_type, "value")
+            )?;
+        }
+        writeln!(out, "}}")?;
+    }
+
+    Ok(())
+}
+
+fn write_function(
+    out: &mut String,
+    package: &ClientPackage,
+    function: &ClientFunction,
+    call_prefix: &str,
+) -> std::fmt::Result {
+    let parameters = function
+        .arguments
+        .iter()
+        .map(|(name, client_type)| format!(", {}: {}", identifier(name), type_name(client_type)))
+        .collect::<String>();
+    let arguments = function
+        .arguments
+        .iter()
+        .map(|(name, client_type)| encode_expression(package, client_type, &identifier(name)))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    writeln!(
+        out,
+        "    def {}(self{parameters}) -> str:",
+        identifier(&function.ident)
+    )?;
+    writeln!(
+        out,
+        "        \"\"\"Returns the manifest instruction calling `{}`.\"\"\"",
+        function.ident
+    )?;
+    writeln!(out, "        return {call_prefix}, [{arguments}])")?;
+    writeln!(out)?;
+    writeln!(out, "    @staticmethod")?;
+    writeln!(
+        out,
+        "    def decode_{}_output(value: ProgrammaticValue) -> {}:",
+        function.ident,
+        type_name(&function.returns)
+    )?;
+    writeln!(
+        out,
+        "        return {}",
+        decode_expression(&function.returns, "value")
+    )
+}
+
+fn identifier(name: &str) -> String {
+    if RESERVED_WORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_owned()
+    }
+}
+
+fn variant_class_name(enum_name: &str, variant: &ClientEnumVariant) -> String {
+    format!("{enum_name}_{}", variant.variant_name)
+}
+
+/// The fields of a dataclass, where unnamed fields are named after their position.
+fn python_fields(fields: &ClientFields) -> Vec<(String, &ClientType)> {
+    match fields {
+        ClientFields::Unnamed(field_types) => field_types
+            .iter()
+            .enumerate()
+            .map(|(index, field_type)| (format!("field_{index}"), field_type))
+            .collect(),
+        ClientFields::Named(fields) => fields
+            .iter()
+            .map(|(field_name, field_type)| (identifier(field_name), field_type))
+            .collect(),
+    }
+}
+
+fn construct_expression(class_name: &str, fields: &ClientFields, decoded_fields: &str) -> String {
+    let arguments = python_fields(fields)
+        .iter()
+        .enumerate()
+        .map(|(index, (_, field_type))| {
+            decode_expression(field_type, &format!("{decoded_fields}[{index}]"))
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("{class_name}({arguments})")
+}
+
+fn type_name(client_type: &ClientType) -> String {
+    match client_type {
+        ClientType::Any => "Any".to_owned(),
+        ClientType::Bool => "bool".to_owned(),
+        ClientType::Integer(..) => "int".to_owned(),
+        ClientType::String
+        | ClientType::Decimal
+        | ClientType::PreciseDecimal
+        | ClientType::NonFungibleLocalId
+        | ClientType::Address
+        | ClientType::Bucket
+        | ClientType::Proof
+        | ClientType::AddressReservation
+        | ClientType::Own => "str".to_owned(),
+        ClientType::Bytes => "bytes".to_owned(),
+        ClientType::Array(element_type) => format!("List[{}]", type_name(element_type)),
+        ClientType::Map(key_type, value_type) => format!(
+            "List[Tuple[{}, {}]]",
+            type_name(key_type),
+            type_name(value_type)
+        ),
+        ClientType::Tuple(field_types) if field_types.is_empty() => "Tuple[()]".to_owned(),
+        ClientType::Tuple(field_types) => format!(
+            "Tuple[{}]",
+            field_types
+                .iter()
+                .map(type_name)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ClientType::Option(some_type) => format!("Optional[{}]", type_name(some_type)),
+        ClientType::Result(ok_type, err_type) => format!(
+            "Union[Ok[{}], Err[{}]]",
+            type_name(ok_type),
+            type_name(err_type)
+        ),
+        ClientType::Named(name) => name.clone(),
+    }
+}
+
+fn quoted_value_kind(package: &ClientPackage, client_type: &ClientType) -> String {
+    match package.manifest_value_kind(client_type) {
+        Some(value_kind) => format!("\"{value_kind}\""),
+        None => "None".to_owned(),
+    }
+}
+
+/// Returns an expression encoding the value of the given expression as a `ManifestValue`.
+fn encode_expression(package: &ClientPackage, client_type: &ClientType, value: &str) -> String {
+    match client_type {
+        ClientType::Any => format!("encode_any({value})"),
+        ClientType::Bool => format!("encode_bool({value})"),
+        ClientType::Integer(integer_kind) => {
+            format!("encode_integer(\"{}\", {value})", integer_kind.value_kind())
+        }
+        ClientType::String => format!("encode_string({value})"),
+        ClientType::Decimal => format!("encode_decimal({value})"),
+        ClientType::PreciseDecimal => format!("encode_precise_decimal({value})"),
+        ClientType::NonFungibleLocalId => format!("encode_non_fungible_local_id({value})"),
+        ClientType::Address => format!("encode_address({value})"),
+        ClientType::Bucket => format!("encode_bucket({value})"),
+        ClientType::Proof => format!("encode_proof({value})"),
+        ClientType::AddressReservation => format!("encode_address_reservation({value})"),
+        ClientType::Own => format!("encode_own({value})"),
+        ClientType::Bytes => format!("encode_bytes({value})"),
+        ClientType::Array(element_type) => format!(
+            "encode_array({}, [{} for element in {value}])",
+            quoted_value_kind(package, element_type),
+            encode_expression(package, element_type, "element")
+        ),
+        ClientType::Map(key_type, value_type) => format!(
+            "encode_map({}, {}, [({}, {}) for key, value in {value}])",
+            quoted_value_kind(package, key_type),
+            quoted_value_kind(package, value_type),
+            encode_expression(package, key_type, "key"),
+            encode_expression(package, value_type, "value")
+        ),
+        ClientType::Tuple(field_types) => format!(
+            "encode_tuple([{}])",
+            field_types
+                .iter()
+                .enumerate()
+                .map(|(index, field_type)| {
+                    encode_expression(package, field_type, &format!("{value}[{index}]"))
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ClientType::Option(some_type) => format!(
+            "encode_option({value}, lambda some: {})",
+            encode_expression(package, some_type, "some")
+        ),
+        ClientType::Result(ok_type, err_type) => format!(
+            "encode_result({value}, lambda ok: {}, lambda err: {})",
+            encode_expression(package, ok_type, "ok"),
+            encode_expression(package, err_type, "err")
+        ),
+        ClientType::Named(..) => format!("{value}.encode()"),
+    }
+}
+
+/// Returns an expression decoding the `ProgrammaticValue` of the given expression.
+fn decode_expression(client_type: &ClientType, value: &str) -> String {
+    match client_type {
+        ClientType::Any => format!("decode_any({value})"),
+        ClientType::Bool => format!("decode_bool({value})"),
+        ClientType::Integer(integer_kind) => {
+            format!("decode_integer(\"{}\", {value})", integer_kind.value_kind())
+        }
+        ClientType::String => format!("decode_string({value})"),
+        ClientType::Decimal => format!("decode_decimal({value})"),
+        ClientType::PreciseDecimal => format!("decode_precise_decimal({value})"),
+        ClientType::NonFungibleLocalId => format!("decode_non_fungible_local_id({value})"),
+        ClientType::Address => format!("decode_reference({value})"),
+        ClientType::Bucket
+        | ClientType::Proof
+        | ClientType::AddressReservation
+        | ClientType::Own => format!("decode_own({value})"),
+        ClientType::Bytes => format!("decode_bytes({value})"),
+        ClientType::Array(element_type) => format!(
+            "[{} for element in decode_array({value})]",
+            decode_expression(element_type, "element")
+        ),
+        ClientType::Map(key_type, value_type) => format!(
+            "[({}, {}) for key, value in decode_map({value})]",
+            decode_expression(key_type, "key"),
+            decode_expression(value_type, "value")
+        ),
+        ClientType::Tuple(field_types) => format!(
+            "(lambda fields: ({}))(decode_tuple({value}))",
+            field_types
+                .iter()
+                .enumerate()
+                .map(|(index, field_type)| {
+                    format!(
+                        "{},",
+                        decode_expression(field_type, &format!("fields[{index}]"))
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        ),
+        ClientType::Option(some_type) => format!(
+            "decode_option({value}, lambda some: {})",
+            decode_expression(some_type, "some")
+        ),
+        ClientType::Result(ok_type, err_type) => format!(
+            "decode_result({value}, lambda ok: {}, lambda err: {})",
+            decode_expression(ok_type, "ok"),
+            decode_expression(err_type, "err")
+        ),
+        ClientType::Named(name) => format!("{name}.decode({value})"),
+    }
+}
@@ -10,21 +10,148 @@ pub trait PackageSchemaResolver {
     fn resolve_type_kind(
         &self,
         type_identifier: &ScopedTypeId,
-    ) -> Result<SchemaTypeKind<ScryptoCustomSchema>, SchemaError>;
+    ) -> Result<SchemaTypeKind<ScryptoCustomSchema>, SchemaError> {
+        self.lookup_schema(&type_identifier.0)
+            .ok_or(SchemaError::FailedToGetSchemaFromSchemaHash)?
+            .as_latest_version()
+            .ok_or(SchemaError::FailedToGetSchemaFromSchemaHash)?
+            .resolve_type_kind(type_identifier.1)
+            .ok_or(SchemaError::NonExistentLocalTypeIndex(type_identifier.1))
+            .cloned()
+    }
 
     fn resolve_type_metadata(
         &self,
         type_identifier: &ScopedTypeId,
-    ) -> Result<TypeMetadata, SchemaError>;
+    ) -> Result<TypeMetadata, SchemaError> {
+        self.lookup_schema(&type_identifier.0)
+            .ok_or(SchemaError::FailedToGetSchemaFromSchemaHash)?
+            .as_latest_version()
+            .ok_or(SchemaError::FailedToGetSchemaFromSchemaHash)?
+            .resolve_type_metadata(type_identifier.1)
+            .ok_or(SchemaError::NonExistentLocalTypeIndex(type_identifier.1))
+            .cloned()
+    }
 
     fn resolve_type_validation(
         &self,
         type_identifier: &ScopedTypeId,
-    ) -> Result<TypeValidation<ScryptoCustomTypeValidation>, SchemaError>;
+    ) -> Result<TypeValidation<ScryptoCustomTypeValidation>, SchemaError> {
+        self.lookup_schema(&type_identifier.0)
+            .ok_or(SchemaError::FailedToGetSchemaFromSchemaHash)?
+            .as_latest_version()
+            .ok_or(SchemaError::FailedToGetSchemaFromSchemaHash)?
+            .resolve_type_validation(type_identifier.1)
+            .ok_or(SchemaError::NonExistentLocalTypeIndex(type_identifier.1))
+            .cloned()
+    }
 
     fn package_address(&self) -> PackageAddress;
 }
 
+/// Converts the [`PackageDefinition`] produced by the Scrypto compiler (the `.rpd` file) into the
+/// blueprint definitions and schemas that the package would have once published, without needing
+/// a ledger. This mirrors what the package blueprint does when a WASM package is published.
+pub fn blueprint_definitions_from_package_definition(
+    package_definition: PackageDefinition,
+    code_hash: CodeHash,
+) -> (
+    BTreeMap<BlueprintVersionKey, BlueprintDefinition>,
+    IndexMap<SchemaHash, VersionedScryptoSchema>,
+) {
+    let mut definitions = BTreeMap::new();
+    let mut schemas = index_map_new();
+
+    for (blueprint, definition_init) in package_definition.blueprints {
+        let schema_hash = definition_init.schema.schema.generate_schema_hash();
+        schemas.insert(schema_hash, definition_init.schema.schema);
+
+        let mut functions = index_map_new();
+        let mut function_exports = index_map_new();
+        for (function, function_schema_init) in definition_init.schema.functions.functions {
+            functions.insert(
+                function.clone(),
+                FunctionSchema {
+                    receiver: function_schema_init.receiver,
+                    input: BlueprintPayloadDef::from_type_ref(
+                        function_schema_init.input,
+                        schema_hash,
+                    ),
+                    output: BlueprintPayloadDef::from_type_ref(
+                        function_schema_init.output,
+                        schema_hash,
+                    ),
+                },
+            );
+            function_exports.insert(
+                function,
+                PackageExport {
+                    code_hash,
+                    export_name: function_schema_init.export,
+                },
+            );
+        }
+
+        let events = definition_init
+            .schema
+            .events
+            .event_schema
+            .into_iter()
+            .map(|(key, type_ref)| {
+                (
+                    key,
+                    BlueprintPayloadDef::from_type_ref(type_ref, schema_hash),
+                )
+            })
+            .collect();
+
+        let types = definition_init
+            .schema
+            .types
+            .type_schema
+            .into_iter()
+            .map(|(key, local_type_id)| (key, ScopedTypeId(schema_hash, local_type_id)))
+            .collect();
+
+        let definition = BlueprintDefinition {
+            interface: radix_engine_interface::blueprints::package::BlueprintInterface {
+                blueprint_type: definition_init.blueprint_type,
+                is_transient: definition_init.is_transient,
+                generics: definition_init.schema.generics,
+                feature_set: definition_init.feature_set,
+                functions,
+                events,
+                types,
+                // WASM packages have no system instructions, so all collections are logical.
+                state: IndexedStateSchema::from_schema(
+                    schema_hash,
+                    definition_init.schema.state,
+                    index_map_new(),
+                ),
+            },
+            function_exports,
+            hook_exports: definition_init
+                .schema
+                .hooks
+                .hooks
+                .into_iter()
+                .map(|(hook, export_name)| {
+                    (
+                        hook,
+                        PackageExport {
+                            code_hash,
+                            export_name,
+                        },
+                    )
+                })
+                .collect(),
+        };
+        definitions.insert(BlueprintVersionKey::new_default(blueprint), definition);
+    }
+
+    (definitions, schemas)
+}
+
 pub fn package_interface_from_package_definition<S>(
     package_definition: BTreeMap<BlueprintVersionKey, BlueprintDefinition>,
     schema_resolver: &S,
@@ -45,11 +172,28 @@ where
             }
         }
 
-        let functions = &mut package_interface
+        let blueprint_interface = package_interface
             .blueprints
             .entry(blueprint_name)
-            .or_default()
-            .functions;
+            .or_default();
+
+        // Events and registered types (e.g. non-fungible data) are part of the interface too, even
+        // if they do not appear in any function. Only the client bindings make use of them, so the
+        // types they refer to are not added to the auxiliary types.
+        for (event_name, event_payload) in blueprint_definition.interface.events {
+            // Events with a generic payload can't be described without the instance's generics
+            let BlueprintPayloadDef::Static(event_type_identifier) = event_payload else {
+                continue;
+            };
+            blueprint_interface
+                .events
+                .insert(event_name, event_type_identifier);
+        }
+        blueprint_interface
+            .registered_types
+            .extend(blueprint_definition.interface.types);
+
+        let functions = &mut blueprint_interface.functions;
 
         for (function_name, function_schema) in blueprint_definition.interface.functions {
             let BlueprintPayloadDef::Static(input_type_identifier) = &function_schema.input else {
@@ -147,6 +291,10 @@ pub struct PackageInterface {
 pub struct BlueprintInterface {
     /// The functions and methods encountered in the blueprint interface.
     pub functions: Vec<Function>,
+    /// The events emitted by the blueprint, keyed by the event name.
+    pub events: IndexMap<String, ScopedTypeId>,
+    /// The types registered by the blueprint, such as its non-fungible data, keyed by their name.
+    pub registered_types: IndexMap<String, ScopedTypeId>,
 }
 
 #[derive(Clone, Debug)]
@@ -157,7 +305,7 @@ pub struct Function {
     pub returns: ScopedTypeId,
 }
 
-fn get_scoped_type_ids_in_path<S>(
+pub fn get_scoped_type_ids_in_path<S>(
     type_id: &ScopedTypeId,
     schema_resolver: &S,
     collection: &mut HashSet<ScopedTypeId>,
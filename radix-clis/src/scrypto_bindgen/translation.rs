@@ -18,9 +18,19 @@ const PACKAGES_WITH_BINDINGS: &'static [PackageAddress] = &[
     LOCKER_PACKAGE,
 ];
 
+/// The crate that the generated bindings are used from, which determines how some types are named.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BindingTarget {
+    /// Blueprints calling the package through stubs generated by `extern_blueprint_internal!`.
+    Scrypto,
+    /// Tests calling the package through the `TestEnvironment` of `scrypto-test`, where there are
+    /// no `Global<T>` and `Own<T>` wrappers for blueprints.
+    ScryptoTest,
+}
+
 pub fn package_schema_interface_to_ast_interface<S>(
     schema_interface: schema::PackageInterface,
-    package_address: PackageAddress,
+    package_address: Option<PackageAddress>,
     schema_resolver: &S,
 ) -> Result<ast::PackageStub, schema::SchemaError>
 where
@@ -42,13 +52,50 @@ where
         auxiliary_types: schema_auxiliary_types_to_ast_types(
             schema_interface.auxiliary_types,
             schema_resolver,
+            BindingTarget::Scrypto,
+        )?,
+    })
+}
+
+pub fn package_schema_interface_to_test_environment_interface<S>(
+    schema_interface: schema::PackageInterface,
+    schema_resolver: &S,
+) -> Result<ast::TestEnvironmentPackageStub, schema::SchemaError>
+where
+    S: schema::PackageSchemaResolver,
+{
+    Ok(ast::TestEnvironmentPackageStub {
+        blueprints: schema_interface
+            .blueprints
+            .into_iter()
+            .map(|(blueprint_name, blueprint_interface)| {
+                Ok(ast::TestEnvironmentBlueprintStub {
+                    fn_signatures: blueprint_interface
+                        .functions
+                        .into_iter()
+                        .map(|func| {
+                            function_schema_interface_to_ast_interface(
+                                func,
+                                schema_resolver,
+                                BindingTarget::ScryptoTest,
+                            )
+                        })
+                        .collect::<Result<_, _>>()?,
+                    blueprint_name,
+                })
+            })
+            .collect::<Result<_, _>>()?,
+        auxiliary_types: schema_auxiliary_types_to_ast_types(
+            schema_interface.auxiliary_types,
+            schema_resolver,
+            BindingTarget::ScryptoTest,
         )?,
     })
 }
 
 pub fn blueprint_schema_interface_to_ast_interface<S>(
     schema_interface: schema::BlueprintInterface,
-    package_address: PackageAddress,
+    package_address: Option<PackageAddress>,
     blueprint_name: String,
     schema_resolver: &S,
 ) -> Result<ast::BlueprintStub, schema::SchemaError>
@@ -59,7 +106,13 @@ where
         fn_signatures: schema_interface
             .functions
             .into_iter()
-            .map(|func| function_schema_interface_to_ast_interface(func, schema_resolver))
+            .map(|func| {
+                function_schema_interface_to_ast_interface(
+                    func,
+                    schema_resolver,
+                    BindingTarget::Scrypto,
+                )
+            })
             .collect::<Result<_, _>>()?,
         blueprint_name,
         package_address,
@@ -69,6 +122,7 @@ where
 pub fn function_schema_interface_to_ast_interface<S>(
     schema_interface: schema::Function,
     schema_resolver: &S,
+    target: BindingTarget,
 ) -> Result<ast::FnSignature, schema::SchemaError>
 where
     S: schema::PackageSchemaResolver,
@@ -95,11 +149,15 @@ where
         .arguments
         .into_iter()
         .map(|(arg_name, arg_type_index)| {
-            type_name(&arg_type_index, schema_resolver)
+            type_name(&arg_type_index, schema_resolver, target)
                 .map(|type_name| (ident!(&arg_name), token_stream_from_str!(&type_name)))
         })
         .collect::<Result<_, _>>()?;
-    let output = token_stream_from_str!(&type_name(&schema_interface.returns, schema_resolver)?);
+    let output = token_stream_from_str!(&type_name(
+        &schema_interface.returns,
+        schema_resolver,
+        target
+    )?);
 
     Ok(ast::FnSignature {
         inputs,
@@ -112,6 +170,7 @@ where
 fn type_name<S>(
     type_identifier: &ScopedTypeId,
     schema_resolver: &S,
+    target: BindingTarget,
 ) -> Result<String, schema::SchemaError>
 where
     S: schema::PackageSchemaResolver,
@@ -140,6 +199,7 @@ where
             type_name(
                 &ScopedTypeId(type_identifier.0, element_type),
                 schema_resolver,
+                target,
             )?
         )),
         TypeKind::Tuple { field_types } => {
@@ -152,6 +212,7 @@ where
                         .map(|local_type_index| type_name(
                             &ScopedTypeId(type_identifier.0, *local_type_index),
                             schema_resolver,
+                            target,
                         ))
                         .collect::<Result<Vec<String>, _>>()?
                         .join(", ")
@@ -174,6 +235,7 @@ where
                     type_name(
                         &ScopedTypeId(type_identifier.0, *some_type_index),
                         schema_resolver,
+                        target,
                     )?
                 )),
                 (Some("Result"), 2usize, Some([ok_type_index]), Some([err_type_index])) => {
@@ -182,10 +244,12 @@ where
                         type_name(
                             &ScopedTypeId(type_identifier.0, *ok_type_index),
                             schema_resolver,
+                            target,
                         )?,
                         type_name(
                             &ScopedTypeId(type_identifier.0, *err_type_index),
                             schema_resolver,
+                            target,
                         )?
                     ))
                 }
@@ -198,10 +262,15 @@ where
             value_type,
         } => metadata_type_name.unwrap_or(format!(
             "IndexMap<{}, {}>",
-            type_name(&ScopedTypeId(type_identifier.0, key_type), schema_resolver,)?,
+            type_name(
+                &ScopedTypeId(type_identifier.0, key_type),
+                schema_resolver,
+                target
+            )?,
             type_name(
                 &ScopedTypeId(type_identifier.0, value_type),
                 schema_resolver,
+                target,
             )?
         )),
         TypeKind::Custom(custom_type_kind) => match custom_type_kind {
@@ -214,6 +283,11 @@ where
                     ReferenceValidation::IsGlobalPackage => "PackageAddress".to_owned(),
                     ReferenceValidation::IsGlobalComponent => "ComponentAddress".to_owned(),
                     ReferenceValidation::IsGlobalResourceManager => "ResourceAddress".to_owned(),
+                    ReferenceValidation::IsGlobalTyped(_, _)
+                        if target == BindingTarget::ScryptoTest =>
+                    {
+                        "ComponentAddress".to_owned()
+                    }
                     ReferenceValidation::IsGlobalTyped(package_address, blueprint_name) => {
                         let this_package_address = schema_resolver.package_address();
                         if package_address.is_none()
@@ -260,6 +334,11 @@ where
                         OwnValidation::IsGlobalAddressReservation => {
                             metadata_type_name.unwrap_or("GlobalAddressReservation".to_owned())
                         }
+                        OwnValidation::IsTypedObject(_, _)
+                            if target == BindingTarget::ScryptoTest =>
+                        {
+                            "Own".to_owned()
+                        }
                         OwnValidation::IsTypedObject(package_address, blueprint_name) => {
                             let this_package_address = schema_resolver.package_address();
                             if package_address.is_none()
@@ -308,6 +387,7 @@ where
 pub fn schema_auxiliary_types_to_ast_types<S>(
     auxiliary_types: HashSet<ScopedTypeId>,
     schema_resolver: &S,
+    target: BindingTarget,
 ) -> Result<Vec<ast::AuxiliaryType>, schema::SchemaError>
 where
     S: schema::PackageSchemaResolver,
@@ -349,6 +429,7 @@ where
                                 let field_type_name = type_name(
                                     &ScopedTypeId(scoped_type_id.0, field_type),
                                     schema_resolver,
+                                    target,
                                 )?;
 
                                 Ok((field_name.as_ref().into(), field_type_name))
@@ -364,6 +445,7 @@ where
                                 type_name(
                                     &ScopedTypeId(scoped_type_id.0, *field_type),
                                     schema_resolver,
+                                    target,
                                 )
                             })
                             .collect::<Result<_, _>>()?,
@@ -419,6 +501,7 @@ where
                                         let field_type_name = type_name(
                                             &ScopedTypeId(scoped_type_id.0, *field_type),
                                             schema_resolver,
+                                            target,
                                         )?;
 
                                         Ok((field_name.as_ref().into(), field_type_name))
@@ -435,6 +518,7 @@ where
                                         type_name(
                                             &ScopedTypeId(scoped_type_id.0, *field_type),
                                             schema_resolver,
+                                            target,
                                         )
                                     })
                                     .collect::<Result<_, _>>()?,
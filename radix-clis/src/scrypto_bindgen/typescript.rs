@@ -0,0 +1,766 @@
+//! Generates TypeScript bindings from the client model in `client.rs`.
+
+use super::client::*;
+use std::fmt::Write;
+
+const PRELUDE: &str = r#"//==================================================================================================
+// This file has been autogenerated by scrypto-bindgen and none of the contents here are
+// hand-written. Regenerate it whenever the interface of the package changes.
+//==================================================================================================
+
+/** A value in the textual manifest representation, along with its manifest value kind. */
+export interface ManifestValue {
+  kind: string;
+  text: string;
+}
+
+/** A value in the programmatic JSON representation, as returned by the Gateway and Core APIs. */
+export interface ProgrammaticValue {
+  kind: string;
+  [field: string]: any;
+}
+
+export type AnyValue = ManifestValue | ProgrammaticValue;
+
+export type Result<T, E> = { Ok: T } | { Err: E };
+
+const manifestValue = (kind: string, text: string): ManifestValue => ({ kind, text });
+
+const joinTexts = (values: ManifestValue[]): string => values.map((value) => value.text).join(", ");
+
+export const encodeAny = (value: AnyValue): ManifestValue => {
+  if (!("text" in value)) {
+    throw new Error("Only manifest values can be encoded");
+  }
+  return value as ManifestValue;
+};
+export const encodeBool = (value: boolean): ManifestValue =>
+  manifestValue("Bool", value ? "true" : "false");
+export const encodeInteger = (kind: string, value: number | bigint): ManifestValue =>
+  manifestValue(kind, `${value}${kind.toLowerCase()}`);
+export const encodeString = (value: string): ManifestValue =>
+  manifestValue("String", JSON.stringify(value));
+export const encodeDecimal = (value: string): ManifestValue =>
+  manifestValue("Decimal", `Decimal("${value}")`);
+export const encodePreciseDecimal = (value: string): ManifestValue =>
+  manifestValue("PreciseDecimal", `PreciseDecimal("${value}")`);
+export const encodeNonFungibleLocalId = (value: string): ManifestValue =>
+  manifestValue("NonFungibleLocalId", `NonFungibleLocalId("${value}")`);
+export const encodeAddress = (value: string): ManifestValue =>
+  manifestValue("Address", `Address("${value}")`);
+export const encodeBucket = (name: string): ManifestValue =>
+  manifestValue("Bucket", `Bucket("${name}")`);
+export const encodeProof = (name: string): ManifestValue =>
+  manifestValue("Proof", `Proof("${name}")`);
+export const encodeAddressReservation = (name: string): ManifestValue =>
+  manifestValue("AddressReservation", `AddressReservation("${name}")`);
+export const encodeOwn = (_value: string): ManifestValue => {
+  throw new Error("Only buckets, proofs and address reservations can be passed by a manifest");
+};
+export const encodeBytes = (hex: string): ManifestValue =>
+  manifestValue("Array", `Bytes("${hex}")`);
+export const encodeArray = (
+  elementKind: string | undefined,
+  elements: ManifestValue[]
+): ManifestValue =>
+  manifestValue(
+    "Array",
+    `Array<${elementKind ?? elements[0]?.kind ?? "Tuple"}>(${joinTexts(elements)})`
+  );
+export const encodeMap = (
+  keyKind: string | undefined,
+  valueKind: string | undefined,
+  entries: [ManifestValue, ManifestValue][]
+): ManifestValue =>
+  manifestValue(
+    "Map",
+    `Map<${keyKind ?? entries[0]?.[0].kind ?? "Tuple"}, ${
+      valueKind ?? entries[0]?.[1].kind ?? "Tuple"
+    }>(${entries.map(([key, value]) => `${key.text} => ${value.text}`).join(", ")})`
+  );
+export const encodeTuple = (fields: ManifestValue[]): ManifestValue =>
+  manifestValue("Tuple", `Tuple(${joinTexts(fields)})`);
+export const encodeEnum = (discriminator: number, fields: ManifestValue[]): ManifestValue =>
+  manifestValue("Enum", `Enum<${discriminator}u8>(${joinTexts(fields)})`);
+export const encodeOption = <T>(
+  value: T | null,
+  encode: (value: T) => ManifestValue
+): ManifestValue => (value === null ? encodeEnum(0, []) : encodeEnum(1, [encode(value)]));
+export const encodeResult = <T, E>(
+  value: Result<T, E>,
+  encodeOk: (value: T) => ManifestValue,
+  encodeErr: (value: E) => ManifestValue
+): ManifestValue =>
+  "Ok" in value ? encodeEnum(0, [encodeOk(value.Ok)]) : encodeEnum(1, [encodeErr(value.Err)]);
+
+const expectKind = (value: ProgrammaticValue, kind: string): ProgrammaticValue => {
+  if (value.kind !== kind) {
+    throw new Error(`Expected a value of kind ${kind} but found ${value.kind}`);
+  }
+  return value;
+};
+
+export const decodeAny = (value: ProgrammaticValue): AnyValue => value;
+export const decodeBool = (value: ProgrammaticValue): boolean => expectKind(value, "Bool").value;
+export const decodeInteger = (kind: string, value: ProgrammaticValue): number =>
+  Number(expectKind(value, kind).value);
+export const decodeBigInteger = (kind: string, value: ProgrammaticValue): bigint =>
+  BigInt(expectKind(value, kind).value);
+export const decodeString = (value: ProgrammaticValue): string =>
+  expectKind(value, "String").value;
+export const decodeDecimal = (value: ProgrammaticValue): string =>
+  expectKind(value, "Decimal").value;
+export const decodePreciseDecimal = (value: ProgrammaticValue): string =>
+  expectKind(value, "PreciseDecimal").value;
+export const decodeNonFungibleLocalId = (value: ProgrammaticValue): string =>
+  expectKind(value, "NonFungibleLocalId").value;
+export const decodeReference = (value: ProgrammaticValue): string =>
+  expectKind(value, "Reference").value;
+export const decodeOwn = (value: ProgrammaticValue): string => expectKind(value, "Own").value;
+export const decodeBytes = (value: ProgrammaticValue): string => expectKind(value, "Bytes").hex;
+export const decodeArray = <T>(
+  value: ProgrammaticValue,
+  decode: (value: ProgrammaticValue) => T
+): T[] => expectKind(value, "Array").elements.map(decode);
+export const decodeMap = <K, V>(
+  value: ProgrammaticValue,
+  decodeKey: (value: ProgrammaticValue) => K,
+  decodeValue: (value: ProgrammaticValue) => V
+): [K, V][] =>
+  expectKind(value, "Map").entries.map(
+    (entry: { key: ProgrammaticValue; value: ProgrammaticValue }): [K, V] => [
+      decodeKey(entry.key),
+      decodeValue(entry.value),
+    ]
+  );
+export const decodeTuple = (value: ProgrammaticValue): ProgrammaticValue[] =>
+  expectKind(value, "Tuple").fields;
+export const decodeEnum = (value: ProgrammaticValue): [number, ProgrammaticValue[]] => {
+  const enumValue = expectKind(value, "Enum");
+  return [Number(enumValue.variant_id), enumValue.fields];
+};
+export const decodeOption = <T>(
+  value: ProgrammaticValue,
+  decode: (value: ProgrammaticValue) => T
+): T | null => {
+  const [variantId, fields] = decodeEnum(value);
+  return variantId === 0 ? null : decode(fields[0]);
+};
+export const decodeResult = <T, E>(
+  value: ProgrammaticValue,
+  decodeOk: (value: ProgrammaticValue) => T,
+  decodeErr: (value: ProgrammaticValue) => E
+): Result<T, E> => {
+  const [variantId, fields] = decodeEnum(value);
+  return variantId === 0 ? { Ok: decodeOk(fields[0]) } : { Err: decodeErr(fields[0]) };
+};
+
+const instruction = (name: string, args: string[]): string =>
+  [name, ...args.map((arg) => `    ${arg}`)].join("\n") + "\n;\n";
+
+/** Returns a `CALL_METHOD` manifest instruction. */
+export const callMethod = (address: string, method: string, args: ManifestValue[]): string =>
+  instruction("CALL_METHOD", [
+    `Address("${address}")`,
+    JSON.stringify(method),
+    ...args.map((arg) => arg.text),
+  ]);
+
+/** Returns a `CALL_FUNCTION` manifest instruction. */
+export const callFunction = (
+  packageAddress: string,
+  blueprintName: string,
+  functionName: string,
+  args: ManifestValue[]
+): string =>
+  instruction("CALL_FUNCTION", [
+    `Address("${packageAddress}")`,
+    JSON.stringify(blueprintName),
+    JSON.stringify(functionName),
+    ...args.map((arg) => arg.text),
+  ]);
+"#;
+
+/// Words which can't be used as the name of a parameter in TypeScript.
+const RESERVED_WORDS: &[&str] = &[
+    "arguments",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+pub fn generate(package: &ClientPackage) -> Result<String, std::fmt::Error> {
+    let mut out = String::from(PRELUDE);
+
+    for type_definition in &package.type_definitions {
+        writeln!(out)?;
+        write_type_definition(&mut out, package, type_definition)?;
+    }
+    for blueprint in &package.blueprints {
+        writeln!(out)?;
+        write_blueprint(&mut out, package, blueprint)?;
+    }
+
+    Ok(out)
+}
+
+fn write_type_definition(
+    out: &mut String,
+    package: &ClientPackage,
+    type_definition: &ClientTypeDefinition,
+) -> std::fmt::Result {
+    match type_definition {
+        ClientTypeDefinition::Struct {
+            name,
+            fields: ClientFields::Named(fields),
+        } => {
+            writeln!(out, "export interface {name} {{")?;
+            for (field_name, field_type) in fields {
+                writeln!(out, "  {field_name}: {};", type_name(field_type))?;
+            }
+            writeln!(out, "}}")?;
+            writeln!(out)?;
+            writeln!(
+                out,
+                "export const encode{name} = (value: {name}): ManifestValue =>"
+            )?;
+            let encoded_fields = fields
+                .iter()
+                .map(|(field_name, field_type)| {
+                    encode_expression(package, field_type, &format!("value.{field_name}"))
+                })
+                .collect::<Vec<_>>();
+            writeln!(out, "  encodeTuple([{}]);", encoded_fields.join(", "))?;
+            writeln!(out)?;
+            writeln!(
+                out,
+                "export const decode{name} = (value: ProgrammaticValue): {name} => {{"
+            )?;
+            writeln!(out, "  const fields = decodeTuple(value);")?;
+            writeln!(out, "  return {{")?;
+            for (index, (field_name, field_type)) in fields.iter().enumerate() {
+                writeln!(
+                    out,
+                    "    {field_name}: {},",
+                    decode_expression(field_type, &format!("fields[{index}]"))
+                )?;
+            }
+            writeln!(out, "  }};")?;
+            writeln!(out, "}};")?;
+        }
+        ClientTypeDefinition::Struct {
+            name,
+            fields: ClientFields::Unnamed(field_types),
+        } => {
+            writeln!(
+                out,
+                "export type {name} = {};",
+                tuple_type_name(field_types)
+            )?;
+            writeln!(out)?;
+            writeln!(
+                out,
+                "export const encode{name} = (value: {name}): ManifestValue =>"
+            )?;
+            let encoded_fields = field_types
+                .iter()
+                .enumerate()
+                .map(|(index, field_type)| {
+                    encode_expression(package, field_type, &format!("value[{index}]"))
+                })
+                .collect::<Vec<_>>();
+            writeln!(out, "  encodeTuple([{}]);", encoded_fields.join(", "))?;
+            writeln!(out)?;
+            writeln!(
+                out,
+                "export const decode{name} = (value: ProgrammaticValue): {name} => {{"
+            )?;
+            writeln!(out, "  const fields = decodeTuple(value);")?;
+            let decoded_fields = field_types
+                .iter()
+                .enumerate()
+                .map(|(index, field_type)| {
+                    decode_expression(field_type, &format!("fields[{index}]"))
+                })
+                .collect::<Vec<_>>();
+            writeln!(out, "  return [{}];", decoded_fields.join(", "))?;
+            writeln!(out, "}};")?;
+        }
+        ClientTypeDefinition::Enum { name, variants } if variants.is_empty() => {
+            writeln!(out, "export type {name} = never;")?;
+            writeln!(out)?;
+            writeln!(
+                out,
+                "export const encode{name} = (_value: {name}): ManifestValue => {{"
+            )?;
+            writeln!(out, "  throw new Error(\"{name} has no variants\");")?;
+            writeln!(out, "}};")?;
+            writeln!(out)?;
+            writeln!(
+                out,
+                "export const decode{name} = (_value: ProgrammaticValue): {name} => {{"
+            )?;
+            writeln!(out, "  throw new Error(\"{name} has no variants\");")?;
+            writeln!(out, "}};")?;
+        }
+        ClientTypeDefinition::Enum { name, variants } => {
+            writeln!(out, "export type {name} =")?;
+            for (index, variant) in variants.iter().enumerate() {
+                let separator = if index == variants.len() - 1 { ";" } else { "" };
+                let variant_name = &variant.variant_name;
+                match &variant.fields {
+                    fields if fields.is_empty() => {
+                        writeln!(out, "  | {{ variant: \"{variant_name}\" }}{separator}")?
+                    }
+                    fields => writeln!(
+                        out,
+                        "  | {{ variant: \"{variant_name}\"; fields: {} }}{separator}",
+                        fields_type_name(fields)
+                    )?,
+                }
+            }
+            writeln!(out)?;
+            writeln!(
+                out,
+                "export const encode{name} = (value: {name}): ManifestValue => {{"
+            )?;
+            writeln!(out, "  switch (value.variant) {{")?;
+            for variant in variants {
+                writeln!(out, "    case \"{}\":", variant.variant_name)?;
+                let encoded_fields = match &variant.fields {
+                    ClientFields::Named(fields) => fields
+                        .iter()
+                        .map(|(field_name, field_type)| {
+                            encode_expression(
+                                package,
+                                field_type,
+                                &format!("value.fields.{field_name}"),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                    ClientFields::Unnamed(field_types) => field_types
+                        .iter()
+                        .enumerate()
+                        .map(|(index, field_type)| {
+                            encode_expression(
+                                package,
+                                field_type,
+                                &format!("value.fields[{index}]"),
+                            )
+                        })
+                        .collect::<Vec<_>>(),
+                };
+                writeln!(
+                    out,
+                    "      return encodeEnum({}, [{}]);",
+                    variant.variant_index,
+                    encoded_fields.join(", ")
+                )?;
+            }
+            writeln!(out, "  }}")?;
+            writeln!(out, "}};")?;
+            writeln!(out)?;
+            writeln!(
+                out,
+                "export const decode{name} = (value: ProgrammaticValue): {name} => {{"
+            )?;
+            writeln!(out, "  const [variantId, fields] = decodeEnum(value);")?;
+            writeln!(out, "  switch (variantId) {{")?;
+            for variant in variants {
+                writeln!(out, "    case {}:", variant.variant_index)?;
+                let variant_name = &variant.variant_name;
+                match &variant.fields {
+                    fields if fields.is_empty() => {
+                        writeln!(out, "      return {{ variant: \"{variant_name}\" }};")?
+                    }
+                    ClientFields::Named(fields) => {
+                        let decoded_fields = fields
+                            .iter()
+                            .enumerate()
+                            .map(|(index, (field_name, field_type))| {
+                                format!(
+                                    "{field_name}: {}",
+                                    decode_expression(field_type, &format!("fields[{index}]"))
+                                )
+                            })
+                            .collect::<Vec<_>>();
+                        writeln!(
+                            out,
+                            "      return {{ variant: \"{variant_name}\", fields: {{ {} }} }};",
+                            decoded_fields.join(", ")
+                        )?
+                    }
+                    ClientFields::Unnamed(field_types) => {
+                        let decoded_fields = field_types
+                            .iter()
+                            .enumerate()
+                            .map(|(index, field_type)| {
+                                decode_expression(field_type, &format!("fields[{index}]"))
+                            })
+                            .collect::<Vec<_>>();
+                        writeln!(
+                            out,
+                            "      return {{ variant: \"{variant_name}\", fields: [{}] }};",
+                            decoded_fields.join(", ")
+                        )?
+                    }
+                }
+            }
+            writeln!(out, "    default:")?;
+            writeln!(
+                out,
+                "      throw new Error(`Unknown variant ${{variantId}} of {name}`);"
+            )?;
+            writeln!(out, "  }}")?;
+            writeln!(out, "}};")?;
+        }
+    }
+    Ok(())
+}
+
+fn write_blueprint(
+    out: &mut String,
+    package: &ClientPackage,
+    blueprint: &ClientBlueprint,
+) -> std::fmt::Result {
+    let blueprint_name = &blueprint.blueprint_name;
+
+    writeln!(
+        out,
+        "/** The functions of the `{blueprint_name}` blueprint. */"
+    )?;
+    writeln!(out, "export class {blueprint_name}Functions {{")?;
+    match &package.package_address {
+        Some(package_address) => writeln!(
+            out,
+            "  constructor(readonly packageAddress: string = \"{package_address}\") {{}}"
+        )?,
+        None => writeln!(out, "  constructor(readonly packageAddress: string) {{}}")?,
+    }
+    for function in blueprint.functions() {
+        writeln!(out)?;
+        write_function(
+            out,
+            package,
+            function,
+            &format!(
+                "callFunction(this.packageAddress, \"{blueprint_name}\", \"{}\"",
+                function.ident
+            ),
+        )?;
+    }
+    writeln!(out, "}}")?;
+    writeln!(out)?;
+
+    writeln!(out, "/** The methods of a `{blueprint_name}` component. */")?;
+    writeln!(out, "export class {blueprint_name}Component {{")?;
+    writeln!(out, "  constructor(readonly address: string) {{}}")?;
+    for method in blueprint.methods() {
+        writeln!(out)?;
+        write_function(
+            out,
+            package,
+            method,
+            &format!("callMethod(this.address, \"{}\"", method.ident),
+        )?;
+    }
+    writeln!(out, "}}")?;
+
+    for (kind, named_types) in [
+        ("Events", &blueprint.events),
+        ("RegisteredTypes", &blueprint.registered_types),
+    ] {
+        if named_types.is_empty() {
+            continue;
+        }
+        writeln!(out)?;
+        writeln!(out, "export const {blueprint_name}{kind} = {{")?;
+        for (name, client_type) in named_types {
+            writeln!(
+                out,
+                "  {name}: (value: ProgrammaticValue): {} => {},",
+                type_name(client_type),
+                decode_expression(client_type, "value")
+            )?;
+        }
+        writeln!(out, "}};")?;
+    }
+
+    Ok(())
+}
+
+fn write_function(
+    out: &mut String,
+    package: &ClientPackage,
+    function: &ClientFunction,
+    call_prefix: &str,
+) -> std::fmt::Result {
+    let parameters = function
+        .arguments
+        .iter()
+        .map(|(name, client_type)| format!("{}: {}", parameter_name(name), type_name(client_type)))
+        .collect::<Vec<_>>();
+    let arguments = function
+        .arguments
+        .iter()
+        .map(|(name, client_type)| encode_expression(package, client_type, &parameter_name(name)))
+        .collect::<Vec<_>>();
+
+    writeln!(
+        out,
+        "  /** Returns the manifest instruction calling `{}`. */",
+        function.ident
+    )?;
+    writeln!(
+        out,
+        "  {}({}): string {{",
+        function.ident,
+        parameters.join(", ")
+    )?;
+    writeln!(
+        out,
+        "    return {call_prefix}, [{}]);",
+        arguments.join(", ")
+    )?;
+    writeln!(out, "  }}")?;
+    writeln!(out)?;
+    writeln!(
+        out,
+        "  static decode{}Output(value: ProgrammaticValue): {} {{",
+        to_pascal_case(&function.ident),
+        type_name(&function.returns)
+    )?;
+    writeln!(
+        out,
+        "    return {};",
+        decode_expression(&function.returns, "value")
+    )?;
+    writeln!(out, "  }}")
+}
+
+fn parameter_name(name: &str) -> String {
+    if RESERVED_WORDS.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_owned()
+    }
+}
+
+fn type_name(client_type: &ClientType) -> String {
+    match client_type {
+        ClientType::Any => "AnyValue".to_owned(),
+        ClientType::Bool => "boolean".to_owned(),
+        ClientType::Integer(integer_kind) if integer_kind.is_large() => "bigint".to_owned(),
+        ClientType::Integer(..) => "number".to_owned(),
+        ClientType::String
+        | ClientType::Decimal
+        | ClientType::PreciseDecimal
+        | ClientType::NonFungibleLocalId
+        | ClientType::Address
+        | ClientType::Bucket
+        | ClientType::Proof
+        | ClientType::AddressReservation
+        | ClientType::Own
+        | ClientType::Bytes => "string".to_owned(),
+        ClientType::Array(element_type) => format!("Array<{}>", type_name(element_type)),
+        ClientType::Map(key_type, value_type) => format!(
+            "Array<[{}, {}]>",
+            type_name(key_type),
+            type_name(value_type)
+        ),
+        ClientType::Tuple(field_types) => tuple_type_name(field_types),
+        ClientType::Option(some_type) => format!("{} | null", type_name(some_type)),
+        ClientType::Result(ok_type, err_type) => {
+            format!("Result<{}, {}>", type_name(ok_type), type_name(err_type))
+        }
+        ClientType::Named(name) => name.clone(),
+    }
+}
+
+fn tuple_type_name(field_types: &[ClientType]) -> String {
+    format!(
+        "[{}]",
+        field_types
+            .iter()
+            .map(type_name)
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+fn fields_type_name(fields: &ClientFields) -> String {
+    match fields {
+        ClientFields::Unnamed(field_types) => tuple_type_name(field_types),
+        ClientFields::Named(fields) => format!(
+            "{{ {} }}",
+            fields
+                .iter()
+                .map(|(field_name, field_type)| format!("{field_name}: {}", type_name(field_type)))
+                .collect::<Vec<_>>()
+                .join("; ")
+        ),
+    }
+}
+
+fn quoted_value_kind(package: &ClientPackage, client_type: &ClientType) -> String {
+    match package.manifest_value_kind(client_type) {
+        Some(value_kind) => format!("\"{value_kind}\""),
+        None => "undefined".to_owned(),
+    }
+}
+
+/// Returns an expression encoding the value of the given expression as a `ManifestValue`.
+fn encode_expression(package: &ClientPackage, client_type: &ClientType, value: &str) -> String {
+    match client_type {
+        ClientType::Any => format!("encodeAny({value})"),
+        ClientType::Bool => format!("encodeBool({value})"),
+        ClientType::Integer(integer_kind) => {
+            format!("encodeInteger(\"{}\", {value})", integer_kind.value_kind())
+        }
+        ClientType::String => format!("encodeString({value})"),
+        ClientType::Decimal => format!("encodeDecimal({value})"),
+        ClientType::PreciseDecimal => format!("encodePreciseDecimal({value})"),
+        ClientType::NonFungibleLocalId => format!("encodeNonFungibleLocalId({value})"),
+        ClientType::Address => format!("encodeAddress({value})"),
+        ClientType::Bucket => format!("encodeBucket({value})"),
+        ClientType::Proof => format!("encodeProof({value})"),
+        ClientType::AddressReservation => format!("encodeAddressReservation({value})"),
+        ClientType::Own => format!("encodeOwn({value})"),
+        ClientType::Bytes => format!("encodeBytes({value})"),
+        ClientType::Array(element_type) => format!(
+            "encodeArray({}, {value}.map((element) => {}))",
+            quoted_value_kind(package, element_type),
+            encode_expression(package, element_type, "element")
+        ),
+        ClientType::Map(key_type, value_type) => format!(
+            "encodeMap({}, {}, {value}.map(([key, value]): [ManifestValue, ManifestValue] => [{}, {}]))",
+            quoted_value_kind(package, key_type),
+            quoted_value_kind(package, value_type),
+            encode_expression(package, key_type, "key"),
+            encode_expression(package, value_type, "value")
+        ),
+        ClientType::Tuple(field_types) => format!(
+            "encodeTuple([{}])",
+            field_types
+                .iter()
+                .enumerate()
+                .map(|(index, field_type)| {
+                    encode_expression(package, field_type, &format!("{value}[{index}]"))
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ClientType::Option(some_type) => format!(
+            "encodeOption({value}, (some) => {})",
+            encode_expression(package, some_type, "some")
+        ),
+        ClientType::Result(ok_type, err_type) => format!(
+            "encodeResult({value}, (ok) => {}, (err) => {})",
+            encode_expression(package, ok_type, "ok"),
+            encode_expression(package, err_type, "err")
+        ),
+        ClientType::Named(name) => format!("encode{name}({value})"),
+    }
+}
+
+/// Returns an expression decoding the `ProgrammaticValue` of the given expression.
+fn decode_expression(client_type: &ClientType, value: &str) -> String {
+    match client_type {
+        ClientType::Any => format!("decodeAny({value})"),
+        ClientType::Bool => format!("decodeBool({value})"),
+        ClientType::Integer(integer_kind) if integer_kind.is_large() => {
+            format!(
+                "decodeBigInteger(\"{}\", {value})",
+                integer_kind.value_kind()
+            )
+        }
+        ClientType::Integer(integer_kind) => {
+            format!("decodeInteger(\"{}\", {value})", integer_kind.value_kind())
+        }
+        ClientType::String => format!("decodeString({value})"),
+        ClientType::Decimal => format!("decodeDecimal({value})"),
+        ClientType::PreciseDecimal => format!("decodePreciseDecimal({value})"),
+        ClientType::NonFungibleLocalId => format!("decodeNonFungibleLocalId({value})"),
+        ClientType::Address => format!("decodeReference({value})"),
+        ClientType::Bucket
+        | ClientType::Proof
+        | ClientType::AddressReservation
+        | ClientType::Own => format!("decodeOwn({value})"),
+        ClientType::Bytes => format!("decodeBytes({value})"),
+        ClientType::Array(element_type) => format!(
+            "decodeArray({value}, (element) => {})",
+            decode_expression(element_type, "element")
+        ),
+        ClientType::Map(key_type, value_type) => format!(
+            "decodeMap({value}, (key) => {}, (value) => {})",
+            decode_expression(key_type, "key"),
+            decode_expression(value_type, "value")
+        ),
+        ClientType::Tuple(field_types) => format!(
+            "((fields: ProgrammaticValue[]): {} => [{}])(decodeTuple({value}))",
+            tuple_type_name(field_types),
+            field_types
+                .iter()
+                .enumerate()
+                .map(|(index, field_type)| {
+                    decode_expression(field_type, &format!("fields[{index}]"))
+                })
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        ClientType::Option(some_type) => format!(
+            "decodeOption({value}, (some) => {})",
+            decode_expression(some_type, "some")
+        ),
+        ClientType::Result(ok_type, err_type) => format!(
+            "decodeResult({value}, (ok) => {}, (err) => {})",
+            decode_expression(ok_type, "ok"),
+            decode_expression(err_type, "err")
+        ),
+        ClientType::Named(name) => format!("decode{name}({value})"),
+    }
+}
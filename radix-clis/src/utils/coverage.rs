@@ -7,4 +7,6 @@ pub enum CoverageError {
     ProfdataMergeFailed,
     ClangFailed,
     LlvmCovFailed,
+    InvalidCoverageSummary,
+    BelowThreshold { line_coverage: f64, fail_under: f64 },
 }
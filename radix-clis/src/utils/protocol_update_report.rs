@@ -0,0 +1,124 @@
+use crate::utils::*;
+use colored::*;
+use radix_common::prelude::*;
+use radix_substate_store_queries::query::*;
+use radix_substate_store_queries::typed_substate_layout::TypedSubstateValue;
+use std::io::Write;
+
+/// Writes a human readable report of protocol update dry runs, grouping the changed substates by
+/// category. Configuration and blueprint definition changes are shown with their decoded values
+/// before and after the update; other changes only with their sizes.
+pub fn write_protocol_update_dry_run_report<O: Write>(
+    out: &mut O,
+    dry_runs: &[ProtocolUpdateDryRun],
+    address_bech32_encoder: &AddressBech32Encoder,
+) -> std::io::Result<()> {
+    if dry_runs.is_empty() {
+        writeln!(out, "No protocol updates to run.")?;
+    }
+
+    for dry_run in dry_runs {
+        writeln!(
+            out,
+            "{}: {} (protocol version {})",
+            "Protocol Update".green().bold(),
+            dry_run.protocol_update.logical_name(),
+            dry_run.protocol_update.protocol_version().logical_name()
+        )?;
+        writeln!(
+            out,
+            "{}: {} substates across {} entities",
+            "Changes".green().bold(),
+            dry_run.substate_changes.len(),
+            dry_run.affected_entities().len()
+        )?;
+
+        for (category, title) in [
+            (SubstateChangeCategory::PackageCode, "Package Code"),
+            (SubstateChangeCategory::PackageSchema, "Package Schemas"),
+            (
+                SubstateChangeCategory::BlueprintDefinition,
+                "Blueprint Definitions",
+            ),
+            (SubstateChangeCategory::Configuration, "Configuration"),
+            (SubstateChangeCategory::Other, "Other Substates"),
+        ] {
+            let changes = dry_run.changes_of_category(category).collect::<Vec<_>>();
+            if changes.is_empty() {
+                continue;
+            }
+            writeln!(out, "{}:", title.green().bold())?;
+            let show_values = matches!(
+                category,
+                SubstateChangeCategory::BlueprintDefinition | SubstateChangeCategory::Configuration
+            );
+            for (last, change) in changes.into_iter().identify_last() {
+                write_substate_change(out, change, last, show_values, address_bech32_encoder)?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_substate_change<O: Write>(
+    out: &mut O,
+    change: &ChangedSubstate,
+    last: bool,
+    show_values: bool,
+    address_bech32_encoder: &AddressBech32Encoder,
+) -> std::io::Result<()> {
+    let entity = address_bech32_encoder
+        .encode(change.node_id.as_bytes())
+        .unwrap_or_else(|_| change.node_id.to_hex());
+    let key = match change.typed_key() {
+        Ok(typed_key) => format!("{:?}", typed_key),
+        Err(_) => format!("{:?} {:?}", change.partition_number, change.db_sort_key),
+    };
+    writeln!(out, "{} {} {}", list_item_prefix(last), entity, key)?;
+
+    let indent = if last { "   " } else { "│  " };
+    if show_values {
+        writeln!(
+            out,
+            "{}{} {}",
+            indent,
+            "Before:".bold(),
+            describe_value(change.typed_previous_value(), &change.previous_value)
+        )?;
+        writeln!(
+            out,
+            "{}{} {}",
+            indent,
+            "After:".bold(),
+            describe_value(change.typed_new_value(), &change.new_value)
+        )?;
+    } else {
+        writeln!(
+            out,
+            "{}{} -> {}",
+            indent,
+            describe_size(&change.previous_value),
+            describe_size(&change.new_value)
+        )?;
+    }
+    Ok(())
+}
+
+fn describe_value(
+    typed_value: Option<Result<TypedSubstateValue, String>>,
+    raw_value: &Option<Vec<u8>>,
+) -> String {
+    match typed_value {
+        Some(Ok(typed_value)) => format!("{:?}", typed_value),
+        Some(Err(_)) => describe_size(raw_value),
+        None => "<none>".to_string(),
+    }
+}
+
+fn describe_size(raw_value: &Option<Vec<u8>>) -> String {
+    match raw_value {
+        Some(value) => format!("<{} bytes>", value.len()),
+        None => "<none>".to_string(),
+    }
+}
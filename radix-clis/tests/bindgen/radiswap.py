@@ -0,0 +1,649 @@
+#===================================================================================================
+# This file has been autogenerated by scrypto-bindgen and none of the contents here are
+# hand-written. Regenerate it whenever the interface of the package changes.
+#===================================================================================================
+
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, Generic, List, Optional, Tuple, TypeVar, Union
+
+T = TypeVar("T")
+E = TypeVar("E")
+
+# A value in the programmatic JSON representation, as returned by the Gateway and Core APIs.
+ProgrammaticValue = Dict[str, Any]
+
+
+@dataclass(frozen=True)
+class ManifestValue:
+    """A value in the textual manifest representation, along with its manifest value kind."""
+
+    kind: str
+    text: str
+
+
+@dataclass(frozen=True)
+class Ok(Generic[T]):
+    value: T
+
+
+@dataclass(frozen=True)
+class Err(Generic[E]):
+    value: E
+
+
+def _join_texts(values: List[ManifestValue]) -> str:
+    return ", ".join(value.text for value in values)
+
+
+def encode_any(value: Any) -> ManifestValue:
+    if not isinstance(value, ManifestValue):
+        raise TypeError("Only manifest values can be encoded")
+    return value
+
+
+def encode_bool(value: bool) -> ManifestValue:
+    return ManifestValue("Bool", "true" if value else "false")
+
+
+def encode_integer(kind: str, value: int) -> ManifestValue:
+    return ManifestValue(kind, f"{value}{kind.lower()}")
+
+
+def encode_string(value: str) -> ManifestValue:
+    return ManifestValue("String", json.dumps(value, ensure_ascii=False))
+
+
+def encode_decimal(value: str) -> ManifestValue:
+    return ManifestValue("Decimal", f'Decimal("{value}")')
+
+
+def encode_precise_decimal(value: str) -> ManifestValue:
+    return ManifestValue("PreciseDecimal", f'PreciseDecimal("{value}")')
+
+
+def encode_non_fungible_local_id(value: str) -> ManifestValue:
+    return ManifestValue("NonFungibleLocalId", f'NonFungibleLocalId("{value}")')
+
+
+def encode_address(value: str) -> ManifestValue:
+    return ManifestValue("Address", f'Address("{value}")')
+
+
+def encode_bucket(name: str) -> ManifestValue:
+    return ManifestValue("Bucket", f'Bucket("{name}")')
+
+
+def encode_proof(name: str) -> ManifestValue:
+    return ManifestValue("Proof", f'Proof("{name}")')
+
+
+def encode_address_reservation(name: str) -> ManifestValue:
+    return ManifestValue("AddressReservation", f'AddressReservation("{name}")')
+
+
+def encode_own(value: str) -> ManifestValue:
+    raise TypeError("Only buckets, proofs and address reservations can be passed by a manifest")
+
+
+def encode_bytes(value: bytes) -> ManifestValue:
+    return ManifestValue("Array", f'Bytes("{value.hex()}")')
+
+
+def encode_array(element_kind: Optional[str], elements: List[ManifestValue]) -> ManifestValue:
+    if element_kind is None:
+        element_kind = elements[0].kind if elements else "Tuple"
+    return ManifestValue("Array", f"Array<{element_kind}>({_join_texts(elements)})")
+
+
+def encode_map(
+    key_kind: Optional[str],
+    value_kind: Optional[str],
+    entries: List[Tuple[ManifestValue, ManifestValue]],
+) -> ManifestValue:
+    if key_kind is None:
+        key_kind = entries[0][0].kind if entries else "Tuple"
+    if value_kind is None:
+        value_kind = entries[0][1].kind if entries else "Tuple"
+    texts = ", ".join(f"{key.text} => {value.text}" for key, value in entries)
+    return ManifestValue("Map", f"Map<{key_kind}, {value_kind}>({texts})")
+
+
+def encode_tuple(fields: List[ManifestValue]) -> ManifestValue:
+    return ManifestValue("Tuple", f"Tuple({_join_texts(fields)})")
+
+
+def encode_enum(discriminator: int, fields: List[ManifestValue]) -> ManifestValue:
+    return ManifestValue("Enum", f"Enum<{discriminator}u8>({_join_texts(fields)})")
+
+
+def encode_option(value: Optional[T], encode: Callable[[T], ManifestValue]) -> ManifestValue:
+    return encode_enum(0, []) if value is None else encode_enum(1, [encode(value)])
+
+
+def encode_result(
+    value: Union[Ok[T], Err[E]],
+    encode_ok: Callable[[T], ManifestValue],
+    encode_err: Callable[[E], ManifestValue],
+) -> ManifestValue:
+    if isinstance(value, Ok):
+        return encode_enum(0, [encode_ok(value.value)])
+    return encode_enum(1, [encode_err(value.value)])
+
+
+def _expect_kind(value: ProgrammaticValue, kind: str) -> ProgrammaticValue:
+    if value["kind"] != kind:
+        raise ValueError(f"Expected a value of kind {kind} but found {value['kind']}")
+    return value
+
+
+def decode_any(value: ProgrammaticValue) -> Any:
+    return value
+
+
+def decode_bool(value: ProgrammaticValue) -> bool:
+    return _expect_kind(value, "Bool")["value"]
+
+
+def decode_integer(kind: str, value: ProgrammaticValue) -> int:
+    return int(_expect_kind(value, kind)["value"])
+
+
+def decode_string(value: ProgrammaticValue) -> str:
+    return _expect_kind(value, "String")["value"]
+
+
+def decode_decimal(value: ProgrammaticValue) -> str:
+    return _expect_kind(value, "Decimal")["value"]
+
+
+def decode_precise_decimal(value: ProgrammaticValue) -> str:
+    return _expect_kind(value, "PreciseDecimal")["value"]
+
+
+def decode_non_fungible_local_id(value: ProgrammaticValue) -> str:
+    return _expect_kind(value, "NonFungibleLocalId")["value"]
+
+
+def decode_reference(value: ProgrammaticValue) -> str:
+    return _expect_kind(value, "Reference")["value"]
+
+
+def decode_own(value: ProgrammaticValue) -> str:
+    return _expect_kind(value, "Own")["value"]
+
+
+def decode_bytes(value: ProgrammaticValue) -> bytes:
+    return bytes.fromhex(_expect_kind(value, "Bytes")["hex"])
+
+
+def decode_array(value: ProgrammaticValue) -> List[ProgrammaticValue]:
+    return _expect_kind(value, "Array")["elements"]
+
+
+def decode_map(value: ProgrammaticValue) -> List[Tuple[ProgrammaticValue, ProgrammaticValue]]:
+    return [(entry["key"], entry["value"]) for entry in _expect_kind(value, "Map")["entries"]]
+
+
+def decode_tuple(value: ProgrammaticValue) -> List[ProgrammaticValue]:
+    return _expect_kind(value, "Tuple")["fields"]
+
+
+def decode_enum(value: ProgrammaticValue) -> Tuple[int, List[ProgrammaticValue]]:
+    enum_value = _expect_kind(value, "Enum")
+    return int(enum_value["variant_id"]), enum_value["fields"]
+
+
+def decode_option(value: ProgrammaticValue, decode: Callable[[ProgrammaticValue], T]) -> Optional[T]:
+    variant_id, fields = decode_enum(value)
+    return None if variant_id == 0 else decode(fields[0])
+
+
+def decode_result(
+    value: ProgrammaticValue,
+    decode_ok: Callable[[ProgrammaticValue], T],
+    decode_err: Callable[[ProgrammaticValue], E],
+) -> Union[Ok[T], Err[E]]:
+    variant_id, fields = decode_enum(value)
+    return Ok(decode_ok(fields[0])) if variant_id == 0 else Err(decode_err(fields[0]))
+
+
+def _instruction(name: str, args: List[str]) -> str:
+    return "\n".join([name] + [f"    {arg}" for arg in args]) + "\n;\n"
+
+
+def call_method(address: str, method: str, args: List[ManifestValue]) -> str:
+    """Returns a `CALL_METHOD` manifest instruction."""
+    return _instruction(
+        "CALL_METHOD",
+        [f'Address("{address}")', json.dumps(method)] + [arg.text for arg in args],
+    )
+
+
+def call_function(
+    package_address: str, blueprint_name: str, function_name: str, args: List[ManifestValue]
+) -> str:
+    """Returns a `CALL_FUNCTION` manifest instruction."""
+    return _instruction(
+        "CALL_FUNCTION",
+        [f'Address("{package_address}")', json.dumps(blueprint_name), json.dumps(function_name)]
+        + [arg.text for arg in args],
+    )
+
+
+class AccessRule:
+    """The base class of the variants of `AccessRule`."""
+
+    def encode(self) -> ManifestValue:
+        raise NotImplementedError
+
+    @staticmethod
+    def decode(value: ProgrammaticValue) -> AccessRule:
+        variant_id, fields = decode_enum(value)
+        if variant_id == 0:
+            return AccessRule_AllowAll()
+        if variant_id == 1:
+            return AccessRule_DenyAll()
+        if variant_id == 2:
+            return AccessRule_Protected(AccessRuleNode.decode(fields[0]))
+        raise ValueError(f"Unknown variant {variant_id} of AccessRule")
+
+
+@dataclass(frozen=True)
+class AccessRule_AllowAll(AccessRule):
+    def encode(self) -> ManifestValue:
+        return encode_enum(0, [])
+
+
+@dataclass(frozen=True)
+class AccessRule_DenyAll(AccessRule):
+    def encode(self) -> ManifestValue:
+        return encode_enum(1, [])
+
+
+@dataclass(frozen=True)
+class AccessRule_Protected(AccessRule):
+    field_0: AccessRuleNode
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(2, [self.field_0.encode()])
+
+
+class AccessRuleNode:
+    """The base class of the variants of `AccessRuleNode`."""
+
+    def encode(self) -> ManifestValue:
+        raise NotImplementedError
+
+    @staticmethod
+    def decode(value: ProgrammaticValue) -> AccessRuleNode:
+        variant_id, fields = decode_enum(value)
+        if variant_id == 0:
+            return AccessRuleNode_ProofRule(ProofRule.decode(fields[0]))
+        if variant_id == 1:
+            return AccessRuleNode_AnyOf([AccessRuleNode.decode(element) for element in decode_array(fields[0])])
+        if variant_id == 2:
+            return AccessRuleNode_AllOf([AccessRuleNode.decode(element) for element in decode_array(fields[0])])
+        if variant_id == 3:
+            return AccessRuleNode_TimeCondition(TimeCondition.decode(fields[0]))
+        raise ValueError(f"Unknown variant {variant_id} of AccessRuleNode")
+
+
+@dataclass(frozen=True)
+class AccessRuleNode_ProofRule(AccessRuleNode):
+    field_0: ProofRule
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(0, [self.field_0.encode()])
+
+
+@dataclass(frozen=True)
+class AccessRuleNode_AnyOf(AccessRuleNode):
+    field_0: List[AccessRuleNode]
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(1, [encode_array("Enum", [element.encode() for element in self.field_0])])
+
+
+@dataclass(frozen=True)
+class AccessRuleNode_AllOf(AccessRuleNode):
+    field_0: List[AccessRuleNode]
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(2, [encode_array("Enum", [element.encode() for element in self.field_0])])
+
+
+@dataclass(frozen=True)
+class AccessRuleNode_TimeCondition(AccessRuleNode):
+    field_0: TimeCondition
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(3, [self.field_0.encode()])
+
+
+@dataclass(frozen=True)
+class AddLiquidityEvent:
+    field_0: List[Tuple[str, str]]
+
+    def encode(self) -> ManifestValue:
+        return encode_tuple([encode_array("Tuple", [encode_tuple([encode_address(element[0]), encode_decimal(element[1])]) for element in self.field_0])])
+
+    @staticmethod
+    def decode(value: ProgrammaticValue) -> AddLiquidityEvent:
+        fields = decode_tuple(value)
+        return AddLiquidityEvent([(lambda fields: (decode_reference(fields[0]), decode_decimal(fields[1]),))(decode_tuple(element)) for element in decode_array(fields[0])])
+
+
+@dataclass(frozen=True)
+class InstantiationEvent:
+    owner_role: OwnerRole
+    resource_address1: str
+    resource_address2: str
+    component_address: str
+
+    def encode(self) -> ManifestValue:
+        return encode_tuple([self.owner_role.encode(), encode_address(self.resource_address1), encode_address(self.resource_address2), encode_address(self.component_address)])
+
+    @staticmethod
+    def decode(value: ProgrammaticValue) -> InstantiationEvent:
+        fields = decode_tuple(value)
+        return InstantiationEvent(OwnerRole.decode(fields[0]), decode_reference(fields[1]), decode_reference(fields[2]), decode_reference(fields[3]))
+
+
+@dataclass(frozen=True)
+class NonFungibleGlobalId:
+    resource_address: str
+    local_id: str
+
+    def encode(self) -> ManifestValue:
+        return encode_tuple([encode_address(self.resource_address), encode_non_fungible_local_id(self.local_id)])
+
+    @staticmethod
+    def decode(value: ProgrammaticValue) -> NonFungibleGlobalId:
+        fields = decode_tuple(value)
+        return NonFungibleGlobalId(decode_reference(fields[0]), decode_non_fungible_local_id(fields[1]))
+
+
+class OwnerRole:
+    """The base class of the variants of `OwnerRole`."""
+
+    def encode(self) -> ManifestValue:
+        raise NotImplementedError
+
+    @staticmethod
+    def decode(value: ProgrammaticValue) -> OwnerRole:
+        variant_id, fields = decode_enum(value)
+        if variant_id == 0:
+            return OwnerRole_None()
+        if variant_id == 1:
+            return OwnerRole_Fixed(AccessRule.decode(fields[0]))
+        if variant_id == 2:
+            return OwnerRole_Updatable(AccessRule.decode(fields[0]))
+        raise ValueError(f"Unknown variant {variant_id} of OwnerRole")
+
+
+@dataclass(frozen=True)
+class OwnerRole_None(OwnerRole):
+    def encode(self) -> ManifestValue:
+        return encode_enum(0, [])
+
+
+@dataclass(frozen=True)
+class OwnerRole_Fixed(OwnerRole):
+    field_0: AccessRule
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(1, [self.field_0.encode()])
+
+
+@dataclass(frozen=True)
+class OwnerRole_Updatable(OwnerRole):
+    field_0: AccessRule
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(2, [self.field_0.encode()])
+
+
+class ProofRule:
+    """The base class of the variants of `ProofRule`."""
+
+    def encode(self) -> ManifestValue:
+        raise NotImplementedError
+
+    @staticmethod
+    def decode(value: ProgrammaticValue) -> ProofRule:
+        variant_id, fields = decode_enum(value)
+        if variant_id == 0:
+            return ProofRule_Require(ResourceOrNonFungible.decode(fields[0]))
+        if variant_id == 1:
+            return ProofRule_AmountOf(decode_decimal(fields[0]), decode_reference(fields[1]))
+        if variant_id == 2:
+            return ProofRule_CountOf(decode_integer("U8", fields[0]), [ResourceOrNonFungible.decode(element) for element in decode_array(fields[1])])
+        if variant_id == 3:
+            return ProofRule_AllOf([ResourceOrNonFungible.decode(element) for element in decode_array(fields[0])])
+        if variant_id == 4:
+            return ProofRule_AnyOf([ResourceOrNonFungible.decode(element) for element in decode_array(fields[0])])
+        raise ValueError(f"Unknown variant {variant_id} of ProofRule")
+
+
+@dataclass(frozen=True)
+class ProofRule_Require(ProofRule):
+    field_0: ResourceOrNonFungible
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(0, [self.field_0.encode()])
+
+
+@dataclass(frozen=True)
+class ProofRule_AmountOf(ProofRule):
+    field_0: str
+    field_1: str
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(1, [encode_decimal(self.field_0), encode_address(self.field_1)])
+
+
+@dataclass(frozen=True)
+class ProofRule_CountOf(ProofRule):
+    field_0: int
+    field_1: List[ResourceOrNonFungible]
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(2, [encode_integer("U8", self.field_0), encode_array("Enum", [element.encode() for element in self.field_1])])
+
+
+@dataclass(frozen=True)
+class ProofRule_AllOf(ProofRule):
+    field_0: List[ResourceOrNonFungible]
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(3, [encode_array("Enum", [element.encode() for element in self.field_0])])
+
+
+@dataclass(frozen=True)
+class ProofRule_AnyOf(ProofRule):
+    field_0: List[ResourceOrNonFungible]
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(4, [encode_array("Enum", [element.encode() for element in self.field_0])])
+
+
+@dataclass(frozen=True)
+class Radiswap:
+    pool_component: str
+
+    def encode(self) -> ManifestValue:
+        return encode_tuple([encode_address(self.pool_component)])
+
+    @staticmethod
+    def decode(value: ProgrammaticValue) -> Radiswap:
+        fields = decode_tuple(value)
+        return Radiswap(decode_reference(fields[0]))
+
+
+@dataclass(frozen=True)
+class RemoveLiquidityEvent:
+    pool_units_amount: str
+    redeemed_resources: List[Tuple[str, str]]
+
+    def encode(self) -> ManifestValue:
+        return encode_tuple([encode_decimal(self.pool_units_amount), encode_array("Tuple", [encode_tuple([encode_address(element[0]), encode_decimal(element[1])]) for element in self.redeemed_resources])])
+
+    @staticmethod
+    def decode(value: ProgrammaticValue) -> RemoveLiquidityEvent:
+        fields = decode_tuple(value)
+        return RemoveLiquidityEvent(decode_decimal(fields[0]), [(lambda fields: (decode_reference(fields[0]), decode_decimal(fields[1]),))(decode_tuple(element)) for element in decode_array(fields[1])])
+
+
+class ResourceOrNonFungible:
+    """The base class of the variants of `ResourceOrNonFungible`."""
+
+    def encode(self) -> ManifestValue:
+        raise NotImplementedError
+
+    @staticmethod
+    def decode(value: ProgrammaticValue) -> ResourceOrNonFungible:
+        variant_id, fields = decode_enum(value)
+        if variant_id == 0:
+            return ResourceOrNonFungible_NonFungible(NonFungibleGlobalId.decode(fields[0]))
+        if variant_id == 1:
+            return ResourceOrNonFungible_Resource(decode_reference(fields[0]))
+        raise ValueError(f"Unknown variant {variant_id} of ResourceOrNonFungible")
+
+
+@dataclass(frozen=True)
+class ResourceOrNonFungible_NonFungible(ResourceOrNonFungible):
+    field_0: NonFungibleGlobalId
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(0, [self.field_0.encode()])
+
+
+@dataclass(frozen=True)
+class ResourceOrNonFungible_Resource(ResourceOrNonFungible):
+    field_0: str
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(1, [encode_address(self.field_0)])
+
+
+@dataclass(frozen=True)
+class SwapEvent:
+    input: Tuple[str, str]
+    output: Tuple[str, str]
+
+    def encode(self) -> ManifestValue:
+        return encode_tuple([encode_tuple([encode_address(self.input[0]), encode_decimal(self.input[1])]), encode_tuple([encode_address(self.output[0]), encode_decimal(self.output[1])])])
+
+    @staticmethod
+    def decode(value: ProgrammaticValue) -> SwapEvent:
+        fields = decode_tuple(value)
+        return SwapEvent((lambda fields: (decode_reference(fields[0]), decode_decimal(fields[1]),))(decode_tuple(fields[0])), (lambda fields: (decode_reference(fields[0]), decode_decimal(fields[1]),))(decode_tuple(fields[1])))
+
+
+class TimeCondition:
+    """The base class of the variants of `TimeCondition`."""
+
+    def encode(self) -> ManifestValue:
+        raise NotImplementedError
+
+    @staticmethod
+    def decode(value: ProgrammaticValue) -> TimeCondition:
+        variant_id, fields = decode_enum(value)
+        if variant_id == 0:
+            return TimeCondition_EpochAtLeast(decode_integer("U64", fields[0]))
+        if variant_id == 1:
+            return TimeCondition_EpochBefore(decode_integer("U64", fields[0]))
+        if variant_id == 2:
+            return TimeCondition_TimeAtLeast(decode_integer("I64", fields[0]))
+        if variant_id == 3:
+            return TimeCondition_TimeBefore(decode_integer("I64", fields[0]))
+        raise ValueError(f"Unknown variant {variant_id} of TimeCondition")
+
+
+@dataclass(frozen=True)
+class TimeCondition_EpochAtLeast(TimeCondition):
+    field_0: int
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(0, [encode_integer("U64", self.field_0)])
+
+
+@dataclass(frozen=True)
+class TimeCondition_EpochBefore(TimeCondition):
+    field_0: int
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(1, [encode_integer("U64", self.field_0)])
+
+
+@dataclass(frozen=True)
+class TimeCondition_TimeAtLeast(TimeCondition):
+    field_0: int
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(2, [encode_integer("I64", self.field_0)])
+
+
+@dataclass(frozen=True)
+class TimeCondition_TimeBefore(TimeCondition):
+    field_0: int
+
+    def encode(self) -> ManifestValue:
+        return encode_enum(3, [encode_integer("I64", self.field_0)])
+
+
+class RadiswapFunctions:
+    """The functions of the `Radiswap` blueprint."""
+
+    def __init__(self, package_address: str):
+        self.package_address = package_address
+
+    def new(self, owner_role: OwnerRole, resource_address1: str, resource_address2: str) -> str:
+        """Returns the manifest instruction calling `new`."""
+        return call_function(self.package_address, "Radiswap", "new", [owner_role.encode(), encode_address(resource_address1), encode_address(resource_address2)])
+
+    @staticmethod
+    def decode_new_output(value: ProgrammaticValue) -> str:
+        return decode_reference(value)
+
+
+class RadiswapComponent:
+    """The methods of a `Radiswap` component."""
+
+    def __init__(self, address: str):
+        self.address = address
+
+    def add_liquidity(self, resource1: str, resource2: str) -> str:
+        """Returns the manifest instruction calling `add_liquidity`."""
+        return call_method(self.address, "add_liquidity", [encode_bucket(resource1), encode_bucket(resource2)])
+
+    @staticmethod
+    def decode_add_liquidity_output(value: ProgrammaticValue) -> Tuple[str, Optional[str]]:
+        return (lambda fields: (decode_own(fields[0]), decode_option(fields[1], lambda some: decode_own(some)),))(decode_tuple(value))
+
+    def remove_liquidity(self, pool_units: str) -> str:
+        """Returns the manifest instruction calling `remove_liquidity`."""
+        return call_method(self.address, "remove_liquidity", [encode_bucket(pool_units)])
+
+    @staticmethod
+    def decode_remove_liquidity_output(value: ProgrammaticValue) -> Tuple[str, str]:
+        return (lambda fields: (decode_own(fields[0]), decode_own(fields[1]),))(decode_tuple(value))
+
+    def swap(self, input_bucket: str) -> str:
+        """Returns the manifest instruction calling `swap`."""
+        return call_method(self.address, "swap", [encode_bucket(input_bucket)])
+
+    @staticmethod
+    def decode_swap_output(value: ProgrammaticValue) -> str:
+        return decode_own(value)
+
+
+RADISWAP_EVENTS: Dict[str, Callable[[ProgrammaticValue], Any]] = {
+    "AddLiquidityEvent": lambda value: AddLiquidityEvent.decode(value),
+    "InstantiationEvent": lambda value: InstantiationEvent.decode(value),
+    "RemoveLiquidityEvent": lambda value: RemoveLiquidityEvent.decode(value),
+    "SwapEvent": lambda value: SwapEvent.decode(value),
+}
@@ -0,0 +1 @@
+extern_blueprint_internal ! { PackageAddress :: new_or_panic ([13u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8 , 1u8]) , Radiswap , "Radiswap" , "OwnedRadiswap" , "GlobalRadiswap" , RadiswapFunctions { fn new (owner_role : OwnerRole , resource_address1 : ResourceAddress , resource_address2 : ResourceAddress) -> Global < Radiswap > ; } , { fn add_liquidity (& mut self , resource1 : Bucket , resource2 : Bucket) -> (Bucket , Option < Bucket >,) ; fn remove_liquidity (& mut self , pool_units : Bucket) -> (Bucket , Bucket ,) ; fn swap (& mut self , input_bucket : Bucket) -> Bucket ; } }
@@ -0,0 +1,516 @@
+//==================================================================================================
+// This file has been autogenerated by scrypto-bindgen and none of the contents here are
+// hand-written. Regenerate it whenever the interface of the package changes.
+//==================================================================================================
+
+/** A value in the textual manifest representation, along with its manifest value kind. */
+export interface ManifestValue {
+  kind: string;
+  text: string;
+}
+
+/** A value in the programmatic JSON representation, as returned by the Gateway and Core APIs. */
+export interface ProgrammaticValue {
+  kind: string;
+  [field: string]: any;
+}
+
+export type AnyValue = ManifestValue | ProgrammaticValue;
+
+export type Result<T, E> = { Ok: T } | { Err: E };
+
+const manifestValue = (kind: string, text: string): ManifestValue => ({ kind, text });
+
+const joinTexts = (values: ManifestValue[]): string => values.map((value) => value.text).join(", ");
+
+export const encodeAny = (value: AnyValue): ManifestValue => {
+  if (!("text" in value)) {
+    throw new Error("Only manifest values can be encoded");
+  }
+  return value as ManifestValue;
+};
+export const encodeBool = (value: boolean): ManifestValue =>
+  manifestValue("Bool", value ? "true" : "false");
+export const encodeInteger = (kind: string, value: number | bigint): ManifestValue =>
+  manifestValue(kind, `${value}${kind.toLowerCase()}`);
+export const encodeString = (value: string): ManifestValue =>
+  manifestValue("String", JSON.stringify(value));
+export const encodeDecimal = (value: string): ManifestValue =>
+  manifestValue("Decimal", `Decimal("${value}")`);
+export const encodePreciseDecimal = (value: string): ManifestValue =>
+  manifestValue("PreciseDecimal", `PreciseDecimal("${value}")`);
+export const encodeNonFungibleLocalId = (value: string): ManifestValue =>
+  manifestValue("NonFungibleLocalId", `NonFungibleLocalId("${value}")`);
+export const encodeAddress = (value: string): ManifestValue =>
+  manifestValue("Address", `Address("${value}")`);
+export const encodeBucket = (name: string): ManifestValue =>
+  manifestValue("Bucket", `Bucket("${name}")`);
+export const encodeProof = (name: string): ManifestValue =>
+  manifestValue("Proof", `Proof("${name}")`);
+export const encodeAddressReservation = (name: string): ManifestValue =>
+  manifestValue("AddressReservation", `AddressReservation("${name}")`);
+export const encodeOwn = (_value: string): ManifestValue => {
+  throw new Error("Only buckets, proofs and address reservations can be passed by a manifest");
+};
+export const encodeBytes = (hex: string): ManifestValue =>
+  manifestValue("Array", `Bytes("${hex}")`);
+export const encodeArray = (
+  elementKind: string | undefined,
+  elements: ManifestValue[]
+): ManifestValue =>
+  manifestValue(
+    "Array",
+    `Array<${elementKind ?? elements[0]?.kind ?? "Tuple"}>(${joinTexts(elements)})`
+  );
+export const encodeMap = (
+  keyKind: string | undefined,
+  valueKind: string | undefined,
+  entries: [ManifestValue, ManifestValue][]
+): ManifestValue =>
+  manifestValue(
+    "Map",
+    `Map<${keyKind ?? entries[0]?.[0].kind ?? "Tuple"}, ${
+      valueKind ?? entries[0]?.[1].kind ?? "Tuple"
+    }>(${entries.map(([key, value]) => `${key.text} => ${value.text}`).join(", ")})`
+  );
+export const encodeTuple = (fields: ManifestValue[]): ManifestValue =>
+  manifestValue("Tuple", `Tuple(${joinTexts(fields)})`);
+export const encodeEnum = (discriminator: number, fields: ManifestValue[]): ManifestValue =>
+  manifestValue("Enum", `Enum<${discriminator}u8>(${joinTexts(fields)})`);
+export const encodeOption = <T>(
+  value: T | null,
+  encode: (value: T) => ManifestValue
+): ManifestValue => (value === null ? encodeEnum(0, []) : encodeEnum(1, [encode(value)]));
+export const encodeResult = <T, E>(
+  value: Result<T, E>,
+  encodeOk: (value: T) => ManifestValue,
+  encodeErr: (value: E) => ManifestValue
+): ManifestValue =>
+  "Ok" in value ? encodeEnum(0, [encodeOk(value.Ok)]) : encodeEnum(1, [encodeErr(value.Err)]);
+
+const expectKind = (value: ProgrammaticValue, kind: string): ProgrammaticValue => {
+  if (value.kind !== kind) {
+    throw new Error(`Expected a value of kind ${kind} but found ${value.kind}`);
+  }
+  return value;
+};
+
+export const decodeAny = (value: ProgrammaticValue): AnyValue => value;
+export const decodeBool = (value: ProgrammaticValue): boolean => expectKind(value, "Bool").value;
+export const decodeInteger = (kind: string, value: ProgrammaticValue): number =>
+  Number(expectKind(value, kind).value);
+export const decodeBigInteger = (kind: string, value: ProgrammaticValue): bigint =>
+  BigInt(expectKind(value, kind).value);
+export const decodeString = (value: ProgrammaticValue): string =>
+  expectKind(value, "String").value;
+export const decodeDecimal = (value: ProgrammaticValue): string =>
+  expectKind(value, "Decimal").value;
+export const decodePreciseDecimal = (value: ProgrammaticValue): string =>
+  expectKind(value, "PreciseDecimal").value;
+export const decodeNonFungibleLocalId = (value: ProgrammaticValue): string =>
+  expectKind(value, "NonFungibleLocalId").value;
+export const decodeReference = (value: ProgrammaticValue): string =>
+  expectKind(value, "Reference").value;
+export const decodeOwn = (value: ProgrammaticValue): string => expectKind(value, "Own").value;
+export const decodeBytes = (value: ProgrammaticValue): string => expectKind(value, "Bytes").hex;
+export const decodeArray = <T>(
+  value: ProgrammaticValue,
+  decode: (value: ProgrammaticValue) => T
+): T[] => expectKind(value, "Array").elements.map(decode);
+export const decodeMap = <K, V>(
+  value: ProgrammaticValue,
+  decodeKey: (value: ProgrammaticValue) => K,
+  decodeValue: (value: ProgrammaticValue) => V
+): [K, V][] =>
+  expectKind(value, "Map").entries.map(
+    (entry: { key: ProgrammaticValue; value: ProgrammaticValue }): [K, V] => [
+      decodeKey(entry.key),
+      decodeValue(entry.value),
+    ]
+  );
+export const decodeTuple = (value: ProgrammaticValue): ProgrammaticValue[] =>
+  expectKind(value, "Tuple").fields;
+export const decodeEnum = (value: ProgrammaticValue): [number, ProgrammaticValue[]] => {
+  const enumValue = expectKind(value, "Enum");
+  return [Number(enumValue.variant_id), enumValue.fields];
+};
+export const decodeOption = <T>(
+  value: ProgrammaticValue,
+  decode: (value: ProgrammaticValue) => T
+): T | null => {
+  const [variantId, fields] = decodeEnum(value);
+  return variantId === 0 ? null : decode(fields[0]);
+};
+export const decodeResult = <T, E>(
+  value: ProgrammaticValue,
+  decodeOk: (value: ProgrammaticValue) => T,
+  decodeErr: (value: ProgrammaticValue) => E
+): Result<T, E> => {
+  const [variantId, fields] = decodeEnum(value);
+  return variantId === 0 ? { Ok: decodeOk(fields[0]) } : { Err: decodeErr(fields[0]) };
+};
+
+const instruction = (name: string, args: string[]): string =>
+  [name, ...args.map((arg) => `    ${arg}`)].join("\n") + "\n;\n";
+
+/** Returns a `CALL_METHOD` manifest instruction. */
+export const callMethod = (address: string, method: string, args: ManifestValue[]): string =>
+  instruction("CALL_METHOD", [
+    `Address("${address}")`,
+    JSON.stringify(method),
+    ...args.map((arg) => arg.text),
+  ]);
+
+/** Returns a `CALL_FUNCTION` manifest instruction. */
+export const callFunction = (
+  packageAddress: string,
+  blueprintName: string,
+  functionName: string,
+  args: ManifestValue[]
+): string =>
+  instruction("CALL_FUNCTION", [
+    `Address("${packageAddress}")`,
+    JSON.stringify(blueprintName),
+    JSON.stringify(functionName),
+    ...args.map((arg) => arg.text),
+  ]);
+
+export type AccessRule =
+  | { variant: "AllowAll" }
+  | { variant: "DenyAll" }
+  | { variant: "Protected"; fields: [AccessRuleNode] };
+
+export const encodeAccessRule = (value: AccessRule): ManifestValue => {
+  switch (value.variant) {
+    case "AllowAll":
+      return encodeEnum(0, []);
+    case "DenyAll":
+      return encodeEnum(1, []);
+    case "Protected":
+      return encodeEnum(2, [encodeAccessRuleNode(value.fields[0])]);
+  }
+};
+
+export const decodeAccessRule = (value: ProgrammaticValue): AccessRule => {
+  const [variantId, fields] = decodeEnum(value);
+  switch (variantId) {
+    case 0:
+      return { variant: "AllowAll" };
+    case 1:
+      return { variant: "DenyAll" };
+    case 2:
+      return { variant: "Protected", fields: [decodeAccessRuleNode(fields[0])] };
+    default:
+      throw new Error(`Unknown variant ${variantId} of AccessRule`);
+  }
+};
+
+export type AccessRuleNode =
+  | { variant: "ProofRule"; fields: [ProofRule] }
+  | { variant: "AnyOf"; fields: [Array<AccessRuleNode>] }
+  | { variant: "AllOf"; fields: [Array<AccessRuleNode>] }
+  | { variant: "TimeCondition"; fields: [TimeCondition] };
+
+export const encodeAccessRuleNode = (value: AccessRuleNode): ManifestValue => {
+  switch (value.variant) {
+    case "ProofRule":
+      return encodeEnum(0, [encodeProofRule(value.fields[0])]);
+    case "AnyOf":
+      return encodeEnum(1, [encodeArray("Enum", value.fields[0].map((element) => encodeAccessRuleNode(element)))]);
+    case "AllOf":
+      return encodeEnum(2, [encodeArray("Enum", value.fields[0].map((element) => encodeAccessRuleNode(element)))]);
+    case "TimeCondition":
+      return encodeEnum(3, [encodeTimeCondition(value.fields[0])]);
+  }
+};
+
+export const decodeAccessRuleNode = (value: ProgrammaticValue): AccessRuleNode => {
+  const [variantId, fields] = decodeEnum(value);
+  switch (variantId) {
+    case 0:
+      return { variant: "ProofRule", fields: [decodeProofRule(fields[0])] };
+    case 1:
+      return { variant: "AnyOf", fields: [decodeArray(fields[0], (element) => decodeAccessRuleNode(element))] };
+    case 2:
+      return { variant: "AllOf", fields: [decodeArray(fields[0], (element) => decodeAccessRuleNode(element))] };
+    case 3:
+      return { variant: "TimeCondition", fields: [decodeTimeCondition(fields[0])] };
+    default:
+      throw new Error(`Unknown variant ${variantId} of AccessRuleNode`);
+  }
+};
+
+export type AddLiquidityEvent = [Array<[string, string]>];
+
+export const encodeAddLiquidityEvent = (value: AddLiquidityEvent): ManifestValue =>
+  encodeTuple([encodeArray("Tuple", value[0].map((element) => encodeTuple([encodeAddress(element[0]), encodeDecimal(element[1])])))]);
+
+export const decodeAddLiquidityEvent = (value: ProgrammaticValue): AddLiquidityEvent => {
+  const fields = decodeTuple(value);
+  return [decodeArray(fields[0], (element) => ((fields: ProgrammaticValue[]): [string, string] => [decodeReference(fields[0]), decodeDecimal(fields[1])])(decodeTuple(element)))];
+};
+
+export interface InstantiationEvent {
+  owner_role: OwnerRole;
+  resource_address1: string;
+  resource_address2: string;
+  component_address: string;
+}
+
+export const encodeInstantiationEvent = (value: InstantiationEvent): ManifestValue =>
+  encodeTuple([encodeOwnerRole(value.owner_role), encodeAddress(value.resource_address1), encodeAddress(value.resource_address2), encodeAddress(value.component_address)]);
+
+export const decodeInstantiationEvent = (value: ProgrammaticValue): InstantiationEvent => {
+  const fields = decodeTuple(value);
+  return {
+    owner_role: decodeOwnerRole(fields[0]),
+    resource_address1: decodeReference(fields[1]),
+    resource_address2: decodeReference(fields[2]),
+    component_address: decodeReference(fields[3]),
+  };
+};
+
+export interface NonFungibleGlobalId {
+  resource_address: string;
+  local_id: string;
+}
+
+export const encodeNonFungibleGlobalId = (value: NonFungibleGlobalId): ManifestValue =>
+  encodeTuple([encodeAddress(value.resource_address), encodeNonFungibleLocalId(value.local_id)]);
+
+export const decodeNonFungibleGlobalId = (value: ProgrammaticValue): NonFungibleGlobalId => {
+  const fields = decodeTuple(value);
+  return {
+    resource_address: decodeReference(fields[0]),
+    local_id: decodeNonFungibleLocalId(fields[1]),
+  };
+};
+
+export type OwnerRole =
+  | { variant: "None" }
+  | { variant: "Fixed"; fields: [AccessRule] }
+  | { variant: "Updatable"; fields: [AccessRule] };
+
+export const encodeOwnerRole = (value: OwnerRole): ManifestValue => {
+  switch (value.variant) {
+    case "None":
+      return encodeEnum(0, []);
+    case "Fixed":
+      return encodeEnum(1, [encodeAccessRule(value.fields[0])]);
+    case "Updatable":
+      return encodeEnum(2, [encodeAccessRule(value.fields[0])]);
+  }
+};
+
+export const decodeOwnerRole = (value: ProgrammaticValue): OwnerRole => {
+  const [variantId, fields] = decodeEnum(value);
+  switch (variantId) {
+    case 0:
+      return { variant: "None" };
+    case 1:
+      return { variant: "Fixed", fields: [decodeAccessRule(fields[0])] };
+    case 2:
+      return { variant: "Updatable", fields: [decodeAccessRule(fields[0])] };
+    default:
+      throw new Error(`Unknown variant ${variantId} of OwnerRole`);
+  }
+};
+
+export type ProofRule =
+  | { variant: "Require"; fields: [ResourceOrNonFungible] }
+  | { variant: "AmountOf"; fields: [string, string] }
+  | { variant: "CountOf"; fields: [number, Array<ResourceOrNonFungible>] }
+  | { variant: "AllOf"; fields: [Array<ResourceOrNonFungible>] }
+  | { variant: "AnyOf"; fields: [Array<ResourceOrNonFungible>] };
+
+export const encodeProofRule = (value: ProofRule): ManifestValue => {
+  switch (value.variant) {
+    case "Require":
+      return encodeEnum(0, [encodeResourceOrNonFungible(value.fields[0])]);
+    case "AmountOf":
+      return encodeEnum(1, [encodeDecimal(value.fields[0]), encodeAddress(value.fields[1])]);
+    case "CountOf":
+      return encodeEnum(2, [encodeInteger("U8", value.fields[0]), encodeArray("Enum", value.fields[1].map((element) => encodeResourceOrNonFungible(element)))]);
+    case "AllOf":
+      return encodeEnum(3, [encodeArray("Enum", value.fields[0].map((element) => encodeResourceOrNonFungible(element)))]);
+    case "AnyOf":
+      return encodeEnum(4, [encodeArray("Enum", value.fields[0].map((element) => encodeResourceOrNonFungible(element)))]);
+  }
+};
+
+export const decodeProofRule = (value: ProgrammaticValue): ProofRule => {
+  const [variantId, fields] = decodeEnum(value);
+  switch (variantId) {
+    case 0:
+      return { variant: "Require", fields: [decodeResourceOrNonFungible(fields[0])] };
+    case 1:
+      return { variant: "AmountOf", fields: [decodeDecimal(fields[0]), decodeReference(fields[1])] };
+    case 2:
+      return { variant: "CountOf", fields: [decodeInteger("U8", fields[0]), decodeArray(fields[1], (element) => decodeResourceOrNonFungible(element))] };
+    case 3:
+      return { variant: "AllOf", fields: [decodeArray(fields[0], (element) => decodeResourceOrNonFungible(element))] };
+    case 4:
+      return { variant: "AnyOf", fields: [decodeArray(fields[0], (element) => decodeResourceOrNonFungible(element))] };
+    default:
+      throw new Error(`Unknown variant ${variantId} of ProofRule`);
+  }
+};
+
+export interface Radiswap {
+  pool_component: string;
+}
+
+export const encodeRadiswap = (value: Radiswap): ManifestValue =>
+  encodeTuple([encodeAddress(value.pool_component)]);
+
+export const decodeRadiswap = (value: ProgrammaticValue): Radiswap => {
+  const fields = decodeTuple(value);
+  return {
+    pool_component: decodeReference(fields[0]),
+  };
+};
+
+export interface RemoveLiquidityEvent {
+  pool_units_amount: string;
+  redeemed_resources: Array<[string, string]>;
+}
+
+export const encodeRemoveLiquidityEvent = (value: RemoveLiquidityEvent): ManifestValue =>
+  encodeTuple([encodeDecimal(value.pool_units_amount), encodeArray("Tuple", value.redeemed_resources.map((element) => encodeTuple([encodeAddress(element[0]), encodeDecimal(element[1])])))]);
+
+export const decodeRemoveLiquidityEvent = (value: ProgrammaticValue): RemoveLiquidityEvent => {
+  const fields = decodeTuple(value);
+  return {
+    pool_units_amount: decodeDecimal(fields[0]),
+    redeemed_resources: decodeArray(fields[1], (element) => ((fields: ProgrammaticValue[]): [string, string] => [decodeReference(fields[0]), decodeDecimal(fields[1])])(decodeTuple(element))),
+  };
+};
+
+export type ResourceOrNonFungible =
+  | { variant: "NonFungible"; fields: [NonFungibleGlobalId] }
+  | { variant: "Resource"; fields: [string] };
+
+export const encodeResourceOrNonFungible = (value: ResourceOrNonFungible): ManifestValue => {
+  switch (value.variant) {
+    case "NonFungible":
+      return encodeEnum(0, [encodeNonFungibleGlobalId(value.fields[0])]);
+    case "Resource":
+      return encodeEnum(1, [encodeAddress(value.fields[0])]);
+  }
+};
+
+export const decodeResourceOrNonFungible = (value: ProgrammaticValue): ResourceOrNonFungible => {
+  const [variantId, fields] = decodeEnum(value);
+  switch (variantId) {
+    case 0:
+      return { variant: "NonFungible", fields: [decodeNonFungibleGlobalId(fields[0])] };
+    case 1:
+      return { variant: "Resource", fields: [decodeReference(fields[0])] };
+    default:
+      throw new Error(`Unknown variant ${variantId} of ResourceOrNonFungible`);
+  }
+};
+
+export interface SwapEvent {
+  input: [string, string];
+  output: [string, string];
+}
+
+export const encodeSwapEvent = (value: SwapEvent): ManifestValue =>
+  encodeTuple([encodeTuple([encodeAddress(value.input[0]), encodeDecimal(value.input[1])]), encodeTuple([encodeAddress(value.output[0]), encodeDecimal(value.output[1])])]);
+
+export const decodeSwapEvent = (value: ProgrammaticValue): SwapEvent => {
+  const fields = decodeTuple(value);
+  return {
+    input: ((fields: ProgrammaticValue[]): [string, string] => [decodeReference(fields[0]), decodeDecimal(fields[1])])(decodeTuple(fields[0])),
+    output: ((fields: ProgrammaticValue[]): [string, string] => [decodeReference(fields[0]), decodeDecimal(fields[1])])(decodeTuple(fields[1])),
+  };
+};
+
+export type TimeCondition =
+  | { variant: "EpochAtLeast"; fields: [bigint] }
+  | { variant: "EpochBefore"; fields: [bigint] }
+  | { variant: "TimeAtLeast"; fields: [bigint] }
+  | { variant: "TimeBefore"; fields: [bigint] };
+
+export const encodeTimeCondition = (value: TimeCondition): ManifestValue => {
+  switch (value.variant) {
+    case "EpochAtLeast":
+      return encodeEnum(0, [encodeInteger("U64", value.fields[0])]);
+    case "EpochBefore":
+      return encodeEnum(1, [encodeInteger("U64", value.fields[0])]);
+    case "TimeAtLeast":
+      return encodeEnum(2, [encodeInteger("I64", value.fields[0])]);
+    case "TimeBefore":
+      return encodeEnum(3, [encodeInteger("I64", value.fields[0])]);
+  }
+};
+
+export const decodeTimeCondition = (value: ProgrammaticValue): TimeCondition => {
+  const [variantId, fields] = decodeEnum(value);
+  switch (variantId) {
+    case 0:
+      return { variant: "EpochAtLeast", fields: [decodeBigInteger("U64", fields[0])] };
+    case 1:
+      return { variant: "EpochBefore", fields: [decodeBigInteger("U64", fields[0])] };
+    case 2:
+      return { variant: "TimeAtLeast", fields: [decodeBigInteger("I64", fields[0])] };
+    case 3:
+      return { variant: "TimeBefore", fields: [decodeBigInteger("I64", fields[0])] };
+    default:
+      throw new Error(`Unknown variant ${variantId} of TimeCondition`);
+  }
+};
+
+/** The functions of the `Radiswap` blueprint. */
+export class RadiswapFunctions {
+  constructor(readonly packageAddress: string) {}
+
+  /** Returns the manifest instruction calling `new`. */
+  new(owner_role: OwnerRole, resource_address1: string, resource_address2: string): string {
+    return callFunction(this.packageAddress, "Radiswap", "new", [encodeOwnerRole(owner_role), encodeAddress(resource_address1), encodeAddress(resource_address2)]);
+  }
+
+  static decodeNewOutput(value: ProgrammaticValue): string {
+    return decodeReference(value);
+  }
+}
+
+/** The methods of a `Radiswap` component. */
+export class RadiswapComponent {
+  constructor(readonly address: string) {}
+
+  /** Returns the manifest instruction calling `add_liquidity`. */
+  add_liquidity(resource1: string, resource2: string): string {
+    return callMethod(this.address, "add_liquidity", [encodeBucket(resource1), encodeBucket(resource2)]);
+  }
+
+  static decodeAddLiquidityOutput(value: ProgrammaticValue): [string, string | null] {
+    return ((fields: ProgrammaticValue[]): [string, string | null] => [decodeOwn(fields[0]), decodeOption(fields[1], (some) => decodeOwn(some))])(decodeTuple(value));
+  }
+
+  /** Returns the manifest instruction calling `remove_liquidity`. */
+  remove_liquidity(pool_units: string): string {
+    return callMethod(this.address, "remove_liquidity", [encodeBucket(pool_units)]);
+  }
+
+  static decodeRemoveLiquidityOutput(value: ProgrammaticValue): [string, string] {
+    return ((fields: ProgrammaticValue[]): [string, string] => [decodeOwn(fields[0]), decodeOwn(fields[1])])(decodeTuple(value));
+  }
+
+  /** Returns the manifest instruction calling `swap`. */
+  swap(input_bucket: string): string {
+    return callMethod(this.address, "swap", [encodeBucket(input_bucket)]);
+  }
+
+  static decodeSwapOutput(value: ProgrammaticValue): string {
+    return decodeOwn(value);
+  }
+}
+
+export const RadiswapEvents = {
+  AddLiquidityEvent: (value: ProgrammaticValue): AddLiquidityEvent => decodeAddLiquidityEvent(value),
+  InstantiationEvent: (value: ProgrammaticValue): InstantiationEvent => decodeInstantiationEvent(value),
+  RemoveLiquidityEvent: (value: ProgrammaticValue): RemoveLiquidityEvent => decodeRemoveLiquidityEvent(value),
+  SwapEvent: (value: ProgrammaticValue): SwapEvent => decodeSwapEvent(value),
+};
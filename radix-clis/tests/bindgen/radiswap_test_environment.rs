@@ -0,0 +1 @@
+# [derive (Clone , Copy , Debug , PartialEq , Eq , Hash)] pub struct RadiswapBlueprint (pub PackageAddress) ; impl RadiswapBlueprint { pub fn new < D > (& self , owner_role : OwnerRole , resource_address1 : ResourceAddress , resource_address2 : ResourceAddress , env : & mut TestEnvironment < D >) -> Result < ComponentAddress , RuntimeError > where D : SubstateDatabase + CommittableSubstateDatabase + 'static , { env . call_function_typed :: < _ , ComponentAddress > (self . 0 , "Radiswap" , "new" , & (owner_role , resource_address1 , resource_address2 ,) ,) } } # [derive (Clone , Copy , Debug , PartialEq , Eq , Hash)] pub struct RadiswapComponent (pub NodeId) ; impl RadiswapComponent { pub fn add_liquidity < D > (& self , resource1 : Bucket , resource2 : Bucket , env : & mut TestEnvironment < D >) -> Result < (Bucket , Option < Bucket >,) , RuntimeError > where D : SubstateDatabase + CommittableSubstateDatabase + 'static , { env . call_method_typed :: < _ , _ , (Bucket , Option < Bucket >,) > (self . 0 , "add_liquidity" , & (resource1 , resource2 ,) ,) } pub fn remove_liquidity < D > (& self , pool_units : Bucket , env : & mut TestEnvironment < D >) -> Result < (Bucket , Bucket ,) , RuntimeError > where D : SubstateDatabase + CommittableSubstateDatabase + 'static , { env . call_method_typed :: < _ , _ , (Bucket , Bucket ,) > (self . 0 , "remove_liquidity" , & (pool_units ,) ,) } pub fn swap < D > (& self , input_bucket : Bucket , env : & mut TestEnvironment < D >) -> Result < Bucket , RuntimeError > where D : SubstateDatabase + CommittableSubstateDatabase + 'static , { env . call_method_typed :: < _ , _ , Bucket > (self . 0 , "swap" , & (input_bucket ,) ,) } } # [derive (ScryptoSbor)] pub struct Radiswap { pool_component : ComponentAddress }
@@ -0,0 +1,29 @@
+#!/bin/bash
+
+# A minimal external signer for the `--signer-command` options, which signs with the Ed25519 key
+# of the given PEM file over the line-based JSON protocol of the `ExternalSigner`.
+# Usage: external_signer.sh <private_key.pem>
+
+set -e
+
+key="$1"
+hash_file=`mktemp`
+trap 'rm -f "$hash_file"' EXIT
+public_key=`openssl pkey -in "$key" -pubout -outform DER | tail -c 32 | xxd -p -c 32`
+
+while read -r request; do
+    case "$request" in
+        *'"method":"public_key"'*)
+            echo "{\"curve\":\"ed25519\",\"public_key\":\"$public_key\"}"
+            ;;
+        *'"method":"sign"'*)
+            hash=`echo "$request" | sed -E 's/.*"hash":"([0-9a-f]+)".*/\1/'`
+            echo -n "$hash" | xxd -r -p > "$hash_file"
+            signature=`openssl pkeyutl -sign -inkey "$key" -rawin -in "$hash_file" | xxd -p -c 64`
+            echo "{\"signature\":\"$signature\"}"
+            ;;
+        *)
+            echo '{"error":"Unsupported request"}'
+            ;;
+    esac
+done
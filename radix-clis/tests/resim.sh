@@ -79,6 +79,16 @@ $resim show $account
 $resim show $account2
 $resim show $token_address
 
+# Test - check metadata against the metadata standards
+$resim show $account --check-metadata
+
+# Test - intent status (transactions run by resim aren't notarized, and so are never tracked)
+intent_status=`$resim intent-status 0000000000000000000000000000000000000000000000000000000000000000`
+if [[ ${intent_status} != *"Not tracked"* ]];then
+    echo "Intent status not reported!"
+    exit 1
+fi
+
 # Test - output manifest
 mkdir -p target
 $resim new-badge-fixed 1 --name 'MintBadge' --manifest ./target/temp.rtm
@@ -92,7 +102,13 @@ $resim run ./target/temp3.rtm
 
 # Test - run manifest with a given set of signing keys
 $resim generate-key-pair
+derived_key=`$resim generate-key-pair --mnemonic "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about" --index 1 | awk '/Private key:/ {print $NF}'`
 $resim run ./target/temp2.rtm --blobs $blobs
+$resim run ./target/temp3.rtm --signing-keys $derived_key
+
+# Test - run manifest with an external signer
+openssl genpkey -algorithm ed25519 -out target/signer.pem
+$resim run ./target/temp3.rtm --signer-command "bash ./tests/external_signer.sh target/signer.pem"
 
 # Test - nft
 package=`$resim publish ./tests/blueprints --owner-badge $owner_badge | awk '/Package:/ {print $NF}'`
@@ -128,3 +144,10 @@ token_address=`$resim new-token-mutable $non_fungible_global_id | awk '/Resource
 
 # Test - mint and transfer (Mintable that requires a `NonFungibleGlobalId`)
 $resim mint 777 $token_address --proofs "$non_fungible_global_id"
+
+# Test - dry run a protocol update
+dry_run_report=`$resim dry-run-protocol-update ./tests/validator_creation_cost.sbor`
+if [[ ${dry_run_report} != *"validator-creation-cost"* ]];then
+    echo "Protocol update not dry run!"
+    exit 1
+fi
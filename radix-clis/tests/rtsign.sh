@@ -0,0 +1,37 @@
+#!/bin/bash
+
+set -x
+set -e
+
+cd "$(dirname "$0")/.."
+
+rtsign="cargo run --bin rtsign $@ --"
+
+notary_private_key=0000000000000000000000000000000000000000000000000000000000000001
+notary_public_key=0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798
+mkdir -p target
+
+# Test - create a partially signed transaction
+$rtsign create ./tests/rtsign.rtm --output target/rtsign.pst --notary-public-key $notary_public_key --start-epoch 0 --end-epoch 100 --message "Hello"
+$rtsign inspect target/rtsign.pst
+
+# Test - sign copies of the transaction with different curves and merge them
+$rtsign sign target/rtsign.pst --private-key 0000000000000000000000000000000000000000000000000000000000000002 --output target/rtsign_signer1.pst
+$rtsign sign target/rtsign.pst --private-key 0000000000000000000000000000000000000000000000000000000000000003 --curve ed25519 --output target/rtsign_signer2.pst
+$rtsign merge target/rtsign_signer1.pst target/rtsign_signer2.pst --output target/rtsign_merged.pst
+inspection=`$rtsign inspect target/rtsign_merged.pst`
+if [[ ${inspection} != *"Signatures: 2"* ]];then
+    echo "Signatures not merged!"
+    exit 1
+fi
+
+# Test - sign with an external signer
+openssl genpkey -algorithm ed25519 -out target/signer.pem
+$rtsign sign target/rtsign_merged.pst --signer-command "bash ./tests/external_signer.sh target/signer.pem"
+
+# Test - notarize the transaction
+$rtsign finalize target/rtsign_merged.pst --notary-private-key $notary_private_key --output target/rtsign.txn
+if [ ! -s target/rtsign.txn ]; then
+    echo "Notarized transaction not written!"
+    exit 1
+fi
@@ -0,0 +1,26 @@
+#!/bin/bash
+
+set -x
+set -e
+
+cd "$(dirname "$0")/.."
+
+sbor_codegen="cargo run --bin sbor-codegen $@ --"
+
+mkdir -p target
+
+# Test - generate the types of a hex-encoded schema
+generated=`$sbor_codegen ./tests/resource_preference_schema.hex --hex`
+if [[ ${generated} != *"pub struct AccountSetResourcePreferenceInput"* ]];then
+    echo "Struct not generated!"
+    exit 1
+fi
+if [[ ${generated} != *"pub enum ResourcePreference"* ]];then
+    echo "Enum not generated!"
+    exit 1
+fi
+
+# Test - generate the types of a raw schema into a file
+xxd -r -p ./tests/resource_preference_schema.hex > target/resource_preference_schema.sbor
+$sbor_codegen target/resource_preference_schema.sbor --derive Debug --output target/resource_preference.rs
+cat target/resource_preference.rs
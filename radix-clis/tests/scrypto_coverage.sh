@@ -19,12 +19,21 @@ $scrypto new-package hello-world --path $test_pkg --local
 $scrypto coverage --path $test_pkg
 
 # Check if coverage report was generated
-if [ -f "$test_pkg/coverage/report/index.html" ]; then
+if [ -f "$test_pkg/coverage/report/index.html" ] && [ -f "$test_pkg/coverage/report/lcov.info" ]; then
     echo "Coverage report generated successfully."
 else
     echo "Error: Coverage report not found."
     exit 1
 fi
 
+# Merge the coverage of another run and check the threshold
+$scrypto coverage --path $test_pkg --keep-data --fail-under 50 -- test_hello
+
+# Check that an unreachable threshold fails
+if $scrypto coverage --path $test_pkg --fail-under 100.1; then
+    echo "Error: Coverage threshold was not enforced."
+    exit 1
+fi
+
 # Clean up
 rm -fr $test_pkg
\ No newline at end of file
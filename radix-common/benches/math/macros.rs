@@ -85,12 +85,43 @@ macro_rules! ops_root_fn {
     };
 }
 
+#[macro_export]
+macro_rules! ops_transcendental_fn {
+    ($t:ty) => {
+        paste::item! {
+            fn [< $t:lower _ln >](a: &$t, _: &str) -> $t {
+                a.checked_ln(RoundingMode::ToNearestMidpointToEven).unwrap()
+            }
+
+            fn [< $t:lower _exp >](a: &$t, _: &str) -> $t {
+                a.checked_exp(RoundingMode::ToNearestMidpointToEven).unwrap()
+            }
+
+            fn [< $t:lower _log >](a: &$t, base: &$t) -> $t {
+                a.checked_log(*base, RoundingMode::ToNearestMidpointToEven).unwrap()
+            }
+
+            fn [< $t:lower _pow_decimal >](a: &$t, exp: &$t) -> $t {
+                a.checked_pow(*exp, RoundingMode::ToNearestMidpointToEven).unwrap()
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! process_op {
     ($t:ty, $i:ident, $op:ident, $bid:ident, "to_string") => {
         let $bid = format!("{}", $i);
         let $op = (<$t>::from_str(*$op).unwrap(), "_");
     };
+    ($t:ty, $i:ident, $op:ident, $bid:ident, "ln") => {
+        let $bid = format!("{}", $i);
+        let $op = (<$t>::from_str(*$op).unwrap(), "_");
+    };
+    ($t:ty, $i:ident, $op:ident, $bid:ident, "exp") => {
+        let $bid = format!("{}", $i);
+        let $op = (<$t>::from_str(*$op).unwrap(), "_");
+    };
     ($t:ty, $i:ident, $op:ident, $bid:ident, "from_string") => {
         let $bid = format!("{}", $i);
         let $op = ($op, "_");
@@ -22,6 +22,10 @@ criterion_group! {
         bench_decimal_div,
         bench_decimal_root,
         bench_decimal_pow,
+        bench_decimal_ln,
+        bench_decimal_exp,
+        bench_decimal_log,
+        bench_decimal_pow_decimal,
         bench_decimal_from_string,
         bench_decimal_to_string,
 }
@@ -37,6 +41,10 @@ criterion_group! {
     bench_precisedecimal_div,
     bench_precisedecimal_root,
     bench_precisedecimal_pow,
+    bench_precisedecimal_ln,
+    bench_precisedecimal_exp,
+    bench_precisedecimal_log,
+    bench_precisedecimal_pow_decimal,
     bench_precisedecimal_from_string,
     bench_precisedecimal_to_string,
 }
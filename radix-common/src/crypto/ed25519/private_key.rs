@@ -1,5 +1,9 @@
 use super::Ed25519Signature;
 use crate::internal_prelude::*;
+#[cfg(feature = "ed25519_diffie_hellman")]
+use curve25519_dalek::{edwards::CompressedEdwardsY, scalar::Scalar};
+#[cfg(feature = "ed25519_diffie_hellman")]
+use ed25519_dalek::ExpandedSecretKey;
 use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
 use zeroize::Zeroize;
 
@@ -25,6 +29,27 @@ impl Ed25519PrivateKey {
         Ed25519Signature(keypair.sign(msg_hash.as_ref()).to_bytes())
     }
 
+    /// Performs X25519 Diffie-Hellman with the given public key, after mapping both keys from their
+    /// Edwards form to their Montgomery form (as per libsodium's `crypto_sign_ed25519_*_to_curve25519`).
+    ///
+    /// Returns the `u` co-ordinate of the shared point, or `Err` if the public key is not a valid
+    /// point, or the shared point is the identity.
+    #[cfg(feature = "ed25519_diffie_hellman")]
+    pub fn diffie_hellman(&self, public_key: &Ed25519PublicKey) -> Result<[u8; 32], ()> {
+        let montgomery_point = CompressedEdwardsY(public_key.0)
+            .decompress()
+            .ok_or(())?
+            .to_montgomery();
+        let mut scalar_bytes = [0u8; 32];
+        scalar_bytes.copy_from_slice(&ExpandedSecretKey::from(&self.0).to_bytes()[..32]);
+        let shared_secret = (montgomery_point * Scalar::from_bits(scalar_bytes)).to_bytes();
+        scalar_bytes.zeroize();
+        if shared_secret == [0u8; 32] {
+            return Err(());
+        }
+        Ok(shared_secret)
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes().to_vec()
     }
@@ -64,4 +89,20 @@ mod tests {
         assert_eq!(sk.sign(&test_message_hash), sig);
         assert!(verify_ed25519(&test_message_hash, &pk, &sig));
     }
+
+    #[test]
+    #[cfg(feature = "ed25519_diffie_hellman")]
+    fn diffie_hellman_is_symmetric() {
+        let sk1 = Ed25519PrivateKey::from_u64(1).unwrap();
+        let sk2 = Ed25519PrivateKey::from_u64(2).unwrap();
+
+        assert_eq!(
+            sk1.diffie_hellman(&sk2.public_key()).unwrap(),
+            sk2.diffie_hellman(&sk1.public_key()).unwrap()
+        );
+        // The identity point is rejected, as it results in an all-zero shared secret
+        let mut identity = [0u8; Ed25519PublicKey::LENGTH];
+        identity[0] = 1;
+        assert!(sk1.diffie_hellman(&Ed25519PublicKey(identity)).is_err());
+    }
 }
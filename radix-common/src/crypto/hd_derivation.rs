@@ -0,0 +1,307 @@
+use crate::internal_prelude::*;
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use zeroize::Zeroize;
+
+/// The offset of hardened indices in a derivation path.
+pub const HARDENED_INDEX_OFFSET: u32 = 0x8000_0000;
+
+/// The BIP-44 purpose.
+pub const BIP44_PURPOSE: u32 = 44;
+/// The SLIP-44 coin type of Radix.
+pub const RADIX_COIN_TYPE: u32 = 1022;
+/// The CAP-26 entity kind of accounts.
+pub const RADIX_ENTITY_KIND_ACCOUNT: u32 = 525;
+/// The CAP-26 entity kind of identities.
+pub const RADIX_ENTITY_KIND_IDENTITY: u32 = 618;
+/// The CAP-26 key kind of transaction signing keys.
+pub const RADIX_KEY_KIND_TRANSACTION_SIGNING: u32 = 1460;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HdDerivationError {
+    InvalidMnemonic(String),
+    InvalidDerivationPath(String),
+    /// SLIP-10 only supports hardened derivation for Ed25519.
+    NonHardenedEd25519Index(u32),
+    /// The derived key is invalid (which happens with negligible probability).
+    InvalidDerivedKey,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl std::error::Error for HdDerivationError {}
+
+impl fmt::Display for HdDerivationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Converts a BIP-39 mnemonic and (optionally empty) passphrase into a 64 byte seed.
+///
+/// Only English mnemonics are supported, and the checksum of the mnemonic is validated.
+pub fn bip39_seed_from_mnemonic(
+    mnemonic: &str,
+    passphrase: &str,
+) -> Result<[u8; 64], HdDerivationError> {
+    let mnemonic = bip39::Mnemonic::parse(mnemonic)
+        .map_err(|err| HdDerivationError::InvalidMnemonic(err.to_string()))?;
+    Ok(mnemonic.to_seed(passphrase))
+}
+
+/// A BIP-32 derivation path, e.g. `m/44H/1022H/1H/525H/1460H/0H`.
+///
+/// Hardened indices are stored with the [`HARDENED_INDEX_OFFSET`] added, and can be written with
+/// either a `H` or a `'` suffix.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DerivationPath(pub Vec<u32>);
+
+impl DerivationPath {
+    /// The CAP-26 path of the transaction signing key of the account with the given index, as
+    /// used by the Radix Babylon wallet: `m/44H/1022H/<network_id>H/525H/1460H/<index>H`.
+    pub fn radix_account(network_id: u8, index: u32) -> Self {
+        Self::radix_entity(network_id, RADIX_ENTITY_KIND_ACCOUNT, index)
+    }
+
+    /// The CAP-26 path of the transaction signing key of the identity (persona) with the given
+    /// index: `m/44H/1022H/<network_id>H/618H/1460H/<index>H`.
+    pub fn radix_identity(network_id: u8, index: u32) -> Self {
+        Self::radix_entity(network_id, RADIX_ENTITY_KIND_IDENTITY, index)
+    }
+
+    fn radix_entity(network_id: u8, entity_kind: u32, index: u32) -> Self {
+        Self(vec![
+            BIP44_PURPOSE | HARDENED_INDEX_OFFSET,
+            RADIX_COIN_TYPE | HARDENED_INDEX_OFFSET,
+            network_id as u32 | HARDENED_INDEX_OFFSET,
+            entity_kind | HARDENED_INDEX_OFFSET,
+            RADIX_KEY_KIND_TRANSACTION_SIGNING | HARDENED_INDEX_OFFSET,
+            index | HARDENED_INDEX_OFFSET,
+        ])
+    }
+
+    /// The BIP-44 path of the secp256k1 key of the Olympia account with the given index:
+    /// `m/44H/1022H/0H/0/<index>H`.
+    pub fn radix_olympia_account(index: u32) -> Self {
+        Self(vec![
+            BIP44_PURPOSE | HARDENED_INDEX_OFFSET,
+            RADIX_COIN_TYPE | HARDENED_INDEX_OFFSET,
+            HARDENED_INDEX_OFFSET,
+            0,
+            index | HARDENED_INDEX_OFFSET,
+        ])
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = HdDerivationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || HdDerivationError::InvalidDerivationPath(s.to_owned());
+        let mut components = s.split('/');
+        if components.next() != Some("m") {
+            return Err(invalid());
+        }
+        components
+            .map(|component| {
+                let (index, hardened) = match component.strip_suffix(['H', '\'']) {
+                    Some(index) => (index, true),
+                    None => (component, false),
+                };
+                let index = index.parse::<u32>().map_err(|_| invalid())?;
+                if index >= HARDENED_INDEX_OFFSET {
+                    return Err(invalid());
+                }
+                Ok(if hardened {
+                    index | HARDENED_INDEX_OFFSET
+                } else {
+                    index
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            if *index >= HARDENED_INDEX_OFFSET {
+                write!(f, "/{}H", index - HARDENED_INDEX_OFFSET)?;
+            } else {
+                write!(f, "/{}", index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for data in data {
+        mac.update(data);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// Derives an Ed25519 private key from a seed, as per SLIP-10. All indices must be hardened.
+pub fn derive_ed25519_private_key(
+    seed: &[u8],
+    path: &DerivationPath,
+) -> Result<Ed25519PrivateKey, HdDerivationError> {
+    let mut node = hmac_sha512(b"ed25519 seed", &[seed]);
+    for index in &path.0 {
+        if *index < HARDENED_INDEX_OFFSET {
+            node.zeroize();
+            return Err(HdDerivationError::NonHardenedEd25519Index(*index));
+        }
+        let child = hmac_sha512(&node[32..], &[&[0u8], &node[..32], &index.to_be_bytes()]);
+        node.zeroize();
+        node = child;
+    }
+    let private_key = Ed25519PrivateKey::from_bytes(&node[..32])
+        .map_err(|_| HdDerivationError::InvalidDerivedKey);
+    node.zeroize();
+    private_key
+}
+
+/// Derives a secp256k1 private key from a seed, as per BIP-32.
+#[cfg(feature = "secp256k1_sign_and_validate")]
+pub fn derive_secp256k1_private_key(
+    seed: &[u8],
+    path: &DerivationPath,
+) -> Result<Secp256k1PrivateKey, HdDerivationError> {
+    let mut node = hmac_sha512(b"Bitcoin seed", &[seed]);
+    let mut private_key = Secp256k1PrivateKey::from_bytes(&node[..32])
+        .map_err(|_| HdDerivationError::InvalidDerivedKey)?;
+    for index in &path.0 {
+        let mut child = if *index >= HARDENED_INDEX_OFFSET {
+            hmac_sha512(&node[32..], &[&[0u8], &node[..32], &index.to_be_bytes()])
+        } else {
+            hmac_sha512(
+                &node[32..],
+                &[&private_key.public_key().0, &index.to_be_bytes()],
+            )
+        };
+        // The child key is the parent key plus the left half of the HMAC, modulo the curve order
+        let tweak = secp256k1::Scalar::from_be_bytes(child[..32].try_into().unwrap())
+            .map_err(|_| HdDerivationError::InvalidDerivedKey)?;
+        let child_key = secp256k1::SecretKey::from_slice(&node[..32])
+            .and_then(|parent_key| parent_key.add_tweak(&tweak))
+            .map_err(|_| HdDerivationError::InvalidDerivedKey)?;
+        child[..32].copy_from_slice(&child_key.secret_bytes());
+        node.zeroize();
+        node = child;
+        private_key = Secp256k1PrivateKey::from_bytes(&node[..32])
+            .map_err(|_| HdDerivationError::InvalidDerivedKey)?;
+    }
+    node.zeroize();
+    Ok(private_key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABANDON_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_bip39_seed_from_mnemonic() {
+        // From the BIP-39 test vectors
+        let seed = bip39_seed_from_mnemonic(ABANDON_MNEMONIC, "TREZOR").unwrap();
+        assert_eq!(
+            hex::encode(seed),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+
+        assert!(matches!(
+            bip39_seed_from_mnemonic(&ABANDON_MNEMONIC.replace("about", "abandon"), ""),
+            Err(HdDerivationError::InvalidMnemonic(_))
+        ));
+    }
+
+    #[test]
+    fn test_derivation_path_parsing_and_display() {
+        let path = DerivationPath::from_str("m/44'/1022'/0'/0/5H").unwrap();
+        assert_eq!(path, DerivationPath::radix_olympia_account(5));
+        assert_eq!(path.to_string(), "m/44H/1022H/0H/0/5H");
+        assert_eq!(
+            DerivationPath::radix_account(1, 2).to_string(),
+            "m/44H/1022H/1H/525H/1460H/2H"
+        );
+        assert_eq!(
+            DerivationPath::radix_identity(1, 2).to_string(),
+            "m/44H/1022H/1H/618H/1460H/2H"
+        );
+
+        for invalid in ["", "44H/1022H", "m/44H/", "m/2147483648", "m/x"] {
+            assert!(DerivationPath::from_str(invalid).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_slip10_ed25519_derivation() {
+        // From the SLIP-10 Ed25519 test vector 1
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let path = DerivationPath::from_str("m/0H/1H/2H/2H/1000000000H").unwrap();
+        assert_eq!(
+            hex::encode(derive_ed25519_private_key(&seed, &path).unwrap().to_bytes()),
+            "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793"
+        );
+
+        assert_eq!(
+            derive_ed25519_private_key(&seed, &DerivationPath::from_str("m/0H/1").unwrap()).err(),
+            Some(HdDerivationError::NonHardenedEd25519Index(1))
+        );
+    }
+
+    #[cfg(feature = "secp256k1_sign_and_validate")]
+    #[test]
+    fn test_bip32_secp256k1_derivation() {
+        // From the BIP-32 test vector 1
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let path = DerivationPath::from_str("m/0H/1/2H/2/1000000000").unwrap();
+        assert_eq!(
+            hex::encode(
+                derive_secp256k1_private_key(&seed, &path)
+                    .unwrap()
+                    .to_bytes()
+            ),
+            "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8"
+        );
+    }
+
+    #[test]
+    fn test_radix_account_derivation() {
+        // From the Ed25519 test vectors of the Radix Babylon Ledger app, which derives the same
+        // keys as the wallet
+        let seed = bip39_seed_from_mnemonic(
+            "equip will roof matter pink blind book anxiety banner elbow sun young",
+            "",
+        )
+        .unwrap();
+        for (index, public_key) in [
+            (
+                0,
+                "451152a1cef7be603205086d4ebac0a0b78fda2ff4684b9dea5ca9ef003d4e7d",
+            ),
+            (
+                1,
+                "0a4b894208a1f6b1bd7e823b59909f01aae0172b534baa2905b25f1bcbbb4f0a",
+            ),
+        ] {
+            let path = DerivationPath::radix_account(12, index);
+            assert_eq!(
+                path.to_string(),
+                format!("m/44H/1022H/12H/525H/1460H/{}H", index)
+            );
+            assert_eq!(
+                derive_ed25519_private_key(&seed, &path)
+                    .unwrap()
+                    .public_key()
+                    .to_string(),
+                public_key
+            );
+        }
+    }
+}
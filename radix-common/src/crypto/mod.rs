@@ -3,6 +3,8 @@ mod bls12381;
 mod ed25519;
 mod hash;
 mod hash_accumulator;
+#[cfg(feature = "hd_key_derivation")]
+mod hd_derivation;
 mod keccak256;
 mod public_key;
 mod public_key_hash;
@@ -13,6 +15,8 @@ pub use self::bls12381::*;
 pub use self::ed25519::*;
 pub use self::hash::*;
 pub use self::hash_accumulator::*;
+#[cfg(feature = "hd_key_derivation")]
+pub use self::hd_derivation::*;
 pub use self::keccak256::*;
 pub use self::public_key::*;
 pub use self::public_key_hash::*;
@@ -41,6 +41,19 @@ impl Secp256k1PrivateKey {
         Secp256k1Signature(buf)
     }
 
+    /// Performs static Diffie-Hellman with the given public key.
+    ///
+    /// Returns the unhashed `x` co-ordinate of the shared point (the ASN1 X9.63 variant of ECDH),
+    /// or `Err` if the public key is not a valid point.
+    pub fn diffie_hellman(&self, public_key: &Secp256k1PublicKey) -> Result<[u8; 32], ()> {
+        let public_key = PublicKey::from_slice(&public_key.0).map_err(|_| ())?;
+        let mut shared_point = secp256k1::ecdh::shared_secret_point(&public_key, &self.0 .0);
+        let mut shared_secret = [0u8; 32];
+        shared_secret.copy_from_slice(&shared_point[..32]);
+        shared_point.zeroize();
+        Ok(shared_secret)
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0 .0.secret_bytes().to_vec()
     }
@@ -118,4 +131,18 @@ mod tests {
             SecretKeyWrapper::default().0.secret_bytes()
         );
     }
+
+    #[test]
+    fn diffie_hellman_is_symmetric() {
+        let sk1 = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let sk2 = Secp256k1PrivateKey::from_u64(2).unwrap();
+        let shared_secret = sk1.diffie_hellman(&sk2.public_key()).unwrap();
+
+        assert_eq!(
+            shared_secret,
+            sk2.diffie_hellman(&sk1.public_key()).unwrap()
+        );
+        // [1][2]G = [2]G, so the shared secret is the x co-ordinate of sk2's public key
+        assert_eq!(shared_secret[..], sk2.public_key().0[1..]);
+    }
 }
@@ -87,12 +87,142 @@ impl SerializableCustomExtension for ManifestCustomExtension {
     }
 }
 
+/// The inverse of the [`ManifestValueDisplayContext`] - resolving the names which buckets, proofs,
+/// address reservations and named addresses may have been serialized with.
+#[derive(Clone, Copy, Default)]
+pub struct ManifestValueDeserializationContext<'a> {
+    /// If not provided, addresses of any network are accepted.
+    pub address_bech32_decoder: Option<&'a AddressBech32Decoder>,
+    pub bucket_ids: Option<&'a NonIterMap<String, ManifestBucket>>,
+    pub proof_ids: Option<&'a NonIterMap<String, ManifestProof>>,
+    pub address_reservation_ids: Option<&'a NonIterMap<String, ManifestAddressReservation>>,
+    pub address_ids: Option<&'a NonIterMap<String, u32>>,
+}
+
+impl<'a> ManifestValueDeserializationContext<'a> {
+    pub fn no_context() -> Self {
+        Self::default()
+    }
+
+    pub fn with_optional_bech32(address_bech32_decoder: Option<&'a AddressBech32Decoder>) -> Self {
+        Self {
+            address_bech32_decoder,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_bech32_and_names(
+        address_bech32_decoder: Option<&'a AddressBech32Decoder>,
+        bucket_ids: &'a NonIterMap<String, ManifestBucket>,
+        proof_ids: &'a NonIterMap<String, ManifestProof>,
+        address_reservation_ids: &'a NonIterMap<String, ManifestAddressReservation>,
+        address_ids: &'a NonIterMap<String, u32>,
+    ) -> Self {
+        Self {
+            address_bech32_decoder,
+            bucket_ids: Some(bucket_ids),
+            proof_ids: Some(proof_ids),
+            address_reservation_ids: Some(address_reservation_ids),
+            address_ids: Some(address_ids),
+        }
+    }
+}
+
+impl DeserializableCustomExtension for ManifestCustomExtension {
+    type CustomValue = ManifestCustomValue;
+    type CustomDeserializationContext<'a> = ManifestValueDeserializationContext<'a>;
+
+    fn deserialize_custom_value(
+        context: &Self::CustomDeserializationContext<'_>,
+        custom_value_kind: Self::CustomValueKind,
+        value: &str,
+    ) -> Result<Self::CustomValue, String> {
+        let custom_value = match custom_value_kind {
+            ManifestCustomValueKind::Address => {
+                ManifestCustomValue::Address(parse_manifest_address(context, value)?)
+            }
+            ManifestCustomValueKind::Bucket => ManifestCustomValue::Bucket(resolve_named_id(
+                context.bucket_ids,
+                value,
+                ManifestBucket,
+            )?),
+            ManifestCustomValueKind::Proof => ManifestCustomValue::Proof(resolve_named_id(
+                context.proof_ids,
+                value,
+                ManifestProof,
+            )?),
+            ManifestCustomValueKind::AddressReservation => {
+                ManifestCustomValue::AddressReservation(resolve_named_id(
+                    context.address_reservation_ids,
+                    value,
+                    ManifestAddressReservation,
+                )?)
+            }
+            ManifestCustomValueKind::Expression => ManifestCustomValue::Expression(match value {
+                "ENTIRE_WORKTOP" => ManifestExpression::EntireWorktop,
+                "ENTIRE_AUTH_ZONE" => ManifestExpression::EntireAuthZone,
+                _ => return Err(format!("Unknown expression: {}", value)),
+            }),
+            ManifestCustomValueKind::Blob => ManifestCustomValue::Blob(ManifestBlobRef(
+                hex::decode(value)
+                    .ok()
+                    .and_then(|bytes| bytes.try_into().ok())
+                    .ok_or_else(|| format!("Invalid blob hash: {}", value))?,
+            )),
+            ManifestCustomValueKind::Decimal => ManifestCustomValue::Decimal(from_decimal(
+                &Decimal::from_str(value).map_err(|error| format!("{:?}", error))?,
+            )),
+            ManifestCustomValueKind::PreciseDecimal => {
+                ManifestCustomValue::PreciseDecimal(from_precise_decimal(
+                    &PreciseDecimal::from_str(value).map_err(|error| format!("{:?}", error))?,
+                ))
+            }
+            ManifestCustomValueKind::NonFungibleLocalId => {
+                ManifestCustomValue::NonFungibleLocalId(from_non_fungible_local_id(
+                    NonFungibleLocalId::from_str(value).map_err(|error| format!("{:?}", error))?,
+                ))
+            }
+        };
+        Ok(custom_value)
+    }
+}
+
+fn parse_manifest_address(
+    context: &ManifestValueDeserializationContext,
+    value: &str,
+) -> Result<ManifestAddress, String> {
+    if let Some(address_id) = context.address_ids.and_then(|ids| ids.get(value)) {
+        return Ok(ManifestAddress::Named(*address_id));
+    }
+    if let Some(node_id) = NodeId::try_from_hex(value) {
+        return Ok(ManifestAddress::Static(node_id));
+    }
+    if let Ok(address_id) = value.parse::<u32>() {
+        return Ok(ManifestAddress::Named(address_id));
+    }
+    parse_node_id(context.address_bech32_decoder, value).map(ManifestAddress::Static)
+}
+
+fn resolve_named_id<T: Copy>(
+    ids: Option<&NonIterMap<String, T>>,
+    value: &str,
+    from_id: fn(u32) -> T,
+) -> Result<T, String> {
+    if let Some(id) = ids.and_then(|ids| ids.get(value)) {
+        return Ok(*id);
+    }
+    value
+        .parse::<u32>()
+        .map(from_id)
+        .map_err(|_| format!("Unknown name: {}", value))
+}
+
 #[cfg(test)]
 #[cfg(feature = "serde")] // Ensures that VS Code runs this module with the features serde tag!
 mod tests {
     use super::*;
     use crate::address::test_addresses::*;
-    use crate::address::AddressBech32Encoder;
+    use crate::address::{AddressBech32Decoder, AddressBech32Encoder};
     use crate::types::*;
     use radix_rust::ContextualSerialize;
     use sbor::rust::vec;
@@ -283,6 +413,84 @@ mod tests {
 
         assert_natural_json_matches(&value, context, expected_natural);
         assert_programmatic_json_matches(&value, context, expected_programmatic);
+        assert_programmatic_json_round_trips(&value);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")] // Workaround for VS Code "Run Test" feature
+    fn test_named_ids_are_resolved_when_deserializing() {
+        let decoder = AddressBech32Decoder::for_simulator();
+        let mut bucket_ids = NonIterMap::new();
+        bucket_ids.insert("my_bucket".to_string(), ManifestBucket(3));
+        let context = ManifestValueDeserializationContext {
+            bucket_ids: Some(&bucket_ids),
+            ..ManifestValueDeserializationContext::with_optional_bech32(Some(&decoder))
+        };
+
+        let payload = deserialize_payload_from_json::<ManifestCustomExtension>(
+            &json!({
+                "kind": "Tuple",
+                "fields": [
+                    { "kind": "Bucket", "value": "my_bucket" },
+                    { "kind": "Bucket", "value": "1" },
+                ]
+            }),
+            &DeserializationParameters::Schemaless {
+                mode: SerializationMode::Programmatic,
+                custom_context: context,
+                depth_limit: MANIFEST_SBOR_V1_MAX_DEPTH,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            payload,
+            manifest_encode(&(ManifestBucket(3), ManifestBucket(1))).unwrap()
+        );
+
+        let error = deserialize_payload_from_json::<ManifestCustomExtension>(
+            &json!({
+                "kind": "Tuple",
+                "fields": [{ "kind": "Proof", "value": "my_bucket" }]
+            }),
+            &DeserializationParameters::Schemaless {
+                mode: SerializationMode::Programmatic,
+                custom_context: context,
+                depth_limit: MANIFEST_SBOR_V1_MAX_DEPTH,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(error.path, "$.fields[0].value");
+    }
+
+    fn assert_programmatic_json_round_trips<T: ManifestEncode>(value: &T) {
+        let payload = manifest_encode(&value).unwrap();
+        let encoder = AddressBech32Encoder::for_simulator();
+        let decoder = AddressBech32Decoder::for_simulator();
+        let json = to_value(
+            ManifestRawPayload::new_from_valid_slice(&payload).serializable(
+                SerializationParameters::Schemaless {
+                    mode: SerializationMode::Programmatic,
+                    custom_context: ManifestValueDisplayContext::with_optional_bech32(Some(
+                        &encoder,
+                    )),
+                    depth_limit: MANIFEST_SBOR_V1_MAX_DEPTH,
+                },
+            ),
+        )
+        .unwrap();
+
+        let deserialized = deserialize_payload_from_json::<ManifestCustomExtension>(
+            &json,
+            &DeserializationParameters::Schemaless {
+                mode: SerializationMode::Programmatic,
+                custom_context: ManifestValueDeserializationContext::with_optional_bech32(Some(
+                    &decoder,
+                )),
+                depth_limit: MANIFEST_SBOR_V1_MAX_DEPTH,
+            },
+        )
+        .unwrap();
+        assert_eq!(deserialized, payload);
     }
 
     fn assert_natural_json_matches<
@@ -17,6 +17,8 @@ mod display_context;
 
 pub use custom_extension::*;
 pub use custom_payload_wrappers::*;
+#[cfg(feature = "serde")]
+pub use custom_serde::*;
 pub use custom_traversal::*;
 pub use custom_value::*;
 pub use custom_value_kind::*;
@@ -33,6 +35,8 @@ pub mod prelude {
     // Private modules to include in prelude
     pub use super::custom_extension::*;
     pub use super::custom_payload_wrappers::*;
+    #[cfg(feature = "serde")]
+    pub use super::custom_serde::*;
     pub use super::custom_traversal::*;
     pub use super::custom_value::*;
     pub use super::custom_value_kind::*;
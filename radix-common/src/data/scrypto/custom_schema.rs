@@ -202,6 +202,59 @@ impl CustomSchema for ScryptoCustomSchema {
     }
 }
 
+impl RustCodegenCustomSchema for ScryptoCustomSchema {
+    const SBOR_DERIVE: &'static str = "ScryptoSbor";
+    const ANY_TYPE: &'static str = "ScryptoValue";
+
+    fn custom_type_name(
+        custom_type_kind: &Self::CustomTypeKind<LocalTypeId>,
+        type_validation: &TypeValidation<Self::CustomTypeValidation>,
+    ) -> String {
+        let name = match (custom_type_kind, type_validation) {
+            (
+                ScryptoCustomTypeKind::Reference,
+                TypeValidation::Custom(ScryptoCustomTypeValidation::Reference(validation)),
+            ) => match validation {
+                ReferenceValidation::IsGlobal | ReferenceValidation::IsGlobalTyped(_, _) => {
+                    "GlobalAddress"
+                }
+                ReferenceValidation::IsGlobalPackage => "PackageAddress",
+                ReferenceValidation::IsGlobalComponent => "ComponentAddress",
+                ReferenceValidation::IsGlobalResourceManager => "ResourceAddress",
+                ReferenceValidation::IsInternal | ReferenceValidation::IsInternalTyped(_, _) => {
+                    "InternalAddress"
+                }
+            },
+            (ScryptoCustomTypeKind::Reference, _) => "Reference",
+            (
+                ScryptoCustomTypeKind::Own,
+                TypeValidation::Custom(ScryptoCustomTypeValidation::Own(validation)),
+            ) => match validation {
+                OwnValidation::IsBucket => "Bucket",
+                OwnValidation::IsProof => "Proof",
+                OwnValidation::IsVault => "Vault",
+                OwnValidation::IsGlobalAddressReservation => "GlobalAddressReservation",
+                // Key value stores and typed objects are generic over their contents or blueprint
+                OwnValidation::IsKeyValueStore | OwnValidation::IsTypedObject(_, _) => "Own",
+            },
+            (ScryptoCustomTypeKind::Own, _) => "Own",
+            (ScryptoCustomTypeKind::Decimal, _) => "Decimal",
+            (ScryptoCustomTypeKind::PreciseDecimal, _) => "PreciseDecimal",
+            (ScryptoCustomTypeKind::NonFungibleLocalId, _) => "NonFungibleLocalId",
+        };
+        name.to_string()
+    }
+
+    fn well_known_type_name(well_known_id: WellKnownTypeId) -> Option<&'static str> {
+        // `KeyValueStore` is generic over its key and value types, which the type doesn't capture
+        if well_known_id == OWN_KEY_VALUE_STORE_TYPE {
+            Some("Own")
+        } else {
+            None
+        }
+    }
+}
+
 pub trait HasSchemaHash {
     fn generate_schema_hash(&self) -> SchemaHash;
 }
@@ -235,3 +288,129 @@ pub fn replace_self_package_address(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod original {
+        use super::*;
+
+        #[derive(ScryptoSbor)]
+        pub struct Deposit {
+            pub account: ComponentAddress,
+            pub resource: ResourceAddress,
+            pub amount: Decimal,
+            pub precise_amount: PreciseDecimal,
+            pub ids: IndexSet<NonFungibleLocalId>,
+            pub badge: Option<NonFungibleGlobalId>,
+            pub node: Reference,
+            pub owned: Own,
+            pub history: Vec<Deposit>,
+            pub kind: DepositKind,
+        }
+
+        #[derive(ScryptoSbor)]
+        pub enum DepositKind {
+            Direct,
+            Forwarded {
+                from: GlobalAddress,
+                via: Box<DepositKind>,
+            },
+            Internal(InternalAddress),
+        }
+    }
+
+    macro_rules! generated_types {
+        ($($code:tt)*) => {
+            mod generated {
+                use super::*;
+
+                $($code)*
+            }
+
+            const GENERATED_CODE: &str = stringify!($($code)*);
+        };
+    }
+
+    generated_types! {
+        #[derive(ScryptoSbor, Debug, Clone, PartialEq, Eq)]
+        pub struct Deposit {
+            pub account: ComponentAddress,
+            pub resource: ResourceAddress,
+            pub amount: Decimal,
+            pub precise_amount: PreciseDecimal,
+            pub ids: Vec<NonFungibleLocalId>,
+            pub badge: Option<NonFungibleGlobalId>,
+            pub node: Reference,
+            pub owned: Own,
+            pub history: Vec<Deposit>,
+            pub kind: DepositKind,
+        }
+
+        #[derive(ScryptoSbor, Debug, Clone, PartialEq, Eq)]
+        pub enum DepositKind {
+            Direct,
+            Forwarded {
+                from: GlobalAddress,
+                via: Box<DepositKind>,
+            },
+            Internal(InternalAddress),
+        }
+    }
+
+    #[test]
+    fn test_generated_scrypto_types_round_trip() {
+        let (type_id, schema) =
+            generate_full_schema_from_single_type::<original::Deposit, ScryptoCustomSchema>();
+        let code = generate_rust_types(&schema, &[type_id]).unwrap();
+        let without_whitespace =
+            |code: &str| -> String { code.chars().filter(|c| !c.is_whitespace()).collect() };
+        assert_eq!(
+            without_whitespace(&code),
+            without_whitespace(GENERATED_CODE)
+        );
+
+        let (generated_type_id, generated_schema) =
+            generate_full_schema_from_single_type::<generated::Deposit, ScryptoCustomSchema>();
+        assert_eq!(generated_type_id, type_id);
+        assert_eq!(generated_schema, schema);
+    }
+
+    #[test]
+    fn test_typed_custom_types_are_named_by_their_validation() {
+        let type_data = |kind, validation| TypeData {
+            kind: TypeKind::Custom(kind),
+            metadata: TypeMetadata::unnamed(),
+            validation: TypeValidation::Custom(validation),
+        };
+        let global_type_id = RustTypeId::novel_with_code("Global", &[], &[]);
+        let vault_type_id = RustTypeId::novel_with_code("Vault", &[], &[]);
+        let mut aggregator = TypeAggregator::<ScryptoCustomTypeKind>::new();
+        let type_id = aggregator
+            .add_child_type(RustTypeId::novel_with_code("Stored", &[], &[]), || {
+                TypeData::struct_with_unnamed_fields("Stored", vec![global_type_id, vault_type_id])
+            });
+        aggregator.add_child_type(global_type_id, || {
+            type_data(
+                ScryptoCustomTypeKind::Reference,
+                ScryptoCustomTypeValidation::Reference(ReferenceValidation::IsGlobalTyped(
+                    None,
+                    "Pool".to_string(),
+                )),
+            )
+        });
+        aggregator.add_child_type(vault_type_id, || {
+            type_data(
+                ScryptoCustomTypeKind::Own,
+                ScryptoCustomTypeValidation::Own(OwnValidation::IsVault),
+            )
+        });
+        let schema = generate_full_schema::<ScryptoCustomSchema>(aggregator);
+
+        assert_eq!(
+            generate_rust_types(&schema, &[type_id]).unwrap(),
+            "#[derive(ScryptoSbor, Debug, Clone, PartialEq, Eq)]\npub struct Stored(pub GlobalAddress, pub Vault);\n"
+        );
+    }
+}
@@ -40,6 +40,85 @@ impl SerializableCustomExtension for ScryptoCustomExtension {
     }
 }
 
+#[derive(Clone, Copy, Default)]
+pub struct ScryptoValueDeserializationContext<'a> {
+    /// If not provided, addresses of any network are accepted.
+    pub address_bech32_decoder: Option<&'a AddressBech32Decoder>,
+}
+
+impl<'a> ScryptoValueDeserializationContext<'a> {
+    pub fn no_context() -> Self {
+        Self::default()
+    }
+
+    pub fn with_optional_bech32(address_bech32_decoder: Option<&'a AddressBech32Decoder>) -> Self {
+        Self {
+            address_bech32_decoder,
+        }
+    }
+}
+
+impl<'a> Into<ScryptoValueDeserializationContext<'a>> for &'a AddressBech32Decoder {
+    fn into(self) -> ScryptoValueDeserializationContext<'a> {
+        ScryptoValueDeserializationContext::with_optional_bech32(Some(self))
+    }
+}
+
+impl DeserializableCustomExtension for ScryptoCustomExtension {
+    type CustomValue = ScryptoCustomValue;
+    type CustomDeserializationContext<'a> = ScryptoValueDeserializationContext<'a>;
+
+    fn deserialize_custom_value(
+        context: &Self::CustomDeserializationContext<'_>,
+        custom_value_kind: Self::CustomValueKind,
+        value: &str,
+    ) -> Result<Self::CustomValue, String> {
+        let custom_value = match custom_value_kind {
+            ScryptoCustomValueKind::Reference => ScryptoCustomValue::Reference(Reference(
+                parse_node_id(context.address_bech32_decoder, value)?,
+            )),
+            ScryptoCustomValueKind::Own => {
+                ScryptoCustomValue::Own(Own(parse_node_id(context.address_bech32_decoder, value)?))
+            }
+            ScryptoCustomValueKind::Decimal => ScryptoCustomValue::Decimal(
+                Decimal::from_str(value).map_err(|error| format!("{:?}", error))?,
+            ),
+            ScryptoCustomValueKind::PreciseDecimal => ScryptoCustomValue::PreciseDecimal(
+                PreciseDecimal::from_str(value).map_err(|error| format!("{:?}", error))?,
+            ),
+            ScryptoCustomValueKind::NonFungibleLocalId => ScryptoCustomValue::NonFungibleLocalId(
+                NonFungibleLocalId::from_str(value).map_err(|error| format!("{:?}", error))?,
+            ),
+        };
+        Ok(custom_value)
+    }
+}
+
+/// Parses a node id from its serialized form - either a Bech32m address, or `NodeId(<hex>)` for
+/// node ids without an address.
+pub(crate) fn parse_node_id(
+    address_bech32_decoder: Option<&AddressBech32Decoder>,
+    value: &str,
+) -> Result<NodeId, String> {
+    if let Some(hex) = value
+        .strip_prefix("NodeId(")
+        .and_then(|value| value.strip_suffix(")"))
+    {
+        return NodeId::try_from_hex(hex).ok_or_else(|| format!("Invalid node id: {}", value));
+    }
+    let bytes = match address_bech32_decoder {
+        Some(decoder) => decoder.validate_and_decode(value).map(|(_, bytes)| bytes),
+        None => {
+            AddressBech32Decoder::validate_and_decode_ignore_hrp(value).map(|(_, _, bytes)| bytes)
+        }
+    }
+    .map_err(|error| format!("{:?}", error))?;
+    bytes
+        .try_into()
+        .map(NodeId)
+        .map_err(|_| format!("Invalid node id: {}", value))
+}
+
 #[cfg(test)]
 #[cfg(feature = "serde")] // Ensures that VS Code runs this module with the features serde tag!
 mod tests {
@@ -234,6 +313,76 @@ mod tests {
 
         assert_natural_json_matches(&value, context, expected_natural);
         assert_programmatic_json_matches(&value, context, expected_programmatic);
+        assert_programmatic_json_round_trips(&value);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")] // Workaround for VS Code "Run Test" feature
+    fn test_natural_json_deserialization_with_schema() {
+        let (type_id, schema) =
+            generate_full_schema_from_single_type::<Sample, ScryptoCustomSchema>();
+        let decoder = AddressBech32Decoder::for_simulator();
+
+        let payload = deserialize_payload_from_json::<ScryptoCustomExtension>(
+            &json!({ "a": { "kind": "Reference", "value": FUNGIBLE_RESOURCE_SIM_ADDRESS } }),
+            &DeserializationParameters::WithSchema {
+                mode: SerializationMode::Natural,
+                custom_context: (&decoder).into(),
+                schema: schema.v1(),
+                type_id,
+                depth_limit: SCRYPTO_SBOR_V1_MAX_DEPTH,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            payload,
+            scrypto_encode(&Sample {
+                a: FUNGIBLE_RESOURCE
+            })
+            .unwrap()
+        );
+
+        let error = deserialize_payload_from_json::<ScryptoCustomExtension>(
+            &json!({ "a": { "kind": "Reference", "value": "not_an_address" } }),
+            &DeserializationParameters::WithSchema {
+                mode: SerializationMode::Natural,
+                custom_context: (&decoder).into(),
+                schema: schema.v1(),
+                type_id,
+                depth_limit: SCRYPTO_SBOR_V1_MAX_DEPTH,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(error.path, "$.a.value");
+    }
+
+    fn assert_programmatic_json_round_trips<T: ScryptoEncode>(value: &T) {
+        let payload = scrypto_encode(&value).unwrap();
+        let encoder = AddressBech32Encoder::for_simulator();
+        let decoder = AddressBech32Decoder::for_simulator();
+        let json = to_value(
+            ScryptoRawPayload::new_from_valid_slice(&payload).serializable(
+                SerializationParameters::Schemaless {
+                    mode: SerializationMode::Programmatic,
+                    custom_context: ScryptoValueDisplayContext::with_optional_bech32(Some(
+                        &encoder,
+                    )),
+                    depth_limit: SCRYPTO_SBOR_V1_MAX_DEPTH,
+                },
+            ),
+        )
+        .unwrap();
+
+        let deserialized = deserialize_payload_from_json::<ScryptoCustomExtension>(
+            &json,
+            &DeserializationParameters::Schemaless {
+                mode: SerializationMode::Programmatic,
+                custom_context: (&decoder).into(),
+                depth_limit: SCRYPTO_SBOR_V1_MAX_DEPTH,
+            },
+        )
+        .unwrap();
+        assert_eq!(deserialized, payload);
     }
 
     fn assert_natural_json_matches<
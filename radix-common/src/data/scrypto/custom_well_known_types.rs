@@ -459,6 +459,7 @@ create_well_known_lookup!(
                     (0u8, named_tuple("ProofRule", [PROOF_RULE_TYPE])),
                     (1u8, named_tuple("AnyOf", [ACCESS_RULE_NODE_LIST_TYPE])),
                     (2u8, named_tuple("AllOf", [ACCESS_RULE_NODE_LIST_TYPE])),
+                    (3u8, named_tuple("TimeCondition", [TIME_CONDITION_TYPE])),
                 ],
             )
         ),
@@ -529,6 +530,19 @@ create_well_known_lookup!(
             ROLE_ASSIGNMENT_TYPES_START + 7,
             named_transparent("RoleKey", string_type_data(),)
         ),
+        (
+            TIME_CONDITION,
+            ROLE_ASSIGNMENT_TYPES_START + 8,
+            named_enum(
+                "TimeCondition",
+                [
+                    (0u8, named_tuple("EpochAtLeast", [U64_TYPE])),
+                    (1u8, named_tuple("EpochBefore", [U64_TYPE])),
+                    (2u8, named_tuple("TimeAtLeast", [INSTANT_TYPE])),
+                    (3u8, named_tuple("TimeBefore", [INSTANT_TYPE])),
+                ],
+            )
+        ),
         // OTHER MODULE TYPES
         (
             MODULE_ID,
@@ -17,6 +17,7 @@ use crate::data::scrypto::*;
 use crate::math::bnum_integer::*;
 use crate::math::rounding_mode::*;
 use crate::math::traits::*;
+use crate::math::transcendental;
 use crate::math::PreciseDecimal;
 use crate::well_known_scrypto_custom_type;
 use crate::*;
@@ -268,6 +269,123 @@ impl Decimal {
             Some(Decimal(nth_root))
         }
     }
+
+    /// Natural logarithm of a Decimal, rounded with the given mode.
+    ///
+    /// The result is correctly rounded, except when the exact result is within `10^-42` of a
+    /// rounding boundary, in which case it can be one unit in the last place off.
+    /// Returns `None` if the number isn't positive.
+    pub fn checked_ln(&self, mode: RoundingMode) -> Option<Self> {
+        let ln = transcendental::ln(&BigInt::from(self.0), Self::SCALE, mode)?;
+        I192::try_from(ln).ok().map(Self)
+    }
+
+    /// Exponential function (`e^self`) of a Decimal, rounded with the given mode.
+    ///
+    /// The result is correctly rounded, except when the exact result is within `10^-42` of a
+    /// rounding boundary, in which case it can be one unit in the last place off.
+    /// Returns `None` if the result overflows.
+    pub fn checked_exp(&self, mode: RoundingMode) -> Option<Self> {
+        let exp = transcendental::exp(&BigInt::from(self.0), Self::SCALE, mode)?;
+        I192::try_from(exp).ok().map(Self)
+    }
+
+    /// Logarithm of a Decimal to the given base, rounded with the given mode.
+    ///
+    /// The result is correctly rounded, except when the exact result is within `10^-42` of a
+    /// rounding boundary, in which case it can be one unit in the last place off.
+    /// Integer results (e.g. `log_2(8) = 3`) are always exact.
+    /// Returns `None` if the number or the base isn't positive, or if the base is one.
+    pub fn checked_log(&self, base: Self, mode: RoundingMode) -> Option<Self> {
+        let log = transcendental::log(
+            &BigInt::from(self.0),
+            &BigInt::from(base.0),
+            Self::SCALE,
+            mode,
+        )?;
+        I192::try_from(log).ok().map(Self)
+    }
+
+    /// Power of a Decimal to a (possibly fractional) Decimal exponent, rounded with the given mode.
+    ///
+    /// The result is correctly rounded, except when the exact result is within `10^-42` of a
+    /// rounding boundary, in which case it can be one unit in the last place off.
+    /// Results for integer exponents up to 256 (in absolute value) are calculated exactly before
+    /// rounding, and exactly representable results of exponents with small denominators (e.g.
+    /// `4^0.5 = 2`) are always exact.
+    /// Returns `None` if the result is undefined (zero to a negative power, or a negative number
+    /// to a fractional power) or overflows.
+    pub fn checked_pow(&self, exp: Self, mode: RoundingMode) -> Option<Self> {
+        let pow = transcendental::pow(
+            &BigInt::from(self.0),
+            &BigInt::from(exp.0),
+            Self::SCALE,
+            mode,
+        )?;
+        I192::try_from(pow).ok().map(Self)
+    }
+
+    /// Multiplies a Decimal by `multiplier` and divides the product by `divisor`, rounding the
+    /// result with the given mode.
+    ///
+    /// The intermediate product is calculated on 384 bits, so unlike `a * b / c` it neither
+    /// overflows nor loses precision, and the result is only rounded once.
+    /// Returns `None` if the divisor is zero or the result overflows.
+    pub fn checked_mul_div(
+        &self,
+        multiplier: Self,
+        divisor: Self,
+        mode: RoundingMode,
+    ) -> Option<Self> {
+        // (a / 10^SCALE) * (b / 10^SCALE) / (c / 10^SCALE) = (a * b / c) / 10^SCALE
+        let numerator = I384::from(self.0).checked_mul(I384::from(multiplier.0))?;
+        let divisor = I384::from(divisor.0);
+        if divisor.is_zero() {
+            return None;
+        }
+        let (numerator, divisor) = if divisor.is_negative() {
+            (-numerator, -divisor)
+        } else {
+            (numerator, divisor)
+        };
+
+        // Floor division, with a non-negative remainder
+        let mut quotient = numerator / divisor;
+        let mut remainder = numerator % divisor;
+        if remainder.is_negative() {
+            quotient -= I384::ONE;
+            remainder += divisor;
+        }
+        let rounded = if remainder.is_zero() {
+            quotient
+        } else {
+            let resolved_strategy =
+                ResolvedRoundingStrategy::from_mode(mode, numerator.is_positive(), || {
+                    (remainder * I384::from(2)).cmp(&divisor)
+                });
+            match resolved_strategy {
+                ResolvedRoundingStrategy::RoundUp => quotient + I384::ONE,
+                ResolvedRoundingStrategy::RoundDown => quotient,
+                ResolvedRoundingStrategy::RoundToEven => {
+                    if (quotient % I384::from(2)).is_zero() {
+                        quotient
+                    } else {
+                        quotient + I384::ONE
+                    }
+                }
+            }
+        };
+        I192::try_from(rounded).ok().map(Self)
+    }
+
+    /// Multiplies a Decimal by `multiplier` and divides the product by `divisor`, rounding the
+    /// result with the given mode. See [`Self::checked_mul_div`].
+    ///
+    /// Panics if the divisor is zero or the result overflows.
+    pub fn mul_div(&self, multiplier: Self, divisor: Self, mode: RoundingMode) -> Self {
+        self.checked_mul_div(multiplier, divisor, mode)
+            .expect("Overflow or division by zero")
+    }
 }
 
 macro_rules! from_primitive_type {
@@ -1931,6 +2049,440 @@ mod tests {
         assert_eq!(root_0, None);
     }
 
+    const DOWN: RoundingMode = RoundingMode::ToNegativeInfinity;
+    const UP: RoundingMode = RoundingMode::ToPositiveInfinity;
+    const EVEN: RoundingMode = RoundingMode::ToNearestMidpointToEven;
+
+    #[test]
+    fn test_ln_against_reference() {
+        // (input..., rounded to -infinity, rounded to +infinity, rounded to nearest even)
+        let ln_cases = [
+            (
+                "0.000000000000000001",
+                "-41.446531673892822313",
+                "-41.446531673892822312",
+                "-41.446531673892822312",
+            ),
+            (
+                "0.5",
+                "-0.69314718055994531",
+                "-0.693147180559945309",
+                "-0.693147180559945309",
+            ),
+            (
+                "2",
+                "0.693147180559945309",
+                "0.69314718055994531",
+                "0.693147180559945309",
+            ),
+            (
+                "10",
+                "2.302585092994045684",
+                "2.302585092994045685",
+                "2.302585092994045684",
+            ),
+            (
+                "123456.789",
+                "11.723646487185880981",
+                "11.723646487185880982",
+                "11.723646487185880981",
+            ),
+            (
+                "1000000000000",
+                "27.631021115928548208",
+                "27.631021115928548209",
+                "27.631021115928548208",
+            ),
+        ];
+        for (x, down, up, even) in ln_cases {
+            let x = Decimal::from_str(x).unwrap();
+            for (mode, expected) in [(DOWN, down), (UP, up), (EVEN, even)] {
+                assert_eq!(
+                    x.checked_ln(mode),
+                    Some(Decimal::from_str(expected).unwrap()),
+                    "ln({}), {:?}",
+                    x,
+                    mode
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_exp_against_reference() {
+        // (input..., rounded to -infinity, rounded to +infinity, rounded to nearest even)
+        let exp_cases = [
+            ("-50", "0", "0.000000000000000001", "0"),
+            (
+                "-1",
+                "0.367879441171442321",
+                "0.367879441171442322",
+                "0.367879441171442322",
+            ),
+            (
+                "0.000000001",
+                "1.000000001",
+                "1.000000001000000001",
+                "1.000000001000000001",
+            ),
+            (
+                "2.5",
+                "12.182493960703473438",
+                "12.182493960703473439",
+                "12.182493960703473438",
+            ),
+            (
+                "20",
+                "485165195.40979027796910683",
+                "485165195.409790277969106831",
+                "485165195.409790277969106831",
+            ),
+        ];
+        for (x, down, up, even) in exp_cases {
+            let x = Decimal::from_str(x).unwrap();
+            for (mode, expected) in [(DOWN, down), (UP, up), (EVEN, even)] {
+                assert_eq!(
+                    x.checked_exp(mode),
+                    Some(Decimal::from_str(expected).unwrap()),
+                    "exp({}), {:?}",
+                    x,
+                    mode
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_log_against_reference() {
+        // (input..., rounded to -infinity, rounded to +infinity, rounded to nearest even)
+        let log_cases = [
+            (
+                "1000",
+                "7",
+                "3.54988398736481498",
+                "3.549883987364814981",
+                "3.54988398736481498",
+            ),
+            (
+                "7",
+                "3",
+                "1.77124374916142226",
+                "1.771243749161422261",
+                "1.77124374916142226",
+            ),
+            ("2", "0.5", "-1", "-1", "-1"),
+            (
+                "0.3",
+                "10",
+                "-0.522878745280337563",
+                "-0.522878745280337562",
+                "-0.522878745280337563",
+            ),
+        ];
+        for (x, base, down, up, even) in log_cases {
+            let (x, base) = (
+                Decimal::from_str(x).unwrap(),
+                Decimal::from_str(base).unwrap(),
+            );
+            for (mode, expected) in [(DOWN, down), (UP, up), (EVEN, even)] {
+                assert_eq!(
+                    x.checked_log(base, mode),
+                    Some(Decimal::from_str(expected).unwrap()),
+                    "log_{}({}), {:?}",
+                    base,
+                    x,
+                    mode
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pow_against_reference() {
+        // (input..., rounded to -infinity, rounded to +infinity, rounded to nearest even)
+        let pow_cases = [
+            (
+                "2",
+                "0.5",
+                "1.414213562373095048",
+                "1.414213562373095049",
+                "1.414213562373095049",
+            ),
+            (
+                "1.5",
+                "2.7",
+                "2.988452789872501891",
+                "2.988452789872501892",
+                "2.988452789872501891",
+            ),
+            (
+                "10",
+                "-1.25",
+                "0.056234132519034908",
+                "0.056234132519034909",
+                "0.056234132519034908",
+            ),
+            ("0.3", "3", "0.027", "0.027", "0.027"),
+            ("-2", "3", "-8", "-8", "-8"),
+            (
+                "7",
+                "40",
+                "6366805760909027985741435139224001",
+                "6366805760909027985741435139224001",
+                "6366805760909027985741435139224001",
+            ),
+            (
+                "1.0001",
+                "300.5",
+                "1.030504509737329716",
+                "1.030504509737329717",
+                "1.030504509737329717",
+            ),
+        ];
+        for (x, y, down, up, even) in pow_cases {
+            let (x, y) = (Decimal::from_str(x).unwrap(), Decimal::from_str(y).unwrap());
+            for (mode, expected) in [(DOWN, down), (UP, up), (EVEN, even)] {
+                assert_eq!(
+                    x.checked_pow(y, mode),
+                    Some(Decimal::from_str(expected).unwrap()),
+                    "pow({}, {}), {:?}",
+                    x,
+                    y,
+                    mode
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_transcendental_exact_results() {
+        let modes = [
+            RoundingMode::ToPositiveInfinity,
+            RoundingMode::ToNegativeInfinity,
+            RoundingMode::ToZero,
+            RoundingMode::AwayFromZero,
+            RoundingMode::ToNearestMidpointTowardZero,
+            RoundingMode::ToNearestMidpointAwayFromZero,
+            RoundingMode::ToNearestMidpointToEven,
+        ];
+        for mode in modes {
+            assert_eq!(test_dec!(1).checked_ln(mode), Some(test_dec!(0)));
+            assert_eq!(test_dec!(0).checked_exp(mode), Some(test_dec!(1)));
+            assert_eq!(
+                test_dec!(8).checked_log(test_dec!(2), mode),
+                Some(test_dec!(3))
+            );
+            assert_eq!(
+                test_dec!("0.001").checked_log(test_dec!(10), mode),
+                Some(test_dec!("-3"))
+            );
+            assert_eq!(
+                test_dec!(2).checked_pow(test_dec!(3), mode),
+                Some(test_dec!(8))
+            );
+            assert_eq!(
+                test_dec!(4).checked_pow(test_dec!("0.5"), mode),
+                Some(test_dec!(2))
+            );
+            assert_eq!(
+                test_dec!("0.25").checked_pow(test_dec!("-1.5"), mode),
+                Some(test_dec!(8))
+            );
+            assert_eq!(
+                test_dec!(-2).checked_pow(test_dec!(-2), mode),
+                Some(test_dec!("0.25"))
+            );
+            assert_eq!(
+                test_dec!(0).checked_pow(test_dec!("0.5"), mode),
+                Some(test_dec!(0))
+            );
+            assert_eq!(
+                test_dec!(0).checked_pow(test_dec!(0), mode),
+                Some(test_dec!(1))
+            );
+        }
+    }
+
+    #[test]
+    fn test_transcendental_undefined_results() {
+        let mode = RoundingMode::ToNearestMidpointToEven;
+        assert_eq!(test_dec!(0).checked_ln(mode), None);
+        assert_eq!(test_dec!(-1).checked_ln(mode), None);
+        assert_eq!(test_dec!(1000).checked_exp(mode), None);
+        assert_eq!(test_dec!(-1000).checked_exp(mode), Some(test_dec!(0)));
+        assert_eq!(
+            test_dec!(-1000).checked_exp(RoundingMode::ToPositiveInfinity),
+            Some(test_dec!("0.000000000000000001"))
+        );
+        assert_eq!(test_dec!(2).checked_log(test_dec!(1), mode), None);
+        assert_eq!(test_dec!(2).checked_log(test_dec!(0), mode), None);
+        assert_eq!(test_dec!(2).checked_log(test_dec!(-2), mode), None);
+        assert_eq!(test_dec!(-2).checked_log(test_dec!(2), mode), None);
+        assert_eq!(test_dec!(0).checked_pow(test_dec!(-1), mode), None);
+        assert_eq!(test_dec!(-2).checked_pow(test_dec!("0.5"), mode), None);
+        assert_eq!(test_dec!(10).checked_pow(test_dec!(100), mode), None);
+        assert_eq!(Decimal::MAX.checked_pow(test_dec!("1.5"), mode), None);
+    }
+
+    #[test]
+    fn test_transcendental_properties() {
+        let ulp = test_dec!("0.000000000000000001");
+        let down = RoundingMode::ToNegativeInfinity;
+        let up = RoundingMode::ToPositiveInfinity;
+        let values = [
+            test_dec!("0.000123"),
+            test_dec!("0.5"),
+            test_dec!("0.999999"),
+            test_dec!("1.000001"),
+            test_dec!("3.14159"),
+            test_dec!("42"),
+            test_dec!("98765.4321"),
+        ];
+        for x in values {
+            // Rounding down and up brackets the exact result
+            let (ln_down, ln_up) = (x.checked_ln(down).unwrap(), x.checked_ln(up).unwrap());
+            assert_eq!(ln_up - ln_down, ulp, "ln({})", x);
+
+            // e^ln(x) = x, up to the error of ln(x) scaled by x
+            let tolerance = (x * ulp * 2u8).max(ulp * 2u8);
+            let exp_ln = ln_down.checked_exp(down).unwrap();
+            assert!(
+                (exp_ln - x).checked_abs().unwrap() <= tolerance,
+                "exp(ln({}))",
+                x
+            );
+
+            // ln(x * y) = ln(x) + ln(y)
+            for y in values {
+                let Some(product) = x.checked_mul(y) else {
+                    continue;
+                };
+                if product.is_zero() || product.checked_ln(down).is_none() {
+                    continue;
+                }
+                let sum = x.checked_ln(down).unwrap() + y.checked_ln(down).unwrap();
+                // The product is rounded, so allow for its relative error as well
+                let tolerance = ulp * 3u8 + ulp / product * 2u8;
+                assert!(
+                    (product.checked_ln(down).unwrap() - sum)
+                        .checked_abs()
+                        .unwrap()
+                        <= tolerance,
+                    "ln({} * {})",
+                    x,
+                    y
+                );
+            }
+
+            // x^(a + b) = x^a * x^b
+            let (a, b) = (test_dec!("1.25"), test_dec!("0.5"));
+            let pow_sum = x.checked_pow(a + b, down).unwrap();
+            let pow_product = x.checked_pow(a, down).unwrap() * x.checked_pow(b, down).unwrap();
+            let tolerance = (pow_sum * ulp * 1000u16).max(ulp * 4u8);
+            assert!(
+                (pow_sum - pow_product).checked_abs().unwrap() <= tolerance,
+                "pow({}, {} + {})",
+                x,
+                a,
+                b
+            );
+
+            // log_x(x^a) = a
+            if x != test_dec!("0.999999") && x != test_dec!("1.000001") {
+                let pow = x.checked_pow(a, down).unwrap();
+                let log = pow.checked_log(x, down).unwrap();
+                // The power is rounded, so allow for its relative error as well
+                let tolerance = ulp * 1000u16 + ulp / pow;
+                assert!((log - a).checked_abs().unwrap() <= tolerance, "log_{}", x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_div() {
+        let (one, two, three) = (test_dec!(1), test_dec!(2), test_dec!(3));
+        let cases = [
+            (
+                RoundingMode::ToPositiveInfinity,
+                "0.666666666666666667",
+                "-0.666666666666666666",
+                "3",
+            ),
+            (
+                RoundingMode::ToNegativeInfinity,
+                "0.666666666666666666",
+                "-0.666666666666666667",
+                "2",
+            ),
+            (
+                RoundingMode::ToZero,
+                "0.666666666666666666",
+                "-0.666666666666666666",
+                "2",
+            ),
+            (
+                RoundingMode::AwayFromZero,
+                "0.666666666666666667",
+                "-0.666666666666666667",
+                "3",
+            ),
+            (
+                RoundingMode::ToNearestMidpointTowardZero,
+                "0.666666666666666667",
+                "-0.666666666666666667",
+                "2",
+            ),
+            (
+                RoundingMode::ToNearestMidpointAwayFromZero,
+                "0.666666666666666667",
+                "-0.666666666666666667",
+                "3",
+            ),
+            (
+                RoundingMode::ToNearestMidpointToEven,
+                "0.666666666666666667",
+                "-0.666666666666666667",
+                "2",
+            ),
+        ];
+        for (mode, positive, negative, midpoint_ulps) in cases {
+            assert_eq!(
+                one.checked_mul_div(two, three, mode),
+                Some(Decimal::from_str(positive).unwrap())
+            );
+            assert_eq!(
+                one.checked_mul_div(-two, three, mode),
+                Some(Decimal::from_str(negative).unwrap())
+            );
+            assert_eq!(
+                one.checked_mul_div(two, -three, mode),
+                Some(Decimal::from_str(negative).unwrap())
+            );
+            assert_eq!(
+                test_dec!("0.000000000000000005").checked_mul_div(one, two, mode),
+                Some(test_dec!("0.000000000000000001") * Decimal::from_str(midpoint_ulps).unwrap())
+            );
+        }
+
+        let mode = RoundingMode::ToNearestMidpointToEven;
+        // The intermediate product doesn't overflow
+        assert_eq!(
+            Decimal::MAX.checked_mul_div(Decimal::MAX, Decimal::MAX, mode),
+            Some(Decimal::MAX)
+        );
+        assert_eq!(
+            Decimal::MAX.checked_mul_div(two, two, mode),
+            Some(Decimal::MAX)
+        );
+        assert_eq!(Decimal::MAX.mul_div(three, three, mode), Decimal::MAX);
+        assert_eq!(Decimal::MAX.checked_mul_div(two, one, mode), None);
+        assert_eq!(one.checked_mul_div(two, Decimal::ZERO, mode), None);
+        assert_eq!(
+            Decimal::ZERO.checked_mul_div(two, three, mode),
+            Some(Decimal::ZERO)
+        );
+    }
+
     #[test]
     fn no_panic_with_18_decimal_places() {
         // Arrange
@@ -1,11 +1,14 @@
 pub mod bnum_integer;
 pub mod decimal;
 pub mod precise_decimal;
+pub mod ratio;
 pub mod rounding_mode;
 pub mod traits;
+mod transcendental;
 
 pub use bnum_integer::*;
 pub use decimal::*;
 pub use precise_decimal::*;
+pub use ratio::*;
 pub use rounding_mode::*;
 pub use traits::*;
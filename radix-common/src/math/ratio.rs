@@ -0,0 +1,454 @@
+use core::cmp::Ordering;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+use sbor::rust::fmt;
+use sbor::rust::ops::*;
+
+use crate::math::bnum_integer::*;
+use crate::math::decimal::*;
+use crate::math::precise_decimal::*;
+use crate::math::rounding_mode::*;
+use crate::math::traits::*;
+
+/// `Ratio` represents an exact fraction of two arbitrarily large integers.
+///
+/// It's meant for intermediate results of resource math, e.g. `amount * supply / total`, which
+/// can be combined without any loss of precision or overflow, and then converted to a [`Decimal`]
+/// or [`PreciseDecimal`] with an explicit [`RoundingMode`].
+///
+/// The fraction is always kept in lowest terms, with a positive denominator.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Ratio {
+    numerator: BigInt,
+    denominator: BigInt,
+}
+
+impl Ratio {
+    /// Returns the ratio of the given numbers, or `None` if the denominator is zero.
+    ///
+    /// ```
+    /// # use radix_common::math::*;
+    /// let ratio = Ratio::new(Decimal::from(3), Decimal::from(9)).unwrap();
+    /// assert_eq!(ratio, Ratio::new(1, 3).unwrap());
+    /// ```
+    pub fn new<N: Into<Ratio>, D: Into<Ratio>>(numerator: N, denominator: D) -> Option<Self> {
+        numerator.into().checked_div(denominator.into())
+    }
+
+    /// Returns the ratio of the given integers, or `None` if the denominator is zero.
+    pub fn from_integers(numerator: BigInt, denominator: BigInt) -> Option<Self> {
+        if denominator.is_zero() {
+            return None;
+        }
+        let divisor = numerator.gcd(&denominator);
+        let (mut numerator, mut denominator) = (numerator / &divisor, denominator / &divisor);
+        if denominator.is_negative() {
+            numerator = -numerator;
+            denominator = -denominator;
+        }
+        Some(Self {
+            numerator,
+            denominator,
+        })
+    }
+
+    pub fn zero() -> Self {
+        Self {
+            numerator: BigInt::zero(),
+            denominator: BigInt::one(),
+        }
+    }
+
+    pub fn one() -> Self {
+        Self {
+            numerator: BigInt::one(),
+            denominator: BigInt::one(),
+        }
+    }
+
+    pub fn numerator(&self) -> &BigInt {
+        &self.numerator
+    }
+
+    /// The denominator, which is always positive.
+    pub fn denominator(&self) -> &BigInt {
+        &self.denominator
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.numerator.is_positive()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.numerator.is_negative()
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denominator.is_one()
+    }
+
+    pub fn abs(&self) -> Self {
+        Self {
+            numerator: self.numerator.abs(),
+            denominator: self.denominator.clone(),
+        }
+    }
+
+    /// Returns `1 / self`, or `None` if the ratio is zero.
+    pub fn checked_recip(&self) -> Option<Self> {
+        Self::from_integers(self.denominator.clone(), self.numerator.clone())
+    }
+
+    /// Rounds the ratio to an integer with the given mode.
+    pub fn round(&self, mode: RoundingMode) -> BigInt {
+        round_quotient(&self.numerator, &self.denominator, mode)
+    }
+
+    /// Converts the ratio to a [`Decimal`] rounded with the given mode, or `None` if it's out of
+    /// range.
+    pub fn checked_to_decimal(&self, mode: RoundingMode) -> Option<Decimal> {
+        CheckedTruncate::<Decimal>::checked_truncate(self.clone(), mode)
+    }
+
+    /// Converts the ratio to a [`PreciseDecimal`] rounded with the given mode, or `None` if it's
+    /// out of range.
+    pub fn checked_to_precise_decimal(&self, mode: RoundingMode) -> Option<PreciseDecimal> {
+        CheckedTruncate::<PreciseDecimal>::checked_truncate(self.clone(), mode)
+    }
+
+    /// Rounds the ratio to `scale` decimal places, and returns the result multiplied by
+    /// `10^scale`.
+    fn round_to_scale(&self, scale: u32, mode: RoundingMode) -> BigInt {
+        let multiplier = BigInt::from(10u8).pow(scale);
+        round_quotient(&(&self.numerator * multiplier), &self.denominator, mode)
+    }
+}
+
+impl CheckedTruncate<Decimal> for Ratio {
+    type Output = Decimal;
+
+    fn checked_truncate(self, mode: RoundingMode) -> Option<Self::Output> {
+        let subunits = self.round_to_scale(Decimal::SCALE, mode);
+        I192::try_from(subunits).ok().map(Decimal)
+    }
+}
+
+impl CheckedTruncate<PreciseDecimal> for Ratio {
+    type Output = PreciseDecimal;
+
+    fn checked_truncate(self, mode: RoundingMode) -> Option<Self::Output> {
+        let subunits = self.round_to_scale(PreciseDecimal::SCALE, mode);
+        I256::try_from(subunits).ok().map(PreciseDecimal)
+    }
+}
+
+impl Default for Ratio {
+    fn default() -> Self {
+        Self::zero()
+    }
+}
+
+impl From<Decimal> for Ratio {
+    fn from(value: Decimal) -> Self {
+        Self::from_integers(BigInt::from(value.0), BigInt::from(Decimal::ONE.0))
+            .expect("Denominator is not zero")
+    }
+}
+
+impl From<PreciseDecimal> for Ratio {
+    fn from(value: PreciseDecimal) -> Self {
+        Self::from_integers(BigInt::from(value.0), BigInt::from(PreciseDecimal::ONE.0))
+            .expect("Denominator is not zero")
+    }
+}
+
+impl From<BigInt> for Ratio {
+    fn from(value: BigInt) -> Self {
+        Self {
+            numerator: value,
+            denominator: BigInt::one(),
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ident),*) => {
+        $(
+            impl From<$t> for Ratio {
+                fn from(value: $t) -> Self {
+                    Self::from(BigInt::from(value))
+                }
+            }
+        )*
+    };
+}
+from_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Ord for Ratio {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The denominators are positive, so cross-multiplying preserves the ordering
+        (&self.numerator * &other.denominator).cmp(&(&other.numerator * &self.denominator))
+    }
+}
+
+impl PartialOrd for Ratio {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Neg for Ratio {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl CheckedAdd<Ratio> for Ratio {
+    type Output = Self;
+
+    fn checked_add(self, other: Self) -> Option<Self::Output> {
+        Self::from_integers(
+            self.numerator * &other.denominator + other.numerator * &self.denominator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl CheckedSub<Ratio> for Ratio {
+    type Output = Self;
+
+    fn checked_sub(self, other: Self) -> Option<Self::Output> {
+        self.checked_add(-other)
+    }
+}
+
+impl CheckedMul<Ratio> for Ratio {
+    type Output = Self;
+
+    fn checked_mul(self, other: Self) -> Option<Self::Output> {
+        Self::from_integers(
+            self.numerator * other.numerator,
+            self.denominator * other.denominator,
+        )
+    }
+}
+
+impl CheckedDiv<Ratio> for Ratio {
+    type Output = Self;
+
+    /// Returns `None` if the divisor is zero.
+    fn checked_div(self, other: Self) -> Option<Self::Output> {
+        Self::from_integers(
+            self.numerator * other.denominator,
+            self.denominator * other.numerator,
+        )
+    }
+}
+
+impl Add<Ratio> for Ratio {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.checked_add(other).expect("Denominator is not zero")
+    }
+}
+
+impl Sub<Ratio> for Ratio {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.checked_sub(other).expect("Denominator is not zero")
+    }
+}
+
+impl Mul<Ratio> for Ratio {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.checked_mul(other).expect("Denominator is not zero")
+    }
+}
+
+impl Div<Ratio> for Ratio {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self::Output {
+        self.checked_div(other).expect("Division by zero")
+    }
+}
+
+impl AddAssign<Ratio> for Ratio {
+    fn add_assign(&mut self, other: Self) {
+        *self = core::mem::take(self) + other;
+    }
+}
+
+impl SubAssign<Ratio> for Ratio {
+    fn sub_assign(&mut self, other: Self) {
+        *self = core::mem::take(self) - other;
+    }
+}
+
+impl MulAssign<Ratio> for Ratio {
+    fn mul_assign(&mut self, other: Self) {
+        *self = core::mem::take(self) * other;
+    }
+}
+
+impl DivAssign<Ratio> for Ratio {
+    fn div_assign(&mut self, other: Self) {
+        *self = core::mem::take(self) / other;
+    }
+}
+
+impl fmt::Display for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        if self.is_integer() {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl fmt::Debug for Ratio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(f, "{}", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sbor::rust::str::FromStr;
+    use sbor::rust::string::ToString;
+
+    fn ratio(numerator: i64, denominator: i64) -> Ratio {
+        Ratio::new(numerator, denominator).unwrap()
+    }
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_ratio_is_kept_in_lowest_terms() {
+        let r = ratio(6, -4);
+        assert_eq!(r.numerator(), &BigInt::from(-3));
+        assert_eq!(r.denominator(), &BigInt::from(2));
+        assert_eq!(r.to_string(), "-3/2");
+        assert_eq!(ratio(10, 5).to_string(), "2");
+        assert_eq!(Ratio::from(dec("0.25")), ratio(1, 4));
+        assert_eq!(
+            Ratio::from(PreciseDecimal::from_str("0.25").unwrap()),
+            ratio(1, 4)
+        );
+        assert_eq!(Ratio::new(dec("3"), dec("0.5")), Some(ratio(6, 1)));
+        assert_eq!(Ratio::new(1, 0), None);
+    }
+
+    #[test]
+    fn test_ratio_arithmetic_is_exact() {
+        assert_eq!(ratio(1, 3) + ratio(1, 6), ratio(1, 2));
+        assert_eq!(ratio(1, 3) - ratio(1, 2), ratio(-1, 6));
+        assert_eq!(ratio(2, 3) * ratio(9, 4), ratio(3, 2));
+        assert_eq!(ratio(2, 3) / ratio(-4, 9), ratio(-3, 2));
+        assert_eq!(ratio(2, 3).checked_div(Ratio::zero()), None);
+        assert_eq!(ratio(-2, 3).checked_recip(), Some(ratio(-3, 2)));
+        assert_eq!(Ratio::zero().checked_recip(), None);
+        assert_eq!(ratio(-7, 2).abs(), ratio(7, 2));
+        assert!(ratio(1, 3) < ratio(1, 2));
+        assert!(ratio(-1, 2) < ratio(-1, 3));
+
+        // A third of Decimal::MAX, added three times, is exactly Decimal::MAX
+        let third = Ratio::from(Decimal::MAX) / Ratio::from(3);
+        let mut sum = Ratio::zero();
+        for _ in 0..3 {
+            sum += third.clone();
+        }
+        assert_eq!(sum, Ratio::from(Decimal::MAX));
+    }
+
+    #[test]
+    fn test_ratio_to_decimal() {
+        // (mode, 2/3, -2/3, 2.5 * 10^-18)
+        let cases = [
+            (
+                RoundingMode::ToPositiveInfinity,
+                "0.666666666666666667",
+                "-0.666666666666666666",
+                "0.000000000000000003",
+            ),
+            (
+                RoundingMode::ToNegativeInfinity,
+                "0.666666666666666666",
+                "-0.666666666666666667",
+                "0.000000000000000002",
+            ),
+            (
+                RoundingMode::ToZero,
+                "0.666666666666666666",
+                "-0.666666666666666666",
+                "0.000000000000000002",
+            ),
+            (
+                RoundingMode::AwayFromZero,
+                "0.666666666666666667",
+                "-0.666666666666666667",
+                "0.000000000000000003",
+            ),
+            (
+                RoundingMode::ToNearestMidpointTowardZero,
+                "0.666666666666666667",
+                "-0.666666666666666667",
+                "0.000000000000000002",
+            ),
+            (
+                RoundingMode::ToNearestMidpointAwayFromZero,
+                "0.666666666666666667",
+                "-0.666666666666666667",
+                "0.000000000000000003",
+            ),
+            (
+                RoundingMode::ToNearestMidpointToEven,
+                "0.666666666666666667",
+                "-0.666666666666666667",
+                "0.000000000000000002",
+            ),
+        ];
+        let midpoint = Ratio::new(dec("0.000000000000000005"), 2).unwrap();
+        for (mode, positive, negative, midpoint_rounded) in cases {
+            let truncate = |r: Ratio| r.checked_to_decimal(mode);
+            assert_eq!(truncate(ratio(2, 3)), Some(dec(positive)), "{:?}", mode);
+            assert_eq!(truncate(ratio(-2, 3)), Some(dec(negative)), "{:?}", mode);
+            assert_eq!(
+                truncate(midpoint.clone()),
+                Some(dec(midpoint_rounded)),
+                "{:?}",
+                mode
+            );
+        }
+
+        let precise = ratio(1, 3).checked_to_precise_decimal(RoundingMode::ToNearestMidpointToEven);
+        assert_eq!(
+            precise,
+            Some(PreciseDecimal::from_str("0.333333333333333333333333333333333333").unwrap())
+        );
+
+        let overflow = Ratio::from(Decimal::MAX) + Ratio::one();
+        assert_eq!(overflow.checked_to_decimal(RoundingMode::ToZero), None);
+        assert!(overflow
+            .checked_to_precise_decimal(RoundingMode::ToZero)
+            .is_some());
+    }
+}
@@ -1,6 +1,9 @@
 #[cfg(feature = "fuzzing")]
 use arbitrary::Arbitrary;
 use core::cmp::Ordering;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Signed, Zero};
 use sbor::Sbor;
 #[cfg(feature = "fuzzing")]
 use serde::{Deserialize, Serialize};
@@ -85,3 +88,30 @@ impl ResolvedRoundingStrategy {
         }
     }
 }
+
+/// Rounds `numerator / denominator` to an integer with the given mode.
+/// The denominator must be positive.
+pub(crate) fn round_quotient(
+    numerator: &BigInt,
+    denominator: &BigInt,
+    mode: RoundingMode,
+) -> BigInt {
+    let (quotient, remainder) = numerator.div_mod_floor(denominator);
+    if remainder.is_zero() {
+        return quotient;
+    }
+    let strategy = ResolvedRoundingStrategy::from_mode(mode, numerator.is_positive(), || {
+        (&remainder * 2u8).cmp(denominator)
+    });
+    match strategy {
+        ResolvedRoundingStrategy::RoundUp => quotient + 1u8,
+        ResolvedRoundingStrategy::RoundDown => quotient,
+        ResolvedRoundingStrategy::RoundToEven => {
+            if quotient.is_even() {
+                quotient
+            } else {
+                quotient + 1u8
+            }
+        }
+    }
+}
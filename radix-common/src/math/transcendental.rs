@@ -0,0 +1,313 @@
+//! Deterministic transcendental functions shared by [`Decimal`](super::Decimal) and
+//! [`PreciseDecimal`](super::PreciseDecimal).
+//!
+//! Every number is a fixed-point [`BigInt`] `n` representing `n / 10^scale`. Only integer
+//! arithmetic is used, so the results are the same on every platform.
+//!
+//! The functions first calculate an approximation with [`GUARD_DIGITS`] extra digits, whose
+//! error is below `10^(SNAP_TOLERANCE_DIGITS - GUARD_DIGITS)` units in the last place of the
+//! result, and then round it with the requested [`RoundingMode`]. When the approximation is that
+//! close to a number with one more digit than the result (which includes every rounding
+//! boundary), the function checks with exact arithmetic whether the result is exactly that
+//! number - e.g. `log_2(8) = 3` or `pow(4, 0.5) = 2` - and if so rounds the exact result instead.
+//! Otherwise, the result is correctly rounded unless the exact result is within `10^-GUARD_DIGITS`
+//! units in the last place of a rounding boundary, in which case it can be one unit in the last
+//! place away from the correctly rounded result.
+
+use crate::math::rounding_mode::*;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Pow, Signed, ToPrimitive, Zero};
+
+/// The number of extra digits the approximations are calculated with.
+pub(crate) const GUARD_DIGITS: u32 = 24;
+/// Approximations within `10^SNAP_TOLERANCE_DIGITS` units of a candidate are checked for exactness.
+const SNAP_TOLERANCE_DIGITS: u32 = 4;
+/// The exponent beyond which `exp` overflows (or underflows to zero) for every supported type.
+const MAX_EXP_ARGUMENT: u32 = 200;
+/// The largest integer exponent for which `pow` is calculated with exact rational arithmetic.
+const MAX_EXACT_EXPONENT: i64 = 256;
+/// The largest number of digits of the results of `pow` which don't overflow.
+const MAX_RESULT_DIGITS: u32 = 45;
+
+fn pow10(exponent: u32) -> BigInt {
+    BigInt::from(10u8).pow(exponent)
+}
+
+/// Returns `(x / 10^scale)^exponent` as a fraction with a positive denominator.
+/// `x` must not be zero if the exponent is negative.
+fn rational_pow(x: &BigInt, scale: u32, exponent: i64) -> (BigInt, BigInt) {
+    let magnitude = exponent.unsigned_abs() as u32;
+    let (numerator, denominator) = if exponent >= 0 {
+        (x.pow(magnitude), pow10(scale * magnitude))
+    } else {
+        (pow10(scale * magnitude), x.pow(magnitude))
+    };
+    if denominator.is_negative() {
+        (-numerator, -denominator)
+    } else {
+        (numerator, denominator)
+    }
+}
+
+/// Rounds an approximation at `working_scale` to `scale`, as described in the module docs.
+fn round_approximation(
+    approximation: &BigInt,
+    working_scale: u32,
+    scale: u32,
+    mode: RoundingMode,
+    is_exact: impl FnOnce(&BigInt) -> bool,
+) -> BigInt {
+    let candidate_divisor = pow10(working_scale - scale - 1);
+    let candidate = round_quotient(
+        approximation,
+        &candidate_divisor,
+        RoundingMode::ToNearestMidpointToEven,
+    );
+    let distance = (approximation - &candidate * &candidate_divisor).abs();
+    if distance <= pow10(SNAP_TOLERANCE_DIGITS) && is_exact(&candidate) {
+        return round_quotient(&candidate, &BigInt::from(10u8), mode);
+    }
+    round_quotient(approximation, &pow10(working_scale - scale), mode)
+}
+
+/// Returns `atanh(z) = z + z^3/3 + z^5/5 + ...` for `0 <= z < 1`, at the scale of `one`.
+fn atanh_series(z: &BigInt, one: &BigInt) -> BigInt {
+    let z_squared = z * z / one;
+    let mut term = z.clone();
+    let mut sum = BigInt::zero();
+    let mut divisor = 1u32;
+    while !term.is_zero() {
+        sum += &term / divisor;
+        term = term * &z_squared / one;
+        divisor += 2;
+    }
+    sum
+}
+
+/// Returns `ln(2) = 2 * atanh(1/3)` at the scale of `one`.
+fn ln2(one: &BigInt) -> BigInt {
+    atanh_series(&(one / 3u8), one) * 2u8
+}
+
+/// Returns `ln(x)` at `result_scale`, within 2 units. `x` must be positive.
+fn ln_approximation(x: &BigInt, scale: u32, result_scale: u32) -> BigInt {
+    let working_scale = result_scale + 10;
+    let one = pow10(working_scale);
+    let x_one = pow10(scale);
+
+    // Reduce to x = m * 2^k, with 1 <= m < 2
+    let as_fraction = |k: i64| {
+        if k >= 0 {
+            (x.clone(), &x_one << k as usize)
+        } else {
+            (x << (-k) as usize, x_one.clone())
+        }
+    };
+    let mut k = x.bits() as i64 - x_one.bits() as i64;
+    let (numerator, denominator) = loop {
+        let (numerator, denominator) = as_fraction(k);
+        if numerator < denominator {
+            k -= 1;
+        } else if numerator >= &denominator * 2u8 {
+            k += 1;
+        } else {
+            break (numerator, denominator);
+        }
+    };
+    let m = numerator * &one / denominator;
+
+    // ln(m) = 2 * atanh((m - 1) / (m + 1)), where 0 <= (m - 1) / (m + 1) < 1/3
+    let z = (&m - &one) * &one / (&m + &one);
+    let ln_m = atanh_series(&z, &one) * 2u8;
+    let ln_x = ln_m + ln2(&one) * k;
+    ln_x.div_floor(&pow10(working_scale - result_scale))
+}
+
+/// Returns `e^y` at `result_scale`, within 2 units, or `None` if it's too large for any of the
+/// supported types.
+fn exp_approximation(y: &BigInt, scale: u32, result_scale: u32) -> Option<BigInt> {
+    if y.abs() > pow10(scale) * MAX_EXP_ARGUMENT {
+        return if y.is_negative() {
+            Some(BigInt::zero())
+        } else {
+            None
+        };
+    }
+
+    // Reduce to y = k * ln(2) + r, with |r| <= ln(2) / 2 (approximately), so e^y = 2^k * e^r
+    let k_scale = 20;
+    let k_one = pow10(k_scale);
+    let y_at_k_scale = (y * &k_one).div_floor(&pow10(scale));
+    let k = round_quotient(
+        &y_at_k_scale,
+        &ln2(&k_one),
+        RoundingMode::ToNearestMidpointToEven,
+    )
+    .to_i64()
+    .expect("|k| is bounded by MAX_EXP_ARGUMENT");
+
+    // Multiplying by 2^k scales up the error, so extra digits are needed
+    let extra_digits = if k > 0 {
+        (k as u32) * 30103 / 100000 + 1
+    } else {
+        0
+    };
+    let working_scale = result_scale + extra_digits + 10;
+    let one = pow10(working_scale);
+    let y_at_working_scale = if working_scale >= scale {
+        y * pow10(working_scale - scale)
+    } else {
+        y.div_floor(&pow10(scale - working_scale))
+    };
+    let r = y_at_working_scale - ln2(&one) * k;
+
+    // e^r = 1 + r + r^2/2! + r^3/3! + ...
+    let mut term = one.clone();
+    let mut sum = one.clone();
+    let mut divisor = 1u32;
+    loop {
+        term = term * &r / &one / divisor;
+        if term.is_zero() {
+            break;
+        }
+        sum += &term;
+        divisor += 1;
+    }
+
+    let exp_y = if k >= 0 {
+        sum << k as usize
+    } else {
+        sum >> (-k) as usize
+    };
+    Some(exp_y.div_floor(&pow10(working_scale - result_scale)))
+}
+
+/// Returns `ln(x)` rounded to `scale`, or `None` if `x` isn't positive.
+pub(crate) fn ln(x: &BigInt, scale: u32, mode: RoundingMode) -> Option<BigInt> {
+    if !x.is_positive() {
+        return None;
+    }
+    let working_scale = scale + GUARD_DIGITS;
+    let approximation = ln_approximation(x, scale, working_scale);
+    Some(round_approximation(
+        &approximation,
+        working_scale,
+        scale,
+        mode,
+        // ln(x) is irrational for every rational x != 1, and ln(1) is approximated exactly
+        |_| false,
+    ))
+}
+
+/// Returns `e^x` rounded to `scale`, or `None` if it's too large for any of the supported types.
+pub(crate) fn exp(x: &BigInt, scale: u32, mode: RoundingMode) -> Option<BigInt> {
+    let working_scale = scale + GUARD_DIGITS;
+    let approximation = exp_approximation(x, scale, working_scale)?;
+    if approximation.is_zero() {
+        // The exact result is positive, but smaller than the last place of the approximation
+        return Some(round_quotient(
+            &BigInt::from(1u8),
+            &pow10(working_scale - scale),
+            mode,
+        ));
+    }
+    Some(round_approximation(
+        &approximation,
+        working_scale,
+        scale,
+        mode,
+        // e^x is irrational for every rational x != 0, and e^0 is approximated exactly
+        |_| false,
+    ))
+}
+
+/// Returns `log_base(x)` rounded to `scale`, or `None` if `x` or `base` isn't positive, or if
+/// `base` is one.
+pub(crate) fn log(x: &BigInt, base: &BigInt, scale: u32, mode: RoundingMode) -> Option<BigInt> {
+    let one = pow10(scale);
+    if !x.is_positive() || !base.is_positive() || *base == one {
+        return None;
+    }
+    let working_scale = scale + GUARD_DIGITS;
+    // ln(base) can be as small as 10^-scale, which scales up the error of the quotient
+    let ln_scale = working_scale + 2 * scale + 4;
+    let ln_x = ln_approximation(x, scale, ln_scale);
+    let ln_base = ln_approximation(base, scale, ln_scale);
+    let approximation = (ln_x * pow10(working_scale)).div_floor(&ln_base);
+    Some(round_approximation(
+        &approximation,
+        working_scale,
+        scale,
+        mode,
+        |candidate| {
+            // Only integer logarithms are checked for exactness
+            let (k, fraction) = candidate.div_mod_floor(&(&one * 10u8));
+            match k.to_i64() {
+                Some(k) if fraction.is_zero() && k.abs() <= MAX_EXACT_EXPONENT => {
+                    let (numerator, denominator) = rational_pow(base, scale, k);
+                    numerator * &one == x * denominator
+                }
+                _ => false,
+            }
+        },
+    ))
+}
+
+/// Returns `x^y` rounded to `scale`, or `None` if it's undefined (`0^y` for negative `y`, or `x^y`
+/// for negative `x` and non-integer `y`) or too large for any of the supported types.
+pub(crate) fn pow(x: &BigInt, y: &BigInt, scale: u32, mode: RoundingMode) -> Option<BigInt> {
+    let one = pow10(scale);
+    if y.is_zero() {
+        return Some(one);
+    }
+    if x.is_zero() {
+        return if y.is_positive() {
+            Some(BigInt::zero())
+        } else {
+            None
+        };
+    }
+
+    let (y_integer, y_fraction) = y.div_mod_floor(&one);
+    if y_fraction.is_zero() {
+        if let Some(exponent) = y_integer.to_i64() {
+            if exponent.abs() <= MAX_EXACT_EXPONENT {
+                let (numerator, denominator) = rational_pow(x, scale, exponent);
+                return Some(round_quotient(&(numerator * &one), &denominator, mode));
+            }
+        }
+    } else if x.is_negative() {
+        return None;
+    }
+
+    // x^y = (-1)^y * e^(y * ln|x|)
+    let negate = x.is_negative() && y_integer.is_odd();
+    let working_scale = scale + GUARD_DIGITS;
+    // The error of y * ln|x| is scaled up by y and then by the result
+    let y_digits = (y_integer.bits() as u32) * 30103 / 100000 + 1;
+    let ln_scale = working_scale + MAX_RESULT_DIGITS + y_digits;
+    let exponent = ln_approximation(&x.abs(), scale, ln_scale) * y / &one;
+    let magnitude = exp_approximation(&exponent, ln_scale, working_scale)?;
+    let approximation = if negate { -magnitude } else { magnitude };
+    Some(round_approximation(
+        &approximation,
+        working_scale,
+        scale,
+        mode,
+        |candidate| {
+            // With y = p / q, checks candidate^q = x^p for small q and p
+            let divisor = y.gcd(&one);
+            let (p, q) = (y / &divisor, &one / &divisor);
+            match (p.to_i64(), q.to_i64()) {
+                (Some(p), Some(q)) if p.abs() <= MAX_EXACT_EXPONENT && q <= 64 => {
+                    let (candidate_numerator, candidate_denominator) =
+                        rational_pow(candidate, scale + 1, q);
+                    let (x_numerator, x_denominator) = rational_pow(x, scale, p);
+                    candidate_numerator * x_denominator == x_numerator * candidate_denominator
+                }
+                _ => false,
+            }
+        },
+    ))
+}
@@ -16,11 +16,21 @@ pub enum NativeCodeId {
     /// * Changes   : Add limit to reserved role key
     PackageCode2 = 22u64,
 
+    /// * Introduced: Cuttlefish
+    /// * Coverage  : `PACKAGE_PUBLISH_NATIVE_IDENT`, `PACKAGE_PUBLISH_WASM_IDENT` and `PACKAGE_PUBLISH_WASM_ADVANCED_IDENT`
+    /// * Changes   : Allow time conditions in function access rules
+    PackageCode3 = 26u64,
+
     /// * Introduced: Babylon
     /// * Coverage  : All resource blueprints
     /// * Changes   : N/A
     ResourceCode1 = 1u64,
 
+    /// * Introduced: Cuttlefish
+    /// * Coverage  : `AUTH_ZONE_ASSERT_ACCESS_RULE_IDENT`
+    /// * Changes   : Evaluate time conditions of access rules
+    ResourceCode2 = 24u64,
+
     /// * Introduced: Babylon
     /// * Coverage  : All identity blueprints
     /// * Changes   : N/A
@@ -110,6 +120,11 @@ pub enum NativeCodeId {
     /// * Coverage  : `ROLE_ASSIGNMENT_GET_OWNER_ROLE_IDENT`
     /// * Changes   : Add `get_owner_role``
     RoleAssignmentCode2 = 18u64,
+
+    /// * Introduced: Cuttlefish
+    /// * Coverage  : `ROLE_ASSIGNMENT_CREATE_IDENT`, `ROLE_ASSIGNMENT_SET_OWNER_IDENT` and `ROLE_ASSIGNMENT_SET_IDENT`
+    /// * Changes   : Allow time conditions in access rules
+    RoleAssignmentCode3 = 25u64,
 }
 
 pub const PACKAGE_FIELDS_PARTITION_OFFSET: PartitionOffset = PartitionOffset(0u8);
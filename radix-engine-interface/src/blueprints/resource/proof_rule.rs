@@ -96,6 +96,55 @@ impl Describe<ScryptoCustomTypeKind> for ProofRule {
     }
 }
 
+/// A condition on the consensus manager clock, evaluated by the auth module alongside the proof
+/// rules of an access rule.
+///
+/// Times are compared against the proposer timestamp rounded down to the minute, as returned by
+/// the consensus manager for `TimePrecision::Minute`.
+#[cfg_attr(
+    feature = "fuzzing",
+    derive(Arbitrary, serde::Serialize, serde::Deserialize)
+)]
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Ord,
+    PartialOrd,
+    ManifestSbor,
+    ScryptoCategorize,
+    ScryptoEncode,
+    ScryptoDecode,
+)]
+pub enum TimeCondition {
+    /// Satisfied from the start of the given epoch onwards.
+    EpochAtLeast(Epoch),
+    /// Satisfied until the given epoch starts.
+    EpochBefore(Epoch),
+    /// Satisfied from the given instant onwards.
+    TimeAtLeast(Instant),
+    /// Satisfied until the given instant.
+    TimeBefore(Instant),
+}
+
+impl Describe<ScryptoCustomTypeKind> for TimeCondition {
+    const TYPE_ID: RustTypeId =
+        RustTypeId::WellKnown(well_known_scrypto_custom_types::TIME_CONDITION_TYPE);
+
+    fn type_data() -> ScryptoTypeData<RustTypeId> {
+        well_known_scrypto_custom_types::time_condition_type_data()
+    }
+}
+
+impl From<TimeCondition> for AccessRuleNode {
+    fn from(time_condition: TimeCondition) -> Self {
+        AccessRuleNode::TimeCondition(time_condition)
+    }
+}
+
 impl From<ResourceAddress> for AccessRuleNode {
     fn from(resource_address: ResourceAddress) -> Self {
         AccessRuleNode::ProofRule(ProofRule::Require(resource_address.into()))
@@ -135,6 +184,7 @@ pub enum AccessRuleNode {
     ProofRule(ProofRule),
     AnyOf(Vec<AccessRuleNode>),
     AllOf(Vec<AccessRuleNode>),
+    TimeCondition(TimeCondition),
 }
 
 impl Describe<ScryptoCustomTypeKind> for AccessRuleNode {
@@ -220,6 +270,26 @@ where
     AccessRuleNode::ProofRule(ProofRule::AmountOf(amount.into(), resource.into()))
 }
 
+/// Requires the current epoch to be at least the given epoch.
+pub fn epoch_at_least(epoch: Epoch) -> AccessRuleNode {
+    TimeCondition::EpochAtLeast(epoch).into()
+}
+
+/// Requires the current epoch to be before the given epoch.
+pub fn epoch_before(epoch: Epoch) -> AccessRuleNode {
+    TimeCondition::EpochBefore(epoch).into()
+}
+
+/// Requires the current time, rounded down to the minute, to be at least the given instant.
+pub fn time_at_least(instant: Instant) -> AccessRuleNode {
+    TimeCondition::TimeAtLeast(instant).into()
+}
+
+/// Requires the current time, rounded down to the minute, to be before the given instant.
+pub fn time_before(instant: Instant) -> AccessRuleNode {
+    TimeCondition::TimeBefore(instant).into()
+}
+
 #[cfg_attr(
     feature = "fuzzing",
     derive(Arbitrary, serde::Serialize, serde::Deserialize)
@@ -284,7 +354,7 @@ impl AccessRuleNode {
         visitor.visit(self, depth)?;
 
         match self {
-            AccessRuleNode::ProofRule(..) => {}
+            AccessRuleNode::ProofRule(..) | AccessRuleNode::TimeCondition(..) => {}
             AccessRuleNode::AnyOf(nodes) | AccessRuleNode::AllOf(nodes) => {
                 for node in nodes {
                     node.dfs_traverse_recursive(visitor, depth + 1)?;
@@ -42,6 +42,16 @@ mod tests {
         );
         test_equivalence(OWNER_ROLE_TYPE, OwnerRole::None);
         test_equivalence(ROLE_KEY_TYPE, RoleKey::from("MyRoleName"));
+        let time_condition = TimeCondition::TimeBefore(Instant::new(1_700_000_000));
+        test_equivalence(TIME_CONDITION_TYPE, time_condition);
+        test_equivalence(
+            TIME_CONDITION_TYPE,
+            TimeCondition::EpochAtLeast(Epoch::of(100)),
+        );
+        test_equivalence(
+            ACCESS_RULE_NODE_TYPE,
+            AccessRuleNode::TimeCondition(time_condition),
+        );
 
         // OTHER MODULE TYPES
         test_equivalence(MODULE_ID_TYPE, ModuleId::Main);
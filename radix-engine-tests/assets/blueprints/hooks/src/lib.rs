@@ -0,0 +1,79 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+mod loan_ticket {
+    struct LoanTicket {
+        amount: Decimal,
+        repaid: Decimal,
+    }
+
+    impl LoanTicket {
+        pub fn borrow(amount: Decimal) -> Owned<LoanTicket> {
+            Self {
+                amount,
+                repaid: Decimal::ZERO,
+            }
+            .instantiate()
+        }
+
+        pub fn repay(&mut self, amount: Decimal) {
+            self.repaid += amount;
+        }
+
+        pub fn close(ticket: Owned<LoanTicket>) {
+            ticket.drop_object();
+        }
+
+        #[on_drop]
+        fn ensure_repaid(&self) {
+            assert!(self.repaid >= self.amount, "Loan has not been repaid");
+        }
+
+        #[on_move]
+        fn ensure_returned_to_lender(
+            &self,
+            is_moving_down: bool,
+            _is_to_barrier: bool,
+            destination_blueprint_id: Option<BlueprintId>,
+        ) {
+            if is_moving_down {
+                let own_blueprint_id =
+                    BlueprintId::new(&Runtime::package_address(), Runtime::blueprint_name());
+                assert_eq!(
+                    destination_blueprint_id,
+                    Some(own_blueprint_id),
+                    "Loan tickets can only be passed to the LoanTicket blueprint"
+                );
+            }
+        }
+    }
+}
+
+#[blueprint]
+mod borrower {
+    use crate::loan_ticket::LoanTicket;
+    use crate::loan_ticket::LoanTicketFunctions;
+
+    struct Borrower {}
+
+    impl Borrower {
+        pub fn borrow_and_repay(amount: Decimal, repayment: Decimal) {
+            let ticket = Blueprint::<LoanTicket>::borrow(amount);
+            ticket.repay(repayment);
+            Blueprint::<LoanTicket>::close(ticket);
+        }
+
+        pub fn borrow_and_discard(amount: Decimal) {
+            let _ticket = Blueprint::<LoanTicket>::borrow(amount);
+        }
+
+        pub fn borrow_and_hand_over(amount: Decimal) {
+            let ticket = Blueprint::<LoanTicket>::borrow(amount);
+            Blueprint::<Borrower>::take_ticket(ticket);
+        }
+
+        pub fn take_ticket(ticket: Owned<LoanTicket>) {
+            Blueprint::<LoanTicket>::close(ticket);
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+#[collections(
+    balances: Index<String, Decimal>,
+    orders: SortedIndex<u64, String>,
+)]
+mod index_test {
+    struct IndexTest;
+
+    impl IndexTest {
+        pub fn new() -> Global<IndexTest> {
+            Self {}
+                .instantiate()
+                .prepare_to_globalize(OwnerRole::None)
+                .globalize()
+        }
+
+        pub fn insert_balance(&mut self, key: String, value: Decimal) {
+            self.balances().insert(key, value);
+        }
+
+        pub fn remove_balance(&mut self, key: String) -> Option<Decimal> {
+            self.balances().remove(&key)
+        }
+
+        pub fn scan_balance_keys(&self, limit: u32) -> Vec<String> {
+            self.balances().scan_keys(limit)
+        }
+
+        pub fn drain_balances(&mut self, limit: u32) -> Vec<(String, Decimal)> {
+            self.balances().drain(limit)
+        }
+
+        pub fn insert_order(&mut self, sort_key: u16, id: u64, description: String) {
+            self.orders().insert(sort_key, id, description);
+        }
+
+        pub fn remove_order(&mut self, sort_key: u16, id: u64) -> Option<String> {
+            self.orders().remove(sort_key, &id)
+        }
+
+        pub fn scan_orders(&self, limit: u32) -> Vec<(u16, u64, String)> {
+            self.orders().scan(limit)
+        }
+    }
+}
@@ -1,3 +1,4 @@
 mod assert_access_rule;
 mod function_access_rules;
 mod mutable_access_rules;
+mod stored_access_rule;
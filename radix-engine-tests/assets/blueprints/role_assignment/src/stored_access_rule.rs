@@ -0,0 +1,37 @@
+use scrypto::prelude::*;
+
+#[blueprint]
+mod stored_access_rule {
+    struct StoredAccessRule {
+        access_rule: AccessRule,
+        access_rules: KeyValueStore<u32, AccessRule>,
+    }
+
+    impl StoredAccessRule {
+        pub fn new() -> Global<StoredAccessRule> {
+            Self::new_with_access_rule(rule!(allow_all))
+        }
+
+        pub fn new_with_epoch_condition(epoch: Epoch) -> Global<StoredAccessRule> {
+            Self::new_with_access_rule(rule!(epoch_at_least(epoch)))
+        }
+
+        pub fn access_rule(&self) -> AccessRule {
+            self.access_rule.clone()
+        }
+
+        pub fn store_epoch_condition(&mut self, epoch: Epoch) {
+            self.access_rules.insert(0u32, rule!(epoch_at_least(epoch)));
+        }
+
+        fn new_with_access_rule(access_rule: AccessRule) -> Global<StoredAccessRule> {
+            Self {
+                access_rule,
+                access_rules: KeyValueStore::new(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+    }
+}
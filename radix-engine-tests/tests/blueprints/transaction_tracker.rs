@@ -1,8 +1,10 @@
 use radix_common::prelude::*;
+use radix_engine::blueprints::transaction_tracker::TransactionStatusV1;
 use radix_engine::errors::RejectionReason;
 use radix_engine::track::{BatchPartitionStateUpdate, NodeStateUpdates, PartitionStateUpdates};
 use radix_engine::transaction::ExecutionConfig;
 use radix_engine_interface::blueprints::consensus_manager::EpochChangeCondition;
+use radix_substate_store_queries::query::TransactionTrackerReader;
 use radix_transactions::errors::TransactionValidationError;
 use scrypto_test::prelude::*;
 
@@ -89,6 +91,41 @@ fn test_transaction_replay_protection() {
     receipt.expect_commit_success();
 }
 
+#[test]
+fn test_committed_intent_status_can_be_read_from_the_transaction_tracker() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+    let current_epoch = ledger.get_current_epoch();
+    let expiry_epoch = current_epoch.after(10).unwrap();
+    let transaction = create_notarized_transaction(TransactionParams {
+        start_epoch_inclusive: current_epoch,
+        end_epoch_exclusive: expiry_epoch,
+    });
+    let validated = get_validated(&transaction).unwrap();
+    let intent_hash = validated.intent_hash();
+    assert_eq!(ledger.get_intent_status(&intent_hash), None);
+
+    // Act
+    let receipt = ledger.execute_transaction(
+        validated.get_executable(),
+        ExecutionConfig::for_notarized_transaction(NetworkDefinition::simulator()),
+    );
+    receipt.expect_commit_success();
+
+    // Assert
+    let tracked_intent = ledger.get_intent_status(&intent_hash).unwrap();
+    assert_eq!(tracked_intent.intent_hash, intent_hash);
+    assert_eq!(tracked_intent.status, TransactionStatusV1::CommittedSuccess);
+    assert!(tracked_intent.expiry_epoch_start_inclusive <= expiry_epoch);
+    assert!(expiry_epoch < tracked_intent.expiry_epoch_end_exclusive);
+    assert_eq!(
+        TransactionTrackerReader::new(ledger.substate_db())
+            .intent_status_with_expiry_epoch(&intent_hash, expiry_epoch),
+        Some(tracked_intent.clone())
+    );
+    assert_eq!(ledger.list_tracked_intents(), vec![tracked_intent]);
+}
+
 fn get_validated(
     transaction: &NotarizedTransactionV1,
 ) -> Result<ValidatedNotarizedTransactionV1, TransactionValidationError> {
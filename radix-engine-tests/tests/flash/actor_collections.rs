@@ -0,0 +1,97 @@
+use radix_common::prelude::*;
+use radix_engine::blueprints::package::PackageError;
+use radix_engine::errors::ApplicationError;
+use radix_engine::errors::RuntimeError;
+use radix_engine::updates::*;
+use radix_engine::vm::*;
+use radix_engine_tests::common::*;
+use scrypto_test::prelude::*;
+
+#[test]
+fn publishing_index_package_without_vm_boot_flash_should_fail() {
+    run_flash_test(false, false);
+}
+
+#[test]
+fn publishing_index_package_with_vm_boot_flash_should_succeed() {
+    run_flash_test(true, true);
+}
+
+fn run_flash_test(flash_substates: bool, expect_success: bool) {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .with_protocol_version(ProtocolVersion::Bottlenose)
+        .build();
+
+    if flash_substates {
+        let cuttlefish_protocol_update_batch_generator = CuttlefishSettings::all_disabled()
+            .enable(|item| &mut item.vm_boot_to_scrypto_v1_2)
+            .create_batch_generator();
+        for batch_index in 0..cuttlefish_protocol_update_batch_generator.batch_count() {
+            let batch = cuttlefish_protocol_update_batch_generator
+                .generate_batch(ledger.substate_db(), batch_index);
+            for transaction in batch.transactions {
+                let ProtocolUpdateTransactionDetails::FlashV1Transaction(
+                    FlashProtocolUpdateTransactionDetails { state_updates, .. },
+                ) = transaction
+                else {
+                    panic!("Only flash transactions are expected");
+                };
+                ledger
+                    .substate_db_mut()
+                    .commit(&state_updates.create_database_updates::<SpreadPrefixKeyMapper>())
+            }
+        }
+    }
+
+    // Act
+    let receipt = ledger.try_publish_package(PackageLoader::get("index"));
+
+    // Assert
+    if expect_success {
+        receipt.expect_commit_success();
+    } else {
+        receipt.expect_specific_failure(|e| {
+            matches!(
+                e,
+                RuntimeError::ApplicationError(ApplicationError::PackageError(
+                    PackageError::InvalidWasm(..)
+                ))
+            )
+        });
+    }
+}
+
+#[test]
+fn cuttlefish_enacts_scrypto_vm_v1_2() {
+    // Arrange
+    let read_scrypto_version = |ledger: &DefaultLedgerSimulator| {
+        ledger
+            .substate_db()
+            .get_mapped::<SpreadPrefixKeyMapper, VmBoot>(
+                TRANSACTION_TRACKER.as_node_id(),
+                BOOT_LOADER_PARTITION,
+                &SubstateKey::Field(BOOT_LOADER_VM_BOOT_FIELD_KEY),
+            )
+            .unwrap()
+            .get_scrypto_version()
+    };
+
+    // Act
+    let bottlenose_ledger = LedgerSimulatorBuilder::new()
+        .with_protocol_version(ProtocolVersion::Bottlenose)
+        .build();
+    let cuttlefish_ledger = LedgerSimulatorBuilder::new()
+        .with_protocol_version(ProtocolVersion::Cuttlefish)
+        .build();
+
+    // Assert
+    assert_eq!(
+        read_scrypto_version(&bottlenose_ledger),
+        ScryptoVmVersion::V1_1
+    );
+    assert_eq!(
+        read_scrypto_version(&cuttlefish_ledger),
+        ScryptoVmVersion::actor_collections_added()
+    );
+}
@@ -0,0 +1,172 @@
+use radix_common::prelude::*;
+use radix_engine::blueprints::consensus_manager::*;
+use radix_engine::system::system_db_reader::*;
+use radix_engine::track::*;
+use radix_engine::updates::*;
+use radix_substate_store_interface::interface::*;
+use radix_transactions::model::*;
+use scrypto_test::prelude::*;
+
+/// A flash update reading the current configuration from the database, as a private network
+/// would ship it.
+#[derive(Clone)]
+struct ValidatorCreationCostUpdate {
+    validator_creation_usd_cost: Decimal,
+}
+
+impl ProtocolUpdateBatchGenerator for ValidatorCreationCostUpdate {
+    fn generate_batch(
+        &self,
+        store: &dyn SubstateDatabase,
+        batch_index: u32,
+    ) -> ProtocolUpdateBatch {
+        if batch_index != 0 {
+            panic!("batch index out of range")
+        }
+
+        let mut config = read_consensus_manager_config(store);
+        config.config.validator_creation_usd_cost = self.validator_creation_usd_cost;
+
+        let state_updates = StateUpdates {
+            by_node: indexmap!(
+                CONSENSUS_MANAGER.into_node_id() => NodeStateUpdates::Delta {
+                    by_partition: indexmap! {
+                        MAIN_BASE_PARTITION => PartitionStateUpdates::Delta {
+                            by_substate: indexmap! {
+                                SubstateKey::Field(ConsensusManagerField::Configuration.field_index()) => DatabaseUpdate::Set(
+                                    scrypto_encode(&config.into_locked_substate()).unwrap()
+                                )
+                            }
+                        },
+                    }
+                }
+            ),
+        };
+
+        ProtocolUpdateBatch {
+            transactions: vec![ProtocolUpdateTransactionDetails::flash(
+                "validator-creation-cost",
+                state_updates,
+            )],
+        }
+    }
+
+    fn batch_count(&self) -> u32 {
+        1
+    }
+}
+
+#[derive(Clone)]
+struct SystemTransactionUpdate {
+    transaction: SystemTransactionV1,
+}
+
+impl ProtocolUpdateBatchGenerator for SystemTransactionUpdate {
+    fn generate_batch(&self, _: &dyn SubstateDatabase, batch_index: u32) -> ProtocolUpdateBatch {
+        if batch_index != 0 {
+            panic!("batch index out of range")
+        }
+        ProtocolUpdateBatch {
+            transactions: vec![ProtocolUpdateTransactionDetails::system(
+                "airdrop",
+                self.transaction.clone(),
+            )],
+        }
+    }
+
+    fn batch_count(&self) -> u32 {
+        1
+    }
+}
+
+fn read_consensus_manager_config(store: &dyn SubstateDatabase) -> ConsensusManagerConfigSubstate {
+    let versioned_config: VersionedConsensusManagerConfiguration = SystemDatabaseReader::new(store)
+        .read_typed_object_field(
+            &CONSENSUS_MANAGER.into_node_id(),
+            ModuleId::Main,
+            ConsensusManagerField::Configuration.field_index(),
+        )
+        .unwrap();
+    versioned_config.fully_update_and_into_latest_version()
+}
+
+#[test]
+fn custom_flash_protocol_update_is_committed() {
+    // Act
+    let ledger = LedgerSimulatorBuilder::new()
+        .without_kernel_trace()
+        .with_custom_protocol(|builder| {
+            builder
+                .with_custom_update(CustomProtocolUpdate::new(
+                    "validator-creation-cost",
+                    ProtocolVersion::LATEST,
+                    ValidatorCreationCostUpdate {
+                        validator_creation_usd_cost: dec!(1234),
+                    },
+                ))
+                .until_latest_protocol_version()
+        })
+        .build();
+
+    // Assert
+    let config = read_consensus_manager_config(ledger.substate_db());
+    assert_eq!(config.config.validator_creation_usd_cost, dec!(1234));
+}
+
+#[test]
+fn custom_flash_protocol_update_enacted_before_builtin_update_can_be_overridden_by_it() {
+    // Act
+    let ledger = LedgerSimulatorBuilder::new()
+        .without_kernel_trace()
+        .with_custom_protocol(|builder| {
+            builder
+                .with_custom_update(CustomProtocolUpdate::new(
+                    "validator-creation-cost",
+                    ProtocolVersion::Babylon,
+                    ValidatorCreationCostUpdate {
+                        validator_creation_usd_cost: dec!(1234),
+                    },
+                ))
+                .until(ProtocolVersion::Anemone)
+        })
+        .build();
+
+    // Assert
+    // The Anemone validator fee fix is enacted after the custom update.
+    let config = read_consensus_manager_config(ledger.substate_db());
+    assert_eq!(config.config.validator_creation_usd_cost, dec!(100));
+}
+
+#[test]
+fn custom_system_transaction_protocol_update_is_committed() {
+    // Arrange
+    let public_key = Secp256k1PrivateKey::from_u64(1).unwrap().public_key();
+    let account = ComponentAddress::virtual_account_from_public_key(&public_key);
+    let manifest = ManifestBuilder::new()
+        .get_free_xrd_from_faucet()
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+    let transaction = SystemTransactionV1 {
+        instructions: InstructionsV1(manifest.instructions),
+        pre_allocated_addresses: vec![],
+        blobs: BlobsV1 { blobs: vec![] },
+        hash_for_execution: hash("custom-protocol-update-airdrop"),
+    };
+
+    // Act
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .without_kernel_trace()
+        .with_custom_protocol(|builder| {
+            builder
+                .with_custom_update(CustomProtocolUpdate::new(
+                    "airdrop",
+                    ProtocolVersion::LATEST,
+                    SystemTransactionUpdate { transaction },
+                ))
+                .until_latest_protocol_version()
+        })
+        .build();
+
+    // Assert
+    assert_eq!(ledger.get_component_balance(account, XRD), dec!(10000));
+}
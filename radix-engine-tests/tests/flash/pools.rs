@@ -72,9 +72,13 @@ fn database_is_consistent_before_and_after_protocol_update() {
         for batch_index in 0..anemone_protocol_update_batch_generator.batch_count() {
             let batch = anemone_protocol_update_batch_generator
                 .generate_batch(ledger.substate_db(), batch_index);
-            for ProtocolUpdateTransactionDetails::FlashV1Transaction(
-                FlashProtocolUpdateTransactionDetails { state_updates, .. },
-            ) in batch.transactions {
+            for transaction in batch.transactions {
+                let ProtocolUpdateTransactionDetails::FlashV1Transaction(
+                    FlashProtocolUpdateTransactionDetails { state_updates, .. },
+                ) = transaction
+                else {
+                    panic!("Only flash transactions are expected");
+                };
                 ledger
                     .substate_db_mut()
                     .commit(&state_updates.create_database_updates::<SpreadPrefixKeyMapper>())
@@ -210,9 +214,13 @@ fn single_sided_contributions_to_two_resource_pool_are_only_allowed_after_protoc
         for batch_index in 0..anemone_protocol_update_batch_generator.batch_count() {
             let batch = anemone_protocol_update_batch_generator
                 .generate_batch(ledger.substate_db(), batch_index);
-            for ProtocolUpdateTransactionDetails::FlashV1Transaction(
-                FlashProtocolUpdateTransactionDetails { state_updates, .. },
-            ) in batch.transactions {
+            for transaction in batch.transactions {
+                let ProtocolUpdateTransactionDetails::FlashV1Transaction(
+                    FlashProtocolUpdateTransactionDetails { state_updates, .. },
+                ) = transaction
+                else {
+                    panic!("Only flash transactions are expected");
+                };
                 ledger
                     .substate_db_mut()
                     .commit(&state_updates.create_database_updates::<SpreadPrefixKeyMapper>())
@@ -374,9 +382,13 @@ fn single_sided_contributions_to_multi_resource_pool_are_only_allowed_after_prot
         for batch_index in 0..anemone_protocol_update_batch_generator.batch_count() {
             let batch = anemone_protocol_update_batch_generator
                 .generate_batch(ledger.substate_db(), batch_index);
-            for ProtocolUpdateTransactionDetails::FlashV1Transaction(
-                FlashProtocolUpdateTransactionDetails { state_updates, .. },
-            ) in batch.transactions {
+            for transaction in batch.transactions {
+                let ProtocolUpdateTransactionDetails::FlashV1Transaction(
+                    FlashProtocolUpdateTransactionDetails { state_updates, .. },
+                ) = transaction
+                else {
+                    panic!("Only flash transactions are expected");
+                };
                 ledger
                     .substate_db_mut()
                     .commit(&state_updates.create_database_updates::<SpreadPrefixKeyMapper>())
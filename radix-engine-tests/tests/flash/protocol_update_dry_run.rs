@@ -0,0 +1,201 @@
+use radix_common::prelude::*;
+use radix_engine::system::system_db_reader::*;
+use radix_engine::track::*;
+use radix_engine::updates::*;
+use radix_engine::vm::wasm::DefaultWasmEngine;
+use radix_engine::vm::{NoExtension, ScryptoVm, VmInit};
+use radix_substate_store_interface::interface::*;
+use radix_substate_store_queries::query::*;
+use radix_substate_store_queries::typed_substate_layout::*;
+use scrypto_test::prelude::*;
+
+fn dry_run_until_latest_protocol_version<S: SubstateDatabase>(
+    substate_db: &S,
+) -> Vec<ProtocolUpdateDryRun> {
+    let scrypto_vm = ScryptoVm::<DefaultWasmEngine>::default();
+    dry_run_protocol_updates(
+        substate_db,
+        ProtocolBuilder::for_simulator()
+            .until_latest_protocol_version()
+            .each_protocol_update_executor(),
+        VmInit::new(&scrypto_vm, NoExtension),
+    )
+}
+
+fn dry_run_validator_creation_cost_update<S: SubstateDatabase>(
+    substate_db: &S,
+    validator_creation_usd_cost: Decimal,
+) -> ProtocolUpdateDryRun {
+    let mut config = SystemDatabaseReader::new(substate_db)
+        .read_typed_object_field::<VersionedConsensusManagerConfiguration>(
+            &CONSENSUS_MANAGER.into_node_id(),
+            ModuleId::Main,
+            ConsensusManagerField::Configuration.field_index(),
+        )
+        .unwrap()
+        .fully_update_and_into_latest_version();
+    config.config.validator_creation_usd_cost = validator_creation_usd_cost;
+    let state_updates = StateUpdates {
+        by_node: indexmap!(
+            CONSENSUS_MANAGER.into_node_id() => NodeStateUpdates::Delta {
+                by_partition: indexmap! {
+                    MAIN_BASE_PARTITION => PartitionStateUpdates::Delta {
+                        by_substate: indexmap! {
+                            SubstateKey::Field(ConsensusManagerField::Configuration.field_index()) => DatabaseUpdate::Set(
+                                scrypto_encode(&config.into_locked_substate()).unwrap()
+                            )
+                        }
+                    },
+                }
+            }
+        ),
+    };
+    let update = CustomProtocolUpdate::flash(
+        "validator-creation-cost",
+        ProtocolVersion::LATEST,
+        vec![("validator-creation-cost".to_string(), state_updates)],
+    );
+
+    let scrypto_vm = ScryptoVm::<DefaultWasmEngine>::default();
+    dry_run_protocol_updates(
+        substate_db,
+        [ProtocolBuilder::for_simulator()
+            .until_latest_protocol_version()
+            .create_executor_for_custom_update(&update)],
+        VmInit::new(&scrypto_vm, NoExtension),
+    )
+    .pop()
+    .unwrap()
+}
+
+fn read_validator_creation_usd_cost<S: SubstateDatabase>(substate_db: &S) -> Decimal {
+    let versioned_config: VersionedConsensusManagerConfiguration =
+        SystemDatabaseReader::new(substate_db)
+            .read_typed_object_field(
+                &CONSENSUS_MANAGER.into_node_id(),
+                ModuleId::Main,
+                ConsensusManagerField::Configuration.field_index(),
+            )
+            .unwrap();
+    versioned_config
+        .fully_update_and_into_latest_version()
+        .config
+        .validator_creation_usd_cost
+}
+
+fn to_validator_creation_usd_cost(value: Option<Result<TypedSubstateValue, String>>) -> Decimal {
+    match value {
+        Some(Ok(TypedSubstateValue::MainModule(
+            TypedMainModuleSubstateValue::ConsensusManager(
+                ConsensusManagerTypedSubstateValue::Field(
+                    ConsensusManagerTypedFieldSubstateValue::Configuration(field),
+                ),
+            ),
+        ))) => {
+            field
+                .into_payload()
+                .fully_update_and_into_latest_version()
+                .config
+                .validator_creation_usd_cost
+        }
+        value => panic!("Unexpected consensus manager configuration {:?}", value),
+    }
+}
+
+#[test]
+fn dry_run_reports_each_protocol_update_in_order() {
+    // Arrange
+    let ledger = LedgerSimulatorBuilder::new()
+        .without_kernel_trace()
+        .with_protocol_version(ProtocolVersion::Babylon)
+        .build();
+
+    // Act
+    let dry_runs = dry_run_until_latest_protocol_version(ledger.substate_db());
+
+    // Assert
+    assert_eq!(
+        dry_runs
+            .iter()
+            .map(|dry_run| dry_run.protocol_update.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            ProtocolUpdateEntry::Builtin(ProtocolUpdate::Anemone),
+            ProtocolUpdateEntry::Builtin(ProtocolUpdate::Bottlenose),
+            ProtocolUpdateEntry::Builtin(ProtocolUpdate::Cuttlefish)
+        ]
+    );
+    for dry_run in &dry_runs {
+        assert!(!dry_run.substate_changes.is_empty());
+        assert!(!dry_run.database_updates.node_updates.is_empty());
+    }
+}
+
+#[test]
+fn dry_run_reports_configuration_change_with_values_before_and_after() {
+    // Arrange
+    let ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+    let cost_before = read_validator_creation_usd_cost(ledger.substate_db());
+
+    // Act
+    let dry_run = dry_run_validator_creation_cost_update(ledger.substate_db(), dec!(1234));
+
+    // Assert
+    assert_eq!(
+        dry_run.protocol_update.logical_name(),
+        "validator-creation-cost"
+    );
+    assert_eq!(dry_run.substate_changes.len(), 1);
+    let change = &dry_run.substate_changes[0];
+    assert_eq!(change.node_id, CONSENSUS_MANAGER.into_node_id());
+    assert_eq!(change.category, SubstateChangeCategory::Configuration);
+    assert_eq!(
+        to_validator_creation_usd_cost(change.typed_previous_value()),
+        cost_before
+    );
+    assert_eq!(
+        to_validator_creation_usd_cost(change.typed_new_value()),
+        dec!(1234)
+    );
+}
+
+#[test]
+fn dry_run_reports_package_code_and_blueprint_definition_changes() {
+    // Arrange
+    let ledger = LedgerSimulatorBuilder::new()
+        .without_kernel_trace()
+        .with_protocol_version(ProtocolVersion::Babylon)
+        .build();
+
+    // Act
+    let dry_runs = dry_run_until_latest_protocol_version(ledger.substate_db());
+
+    // Assert
+    // Anemone replaces the consensus manager code to expose second-precision timestamps.
+    let anemone = &dry_runs[0];
+    assert!(anemone
+        .changes_of_category(SubstateChangeCategory::PackageCode)
+        .any(|change| change.node_id == CONSENSUS_MANAGER_PACKAGE.into_node_id()));
+    assert!(anemone
+        .changes_of_category(SubstateChangeCategory::BlueprintDefinition)
+        .any(|change| change.node_id == CONSENSUS_MANAGER_PACKAGE.into_node_id()));
+    assert!(anemone
+        .affected_entities()
+        .contains(&CONSENSUS_MANAGER_PACKAGE.into_node_id()));
+}
+
+#[test]
+fn dry_run_does_not_commit_to_the_database() {
+    // Arrange
+    let ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+    let cost_before = read_validator_creation_usd_cost(ledger.substate_db());
+
+    // Act
+    dry_run_validator_creation_cost_update(ledger.substate_db(), dec!(1234));
+
+    // Assert
+    assert_eq!(
+        read_validator_creation_usd_cost(ledger.substate_db()),
+        cost_before
+    );
+}
@@ -44,9 +44,13 @@ fn run_flash_test(flash_substates: bool, expect_success: bool) {
         for batch_index in 0..anemone_protocol_update_batch_generator.batch_count() {
             let batch = anemone_protocol_update_batch_generator
                 .generate_batch(ledger.substate_db(), batch_index);
-            for ProtocolUpdateTransactionDetails::FlashV1Transaction(
-                FlashProtocolUpdateTransactionDetails { state_updates, .. },
-            ) in batch.transactions {
+            for transaction in batch.transactions {
+                let ProtocolUpdateTransactionDetails::FlashV1Transaction(
+                    FlashProtocolUpdateTransactionDetails { state_updates, .. },
+                ) = transaction
+                else {
+                    panic!("Only flash transactions are expected");
+                };
                 ledger
                     .substate_db_mut()
                     .commit(&state_updates.create_database_updates::<SpreadPrefixKeyMapper>())
@@ -0,0 +1,339 @@
+use radix_common::prelude::*;
+use radix_engine::errors::*;
+use radix_engine::system::system_modules::auth::*;
+use radix_engine::system::system_type_checker::TypeCheckError;
+use radix_engine::transaction::*;
+use radix_engine::updates::*;
+use radix_engine_tests::common::*;
+use scrypto_test::prelude::*;
+
+fn withdraw_from_account(
+    ledger: &mut DefaultLedgerSimulator,
+    account: ComponentAddress,
+    public_key: Secp256k1PublicKey,
+) -> TransactionReceipt {
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, XRD, dec!(1))
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+    ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    )
+}
+
+fn expect_unauthorized(receipt: &TransactionReceipt) -> Unauthorized {
+    match receipt.expect_commit_failure().outcome.expect_failure() {
+        RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(
+            unauthorized,
+        ))) => unauthorized.as_ref().clone(),
+        error => panic!("Expected an unauthorized error but got: {:?}", error),
+    }
+}
+
+#[test]
+fn role_with_epoch_condition_is_only_satisfied_from_the_epoch() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+    let (public_key, _) = ledger.new_key_pair();
+    let signature = NonFungibleGlobalId::from_public_key(&public_key);
+    let account = ledger.new_account_advanced(OwnerRole::Fixed(rule!(
+        require(signature) && epoch_at_least(Epoch::of(1000))
+    )));
+
+    // Act
+    let receipt_before = withdraw_from_account(&mut ledger, account, public_key);
+    ledger.set_current_epoch(Epoch::of(1000));
+    let receipt_after = withdraw_from_account(&mut ledger, account, public_key);
+
+    // Assert
+    let diagnostics = expect_unauthorized(&receipt_before).diagnostics.unwrap();
+    assert_eq!(diagnostics.failed_access_rules.len(), 1);
+    assert!(diagnostics.failed_access_rules[0]
+        .failed_proof_rules
+        .is_empty());
+    assert_eq!(
+        diagnostics.failed_access_rules[0].failed_time_conditions,
+        vec![TimeCondition::EpochAtLeast(Epoch::of(1000))]
+    );
+    receipt_after.expect_commit_success();
+}
+
+#[test]
+fn role_with_time_condition_expires_at_the_time() {
+    // Arrange
+    let genesis = CustomGenesis::default(
+        Epoch::of(1),
+        CustomGenesis::default_consensus_manager_config(),
+    );
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .with_custom_genesis(genesis)
+        .without_kernel_trace()
+        .build();
+    let (public_key, _) = ledger.new_key_pair();
+    let signature = NonFungibleGlobalId::from_public_key(&public_key);
+    let expiry = Instant::new(1_700_000_040);
+    let account = ledger.new_account_advanced(OwnerRole::Fixed(rule!(
+        require(signature) && time_before(expiry)
+    )));
+
+    let expiry_ms = expiry.seconds_since_unix_epoch * 1000;
+
+    // Act
+    ledger
+        .advance_to_round_at_timestamp(Round::of(1), expiry_ms - 60_000)
+        .expect_commit_success();
+    let receipt_before = withdraw_from_account(&mut ledger, account, public_key);
+    ledger
+        .advance_to_round_at_timestamp(Round::of(2), expiry_ms)
+        .expect_commit_success();
+    let receipt_after = withdraw_from_account(&mut ledger, account, public_key);
+
+    // Assert
+    receipt_before.expect_commit_success();
+    let diagnostics = expect_unauthorized(&receipt_after).diagnostics.unwrap();
+    assert_eq!(
+        diagnostics.failed_access_rules[0].failed_time_conditions,
+        vec![TimeCondition::TimeBefore(expiry)]
+    );
+}
+
+#[test]
+fn any_of_time_conditions_is_satisfied_by_either_branch() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+    let (public_key, _) = ledger.new_key_pair();
+    let signature = NonFungibleGlobalId::from_public_key(&public_key);
+    let account = ledger.new_account_advanced(OwnerRole::Fixed(rule!(
+        require(signature) && (epoch_before(Epoch::of(10)) || epoch_at_least(Epoch::of(1000)))
+    )));
+
+    // Act
+    ledger.set_current_epoch(Epoch::of(500));
+    let receipt_between = withdraw_from_account(&mut ledger, account, public_key);
+    ledger.set_current_epoch(Epoch::of(1000));
+    let receipt_after = withdraw_from_account(&mut ledger, account, public_key);
+
+    // Assert
+    let diagnostics = expect_unauthorized(&receipt_between).diagnostics.unwrap();
+    assert_eq!(
+        diagnostics.failed_access_rules[0].failed_time_conditions,
+        vec![
+            TimeCondition::EpochBefore(Epoch::of(10)),
+            TimeCondition::EpochAtLeast(Epoch::of(1000))
+        ]
+    );
+    receipt_after.expect_commit_success();
+}
+
+#[test]
+fn asserting_access_rule_with_time_condition_evaluates_it() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("role_assignment"));
+    let component_address = {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_function(package_address, "AssertAccessRule", "new", manifest_args!())
+            .build();
+        let receipt = ledger.execute_manifest(manifest, []);
+        receipt.expect_commit(true).new_component_addresses()[0]
+    };
+    ledger.set_current_epoch(Epoch::of(100));
+
+    // Act
+    let mut assert_rule = |access_rule: AccessRule| {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(
+                component_address,
+                "assert_access_rule",
+                manifest_args!(access_rule),
+            )
+            .build();
+        ledger.execute_manifest(manifest, [])
+    };
+    let satisfied_receipt = assert_rule(rule!(epoch_at_least(Epoch::of(100))));
+    let unsatisfied_receipt = assert_rule(rule!(epoch_before(Epoch::of(100))));
+
+    // Assert
+    satisfied_receipt.expect_commit_success();
+    unsatisfied_receipt.expect_specific_failure(|error: &RuntimeError| {
+        matches!(
+            error,
+            RuntimeError::SystemError(SystemError::AssertAccessRuleFailed)
+        )
+    });
+}
+
+fn expect_time_conditions_rejected(receipt: &TransactionReceipt) {
+    receipt.expect_specific_failure(|error: &RuntimeError| match error {
+        RuntimeError::SystemError(SystemError::TypeCheckError(
+            TypeCheckError::BlueprintPayloadValidationError(.., error)
+            | TypeCheckError::KeyValueStorePayloadValidationError(.., error),
+        )) => error.contains("Access rule time conditions are not supported before Cuttlefish"),
+        _ => false,
+    });
+}
+
+#[test]
+fn time_conditions_cannot_be_stored_in_role_assignments_before_cuttlefish() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .without_kernel_trace()
+        .with_protocol_version(ProtocolVersion::Bottlenose)
+        .build();
+    let (public_key, _) = ledger.new_key_pair();
+    let signature = NonFungibleGlobalId::from_public_key(&public_key);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .new_account_advanced(
+            OwnerRole::Fixed(rule!(require(signature) && epoch_at_least(Epoch::of(1)))),
+            None,
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    expect_time_conditions_rejected(&receipt);
+}
+
+#[test]
+fn owner_role_cannot_be_set_to_time_condition_before_cuttlefish() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .without_kernel_trace()
+        .with_protocol_version(ProtocolVersion::Bottlenose)
+        .build();
+    let (public_key, _) = ledger.new_key_pair();
+    let signature = NonFungibleGlobalId::from_public_key(&public_key);
+    let account =
+        ledger.new_account_advanced(OwnerRole::Updatable(rule!(require(signature.clone()))));
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .set_owner_role(
+            account,
+            rule!(require(signature.clone()) && epoch_at_least(Epoch::of(1))),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![signature]);
+
+    // Assert
+    expect_time_conditions_rejected(&receipt);
+}
+
+#[test]
+fn time_conditions_cannot_be_stored_in_access_controller_before_cuttlefish() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .without_kernel_trace()
+        .with_protocol_version(ProtocolVersion::Bottlenose)
+        .build();
+    let (public_key, _, account) = ledger.new_account(false);
+    let badge = ledger.create_fungible_resource(1.into(), 0, account);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_standard_test_fee(account)
+        .withdraw_from_account(account, badge, 1)
+        .take_all_from_worktop(badge, "controlled_asset")
+        .create_access_controller(
+            "controlled_asset",
+            rule!(require(badge) && epoch_at_least(Epoch::of(1))),
+            rule!(require(badge)),
+            rule!(require(badge)),
+            None,
+        )
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        [NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+
+    // Assert
+    expect_time_conditions_rejected(&receipt);
+}
+
+#[test]
+fn time_conditions_cannot_be_stored_in_component_state_before_cuttlefish() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .without_kernel_trace()
+        .with_protocol_version(ProtocolVersion::Bottlenose)
+        .build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("role_assignment"));
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "StoredAccessRule",
+            "new_with_epoch_condition",
+            manifest_args!(Epoch::of(1)),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, []);
+
+    // Assert
+    expect_time_conditions_rejected(&receipt);
+}
+
+#[test]
+fn time_conditions_cannot_be_stored_in_key_value_store_before_cuttlefish() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .without_kernel_trace()
+        .with_protocol_version(ProtocolVersion::Bottlenose)
+        .build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("role_assignment"));
+    let component_address = {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_function(package_address, "StoredAccessRule", "new", manifest_args!())
+            .build();
+        let receipt = ledger.execute_manifest(manifest, []);
+        receipt.expect_commit(true).new_component_addresses()[0]
+    };
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            component_address,
+            "store_epoch_condition",
+            manifest_args!(Epoch::of(1)),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, []);
+
+    // Assert
+    expect_time_conditions_rejected(&receipt);
+}
+
+#[test]
+fn time_conditions_can_be_stored_in_component_state_after_cuttlefish() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("role_assignment"));
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "StoredAccessRule",
+            "new_with_epoch_condition",
+            manifest_args!(Epoch::of(1)),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, []);
+
+    // Assert
+    receipt.expect_commit_success();
+}
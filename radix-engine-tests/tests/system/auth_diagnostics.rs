@@ -0,0 +1,332 @@
+use radix_common::prelude::*;
+use radix_engine::errors::*;
+use radix_engine::kernel::kernel_api::{KernelNodeApi, KernelSubstateApi};
+use radix_engine::system::system_callback::SystemLockData;
+use radix_engine::system::system_modules::auth::*;
+use radix_engine::transaction::*;
+use radix_engine::vm::{OverridePackageCode, VmApi, VmInvoke};
+use radix_engine_interface::api::ClientApi;
+use radix_substate_store_queries::typed_substate_layout::{ConsensusManagerField, FunctionAuth};
+use scrypto_test::prelude::*;
+
+fn expect_unauthorized(receipt: &TransactionReceipt) -> Unauthorized {
+    match receipt.expect_commit_failure().outcome.expect_failure() {
+        RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(
+            unauthorized,
+        ))) => unauthorized.as_ref().clone(),
+        error => panic!("Expected an unauthorized error but got: {:?}", error),
+    }
+}
+
+#[test]
+fn withdraw_without_signature_explains_owner_role_and_missing_signature() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+    let (public_key, _, account) = ledger.new_virtual_account();
+    let (other_public_key, _, _) = ledger.new_virtual_account();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, XRD, dec!(1))
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+
+    // Act
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&other_public_key)],
+    );
+
+    // Assert
+    let diagnostics = expect_unauthorized(&receipt).diagnostics.unwrap();
+    let signature = NonFungibleGlobalId::from_public_key(&public_key);
+    assert_eq!(
+        diagnostics.failed_access_rules,
+        vec![AccessRuleDiagnostics {
+            role_key: Some(RoleKey::new(OWNER_ROLE)),
+            source: AccessRuleSource::OwnerRole(account.into()),
+            access_rule: rule!(require(signature.clone())),
+            failed_proof_rules: vec![FailedProofRule {
+                proof_rule: ProofRule::Require(signature.clone().into()),
+                unmet_requirements: vec![UnmetRequirement::MissingProof(signature.into())],
+            }],
+            failed_time_conditions: vec![],
+        }]
+    );
+    assert!(diagnostics.auth_zones.iter().any(|auth_zone| {
+        matches!(auth_zone.origin, AuthZoneOrigin::GlobalCaller(..))
+            && auth_zone
+                .virtual_non_fungibles
+                .contains(&NonFungibleGlobalId::from_public_key(&other_public_key))
+    }));
+}
+
+#[test]
+fn set_owner_role_of_virtual_account_explains_owner_role_updater() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+    let (public_key, _, account) = ledger.new_virtual_account();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .set_owner_role(account, rule!(allow_all))
+        .build();
+
+    // Act
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+
+    // Assert
+    let diagnostics = expect_unauthorized(&receipt).diagnostics.unwrap();
+    let global_caller_badge = NonFungibleGlobalId::global_caller_badge(account);
+    assert_eq!(
+        diagnostics.failed_access_rules,
+        vec![AccessRuleDiagnostics {
+            role_key: None,
+            source: AccessRuleSource::OwnerRoleUpdater(account.into(), OwnerRoleUpdater::Object),
+            access_rule: rule!(require(global_caller_badge.clone())),
+            failed_proof_rules: vec![FailedProofRule {
+                proof_rule: ProofRule::Require(global_caller_badge.clone().into()),
+                unmet_requirements: vec![UnmetRequirement::MissingProof(
+                    global_caller_badge.into()
+                )],
+            }],
+            failed_time_conditions: vec![],
+        }]
+    );
+}
+
+#[test]
+fn insufficient_proof_amount_explains_largest_proof_in_auth_zone() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+    let (public_key, _, badge_holder) = ledger.new_allocated_account();
+    let badge = ledger.create_fungible_resource(dec!(100), 18, badge_holder);
+    let account =
+        ledger.new_account_advanced(OwnerRole::Fixed(rule!(require_amount(dec!(10), badge))));
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_amount(badge_holder, badge, dec!(5))
+        .withdraw_from_account(account, XRD, dec!(1))
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+
+    // Act
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+
+    // Assert
+    let diagnostics = expect_unauthorized(&receipt).diagnostics.unwrap();
+    assert_eq!(diagnostics.failed_access_rules.len(), 1);
+    assert_eq!(
+        diagnostics.failed_access_rules[0].failed_proof_rules,
+        vec![FailedProofRule {
+            proof_rule: ProofRule::AmountOf(dec!(10), badge),
+            unmet_requirements: vec![UnmetRequirement::InsufficientAmount {
+                resource_address: badge,
+                required_amount: dec!(10),
+                largest_proof_amount: dec!(5),
+            }],
+        }]
+    );
+    assert!(diagnostics
+        .auth_zones
+        .iter()
+        .any(|auth_zone| auth_zone.proofs.contains(&ProofDiagnostics {
+            resource_address: badge,
+            amount: dec!(5),
+            non_fungible_local_ids: None,
+        })));
+}
+
+#[test]
+fn role_assignment_diagnostics_are_rendered_in_receipt() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+    let (_, _, account) = ledger.new_virtual_account();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, XRD, dec!(1))
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+
+    // Act
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let encoder = AddressBech32Encoder::for_simulator();
+    let display = receipt
+        .display(
+            TransactionReceiptDisplayContextBuilder::new()
+                .encoder(&encoder)
+                .use_ansi_colors(false)
+                .build(),
+        )
+        .to_string();
+    assert!(display.contains("AUTHORIZATION FAILURE:"));
+    assert!(display.contains(&format!(
+        "not assigned, using the owner role of {}",
+        account.display(&encoder)
+    )));
+    assert!(display.contains("CHECKED AUTH ZONES:"));
+}
+
+#[test]
+fn diagnostics_are_not_collected_for_notarized_transactions() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+    let (public_key, _, account) = ledger.new_virtual_account();
+    let notary = Secp256k1PrivateKey::from_u64(1).unwrap();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, XRD, dec!(1))
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+
+    // Act
+    let receipt = ledger
+        .execute_notarized_manifest(manifest, &[&notary], &notary)
+        .unwrap();
+
+    // Assert
+    let unauthorized = expect_unauthorized(&receipt);
+    assert_eq!(unauthorized.diagnostics, None);
+    assert_eq!(
+        unauthorized.failed_access_rules,
+        FailedAccessRules::RoleList(vec![(
+            RoleKey::new(OWNER_ROLE),
+            vec![rule!(require(NonFungibleGlobalId::from_public_key(
+                &public_key
+            )))]
+        )])
+    );
+}
+
+#[test]
+fn diagnostics_do_not_change_the_fee_of_a_failed_transaction() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+    let (_, _, account) = ledger.new_virtual_account();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, XRD, dec!(1))
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+    let mut execute = |enable_auth_diagnostics: bool| {
+        let nonce = ledger.next_transaction_nonce();
+        ledger.execute_transaction(
+            TestTransaction::new_from_nonce(manifest.clone(), nonce)
+                .prepare()
+                .unwrap()
+                .get_executable(btreeset!()),
+            ExecutionConfig::for_test_transaction().with_auth_diagnostics(enable_auth_diagnostics),
+        )
+    };
+
+    // Act
+    let receipt_with_diagnostics = execute(true);
+    let receipt_without_diagnostics = execute(false);
+
+    // Assert
+    assert!(expect_unauthorized(&receipt_with_diagnostics)
+        .diagnostics
+        .is_some());
+    assert!(expect_unauthorized(&receipt_without_diagnostics)
+        .diagnostics
+        .is_none());
+    assert_eq!(
+        receipt_with_diagnostics.fee_summary,
+        receipt_without_diagnostics.fee_summary
+    );
+}
+
+#[test]
+fn failing_to_collect_diagnostics_still_reports_the_unauthorized_error() {
+    // Arrange
+    const BLUEPRINT_NAME: &str = "MyBlueprint";
+    const CUSTOM_PACKAGE_CODE_ID: u64 = 1024;
+    #[derive(Clone)]
+    struct TestInvoke;
+    impl VmInvoke for TestInvoke {
+        fn invoke<Y, V>(
+            &mut self,
+            export_name: &str,
+            _input: &IndexedScryptoValue,
+            api: &mut Y,
+            _vm_api: &V,
+        ) -> Result<IndexedScryptoValue, RuntimeError>
+        where
+            Y: ClientApi<RuntimeError> + KernelNodeApi + KernelSubstateApi<SystemLockData>,
+            V: VmApi,
+        {
+            match export_name {
+                "call_protected" => {
+                    // Holding a write lock on the consensus manager state makes the diagnostics of
+                    // the time condition, which the failed check itself never reaches, fail to read it
+                    let handle = api.kernel_open_substate(
+                        CONSENSUS_MANAGER.as_node_id(),
+                        MAIN_BASE_PARTITION,
+                        &ConsensusManagerField::State.into(),
+                        LockFlags::MUTABLE,
+                        SystemLockData::default(),
+                    )?;
+                    let blueprint_id = api.actor_get_blueprint_id()?;
+                    api.call_function(
+                        blueprint_id.package_address,
+                        blueprint_id.blueprint_name.as_str(),
+                        "protected",
+                        scrypto_encode(&()).unwrap(),
+                    )?;
+                    api.kernel_close_substate(handle)?;
+                }
+                _ => {}
+            }
+
+            Ok(IndexedScryptoValue::from_typed(&()))
+        }
+    }
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .with_custom_extension(OverridePackageCode::new(CUSTOM_PACKAGE_CODE_ID, TestInvoke))
+        .without_kernel_trace()
+        .build();
+    let badge = NonFungibleGlobalId::from_public_key(&ledger.new_key_pair().0);
+    let protected_rule = rule!(require(badge) && epoch_at_least(Epoch::of(0)));
+    let mut definition = PackageDefinition::new_functions_only_test_definition(
+        BLUEPRINT_NAME,
+        vec![
+            ("call_protected", "call_protected", false),
+            ("protected", "protected", false),
+        ],
+    );
+    definition.blueprints.values_mut().for_each(|bp_def| {
+        bp_def.auth_config.function_auth = FunctionAuth::AccessRules(indexmap!(
+            "call_protected".to_string() => rule!(allow_all),
+            "protected".to_string() => protected_rule.clone(),
+        ));
+    });
+    let package_address = ledger.publish_native_package(CUSTOM_PACKAGE_CODE_ID, definition);
+
+    // Act
+    let receipt = ledger.execute_manifest(
+        ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_function(
+                package_address,
+                BLUEPRINT_NAME,
+                "call_protected",
+                manifest_args!(),
+            )
+            .build(),
+        vec![],
+    );
+
+    // Assert
+    let unauthorized = expect_unauthorized(&receipt);
+    assert_eq!(unauthorized.diagnostics, None);
+    assert!(matches!(
+        unauthorized.failed_access_rules,
+        FailedAccessRules::AccessRule(..)
+    ));
+}
@@ -0,0 +1,252 @@
+use radix_common::prelude::*;
+use radix_substate_store_queries::query::*;
+use scrypto_test::prelude::*;
+
+#[test]
+fn vault_balance_changes_are_resolved_to_owning_accounts() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, sender) = ledger.new_account(false);
+    let (_, _, recipient) = ledger.new_account(false);
+    let mut change_feed = ChangeFeed::for_database(ledger.substate_db());
+    let pre_state = ledger.substate_db().clone();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(sender, XRD, dec!(10))
+        .try_deposit_entire_worktop_or_abort(recipient, None)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    let changes = change_feed.changes_of_commit(&pre_state, receipt.expect_commit_success());
+
+    // Assert
+    let balance_change_of = |account: ComponentAddress| {
+        changes
+            .iter()
+            .find_map(|record| match &record.change {
+                TypedChange::FungibleVaultBalanceChanged {
+                    resource_address,
+                    previous_balance,
+                    new_balance,
+                } if record.owner == Some(account.into()) && *resource_address == XRD => {
+                    Some(new_balance.checked_sub(*previous_balance).unwrap())
+                }
+                _ => None,
+            })
+            .expect("Account vault should have changed")
+    };
+    assert_eq!(balance_change_of(sender), dec!(-10));
+    assert_eq!(balance_change_of(recipient), dec!(10));
+}
+
+#[test]
+fn metadata_changes_are_reported_with_previous_values() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, account) = ledger.new_account(false);
+    let mut change_feed = ChangeFeed::for_database(ledger.substate_db());
+    let pre_state = ledger.substate_db().clone();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .set_metadata(account, "name", "My Account")
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    let changes = change_feed.changes_of_commit(&pre_state, receipt.expect_commit_success());
+
+    // Assert
+    let metadata_changes = changes
+        .into_iter()
+        .filter(|record| matches!(record.change, TypedChange::MetadataSet { .. }))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        metadata_changes,
+        vec![ChangeRecord {
+            node_id: account.into_node_id(),
+            owner: Some(account.into()),
+            change: TypedChange::MetadataSet {
+                key: "name".to_string(),
+                previous_value: None,
+                new_value: MetadataValue::String("My Account".to_string()),
+            },
+        }]
+    );
+}
+
+#[test]
+fn minted_non_fungibles_are_reported_in_newly_created_vaults() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (_, _, account) = ledger.new_account(false);
+    let mut change_feed = ChangeFeed::for_database(ledger.substate_db());
+    let pre_state = ledger.substate_db().clone();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_non_fungible_resource(
+            OwnerRole::None,
+            NonFungibleIdType::Integer,
+            false,
+            NonFungibleResourceRoles::default(),
+            metadata!(),
+            Some([(NonFungibleLocalId::integer(1), EmptyNonFungibleData {})]),
+        )
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+    let commit_result = receipt.expect_commit_success();
+    let resource_address = commit_result.new_resource_addresses()[0];
+    let changes = change_feed.changes_of_commit(&pre_state, commit_result);
+
+    // Assert
+    assert!(changes
+        .iter()
+        .any(|record| record.node_id == resource_address.into_node_id()
+            && record.change
+                == TypedChange::EntityCreated {
+                    entity_type: EntityType::GlobalNonFungibleResourceManager
+                }));
+    assert!(changes
+        .iter()
+        .any(|record| record.node_id == resource_address.into_node_id()
+            && matches!(
+                &record.change,
+                TypedChange::NonFungibleDataChanged {
+                    local_id,
+                    previous_data: None,
+                    new_data: Some(_),
+                } if *local_id == NonFungibleLocalId::integer(1)
+            )));
+    assert!(changes
+        .iter()
+        .any(|record| record.owner == Some(account.into())
+            && record.change
+                == TypedChange::NonFungibleDeposited {
+                    resource_address,
+                    local_id: NonFungibleLocalId::integer(1),
+                }));
+}
+
+#[test]
+fn resource_supply_and_role_changes_are_reported_on_the_resource_manager() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (_, _, account) = ledger.new_account(false);
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_fungible_resource(
+            OwnerRole::None,
+            true,
+            18,
+            FungibleResourceRoles {
+                mint_roles: mint_roles! {
+                    minter => rule!(allow_all);
+                    minter_updater => rule!(allow_all);
+                },
+                ..Default::default()
+            },
+            metadata!(),
+            Some(dec!(100)),
+        )
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+    let resource_address = ledger
+        .execute_manifest(manifest, vec![])
+        .expect_commit_success()
+        .new_resource_addresses()[0];
+    let mut change_feed = ChangeFeed::for_database(ledger.substate_db());
+    let pre_state = ledger.substate_db().clone();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .mint_fungible(resource_address, dec!(5))
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .set_role(resource_address, ModuleId::Main, "minter", rule!(deny_all))
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+    let changes = change_feed.changes_of_commit(&pre_state, receipt.expect_commit_success());
+
+    // Assert
+    let resource_manager_changes = changes
+        .into_iter()
+        .filter(|record| record.node_id == resource_address.into_node_id())
+        .map(|record| record.change)
+        .collect::<Vec<_>>();
+    assert!(
+        resource_manager_changes.contains(&TypedChange::ResourceTotalSupplyChanged {
+            previous_total_supply: dec!(100),
+            new_total_supply: dec!(105),
+        })
+    );
+    assert!(
+        resource_manager_changes.contains(&TypedChange::RoleChanged {
+            role_key: ModuleRoleKey::new(ModuleId::Main, "minter"),
+            previous_rule: Some(rule!(allow_all)),
+            new_rule: Some(rule!(deny_all)),
+        })
+    );
+}
+
+#[test]
+fn account_deposit_rule_and_preference_changes_are_reported() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, account) = ledger.new_account(false);
+    let mut change_feed = ChangeFeed::for_database(ledger.substate_db());
+    let pre_state = ledger.substate_db().clone();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            account,
+            ACCOUNT_SET_DEFAULT_DEPOSIT_RULE_IDENT,
+            AccountSetDefaultDepositRuleInput {
+                default: DefaultDepositRule::Reject,
+            },
+        )
+        .call_method(
+            account,
+            ACCOUNT_SET_RESOURCE_PREFERENCE_IDENT,
+            AccountSetResourcePreferenceInput {
+                resource_address: XRD,
+                resource_preference: ResourcePreference::Allowed,
+            },
+        )
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    let changes = change_feed.changes_of_commit(&pre_state, receipt.expect_commit_success());
+
+    // Assert
+    let account_changes = changes
+        .into_iter()
+        .filter(|record| record.node_id == account.into_node_id())
+        .map(|record| record.change)
+        .collect::<Vec<_>>();
+    assert!(
+        account_changes.contains(&TypedChange::AccountDepositRuleChanged {
+            previous_rule: Some(DefaultDepositRule::Accept),
+            new_rule: DefaultDepositRule::Reject,
+        })
+    );
+    assert!(
+        account_changes.contains(&TypedChange::AccountResourcePreferenceChanged {
+            resource_address: XRD,
+            previous_preference: None,
+            new_preference: Some(ResourcePreference::Allowed),
+        })
+    );
+}
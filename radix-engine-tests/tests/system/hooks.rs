@@ -0,0 +1,198 @@
+use radix_blueprint_schema_init::BlueprintHook;
+use radix_common::prelude::*;
+use radix_engine::blueprints::package::PackageError;
+use radix_engine::errors::{ApplicationError, KernelError, RuntimeError, SystemError};
+use radix_engine::system::system_type_checker::TypeCheckError;
+use radix_engine::updates::*;
+use radix_engine::vm::wasm::PrepareError;
+use radix_engine_tests::common::*;
+use scrypto_test::prelude::*;
+
+fn call_borrower(function_name: &str, args: ManifestArgs) -> TransactionReceipt {
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("hooks"));
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(package_address, "Borrower", function_name, args)
+        .build();
+    ledger.execute_manifest(manifest, vec![])
+}
+
+fn is_panic_containing(e: &RuntimeError, message: &str) -> bool {
+    match e {
+        RuntimeError::ApplicationError(ApplicationError::PanicMessage(e)) => e.contains(message),
+        _ => false,
+    }
+}
+
+#[test]
+fn repaid_loan_ticket_can_be_dropped() {
+    // Act
+    let receipt = call_borrower("borrow_and_repay", manifest_args!(dec!(10), dec!(10)));
+
+    // Assert
+    receipt.expect_commit_success();
+}
+
+#[test]
+fn on_drop_hook_prevents_dropping_unpaid_loan_ticket() {
+    // Act
+    let receipt = call_borrower("borrow_and_repay", manifest_args!(dec!(10), dec!(5)));
+
+    // Assert
+    receipt.expect_specific_failure(|e| is_panic_containing(e, "Loan has not been repaid"));
+}
+
+#[test]
+fn loan_ticket_cannot_be_discarded() {
+    // Act
+    let receipt = call_borrower("borrow_and_discard", manifest_args!(dec!(10)));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(e, RuntimeError::KernelError(KernelError::OrphanedNodes(..)))
+    });
+}
+
+#[test]
+fn on_move_hook_prevents_passing_loan_ticket_to_other_blueprint() {
+    // Act
+    let receipt = call_borrower("borrow_and_hand_over", manifest_args!(dec!(10)));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        is_panic_containing(
+            e,
+            "Loan tickets can only be passed to the LoanTicket blueprint",
+        )
+    });
+}
+
+#[test]
+fn hook_functions_cannot_be_called_directly() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let package_address = ledger.publish_package_simple(PackageLoader::get("hooks"));
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(
+            package_address,
+            "LoanTicket",
+            "ensure_repaid",
+            manifest_args!(),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::SystemError(SystemError::TypeCheckError(
+                TypeCheckError::BlueprintPayloadDoesNotExist(..)
+            ))
+        )
+    });
+}
+
+#[test]
+fn publishing_of_package_with_on_virtualize_hook_fails() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (code, mut definition) = PackageLoader::get("hooks");
+    let blueprint = definition.blueprints.get_mut("LoanTicket").unwrap();
+    let on_drop_export = blueprint
+        .schema
+        .hooks
+        .hooks
+        .get(&BlueprintHook::OnDrop)
+        .unwrap()
+        .clone();
+    blueprint
+        .schema
+        .hooks
+        .hooks
+        .insert(BlueprintHook::OnVirtualize, on_drop_export);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .publish_package_advanced(
+            None,
+            code,
+            definition,
+            MetadataInit::default(),
+            OwnerRole::None,
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::WasmUnsupported(..)
+            ))
+        )
+    });
+}
+
+#[test]
+fn object_drop_host_function_is_rejected_before_cuttlefish() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .with_protocol_version(ProtocolVersion::Bottlenose)
+        .build();
+
+    // Act
+    let receipt = ledger.try_publish_package(PackageLoader::get("hooks"));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::InvalidWasm(PrepareError::InvalidImport(
+                    InvalidImport::ProtocolVersionMismatch { .. }
+                ))
+            ))
+        )
+    });
+}
+
+#[test]
+fn publishing_of_package_with_hooks_fails_before_cuttlefish() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .with_protocol_version(ProtocolVersion::Bottlenose)
+        .build();
+    let (code, mut definition) = PackageLoader::get("kv_store");
+    for blueprint in definition.blueprints.values_mut() {
+        let Some(function) = blueprint.schema.functions.functions.values().next() else {
+            continue;
+        };
+        let export = function.export.clone();
+        blueprint
+            .schema
+            .hooks
+            .hooks
+            .insert(BlueprintHook::OnDrop, export);
+    }
+
+    // Act
+    let receipt = ledger.try_publish_package((code, definition));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::WasmUnsupported(..)
+            ))
+        )
+    });
+}
@@ -0,0 +1,274 @@
+use radix_blueprint_schema_init::{BlueprintCollectionSchema, BlueprintKeyValueSchema};
+use radix_common::prelude::*;
+use radix_engine::updates::*;
+use radix_engine::vm::wasm::PrepareError;
+use radix_engine_tests::common::*;
+use scrypto_test::prelude::*;
+
+fn create_index_component(ledger: &mut DefaultLedgerSimulator) -> ComponentAddress {
+    let package_address = ledger.publish_package_simple(PackageLoader::get("index"));
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(package_address, "IndexTest", "new", manifest_args!())
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success().new_component_addresses()[0]
+}
+
+#[test]
+fn can_insert_into_and_scan_index() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let component_address = create_index_component(&mut ledger);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            component_address,
+            "insert_balance",
+            manifest_args!("alice".to_string(), dec!(10)),
+        )
+        .call_method(
+            component_address,
+            "insert_balance",
+            manifest_args!("bob".to_string(), dec!(20)),
+        )
+        .call_method(
+            component_address,
+            "scan_balance_keys",
+            manifest_args!(10u32),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let keys: Vec<String> = receipt.expect_commit_success().output(3);
+    assert_eq!(
+        keys.into_iter().collect::<BTreeSet<_>>(),
+        btreeset!("alice".to_string(), "bob".to_string())
+    );
+    let entries = ledger
+        .get_index_entries::<String, Decimal>(component_address, 0u8)
+        .into_iter()
+        .collect::<BTreeMap<_, _>>();
+    assert_eq!(
+        entries,
+        btreemap!("alice".to_string() => dec!(10), "bob".to_string() => dec!(20))
+    );
+}
+
+#[test]
+fn can_remove_from_index() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let component_address = create_index_component(&mut ledger);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            component_address,
+            "insert_balance",
+            manifest_args!("alice".to_string(), dec!(10)),
+        )
+        .call_method(
+            component_address,
+            "remove_balance",
+            manifest_args!("alice".to_string()),
+        )
+        .call_method(
+            component_address,
+            "remove_balance",
+            manifest_args!("alice".to_string()),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let commit = receipt.expect_commit_success();
+    assert_eq!(commit.output::<Option<Decimal>>(2), Some(dec!(10)));
+    assert_eq!(commit.output::<Option<Decimal>>(3), None);
+    assert!(ledger
+        .get_index_entries::<String, Decimal>(component_address, 0u8)
+        .is_empty());
+}
+
+#[test]
+fn can_drain_index() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let component_address = create_index_component(&mut ledger);
+    let mut builder = ManifestBuilder::new().lock_fee_from_faucet();
+    for i in 0..5 {
+        builder = builder.call_method(
+            component_address,
+            "insert_balance",
+            manifest_args!(i.to_string(), Decimal::from(i)),
+        );
+    }
+    ledger
+        .execute_manifest(builder.build(), vec![])
+        .expect_commit_success();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(component_address, "drain_balances", manifest_args!(3u32))
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let drained: Vec<(String, Decimal)> = receipt.expect_commit_success().output(1);
+    assert_eq!(drained.len(), 3);
+    let remaining = ledger.get_index_entries::<String, Decimal>(component_address, 0u8);
+    assert_eq!(remaining.len(), 2);
+    for (key, _) in drained {
+        assert!(!remaining
+            .iter()
+            .any(|(remaining_key, _)| remaining_key.eq(&key)));
+    }
+}
+
+#[test]
+fn sorted_index_is_scanned_in_sort_key_order() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let component_address = create_index_component(&mut ledger);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            component_address,
+            "insert_order",
+            manifest_args!(300u16, 1u64, "c".to_string()),
+        )
+        .call_method(
+            component_address,
+            "insert_order",
+            manifest_args!(2u16, 2u64, "a".to_string()),
+        )
+        .call_method(
+            component_address,
+            "insert_order",
+            manifest_args!(20u16, 3u64, "b".to_string()),
+        )
+        .call_method(component_address, "scan_orders", manifest_args!(2u32))
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let orders: Vec<(u16, u64, String)> = receipt.expect_commit_success().output(4);
+    assert_eq!(
+        orders,
+        vec![
+            (2u16, 2u64, "a".to_string()),
+            (20u16, 3u64, "b".to_string())
+        ]
+    );
+    assert_eq!(
+        ledger.get_sorted_index_entries::<u64, String>(component_address, 1u8),
+        vec![
+            (2u16, 2u64, "a".to_string()),
+            (20u16, 3u64, "b".to_string()),
+            (300u16, 1u64, "c".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn can_remove_from_sorted_index() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let component_address = create_index_component(&mut ledger);
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(
+            component_address,
+            "insert_order",
+            manifest_args!(5u16, 1u64, "a".to_string()),
+        )
+        .call_method(
+            component_address,
+            "remove_order",
+            manifest_args!(6u16, 1u64),
+        )
+        .call_method(
+            component_address,
+            "remove_order",
+            manifest_args!(5u16, 1u64),
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+
+    // Assert
+    let commit = receipt.expect_commit_success();
+    assert_eq!(commit.output::<Option<String>>(2), None);
+    assert_eq!(commit.output::<Option<String>>(3), Some("a".to_string()));
+    assert!(ledger
+        .get_sorted_index_entries::<u64, String>(component_address, 1u8)
+        .is_empty());
+}
+
+#[test]
+fn index_host_functions_are_rejected_before_cuttlefish() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .with_protocol_version(ProtocolVersion::Bottlenose)
+        .build();
+    let (code, definition) = PackageLoader::get("index");
+
+    // Act
+    let receipt = ledger.try_publish_package((code, definition));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::InvalidWasm(PrepareError::InvalidImport(
+                    InvalidImport::ProtocolVersionMismatch { .. }
+                ))
+            ))
+        )
+    });
+}
+
+#[test]
+fn publishing_of_package_with_collections_fails_before_cuttlefish() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new()
+        .with_protocol_version(ProtocolVersion::Bottlenose)
+        .build();
+    let (code, mut definition) = PackageLoader::get("kv_store");
+    for blueprint in definition.blueprints.values_mut() {
+        let state = &mut blueprint.schema.state;
+        let Some(field) = state.fields.first() else {
+            continue;
+        };
+        let type_ref = field.field.clone();
+        state
+            .collections
+            .push(BlueprintCollectionSchema::Index(BlueprintKeyValueSchema {
+                key: type_ref.clone(),
+                value: type_ref,
+                allow_ownership: false,
+            }));
+    }
+
+    // Act
+    let receipt = ledger.try_publish_package((code, definition));
+
+    // Assert
+    receipt.expect_specific_failure(|e| {
+        matches!(
+            e,
+            RuntimeError::ApplicationError(ApplicationError::PackageError(
+                PackageError::WasmUnsupported(..)
+            ))
+        )
+    });
+}
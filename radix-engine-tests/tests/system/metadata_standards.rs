@@ -0,0 +1,129 @@
+use radix_common::prelude::*;
+use radix_engine_interface::object_modules::metadata::*;
+use radix_substate_store_queries::query::*;
+use scrypto_test::prelude::*;
+
+fn create_resource_linking_to(
+    ledger: &mut DefaultLedgerSimulator,
+    account: ComponentAddress,
+    dapp_definitions: Vec<GlobalAddress>,
+) -> ResourceAddress {
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_fungible_resource(
+            OwnerRole::None,
+            true,
+            18,
+            FungibleResourceRoles::default(),
+            metadata! {
+                init {
+                    "name" => "Linked Token".to_owned(), locked;
+                    "dapp_definitions" => dapp_definitions, locked;
+                }
+            },
+            Some(dec!(1)),
+        )
+        .try_deposit_entire_worktop_or_abort(account, None)
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success().new_resource_addresses()[0]
+}
+
+#[test]
+fn dapp_definition_two_way_links_are_validated() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, dapp_definition) = ledger.new_account(false);
+    let (_, _, plain_account) = ledger.new_account(false);
+    let linked =
+        create_resource_linking_to(&mut ledger, plain_account, vec![dapp_definition.into()]);
+    let unlinked = create_resource_linking_to(&mut ledger, plain_account, vec![]);
+    let unclaimed =
+        create_resource_linking_to(&mut ledger, plain_account, vec![dapp_definition.into()]);
+    let misdirected =
+        create_resource_linking_to(&mut ledger, plain_account, vec![plain_account.into()]);
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .set_metadata(dapp_definition, "account_type", "dapp definition")
+        .set_metadata(
+            dapp_definition,
+            "claimed_entities",
+            vec![GlobalAddress::from(linked), GlobalAddress::from(unlinked)],
+        )
+        .build();
+    ledger
+        .execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&public_key)],
+        )
+        .expect_commit_success();
+
+    // Act
+    let validator = MetadataStandardsValidator::new(ledger.substate_db());
+
+    // Assert
+    assert!(validator.validate(linked.into()).is_valid());
+    assert!(validator.validate(unlinked.into()).is_valid());
+    assert_eq!(
+        validator.validate(dapp_definition.into()).issues,
+        vec![MetadataStandardsIssue::ClaimedEntityDoesNotLinkBack {
+            claimed_entity: unlinked.into()
+        }]
+    );
+    assert_eq!(
+        validator.validate(unclaimed.into()).issues,
+        vec![MetadataStandardsIssue::DappDefinitionDoesNotClaimEntity {
+            dapp_definition: dapp_definition.into()
+        }]
+    );
+    assert_eq!(
+        validator.validate(misdirected.into()).issues,
+        vec![MetadataStandardsIssue::LinkedEntityIsNotADappDefinition {
+            dapp_definition: plain_account.into()
+        }]
+    );
+}
+
+#[test]
+fn well_known_keys_with_unexpected_types_are_reported() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, account) = ledger.new_account(false);
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .set_metadata(account, "icon_url", "https://example.com/icon.png")
+        .set_metadata(account, "claimed_websites", ["https://example.com"])
+        .set_metadata(account, "account_type", "dapp")
+        .build();
+    ledger
+        .execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&public_key)],
+        )
+        .expect_commit_success();
+
+    // Act
+    let report = MetadataStandardsValidator::new(ledger.substate_db()).validate(account.into());
+
+    // Assert
+    let issues = report.issues;
+    assert_eq!(issues.len(), 4);
+    assert!(issues.contains(&MetadataStandardsIssue::UnexpectedType {
+        key: "icon_url".to_string(),
+        expected_type_id: METADATA_VALUE_URL_DISCRIMINATOR,
+        actual_type_id: METADATA_VALUE_STRING_DISCRIMINATOR,
+    }));
+    assert!(issues.contains(&MetadataStandardsIssue::UnexpectedType {
+        key: "claimed_websites".to_string(),
+        expected_type_id: METADATA_VALUE_ORIGIN_ARRAY_DISCRIMINATOR,
+        actual_type_id: METADATA_VALUE_STRING_ARRAY_DISCRIMINATOR,
+    }));
+    assert!(issues.contains(&MetadataStandardsIssue::UnknownAccountType(
+        "dapp".to_string()
+    )));
+    assert!(
+        issues.contains(&MetadataStandardsIssue::NotADappDefinition {
+            key: "claimed_websites".to_string()
+        })
+    );
+}
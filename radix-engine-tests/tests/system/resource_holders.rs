@@ -0,0 +1,188 @@
+use radix_common::prelude::*;
+use radix_substate_store_queries::query::*;
+use scrypto_test::prelude::*;
+
+#[test]
+fn holders_of_fungible_resource_are_ranked_by_balance() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, issuer) = ledger.new_account(false);
+    let (_, _, holder1) = ledger.new_account(false);
+    let (_, _, holder2) = ledger.new_account(false);
+    let resource_address = ledger.create_fungible_resource(dec!(1000), 18, issuer);
+    let mut index = ResourceHolderIndex::from_database(ledger.substate_db());
+    let pre_state = ledger.substate_db().clone();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(issuer, resource_address, dec!(400))
+        .take_from_worktop(resource_address, dec!(300), "bucket")
+        .try_deposit_or_abort(holder1, None, "bucket")
+        .try_deposit_entire_worktop_or_abort(holder2, None)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    index.update_from_commit(&pre_state, receipt.expect_commit_success());
+
+    // Assert
+    let top_holders = index
+        .top_holders(&resource_address, 2)
+        .into_iter()
+        .map(|holder| (holder.owner, holder.balance))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        top_holders,
+        vec![(issuer.into(), dec!(600)), (holder1.into(), dec!(300))]
+    );
+    assert_eq!(index.holders(&resource_address).len(), 3);
+    assert_eq!(
+        index.circulating_supply(&resource_address, &[issuer.into()]),
+        dec!(400)
+    );
+    assert_eq!(index.circulating_supply(&resource_address, &[]), dec!(1000));
+}
+
+#[test]
+fn holders_of_non_fungibles_follow_transfers() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (public_key, _, sender) = ledger.new_account(false);
+    let (_, _, recipient) = ledger.new_account(false);
+    let resource_address = ledger.create_non_fungible_resource(sender);
+    let non_fungible_global_id =
+        NonFungibleGlobalId::new(resource_address, NonFungibleLocalId::integer(1));
+    let mut index = ResourceHolderIndex::from_database(ledger.substate_db());
+    let pre_state = ledger.substate_db().clone();
+    assert_eq!(
+        index
+            .holder_of_non_fungible(&non_fungible_global_id)
+            .unwrap()
+            .owner,
+        Some(sender.into())
+    );
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_non_fungibles_from_account(
+            sender,
+            resource_address,
+            [NonFungibleLocalId::integer(1)],
+        )
+        .try_deposit_entire_worktop_or_abort(recipient, None)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    index.update_from_commit(&pre_state, receipt.expect_commit_success());
+
+    // Assert
+    assert_eq!(
+        index
+            .holder_of_non_fungible(&non_fungible_global_id)
+            .unwrap()
+            .owner,
+        Some(recipient.into())
+    );
+    let holders = index
+        .holders(&resource_address)
+        .into_iter()
+        .map(|holder| (holder.owner, holder.balance))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        holders,
+        vec![(sender.into(), dec!(2)), (recipient.into(), dec!(1))]
+    );
+}
+
+#[test]
+fn holders_are_reranked_as_their_balances_change() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (issuer_public_key, _, issuer) = ledger.new_account(false);
+    let (public_key, _, holder1) = ledger.new_account(false);
+    let (_, _, holder2) = ledger.new_account(false);
+    let resource_address = ledger.create_fungible_resource(dec!(1000), 18, issuer);
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(issuer, resource_address, dec!(400))
+        .take_from_worktop(resource_address, dec!(300), "bucket")
+        .try_deposit_or_abort(holder1, None, "bucket")
+        .try_deposit_entire_worktop_or_abort(holder2, None)
+        .build();
+    ledger
+        .execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&issuer_public_key)],
+        )
+        .expect_commit_success();
+    let mut index = ResourceHolderIndex::from_database(ledger.substate_db());
+    let pre_state = ledger.substate_db().clone();
+
+    // Act
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(holder1, resource_address, dec!(300))
+        .try_deposit_entire_worktop_or_abort(holder2, None)
+        .build();
+    let receipt = ledger.execute_manifest(
+        manifest,
+        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    );
+    index.update_from_commit(&pre_state, receipt.expect_commit_success());
+
+    // Assert
+    let holders = index
+        .holders(&resource_address)
+        .into_iter()
+        .map(|holder| (holder.owner, holder.balance))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        holders,
+        vec![(issuer.into(), dec!(600)), (holder2.into(), dec!(400))]
+    );
+    assert_eq!(index.holder(&resource_address, &holder1.into()), None);
+    assert_eq!(
+        index.circulating_supply(&resource_address, &[issuer.into(), issuer.into()]),
+        dec!(400)
+    );
+}
+
+#[test]
+fn vaults_change_hands_when_attached_to_a_new_parent() {
+    // Arrange
+    let mut ledger = LedgerSimulatorBuilder::new().build();
+    let (_, _, issuer) = ledger.new_account(false);
+    let (_, _, holder) = ledger.new_account(false);
+    let resource_address = ledger.create_fungible_resource(dec!(1000), 18, issuer);
+    let mut index = ResourceHolderIndex::from_database(ledger.substate_db());
+    let vault_id = index
+        .holder(&resource_address, &issuer.into())
+        .unwrap()
+        .vaults[0];
+
+    // Act
+    // None of the vault's own substates change, only the substate which owns it
+    let mut state_updates = StateUpdates::default();
+    state_updates
+        .of_node(holder.into_node_id())
+        .of_partition(PartitionNumber(200))
+        .update_substates([(
+            SubstateKey::Field(0),
+            DatabaseUpdate::Set(scrypto_encode(&Own(vault_id)).unwrap()),
+        )]);
+    index.update(ledger.substate_db(), &state_updates);
+
+    // Assert
+    let holders = index
+        .holders(&resource_address)
+        .into_iter()
+        .map(|holder| (holder.owner, holder.balance))
+        .collect::<Vec<_>>();
+    assert_eq!(holders, vec![(holder.into(), dec!(1000))]);
+    assert_eq!(index.holder(&resource_address, &issuer.into()), None);
+}
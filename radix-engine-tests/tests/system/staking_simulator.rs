@@ -0,0 +1,156 @@
+use radix_common::prelude::*;
+use radix_engine_interface::blueprints::consensus_manager::*;
+use scrypto_test::prelude::*;
+
+fn config_with_rounds_per_epoch(rounds_per_epoch: u64) -> ConsensusManagerConfig {
+    CustomGenesis::default_consensus_manager_config().with_epoch_change_condition(
+        EpochChangeCondition {
+            min_round_count: rounds_per_epoch,
+            max_round_count: rounds_per_epoch,
+            target_duration_millis: 0,
+        },
+    )
+}
+
+#[test]
+fn emissions_are_distributed_proportionally_to_stake() {
+    // Arrange
+    let mut simulator = StakingSimulatorBuilder::new()
+        .with_validator(SimulatedValidatorConfig::new(dec!(100)))
+        .with_validator(SimulatedValidatorConfig::new(dec!(300)).with_fee_factor(dec!("0.5")))
+        .with_consensus_manager_config(
+            config_with_rounds_per_epoch(2).with_total_emission_xrd_per_epoch(dec!(4)),
+        )
+        .build();
+
+    // Act
+    let reports = simulator.advance_epochs(2);
+
+    // Assert
+    let report = &reports[0];
+    assert_eq!(report.total_emission_xrd(), dec!(4));
+    let [a, b] = [&report.validators[0], &report.validators[1]];
+    assert_eq!((a.proposals_made, a.proposals_missed), (1, 0));
+    assert_eq!((b.proposals_made, b.proposals_missed), (1, 0));
+    assert_eq!(a.stake_pool_added_xrd, dec!(1));
+    assert_eq!(a.validator_fee_xrd, dec!(0));
+    assert_eq!(a.stake_xrd, dec!(101));
+    assert_eq!(a.stake_unit_redemption_value, dec!("1.01"));
+    assert_eq!(a.stake_unit_redemption_value_growth, dec!("0.01"));
+    assert_eq!(b.stake_pool_added_xrd, dec!("1.5"));
+    assert_eq!(b.validator_fee_xrd, dec!("1.5"));
+    assert_eq!(b.stake_xrd, dec!(303));
+    assert_eq!(
+        reports[1].epoch,
+        report.epoch.next().unwrap(),
+        "Reports should cover consecutive epochs"
+    );
+    let annualized_yield = StakingSimulator::annualized_yield(&reports[..1], 0, 2);
+    assert!(
+        annualized_yield
+            .checked_sub(dec!("0.0201"))
+            .unwrap()
+            .checked_abs()
+            .unwrap()
+            < dec!("0.000001")
+    );
+}
+
+#[test]
+fn missed_proposals_reduce_the_emission() {
+    // Arrange
+    let mut simulator = StakingSimulatorBuilder::new()
+        .with_validator(
+            SimulatedValidatorConfig::new(dec!(100))
+                .with_proposal_pattern(ProposalPattern::Reliability(dec!("0.75"))),
+        )
+        .with_consensus_manager_config(
+            config_with_rounds_per_epoch(4)
+                .with_total_emission_xrd_per_epoch(dec!(10))
+                .with_min_validator_reliability(dec!("0.5")),
+        )
+        .build();
+
+    // Act
+    let report = simulator.advance_epoch();
+
+    // Assert
+    let validator = &report.validators[0];
+    assert_eq!(
+        (validator.proposals_made, validator.proposals_missed),
+        (3, 1)
+    );
+    assert_eq!(validator.emission_xrd(), dec!(5));
+}
+
+#[test]
+#[should_panic(expected = "Reliability should be between 0 and 1, but is 1.5")]
+fn reliability_above_one_is_rejected() {
+    SimulatedValidatorConfig::new(dec!(100))
+        .with_proposal_pattern(ProposalPattern::Reliability(dec!("1.5")));
+}
+
+#[test]
+#[should_panic(expected = "Reliability should be between 0 and 1, but is -0.5")]
+fn negative_reliability_is_rejected() {
+    SimulatedValidatorConfig::new(dec!(100))
+        .with_proposal_pattern(ProposalPattern::Reliability(dec!("-0.5")));
+}
+
+#[test]
+fn validator_missing_the_epoch_ending_proposal_gets_no_emission() {
+    // Arrange
+    let mut simulator = StakingSimulatorBuilder::new()
+        .with_validator(SimulatedValidatorConfig::new(dec!(300)))
+        .with_validator(
+            SimulatedValidatorConfig::new(dec!(100))
+                .with_proposal_pattern(ProposalPattern::always_missed()),
+        )
+        .with_consensus_manager_config(
+            config_with_rounds_per_epoch(2).with_total_emission_xrd_per_epoch(dec!(4)),
+        )
+        .build();
+
+    // Act
+    let report = simulator.advance_epoch();
+
+    // Assert
+    let [reliable, offline] = [&report.validators[0], &report.validators[1]];
+    assert_eq!((reliable.proposals_made, reliable.proposals_missed), (1, 0));
+    assert_eq!(reliable.emission_xrd(), dec!(3));
+    assert_eq!((offline.proposals_made, offline.proposals_missed), (0, 1));
+    assert_eq!(offline.emission_xrd(), dec!(0));
+    assert_eq!(offline.stake_unit_redemption_value_growth, dec!(0));
+}
+
+#[test]
+fn unstaked_xrd_can_be_claimed_after_the_unstake_delay() {
+    // Arrange
+    let mut simulator = StakingSimulatorBuilder::new()
+        .with_validator(SimulatedValidatorConfig::new(dec!(100)))
+        .with_consensus_manager_config(
+            config_with_rounds_per_epoch(1)
+                .with_total_emission_xrd_per_epoch(dec!(0))
+                .with_num_unstake_epochs(2),
+        )
+        .build();
+    let current_epoch = simulator.ledger().get_consensus_manager_state().epoch;
+
+    // Act
+    let claim = simulator.unstake(0, dec!(10));
+    let early_claimed_xrd = simulator.claim_xrd(0);
+    let reports = simulator.advance_epochs(2);
+    let claimed_xrd = simulator.claim_xrd(0);
+
+    // Assert
+    assert_eq!(claim.claim_epoch, current_epoch.after(2).unwrap());
+    assert_eq!(claim.claim_amount, dec!(10));
+    assert_eq!(early_claimed_xrd, dec!(0));
+    assert_eq!(reports[0].validators[0].pending_unbonding_xrd, dec!(10));
+    assert_eq!(reports[0].validators[0].claimable_unbonding_xrd, dec!(0));
+    assert_eq!(reports[1].validators[0].pending_unbonding_xrd, dec!(0));
+    assert_eq!(reports[1].validators[0].claimable_unbonding_xrd, dec!(10));
+    assert_eq!(claimed_xrd, dec!(10));
+    assert_eq!(simulator.staker_stake_units(0), dec!(90));
+    assert!(simulator.unbonding_claims().is_empty());
+}
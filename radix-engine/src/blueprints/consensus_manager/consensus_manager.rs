@@ -894,7 +894,7 @@ impl ConsensusManagerBlueprint {
         }
     }
 
-    fn epoch_minute_to_instant(epoch_minute: i32) -> Instant {
+    pub(crate) fn epoch_minute_to_instant(epoch_minute: i32) -> Instant {
         Instant::new(epoch_minute as i64 * SECONDS_IN_MINUTE)
     }
 
@@ -27,7 +27,7 @@ use crate::roles_template;
 use crate::system::system::*;
 use crate::system::system_callback::{System, SystemLockData};
 use crate::system::system_callback_api::SystemCallbackObject;
-use crate::system::system_modules::auth::{AuthError, ResolvedPermission};
+use crate::system::system_modules::auth::{AccessRuleSource, AuthError, ResolvedPermission};
 use crate::system::system_type_checker::SystemMapper;
 use crate::vm::{VmApi, VmPackageValidation};
 
@@ -35,6 +35,19 @@ use crate::vm::{VmApi, VmPackageValidation};
 pub enum PackageV1MinorVersion {
     Zero,
     One,
+    Two,
+}
+
+impl PackageV1MinorVersion {
+    /// Whether reserved role keys used in a blueprint's role lists must be defined.
+    pub fn restricts_reserved_role_keys(&self) -> bool {
+        *self >= Self::One
+    }
+
+    /// Whether function access rules may contain time conditions.
+    pub fn supports_time_conditions(&self) -> bool {
+        *self >= Self::Two
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
@@ -417,7 +430,7 @@ where
 
 fn validate_auth(
     definition: &PackageDefinition,
-    restrict_reserved_key: bool,
+    version: PackageV1MinorVersion,
 ) -> Result<(), PackageError> {
     for (blueprint, definition_init) in &definition.blueprints {
         match &definition_init.auth_config.function_auth {
@@ -450,7 +463,12 @@ fn validate_auth(
 
                 functions
                     .values()
-                    .map(RoleAssignmentNativePackage::verify_access_rule)
+                    .map(|access_rule| {
+                        RoleAssignmentNativePackage::verify_access_rule(
+                            access_rule,
+                            version.supports_time_conditions(),
+                        )
+                    })
                     .collect::<Result<_, _>>()
                     .map_err(PackageError::RoleAssignmentError)?;
             }
@@ -490,7 +508,7 @@ fn validate_auth(
                 let check_list = |list: &RoleList| {
                     for role_key in &list.list {
                         if RoleAssignmentNativePackage::is_role_key_reserved(role_key) {
-                            if restrict_reserved_key
+                            if version.restricts_reserved_role_keys()
                                 && !RoleAssignmentNativePackage::is_role_key_reserved_and_defined(
                                     role_key,
                                 )
@@ -752,9 +770,12 @@ pub fn create_package_partition_substates(
             ROLE_ASSIGNMENT_BLUEPRINT,
             indexmap!(),
         );
-        let role_assignment_system_struct =
-            RoleAssignmentNativePackage::init_system_struct(OwnerRole::None.into(), indexmap!())
-                .unwrap();
+        let role_assignment_system_struct = RoleAssignmentNativePackage::init_system_struct(
+            OwnerRole::None.into(),
+            indexmap!(),
+            false,
+        )
+        .unwrap();
         let role_assignment_substates = SystemMapper::system_struct_to_node_substates(
             &role_assignment_schema,
             role_assignment_system_struct,
@@ -956,11 +977,6 @@ impl PackageNativePackage {
         Y: ClientApi<RuntimeError>,
         V: VmApi,
     {
-        let restrict_reserved_key = match version {
-            PackageV1MinorVersion::Zero => false,
-            PackageV1MinorVersion::One => true,
-        };
-
         match export_name {
             PACKAGE_PUBLISH_NATIVE_IDENT => {
                 let input: PackagePublishNativeInput = input.as_typed().map_err(|e| {
@@ -972,7 +988,7 @@ impl PackageNativePackage {
                     input.native_package_code_id,
                     input.definition,
                     input.metadata,
-                    restrict_reserved_key,
+                    version,
                     api,
                     vm_api,
                 )?;
@@ -988,7 +1004,7 @@ impl PackageNativePackage {
                     input.code,
                     input.definition,
                     input.metadata,
-                    restrict_reserved_key,
+                    version,
                     api,
                     vm_api,
                 )?;
@@ -1006,7 +1022,7 @@ impl PackageNativePackage {
                     input.definition,
                     input.metadata,
                     input.owner_role,
-                    restrict_reserved_key,
+                    version,
                     api,
                     vm_api,
                 )?;
@@ -1175,7 +1191,7 @@ impl PackageNativePackage {
         vm_type: VmType,
         original_code: Vec<u8>,
         system_instructions: BTreeMap<String, Vec<SystemInstruction>>,
-        restrict_reserved_key: bool,
+        version: PackageV1MinorVersion,
         vm_api: &V,
     ) -> Result<PackageStructure, RuntimeError> {
         // Validate schema
@@ -1185,7 +1201,7 @@ impl PackageNativePackage {
             .map_err(|e| RuntimeError::ApplicationError(ApplicationError::PackageError(e)))?;
         validate_type_schemas(definition.blueprints.values())
             .map_err(|e| RuntimeError::ApplicationError(ApplicationError::PackageError(e)))?;
-        validate_auth(&definition, restrict_reserved_key)
+        validate_auth(&definition, version)
             .map_err(|e| RuntimeError::ApplicationError(ApplicationError::PackageError(e)))?;
         validate_names(&definition)
             .map_err(|e| RuntimeError::ApplicationError(ApplicationError::PackageError(e)))?;
@@ -1366,7 +1382,7 @@ impl PackageNativePackage {
         native_package_code_id: u64,
         definition: PackageDefinition,
         metadata_init: MetadataInit,
-        restrict_reserved_key: bool,
+        version: PackageV1MinorVersion,
         api: &mut Y,
         vm_api: &V,
     ) -> Result<PackageAddress, RuntimeError>
@@ -1380,7 +1396,7 @@ impl PackageNativePackage {
             VmType::Native,
             native_package_code_id.to_be_bytes().to_vec(),
             Default::default(),
-            restrict_reserved_key,
+            version,
             vm_api,
         )?;
         let role_assignment = RoleAssignment::create(OwnerRole::None, indexmap!(), api)?;
@@ -1399,7 +1415,7 @@ impl PackageNativePackage {
         code: Vec<u8>,
         definition: PackageDefinition,
         metadata_init: MetadataInit,
-        restrict_reserved_key: bool,
+        version: PackageV1MinorVersion,
         api: &mut Y,
         vm_api: &V,
     ) -> Result<(PackageAddress, Bucket), RuntimeError>
@@ -1414,7 +1430,7 @@ impl PackageNativePackage {
             VmType::ScryptoV1,
             code,
             Default::default(),
-            restrict_reserved_key,
+            version,
             vm_api,
         )?;
 
@@ -1450,7 +1466,7 @@ impl PackageNativePackage {
         definition: PackageDefinition,
         metadata_init: MetadataInit,
         owner_role: OwnerRole,
-        restrict_reserved_key: bool,
+        version: PackageV1MinorVersion,
         api: &mut Y,
         vm_api: &V,
     ) -> Result<PackageAddress, RuntimeError>
@@ -1464,7 +1480,7 @@ impl PackageNativePackage {
             VmType::ScryptoV1,
             code,
             Default::default(),
-            restrict_reserved_key,
+            version,
             vm_api,
         )?;
         let metadata = Metadata::create_with_data(metadata_init, api)?;
@@ -1616,13 +1632,19 @@ impl PackageAuthNativeBlueprint {
                 if api.kernel_get_current_depth() == 0 {
                     Ok(ResolvedPermission::AllowAll)
                 } else {
-                    Ok(ResolvedPermission::AccessRule(AccessRule::DenyAll))
+                    Ok(ResolvedPermission::AccessRule {
+                        access_rule: AccessRule::DenyAll,
+                        source: AccessRuleSource::FunctionAuth,
+                    })
                 }
             }
             FunctionAuth::AccessRules(rules) => {
                 let access_rule = rules.get(ident);
                 if let Some(access_rule) = access_rule {
-                    Ok(ResolvedPermission::AccessRule(access_rule.clone()))
+                    Ok(ResolvedPermission::AccessRule {
+                        access_rule: access_rule.clone(),
+                        source: AccessRuleSource::FunctionAuth,
+                    })
                 } else {
                     let package_address = PackageAddress::new_or_panic(receiver.0.clone());
                     let blueprint_id =
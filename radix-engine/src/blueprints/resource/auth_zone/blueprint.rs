@@ -1,4 +1,4 @@
-use crate::blueprints::resource::ComposedProof;
+use crate::blueprints::resource::{ComposedProof, AUTH_ZONE_ASSERT_ACCESS_RULE_EXPORT_NAME};
 use crate::errors::*;
 use crate::internal_prelude::*;
 use crate::kernel::kernel_api::{KernelNodeApi, KernelSubstateApi};
@@ -282,14 +282,19 @@ impl AuthZoneBlueprint {
 
     pub fn assert_access_rule<Y, L: Default>(
         access_rule: AccessRule,
+        enable_time_conditions: bool,
         api: &mut Y,
     ) -> Result<(), RuntimeError>
     where
         Y: KernelSubstateApi<L> + ClientApi<RuntimeError>,
     {
         let node_id = api.actor_get_node_id(ACTOR_REF_SELF)?;
-        let auth_result =
-            Authorization::check_authorization_against_access_rule(api, &node_id, &access_rule)?;
+        let auth_result = Authorization::check_authorization_against_access_rule(
+            api,
+            &node_id,
+            &access_rule,
+            enable_time_conditions,
+        )?;
 
         match auth_result {
             AuthorizationCheckResult::Authorized => Ok(()),
@@ -299,3 +304,35 @@ impl AuthZoneBlueprint {
         }
     }
 }
+
+/// The auth zone code as of Cuttlefish, which evaluates the time conditions of asserted access
+/// rules. The remaining exports are still served by [`ResourceNativePackage`].
+///
+/// [`ResourceNativePackage`]: crate::blueprints::resource::ResourceNativePackage
+pub struct AuthZoneCuttlefishExtension;
+
+impl AuthZoneCuttlefishExtension {
+    pub fn invoke_export<Y>(
+        export_name: &str,
+        input: &IndexedScryptoValue,
+        api: &mut Y,
+    ) -> Result<IndexedScryptoValue, RuntimeError>
+    where
+        Y: KernelSubstateApi<SystemLockData> + ClientApi<RuntimeError>,
+    {
+        match export_name {
+            AUTH_ZONE_ASSERT_ACCESS_RULE_EXPORT_NAME => {
+                let input: AuthZoneAssertAccessRuleInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+
+                let rtn = AuthZoneBlueprint::assert_access_rule(input.rule, true, api)?;
+
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            _ => Err(RuntimeError::ApplicationError(
+                ApplicationError::ExportDoesNotExist(export_name.to_string()),
+            )),
+        }
+    }
+}
@@ -2043,7 +2043,7 @@ impl ResourceNativePackage {
                     RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
                 })?;
 
-                let rtn = AuthZoneBlueprint::assert_access_rule(input.rule, api)?;
+                let rtn = AuthZoneBlueprint::assert_access_rule(input.rule, false, api)?;
 
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
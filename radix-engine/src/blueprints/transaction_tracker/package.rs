@@ -153,7 +153,7 @@ impl TransactionStatus {
     }
 }
 
-#[derive(Debug, Clone, ScryptoSbor)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ScryptoSbor)]
 pub enum TransactionStatusV1 {
     CommittedSuccess,
     CommittedFailure,
@@ -224,6 +224,31 @@ impl TransactionTrackerSubstateV1 {
         Some(partition_number as u8)
     }
 
+    /// Returns the range of expiry epochs (start inclusive, end exclusive) whose intents are
+    /// stored in the given partition, i.e. the inverse of [`Self::partition_for_expiry_epoch`].
+    pub fn expiry_epochs_of_partition(&self, partition_number: u8) -> Option<(Epoch, Epoch)> {
+        if partition_number < self.partition_range_start_inclusive
+            || partition_number > self.partition_range_end_inclusive
+        {
+            return None;
+        }
+
+        // Count the partitions from the start partition, wrapping around the partition range
+        let num_partitions =
+            (self.partition_range_end_inclusive - self.partition_range_start_inclusive) as u64 + 1;
+        let offset = if partition_number >= self.start_partition {
+            (partition_number - self.start_partition) as u64
+        } else {
+            partition_number as u64 + num_partitions - self.start_partition as u64
+        };
+
+        let start_epoch = self.start_epoch + offset * self.epochs_per_partition;
+        Some((
+            Epoch::of(start_epoch),
+            Epoch::of(start_epoch + self.epochs_per_partition),
+        ))
+    }
+
     /// This method will shift the start partition by 1, considering the partition range as a buffer.
     /// Protocol-specific implementation is within transaction executor.
     pub fn advance(&mut self) -> u8 {
@@ -338,4 +363,45 @@ mod tests {
         assert_eq!(store.v1().start_epoch, 256 + EPOCHS_PER_PARTITION);
         assert_eq!(store.v1().start_partition, 71);
     }
+
+    #[test]
+    fn test_expiry_epochs_of_partition_calculation() {
+        let store = TransactionTrackerSubstateV1 {
+            start_epoch: 256,
+            start_partition: 70,
+            partition_range_start_inclusive: PARTITION_RANGE_START,
+            partition_range_end_inclusive: PARTITION_RANGE_END,
+            epochs_per_partition: EPOCHS_PER_PARTITION,
+        };
+        let num_partitions = (PARTITION_RANGE_END - PARTITION_RANGE_START + 1) as u64;
+
+        assert_eq!(
+            store.expiry_epochs_of_partition(MAIN_BASE_PARTITION.0),
+            None
+        );
+        assert_eq!(
+            store.expiry_epochs_of_partition(70),
+            Some((Epoch::of(256), Epoch::of(256 + EPOCHS_PER_PARTITION)))
+        );
+        assert_eq!(
+            store.expiry_epochs_of_partition(PARTITION_RANGE_END),
+            Some((
+                Epoch::of(256 + EPOCHS_PER_PARTITION * (PARTITION_RANGE_END as u64 - 70)),
+                Epoch::of(256 + EPOCHS_PER_PARTITION * (PARTITION_RANGE_END as u64 - 69))
+            ))
+        );
+        assert_eq!(
+            store.expiry_epochs_of_partition(69),
+            Some((
+                Epoch::of(256 + EPOCHS_PER_PARTITION * (num_partitions - 1)),
+                Epoch::of(256 + EPOCHS_PER_PARTITION * num_partitions)
+            ))
+        );
+        for epoch in [256, 256 + EPOCHS_PER_PARTITION * 100 + 1] {
+            let epoch = Epoch::of(epoch);
+            let partition = store.partition_for_expiry_epoch(epoch).unwrap();
+            let (start, end) = store.expiry_epochs_of_partition(partition).unwrap();
+            assert!(start <= epoch && epoch < end);
+        }
+    }
 }
@@ -7,7 +7,7 @@ use crate::object_modules::role_assignment::{LockOwnerRoleEvent, SetOwnerRoleEve
 use crate::system::system::SystemService;
 use crate::system::system_callback::{System, SystemLockData};
 use crate::system::system_callback_api::SystemCallbackObject;
-use crate::system::system_modules::auth::{AuthError, ResolvedPermission};
+use crate::system::system_modules::auth::{AccessRuleSource, AuthError, ResolvedPermission};
 use crate::system::system_substates::FieldSubstate;
 use crate::{errors::*, event_schema};
 use radix_blueprint_schema_init::{
@@ -38,6 +38,7 @@ pub enum RoleAssignmentError {
     InvalidName(InvalidNameError),
     ExceededMaxRoles,
     CannotSetRoleIfNotAttached,
+    TimeConditionsNotSupported,
 }
 
 pub struct RoleAssignmentNativePackage;
@@ -217,7 +218,7 @@ impl RoleAssignmentNativePackage {
                     RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
                 })?;
 
-                let rtn = Self::create(input.owner_role, input.roles, api)?;
+                let rtn = Self::create(input.owner_role, input.roles, false, api)?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
             ROLE_ASSIGNMENT_SET_OWNER_IDENT => {
@@ -225,7 +226,7 @@ impl RoleAssignmentNativePackage {
                     RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
                 })?;
 
-                let rtn = Self::set_owner_role(input.rule, api)?;
+                let rtn = Self::set_owner_role(input.rule, false, api)?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
             ROLE_ASSIGNMENT_LOCK_OWNER_IDENT => {
@@ -241,7 +242,7 @@ impl RoleAssignmentNativePackage {
                     RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
                 })?;
 
-                let rtn = Self::set_role(input.module, input.role_key, input.rule, api)?;
+                let rtn = Self::set_role(input.module, input.role_key, input.rule, false, api)?;
                 Ok(IndexedScryptoValue::from_typed(&rtn))
             }
             ROLE_ASSIGNMENT_GET_IDENT => {
@@ -273,19 +274,33 @@ impl RoleAssignmentNativePackage {
             && (role_key.key.eq(OWNER_ROLE) || role_key.key.eq(SELF_ROLE))
     }
 
-    pub fn verify_access_rule(access_rule: &AccessRule) -> Result<(), RoleAssignmentError> {
-        pub struct AccessRuleVerifier(usize);
+    /// Checks that an access rule is within the depth and size limits, and that it only contains
+    /// time conditions if they are supported (i.e. as of Cuttlefish).
+    pub fn verify_access_rule(
+        access_rule: &AccessRule,
+        allow_time_conditions: bool,
+    ) -> Result<(), RoleAssignmentError> {
+        pub struct AccessRuleVerifier {
+            node_count: usize,
+            allow_time_conditions: bool,
+        }
         impl AccessRuleVisitor for AccessRuleVerifier {
             type Error = RoleAssignmentError;
-            fn visit(&mut self, _node: &AccessRuleNode, depth: usize) -> Result<(), Self::Error> {
+            fn visit(&mut self, node: &AccessRuleNode, depth: usize) -> Result<(), Self::Error> {
                 // This is to protect unbounded native stack usage during authorization
                 if depth > MAX_ACCESS_RULE_DEPTH {
                     return Err(RoleAssignmentError::ExceededMaxAccessRuleDepth);
                 }
 
-                self.0 += 1;
+                if !self.allow_time_conditions {
+                    if let AccessRuleNode::TimeCondition(..) = node {
+                        return Err(RoleAssignmentError::TimeConditionsNotSupported);
+                    }
+                }
+
+                self.node_count += 1;
 
-                if self.0 > MAX_ACCESS_RULE_NODES {
+                if self.node_count > MAX_ACCESS_RULE_NODES {
                     return Err(RoleAssignmentError::ExceededMaxAccessRuleNodes);
                 }
 
@@ -293,7 +308,10 @@ impl RoleAssignmentNativePackage {
             }
         }
 
-        access_rule.dfs_traverse_nodes(&mut AccessRuleVerifier(0))
+        access_rule.dfs_traverse_nodes(&mut AccessRuleVerifier {
+            node_count: 0,
+            allow_time_conditions,
+        })
     }
 
     fn resolve_update_owner_role_method_permission<
@@ -321,15 +339,18 @@ impl RoleAssignmentNativePackage {
             .into_payload()
             .fully_update_and_into_latest_version();
 
-        let rule = match owner_role.owner_role_entry.updater {
+        let global_address = GlobalAddress::new_or_panic(receiver.0);
+        let updater = owner_role.owner_role_entry.updater;
+        let rule = match updater {
             OwnerRoleUpdater::None => AccessRule::DenyAll,
             OwnerRoleUpdater::Owner => owner_role.owner_role_entry.rule,
-            OwnerRoleUpdater::Object => rule!(require(global_caller(GlobalAddress::new_or_panic(
-                receiver.0
-            )))),
+            OwnerRoleUpdater::Object => rule!(require(global_caller(global_address))),
         };
 
-        Ok(ResolvedPermission::AccessRule(rule))
+        Ok(ResolvedPermission::AccessRule {
+            access_rule: rule,
+            source: AccessRuleSource::OwnerRoleUpdater(global_address, updater),
+        })
     }
 
     fn resolve_update_role_method_permission<Y: KernelApi<System<V>>, V: SystemCallbackObject>(
@@ -368,6 +389,7 @@ impl RoleAssignmentNativePackage {
     pub fn init_system_struct(
         owner_role: OwnerRoleEntry,
         roles: IndexMap<ModuleId, RoleAssignmentInit>,
+        allow_time_conditions: bool,
     ) -> Result<
         (
             IndexMap<u8, FieldValue>,
@@ -379,7 +401,7 @@ impl RoleAssignmentNativePackage {
             return Err(RoleAssignmentError::UsedReservedSpace);
         }
 
-        Self::verify_access_rule(&owner_role.rule)?;
+        Self::verify_access_rule(&owner_role.rule, allow_time_conditions)?;
 
         let owner_role_substate = OwnerRoleSubstate {
             owner_role_entry: owner_role.clone(),
@@ -419,7 +441,7 @@ impl RoleAssignmentNativePackage {
                 let module_role_key = ModuleRoleKey::new(module, role_key);
 
                 if let Some(access_rule) = &role_def {
-                    Self::verify_access_rule(access_rule)?;
+                    Self::verify_access_rule(access_rule, allow_time_conditions)?;
                 }
 
                 let value = role_def.map(|rule| {
@@ -447,14 +469,16 @@ impl RoleAssignmentNativePackage {
     pub(crate) fn create<Y>(
         owner_role: OwnerRoleEntry,
         roles: IndexMap<ModuleId, RoleAssignmentInit>,
+        allow_time_conditions: bool,
         api: &mut Y,
     ) -> Result<Own, RuntimeError>
     where
         Y: ClientApi<RuntimeError>,
     {
-        let (fields, kv_entries) = Self::init_system_struct(owner_role, roles).map_err(|e| {
-            RuntimeError::ApplicationError(ApplicationError::RoleAssignmentError(e))
-        })?;
+        let (fields, kv_entries) =
+            Self::init_system_struct(owner_role, roles, allow_time_conditions).map_err(|e| {
+                RuntimeError::ApplicationError(ApplicationError::RoleAssignmentError(e))
+            })?;
 
         let component_id = api.new_object(
             ROLE_ASSIGNMENT_BLUEPRINT,
@@ -467,11 +491,15 @@ impl RoleAssignmentNativePackage {
         Ok(Own(component_id))
     }
 
-    fn set_owner_role<Y>(rule: AccessRule, api: &mut Y) -> Result<(), RuntimeError>
+    fn set_owner_role<Y>(
+        rule: AccessRule,
+        allow_time_conditions: bool,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError>
     where
         Y: ClientApi<RuntimeError>,
     {
-        Self::verify_access_rule(&rule).map_err(|e| {
+        Self::verify_access_rule(&rule, allow_time_conditions).map_err(|e| {
             RuntimeError::ApplicationError(ApplicationError::RoleAssignmentError(e))
         })?;
 
@@ -517,6 +545,7 @@ impl RoleAssignmentNativePackage {
         module: ModuleId,
         role_key: RoleKey,
         rule: AccessRule,
+        allow_time_conditions: bool,
         api: &mut Y,
     ) -> Result<(), RuntimeError>
     where
@@ -552,7 +581,7 @@ impl RoleAssignmentNativePackage {
 
         let module_role_key = ModuleRoleKey::new(module, role_key.clone());
 
-        Self::verify_access_rule(&rule).map_err(|e| {
+        Self::verify_access_rule(&rule, allow_time_conditions).map_err(|e| {
             RuntimeError::ApplicationError(ApplicationError::RoleAssignmentError(e))
         })?;
 
@@ -682,3 +711,56 @@ impl RoleAssignmentBottlenoseExtension {
         Ok(owner_role_entry)
     }
 }
+
+/// The role assignment code as of Cuttlefish, which allows access rules with time conditions to
+/// be stored. The remaining exports are still served by [`RoleAssignmentNativePackage`] and
+/// [`RoleAssignmentBottlenoseExtension`].
+pub struct RoleAssignmentCuttlefishExtension;
+
+impl RoleAssignmentCuttlefishExtension {
+    pub fn invoke_export<Y>(
+        export_name: &str,
+        input: &IndexedScryptoValue,
+        api: &mut Y,
+    ) -> Result<IndexedScryptoValue, RuntimeError>
+    where
+        Y: ClientApi<RuntimeError>,
+    {
+        match export_name {
+            ROLE_ASSIGNMENT_CREATE_IDENT => {
+                let input: RoleAssignmentCreateInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+
+                let rtn =
+                    RoleAssignmentNativePackage::create(input.owner_role, input.roles, true, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ROLE_ASSIGNMENT_SET_OWNER_IDENT => {
+                let input: RoleAssignmentSetOwnerInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+
+                let rtn = RoleAssignmentNativePackage::set_owner_role(input.rule, true, api)?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            ROLE_ASSIGNMENT_SET_IDENT => {
+                let input: RoleAssignmentSetInput = input.as_typed().map_err(|e| {
+                    RuntimeError::ApplicationError(ApplicationError::InputDecodeError(e))
+                })?;
+
+                let rtn = RoleAssignmentNativePackage::set_role(
+                    input.module,
+                    input.role_key,
+                    input.rule,
+                    true,
+                    api,
+                )?;
+                Ok(IndexedScryptoValue::from_typed(&rtn))
+            }
+            _ => Err(RuntimeError::ApplicationError(
+                ApplicationError::ExportDoesNotExist(export_name.to_string()),
+            )),
+        }
+    }
+}
@@ -4,7 +4,7 @@ use crate::blueprints::consensus_manager::ConsensusManagerNativePackage;
 use crate::blueprints::identity::{IdentityNativePackage, IdentityOwnerBadgeData};
 use crate::blueprints::package::{
     create_package_partition_substates, PackageCollection, PackageNativePackage,
-    PackageOwnerBadgeData, SystemInstruction,
+    PackageOwnerBadgeData, PackageV1MinorVersion, SystemInstruction,
 };
 use crate::blueprints::pool::v1::package::{PoolNativePackage, PoolV1MinorVersion};
 use crate::blueprints::resource::ResourceNativePackage;
@@ -574,7 +574,7 @@ pub fn create_system_bootstrap_flash(
                 VmType::Native,
                 native_code_id.to_be_bytes().to_vec(),
                 system_instructions,
-                false,
+                PackageV1MinorVersion::Zero,
                 &VmBoot::babylon(),
             )
             .unwrap_or_else(|err| {
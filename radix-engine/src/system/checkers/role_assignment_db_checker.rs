@@ -179,7 +179,8 @@ impl RoleAssignmentDatabaseChecker {
     where
         F: FnMut(RoleAssignmentDatabaseCheckerError),
     {
-        if let Err(error) = RoleAssignmentNativePackage::verify_access_rule(&access_rule) {
+        // Time conditions can't be checked against the protocol version here, so are allowed
+        if let Err(error) = RoleAssignmentNativePackage::verify_access_rule(&access_rule, true) {
             add_error(RoleAssignmentDatabaseCheckerError::InvalidAccessRule(
                 access_rule,
                 error,
@@ -2,12 +2,15 @@ use crate::errors::RuntimeError;
 use crate::internal_prelude::*;
 use crate::kernel::kernel_api::KernelApi;
 use radix_common::constants::*;
+use radix_common::data::scrypto::well_known_scrypto_custom_types::ACCESS_RULE_NODE_TYPE;
 use radix_engine_interface::blueprints::resource::{
     FUNGIBLE_BUCKET_BLUEPRINT, FUNGIBLE_PROOF_BLUEPRINT, NON_FUNGIBLE_BUCKET_BLUEPRINT,
     NON_FUNGIBLE_PROOF_BLUEPRINT,
 };
 use sbor::rust::prelude::*;
-use sbor::traversal::TerminalValueRef;
+use sbor::traversal::{
+    traverse_payload_with_types, ContainerHeader, TerminalValueRef, TypedTraversalEvent,
+};
 
 use super::system::SystemService;
 use super::system_callback::System;
@@ -300,6 +303,70 @@ fn apply_custom_validation_to_custom_value<E: ToString>(
     Ok(())
 }
 
+/// Validates a payload against a schema, additionally rejecting the `TimeCondition` variant of the
+/// well-known access rule node type unless `allow_time_conditions` is set.
+///
+/// The variant was added to the well-known type in Cuttlefish, so before `SystemVersion::V2`
+/// payloads containing it must fail validation, as they did before the variant existed.
+pub fn validate_payload_against_schema_and_system_version<'s, 'a, E: ToString>(
+    payload: &[u8],
+    schema: &'s Schema<ScryptoCustomSchema>,
+    type_id: LocalTypeId,
+    context: &Lookup<'a, E>,
+    depth_limit: usize,
+    allow_time_conditions: bool,
+) -> Result<(), LocatedValidationError<'s, ScryptoCustomExtension>> {
+    if allow_time_conditions {
+        return validate_payload_against_schema::<ScryptoCustomExtension, _>(
+            payload,
+            schema,
+            type_id,
+            context,
+            depth_limit,
+        );
+    }
+
+    let mut traverser = traverse_payload_with_types::<ScryptoCustomExtension>(
+        payload,
+        schema,
+        type_id,
+        depth_limit,
+    );
+    loop {
+        let typed_event = traverser.next_event();
+        let is_end = reject_access_rule_time_condition(&typed_event.event)
+            .and_then(|_| validate_event_with_type(schema, &typed_event.event, context))
+            .map_err(|error| LocatedValidationError {
+                error,
+                location: typed_event.full_location(),
+            })?;
+        if is_end {
+            return Ok(());
+        }
+    }
+}
+
+fn reject_access_rule_time_condition(
+    event: &TypedTraversalEvent<ScryptoCustomExtension>,
+) -> Result<(), PayloadValidationError<ScryptoCustomExtension>> {
+    match event {
+        TypedTraversalEvent::ContainerStart(type_id, ContainerHeader::EnumVariant(header))
+            if *type_id == LocalTypeId::WellKnown(ACCESS_RULE_NODE_TYPE)
+                && header.variant == ACCESS_RULE_NODE_TIME_CONDITION_VARIANT =>
+        {
+            Err(PayloadValidationError::ValidationError(
+                ValidationError::CustomError(format!(
+                    "Access rule time conditions are not supported before Cuttlefish"
+                )),
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// The discriminator of `AccessRuleNode::TimeCondition` in the well-known access rule node type.
+const ACCESS_RULE_NODE_TIME_CONDITION_VARIANT: u8 = 3;
+
 fn resolve_type_info<E: ToString>(
     node_id: &NodeId,
     lookup: &Lookup<E>,
@@ -92,6 +92,41 @@ pub type SystemBootSubstate = SystemBoot;
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
 pub enum SystemBoot {
     V1(SystemParameters),
+    V2(SystemVersion, SystemParameters),
+}
+
+impl SystemBoot {
+    pub fn system_version(&self) -> SystemVersion {
+        match self {
+            SystemBoot::V1(..) => SystemVersion::V1,
+            SystemBoot::V2(system_version, ..) => *system_version,
+        }
+    }
+
+    pub fn into_parameters(self) -> SystemParameters {
+        match self {
+            SystemBoot::V1(system_parameters) | SystemBoot::V2(_, system_parameters) => {
+                system_parameters
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ScryptoSbor)]
+pub enum SystemVersion {
+    /// * Introduced: Babylon
+    /// * Changes   : N/A
+    V1,
+
+    /// * Introduced: Cuttlefish
+    /// * Changes   : Evaluate time conditions of access rules in the auth module
+    V2,
+}
+
+impl SystemVersion {
+    pub fn supports_time_conditions(&self) -> bool {
+        *self >= Self::V2
+    }
 }
 
 #[derive(Clone)]
@@ -148,6 +183,7 @@ pub struct SystemInit<C> {
     pub enable_kernel_trace: bool,
     pub enable_cost_breakdown: bool,
     pub execution_trace: Option<usize>,
+    pub enable_auth_diagnostics: bool,
 
     // Higher layer initialization object
     pub callback_init: C,
@@ -745,7 +781,7 @@ impl<C: SystemCallbackObject> KernelCallbackObject for System<C> {
             Self::print_executable(&executable);
         }
 
-        let mut system_parameters = {
+        let (system_version, mut system_parameters) = {
             let system_boot = store
                 .read_boot_substate(
                     TRANSACTION_TRACKER.as_node_id(),
@@ -760,9 +796,7 @@ impl<C: SystemCallbackObject> KernelCallbackObject for System<C> {
                     limit_parameters: LimitParameters::babylon_genesis(),
                 }));
 
-            match system_boot {
-                SystemBoot::V1(system_parameters) => system_parameters,
-            }
+            (system_boot.system_version(), system_boot.into_parameters())
         };
 
         let callback =
@@ -817,7 +851,9 @@ impl<C: SystemCallbackObject> KernelCallbackObject for System<C> {
             executable.intent_hash().to_hash(),
         );
 
-        let auth_module = AuthModule::new(executable.auth_zone_params().clone());
+        let auth_module = AuthModule::new(executable.auth_zone_params().clone())
+            .with_diagnostics(init_input.enable_auth_diagnostics)
+            .with_time_conditions(system_version.supports_time_conditions());
         let limits_module = { LimitsModule::from_params(system_parameters.limit_parameters) };
 
         let costing_module = CostingModule {
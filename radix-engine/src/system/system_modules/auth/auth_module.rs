@@ -1,4 +1,4 @@
-use super::Authorization;
+use super::{AccessRuleSource, Authorization, AuthorizationDiagnostics};
 use crate::blueprints::package::PackageAuthNativeBlueprint;
 use crate::blueprints::resource::AuthZone;
 use crate::errors::*;
@@ -12,6 +12,7 @@ use crate::system::node_init::type_info_partition;
 use crate::system::system::SystemService;
 use crate::system::system_callback::{System, SystemLockData};
 use crate::system::system_callback_api::SystemCallbackObject;
+use crate::system::system_modules::EnabledModules;
 use crate::system::type_info::TypeInfoSubstate;
 use radix_engine_interface::api::{AttachedModuleId, ClientBlueprintApi, LockFlags, ModuleId};
 use radix_engine_interface::blueprints::package::{
@@ -30,6 +31,7 @@ pub enum AuthError {
     Unauthorized(Box<Unauthorized>),
     InnerBlueprintDoesNotExist(String),
     InvalidOuterObjectMapping,
+    TimeConditionsNotSupported(TimeCondition),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
@@ -42,11 +44,16 @@ pub enum FailedAccessRules {
 pub struct Unauthorized {
     pub failed_access_rules: FailedAccessRules,
     pub fn_identifier: FnIdentifier,
+    /// Available if `ExecutionConfig::enable_auth_diagnostics` is enabled
+    pub diagnostics: Option<AuthorizationDiagnostics>,
 }
 
 #[derive(Debug, Clone)]
 pub struct AuthModule {
     pub params: AuthZoneParams,
+    pub enable_diagnostics: bool,
+    /// Whether time conditions of access rules can be evaluated, as of `SystemVersion::V2`
+    pub enable_time_conditions: bool,
 }
 
 pub enum AuthorizationCheckResult {
@@ -65,13 +72,30 @@ pub enum ResolvedPermission {
         module_id: ModuleId,
         role_list: RoleList,
     },
-    AccessRule(AccessRule),
+    AccessRule {
+        access_rule: AccessRule,
+        source: AccessRuleSource,
+    },
     AllowAll,
 }
 
 impl AuthModule {
     pub fn new(params: AuthZoneParams) -> Self {
-        Self { params }
+        Self {
+            params,
+            enable_diagnostics: false,
+            enable_time_conditions: false,
+        }
+    }
+
+    pub fn with_diagnostics(mut self, enabled: bool) -> Self {
+        self.enable_diagnostics = enabled;
+        self
+    }
+
+    pub fn with_time_conditions(mut self, enabled: bool) -> Self {
+        self.enable_time_conditions = enabled;
+        self
     }
 
     pub fn on_call_function<V, Y>(
@@ -403,24 +427,52 @@ impl AuthModule {
         fn_identifier: FnIdentifier,
         api: &mut SystemService<Y, V>,
     ) -> Result<(), RuntimeError> {
+        let enable_time_conditions = api.kernel_get_system().modules.auth.enable_time_conditions;
         match resolved_permission {
             ResolvedPermission::AllowAll => return Ok(()),
-            ResolvedPermission::AccessRule(rule) => {
-                let result =
-                    Authorization::check_authorization_against_access_rule(api, &auth_zone, &rule)?;
+            ResolvedPermission::AccessRule {
+                access_rule,
+                source,
+            } => {
+                let result = Authorization::check_authorization_against_access_rule(
+                    api,
+                    &auth_zone,
+                    &access_rule,
+                    enable_time_conditions,
+                )?;
 
                 match result {
                     AuthorizationCheckResult::Authorized => Ok(()),
-                    AuthorizationCheckResult::Failed(access_rule_stack) => Err(
-                        RuntimeError::SystemModuleError(SystemModuleError::AuthError(
-                            AuthError::Unauthorized(Box::new(Unauthorized {
-                                failed_access_rules: FailedAccessRules::AccessRule(
-                                    access_rule_stack,
-                                ),
-                                fn_identifier,
-                            })),
-                        )),
-                    ),
+                    AuthorizationCheckResult::Failed(access_rule_stack) => {
+                        let diagnostics = Self::collect_diagnostics(api, |api| {
+                            let failed_access_rules = vec![Authorization::diagnose_access_rule(
+                                auth_zone,
+                                None,
+                                source,
+                                &access_rule,
+                                enable_time_conditions,
+                                api,
+                            )?];
+                            Ok(AuthorizationDiagnostics {
+                                failed_access_rules,
+                                auth_zones: Authorization::diagnose_auth_zone_stack(
+                                    auth_zone, api,
+                                )?,
+                            })
+                        });
+
+                        Err(RuntimeError::SystemModuleError(
+                            SystemModuleError::AuthError(AuthError::Unauthorized(Box::new(
+                                Unauthorized {
+                                    failed_access_rules: FailedAccessRules::AccessRule(
+                                        access_rule_stack,
+                                    ),
+                                    fn_identifier,
+                                    diagnostics,
+                                },
+                            ))),
+                        ))
+                    }
                 }
             }
             ResolvedPermission::RoleList {
@@ -433,24 +485,72 @@ impl AuthModule {
                     &role_assignment_of,
                     module_id,
                     &role_list,
+                    enable_time_conditions,
                     api,
                 )?;
 
                 match result {
                     AuthorityListAuthorizationResult::Authorized => Ok(()),
-                    AuthorityListAuthorizationResult::Failed(auth_list_fail) => Err(
-                        RuntimeError::SystemModuleError(SystemModuleError::AuthError(
-                            AuthError::Unauthorized(Box::new(Unauthorized {
-                                failed_access_rules: FailedAccessRules::RoleList(auth_list_fail),
-                                fn_identifier,
-                            })),
-                        )),
-                    ),
+                    AuthorityListAuthorizationResult::Failed(auth_list_fail) => {
+                        let diagnostics = Self::collect_diagnostics(api, |api| {
+                            Ok(AuthorizationDiagnostics {
+                                failed_access_rules: Authorization::diagnose_role_list(
+                                    auth_zone,
+                                    &role_assignment_of,
+                                    module_id,
+                                    &role_list,
+                                    enable_time_conditions,
+                                    api,
+                                )?,
+                                auth_zones: Authorization::diagnose_auth_zone_stack(
+                                    auth_zone, api,
+                                )?,
+                            })
+                        });
+
+                        Err(RuntimeError::SystemModuleError(
+                            SystemModuleError::AuthError(AuthError::Unauthorized(Box::new(
+                                Unauthorized {
+                                    failed_access_rules: FailedAccessRules::RoleList(
+                                        auth_list_fail,
+                                    ),
+                                    fn_identifier,
+                                    diagnostics,
+                                },
+                            ))),
+                        ))
+                    }
                 }
             }
         }
     }
 
+    /// Collects the diagnostics of a failed authorization check, if they are enabled.
+    ///
+    /// This is done with costing and limits suspended, so that the failed transaction is charged
+    /// the same fee, and fails with the same error, whether or not diagnostics are enabled. For
+    /// the same reason, diagnostics are best-effort: if collecting them fails, they are omitted.
+    fn collect_diagnostics<Y: KernelApi<System<V>>, V: SystemCallbackObject>(
+        api: &mut SystemService<Y, V>,
+        diagnose: impl FnOnce(
+            &mut SystemService<Y, V>,
+        ) -> Result<AuthorizationDiagnostics, RuntimeError>,
+    ) -> Option<AuthorizationDiagnostics> {
+        if !api.kernel_get_system().modules.auth.enable_diagnostics {
+            return None;
+        }
+
+        let enabled_modules = api.kernel_get_system().modules.enabled_modules;
+        api.kernel_get_system()
+            .modules
+            .enabled_modules
+            .remove(EnabledModules::COSTING | EnabledModules::LIMITS);
+        let diagnostics = diagnose(api);
+        api.kernel_get_system().modules.enabled_modules = enabled_modules;
+
+        diagnostics.ok()
+    }
+
     fn resolve_method_permission<Y: KernelApi<System<V>>, V: SystemCallbackObject>(
         api: &mut SystemService<Y, V>,
         blueprint_id: &BlueprintId,
@@ -499,18 +599,20 @@ impl AuthModule {
             Some(MethodAccessibility::Public) => Ok(ResolvedPermission::AllowAll),
             Some(MethodAccessibility::OwnPackageOnly) => {
                 let package = blueprint_id.package_address;
-                Ok(ResolvedPermission::AccessRule(rule!(require(
-                    package_of_direct_caller(package)
-                ))))
+                Ok(ResolvedPermission::AccessRule {
+                    access_rule: rule!(require(package_of_direct_caller(package))),
+                    source: AccessRuleSource::OwnPackageOnly,
+                })
             }
             Some(MethodAccessibility::OuterObjectOnly) => match module_id {
                 ModuleId::Main => {
                     let outer_object_info = &receiver_object_info.blueprint_info.outer_obj_info;
                     match outer_object_info {
                         OuterObjectInfo::Some { outer_object } => {
-                            Ok(ResolvedPermission::AccessRule(rule!(require(
-                                global_caller(*outer_object)
-                            ))))
+                            Ok(ResolvedPermission::AccessRule {
+                                access_rule: rule!(require(global_caller(*outer_object))),
+                                source: AccessRuleSource::OuterObjectOnly,
+                            })
                         }
                         OuterObjectInfo::None { .. } => Err(RuntimeError::SystemModuleError(
                             SystemModuleError::AuthError(AuthError::InvalidOuterObjectMapping),
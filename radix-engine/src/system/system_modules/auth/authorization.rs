@@ -1,12 +1,18 @@
+use crate::blueprints::consensus_manager::{
+    ConsensusManagerBlueprint, ConsensusManagerField,
+    ConsensusManagerProposerMinuteTimestampFieldPayload, ConsensusManagerStateFieldPayload,
+};
 use crate::blueprints::resource::AuthZone;
-use crate::errors::RuntimeError;
+use crate::errors::{RuntimeError, SystemModuleError};
 use crate::internal_prelude::*;
 use crate::kernel::kernel_api::KernelSubstateApi;
 use crate::object_modules::role_assignment::{
     RoleAssignmentAccessRuleEntryPayload, RoleAssignmentOwnerFieldPayload,
 };
 use crate::system::system_modules::auth::{
-    AuthorityListAuthorizationResult, AuthorizationCheckResult,
+    AccessRuleDiagnostics, AccessRuleSource, AuthError, AuthZoneDiagnostics, AuthZoneOrigin,
+    AuthorityListAuthorizationResult, AuthorizationCheckResult, FailedProofRule, ProofDiagnostics,
+    UnmetRequirement,
 };
 use crate::system::system_substates::FieldSubstate;
 use crate::system::system_substates::KeyValueEntrySubstate;
@@ -14,6 +20,7 @@ use num_traits::Zero;
 use radix_engine_interface::api::{ClientObjectApi, LockFlags, ModuleId};
 use radix_engine_interface::blueprints::resource::*;
 use radix_native_sdk::resource::{NativeNonFungibleProof, NativeProof};
+use sbor::rust::cell::Cell;
 use sbor::rust::ops::Fn;
 
 pub struct Authorization;
@@ -288,9 +295,75 @@ impl Authorization {
         }
     }
 
+    /// Evaluates the time condition against the consensus manager clock, i.e. the current epoch
+    /// or the proposer timestamp rounded down to the minute.
+    pub fn verify_time_condition<Y: KernelSubstateApi<L>, L: Default>(
+        time_condition: &TimeCondition,
+        enable_time_conditions: bool,
+        api: &mut Y,
+    ) -> Result<bool, RuntimeError> {
+        if !enable_time_conditions {
+            return Err(RuntimeError::SystemModuleError(
+                SystemModuleError::AuthError(AuthError::TimeConditionsNotSupported(
+                    *time_condition,
+                )),
+            ));
+        }
+
+        let satisfied = match time_condition {
+            TimeCondition::EpochAtLeast(epoch) => Self::read_current_epoch(api)? >= *epoch,
+            TimeCondition::EpochBefore(epoch) => Self::read_current_epoch(api)? < *epoch,
+            TimeCondition::TimeAtLeast(instant) => Self::read_current_time(api)? >= *instant,
+            TimeCondition::TimeBefore(instant) => Self::read_current_time(api)? < *instant,
+        };
+        Ok(satisfied)
+    }
+
+    fn read_current_epoch<Y: KernelSubstateApi<L>, L: Default>(
+        api: &mut Y,
+    ) -> Result<Epoch, RuntimeError> {
+        let handle = api.kernel_open_substate(
+            CONSENSUS_MANAGER.as_node_id(),
+            MAIN_BASE_PARTITION,
+            &ConsensusManagerField::State.into(),
+            LockFlags::read_only(),
+            L::default(),
+        )?;
+        let state: FieldSubstate<ConsensusManagerStateFieldPayload> =
+            api.kernel_read_substate(handle)?.as_typed().unwrap();
+        api.kernel_close_substate(handle)?;
+        Ok(state
+            .into_payload()
+            .fully_update_and_into_latest_version()
+            .epoch)
+    }
+
+    fn read_current_time<Y: KernelSubstateApi<L>, L: Default>(
+        api: &mut Y,
+    ) -> Result<Instant, RuntimeError> {
+        let handle = api.kernel_open_substate(
+            CONSENSUS_MANAGER.as_node_id(),
+            MAIN_BASE_PARTITION,
+            &ConsensusManagerField::ProposerMinuteTimestamp.into(),
+            LockFlags::read_only(),
+            L::default(),
+        )?;
+        let proposer_minute_timestamp: FieldSubstate<
+            ConsensusManagerProposerMinuteTimestampFieldPayload,
+        > = api.kernel_read_substate(handle)?.as_typed().unwrap();
+        api.kernel_close_substate(handle)?;
+        Ok(ConsensusManagerBlueprint::epoch_minute_to_instant(
+            proposer_minute_timestamp
+                .into_payload()
+                .fully_update_and_into_latest_version()
+                .epoch_minute,
+        ))
+    }
+
     pub fn verify_auth_rule<Y: KernelSubstateApi<L> + ClientObjectApi<RuntimeError>, L: Default>(
         auth_zone: &NodeId,
         auth_rule: &AccessRuleNode,
+        enable_time_conditions: bool,
         api: &mut Y,
     ) -> Result<AuthorizationCheckResult, RuntimeError> {
         match auth_rule {
@@ -301,9 +374,16 @@ impl Authorization {
                     Ok(AuthorizationCheckResult::Failed(vec![]))
                 }
             }
+            AccessRuleNode::TimeCondition(time_condition) => {
+                if Self::verify_time_condition(time_condition, enable_time_conditions, api)? {
+                    Ok(AuthorizationCheckResult::Authorized)
+                } else {
+                    Ok(AuthorizationCheckResult::Failed(vec![]))
+                }
+            }
             AccessRuleNode::AnyOf(rules) => {
                 for r in rules {
-                    let rtn = Self::verify_auth_rule(auth_zone, r, api)?;
+                    let rtn = Self::verify_auth_rule(auth_zone, r, enable_time_conditions, api)?;
                     if matches!(rtn, AuthorizationCheckResult::Authorized) {
                         return Ok(rtn);
                     }
@@ -312,7 +392,7 @@ impl Authorization {
             }
             AccessRuleNode::AllOf(rules) => {
                 for r in rules {
-                    let rtn = Self::verify_auth_rule(auth_zone, r, api)?;
+                    let rtn = Self::verify_auth_rule(auth_zone, r, enable_time_conditions, api)?;
                     if matches!(rtn, AuthorizationCheckResult::Failed(..)) {
                         return Ok(rtn);
                     }
@@ -330,54 +410,79 @@ impl Authorization {
         auth_zone: &NodeId,
         role_assignment_of: &GlobalAddress,
         key: &ModuleRoleKey,
+        enable_time_conditions: bool,
         api: &mut Y,
     ) -> Result<AuthorizationCheckResult, RuntimeError> {
-        let access_rule = if key.key.key.eq(SELF_ROLE) {
-            rule!(require(global_caller(role_assignment_of.clone())))
-        } else {
-            let handle = api.kernel_open_substate_with_default(
-                role_assignment_of.as_node_id(),
-                ROLE_ASSIGNMENT_BASE_PARTITION
-                    .at_offset(ROLE_ASSIGNMENT_ROLE_DEF_PARTITION_OFFSET)
-                    .unwrap(),
-                &SubstateKey::Map(scrypto_encode(&key).unwrap()),
-                LockFlags::read_only(),
-                Some(|| {
-                    let kv_entry = KeyValueEntrySubstate::<()>::default();
-                    IndexedScryptoValue::from_typed(&kv_entry)
-                }),
-                L::default(),
-            )?;
-            let substate: KeyValueEntrySubstate<RoleAssignmentAccessRuleEntryPayload> =
-                api.kernel_read_substate(handle)?.as_typed().unwrap();
-            api.kernel_close_substate(handle)?;
+        let (access_rule, _source) = Self::resolve_role_key_rule(role_assignment_of, key, api)?;
 
-            match substate.into_value() {
-                Some(access_rule) => access_rule.fully_update_and_into_latest_version(),
-                None => {
-                    let handle = api.kernel_open_substate(
-                        role_assignment_of.as_node_id(),
-                        ROLE_ASSIGNMENT_BASE_PARTITION
-                            .at_offset(ROLE_ASSIGNMENT_FIELDS_PARTITION_OFFSET)
-                            .unwrap(),
-                        &SubstateKey::Field(0u8),
-                        LockFlags::read_only(),
-                        L::default(),
-                    )?;
+        Self::check_authorization_against_access_rule(
+            api,
+            auth_zone,
+            &access_rule,
+            enable_time_conditions,
+        )
+    }
+
+    /// Resolves the access rule of a role, falling back to the owner role if the role has no
+    /// rule assigned.
+    pub fn resolve_role_key_rule<Y: KernelSubstateApi<L>, L: Default>(
+        role_assignment_of: &GlobalAddress,
+        key: &ModuleRoleKey,
+        api: &mut Y,
+    ) -> Result<(AccessRule, AccessRuleSource), RuntimeError> {
+        if key.key.key.eq(SELF_ROLE) {
+            return Ok((
+                rule!(require(global_caller(role_assignment_of.clone()))),
+                AccessRuleSource::SelfRole(*role_assignment_of),
+            ));
+        }
+
+        let handle = api.kernel_open_substate_with_default(
+            role_assignment_of.as_node_id(),
+            ROLE_ASSIGNMENT_BASE_PARTITION
+                .at_offset(ROLE_ASSIGNMENT_ROLE_DEF_PARTITION_OFFSET)
+                .unwrap(),
+            &SubstateKey::Map(scrypto_encode(&key).unwrap()),
+            LockFlags::read_only(),
+            Some(|| {
+                let kv_entry = KeyValueEntrySubstate::<()>::default();
+                IndexedScryptoValue::from_typed(&kv_entry)
+            }),
+            L::default(),
+        )?;
+        let substate: KeyValueEntrySubstate<RoleAssignmentAccessRuleEntryPayload> =
+            api.kernel_read_substate(handle)?.as_typed().unwrap();
+        api.kernel_close_substate(handle)?;
 
-                    let owner_role_substate: FieldSubstate<RoleAssignmentOwnerFieldPayload> =
-                        api.kernel_read_substate(handle)?.as_typed().unwrap();
-                    api.kernel_close_substate(handle)?;
+        match substate.into_value() {
+            Some(access_rule) => Ok((
+                access_rule.fully_update_and_into_latest_version(),
+                AccessRuleSource::RoleAssignment(*role_assignment_of, key.module),
+            )),
+            None => {
+                let handle = api.kernel_open_substate(
+                    role_assignment_of.as_node_id(),
+                    ROLE_ASSIGNMENT_BASE_PARTITION
+                        .at_offset(ROLE_ASSIGNMENT_FIELDS_PARTITION_OFFSET)
+                        .unwrap(),
+                    &SubstateKey::Field(0u8),
+                    LockFlags::read_only(),
+                    L::default(),
+                )?;
+
+                let owner_role_substate: FieldSubstate<RoleAssignmentOwnerFieldPayload> =
+                    api.kernel_read_substate(handle)?.as_typed().unwrap();
+                api.kernel_close_substate(handle)?;
+                Ok((
                     owner_role_substate
                         .into_payload()
                         .fully_update_and_into_latest_version()
                         .owner_role_entry
-                        .rule
-                }
+                        .rule,
+                    AccessRuleSource::OwnerRole(*role_assignment_of),
+                ))
             }
-        };
-
-        Self::check_authorization_against_access_rule(api, auth_zone, &access_rule)
+        }
     }
 
     pub fn check_authorization_against_access_rule<
@@ -387,10 +492,12 @@ impl Authorization {
         api: &mut Y,
         auth_zone: &NodeId,
         rule: &AccessRule,
+        enable_time_conditions: bool,
     ) -> Result<AuthorizationCheckResult, RuntimeError> {
         match rule {
             AccessRule::Protected(rule_node) => {
-                let mut rtn = Self::verify_auth_rule(auth_zone, rule_node, api)?;
+                let mut rtn =
+                    Self::verify_auth_rule(auth_zone, rule_node, enable_time_conditions, api)?;
                 match &mut rtn {
                     AuthorizationCheckResult::Authorized => {}
                     AuthorizationCheckResult::Failed(stack) => {
@@ -412,6 +519,7 @@ impl Authorization {
         role_assignment_of: &GlobalAddress,
         module: ModuleId,
         role_list: &RoleList,
+        enable_time_conditions: bool,
         api: &mut Y,
     ) -> Result<AuthorityListAuthorizationResult, RuntimeError> {
         let mut failed = Vec::new();
@@ -422,6 +530,7 @@ impl Authorization {
                 &auth_zone,
                 role_assignment_of,
                 &module_role_key,
+                enable_time_conditions,
                 api,
             )?;
             match result {
@@ -436,4 +545,335 @@ impl Authorization {
 
         Ok(AuthorityListAuthorizationResult::Failed(failed))
     }
+
+    /// Explains why the access rule isn't satisfied by the auth zone stack.
+    pub fn diagnose_access_rule<
+        Y: KernelSubstateApi<L> + ClientObjectApi<RuntimeError>,
+        L: Default,
+    >(
+        auth_zone: &NodeId,
+        role_key: Option<RoleKey>,
+        source: AccessRuleSource,
+        access_rule: &AccessRule,
+        enable_time_conditions: bool,
+        api: &mut Y,
+    ) -> Result<AccessRuleDiagnostics, RuntimeError> {
+        let mut failed_proof_rules = Vec::new();
+        let mut failed_time_conditions = Vec::new();
+        if let AccessRule::Protected(rule_node) = access_rule {
+            Self::diagnose_auth_rule(
+                auth_zone,
+                rule_node,
+                &mut failed_proof_rules,
+                &mut failed_time_conditions,
+                enable_time_conditions,
+                api,
+            )?;
+        }
+
+        Ok(AccessRuleDiagnostics {
+            role_key,
+            source,
+            access_rule: access_rule.clone(),
+            failed_proof_rules,
+            failed_time_conditions,
+        })
+    }
+
+    /// Explains why none of the roles in the role list are satisfied by the auth zone stack.
+    pub fn diagnose_role_list<
+        Y: KernelSubstateApi<L> + ClientObjectApi<RuntimeError>,
+        L: Default,
+    >(
+        auth_zone: &NodeId,
+        role_assignment_of: &GlobalAddress,
+        module: ModuleId,
+        role_list: &RoleList,
+        enable_time_conditions: bool,
+        api: &mut Y,
+    ) -> Result<Vec<AccessRuleDiagnostics>, RuntimeError> {
+        let mut diagnostics = Vec::new();
+        for key in &role_list.list {
+            let module_role_key = ModuleRoleKey::new(module, key.key.as_str());
+            let (access_rule, source) =
+                Self::resolve_role_key_rule(role_assignment_of, &module_role_key, api)?;
+            diagnostics.push(Self::diagnose_access_rule(
+                auth_zone,
+                Some(key.clone()),
+                source,
+                &access_rule,
+                enable_time_conditions,
+                api,
+            )?);
+        }
+        Ok(diagnostics)
+    }
+
+    /// Describes the auth zones which are checked against, in the same order as in
+    /// [`Self::verify_proof_rule`].
+    pub fn diagnose_auth_zone_stack<
+        Y: KernelSubstateApi<L> + ClientObjectApi<RuntimeError>,
+        L: Default,
+    >(
+        auth_zone: &NodeId,
+        api: &mut Y,
+    ) -> Result<Vec<AuthZoneDiagnostics>, RuntimeError> {
+        let mut diagnostics = Vec::new();
+        // The auth zone is kept open, so that the auth zones it references stay visible
+        let (auth_zone, handle) = Self::open_auth_zone(auth_zone, api)?;
+
+        let virtual_non_fungibles = auth_zone.local_virtual_non_fungibles();
+        if !virtual_non_fungibles.is_empty() {
+            diagnostics.push(AuthZoneDiagnostics {
+                origin: AuthZoneOrigin::CallerBadges,
+                proofs: vec![],
+                virtual_resources: btreeset!(),
+                virtual_non_fungibles,
+            });
+        }
+
+        if let Some((global_caller, global_caller_reference)) = &auth_zone.global_caller {
+            Self::diagnose_global_auth_zone(
+                &global_caller_reference.0,
+                AuthZoneOrigin::GlobalCaller(global_caller.clone()),
+                &mut diagnostics,
+                api,
+            )?;
+        }
+
+        if let Some(parent) = auth_zone.parent {
+            Self::diagnose_global_auth_zone(
+                &parent.0,
+                AuthZoneOrigin::CurrentCaller,
+                &mut diagnostics,
+                api,
+            )?;
+        }
+
+        api.kernel_close_substate(handle)?;
+
+        Ok(diagnostics)
+    }
+
+    fn diagnose_global_auth_zone<
+        Y: KernelSubstateApi<L> + ClientObjectApi<RuntimeError>,
+        L: Default,
+    >(
+        auth_zone_id: &NodeId,
+        origin: AuthZoneOrigin,
+        diagnostics: &mut Vec<AuthZoneDiagnostics>,
+        api: &mut Y,
+    ) -> Result<(), RuntimeError> {
+        let mut current_auth_zone_id = *auth_zone_id;
+        // As in `global_auth_zone_matches`, the auth zones are kept open until the whole stack
+        // is read, so that their proofs and parents stay visible
+        let mut handles = Vec::new();
+        loop {
+            let (auth_zone, handle) = Self::open_auth_zone(&current_auth_zone_id, api)?;
+            handles.push(handle);
+
+            let mut proofs = Vec::new();
+            for proof in auth_zone.proofs() {
+                let resource_address = proof.resource_address(api)?;
+                let non_fungible_local_ids = if resource_address.is_fungible() {
+                    None
+                } else {
+                    Some(proof.non_fungible_local_ids(api)?)
+                };
+                proofs.push(ProofDiagnostics {
+                    resource_address,
+                    amount: proof.amount(api)?,
+                    non_fungible_local_ids,
+                });
+            }
+            diagnostics.push(AuthZoneDiagnostics {
+                origin: origin.clone(),
+                proofs,
+                virtual_resources: auth_zone.virtual_resources().clone(),
+                virtual_non_fungibles: auth_zone.virtual_non_fungibles().clone(),
+            });
+
+            match auth_zone.parent {
+                Some(parent) => current_auth_zone_id = parent.0,
+                None => break,
+            }
+        }
+
+        for handle in handles {
+            api.kernel_close_substate(handle)?;
+        }
+
+        Ok(())
+    }
+
+    fn open_auth_zone<Y: KernelSubstateApi<L>, L: Default>(
+        auth_zone_id: &NodeId,
+        api: &mut Y,
+    ) -> Result<(AuthZone, SubstateHandle), RuntimeError> {
+        let handle = api.kernel_open_substate(
+            auth_zone_id,
+            MAIN_BASE_PARTITION,
+            &AuthZoneField::AuthZone.into(),
+            LockFlags::read_only(),
+            L::default(),
+        )?;
+        let auth_zone = api
+            .kernel_read_substate(handle)?
+            .as_typed::<FieldSubstate<AuthZone>>()
+            .unwrap()
+            .into_payload();
+        Ok((auth_zone, handle))
+    }
+
+    /// Collects the failed proof rules and time conditions of the rule node, returning whether
+    /// it is satisfied. Time conditions which can't be evaluated yet are reported as failed.
+    fn diagnose_auth_rule<Y: KernelSubstateApi<L> + ClientObjectApi<RuntimeError>, L: Default>(
+        auth_zone: &NodeId,
+        auth_rule: &AccessRuleNode,
+        failed_proof_rules: &mut Vec<FailedProofRule>,
+        failed_time_conditions: &mut Vec<TimeCondition>,
+        enable_time_conditions: bool,
+        api: &mut Y,
+    ) -> Result<bool, RuntimeError> {
+        match auth_rule {
+            AccessRuleNode::ProofRule(rule) => {
+                let unmet_requirements = Self::diagnose_proof_rule(auth_zone, rule, api)?;
+                match unmet_requirements {
+                    None => Ok(true),
+                    Some(unmet_requirements) => {
+                        failed_proof_rules.push(FailedProofRule {
+                            proof_rule: rule.clone(),
+                            unmet_requirements,
+                        });
+                        Ok(false)
+                    }
+                }
+            }
+            AccessRuleNode::TimeCondition(time_condition) => {
+                let satisfied = enable_time_conditions
+                    && Self::verify_time_condition(time_condition, enable_time_conditions, api)?;
+                if !satisfied {
+                    failed_time_conditions.push(*time_condition);
+                }
+                Ok(satisfied)
+            }
+            AccessRuleNode::AnyOf(rules) => {
+                let mut failed = Vec::new();
+                let mut failed_conditions = Vec::new();
+                for r in rules {
+                    if Self::diagnose_auth_rule(
+                        auth_zone,
+                        r,
+                        &mut failed,
+                        &mut failed_conditions,
+                        enable_time_conditions,
+                        api,
+                    )? {
+                        return Ok(true);
+                    }
+                }
+                failed_proof_rules.extend(failed);
+                failed_time_conditions.extend(failed_conditions);
+                Ok(false)
+            }
+            AccessRuleNode::AllOf(rules) => {
+                let mut pass = true;
+                for r in rules {
+                    pass &= Self::diagnose_auth_rule(
+                        auth_zone,
+                        r,
+                        failed_proof_rules,
+                        failed_time_conditions,
+                        enable_time_conditions,
+                        api,
+                    )?;
+                }
+                Ok(pass)
+            }
+        }
+    }
+
+    /// Returns the unmet requirements of the proof rule, or `None` if it is satisfied.
+    fn diagnose_proof_rule<Y: KernelSubstateApi<L> + ClientObjectApi<RuntimeError>, L: Default>(
+        auth_zone: &NodeId,
+        proof_rule: &ProofRule,
+        api: &mut Y,
+    ) -> Result<Option<Vec<UnmetRequirement>>, RuntimeError> {
+        let missing_proofs = |resources: &[ResourceOrNonFungible],
+                              api: &mut Y|
+         -> Result<Vec<UnmetRequirement>, RuntimeError> {
+            let mut missing = Vec::new();
+            for resource in resources {
+                if !Self::auth_zone_stack_matches_rule(auth_zone, resource, api)? {
+                    missing.push(UnmetRequirement::MissingProof(resource.clone()));
+                }
+            }
+            Ok(missing)
+        };
+
+        let unmet_requirements = match proof_rule {
+            ProofRule::Require(resource) => {
+                let missing = missing_proofs(core::slice::from_ref(resource), api)?;
+                (!missing.is_empty()).then_some(missing)
+            }
+            ProofRule::AmountOf(amount, resource) => {
+                if Self::auth_zone_stack_has_amount(auth_zone, resource, *amount, api)? {
+                    None
+                } else {
+                    Some(vec![UnmetRequirement::InsufficientAmount {
+                        resource_address: *resource,
+                        required_amount: *amount,
+                        largest_proof_amount: Self::auth_zone_stack_largest_amount(
+                            auth_zone, resource, api,
+                        )?,
+                    }])
+                }
+            }
+            ProofRule::AllOf(resources) => {
+                let missing = missing_proofs(resources, api)?;
+                (!missing.is_empty()).then_some(missing)
+            }
+            ProofRule::AnyOf(resources) => {
+                let missing = missing_proofs(resources, api)?;
+                (missing.len() == resources.len()).then_some(missing)
+            }
+            ProofRule::CountOf(count, resources) => {
+                let missing = missing_proofs(resources, api)?;
+                let proven_count = resources.len() - missing.len();
+                if proven_count >= *count as usize {
+                    None
+                } else {
+                    let mut unmet_requirements = vec![UnmetRequirement::InsufficientCount {
+                        required_count: *count,
+                        proven_count: proven_count as u8,
+                    }];
+                    unmet_requirements.extend(missing);
+                    Some(unmet_requirements)
+                }
+            }
+        };
+
+        Ok(unmet_requirements)
+    }
+
+    fn auth_zone_stack_largest_amount<
+        Y: KernelSubstateApi<L> + ClientObjectApi<RuntimeError>,
+        L: Default,
+    >(
+        auth_zone: &NodeId,
+        resource: &ResourceAddress,
+        api: &mut Y,
+    ) -> Result<Decimal, RuntimeError> {
+        let largest_amount = Cell::new(Decimal::ZERO);
+        Self::auth_zone_stack_matches(auth_zone, api, |proofs, _, _, api| {
+            for p in proofs {
+                if Self::proof_matches(&ResourceOrNonFungible::Resource(*resource), p, api)? {
+                    largest_amount.set(largest_amount.get().max(p.amount(api)?));
+                }
+            }
+
+            Ok(false)
+        })?;
+        Ok(largest_amount.get())
+    }
 }
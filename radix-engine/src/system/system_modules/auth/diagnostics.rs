@@ -0,0 +1,100 @@
+use crate::internal_prelude::*;
+use radix_engine_interface::api::ModuleId;
+use radix_engine_interface::blueprints::resource::*;
+
+/// An explanation of an [`Unauthorized`] error: which access rules were checked, why each of them
+/// failed, and what the checked auth zones contained.
+///
+/// It is only collected if enabled with `ExecutionConfig::enable_auth_diagnostics`, as collecting
+/// it requires reading the auth zones and proofs again.
+///
+/// [`Unauthorized`]: super::Unauthorized
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct AuthorizationDiagnostics {
+    pub failed_access_rules: Vec<AccessRuleDiagnostics>,
+    /// The auth zones checked against, in the order in which they were checked.
+    pub auth_zones: Vec<AuthZoneDiagnostics>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct AccessRuleDiagnostics {
+    /// The role which the access rule is assigned to, if it was resolved from a role.
+    pub role_key: Option<RoleKey>,
+    pub source: AccessRuleSource,
+    pub access_rule: AccessRule,
+    /// The proof rules of the access rule which weren't satisfied. It is empty for `DenyAll`.
+    pub failed_proof_rules: Vec<FailedProofRule>,
+    /// The time conditions of the access rule which weren't met by the consensus manager clock.
+    pub failed_time_conditions: Vec<TimeCondition>,
+}
+
+/// Where an access rule was resolved from.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum AccessRuleSource {
+    /// The access rule of a function, as defined by its blueprint.
+    FunctionAuth,
+    /// The method can only be called by the package of its blueprint.
+    OwnPackageOnly,
+    /// The method can only be called by the outer object.
+    OuterObjectOnly,
+    /// The rule assigned to the role in the role assignment module of the entity.
+    RoleAssignment(GlobalAddress, ModuleId),
+    /// The role has no rule assigned, so the owner role of the entity applies.
+    OwnerRole(GlobalAddress),
+    /// The reserved `_self_` role, which requires the entity itself to be the global caller.
+    SelfRole(GlobalAddress),
+    /// The owner role of the entity is being updated or locked, as permitted by its updater.
+    OwnerRoleUpdater(GlobalAddress, OwnerRoleUpdater),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct FailedProofRule {
+    pub proof_rule: ProofRule,
+    pub unmet_requirements: Vec<UnmetRequirement>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum UnmetRequirement {
+    /// None of the checked auth zones had a proof of the resource or non-fungible.
+    MissingProof(ResourceOrNonFungible),
+    /// No single proof of the resource had the required amount.
+    InsufficientAmount {
+        resource_address: ResourceAddress,
+        required_amount: Decimal,
+        largest_proof_amount: Decimal,
+    },
+    /// Fewer of the listed resources or non-fungibles were proven than required.
+    InsufficientCount {
+        required_count: u8,
+        proven_count: u8,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct AuthZoneDiagnostics {
+    pub origin: AuthZoneOrigin,
+    pub proofs: Vec<ProofDiagnostics>,
+    /// Virtual resources, any non-fungible of which is considered proven.
+    pub virtual_resources: BTreeSet<ResourceAddress>,
+    /// Virtual proofs, such as signature proofs and caller badges.
+    pub virtual_non_fungibles: BTreeSet<NonFungibleGlobalId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum AuthZoneOrigin {
+    /// The caller badges of the call itself, i.e. the package of the direct caller and the global
+    /// caller.
+    CallerBadges,
+    /// The auth zone of the global caller, or one of its parents.
+    GlobalCaller(GlobalCaller),
+    /// The auth zone of the current caller, or one of its parents.
+    CurrentCaller,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct ProofDiagnostics {
+    pub resource_address: ResourceAddress,
+    pub amount: Decimal,
+    /// The proven non-fungibles, for proofs of non-fungible resources.
+    pub non_fungible_local_ids: Option<IndexSet<NonFungibleLocalId>>,
+}
@@ -1,5 +1,7 @@
 mod auth_module;
 mod authorization;
+mod diagnostics;
 
 pub use auth_module::*;
 pub use authorization::*;
+pub use diagnostics::*;
@@ -608,6 +608,12 @@ impl SystemModuleMixer {
         }
     }
 
+    /// Whether access rule time conditions are supported by the system version, regardless of
+    /// whether the auth module is enabled.
+    pub fn time_conditions_enabled(&self) -> bool {
+        self.auth.enable_time_conditions
+    }
+
     pub fn generate_ruid(&mut self) -> Option<[u8; 32]> {
         if self
             .enabled_modules
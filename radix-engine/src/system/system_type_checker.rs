@@ -360,6 +360,12 @@ where
         allow_non_global_ref: bool,
         depth_limit: usize,
     ) -> Result<(), LocatedValidationError<'s, ScryptoCustomExtension>> {
+        let allow_time_conditions = self
+            .api
+            .kernel_get_system_state()
+            .system
+            .modules
+            .time_conditions_enabled();
         let validation_context: Box<dyn ValidationContext<Error = RuntimeError>> =
             Box::new(SystemServiceTypeInfoLookup::new(
                 self,
@@ -367,12 +373,13 @@ where
                 allow_ownership,
                 allow_non_global_ref,
             ));
-        validate_payload_against_schema::<ScryptoCustomExtension, _>(
+        validate_payload_against_schema_and_system_version(
             payload,
             schema.v1(),
             type_id,
             &validation_context,
             depth_limit,
+            allow_time_conditions,
         )
     }
 
@@ -159,6 +159,9 @@ pub struct ExecutionConfig {
     pub enable_kernel_trace: bool,
     pub enable_cost_breakdown: bool,
     pub execution_trace: Option<usize>,
+    /// Explains auth failures in `AuthError::Unauthorized`. It only affects the cost of failed
+    /// transactions.
+    pub enable_auth_diagnostics: bool,
 
     pub system_overrides: Option<SystemOverrides>,
 }
@@ -169,6 +172,7 @@ impl Default for ExecutionConfig {
             enable_kernel_trace: false,
             enable_cost_breakdown: false,
             execution_trace: None,
+            enable_auth_diagnostics: false,
             system_overrides: None,
         }
     }
@@ -219,6 +223,7 @@ impl ExecutionConfig {
         Self {
             enable_kernel_trace: true,
             enable_cost_breakdown: true,
+            enable_auth_diagnostics: true,
             ..Self::with_network(NetworkDefinition::simulator())
         }
     }
@@ -227,6 +232,7 @@ impl ExecutionConfig {
         Self {
             enable_cost_breakdown: true,
             execution_trace: Some(MAX_EXECUTION_TRACE_DEPTH),
+            enable_auth_diagnostics: true,
             ..Self::with_network(network_definition)
         }
     }
@@ -253,6 +259,11 @@ impl ExecutionConfig {
         self.enable_cost_breakdown = enabled;
         self
     }
+
+    pub fn with_auth_diagnostics(mut self, enabled: bool) -> Self {
+        self.enable_auth_diagnostics = enabled;
+        self
+    }
 }
 
 pub struct SubstateBootStore<'a, S: SubstateDatabase> {
@@ -320,6 +331,7 @@ pub fn execute_transaction_with_configuration<S: SubstateDatabase, V: SystemCall
             enable_kernel_trace: execution_config.enable_kernel_trace,
             enable_cost_breakdown: execution_config.enable_cost_breakdown,
             execution_trace: execution_config.execution_trace,
+            enable_auth_diagnostics: execution_config.enable_auth_diagnostics,
             callback_init: vms,
             system_overrides: execution_config.system_overrides.clone(),
         },
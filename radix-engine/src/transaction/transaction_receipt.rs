@@ -4,6 +4,7 @@ use crate::errors::*;
 use crate::internal_prelude::*;
 use crate::kernel::kernel_callback_api::ExecutionReceipt;
 use crate::system::system_db_reader::SystemDatabaseReader;
+use crate::system::system_modules::auth::*;
 use crate::system::system_modules::costing::*;
 use crate::system::system_modules::execution_trace::*;
 use crate::system::system_substate_schemas::*;
@@ -796,6 +797,10 @@ impl<'a> ContextualDisplay<TransactionReceiptDisplayContext<'a>> for Transaction
             context.display_result(result),
         )?;
 
+        if let Some((fn_identifier, diagnostics)) = auth_diagnostics(result) {
+            display_auth_diagnostics(f, fn_identifier, diagnostics, context)?;
+        }
+
         context.format_top_level_title_with_detail(
             f,
             "Transaction Cost",
@@ -1039,6 +1044,202 @@ impl<'a> ContextualDisplay<TransactionReceiptDisplayContext<'a>> for Transaction
     }
 }
 
+fn auth_diagnostics(
+    result: &TransactionResult,
+) -> Option<(&FnIdentifier, &AuthorizationDiagnostics)> {
+    let error = match result {
+        TransactionResult::Commit(CommitResult {
+            outcome: TransactionOutcome::Failure(error),
+            ..
+        }) => error,
+        TransactionResult::Reject(RejectResult {
+            reason: RejectionReason::ErrorBeforeLoanAndDeferredCostsRepaid(error),
+        }) => error,
+        _ => return None,
+    };
+    match error {
+        RuntimeError::SystemModuleError(SystemModuleError::AuthError(AuthError::Unauthorized(
+            unauthorized,
+        ))) => unauthorized
+            .diagnostics
+            .as_ref()
+            .map(|diagnostics| (&unauthorized.fn_identifier, diagnostics)),
+        _ => None,
+    }
+}
+
+fn display_auth_diagnostics<'a, F: fmt::Write>(
+    f: &mut F,
+    fn_identifier: &FnIdentifier,
+    diagnostics: &AuthorizationDiagnostics,
+    context: &TransactionReceiptDisplayContext<'a>,
+) -> Result<(), fmt::Error> {
+    let address_display_context = context.address_display_context();
+
+    context.format_top_level_title_with_detail(
+        f,
+        "Authorization Failure",
+        fn_identifier.display(address_display_context),
+    )?;
+    if diagnostics.failed_access_rules.is_empty() {
+        write!(f, "\n└─ No role can call this method")?;
+    }
+    for (i, rule) in diagnostics.failed_access_rules.iter().enumerate() {
+        let indent = if i == diagnostics.failed_access_rules.len() - 1 {
+            "   "
+        } else {
+            "│  "
+        };
+        let source = match &rule.source {
+            AccessRuleSource::FunctionAuth => "function access rule".to_string(),
+            AccessRuleSource::OwnPackageOnly => "only callable by its own package".to_string(),
+            AccessRuleSource::OuterObjectOnly => "only callable by the outer object".to_string(),
+            AccessRuleSource::RoleAssignment(address, module_id) => format!(
+                "assigned in the {:?} module of {}",
+                module_id,
+                address.display(address_display_context)
+            ),
+            AccessRuleSource::OwnerRole(address) => format!(
+                "not assigned, using the owner role of {}",
+                address.display(address_display_context)
+            ),
+            AccessRuleSource::SelfRole(address) => format!(
+                "requires {} to be the global caller",
+                address.display(address_display_context)
+            ),
+            AccessRuleSource::OwnerRoleUpdater(address, updater) => format!(
+                "owner role updater {:?} of {}",
+                updater,
+                address.display(address_display_context)
+            ),
+        };
+        match &rule.role_key {
+            Some(role_key) => write!(
+                f,
+                "\n{} {} {:?} ({})",
+                prefix!(i, diagnostics.failed_access_rules),
+                context.display_title("Role:"),
+                role_key.key,
+                source
+            )?,
+            None => write!(
+                f,
+                "\n{} {} {}",
+                prefix!(i, diagnostics.failed_access_rules),
+                context.display_title("Rule:"),
+                source
+            )?,
+        }
+        write!(f, "\n{}Access Rule: {:?}", indent, rule.access_rule)?;
+        for failed_proof_rule in &rule.failed_proof_rules {
+            write!(f, "\n{}Failed: {:?}", indent, failed_proof_rule.proof_rule)?;
+            for requirement in &failed_proof_rule.unmet_requirements {
+                let requirement = match requirement {
+                    UnmetRequirement::MissingProof(ResourceOrNonFungible::Resource(address)) => {
+                        format!("no proof of {}", address.display(address_display_context))
+                    }
+                    UnmetRequirement::MissingProof(ResourceOrNonFungible::NonFungible(id)) => {
+                        format!("no proof of {}", id.display(address_display_context))
+                    }
+                    UnmetRequirement::InsufficientAmount {
+                        resource_address,
+                        required_amount,
+                        largest_proof_amount,
+                    } => format!(
+                        "needs a proof of {} {}, the largest is {}",
+                        required_amount,
+                        resource_address.display(address_display_context),
+                        largest_proof_amount
+                    ),
+                    UnmetRequirement::InsufficientCount {
+                        required_count,
+                        proven_count,
+                    } => format!("needs {} proven, only {} are", required_count, proven_count),
+                };
+                write!(f, "\n{}  - {}", indent, requirement)?;
+            }
+        }
+        for time_condition in &rule.failed_time_conditions {
+            write!(f, "\n{}Not met: {:?}", indent, time_condition)?;
+        }
+    }
+
+    context.format_top_level_title_with_detail(
+        f,
+        "Checked Auth Zones",
+        diagnostics.auth_zones.len(),
+    )?;
+    for (i, auth_zone) in diagnostics.auth_zones.iter().enumerate() {
+        let indent = if i == diagnostics.auth_zones.len() - 1 {
+            "   "
+        } else {
+            "│  "
+        };
+        let origin = match &auth_zone.origin {
+            AuthZoneOrigin::CallerBadges => "Caller badges".to_string(),
+            AuthZoneOrigin::GlobalCaller(GlobalCaller::GlobalObject(address)) => {
+                format!("Global caller {}", address.display(address_display_context))
+            }
+            AuthZoneOrigin::GlobalCaller(GlobalCaller::PackageBlueprint(blueprint_id)) => {
+                format!(
+                    "Global caller {}",
+                    blueprint_id.display(address_display_context)
+                )
+            }
+            AuthZoneOrigin::CurrentCaller => "Current caller".to_string(),
+        };
+        write!(
+            f,
+            "\n{} {}",
+            prefix!(i, diagnostics.auth_zones),
+            context.display_title(&origin)
+        )?;
+        if auth_zone.proofs.is_empty()
+            && auth_zone.virtual_resources.is_empty()
+            && auth_zone.virtual_non_fungibles.is_empty()
+        {
+            write!(f, "\n{}(empty)", indent)?;
+        }
+        for proof in &auth_zone.proofs {
+            write!(
+                f,
+                "\n{}Proof: {} {}",
+                indent,
+                proof.amount,
+                proof.resource_address.display(address_display_context)
+            )?;
+            if let Some(ids) = &proof.non_fungible_local_ids {
+                write!(
+                    f,
+                    " [{}]",
+                    ids.iter()
+                        .map(|id| id.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )?;
+            }
+        }
+        for resource_address in &auth_zone.virtual_resources {
+            write!(
+                f,
+                "\n{}Virtual Resource: {}",
+                indent,
+                resource_address.display(address_display_context)
+            )?;
+        }
+        for non_fungible_global_id in &auth_zone.virtual_non_fungibles {
+            write!(
+                f,
+                "\n{}Virtual Proof: {}",
+                indent,
+                non_fungible_global_id.display(address_display_context)
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
 fn display_substate_change<'a, F: fmt::Write>(
     f: &mut F,
     prefix: &str,
@@ -336,7 +336,7 @@ fn generate_locker_package_state_updates() -> StateUpdates {
         VmType::Native,
         (NativeCodeId::LockerCode1 as u64).to_be_bytes().to_vec(),
         Default::default(),
-        false,
+        PackageV1MinorVersion::Zero,
         &VmBoot::latest(),
     )
     .unwrap_or_else(|err| {
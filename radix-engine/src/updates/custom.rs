@@ -0,0 +1,103 @@
+use super::*;
+use crate::track::StateUpdates;
+
+/// A protocol update defined outside of this crate, e.g. by a private network or a test.
+///
+/// It is registered with [`ProtocolBuilder::with_custom_update`] and enacted directly after the
+/// built-in protocol update which brings the ledger to `enact_after` (or directly after genesis
+/// for [`ProtocolVersion::Babylon`]). Custom updates enacted after the same version run in the
+/// order in which they were registered.
+#[derive(Clone)]
+pub struct CustomProtocolUpdate {
+    pub logical_name: String,
+    pub enact_after: ProtocolVersion,
+    pub batch_generator: Box<dyn ProtocolUpdateBatchGenerator>,
+}
+
+impl CustomProtocolUpdate {
+    pub fn new(
+        logical_name: &str,
+        enact_after: ProtocolVersion,
+        batch_generator: impl ProtocolUpdateBatchGenerator + Clone + 'static,
+    ) -> Self {
+        Self {
+            logical_name: logical_name.to_string(),
+            enact_after,
+            batch_generator: Box::new(batch_generator),
+        }
+    }
+
+    /// Creates an update committing each of the given flashes in its own batch.
+    pub fn flash(
+        logical_name: &str,
+        enact_after: ProtocolVersion,
+        flashes: Vec<(String, StateUpdates)>,
+    ) -> Self {
+        Self::new(logical_name, enact_after, FlashBatchGenerator { flashes })
+    }
+}
+
+/// Identifies the protocol update run by a [`ProtocolUpdateExecutor`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ProtocolUpdateEntry {
+    Builtin(ProtocolUpdate),
+    Custom {
+        logical_name: String,
+        enact_after: ProtocolVersion,
+    },
+}
+
+impl ProtocolUpdateEntry {
+    pub fn logical_name(&self) -> &str {
+        match self {
+            Self::Builtin(protocol_update) => protocol_update.logical_name(),
+            Self::Custom { logical_name, .. } => logical_name,
+        }
+    }
+
+    /// The protocol version of the ledger once this update has been enacted. Custom updates
+    /// don't bump the protocol version.
+    pub fn protocol_version(&self) -> ProtocolVersion {
+        match self {
+            Self::Builtin(protocol_update) => (*protocol_update).into(),
+            Self::Custom { enact_after, .. } => *enact_after,
+        }
+    }
+}
+
+impl From<ProtocolUpdate> for ProtocolUpdateEntry {
+    fn from(value: ProtocolUpdate) -> Self {
+        Self::Builtin(value)
+    }
+}
+
+/// A [`ProtocolUpdateBatchGenerator`] which commits each named flash in its own batch.
+///
+/// It is SBOR-encodable, so that flash updates can be prepared ahead of time and shipped as files.
+#[derive(Debug, Clone, PartialEq, Eq, Sbor)]
+pub struct FlashBatchGenerator {
+    pub flashes: Vec<(String, StateUpdates)>,
+}
+
+impl ProtocolUpdateBatchGenerator for FlashBatchGenerator {
+    fn generate_batch(
+        &self,
+        _store: &dyn SubstateDatabase,
+        batch_index: u32,
+    ) -> ProtocolUpdateBatch {
+        let (name, state_updates) = self
+            .flashes
+            .get(batch_index as usize)
+            .expect("batch index out of range");
+        ProtocolUpdateBatch {
+            transactions: vec![ProtocolUpdateTransactionDetails::flash(
+                name,
+                state_updates.clone(),
+            )],
+        }
+    }
+
+    fn batch_count(&self) -> u32 {
+        self.flashes.len() as u32
+    }
+}
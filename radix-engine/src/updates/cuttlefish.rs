@@ -0,0 +1,326 @@
+use super::*;
+use crate::blueprints::package::*;
+use crate::system::system_callback::*;
+use crate::system::system_db_reader::*;
+use crate::system::system_modules::costing::CostingModuleConfig;
+use crate::track::*;
+use crate::transaction::*;
+use crate::vm::*;
+use radix_engine_interface::blueprints::resource::*;
+use radix_engine_interface::object_modules::role_assignment::*;
+use radix_substate_store_interface::db_key_mapper::{
+    MappedSubstateDatabase, SpreadPrefixKeyMapper,
+};
+
+#[derive(Clone)]
+pub struct CuttlefishSettings {
+    /// Allows access rules to be conditioned on the current epoch or the proposer timestamp, by
+    /// bumping the system version and the auth zone code.
+    ///
+    /// This also bumps the role assignment and package code, which reject access rules with time
+    /// conditions until this setting is enabled, as does payload validation for `SystemVersion::V1`.
+    pub enable_access_rule_time_conditions: UpdateSetting<NoSettings>,
+
+    /// Bumps the Scrypto VM to V1_2, which allows blueprints to declare index and sorted index
+    /// collections, as well as `on_drop` and `on_move` hooks.
+    pub vm_boot_to_scrypto_v1_2: UpdateSetting<NoSettings>,
+}
+
+impl UpdateSettings for CuttlefishSettings {
+    type BatchGenerator = CuttlefishBatchGenerator;
+
+    fn all_enabled_as_default_for_network(network: &NetworkDefinition) -> Self {
+        Self {
+            enable_access_rule_time_conditions: UpdateSetting::enabled_as_default_for_network(
+                network,
+            ),
+            vm_boot_to_scrypto_v1_2: UpdateSetting::enabled_as_default_for_network(network),
+        }
+    }
+
+    fn all_disabled() -> Self {
+        Self {
+            enable_access_rule_time_conditions: UpdateSetting::Disabled,
+            vm_boot_to_scrypto_v1_2: UpdateSetting::Disabled,
+        }
+    }
+
+    fn create_batch_generator(&self) -> Self::BatchGenerator {
+        CuttlefishBatchGenerator {
+            settings: self.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CuttlefishBatchGenerator {
+    settings: CuttlefishSettings,
+}
+
+impl ProtocolUpdateBatchGenerator for CuttlefishBatchGenerator {
+    fn generate_batch(
+        &self,
+        store: &dyn SubstateDatabase,
+        batch_index: u32,
+    ) -> ProtocolUpdateBatch {
+        if batch_index != 0 {
+            panic!("batch index out of range")
+        }
+        generate_principal_batch(store, &self.settings)
+    }
+
+    fn batch_count(&self) -> u32 {
+        1
+    }
+}
+
+#[deny(unused_variables)]
+fn generate_principal_batch(
+    store: &dyn SubstateDatabase,
+    CuttlefishSettings {
+        enable_access_rule_time_conditions,
+        vm_boot_to_scrypto_v1_2,
+    }: &CuttlefishSettings,
+) -> ProtocolUpdateBatch {
+    let mut transactions = vec![];
+    if let UpdateSetting::Enabled(_) = &enable_access_rule_time_conditions {
+        transactions.push(ProtocolUpdateTransactionDetails::flash(
+            "cuttlefish-system-version-v2",
+            generate_system_version_v2_state_updates(store),
+        ));
+        transactions.push(ProtocolUpdateTransactionDetails::flash(
+            "cuttlefish-auth-zone-time-conditions",
+            generate_auth_zone_cuttlefish_extension_state_updates(store),
+        ));
+        transactions.push(ProtocolUpdateTransactionDetails::flash(
+            "cuttlefish-role-assignment-time-conditions",
+            generate_role_assignment_cuttlefish_extension_state_updates(store),
+        ));
+        transactions.push(ProtocolUpdateTransactionDetails::flash(
+            "cuttlefish-package-time-conditions",
+            generate_package_cuttlefish_extension_state_updates(store),
+        ));
+    }
+    if let UpdateSetting::Enabled(_) = &vm_boot_to_scrypto_v1_2 {
+        transactions.push(ProtocolUpdateTransactionDetails::flash(
+            "cuttlefish-vm-boot",
+            generate_vm_boot_for_scrypto_v1_2_state_updates(),
+        ));
+    }
+    ProtocolUpdateBatch { transactions }
+}
+
+/// A quick macro for encoding and unwrapping.
+macro_rules! scrypto_encode {
+    (
+        $expr: expr
+    ) => {
+        ::radix_common::prelude::scrypto_encode($expr).unwrap()
+    };
+}
+
+fn generate_system_version_v2_state_updates<S: SubstateDatabase + ?Sized>(db: &S) -> StateUpdates {
+    // The system parameters are kept as they are, falling back to the same defaults as the system
+    let system_parameters = db
+        .get_mapped::<SpreadPrefixKeyMapper, SystemBoot>(
+            TRANSACTION_TRACKER.as_node_id(),
+            BOOT_LOADER_PARTITION,
+            &SubstateKey::Field(BOOT_LOADER_SYSTEM_SUBSTATE_FIELD_KEY),
+        )
+        .map(|system_boot| system_boot.into_parameters())
+        .unwrap_or(SystemParameters {
+            network_definition: NetworkDefinition::mainnet(),
+            costing_parameters: CostingParameters::babylon_genesis(),
+            costing_module_config: CostingModuleConfig::babylon_genesis(),
+            limit_parameters: LimitParameters::babylon_genesis(),
+        });
+
+    StateUpdates {
+        by_node: indexmap!(
+            TRANSACTION_TRACKER.into_node_id() => NodeStateUpdates::Delta {
+                by_partition: indexmap! {
+                    BOOT_LOADER_PARTITION => PartitionStateUpdates::Delta {
+                        by_substate: indexmap! {
+                            SubstateKey::Field(BOOT_LOADER_SYSTEM_SUBSTATE_FIELD_KEY) => DatabaseUpdate::Set(
+                                scrypto_encode!(&SystemBoot::V2(SystemVersion::V2, system_parameters))
+                            ),
+                        }
+                    },
+                }
+            }
+        ),
+    }
+}
+
+fn generate_vm_boot_for_scrypto_v1_2_state_updates() -> StateUpdates {
+    let substate = scrypto_encode!(&VmBoot::V1 {
+        scrypto_version: ScryptoVmVersion::V1_2.into(),
+    });
+
+    StateUpdates {
+        by_node: indexmap!(
+            TRANSACTION_TRACKER.into_node_id() => NodeStateUpdates::Delta {
+                by_partition: indexmap! {
+                    BOOT_LOADER_PARTITION => PartitionStateUpdates::Delta {
+                        by_substate: indexmap! {
+                            SubstateKey::Field(BOOT_LOADER_VM_BOOT_FIELD_KEY) => DatabaseUpdate::Set(substate)
+                        }
+                    },
+                }
+            }
+        ),
+    }
+}
+
+fn generate_auth_zone_cuttlefish_extension_state_updates<S: SubstateDatabase + ?Sized>(
+    db: &S,
+) -> StateUpdates {
+    generate_native_code_extension_state_updates(
+        db,
+        RESOURCE_PACKAGE,
+        AUTH_ZONE_BLUEPRINT,
+        NativeCodeId::ResourceCode2,
+        &[AUTH_ZONE_ASSERT_ACCESS_RULE_IDENT],
+    )
+}
+
+fn generate_role_assignment_cuttlefish_extension_state_updates<S: SubstateDatabase + ?Sized>(
+    db: &S,
+) -> StateUpdates {
+    generate_native_code_extension_state_updates(
+        db,
+        ROLE_ASSIGNMENT_MODULE_PACKAGE,
+        ROLE_ASSIGNMENT_BLUEPRINT,
+        NativeCodeId::RoleAssignmentCode3,
+        &[
+            ROLE_ASSIGNMENT_CREATE_IDENT,
+            ROLE_ASSIGNMENT_SET_OWNER_IDENT,
+            ROLE_ASSIGNMENT_SET_IDENT,
+        ],
+    )
+}
+
+fn generate_package_cuttlefish_extension_state_updates<S: SubstateDatabase + ?Sized>(
+    db: &S,
+) -> StateUpdates {
+    generate_native_code_extension_state_updates(
+        db,
+        PACKAGE_PACKAGE,
+        PACKAGE_BLUEPRINT,
+        NativeCodeId::PackageCode3,
+        &[
+            PACKAGE_PUBLISH_NATIVE_IDENT,
+            PACKAGE_PUBLISH_WASM_IDENT,
+            PACKAGE_PUBLISH_WASM_ADVANCED_IDENT,
+        ],
+    )
+}
+
+/// Generates the state updates which make the given functions of a native blueprint use the
+/// given native code. The other functions keep using their current code.
+fn generate_native_code_extension_state_updates<S: SubstateDatabase + ?Sized>(
+    db: &S,
+    package_address: PackageAddress,
+    blueprint_name: &str,
+    native_code_id: NativeCodeId,
+    function_names: &[&str],
+) -> StateUpdates {
+    let reader = SystemDatabaseReader::new(db);
+    let node_id = package_address.into_node_id();
+    let blueprint_version_key = BlueprintVersionKey {
+        blueprint: blueprint_name.to_string(),
+        version: Default::default(),
+    };
+
+    // Creating the original code substates for extension.
+    let (code_hash, (code_substate, vm_type_substate)) = {
+        let original_code = (native_code_id as u64).to_be_bytes().to_vec();
+
+        let code_hash = CodeHash::from_hash(hash(&original_code));
+        let code_substate = PackageCodeOriginalCodeV1 {
+            code: original_code,
+        }
+        .into_locked_substate();
+        let vm_type_substate = PackageCodeVmTypeV1 {
+            vm_type: VmType::Native,
+        }
+        .into_locked_substate();
+
+        (code_hash, (code_substate, vm_type_substate))
+    };
+
+    // Updating the blueprint definition of the existing blueprint so that the code used is the new
+    // one.
+    let blueprint_definition_substate = {
+        let mut blueprint_definition = reader
+            .read_object_collection_entry::<_, VersionedPackageBlueprintVersionDefinition>(
+                &node_id,
+                ObjectModuleId::Main,
+                ObjectCollectionKey::KeyValue(
+                    PackageCollection::BlueprintVersionDefinitionKeyValue.collection_index(),
+                    &blueprint_version_key,
+                ),
+            )
+            .unwrap()
+            .unwrap()
+            .fully_update_and_into_latest_version();
+
+        for function_name in function_names {
+            blueprint_definition
+                .function_exports
+                .get_mut(*function_name)
+                .expect("This function must exist")
+                .code_hash = code_hash;
+        }
+
+        blueprint_definition.into_locked_substate()
+    };
+
+    // Getting the partition number of the various collections that we're updating
+    let [blueprint_version_definition_partition_number, code_vm_type_partition_number, code_original_code_partition_number] =
+        [
+            PackageCollection::BlueprintVersionDefinitionKeyValue,
+            PackageCollection::CodeVmTypeKeyValue,
+            PackageCollection::CodeOriginalCodeKeyValue,
+        ]
+        .map(|package_collection| {
+            reader
+                .get_partition_of_collection(
+                    &node_id,
+                    ObjectModuleId::Main,
+                    package_collection.collection_index(),
+                )
+                .unwrap()
+        });
+
+    // Generating the state updates
+    StateUpdates {
+        by_node: indexmap!(
+            node_id => NodeStateUpdates::Delta {
+                by_partition: indexmap! {
+                    blueprint_version_definition_partition_number => PartitionStateUpdates::Delta {
+                        by_substate: indexmap! {
+                            SubstateKey::Map(scrypto_encode!(&blueprint_version_key)) => DatabaseUpdate::Set(
+                                scrypto_encode!(&blueprint_definition_substate)
+                            )
+                        }
+                    },
+                    code_vm_type_partition_number => PartitionStateUpdates::Delta {
+                        by_substate: indexmap! {
+                            SubstateKey::Map(scrypto_encode!(&code_hash)) => DatabaseUpdate::Set(
+                                scrypto_encode!(&vm_type_substate)
+                            )
+                        }
+                    },
+                    code_original_code_partition_number => PartitionStateUpdates::Delta {
+                        by_substate: indexmap! {
+                            SubstateKey::Map(scrypto_encode!(&code_hash)) => DatabaseUpdate::Set(
+                                scrypto_encode!(&code_substate)
+                            )
+                        }
+                    },
+                }
+            }
+        ),
+    }
+}
@@ -1,17 +1,23 @@
 use crate::{internal_prelude::*, track::StateUpdates};
+use radix_transactions::model::SystemTransactionV1;
 mod anemone;
 mod bottlenose;
+mod custom;
+mod cuttlefish;
 mod protocol_builder;
 mod protocol_updates;
 
 pub use anemone::*;
 pub use bottlenose::*;
+pub use custom::*;
+pub use cuttlefish::*;
 pub use protocol_builder::*;
 pub use protocol_updates::*;
 
 // TODO AFTER MERGE WITH NODE: Replace with node's UpdateTransaction
 pub enum ProtocolUpdateTransactionDetails {
     FlashV1Transaction(FlashProtocolUpdateTransactionDetails),
+    SystemTransactionV1(SystemProtocolUpdateTransactionDetails),
 }
 
 impl ProtocolUpdateTransactionDetails {
@@ -21,6 +27,13 @@ impl ProtocolUpdateTransactionDetails {
             state_updates,
         })
     }
+
+    pub fn system(name: &str, transaction: SystemTransactionV1) -> Self {
+        Self::SystemTransactionV1(SystemProtocolUpdateTransactionDetails {
+            name: name.to_string(),
+            transaction,
+        })
+    }
 }
 
 // TODO AFTER MERGE WITH NODE: Merge replace with node's FlashTransactionV1
@@ -29,6 +42,13 @@ pub struct FlashProtocolUpdateTransactionDetails {
     pub state_updates: StateUpdates,
 }
 
+/// A system transaction, executed with the system role against the state left by the
+/// preceding transactions of the update. It must commit successfully.
+pub struct SystemProtocolUpdateTransactionDetails {
+    pub name: String,
+    pub transaction: SystemTransactionV1,
+}
+
 /// A set of transactions which all get committed together with the same proof.
 /// To avoid memory overflows, this should be kept small (e.g. one transaction each).
 pub struct ProtocolUpdateBatch {
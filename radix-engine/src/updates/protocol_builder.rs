@@ -1,15 +1,37 @@
 use radix_substate_store_interface::db_key_mapper::SpreadPrefixKeyMapper;
 
 use super::*;
+use crate::transaction::{execute_transaction, ExecutionConfig};
+use crate::vm::wasm::{DefaultWasmEngine, WasmEngine};
+use crate::vm::{NativeVmExtension, NoExtension, ScryptoVm, VmInit};
+use radix_transactions::model::TransactionPayload;
 
 #[derive(Clone)]
 pub struct ProtocolUpdateExecutor {
-    pub protocol_update: ProtocolUpdate,
+    pub protocol_update: ProtocolUpdateEntry,
+    pub network_definition: NetworkDefinition,
     pub batch_generator: Box<dyn ProtocolUpdateBatchGenerator>,
 }
 
 impl ProtocolUpdateExecutor {
+    /// Runs and commits the update, executing any system transactions with a default Scrypto VM.
     pub fn run_and_commit<S: SubstateDatabase + CommittableSubstateDatabase>(self, store: &mut S) {
+        let scrypto_vm = ScryptoVm::<DefaultWasmEngine>::default();
+        self.run_and_commit_with_vm_init(store, VmInit::new(&scrypto_vm, NoExtension));
+    }
+
+    /// Runs and commits the update, executing any system transactions with the given VMs.
+    ///
+    /// *Panics* if a system transaction fails to commit successfully.
+    pub fn run_and_commit_with_vm_init<
+        S: SubstateDatabase + CommittableSubstateDatabase,
+        W: WasmEngine,
+        E: NativeVmExtension,
+    >(
+        self,
+        store: &mut S,
+        vm_init: VmInit<W, E>,
+    ) {
         for batch_index in 0..self.batch_generator.batch_count() {
             let batch = self.batch_generator.generate_batch(store, batch_index);
             for transaction in batch.transactions {
@@ -17,6 +39,26 @@ impl ProtocolUpdateExecutor {
                     ProtocolUpdateTransactionDetails::FlashV1Transaction(flash) => {
                         flash.state_updates
                     }
+                    ProtocolUpdateTransactionDetails::SystemTransactionV1(system) => {
+                        let receipt = execute_transaction(
+                            store,
+                            vm_init.clone(),
+                            &ExecutionConfig::for_system_transaction(
+                                self.network_definition.clone(),
+                            ),
+                            &system
+                                .transaction
+                                .prepare()
+                                .unwrap_or_else(|err| {
+                                    panic!(
+                                        "Expected protocol update transaction {} to be preparable: {:?}",
+                                        system.name, err
+                                    )
+                                })
+                                .get_executable(btreeset![AuthAddresses::system_role()]),
+                        );
+                        receipt.expect_commit_success().state_updates.clone()
+                    }
                 };
                 let db_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
                 store.commit(&db_updates);
@@ -33,8 +75,11 @@ pub struct ProtocolBuilder {
 #[derive(Clone)]
 pub struct ProtocolSettings {
     // TODO: It would be nice to move bootstrap / Genesis into this formulation
+    network_definition: NetworkDefinition,
     anemone: AnemoneSettings,
     bottlenose: BottlenoseSettings,
+    cuttlefish: CuttlefishSettings,
+    custom_updates: Vec<CustomProtocolUpdate>,
 }
 
 impl ProtocolBuilder {
@@ -45,10 +90,15 @@ impl ProtocolBuilder {
     pub fn for_network(network_definition: &NetworkDefinition) -> Self {
         Self {
             settings: ProtocolSettings {
+                network_definition: network_definition.clone(),
                 anemone: AnemoneSettings::all_enabled_as_default_for_network(network_definition),
                 bottlenose: BottlenoseSettings::all_enabled_as_default_for_network(
                     network_definition,
                 ),
+                cuttlefish: CuttlefishSettings::all_enabled_as_default_for_network(
+                    network_definition,
+                ),
+                custom_updates: vec![],
             },
         }
     }
@@ -63,6 +113,30 @@ impl ProtocolBuilder {
         self
     }
 
+    pub fn with_cuttlefish(mut self, settings: CuttlefishSettings) -> Self {
+        self.settings.cuttlefish = settings;
+        self
+    }
+
+    /// Registers a custom protocol update, to be enacted after the given update's
+    /// `enact_after` version (see [`CustomProtocolUpdate`]).
+    ///
+    /// *Panics* if the logical name clashes with a protocol version or another custom update.
+    pub fn with_custom_update(mut self, update: CustomProtocolUpdate) -> Self {
+        let logical_name = update.logical_name.as_str();
+        if ProtocolVersion::try_from_logical_name(logical_name).is_some()
+            || self
+                .settings
+                .custom_updates
+                .iter()
+                .any(|existing| existing.logical_name == logical_name)
+        {
+            panic!("Protocol update name {} is already in use", logical_name);
+        }
+        self.settings.custom_updates.push(update);
+        self
+    }
+
     pub fn until_babylon(self) -> ProtocolExecutor {
         self.until(ProtocolVersion::Babylon)
     }
@@ -92,20 +166,45 @@ impl ProtocolExecutor {
     pub fn commit_each_protocol_update<S: SubstateDatabase + CommittableSubstateDatabase>(
         &self,
         store: &mut S,
+    ) {
+        let scrypto_vm = ScryptoVm::<DefaultWasmEngine>::default();
+        self.commit_each_protocol_update_with_vm_init(store, VmInit::new(&scrypto_vm, NoExtension));
+    }
+
+    pub fn commit_each_protocol_update_with_vm_init<
+        S: SubstateDatabase + CommittableSubstateDatabase,
+        W: WasmEngine,
+        E: NativeVmExtension,
+    >(
+        &self,
+        store: &mut S,
+        vm_init: VmInit<W, E>,
     ) {
         for update_execution in self.each_protocol_update_executor() {
-            update_execution.run_and_commit(store);
+            update_execution.run_and_commit_with_vm_init(store, vm_init.clone());
         }
     }
 
+    /// Iterates over the built-in updates up to the target protocol version, each followed by
+    /// the custom updates enacted after it. Custom updates enacted after genesis come first.
     pub fn each_protocol_update_executor(
         &self,
     ) -> impl Iterator<Item = ProtocolUpdateExecutor> + '_ {
         let until_protocol_version = self.update_until;
-        ProtocolUpdate::VARIANTS
+        ProtocolVersion::VARIANTS
             .into_iter()
-            .take_while(move |update| ProtocolVersion::from(*update) <= until_protocol_version)
-            .map(move |protocol_update| self.create_executor_for_update(protocol_update))
+            .take_while(move |version| *version <= until_protocol_version)
+            .flat_map(move |version| {
+                let builtin_update = ProtocolUpdate::try_from_logical_name(version.logical_name())
+                    .map(|protocol_update| self.create_executor_for_update(protocol_update));
+                let custom_updates = self
+                    .settings
+                    .custom_updates
+                    .iter()
+                    .filter(move |update| update.enact_after == version)
+                    .map(move |update| self.create_executor_for_custom_update(update));
+                builtin_update.into_iter().chain(custom_updates)
+            })
     }
 
     pub fn create_executor_for_update(
@@ -117,10 +216,107 @@ impl ProtocolExecutor {
             ProtocolUpdate::Bottlenose => {
                 Box::new(self.settings.bottlenose.create_batch_generator())
             }
+            ProtocolUpdate::Cuttlefish => {
+                Box::new(self.settings.cuttlefish.create_batch_generator())
+            }
         };
         ProtocolUpdateExecutor {
-            protocol_update,
+            protocol_update: protocol_update.into(),
+            network_definition: self.settings.network_definition.clone(),
             batch_generator: generator,
         }
     }
+
+    pub fn create_executor_for_custom_update(
+        &self,
+        update: &CustomProtocolUpdate,
+    ) -> ProtocolUpdateExecutor {
+        ProtocolUpdateExecutor {
+            protocol_update: ProtocolUpdateEntry::Custom {
+                logical_name: update.logical_name.clone(),
+                enact_after: update.enact_after,
+            },
+            network_definition: self.settings.network_definition.clone(),
+            batch_generator: update.batch_generator.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_update(logical_name: &str, enact_after: ProtocolVersion) -> CustomProtocolUpdate {
+        CustomProtocolUpdate::flash(logical_name, enact_after, vec![])
+    }
+
+    #[test]
+    fn custom_updates_are_enacted_after_their_protocol_version() {
+        // Arrange
+        let executor = ProtocolBuilder::for_simulator()
+            .with_custom_update(empty_update(
+                "custom_after_anemone",
+                ProtocolVersion::Anemone,
+            ))
+            .with_custom_update(empty_update(
+                "custom_after_babylon",
+                ProtocolVersion::Babylon,
+            ))
+            .with_custom_update(empty_update(
+                "custom_after_bottlenose",
+                ProtocolVersion::Bottlenose,
+            ))
+            .with_custom_update(empty_update(
+                "custom_after_anemone_2",
+                ProtocolVersion::Anemone,
+            ))
+            .until(ProtocolVersion::Anemone);
+
+        // Act
+        let updates = executor
+            .each_protocol_update_executor()
+            .map(|executor| executor.protocol_update)
+            .collect::<Vec<_>>();
+
+        // Assert
+        assert_eq!(
+            updates
+                .iter()
+                .map(|update| update.logical_name())
+                .collect::<Vec<_>>(),
+            vec![
+                "custom_after_babylon",
+                "anemone",
+                "custom_after_anemone",
+                "custom_after_anemone_2"
+            ]
+        );
+        assert_eq!(
+            updates
+                .iter()
+                .map(|update| update.protocol_version())
+                .collect::<Vec<_>>(),
+            vec![
+                ProtocolVersion::Babylon,
+                ProtocolVersion::Anemone,
+                ProtocolVersion::Anemone,
+                ProtocolVersion::Anemone
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn custom_update_cannot_reuse_protocol_version_name() {
+        ProtocolBuilder::for_simulator()
+            .with_custom_update(empty_update("bottlenose", ProtocolVersion::Anemone));
+    }
+
+    #[test]
+    #[should_panic]
+    fn custom_update_names_must_be_unique() {
+        ProtocolBuilder::for_simulator()
+            .with_custom_update(empty_update("custom", ProtocolVersion::Babylon))
+            .with_custom_update(empty_update("custom", ProtocolVersion::Anemone));
+    }
 }
@@ -8,7 +8,7 @@ use crate::blueprints::identity::IdentityNativePackage;
 use crate::blueprints::locker::LockerNativePackage;
 use crate::blueprints::package::PackageNativePackage;
 use crate::blueprints::pool::v1::package::*;
-use crate::blueprints::resource::ResourceNativePackage;
+use crate::blueprints::resource::{AuthZoneCuttlefishExtension, ResourceNativePackage};
 use crate::blueprints::test_utils::TestUtilsNativePackage;
 use crate::blueprints::transaction_processor::{
     TransactionProcessorNativePackage, TransactionProcessorV1MinorVersion,
@@ -136,9 +136,19 @@ impl<I: VmInvoke> VmInvoke for NativeVmInstance<I> {
                         api,
                         vm_api,
                     ),
+                    NativeCodeId::PackageCode3 => PackageNativePackage::invoke_export(
+                        export_name,
+                        input,
+                        PackageV1MinorVersion::Two,
+                        api,
+                        vm_api,
+                    ),
                     NativeCodeId::ResourceCode1 => {
                         ResourceNativePackage::invoke_export(export_name, input, api)
                     }
+                    NativeCodeId::ResourceCode2 => {
+                        AuthZoneCuttlefishExtension::invoke_export(export_name, input, api)
+                    }
                     NativeCodeId::ConsensusManagerCode1 => {
                         ConsensusManagerNativePackage::invoke_export(export_name, input, api)
                     }
@@ -192,6 +202,9 @@ impl<I: VmInvoke> VmInvoke for NativeVmInstance<I> {
                     NativeCodeId::RoleAssignmentCode2 => {
                         RoleAssignmentBottlenoseExtension::invoke_export(export_name, input, api)
                     }
+                    NativeCodeId::RoleAssignmentCode3 => {
+                        RoleAssignmentCuttlefishExtension::invoke_export(export_name, input, api)
+                    }
                     NativeCodeId::PoolCode1 => PoolNativePackage::invoke_export(
                         export_name,
                         input,
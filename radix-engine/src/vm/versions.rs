@@ -5,16 +5,25 @@ use radix_common::ScryptoSbor;
 pub enum ScryptoVmVersion {
     V1_0,
     V1_1,
+    V1_2,
 }
 
 impl ScryptoVmVersion {
     pub fn latest() -> ScryptoVmVersion {
-        ScryptoVmVersion::V1_1
+        ScryptoVmVersion::V1_2
     }
 
     pub fn crypto_utils_added() -> ScryptoVmVersion {
         ScryptoVmVersion::V1_1
     }
+
+    pub fn actor_collections_added() -> ScryptoVmVersion {
+        ScryptoVmVersion::V1_2
+    }
+
+    pub fn blueprint_hooks_added() -> ScryptoVmVersion {
+        ScryptoVmVersion::V1_2
+    }
 }
 
 impl From<ScryptoVmVersion> for u64 {
@@ -30,6 +39,7 @@ impl TryFrom<u64> for ScryptoVmVersion {
         match version {
             0 => Ok(Self::V1_0),
             1 => Ok(Self::V1_1),
+            2 => Ok(Self::V1_2),
             v => Err(Self::Error::FromIntError(v)),
         }
     }
@@ -47,7 +57,7 @@ mod test {
     #[test]
     fn test_scrypto_vm_version() {
         let v = ScryptoVmVersion::latest();
-        assert_eq!(v, ScryptoVmVersion::V1_1);
+        assert_eq!(v, ScryptoVmVersion::V1_2);
         assert_eq!(
             ScryptoVmVersion::crypto_utils_added(),
             ScryptoVmVersion::V1_1
@@ -62,14 +72,21 @@ mod test {
         let v: ScryptoVmVersion = 1u64.try_into().unwrap();
         assert_eq!(v, ScryptoVmVersion::V1_1);
 
-        let e = ScryptoVmVersion::try_from(2u64).unwrap_err();
+        let v: ScryptoVmVersion = 2u64.try_into().unwrap();
+        assert_eq!(v, ScryptoVmVersion::V1_2);
+
+        let e = ScryptoVmVersion::try_from(3u64).unwrap_err();
 
-        assert_eq!(e, ScryptoVmVersionError::FromIntError(2u64));
+        assert_eq!(e, ScryptoVmVersionError::FromIntError(3u64));
     }
 
     #[test]
     fn test_scrypto_vm_version_ordering() {
         assert!(ScryptoVmVersion::crypto_utils_added() == ScryptoVmVersion::V1_1);
         assert!(ScryptoVmVersion::crypto_utils_added() > ScryptoVmVersion::V1_0);
+        assert!(ScryptoVmVersion::actor_collections_added() == ScryptoVmVersion::V1_2);
+        assert!(ScryptoVmVersion::actor_collections_added() > ScryptoVmVersion::V1_1);
+        assert!(ScryptoVmVersion::blueprint_hooks_added() == ScryptoVmVersion::V1_2);
+        assert!(ScryptoVmVersion::blueprint_hooks_added() > ScryptoVmVersion::V1_1);
     }
 }
@@ -299,7 +299,9 @@ impl VmPackageValidation {
                         ));
                     }
 
-                    if !collections.is_empty() {
+                    if !collections.is_empty()
+                        && version < ScryptoVmVersion::actor_collections_added()
+                    {
                         return Err(RuntimeError::ApplicationError(
                             ApplicationError::PackageError(PackageError::WasmUnsupported(
                                 "Static collections not supported".to_string(),
@@ -339,7 +341,9 @@ impl VmPackageValidation {
                         }
                     }
 
-                    if !hooks.hooks.is_empty() {
+                    if !hooks.hooks.is_empty()
+                        && version < ScryptoVmVersion::blueprint_hooks_added()
+                    {
                         return Err(RuntimeError::ApplicationError(
                             ApplicationError::PackageError(PackageError::WasmUnsupported(
                                 "Hooks not supported".to_string(),
@@ -347,6 +351,14 @@ impl VmPackageValidation {
                         ));
                     }
 
+                    if hooks.hooks.contains_key(&BlueprintHook::OnVirtualize) {
+                        return Err(RuntimeError::ApplicationError(
+                            ApplicationError::PackageError(PackageError::WasmUnsupported(
+                                "OnVirtualize hook not supported".to_string(),
+                            )),
+                        ));
+                    }
+
                     for (_name, schema) in &functions.functions {
                         if let Some(info) = &schema.receiver {
                             if info.ref_types != RefTypes::NORMAL {
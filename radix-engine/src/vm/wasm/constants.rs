@@ -17,6 +17,7 @@ pub const OBJECT_GLOBALIZE_FUNCTION_NAME: &str = "object_globalize";
 pub const OBJECT_INSTANCE_OF_FUNCTION_NAME: &str = "object_instance_of";
 pub const OBJECT_GET_BLUEPRINT_ID_FUNCTION_NAME: &str = "object_get_blueprint_id";
 pub const OBJECT_GET_OUTER_OBJECT_FUNCTION_NAME: &str = "object_get_outer_object";
+pub const OBJECT_DROP_FUNCTION_NAME: &str = "object_drop";
 pub const OBJECT_CALL_FUNCTION_NAME: &str = "object_call";
 pub const OBJECT_CALL_DIRECT_FUNCTION_NAME: &str = "object_call_direct";
 pub const OBJECT_CALL_MODULE_FUNCTION_NAME: &str = "object_call_module";
@@ -30,6 +31,21 @@ pub const ACTOR_OPEN_FIELD_FUNCTION_NAME: &str = "actor_open_field";
 pub const ACTOR_GET_OBJECT_ID_FUNCTION_NAME: &str = "actor_get_object_id";
 pub const ACTOR_EMIT_EVENT_FUNCTION_NAME: &str = "actor_emit_event";
 
+//=================
+// Actor Index
+//=================
+pub const ACTOR_INDEX_INSERT_FUNCTION_NAME: &str = "actor_index_insert";
+pub const ACTOR_INDEX_REMOVE_FUNCTION_NAME: &str = "actor_index_remove";
+pub const ACTOR_INDEX_SCAN_KEYS_FUNCTION_NAME: &str = "actor_index_scan_keys";
+pub const ACTOR_INDEX_DRAIN_FUNCTION_NAME: &str = "actor_index_drain";
+
+//=================
+// Actor Sorted Index
+//=================
+pub const ACTOR_SORTED_INDEX_INSERT_FUNCTION_NAME: &str = "actor_sorted_index_insert";
+pub const ACTOR_SORTED_INDEX_REMOVE_FUNCTION_NAME: &str = "actor_sorted_index_remove";
+pub const ACTOR_SORTED_INDEX_SCAN_FUNCTION_NAME: &str = "actor_sorted_index_scan";
+
 //=================
 // Key Value Store
 //=================
@@ -163,6 +163,9 @@ pub enum WasmRuntimeError {
     InvalidBlsPublicKey(DecodeError),
     InvalidBlsSignature(DecodeError),
     InvalidBlsPublicKeyOrMessage(DecodeError),
+
+    /// Invalid sorted index key
+    InvalidSortedKey(DecodeError),
 }
 
 impl SelfError for WasmRuntimeError {
@@ -333,6 +333,18 @@ impl WasmerModule {
                 .map(|buffer| buffer.0)
         }
 
+        pub fn object_drop(
+            env: &WasmerInstanceEnv,
+            obj_ptr: u32,
+            obj_len: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            runtime
+                .drop_object(read_memory(&instance, obj_ptr, obj_len)?)
+                .map(|buffer| buffer.0)
+        }
+
         pub fn object_instance_of(
             env: &WasmerInstanceEnv,
             component_id_ptr: u32,
@@ -505,6 +517,116 @@ impl WasmerModule {
             runtime.actor_open_field(object_handle, field, flags)
         }
 
+        pub fn actor_index_insert(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            key_ptr: u32,
+            key_len: u32,
+            value_ptr: u32,
+            value_len: u32,
+        ) -> Result<(), InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let key = read_memory(&instance, key_ptr, key_len)?;
+            let value = read_memory(&instance, value_ptr, value_len)?;
+
+            runtime.actor_index_insert(object_handle, collection_index as u8, key, value)
+        }
+
+        pub fn actor_index_remove(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            key_ptr: u32,
+            key_len: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let key = read_memory(&instance, key_ptr, key_len)?;
+
+            runtime
+                .actor_index_remove(object_handle, collection_index as u8, key)
+                .map(|buffer| buffer.0)
+        }
+
+        pub fn actor_index_scan_keys(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            limit: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (_instance, runtime) = grab_runtime!(env);
+
+            runtime
+                .actor_index_scan_keys(object_handle, collection_index as u8, limit)
+                .map(|buffer| buffer.0)
+        }
+
+        pub fn actor_index_drain(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            limit: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (_instance, runtime) = grab_runtime!(env);
+
+            runtime
+                .actor_index_drain(object_handle, collection_index as u8, limit)
+                .map(|buffer| buffer.0)
+        }
+
+        pub fn actor_sorted_index_insert(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            sorted_key_ptr: u32,
+            sorted_key_len: u32,
+            value_ptr: u32,
+            value_len: u32,
+        ) -> Result<(), InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let sorted_key = read_memory(&instance, sorted_key_ptr, sorted_key_len)?;
+            let value = read_memory(&instance, value_ptr, value_len)?;
+
+            runtime.actor_sorted_index_insert(
+                object_handle,
+                collection_index as u8,
+                sorted_key,
+                value,
+            )
+        }
+
+        pub fn actor_sorted_index_remove(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            sorted_key_ptr: u32,
+            sorted_key_len: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (instance, runtime) = grab_runtime!(env);
+
+            let sorted_key = read_memory(&instance, sorted_key_ptr, sorted_key_len)?;
+
+            runtime
+                .actor_sorted_index_remove(object_handle, collection_index as u8, sorted_key)
+                .map(|buffer| buffer.0)
+        }
+
+        pub fn actor_sorted_index_scan(
+            env: &WasmerInstanceEnv,
+            object_handle: u32,
+            collection_index: u32,
+            limit: u32,
+        ) -> Result<u64, InvokeError<WasmRuntimeError>> {
+            let (_instance, runtime) = grab_runtime!(env);
+
+            runtime
+                .actor_sorted_index_scan(object_handle, collection_index as u8, limit)
+                .map(|buffer| buffer.0)
+        }
+
         pub fn actor_get_node_id(
             env: &WasmerInstanceEnv,
             actor_ref_handle: u32,
@@ -828,6 +950,7 @@ impl WasmerModule {
                 ADDRESS_GET_RESERVATION_ADDRESS_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), address_get_reservation_address),
                 OBJECT_NEW_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), object_new),
                 OBJECT_GLOBALIZE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), object_globalize),
+                OBJECT_DROP_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), object_drop),
                 OBJECT_INSTANCE_OF_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), object_instance_of),
                 OBJECT_GET_BLUEPRINT_ID_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), object_get_blueprint_id),
                 OBJECT_GET_OUTER_OBJECT_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), object_get_outer_object),
@@ -845,6 +968,13 @@ impl WasmerModule {
                 FIELD_ENTRY_WRITE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), field_entry_write),
                 FIELD_ENTRY_CLOSE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), field_entry_close),
                 ACTOR_OPEN_FIELD_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_open_field),
+                ACTOR_INDEX_INSERT_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_index_insert),
+                ACTOR_INDEX_REMOVE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_index_remove),
+                ACTOR_INDEX_SCAN_KEYS_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_index_scan_keys),
+                ACTOR_INDEX_DRAIN_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_index_drain),
+                ACTOR_SORTED_INDEX_INSERT_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_sorted_index_insert),
+                ACTOR_SORTED_INDEX_REMOVE_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_sorted_index_remove),
+                ACTOR_SORTED_INDEX_SCAN_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_sorted_index_scan),
                 ACTOR_GET_OBJECT_ID_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_get_node_id),
                 ACTOR_GET_PACKAGE_ADDRESS_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_get_package_address),
                 ACTOR_GET_BLUEPRINT_NAME_FUNCTION_NAME => Function::new_native_with_env(self.module.store(), env.clone(), actor_get_blueprint_name),
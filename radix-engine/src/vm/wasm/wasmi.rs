@@ -369,6 +369,23 @@ fn globalize_object(
         .map(|buffer| buffer.0)
 }
 
+fn drop_object(
+    mut caller: Caller<'_, HostState>,
+    obj_id_ptr: u32,
+    obj_id_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+
+    runtime
+        .drop_object(read_memory(
+            caller.as_context_mut(),
+            memory,
+            obj_id_ptr,
+            obj_id_len,
+        )?)
+        .map(|buffer| buffer.0)
+}
+
 fn instance_of(
     mut caller: Caller<'_, HostState>,
     component_id_ptr: u32,
@@ -546,6 +563,117 @@ fn field_lock_release(
     runtime.field_entry_close(handle)
 }
 
+fn actor_index_insert(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    key_ptr: u32,
+    key_len: u32,
+    value_ptr: u32,
+    value_len: u32,
+) -> Result<(), InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+    let key = read_memory(caller.as_context_mut(), memory, key_ptr, key_len)?;
+    let value = read_memory(caller.as_context_mut(), memory, value_ptr, value_len)?;
+
+    runtime.actor_index_insert(object_handle, collection_index as u8, key, value)
+}
+
+fn actor_index_remove(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    key_ptr: u32,
+    key_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+    let key = read_memory(caller.as_context_mut(), memory, key_ptr, key_len)?;
+
+    runtime
+        .actor_index_remove(object_handle, collection_index as u8, key)
+        .map(|buffer| buffer.0)
+}
+
+fn actor_index_scan_keys(
+    caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    limit: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (_memory, runtime) = grab_runtime!(caller);
+
+    runtime
+        .actor_index_scan_keys(object_handle, collection_index as u8, limit)
+        .map(|buffer| buffer.0)
+}
+
+fn actor_index_drain(
+    caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    limit: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (_memory, runtime) = grab_runtime!(caller);
+
+    runtime
+        .actor_index_drain(object_handle, collection_index as u8, limit)
+        .map(|buffer| buffer.0)
+}
+
+fn actor_sorted_index_insert(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    sorted_key_ptr: u32,
+    sorted_key_len: u32,
+    value_ptr: u32,
+    value_len: u32,
+) -> Result<(), InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+    let sorted_key = read_memory(
+        caller.as_context_mut(),
+        memory,
+        sorted_key_ptr,
+        sorted_key_len,
+    )?;
+    let value = read_memory(caller.as_context_mut(), memory, value_ptr, value_len)?;
+
+    runtime.actor_sorted_index_insert(object_handle, collection_index as u8, sorted_key, value)
+}
+
+fn actor_sorted_index_remove(
+    mut caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    sorted_key_ptr: u32,
+    sorted_key_len: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (memory, runtime) = grab_runtime!(caller);
+    let sorted_key = read_memory(
+        caller.as_context_mut(),
+        memory,
+        sorted_key_ptr,
+        sorted_key_len,
+    )?;
+
+    runtime
+        .actor_sorted_index_remove(object_handle, collection_index as u8, sorted_key)
+        .map(|buffer| buffer.0)
+}
+
+fn actor_sorted_index_scan(
+    caller: Caller<'_, HostState>,
+    object_handle: u32,
+    collection_index: u32,
+    limit: u32,
+) -> Result<u64, InvokeError<WasmRuntimeError>> {
+    let (_memory, runtime) = grab_runtime!(caller);
+
+    runtime
+        .actor_sorted_index_scan(object_handle, collection_index as u8, limit)
+        .map(|buffer| buffer.0)
+}
+
 fn actor_get_node_id(
     caller: Caller<'_, HostState>,
     handle: u32,
@@ -1100,6 +1228,13 @@ impl WasmiModule {
             },
         );
 
+        let host_drop_object = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>, obj_ptr: u32, obj_len: u32| -> Result<u64, Trap> {
+                drop_object(caller, obj_ptr, obj_len).map_err(|e| e.into())
+            },
+        );
+
         let host_instance_of = Func::wrap(
             store.as_context_mut(),
             |caller: Caller<'_, HostState>,
@@ -1245,6 +1380,120 @@ impl WasmiModule {
             },
         );
 
+        let host_actor_index_insert = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             key_ptr: u32,
+             key_len: u32,
+             value_ptr: u32,
+             value_len: u32|
+             -> Result<(), Trap> {
+                actor_index_insert(
+                    caller,
+                    object_handle,
+                    collection_index,
+                    key_ptr,
+                    key_len,
+                    value_ptr,
+                    value_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_index_remove = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             key_ptr: u32,
+             key_len: u32|
+             -> Result<u64, Trap> {
+                actor_index_remove(caller, object_handle, collection_index, key_ptr, key_len)
+                    .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_index_scan_keys = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             limit: u32|
+             -> Result<u64, Trap> {
+                actor_index_scan_keys(caller, object_handle, collection_index, limit)
+                    .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_index_drain = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             limit: u32|
+             -> Result<u64, Trap> {
+                actor_index_drain(caller, object_handle, collection_index, limit)
+                    .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_sorted_index_insert = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             sorted_key_ptr: u32,
+             sorted_key_len: u32,
+             value_ptr: u32,
+             value_len: u32|
+             -> Result<(), Trap> {
+                actor_sorted_index_insert(
+                    caller,
+                    object_handle,
+                    collection_index,
+                    sorted_key_ptr,
+                    sorted_key_len,
+                    value_ptr,
+                    value_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_sorted_index_remove = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             sorted_key_ptr: u32,
+             sorted_key_len: u32|
+             -> Result<u64, Trap> {
+                actor_sorted_index_remove(
+                    caller,
+                    object_handle,
+                    collection_index,
+                    sorted_key_ptr,
+                    sorted_key_len,
+                )
+                .map_err(|e| e.into())
+            },
+        );
+
+        let host_actor_sorted_index_scan = Func::wrap(
+            store.as_context_mut(),
+            |caller: Caller<'_, HostState>,
+             object_handle: u32,
+             collection_index: u32,
+             limit: u32|
+             -> Result<u64, Trap> {
+                actor_sorted_index_scan(caller, object_handle, collection_index, limit)
+                    .map_err(|e| e.into())
+            },
+        );
+
         let host_actor_get_node_id = Func::wrap(
             store.as_context_mut(),
             |caller: Caller<'_, HostState>, handle: u32| -> Result<u64, Trap> {
@@ -1487,6 +1736,7 @@ impl WasmiModule {
             OBJECT_GLOBALIZE_FUNCTION_NAME,
             host_globalize_object
         );
+        linker_define!(linker, OBJECT_DROP_FUNCTION_NAME, host_drop_object);
         linker_define!(linker, OBJECT_INSTANCE_OF_FUNCTION_NAME, host_instance_of);
         linker_define!(
             linker,
@@ -1547,6 +1797,41 @@ impl WasmiModule {
             FIELD_ENTRY_CLOSE_FUNCTION_NAME,
             host_field_lock_release
         );
+        linker_define!(
+            linker,
+            ACTOR_INDEX_INSERT_FUNCTION_NAME,
+            host_actor_index_insert
+        );
+        linker_define!(
+            linker,
+            ACTOR_INDEX_REMOVE_FUNCTION_NAME,
+            host_actor_index_remove
+        );
+        linker_define!(
+            linker,
+            ACTOR_INDEX_SCAN_KEYS_FUNCTION_NAME,
+            host_actor_index_scan_keys
+        );
+        linker_define!(
+            linker,
+            ACTOR_INDEX_DRAIN_FUNCTION_NAME,
+            host_actor_index_drain
+        );
+        linker_define!(
+            linker,
+            ACTOR_SORTED_INDEX_INSERT_FUNCTION_NAME,
+            host_actor_sorted_index_insert
+        );
+        linker_define!(
+            linker,
+            ACTOR_SORTED_INDEX_REMOVE_FUNCTION_NAME,
+            host_actor_sorted_index_remove
+        );
+        linker_define!(
+            linker,
+            ACTOR_SORTED_INDEX_SCAN_FUNCTION_NAME,
+            host_actor_sorted_index_scan
+        );
         linker_define!(
             linker,
             ACTOR_GET_OBJECT_ID_FUNCTION_NAME,
@@ -237,6 +237,18 @@ where
         self.allocate_buffer(address.to_vec())
     }
 
+    fn drop_object(&mut self, node_id: Vec<u8>) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let node_id = NodeId(
+            TryInto::<[u8; NodeId::LENGTH]>::try_into(node_id.as_ref())
+                .map_err(|_| WasmRuntimeError::InvalidNodeId)?,
+        );
+
+        let fields = self.api.drop_object(&node_id)?;
+        let encoded = scrypto_encode(&fields).expect("Failed to encode object fields");
+
+        self.allocate_buffer(encoded)
+    }
+
     fn key_value_store_new(
         &mut self,
         schema: Vec<u8>,
@@ -349,6 +361,103 @@ where
         Ok(())
     }
 
+    fn actor_index_insert(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), InvokeError<WasmRuntimeError>> {
+        self.api
+            .actor_index_insert(object_handle, collection_index, key, value)?;
+
+        Ok(())
+    }
+
+    fn actor_index_remove(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        key: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let rtn = self
+            .api
+            .actor_index_remove(object_handle, collection_index, key)?;
+
+        self.allocate_buffer(scrypto_encode(&rtn).expect("Failed to encode index entry"))
+    }
+
+    fn actor_index_scan_keys(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let keys = self
+            .api
+            .actor_index_scan_keys(object_handle, collection_index, limit)?;
+
+        self.allocate_buffer(scrypto_encode(&keys).expect("Failed to encode index keys"))
+    }
+
+    fn actor_index_drain(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let entries = self
+            .api
+            .actor_index_drain(object_handle, collection_index, limit)?;
+
+        self.allocate_buffer(scrypto_encode(&entries).expect("Failed to encode index entries"))
+    }
+
+    fn actor_sorted_index_insert(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        sorted_key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> Result<(), InvokeError<WasmRuntimeError>> {
+        let sorted_key =
+            scrypto_decode::<SortedKey>(&sorted_key).map_err(WasmRuntimeError::InvalidSortedKey)?;
+        self.api
+            .actor_sorted_index_insert(object_handle, collection_index, sorted_key, value)?;
+
+        Ok(())
+    }
+
+    fn actor_sorted_index_remove(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        sorted_key: Vec<u8>,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let sorted_key =
+            scrypto_decode::<SortedKey>(&sorted_key).map_err(WasmRuntimeError::InvalidSortedKey)?;
+        let rtn =
+            self.api
+                .actor_sorted_index_remove(object_handle, collection_index, &sorted_key)?;
+
+        self.allocate_buffer(scrypto_encode(&rtn).expect("Failed to encode sorted index entry"))
+    }
+
+    fn actor_sorted_index_scan(
+        &mut self,
+        object_handle: u32,
+        collection_index: u8,
+        limit: u32,
+    ) -> Result<Buffer, InvokeError<WasmRuntimeError>> {
+        let entries = self
+            .api
+            .actor_sorted_index_scan(object_handle, collection_index, limit)?;
+
+        self.allocate_buffer(
+            scrypto_encode(&entries).expect("Failed to encode sorted index entries"),
+        )
+    }
+
     fn actor_get_node_id(
         &mut self,
         actor_ref_handle: ActorRefHandle,
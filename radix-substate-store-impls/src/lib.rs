@@ -12,5 +12,7 @@ pub mod rocks_db;
 pub mod rocks_db_with_merkle_tree;
 pub mod state_tree;
 pub mod substate_database_overlay;
+#[cfg(feature = "std")]
+pub mod substate_snapshot;
 
 pub mod state_tree_support;
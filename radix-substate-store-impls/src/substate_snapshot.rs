@@ -0,0 +1,321 @@
+//! A portable snapshot of the substates held in a database.
+//!
+//! The snapshot is a stream of length-prefixed SBOR payloads: a [`SubstateSnapshotHeader`],
+//! followed by any number of [`PartitionSnapshot`] chunks. A partition larger than
+//! [`MAX_SUBSTATES_PER_CHUNK`] is split across consecutive chunks, so that neither the writer nor
+//! the reader ever needs to hold more than one chunk in memory.
+
+use radix_common::prelude::*;
+use radix_substate_store_interface::interface::*;
+use std::io::{Read, Write};
+
+/// The bytes every snapshot starts with.
+pub const SUBSTATE_SNAPSHOT_MAGIC: [u8; 4] = *b"RSSS";
+/// The version of the snapshot format produced by [`SubstateSnapshotWriter`].
+pub const SUBSTATE_SNAPSHOT_FORMAT_VERSION: u8 = 1;
+/// The maximum number of substates written in a single [`PartitionSnapshot`] chunk.
+pub const MAX_SUBSTATES_PER_CHUNK: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct SubstateSnapshotHeader {
+    /// The id of the network the state was taken from.
+    pub network_id: u8,
+    /// The state version the snapshot was taken at.
+    pub state_version: u64,
+    /// The state root hash at [`Self::state_version`], if the source database tracks it.
+    pub state_root_hash: Option<Hash>,
+    /// Whether the snapshot only contains a subset of the source database's entities. A partial
+    /// snapshot is expected to be imported on top of an already bootstrapped database.
+    pub is_partial: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct PartitionSnapshot {
+    pub partition_key: DbPartitionKey,
+    pub substates: Vec<(DbSortKey, DbSubstateValue)>,
+}
+
+#[derive(Debug)]
+pub enum SubstateSnapshotError {
+    IOError(std::io::Error),
+    EncodeError(EncodeError),
+    DecodeError(DecodeError),
+    InvalidMagic,
+    UnsupportedFormatVersion(u8),
+}
+
+impl From<std::io::Error> for SubstateSnapshotError {
+    fn from(value: std::io::Error) -> Self {
+        Self::IOError(value)
+    }
+}
+
+pub struct SubstateSnapshotWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> SubstateSnapshotWriter<W> {
+    pub fn new(
+        mut writer: W,
+        header: &SubstateSnapshotHeader,
+    ) -> Result<Self, SubstateSnapshotError> {
+        writer.write_all(&SUBSTATE_SNAPSHOT_MAGIC)?;
+        writer.write_all(&[SUBSTATE_SNAPSHOT_FORMAT_VERSION])?;
+        let mut snapshot_writer = Self { writer };
+        snapshot_writer.write_chunk(header)?;
+        Ok(snapshot_writer)
+    }
+
+    pub fn write_partition(
+        &mut self,
+        partition: &PartitionSnapshot,
+    ) -> Result<(), SubstateSnapshotError> {
+        self.write_chunk(partition)
+    }
+
+    /// Writes all entries of the given partition, split into chunks of at most
+    /// [`MAX_SUBSTATES_PER_CHUNK`] substates.
+    pub fn write_partition_from<S: SubstateDatabase + ?Sized>(
+        &mut self,
+        database: &S,
+        partition_key: &DbPartitionKey,
+    ) -> Result<(), SubstateSnapshotError> {
+        let mut entries = database.list_entries(partition_key).peekable();
+        while entries.peek().is_some() {
+            let substates = entries.by_ref().take(MAX_SUBSTATES_PER_CHUNK).collect();
+            self.write_chunk(&PartitionSnapshot {
+                partition_key: partition_key.clone(),
+                substates,
+            })?;
+        }
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<W, SubstateSnapshotError> {
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_chunk<T: ScryptoEncode + ?Sized>(
+        &mut self,
+        chunk: &T,
+    ) -> Result<(), SubstateSnapshotError> {
+        let payload = scrypto_encode(chunk).map_err(SubstateSnapshotError::EncodeError)?;
+        let length = u32::try_from(payload.len()).expect("Chunk length should fit into u32");
+        self.writer.write_all(&length.to_le_bytes())?;
+        self.writer.write_all(&payload)?;
+        Ok(())
+    }
+}
+
+/// Writes a snapshot of all partitions of the given database which match the filter.
+/// Returns the number of partitions written.
+pub fn write_substate_snapshot<S, W, F>(
+    database: &S,
+    header: &SubstateSnapshotHeader,
+    mut partition_filter: F,
+    writer: W,
+) -> Result<(W, usize), SubstateSnapshotError>
+where
+    S: SubstateDatabase + ListableSubstateDatabase,
+    W: Write,
+    F: FnMut(&DbPartitionKey) -> bool,
+{
+    let mut snapshot_writer = SubstateSnapshotWriter::new(writer, header)?;
+    let mut partition_count = 0;
+    for partition_key in database.list_partition_keys() {
+        if !partition_filter(&partition_key) {
+            continue;
+        }
+        snapshot_writer.write_partition_from(database, &partition_key)?;
+        partition_count += 1;
+    }
+    Ok((snapshot_writer.finish()?, partition_count))
+}
+
+pub struct SubstateSnapshotReader<R: Read> {
+    reader: R,
+    header: SubstateSnapshotHeader,
+}
+
+impl<R: Read> SubstateSnapshotReader<R> {
+    pub fn new(mut reader: R) -> Result<Self, SubstateSnapshotError> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if magic != SUBSTATE_SNAPSHOT_MAGIC {
+            return Err(SubstateSnapshotError::InvalidMagic);
+        }
+        let mut format_version = [0u8; 1];
+        reader.read_exact(&mut format_version)?;
+        if format_version[0] != SUBSTATE_SNAPSHOT_FORMAT_VERSION {
+            return Err(SubstateSnapshotError::UnsupportedFormatVersion(
+                format_version[0],
+            ));
+        }
+        let header = Self::read_chunk(&mut reader)?.ok_or_else(|| {
+            SubstateSnapshotError::IOError(std::io::ErrorKind::UnexpectedEof.into())
+        })?;
+        Ok(Self { reader, header })
+    }
+
+    pub fn header(&self) -> &SubstateSnapshotHeader {
+        &self.header
+    }
+
+    /// Commits all partitions of the snapshot into the given database, replacing any existing
+    /// content of these partitions. Returns the number of partitions imported.
+    pub fn import_into<D: CommittableSubstateDatabase + ?Sized>(
+        self,
+        database: &mut D,
+    ) -> Result<usize, SubstateSnapshotError> {
+        let mut partition_count = 0;
+        let mut previous_partition_key = None;
+        for partition in self {
+            let partition = partition?;
+            // Only the first chunk of a partition resets it - the next ones extend it
+            let is_continuation = previous_partition_key.as_ref() == Some(&partition.partition_key);
+            let partition_updates = if is_continuation {
+                PartitionDatabaseUpdates::Delta {
+                    substate_updates: partition
+                        .substates
+                        .into_iter()
+                        .map(|(sort_key, value)| (sort_key, DatabaseUpdate::Set(value)))
+                        .collect(),
+                }
+            } else {
+                partition_count += 1;
+                PartitionDatabaseUpdates::Reset {
+                    new_substate_values: partition.substates.into_iter().collect(),
+                }
+            };
+            let DbPartitionKey {
+                node_key,
+                partition_num,
+            } = partition.partition_key.clone();
+            database.commit(&DatabaseUpdates {
+                node_updates: indexmap!(
+                    node_key => NodeDatabaseUpdates {
+                        partition_updates: indexmap!(partition_num => partition_updates),
+                    }
+                ),
+            });
+            previous_partition_key = Some(partition.partition_key);
+        }
+        Ok(partition_count)
+    }
+
+    fn read_chunk<T: ScryptoDecode>(reader: &mut R) -> Result<Option<T>, SubstateSnapshotError> {
+        let mut length = [0u8; 4];
+        // A clean end of the stream is only allowed on a chunk boundary
+        match reader.read(&mut length[..1])? {
+            0 => return Ok(None),
+            _ => reader.read_exact(&mut length[1..])?,
+        }
+        let mut payload = vec![0u8; u32::from_le_bytes(length) as usize];
+        reader.read_exact(&mut payload)?;
+        scrypto_decode(&payload)
+            .map(Some)
+            .map_err(SubstateSnapshotError::DecodeError)
+    }
+}
+
+impl<R: Read> Iterator for SubstateSnapshotReader<R> {
+    type Item = Result<PartitionSnapshot, SubstateSnapshotError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Self::read_chunk(&mut self.reader).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory_db::InMemorySubstateDatabase;
+
+    #[test]
+    fn snapshot_round_trips_through_in_memory_database() {
+        let mut source = InMemorySubstateDatabase::standard();
+        let large_partition = (0..MAX_SUBSTATES_PER_CHUNK as u32 + 10)
+            .map(|i| (DbSortKey(i.to_be_bytes().to_vec()), vec![1, 2, 3]))
+            .collect::<IndexMap<_, _>>();
+        source.commit(&DatabaseUpdates {
+            node_updates: indexmap!(
+                vec![1] => NodeDatabaseUpdates {
+                    partition_updates: indexmap!(
+                        0 => PartitionDatabaseUpdates::Reset {
+                            new_substate_values: large_partition,
+                        },
+                        7 => PartitionDatabaseUpdates::Reset {
+                            new_substate_values: indexmap!(DbSortKey(vec![9]) => vec![10]),
+                        }
+                    ),
+                },
+                vec![2] => NodeDatabaseUpdates {
+                    partition_updates: indexmap!(
+                        0 => PartitionDatabaseUpdates::Reset {
+                            new_substate_values: indexmap!(DbSortKey(vec![5]) => vec![6]),
+                        }
+                    ),
+                }
+            ),
+        });
+        let header = SubstateSnapshotHeader {
+            network_id: 0xf2,
+            state_version: 42,
+            state_root_hash: None,
+            is_partial: false,
+        };
+
+        let (bytes, partition_count) =
+            write_substate_snapshot(&source, &header, |_| true, Vec::new()).unwrap();
+        assert_eq!(partition_count, 3);
+
+        let reader = SubstateSnapshotReader::new(bytes.as_slice()).unwrap();
+        assert_eq!(reader.header(), &header);
+        let mut target = InMemorySubstateDatabase::standard();
+        assert_eq!(reader.import_into(&mut target).unwrap(), 3);
+        assert_eq!(target, source);
+    }
+
+    #[test]
+    fn snapshot_import_replaces_existing_partitions() {
+        let mut source = InMemorySubstateDatabase::standard();
+        source.commit(&DatabaseUpdates {
+            node_updates: indexmap!(
+                vec![1] => NodeDatabaseUpdates {
+                    partition_updates: indexmap!(
+                        0 => PartitionDatabaseUpdates::Reset {
+                            new_substate_values: indexmap!(DbSortKey(vec![1]) => vec![1]),
+                        }
+                    ),
+                }
+            ),
+        });
+        let mut target = InMemorySubstateDatabase::standard();
+        target.commit(&DatabaseUpdates {
+            node_updates: indexmap!(
+                vec![1] => NodeDatabaseUpdates {
+                    partition_updates: indexmap!(
+                        0 => PartitionDatabaseUpdates::Reset {
+                            new_substate_values: indexmap!(DbSortKey(vec![2]) => vec![2]),
+                        }
+                    ),
+                }
+            ),
+        });
+        let header = SubstateSnapshotHeader {
+            network_id: 0xf2,
+            state_version: 1,
+            state_root_hash: None,
+            is_partial: true,
+        };
+
+        let (bytes, _) = write_substate_snapshot(&source, &header, |_| true, Vec::new()).unwrap();
+        SubstateSnapshotReader::new(bytes.as_slice())
+            .unwrap()
+            .import_into(&mut target)
+            .unwrap();
+
+        assert_eq!(target, source);
+    }
+}
@@ -0,0 +1,928 @@
+use super::{ChangedSubstate, SubstateChangeCollector};
+use crate::typed_substate_layout::*;
+use radix_common::prelude::*;
+use radix_engine::blueprints::account::{
+    AccountField, AccountTypedFieldSubstateValue, AccountTypedSubstateKey,
+    AccountTypedSubstateValue,
+};
+use radix_engine::blueprints::transaction_tracker::TransactionStatusV1;
+use radix_engine::system::system_db_reader::SystemDatabaseReader;
+use radix_engine::system::system_substates::KeyValueEntrySubstate;
+use radix_engine::track::StateUpdates;
+use radix_engine::transaction::CommitResult;
+use radix_engine_interface::blueprints::account::{DefaultDepositRule, ResourcePreference};
+use radix_engine_interface::blueprints::package::{BlueprintVersionKey, CodeHash, VmType};
+use radix_engine_interface::blueprints::resource::{
+    AccessRule, ModuleRoleKey, OwnerRoleEntry, ResourceOrNonFungible, VaultFreezeFlags,
+};
+use radix_engine_interface::object_modules::metadata::MetadataValue;
+use radix_engine_interface::types::{IndexedScryptoValue, OuterObjectInfo};
+use radix_substate_store_impls::substate_database_overlay::SubstateDatabaseOverlay;
+use radix_substate_store_interface::db_key_mapper::{DatabaseKeyMapper, SpreadPrefixKeyMapper};
+use radix_substate_store_interface::interface::*;
+use radix_transactions::prelude::IntentHash;
+
+/// A change to the ledger state made by a committed transaction, interpreted with the knowledge
+/// of the native blueprints and modules.
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub struct ChangeRecord {
+    pub node_id: NodeId,
+    /// The global entity the changed node belongs to: either the node itself, or the entity
+    /// which (transitively) owns it. `None` if the ownership of the node isn't known.
+    pub owner: Option<GlobalAddress>,
+    pub change: TypedChange,
+}
+
+/// The typed changes cover the substates of the native blueprints and modules which are of
+/// interest to an indexer. Changes to any other substates (e.g. package schemas, pool state or
+/// generic Scrypto component state) are reported as [`TypedChange::SubstateChanged`].
+#[derive(Debug, Clone, PartialEq, Eq, ScryptoSbor)]
+pub enum TypedChange {
+    EntityCreated {
+        entity_type: EntityType,
+    },
+    EntityDeleted {
+        entity_type: EntityType,
+    },
+    FungibleVaultBalanceChanged {
+        resource_address: ResourceAddress,
+        previous_balance: Decimal,
+        new_balance: Decimal,
+    },
+    NonFungibleVaultBalanceChanged {
+        resource_address: ResourceAddress,
+        previous_balance: Decimal,
+        new_balance: Decimal,
+    },
+    NonFungibleDeposited {
+        resource_address: ResourceAddress,
+        local_id: NonFungibleLocalId,
+    },
+    NonFungibleWithdrawn {
+        resource_address: ResourceAddress,
+        local_id: NonFungibleLocalId,
+    },
+    /// A fungible or non-fungible vault was frozen or unfrozen.
+    VaultFreezeChanged {
+        resource_address: ResourceAddress,
+        previous_frozen: VaultFreezeFlags,
+        new_frozen: VaultFreezeFlags,
+    },
+    /// The total supply of a resource changed. The changed node is the resource manager.
+    ResourceTotalSupplyChanged {
+        previous_total_supply: Decimal,
+        new_total_supply: Decimal,
+    },
+    /// The data of a non-fungible was created, updated or burnt. The changed node is the
+    /// non-fungible resource manager.
+    NonFungibleDataChanged {
+        local_id: NonFungibleLocalId,
+        previous_data: Option<ScryptoValue>,
+        new_data: Option<ScryptoValue>,
+    },
+    MetadataSet {
+        key: String,
+        previous_value: Option<MetadataValue>,
+        new_value: MetadataValue,
+    },
+    MetadataRemoved {
+        key: String,
+        previous_value: Option<MetadataValue>,
+    },
+    OwnerRoleChanged {
+        previous_owner_role: Option<OwnerRoleEntry>,
+        new_owner_role: OwnerRoleEntry,
+    },
+    /// The rule of a role was set or, with a `new_rule` of `None`, removed.
+    RoleChanged {
+        role_key: ModuleRoleKey,
+        previous_rule: Option<AccessRule>,
+        new_rule: Option<AccessRule>,
+    },
+    MethodRoyaltyChanged {
+        method_name: String,
+        previous_amount: Option<RoyaltyAmount>,
+        new_amount: Option<RoyaltyAmount>,
+    },
+    BlueprintDefined {
+        blueprint: BlueprintVersionKey,
+    },
+    PackageCodeAdded {
+        code_hash: CodeHash,
+        vm_type: VmType,
+    },
+    ConsensusManagerStateChanged {
+        previous_state: Option<ConsensusManagerSubstate>,
+        new_state: ConsensusManagerSubstate,
+    },
+    ProposerTimestampChanged {
+        previous_epoch_milli: Option<i64>,
+        new_epoch_milli: i64,
+    },
+    ValidatorStateChanged {
+        previous_state: Option<Box<ValidatorSubstate>>,
+        new_state: Box<ValidatorSubstate>,
+    },
+    ValidatorProtocolUpdateReadinessSignalled {
+        protocol_version_name: Option<String>,
+    },
+    AccountDepositRuleChanged {
+        previous_rule: Option<DefaultDepositRule>,
+        new_rule: DefaultDepositRule,
+    },
+    /// The deposit preference of an account for a resource was set or, with a `new_preference`
+    /// of `None`, removed.
+    AccountResourcePreferenceChanged {
+        resource_address: ResourceAddress,
+        previous_preference: Option<ResourcePreference>,
+        new_preference: Option<ResourcePreference>,
+    },
+    AccountAuthorizedDepositorAdded {
+        badge: ResourceOrNonFungible,
+    },
+    AccountAuthorizedDepositorRemoved {
+        badge: ResourceOrNonFungible,
+    },
+    /// The status of an intent was recorded by the transaction tracker.
+    TransactionStatusChanged {
+        intent_hash: IntentHash,
+        new_status: Option<TransactionStatusV1>,
+    },
+    /// Any other substate, with its values decoded without a schema. The substate can be
+    /// interpreted further with [`ChangeRecord::typed_substate_key`] and the functions of the
+    /// typed substate layout.
+    SubstateChanged {
+        partition_number: PartitionNumber,
+        substate_key: Option<SubstateKey>,
+        previous_value: Option<ScryptoValue>,
+        new_value: Option<ScryptoValue>,
+    },
+}
+
+impl ChangeRecord {
+    pub fn typed_substate_key(&self) -> Option<Result<TypedSubstateKey, String>> {
+        let TypedChange::SubstateChanged {
+            partition_number,
+            substate_key: Some(substate_key),
+            ..
+        } = &self.change
+        else {
+            return None;
+        };
+        let entity_type = self.node_id.entity_type()?;
+        Some(to_typed_substate_key(
+            entity_type,
+            *partition_number,
+            substate_key,
+        ))
+    }
+}
+
+/// Turns the state updates of committed transactions into a feed of [`ChangeRecord`]s.
+///
+/// Resolving the owner of a changed node requires knowing the ownership tree of the ledger, which
+/// the feed keeps track of from the substates written by each transaction. It therefore needs to
+/// see the state updates of every transaction, in order, from the state it was created for.
+pub struct ChangeFeed {
+    parents: NonIterMap<NodeId, NodeId>,
+    children: NonIterMap<NodeId, IndexSet<NodeId>>,
+}
+
+impl Default for ChangeFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChangeFeed {
+    /// Creates a change feed for a ledger which starts out empty, e.g. when replaying a whole
+    /// ledger from genesis.
+    pub fn new() -> Self {
+        Self {
+            parents: NonIterMap::new(),
+            children: NonIterMap::new(),
+        }
+    }
+
+    /// Creates a change feed for a ledger which continues from the given database. Every substate
+    /// of the database is read to find the owned nodes, which can take a while on a large ledger.
+    pub fn for_database<S: SubstateDatabase + ListableSubstateDatabase>(substate_db: &S) -> Self {
+        let mut change_feed = Self::new();
+        for partition_key in substate_db.list_partition_keys() {
+            let (node_id, _) = SpreadPrefixKeyMapper::from_db_partition_key(&partition_key);
+            for (_, value) in substate_db.list_entries(&partition_key) {
+                change_feed.record_owned_nodes(node_id, &value);
+            }
+        }
+        change_feed
+    }
+
+    /// Drives the change feed over a sequence of transactions. The `execute_next` closure
+    /// executes the next transaction against the current state of the database, and returns
+    /// `None` once there are no more transactions. The state updates of each transaction are
+    /// committed to the database after their changes have been read.
+    pub fn replay<D, F>(self, database: &mut D, execute_next: F) -> ChangeFeedIter<'_, D, F>
+    where
+        D: SubstateDatabase + CommittableSubstateDatabase,
+        F: FnMut(&D) -> Option<StateUpdates>,
+    {
+        ChangeFeedIter {
+            change_feed: self,
+            database,
+            execute_next,
+        }
+    }
+
+    pub fn changes_of_commit<S: SubstateDatabase>(
+        &mut self,
+        pre_state: &S,
+        commit_result: &CommitResult,
+    ) -> Vec<ChangeRecord> {
+        self.changes(pre_state, &commit_result.state_updates)
+    }
+
+    /// Reads the changes made by the given state updates, which haven't been committed to the
+    /// `pre_state` database yet.
+    pub fn changes<S: SubstateDatabase>(
+        &mut self,
+        pre_state: &S,
+        state_updates: &StateUpdates,
+    ) -> Vec<ChangeRecord> {
+        self.changes_and_attached_nodes(pre_state, state_updates).0
+    }
+
+    /// As [`ChangeFeed::changes`], but also returns the nodes which were attached to a new parent
+    /// (including newly created nodes), as every node they own may now belong to a different
+    /// global entity.
+    pub(crate) fn changes_and_attached_nodes<S: SubstateDatabase>(
+        &mut self,
+        pre_state: &S,
+        state_updates: &StateUpdates,
+    ) -> (Vec<ChangeRecord>, IndexSet<NodeId>) {
+        let database_updates = state_updates.create_database_updates::<SpreadPrefixKeyMapper>();
+        let mut post_state = SubstateDatabaseOverlay::new_unmergeable(pre_state);
+        post_state.commit(&database_updates);
+        let changed_substates =
+            SubstateChangeCollector::new(pre_state, &post_state).collect(&database_updates);
+
+        // The owners of nodes created by the transaction are only known once all of its
+        // substates have been seen.
+        let mut attached_nodes = index_set_new();
+        for changed_substate in &changed_substates {
+            if let Some(new_value) = &changed_substate.new_value {
+                attached_nodes.extend(self.record_owned_nodes(changed_substate.node_id, new_value));
+            }
+        }
+
+        let pre_state_reader = SystemDatabaseReader::new(pre_state);
+        let post_state_reader = SystemDatabaseReader::new(&post_state);
+        let changes = changed_substates
+            .into_iter()
+            .map(|changed_substate| {
+                // Deleted nodes can only be resolved against the previous state.
+                let outer_object = post_state_reader
+                    .get_object_info(changed_substate.node_id)
+                    .or_else(|_| pre_state_reader.get_object_info(changed_substate.node_id))
+                    .ok()
+                    .and_then(
+                        |object_info| match object_info.blueprint_info.outer_obj_info {
+                            OuterObjectInfo::Some { outer_object } => Some(outer_object),
+                            OuterObjectInfo::None => None,
+                        },
+                    );
+                ChangeRecord {
+                    node_id: changed_substate.node_id,
+                    owner: self.owner_of(&changed_substate.node_id),
+                    change: to_typed_change(&changed_substate, outer_object),
+                }
+            })
+            .collect();
+        (changes, attached_nodes)
+    }
+
+    /// Resolves the global entity a node belongs to.
+    pub fn owner_of(&self, node_id: &NodeId) -> Option<GlobalAddress> {
+        let mut node_id = *node_id;
+        loop {
+            if node_id.is_global() {
+                return Some(GlobalAddress::new_or_panic(node_id.0));
+            }
+            node_id = *self.parents.get(&node_id)?;
+        }
+    }
+
+    /// The given node, and every node it (transitively) owns.
+    pub(crate) fn owned_subtree(&self, node_id: &NodeId) -> Vec<NodeId> {
+        let mut subtree = vec![*node_id];
+        let mut next = 0;
+        while let Some(node_id) = subtree.get(next) {
+            if let Some(children) = self.children.get(node_id) {
+                subtree.extend(children.iter().cloned());
+            }
+            next += 1;
+        }
+        subtree
+    }
+
+    /// Records the nodes owned by the given substate value, and returns those which weren't
+    /// owned by the node before.
+    fn record_owned_nodes(&mut self, node_id: NodeId, value: &DbSubstateValue) -> Vec<NodeId> {
+        let Ok(value) = IndexedScryptoValue::from_slice(value) else {
+            return vec![];
+        };
+        let mut attached_nodes = vec![];
+        for owned_node_id in value.owned_nodes() {
+            let previous_parent = self.parents.insert(*owned_node_id, node_id);
+            if previous_parent == Some(node_id) {
+                continue;
+            }
+            if let Some(previous_parent) = previous_parent {
+                if let Some(siblings) = self.children.get_mut(&previous_parent) {
+                    siblings.swap_remove(owned_node_id);
+                }
+            }
+            self.children
+                .entry(node_id)
+                .or_default()
+                .insert(*owned_node_id);
+            attached_nodes.push(*owned_node_id);
+        }
+        attached_nodes
+    }
+}
+
+/// An iterator over the changes made by each transaction of a replay, see [`ChangeFeed::replay`].
+pub struct ChangeFeedIter<'d, D, F> {
+    change_feed: ChangeFeed,
+    database: &'d mut D,
+    execute_next: F,
+}
+
+impl<'d, D, F> ChangeFeedIter<'d, D, F> {
+    pub fn change_feed(&self) -> &ChangeFeed {
+        &self.change_feed
+    }
+}
+
+impl<'d, D, F> Iterator for ChangeFeedIter<'d, D, F>
+where
+    D: SubstateDatabase + CommittableSubstateDatabase,
+    F: FnMut(&D) -> Option<StateUpdates>,
+{
+    type Item = Vec<ChangeRecord>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let state_updates = (self.execute_next)(self.database)?;
+        let changes = self.change_feed.changes(self.database, &state_updates);
+        self.database
+            .commit(&state_updates.create_database_updates::<SpreadPrefixKeyMapper>());
+        Some(changes)
+    }
+}
+
+fn to_typed_change(
+    changed_substate: &ChangedSubstate,
+    outer_object: Option<GlobalAddress>,
+) -> TypedChange {
+    let typed_key = changed_substate.typed_key().ok();
+    let previous_value = changed_substate
+        .typed_previous_value()
+        .and_then(|value| value.ok());
+    let new_value = changed_substate
+        .typed_new_value()
+        .and_then(|value| value.ok());
+    let resource_address =
+        outer_object.and_then(|outer_object| ResourceAddress::try_from(outer_object).ok());
+
+    match (typed_key, resource_address) {
+        (Some(TypedSubstateKey::TypeInfo(_)), _) => {
+            if let Some(entity_type) = changed_substate.node_id.entity_type() {
+                match (
+                    &changed_substate.previous_value,
+                    &changed_substate.new_value,
+                ) {
+                    (None, Some(_)) => return TypedChange::EntityCreated { entity_type },
+                    (Some(_), None) => return TypedChange::EntityDeleted { entity_type },
+                    _ => {}
+                }
+            }
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::FungibleVault(
+                FungibleVaultTypedSubstateKey::Field(FungibleVaultField::Balance),
+            ))),
+            Some(resource_address),
+        ) => {
+            let balance = |value: Option<TypedSubstateValue>| match value {
+                Some(TypedSubstateValue::MainModule(
+                    TypedMainModuleSubstateValue::FungibleVault(
+                        FungibleVaultTypedSubstateValue::Field(
+                            FungibleVaultTypedFieldSubstateValue::Balance(balance),
+                        ),
+                    ),
+                )) => balance
+                    .into_payload()
+                    .fully_update_and_into_latest_version()
+                    .amount(),
+                _ => Decimal::ZERO,
+            };
+            return TypedChange::FungibleVaultBalanceChanged {
+                resource_address,
+                previous_balance: balance(previous_value),
+                new_balance: balance(new_value),
+            };
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::NonFungibleVault(
+                NonFungibleVaultTypedSubstateKey::Field(NonFungibleVaultField::Balance),
+            ))),
+            Some(resource_address),
+        ) => {
+            let balance = |value: Option<TypedSubstateValue>| match value {
+                Some(TypedSubstateValue::MainModule(
+                    TypedMainModuleSubstateValue::NonFungibleVault(
+                        NonFungibleVaultTypedSubstateValue::Field(
+                            NonFungibleVaultTypedFieldSubstateValue::Balance(balance),
+                        ),
+                    ),
+                )) => {
+                    balance
+                        .into_payload()
+                        .fully_update_and_into_latest_version()
+                        .amount
+                }
+                _ => Decimal::ZERO,
+            };
+            return TypedChange::NonFungibleVaultBalanceChanged {
+                resource_address,
+                previous_balance: balance(previous_value),
+                new_balance: balance(new_value),
+            };
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::NonFungibleVault(
+                NonFungibleVaultTypedSubstateKey::NonFungibleIndexEntry(key),
+            ))),
+            Some(resource_address),
+        ) => {
+            let local_id = key.content;
+            match (
+                &changed_substate.previous_value,
+                &changed_substate.new_value,
+            ) {
+                (None, Some(_)) => {
+                    return TypedChange::NonFungibleDeposited {
+                        resource_address,
+                        local_id,
+                    }
+                }
+                (Some(_), None) => {
+                    return TypedChange::NonFungibleWithdrawn {
+                        resource_address,
+                        local_id,
+                    }
+                }
+                _ => {}
+            }
+        }
+        (
+            Some(TypedSubstateKey::MainModule(
+                TypedMainModuleSubstateKey::NonFungibleResourceManager(
+                    NonFungibleResourceManagerTypedSubstateKey::DataKeyValueEntry(key),
+                ),
+            )),
+            _,
+        ) => {
+            // The data is of a type only known from the generic substitution of the resource.
+            let data = |value: &Option<DbSubstateValue>| {
+                value
+                    .as_ref()
+                    .and_then(|value| {
+                        scrypto_decode::<KeyValueEntrySubstate<ScryptoValue>>(value).ok()
+                    })
+                    .and_then(|entry| entry.into_value())
+            };
+            return TypedChange::NonFungibleDataChanged {
+                local_id: key.content,
+                previous_data: data(&changed_substate.previous_value),
+                new_data: data(&changed_substate.new_value),
+            };
+        }
+        (
+            Some(TypedSubstateKey::MetadataModule(
+                TypedMetadataModuleSubstateKey::MetadataEntryKey(key),
+            )),
+            _,
+        ) => {
+            let metadata_value = |value: Option<TypedSubstateValue>| match value {
+                Some(TypedSubstateValue::MetadataModule(
+                    TypedMetadataModuleSubstateValue::MetadataEntry(entry),
+                )) => entry
+                    .into_value()
+                    .map(|value| value.fully_update_and_into_latest_version()),
+                _ => None,
+            };
+            let previous_value = metadata_value(previous_value);
+            return match metadata_value(new_value) {
+                Some(new_value) => TypedChange::MetadataSet {
+                    key,
+                    previous_value,
+                    new_value,
+                },
+                None => TypedChange::MetadataRemoved {
+                    key,
+                    previous_value,
+                },
+            };
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::FungibleVault(
+                FungibleVaultTypedSubstateKey::Field(FungibleVaultField::FreezeStatus),
+            ))),
+            Some(resource_address),
+        ) => {
+            let frozen = |value: Option<TypedSubstateValue>| match main_module_value(value) {
+                Some(TypedMainModuleSubstateValue::FungibleVault(
+                    FungibleVaultTypedSubstateValue::Field(
+                        FungibleVaultTypedFieldSubstateValue::FreezeStatus(freeze_status),
+                    ),
+                )) => {
+                    freeze_status
+                        .into_payload()
+                        .fully_update_and_into_latest_version()
+                        .frozen
+                }
+                _ => VaultFreezeFlags::empty(),
+            };
+            return TypedChange::VaultFreezeChanged {
+                resource_address,
+                previous_frozen: frozen(previous_value),
+                new_frozen: frozen(new_value),
+            };
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::NonFungibleVault(
+                NonFungibleVaultTypedSubstateKey::Field(NonFungibleVaultField::FreezeStatus),
+            ))),
+            Some(resource_address),
+        ) => {
+            let frozen = |value: Option<TypedSubstateValue>| match main_module_value(value) {
+                Some(TypedMainModuleSubstateValue::NonFungibleVault(
+                    NonFungibleVaultTypedSubstateValue::Field(
+                        NonFungibleVaultTypedFieldSubstateValue::FreezeStatus(freeze_status),
+                    ),
+                )) => {
+                    freeze_status
+                        .into_payload()
+                        .fully_update_and_into_latest_version()
+                        .frozen
+                }
+                _ => VaultFreezeFlags::empty(),
+            };
+            return TypedChange::VaultFreezeChanged {
+                resource_address,
+                previous_frozen: frozen(previous_value),
+                new_frozen: frozen(new_value),
+            };
+        }
+        (
+            Some(TypedSubstateKey::MainModule(
+                TypedMainModuleSubstateKey::FungibleResourceManager(
+                    FungibleResourceManagerTypedSubstateKey::Field(
+                        FungibleResourceManagerField::TotalSupply,
+                    ),
+                ),
+            )),
+            _,
+        ) => {
+            let total_supply = |value: Option<TypedSubstateValue>| match main_module_value(value) {
+                Some(TypedMainModuleSubstateValue::FungibleResourceManager(
+                    FungibleResourceManagerTypedSubstateValue::Field(
+                        FungibleResourceManagerTypedFieldSubstateValue::TotalSupply(total_supply),
+                    ),
+                )) => total_supply
+                    .into_payload()
+                    .fully_update_and_into_latest_version(),
+                _ => Decimal::ZERO,
+            };
+            return TypedChange::ResourceTotalSupplyChanged {
+                previous_total_supply: total_supply(previous_value),
+                new_total_supply: total_supply(new_value),
+            };
+        }
+        (
+            Some(TypedSubstateKey::MainModule(
+                TypedMainModuleSubstateKey::NonFungibleResourceManager(
+                    NonFungibleResourceManagerTypedSubstateKey::Field(
+                        NonFungibleResourceManagerField::TotalSupply,
+                    ),
+                ),
+            )),
+            _,
+        ) => {
+            let total_supply = |value: Option<TypedSubstateValue>| match main_module_value(value) {
+                Some(TypedMainModuleSubstateValue::NonFungibleResourceManager(
+                    NonFungibleResourceManagerTypedSubstateValue::Field(
+                        NonFungibleResourceManagerTypedFieldSubstateValue::TotalSupply(
+                            total_supply,
+                        ),
+                    ),
+                )) => total_supply
+                    .into_payload()
+                    .fully_update_and_into_latest_version(),
+                _ => Decimal::ZERO,
+            };
+            return TypedChange::ResourceTotalSupplyChanged {
+                previous_total_supply: total_supply(previous_value),
+                new_total_supply: total_supply(new_value),
+            };
+        }
+        (
+            Some(TypedSubstateKey::RoleAssignmentModule(
+                TypedRoleAssignmentSubstateKey::RoleAssignmentField(RoleAssignmentField::Owner),
+            )),
+            _,
+        ) => {
+            let owner_role = |value: Option<TypedSubstateValue>| match value {
+                Some(TypedSubstateValue::RoleAssignmentModule(
+                    TypedRoleAssignmentModuleSubstateValue::OwnerRole(owner_role),
+                )) => Some(
+                    owner_role
+                        .into_payload()
+                        .fully_update_and_into_latest_version()
+                        .owner_role_entry,
+                ),
+                _ => None,
+            };
+            if let Some(new_owner_role) = owner_role(new_value) {
+                return TypedChange::OwnerRoleChanged {
+                    previous_owner_role: owner_role(previous_value),
+                    new_owner_role,
+                };
+            }
+        }
+        (
+            Some(TypedSubstateKey::RoleAssignmentModule(TypedRoleAssignmentSubstateKey::Rule(
+                role_key,
+            ))),
+            _,
+        ) => {
+            let rule = |value: Option<TypedSubstateValue>| match value {
+                Some(TypedSubstateValue::RoleAssignmentModule(
+                    TypedRoleAssignmentModuleSubstateValue::Rule(entry),
+                )) => entry
+                    .into_value()
+                    .map(|rule| rule.fully_update_and_into_latest_version()),
+                _ => None,
+            };
+            return TypedChange::RoleChanged {
+                role_key,
+                previous_rule: rule(previous_value),
+                new_rule: rule(new_value),
+            };
+        }
+        (
+            Some(TypedSubstateKey::RoyaltyModule(
+                TypedRoyaltyModuleSubstateKey::RoyaltyMethodRoyaltyEntryKey(method_name),
+            )),
+            _,
+        ) => {
+            let amount = |value: Option<TypedSubstateValue>| match value {
+                Some(TypedSubstateValue::RoyaltyModule(
+                    TypedRoyaltyModuleSubstateValue::ComponentMethodRoyalty(entry),
+                )) => entry
+                    .into_value()
+                    .map(|amount| amount.fully_update_and_into_latest_version()),
+                _ => None,
+            };
+            return TypedChange::MethodRoyaltyChanged {
+                method_name,
+                previous_amount: amount(previous_value),
+                new_amount: amount(new_value),
+            };
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::Package(
+                PackageTypedSubstateKey::BlueprintVersionDefinitionKeyValueEntry(key),
+            ))),
+            _,
+        ) if changed_substate.previous_value.is_none() => {
+            return TypedChange::BlueprintDefined {
+                blueprint: key.content,
+            };
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::Package(
+                PackageTypedSubstateKey::CodeVmTypeKeyValueEntry(key),
+            ))),
+            _,
+        ) if changed_substate.previous_value.is_none() => {
+            if let Some(TypedMainModuleSubstateValue::Package(
+                PackageTypedSubstateValue::CodeVmTypeKeyValue(entry),
+            )) = main_module_value(new_value)
+            {
+                if let Some(vm_type) = entry.into_value() {
+                    return TypedChange::PackageCodeAdded {
+                        code_hash: key.content,
+                        vm_type: vm_type.fully_update_and_into_latest_version().vm_type,
+                    };
+                }
+            }
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::ConsensusManager(
+                ConsensusManagerTypedSubstateKey::Field(ConsensusManagerField::State),
+            ))),
+            _,
+        ) => {
+            let state = |value: Option<TypedSubstateValue>| match main_module_value(value) {
+                Some(TypedMainModuleSubstateValue::ConsensusManager(
+                    ConsensusManagerTypedSubstateValue::Field(
+                        ConsensusManagerTypedFieldSubstateValue::State(state),
+                    ),
+                )) => Some(state.into_payload().fully_update_and_into_latest_version()),
+                _ => None,
+            };
+            if let Some(new_state) = state(new_value) {
+                return TypedChange::ConsensusManagerStateChanged {
+                    previous_state: state(previous_value),
+                    new_state,
+                };
+            }
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::ConsensusManager(
+                ConsensusManagerTypedSubstateKey::Field(
+                    ConsensusManagerField::ProposerMilliTimestamp,
+                ),
+            ))),
+            _,
+        ) => {
+            let epoch_milli = |value: Option<TypedSubstateValue>| match main_module_value(value) {
+                Some(TypedMainModuleSubstateValue::ConsensusManager(
+                    ConsensusManagerTypedSubstateValue::Field(
+                        ConsensusManagerTypedFieldSubstateValue::ProposerMilliTimestamp(timestamp),
+                    ),
+                )) => Some(
+                    timestamp
+                        .into_payload()
+                        .fully_update_and_into_latest_version()
+                        .epoch_milli,
+                ),
+                _ => None,
+            };
+            if let Some(new_epoch_milli) = epoch_milli(new_value) {
+                return TypedChange::ProposerTimestampChanged {
+                    previous_epoch_milli: epoch_milli(previous_value),
+                    new_epoch_milli,
+                };
+            }
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::ValidatorField(
+                ValidatorTypedSubstateKey::Field(ValidatorField::State),
+            ))),
+            _,
+        ) => {
+            let state = |value: Option<TypedSubstateValue>| match main_module_value(value) {
+                Some(TypedMainModuleSubstateValue::Validator(
+                    ValidatorTypedSubstateValue::Field(ValidatorTypedFieldSubstateValue::State(
+                        state,
+                    )),
+                )) => Some(Box::new(
+                    state.into_payload().fully_update_and_into_latest_version(),
+                )),
+                _ => None,
+            };
+            if let Some(new_state) = state(new_value) {
+                return TypedChange::ValidatorStateChanged {
+                    previous_state: state(previous_value),
+                    new_state,
+                };
+            }
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::ValidatorField(
+                ValidatorTypedSubstateKey::Field(ValidatorField::ProtocolUpdateReadinessSignal),
+            ))),
+            _,
+        ) => {
+            if let Some(TypedMainModuleSubstateValue::Validator(
+                ValidatorTypedSubstateValue::Field(
+                    ValidatorTypedFieldSubstateValue::ProtocolUpdateReadinessSignal(signal),
+                ),
+            )) = main_module_value(new_value)
+            {
+                return TypedChange::ValidatorProtocolUpdateReadinessSignalled {
+                    protocol_version_name: signal
+                        .into_payload()
+                        .fully_update_and_into_latest_version()
+                        .protocol_version_name,
+                };
+            }
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::Account(
+                AccountTypedSubstateKey::Field(AccountField::DepositRule),
+            ))),
+            _,
+        ) => {
+            let deposit_rule = |value: Option<TypedSubstateValue>| match main_module_value(value) {
+                Some(TypedMainModuleSubstateValue::Account(AccountTypedSubstateValue::Field(
+                    AccountTypedFieldSubstateValue::DepositRule(deposit_rule),
+                ))) => Some(
+                    deposit_rule
+                        .into_payload()
+                        .fully_update_and_into_latest_version()
+                        .default_deposit_rule,
+                ),
+                _ => None,
+            };
+            if let Some(new_rule) = deposit_rule(new_value) {
+                return TypedChange::AccountDepositRuleChanged {
+                    previous_rule: deposit_rule(previous_value),
+                    new_rule,
+                };
+            }
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::Account(
+                AccountTypedSubstateKey::ResourcePreferenceKeyValueEntry(key),
+            ))),
+            _,
+        ) => {
+            let preference = |value: Option<TypedSubstateValue>| match main_module_value(value) {
+                Some(TypedMainModuleSubstateValue::Account(
+                    AccountTypedSubstateValue::ResourcePreferenceKeyValue(entry),
+                )) => entry
+                    .into_value()
+                    .map(|preference| preference.fully_update_and_into_latest_version()),
+                _ => None,
+            };
+            return TypedChange::AccountResourcePreferenceChanged {
+                resource_address: key.content,
+                previous_preference: preference(previous_value),
+                new_preference: preference(new_value),
+            };
+        }
+        (
+            Some(TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::Account(
+                AccountTypedSubstateKey::AuthorizedDepositorKeyValueEntry(key),
+            ))),
+            _,
+        ) => {
+            let is_authorized = |value: Option<TypedSubstateValue>| match main_module_value(value) {
+                Some(TypedMainModuleSubstateValue::Account(
+                    AccountTypedSubstateValue::AuthorizedDepositorKeyValue(entry),
+                )) => entry.into_value().is_some(),
+                _ => false,
+            };
+            match (is_authorized(previous_value), is_authorized(new_value)) {
+                (false, true) => {
+                    return TypedChange::AccountAuthorizedDepositorAdded { badge: key.content }
+                }
+                (true, false) => {
+                    return TypedChange::AccountAuthorizedDepositorRemoved { badge: key.content }
+                }
+                _ => {}
+            }
+        }
+        (
+            Some(TypedSubstateKey::MainModule(
+                TypedMainModuleSubstateKey::TransactionTrackerCollectionEntry(intent_hash),
+            )),
+            _,
+        ) => {
+            let new_status = match main_module_value(new_value) {
+                Some(TypedMainModuleSubstateValue::TransactionTrackerCollectionEntry(entry)) => {
+                    entry.into_value().map(|status| status.into_v1())
+                }
+                _ => None,
+            };
+            return TypedChange::TransactionStatusChanged {
+                intent_hash,
+                new_status,
+            };
+        }
+        _ => {}
+    }
+
+    let decode = |value: &Option<DbSubstateValue>| {
+        value
+            .as_ref()
+            .and_then(|value| scrypto_decode::<ScryptoValue>(value).ok())
+    };
+    TypedChange::SubstateChanged {
+        partition_number: changed_substate.partition_number,
+        substate_key: changed_substate.substate_key.clone(),
+        previous_value: decode(&changed_substate.previous_value),
+        new_value: decode(&changed_substate.new_value),
+    }
+}
+
+fn main_module_value(value: Option<TypedSubstateValue>) -> Option<TypedMainModuleSubstateValue> {
+    match value {
+        Some(TypedSubstateValue::MainModule(value)) => Some(value),
+        _ => None,
+    }
+}
@@ -0,0 +1,285 @@
+use radix_common::prelude::*;
+use radix_engine::object_modules::metadata::{MetadataCollection, MetadataEntryEntryPayload};
+use radix_engine::system::system_db_reader::SystemDatabaseReader;
+use radix_engine_interface::api::ModuleId;
+use radix_engine_interface::object_modules::metadata::*;
+use radix_engine_interface::types::CollectionDescriptor;
+use radix_substate_store_interface::interface::SubstateDatabase;
+
+pub const METADATA_KEY_NAME: &str = "name";
+pub const METADATA_KEY_SYMBOL: &str = "symbol";
+pub const METADATA_KEY_DESCRIPTION: &str = "description";
+pub const METADATA_KEY_TAGS: &str = "tags";
+pub const METADATA_KEY_ICON_URL: &str = "icon_url";
+pub const METADATA_KEY_INFO_URL: &str = "info_url";
+pub const METADATA_KEY_ACCOUNT_TYPE: &str = "account_type";
+pub const METADATA_KEY_CLAIMED_ENTITIES: &str = "claimed_entities";
+pub const METADATA_KEY_CLAIMED_WEBSITES: &str = "claimed_websites";
+pub const METADATA_KEY_DAPP_DEFINITION: &str = "dapp_definition";
+pub const METADATA_KEY_DAPP_DEFINITIONS: &str = "dapp_definitions";
+
+pub const ACCOUNT_TYPE_DAPP_DEFINITION: &str = "dapp definition";
+
+/// A violation of the ecosystem's metadata standards found on an entity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MetadataStandardsIssue {
+    /// The entity doesn't exist, or has no metadata module.
+    EntityNotFound,
+    /// A well-known key holds a value of a type other than the one the standard prescribes.
+    UnexpectedType {
+        key: String,
+        expected_type_id: u8,
+        actual_type_id: u8,
+    },
+    /// The `account_type` is set to a value which isn't defined by the standard.
+    UnknownAccountType(String),
+    /// A key which only has a meaning on dApp definitions is set on an entity which isn't one.
+    NotADappDefinition { key: String },
+    /// The entity links to a dApp definition which doesn't exist or isn't a dApp definition.
+    LinkedEntityIsNotADappDefinition { dapp_definition: GlobalAddress },
+    /// The entity links to a dApp definition which doesn't claim it back.
+    DappDefinitionDoesNotClaimEntity { dapp_definition: GlobalAddress },
+    /// The dApp definition claims an entity which doesn't link back to it.
+    ClaimedEntityDoesNotLinkBack { claimed_entity: GlobalAddress },
+    /// The dApp definition links to another dApp definition which doesn't link back to it.
+    DappDefinitionDoesNotLinkBack { dapp_definition: GlobalAddress },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataStandardsReport {
+    pub entity: GlobalAddress,
+    pub issues: Vec<MetadataStandardsIssue>,
+}
+
+impl MetadataStandardsReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Validates the metadata of entities against the ecosystem's metadata standards, which aren't
+/// enforced by the metadata module itself:
+/// * Well-known keys (e.g. `name`, `icon_url` or `claimed_entities`) must hold values of the
+///   prescribed type.
+/// * Accounts with an `account_type` of `dapp definition` are dApp definitions, and are the only
+///   entities which may set `claimed_entities` and `claimed_websites`.
+/// * Links between entities and dApp definitions must be two-way. Resources link to their dApp
+///   definitions with `dapp_definitions`, whereas components and packages link to a single one
+///   with `dapp_definition`. A component is also considered claimed if its dApp definition claims
+///   the package of its blueprint.
+///
+/// Note that `claimed_websites` can only be type-checked here, since verifying them requires
+/// fetching the `.well-known/radix.json` file of each website.
+pub struct MetadataStandardsValidator<'s, S: SubstateDatabase + ?Sized> {
+    reader: SystemDatabaseReader<'s, S>,
+}
+
+impl<'s, S: SubstateDatabase + ?Sized> MetadataStandardsValidator<'s, S> {
+    pub fn new(substate_db: &'s S) -> Self {
+        Self {
+            reader: SystemDatabaseReader::new(substate_db),
+        }
+    }
+
+    /// Reads all metadata entries of an entity, or `None` if it has no metadata module.
+    pub fn read_metadata(&self, entity: GlobalAddress) -> Option<IndexMap<String, MetadataValue>> {
+        let entries = self
+            .reader
+            .collection_iter(
+                entity.as_node_id(),
+                ModuleId::Metadata,
+                MetadataCollection::EntryKeyValue.collection_index(),
+            )
+            .ok()?
+            .map(|(key, value)| {
+                let key = scrypto_decode::<String>(&key.into_map()).unwrap();
+                let value = scrypto_decode::<MetadataEntryEntryPayload>(&value).unwrap();
+                (key, value.fully_update_and_into_latest_version())
+            })
+            .collect();
+        Some(entries)
+    }
+
+    pub fn validate(&self, entity: GlobalAddress) -> MetadataStandardsReport {
+        let mut issues = Vec::new();
+        match self.read_metadata(entity) {
+            Some(metadata) => {
+                Self::check_types(&metadata, &mut issues);
+                if Self::is_dapp_definition(&metadata) {
+                    self.check_dapp_definition_links(entity, &metadata, &mut issues);
+                } else {
+                    Self::check_dapp_definition_keys_are_unused(&metadata, &mut issues);
+                    self.check_entity_links(entity, &metadata, &mut issues);
+                }
+            }
+            None => issues.push(MetadataStandardsIssue::EntityNotFound),
+        }
+        MetadataStandardsReport { entity, issues }
+    }
+
+    fn check_types(
+        metadata: &IndexMap<String, MetadataValue>,
+        issues: &mut Vec<MetadataStandardsIssue>,
+    ) {
+        for (key, value) in metadata {
+            let result = match key.as_str() {
+                METADATA_KEY_NAME
+                | METADATA_KEY_SYMBOL
+                | METADATA_KEY_DESCRIPTION
+                | METADATA_KEY_ACCOUNT_TYPE => check_type::<String>(value),
+                METADATA_KEY_TAGS => check_type::<Vec<String>>(value),
+                METADATA_KEY_ICON_URL | METADATA_KEY_INFO_URL => check_type::<UncheckedUrl>(value),
+                METADATA_KEY_CLAIMED_ENTITIES | METADATA_KEY_DAPP_DEFINITIONS => {
+                    check_type::<Vec<GlobalAddress>>(value)
+                }
+                METADATA_KEY_CLAIMED_WEBSITES => check_type::<Vec<UncheckedOrigin>>(value),
+                METADATA_KEY_DAPP_DEFINITION => check_type::<GlobalAddress>(value),
+                _ => Ok(()),
+            };
+            if let Err(MetadataConversionError::UnexpectedType {
+                expected_type_id,
+                actual_type_id,
+            }) = result
+            {
+                issues.push(MetadataStandardsIssue::UnexpectedType {
+                    key: key.clone(),
+                    expected_type_id,
+                    actual_type_id,
+                });
+            }
+        }
+
+        if let Some(MetadataValue::String(account_type)) = metadata.get(METADATA_KEY_ACCOUNT_TYPE) {
+            if account_type != ACCOUNT_TYPE_DAPP_DEFINITION {
+                issues.push(MetadataStandardsIssue::UnknownAccountType(
+                    account_type.clone(),
+                ));
+            }
+        }
+    }
+
+    fn check_dapp_definition_keys_are_unused(
+        metadata: &IndexMap<String, MetadataValue>,
+        issues: &mut Vec<MetadataStandardsIssue>,
+    ) {
+        for key in [METADATA_KEY_CLAIMED_ENTITIES, METADATA_KEY_CLAIMED_WEBSITES] {
+            if metadata.contains_key(key) {
+                issues.push(MetadataStandardsIssue::NotADappDefinition {
+                    key: key.to_string(),
+                });
+            }
+        }
+    }
+
+    fn check_entity_links(
+        &self,
+        entity: GlobalAddress,
+        metadata: &IndexMap<String, MetadataValue>,
+        issues: &mut Vec<MetadataStandardsIssue>,
+    ) {
+        for dapp_definition in linked_dapp_definitions(metadata) {
+            let dapp_definition_metadata = self
+                .read_metadata(dapp_definition)
+                .filter(Self::is_dapp_definition);
+            let Some(dapp_definition_metadata) = dapp_definition_metadata else {
+                issues.push(MetadataStandardsIssue::LinkedEntityIsNotADappDefinition {
+                    dapp_definition,
+                });
+                continue;
+            };
+            if !self.is_claimed_by(entity, &dapp_definition_metadata) {
+                issues.push(MetadataStandardsIssue::DappDefinitionDoesNotClaimEntity {
+                    dapp_definition,
+                });
+            }
+        }
+    }
+
+    fn check_dapp_definition_links(
+        &self,
+        dapp_definition: GlobalAddress,
+        metadata: &IndexMap<String, MetadataValue>,
+        issues: &mut Vec<MetadataStandardsIssue>,
+    ) {
+        for claimed_entity in global_addresses(metadata.get(METADATA_KEY_CLAIMED_ENTITIES)) {
+            let links_back = self
+                .read_metadata(claimed_entity)
+                .map(|claimed_entity_metadata| {
+                    linked_dapp_definitions(&claimed_entity_metadata).contains(&dapp_definition)
+                })
+                .unwrap_or(false);
+            if !links_back {
+                issues
+                    .push(MetadataStandardsIssue::ClaimedEntityDoesNotLinkBack { claimed_entity });
+            }
+        }
+
+        for other_dapp_definition in global_addresses(metadata.get(METADATA_KEY_DAPP_DEFINITIONS)) {
+            let links_back = self
+                .read_metadata(other_dapp_definition)
+                .filter(Self::is_dapp_definition)
+                .map(|other_metadata| {
+                    global_addresses(other_metadata.get(METADATA_KEY_DAPP_DEFINITIONS))
+                        .contains(&dapp_definition)
+                })
+                .unwrap_or(false);
+            if !links_back {
+                issues.push(MetadataStandardsIssue::DappDefinitionDoesNotLinkBack {
+                    dapp_definition: other_dapp_definition,
+                });
+            }
+        }
+    }
+
+    fn is_claimed_by(
+        &self,
+        entity: GlobalAddress,
+        dapp_definition_metadata: &IndexMap<String, MetadataValue>,
+    ) -> bool {
+        let claimed_entities =
+            global_addresses(dapp_definition_metadata.get(METADATA_KEY_CLAIMED_ENTITIES));
+        if claimed_entities.contains(&entity) {
+            return true;
+        }
+        if !entity.as_node_id().is_global_component() {
+            return false;
+        }
+        self.reader
+            .get_object_info(entity)
+            .map(|object_info| {
+                let package_address = object_info.blueprint_info.blueprint_id.package_address;
+                claimed_entities.contains(&package_address.into())
+            })
+            .unwrap_or(false)
+    }
+
+    fn is_dapp_definition(metadata: &IndexMap<String, MetadataValue>) -> bool {
+        matches!(
+            metadata.get(METADATA_KEY_ACCOUNT_TYPE),
+            Some(MetadataValue::String(account_type)) if account_type == ACCOUNT_TYPE_DAPP_DEFINITION
+        )
+    }
+}
+
+fn check_type<T: MetadataVal>(value: &MetadataValue) -> Result<(), MetadataConversionError> {
+    T::from_metadata_value(value.clone()).map(|_| ())
+}
+
+fn global_addresses(value: Option<&MetadataValue>) -> Vec<GlobalAddress> {
+    match value {
+        Some(MetadataValue::GlobalAddressArray(addresses)) => addresses.clone(),
+        _ => vec![],
+    }
+}
+
+/// The dApp definitions an entity links to, through either of the `dapp_definition` and
+/// `dapp_definitions` keys.
+fn linked_dapp_definitions(metadata: &IndexMap<String, MetadataValue>) -> Vec<GlobalAddress> {
+    let mut dapp_definitions = global_addresses(metadata.get(METADATA_KEY_DAPP_DEFINITIONS));
+    if let Some(MetadataValue::GlobalAddress(dapp_definition)) =
+        metadata.get(METADATA_KEY_DAPP_DEFINITION)
+    {
+        dapp_definitions.push(*dapp_definition);
+    }
+    dapp_definitions
+}
@@ -1,7 +1,17 @@
 mod accounter;
+mod change_feed;
+mod metadata_standards;
+mod protocol_update_dry_run;
+mod resource_holders;
+mod transaction_tracker;
 mod traverse;
 mod vault_finder;
 
 pub use accounter::*;
+pub use change_feed::*;
+pub use metadata_standards::*;
+pub use protocol_update_dry_run::*;
+pub use resource_holders::*;
+pub use transaction_tracker::*;
 pub use traverse::*;
 pub use vault_finder::*;
@@ -0,0 +1,289 @@
+use crate::typed_substate_layout::*;
+use radix_common::prelude::*;
+use radix_engine::system::system_db_reader::*;
+use radix_engine::updates::*;
+use radix_engine::vm::wasm::WasmEngine;
+use radix_engine::vm::{NativeVmExtension, VmInit};
+use radix_substate_store_impls::substate_database_overlay::SubstateDatabaseOverlay;
+use radix_substate_store_interface::db_key_mapper::{DatabaseKeyMapper, SpreadPrefixKeyMapper};
+use radix_substate_store_interface::interface::*;
+
+/// Runs the given protocol updates, in order, against an overlay of the given database and
+/// reports the changes made by each of them. The database itself is left untouched.
+///
+/// Each update sees the changes made by the preceding ones, so the executors should start from
+/// the first update which hasn't yet been enacted on the ledger.
+pub fn dry_run_protocol_updates<S: SubstateDatabase, W: WasmEngine, E: NativeVmExtension>(
+    substate_db: &S,
+    protocol_update_executors: impl IntoIterator<Item = ProtocolUpdateExecutor>,
+    vm_init: VmInit<W, E>,
+) -> Vec<ProtocolUpdateDryRun> {
+    let mut overlay = SubstateDatabaseOverlay::new_unmergeable(substate_db);
+    let mut dry_runs = Vec::new();
+    for executor in protocol_update_executors {
+        let protocol_update = executor.protocol_update.clone();
+        let mut update_overlay = SubstateDatabaseOverlay::new_unmergeable(&overlay);
+        executor.run_and_commit_with_vm_init(&mut update_overlay, vm_init.clone());
+        let database_updates = update_overlay.database_updates();
+        let substate_changes =
+            SubstateChangeCollector::new(&overlay, &update_overlay).collect(&database_updates);
+        drop(update_overlay);
+
+        overlay.commit(&database_updates);
+        dry_runs.push(ProtocolUpdateDryRun {
+            protocol_update,
+            database_updates,
+            substate_changes,
+        });
+    }
+    dry_runs
+}
+
+/// The changes a single protocol update would make to the ledger.
+#[derive(Debug, Clone)]
+pub struct ProtocolUpdateDryRun {
+    pub protocol_update: ProtocolUpdateEntry,
+    pub database_updates: DatabaseUpdates,
+    pub substate_changes: Vec<ChangedSubstate>,
+}
+
+impl ProtocolUpdateDryRun {
+    pub fn changes_of_category(
+        &self,
+        category: SubstateChangeCategory,
+    ) -> impl Iterator<Item = &ChangedSubstate> {
+        self.substate_changes
+            .iter()
+            .filter(move |change| change.category == category)
+    }
+
+    /// The entities with at least one changed substate, in order of first change.
+    pub fn affected_entities(&self) -> IndexSet<NodeId> {
+        self.substate_changes
+            .iter()
+            .map(|change| change.node_id)
+            .collect()
+    }
+}
+
+/// A single substate written or deleted by a protocol update, with its values before and after.
+#[derive(Debug, Clone)]
+pub struct ChangedSubstate {
+    pub node_id: NodeId,
+    pub partition_number: PartitionNumber,
+    pub db_sort_key: DbSortKey,
+    /// The substate key, if the partition layout could be resolved from the ledger.
+    pub substate_key: Option<SubstateKey>,
+    pub category: SubstateChangeCategory,
+    pub previous_value: Option<DbSubstateValue>,
+    pub new_value: Option<DbSubstateValue>,
+}
+
+impl ChangedSubstate {
+    pub fn typed_key(&self) -> Result<TypedSubstateKey, String> {
+        let entity_type = self
+            .node_id
+            .entity_type()
+            .ok_or_else(|| format!("Unknown entity type of {:?}", self.node_id))?;
+        let substate_key = self
+            .substate_key
+            .as_ref()
+            .ok_or_else(|| format!("Unresolved substate key {:?}", self.db_sort_key))?;
+        to_typed_substate_key(entity_type, self.partition_number, substate_key)
+    }
+
+    pub fn typed_previous_value(&self) -> Option<Result<TypedSubstateValue, String>> {
+        self.previous_value
+            .as_ref()
+            .map(|value| to_typed_substate_value(&self.typed_key()?, value))
+    }
+
+    pub fn typed_new_value(&self) -> Option<Result<TypedSubstateValue, String>> {
+        self.new_value
+            .as_ref()
+            .map(|value| to_typed_substate_value(&self.typed_key()?, value))
+    }
+}
+
+/// A coarse classification of changed substates, for what reviewers of protocol updates most
+/// care about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SubstateChangeCategory {
+    /// The VM type, original code or instrumented code of a package.
+    PackageCode,
+    /// A schema stored by a package.
+    PackageSchema,
+    /// A blueprint definition, its dependencies, or its auth or royalty configuration.
+    BlueprintDefinition,
+    /// The boot loader substates or the consensus manager configuration.
+    Configuration,
+    Other,
+}
+
+impl SubstateChangeCategory {
+    pub fn of(node_id: &NodeId, typed_key: &TypedSubstateKey) -> Self {
+        match typed_key {
+            TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::Package(key)) => match key {
+                PackageTypedSubstateKey::CodeVmTypeKeyValueEntry(_)
+                | PackageTypedSubstateKey::CodeOriginalCodeKeyValueEntry(_)
+                | PackageTypedSubstateKey::CodeInstrumentedCodeKeyValueEntry(_) => {
+                    Self::PackageCode
+                }
+                PackageTypedSubstateKey::BlueprintVersionDefinitionKeyValueEntry(_)
+                | PackageTypedSubstateKey::BlueprintVersionDependenciesKeyValueEntry(_)
+                | PackageTypedSubstateKey::BlueprintVersionRoyaltyConfigKeyValueEntry(_)
+                | PackageTypedSubstateKey::BlueprintVersionAuthConfigKeyValueEntry(_) => {
+                    Self::BlueprintDefinition
+                }
+                PackageTypedSubstateKey::Field(_) => Self::Other,
+            },
+            TypedSubstateKey::Schema(_) if node_id.is_global_package() => Self::PackageSchema,
+            TypedSubstateKey::BootLoader(_)
+            | TypedSubstateKey::MainModule(TypedMainModuleSubstateKey::ConsensusManager(
+                ConsensusManagerTypedSubstateKey::Field(ConsensusManagerField::Configuration),
+            )) => Self::Configuration,
+            _ => Self::Other,
+        }
+    }
+}
+
+pub(crate) struct SubstateChangeCollector<'a, S: SubstateDatabase, U: SubstateDatabase> {
+    previous_db: &'a S,
+    updated_db: &'a U,
+}
+
+impl<'a, S: SubstateDatabase, U: SubstateDatabase> SubstateChangeCollector<'a, S, U> {
+    pub(crate) fn new(previous_db: &'a S, updated_db: &'a U) -> Self {
+        Self {
+            previous_db,
+            updated_db,
+        }
+    }
+
+    pub(crate) fn collect(&self, database_updates: &DatabaseUpdates) -> Vec<ChangedSubstate> {
+        let mut changes = Vec::new();
+        for (node_key, node_updates) in &database_updates.node_updates {
+            for (partition_num, partition_updates) in &node_updates.partition_updates {
+                let partition_key = DbPartitionKey {
+                    node_key: node_key.clone(),
+                    partition_num: *partition_num,
+                };
+                let updates: Vec<(DbSortKey, Option<DbSubstateValue>)> = match partition_updates {
+                    PartitionDatabaseUpdates::Delta { substate_updates } => substate_updates
+                        .iter()
+                        .map(|(sort_key, update)| {
+                            let new_value = match update {
+                                DatabaseUpdate::Set(value) => Some(value.clone()),
+                                DatabaseUpdate::Delete => None,
+                            };
+                            (sort_key.clone(), new_value)
+                        })
+                        .collect(),
+                    PartitionDatabaseUpdates::Reset {
+                        new_substate_values,
+                    } => {
+                        // Every substate previously in the partition is deleted, unless re-set.
+                        let mut updates = self
+                            .previous_db
+                            .list_entries(&partition_key)
+                            .filter(|(sort_key, _)| !new_substate_values.contains_key(sort_key))
+                            .map(|(sort_key, _)| (sort_key, None))
+                            .collect::<Vec<_>>();
+                        updates.extend(
+                            new_substate_values
+                                .iter()
+                                .map(|(sort_key, value)| (sort_key.clone(), Some(value.clone()))),
+                        );
+                        updates
+                    }
+                };
+
+                for (sort_key, new_value) in updates {
+                    let previous_value = self.previous_db.get_substate(&partition_key, &sort_key);
+                    if previous_value == new_value {
+                        continue;
+                    }
+                    changes.push(self.to_change(
+                        &partition_key,
+                        sort_key,
+                        previous_value,
+                        new_value,
+                    ));
+                }
+            }
+        }
+        changes
+    }
+
+    fn to_change(
+        &self,
+        partition_key: &DbPartitionKey,
+        db_sort_key: DbSortKey,
+        previous_value: Option<DbSubstateValue>,
+        new_value: Option<DbSubstateValue>,
+    ) -> ChangedSubstate {
+        let (node_id, partition_number) =
+            SpreadPrefixKeyMapper::from_db_partition_key(partition_key);
+        // Deleted entities can only be resolved against the previous state.
+        let substate_key =
+            Self::resolve_substate_key(self.updated_db, &node_id, partition_number, &db_sort_key)
+                .or_else(|| {
+                    Self::resolve_substate_key(
+                        self.previous_db,
+                        &node_id,
+                        partition_number,
+                        &db_sort_key,
+                    )
+                });
+        let category = substate_key
+            .as_ref()
+            .and_then(|substate_key| {
+                let entity_type = node_id.entity_type()?;
+                to_typed_substate_key(entity_type, partition_number, substate_key).ok()
+            })
+            .map(|typed_key| SubstateChangeCategory::of(&node_id, &typed_key))
+            .unwrap_or(SubstateChangeCategory::Other);
+
+        ChangedSubstate {
+            node_id,
+            partition_number,
+            db_sort_key,
+            substate_key,
+            category,
+            previous_value,
+            new_value,
+        }
+    }
+
+    fn resolve_substate_key<D: SubstateDatabase>(
+        substate_db: &D,
+        node_id: &NodeId,
+        partition_number: PartitionNumber,
+        db_sort_key: &DbSortKey,
+    ) -> Option<SubstateKey> {
+        let descriptors = SystemDatabaseReader::new(substate_db)
+            .get_partition_descriptors(node_id, &partition_number)
+            .ok()?;
+        let substate_key = match descriptors.first()? {
+            SystemPartitionDescriptor::BootLoader
+            | SystemPartitionDescriptor::TypeInfo
+            | SystemPartitionDescriptor::Object(_, ObjectPartitionDescriptor::Fields) => {
+                SpreadPrefixKeyMapper::from_db_sort_key::<FieldKey>(db_sort_key)
+            }
+            SystemPartitionDescriptor::Schema
+            | SystemPartitionDescriptor::KeyValueStore
+            | SystemPartitionDescriptor::Object(
+                _,
+                ObjectPartitionDescriptor::KeyValueCollection(_),
+            )
+            | SystemPartitionDescriptor::Object(_, ObjectPartitionDescriptor::IndexCollection(_)) => {
+                SpreadPrefixKeyMapper::from_db_sort_key::<MapKey>(db_sort_key)
+            }
+            SystemPartitionDescriptor::Object(
+                _,
+                ObjectPartitionDescriptor::SortedIndexCollection(_),
+            ) => SpreadPrefixKeyMapper::from_db_sort_key::<SortedKey>(db_sort_key),
+        };
+        Some(substate_key)
+    }
+}
@@ -0,0 +1,380 @@
+use super::{ChangeFeed, ChangeRecord, TypedChange};
+use radix_common::prelude::*;
+use radix_engine::blueprints::resource::*;
+use radix_engine::system::system_db_reader::SystemDatabaseReader;
+use radix_engine::track::StateUpdates;
+use radix_engine::transaction::CommitResult;
+use radix_engine_interface::api::ModuleId;
+use radix_engine_interface::types::CollectionDescriptor;
+use radix_substate_store_interface::db_key_mapper::{DatabaseKeyMapper, SpreadPrefixKeyMapper};
+use radix_substate_store_interface::interface::*;
+use sbor::rust::cmp::Reverse;
+use sbor::rust::collections::BTreeSet;
+
+/// A vault holding a resource, as tracked by the [`ResourceHolderIndex`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeldVault {
+    pub resource_address: ResourceAddress,
+    /// The global entity which (transitively) owns the vault, or `None` if it isn't known.
+    pub owner: Option<GlobalAddress>,
+    pub balance: Decimal,
+}
+
+/// The amount of a resource held by a global entity, across all of its vaults.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceHolder {
+    pub owner: GlobalAddress,
+    pub balance: Decimal,
+    pub vaults: Vec<NodeId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonFungibleHolder {
+    pub vault: NodeId,
+    pub owner: Option<GlobalAddress>,
+}
+
+/// A secondary index of which vaults, and which global entities, hold each resource.
+///
+/// The index is built once from a full scan of a database, and then kept up to date from the
+/// state updates of each committed transaction, in order, so that holder queries don't need to
+/// traverse the ledger. The ownership of vaults is resolved with a [`ChangeFeed`], and the balance
+/// of each holder is updated as its vaults change, so that holders are always ranked.
+pub struct ResourceHolderIndex {
+    change_feed: ChangeFeed,
+    vaults: IndexMap<NodeId, HeldVault>,
+    resources: IndexMap<ResourceAddress, ResourceHolders>,
+    non_fungible_vaults: IndexMap<NonFungibleGlobalId, NodeId>,
+}
+
+/// The vaults and holders of a single resource.
+#[derive(Default)]
+struct ResourceHolders {
+    vaults: IndexSet<NodeId>,
+    /// The balance of all vaults, including those whose owner isn't known.
+    total_balance: Decimal,
+    /// The holders with a non-zero balance, with the vaults holding it.
+    holders: IndexMap<GlobalAddress, (Decimal, IndexSet<NodeId>)>,
+    /// The holders, by descending balance.
+    ranking: BTreeSet<(Reverse<Decimal>, GlobalAddress)>,
+}
+
+impl ResourceHolders {
+    fn add_balance(&mut self, vault_id: NodeId, vault: &HeldVault) {
+        // NOTE: Decimal arithmetic operation safe unwrap.
+        //       Resources have a mint limit below the Decimal max
+        self.total_balance = self.total_balance.checked_add(vault.balance).unwrap();
+        let Some(owner) = vault.owner else {
+            return;
+        };
+        if vault.balance.is_zero() {
+            return;
+        }
+        let (balance, vaults) = self
+            .holders
+            .entry(owner)
+            .or_insert_with(|| (Decimal::ZERO, index_set_new()));
+        self.ranking.remove(&(Reverse(*balance), owner));
+        *balance = balance.checked_add(vault.balance).unwrap();
+        vaults.insert(vault_id);
+        self.ranking.insert((Reverse(*balance), owner));
+    }
+
+    fn remove_balance(&mut self, vault_id: NodeId, vault: &HeldVault) {
+        self.total_balance = self.total_balance.checked_sub(vault.balance).unwrap();
+        let Some(owner) = vault.owner else {
+            return;
+        };
+        let Some((balance, vaults)) = self.holders.get_mut(&owner) else {
+            return;
+        };
+        if !vaults.swap_remove(&vault_id) {
+            return;
+        }
+        self.ranking.remove(&(Reverse(*balance), owner));
+        if vaults.is_empty() {
+            self.holders.swap_remove(&owner);
+        } else {
+            *balance = balance.checked_sub(vault.balance).unwrap();
+            self.ranking.insert((Reverse(*balance), owner));
+        }
+    }
+
+    fn holder(&self, owner: &GlobalAddress) -> Option<ResourceHolder> {
+        let (balance, vaults) = self.holders.get(owner)?;
+        Some(ResourceHolder {
+            owner: *owner,
+            balance: *balance,
+            vaults: vaults.iter().cloned().collect(),
+        })
+    }
+}
+
+impl Default for ResourceHolderIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ResourceHolderIndex {
+    /// Creates an index for a ledger which starts out empty, e.g. when replaying a whole ledger
+    /// from genesis.
+    pub fn new() -> Self {
+        Self {
+            change_feed: ChangeFeed::new(),
+            vaults: index_map_new(),
+            resources: index_map_new(),
+            non_fungible_vaults: index_map_new(),
+        }
+    }
+
+    /// Builds the index from every vault of the given database. Like [`ChangeFeed::for_database`],
+    /// this reads every substate of the database.
+    pub fn from_database<S: SubstateDatabase + ListableSubstateDatabase>(substate_db: &S) -> Self {
+        let mut index = Self {
+            change_feed: ChangeFeed::for_database(substate_db),
+            ..Self::new()
+        };
+
+        let vault_ids: IndexSet<NodeId> = substate_db
+            .list_partition_keys()
+            .map(|partition_key| SpreadPrefixKeyMapper::from_db_partition_key(&partition_key).0)
+            .filter(|node_id| node_id.is_internal_vault())
+            .collect();
+        let reader = SystemDatabaseReader::new(substate_db);
+        for vault_id in vault_ids {
+            let Ok(object_info) = reader.get_object_info(vault_id) else {
+                continue;
+            };
+            let resource_address =
+                ResourceAddress::new_or_panic(object_info.get_outer_object().into());
+
+            if vault_id.is_internal_fungible_vault() {
+                let balance = reader
+                    .read_typed_object_field::<FungibleVaultBalanceFieldPayload>(
+                        &vault_id,
+                        ModuleId::Main,
+                        FungibleVaultField::Balance.field_index(),
+                    )
+                    .map(|balance| balance.fully_update_and_into_latest_version().amount())
+                    .unwrap_or_default();
+                index.set_vault_balance(vault_id, resource_address, balance);
+            } else {
+                let balance = reader
+                    .read_typed_object_field::<NonFungibleVaultBalanceFieldPayload>(
+                        &vault_id,
+                        ModuleId::Main,
+                        NonFungibleVaultField::Balance.field_index(),
+                    )
+                    .map(|balance| balance.fully_update_and_into_latest_version().amount)
+                    .unwrap_or_default();
+                index.set_vault_balance(vault_id, resource_address, balance);
+
+                let Ok(non_fungibles) = reader.collection_iter(
+                    &vault_id,
+                    ModuleId::Main,
+                    NonFungibleVaultCollection::NonFungibleIndex.collection_index(),
+                ) else {
+                    continue;
+                };
+                for (key, _) in non_fungibles {
+                    let local_id: NonFungibleLocalId = scrypto_decode(&key.into_map()).unwrap();
+                    index.non_fungible_vaults.insert(
+                        NonFungibleGlobalId::new(resource_address, local_id),
+                        vault_id,
+                    );
+                }
+            }
+        }
+        index
+    }
+
+    /// Updates the index with a committed transaction, whose state updates haven't been
+    /// committed to the `pre_state` database yet. Returns the changes made by the transaction.
+    pub fn update_from_commit<S: SubstateDatabase>(
+        &mut self,
+        pre_state: &S,
+        commit_result: &CommitResult,
+    ) -> Vec<ChangeRecord> {
+        self.update(pre_state, &commit_result.state_updates)
+    }
+
+    pub fn update<S: SubstateDatabase>(
+        &mut self,
+        pre_state: &S,
+        state_updates: &StateUpdates,
+    ) -> Vec<ChangeRecord> {
+        let (changes, attached_nodes) = self
+            .change_feed
+            .changes_and_attached_nodes(pre_state, state_updates);
+        for record in &changes {
+            self.apply_change(record);
+        }
+        // A vault changes hands when it, or any node above it, is attached to a new parent,
+        // without any of its own substates necessarily changing.
+        for node_id in attached_nodes {
+            for vault_id in self.change_feed.owned_subtree(&node_id) {
+                let owner = self.change_feed.owner_of(&vault_id);
+                self.set_vault_owner(vault_id, owner);
+            }
+        }
+        changes
+    }
+
+    /// The vaults holding the given resource, including empty ones.
+    pub fn vaults(
+        &self,
+        resource_address: &ResourceAddress,
+    ) -> impl Iterator<Item = (&NodeId, &HeldVault)> {
+        self.resources
+            .get(resource_address)
+            .into_iter()
+            .flat_map(|resource| resource.vaults.iter())
+            .filter_map(|vault_id| self.vaults.get(vault_id).map(|vault| (vault_id, vault)))
+    }
+
+    /// The global entities holding a non-zero amount of the given resource, by descending
+    /// balance. Vaults whose owner isn't known are left out.
+    pub fn holders(&self, resource_address: &ResourceAddress) -> Vec<ResourceHolder> {
+        self.top_holders(resource_address, usize::MAX)
+    }
+
+    /// The `count` global entities holding the most of the given resource.
+    pub fn top_holders(
+        &self,
+        resource_address: &ResourceAddress,
+        count: usize,
+    ) -> Vec<ResourceHolder> {
+        let Some(resource) = self.resources.get(resource_address) else {
+            return vec![];
+        };
+        resource
+            .ranking
+            .iter()
+            .take(count)
+            .filter_map(|(_, owner)| resource.holder(owner))
+            .collect()
+    }
+
+    /// The amount of the given resource held by the given global entity, across all of its vaults.
+    pub fn holder(
+        &self,
+        resource_address: &ResourceAddress,
+        owner: &GlobalAddress,
+    ) -> Option<ResourceHolder> {
+        self.resources.get(resource_address)?.holder(owner)
+    }
+
+    pub fn holder_of_non_fungible(
+        &self,
+        non_fungible_global_id: &NonFungibleGlobalId,
+    ) -> Option<NonFungibleHolder> {
+        let vault = *self.non_fungible_vaults.get(non_fungible_global_id)?;
+        Some(NonFungibleHolder {
+            vault,
+            owner: self.vaults.get(&vault).and_then(|vault| vault.owner),
+        })
+    }
+
+    /// The amount of the given resource held in the vaults of the ledger, excluding those owned
+    /// by any of the `excluded` entities (e.g. the reserves of its issuer).
+    pub fn circulating_supply(
+        &self,
+        resource_address: &ResourceAddress,
+        excluded: &[GlobalAddress],
+    ) -> Decimal {
+        let Some(resource) = self.resources.get(resource_address) else {
+            return Decimal::ZERO;
+        };
+        excluded
+            .iter()
+            .collect::<IndexSet<_>>()
+            .into_iter()
+            .filter_map(|owner| resource.holders.get(owner))
+            .fold(resource.total_balance, |supply, (balance, _)| {
+                supply.checked_sub(*balance).unwrap()
+            })
+    }
+
+    fn apply_change(&mut self, record: &ChangeRecord) {
+        match &record.change {
+            TypedChange::FungibleVaultBalanceChanged {
+                resource_address,
+                new_balance,
+                ..
+            }
+            | TypedChange::NonFungibleVaultBalanceChanged {
+                resource_address,
+                new_balance,
+                ..
+            } => {
+                self.set_vault_balance(record.node_id, *resource_address, *new_balance);
+            }
+            TypedChange::NonFungibleDeposited {
+                resource_address,
+                local_id,
+            } => {
+                self.non_fungible_vaults.insert(
+                    NonFungibleGlobalId::new(*resource_address, local_id.clone()),
+                    record.node_id,
+                );
+            }
+            TypedChange::NonFungibleWithdrawn {
+                resource_address,
+                local_id,
+            } => {
+                // Within a transaction, the deposit to the new vault may be seen first.
+                let non_fungible_global_id =
+                    NonFungibleGlobalId::new(*resource_address, local_id.clone());
+                if self.non_fungible_vaults.get(&non_fungible_global_id) == Some(&record.node_id) {
+                    self.non_fungible_vaults
+                        .swap_remove(&non_fungible_global_id);
+                }
+            }
+            TypedChange::EntityDeleted { .. } if record.node_id.is_internal_vault() => {
+                if let Some(vault) = self.vaults.swap_remove(&record.node_id) {
+                    if let Some(resource) = self.resources.get_mut(&vault.resource_address) {
+                        resource.remove_balance(record.node_id, &vault);
+                        resource.vaults.swap_remove(&record.node_id);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn set_vault_balance(
+        &mut self,
+        vault_id: NodeId,
+        resource_address: ResourceAddress,
+        balance: Decimal,
+    ) {
+        let owner = self.change_feed.owner_of(&vault_id);
+        let vault = self.vaults.entry(vault_id).or_insert_with(|| HeldVault {
+            resource_address,
+            owner,
+            balance: Decimal::ZERO,
+        });
+        let resource = self.resources.entry(resource_address).or_default();
+        resource.vaults.insert(vault_id);
+        resource.remove_balance(vault_id, vault);
+        vault.balance = balance;
+        resource.add_balance(vault_id, vault);
+    }
+
+    fn set_vault_owner(&mut self, vault_id: NodeId, owner: Option<GlobalAddress>) {
+        let Some(vault) = self.vaults.get_mut(&vault_id) else {
+            return;
+        };
+        if vault.owner == owner {
+            return;
+        }
+        let resource = self
+            .resources
+            .get_mut(&vault.resource_address)
+            .expect("Every indexed vault belongs to an indexed resource");
+        resource.remove_balance(vault_id, vault);
+        vault.owner = owner;
+        resource.add_balance(vault_id, vault);
+    }
+}
@@ -0,0 +1,162 @@
+use radix_common::prelude::*;
+use radix_engine::blueprints::transaction_tracker::*;
+use radix_engine::system::system_substates::{FieldSubstate, KeyValueEntrySubstate};
+use radix_engine_interface::prelude::*;
+use radix_substate_store_interface::db_key_mapper::{
+    MappedSubstateDatabase, SpreadPrefixKeyMapper,
+};
+use radix_substate_store_interface::interface::SubstateDatabase;
+use radix_transactions::model::IntentHash;
+use sbor::rust::ops::RangeInclusive;
+
+type TransactionStatusEntry = KeyValueEntrySubstate<TransactionStatus>;
+
+/// Reads the intents tracked by the native transaction tracker, which records the outcome of
+/// each committed intent until its expiry epoch has passed, in order to prevent replays.
+///
+/// Note that only intents which need replay protection are tracked, so e.g. test transactions
+/// never show up here. An intent also stops being tracked once the partition holding its expiry
+/// epoch is pruned by the tracker.
+pub struct TransactionTrackerReader<'s, S: SubstateDatabase + ?Sized> {
+    substate_db: &'s S,
+}
+
+/// A tracked intent, along with the window of expiry epochs of the partition it's stored in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedIntent {
+    pub intent_hash: IntentHash,
+    pub status: TransactionStatusV1,
+    pub partition_number: PartitionNumber,
+    pub expiry_epoch_start_inclusive: Epoch,
+    pub expiry_epoch_end_exclusive: Epoch,
+}
+
+/// A partition of the transaction tracker, along with the window of expiry epochs it covers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedIntentPartition {
+    pub partition_number: PartitionNumber,
+    pub expiry_epoch_start_inclusive: Epoch,
+    pub expiry_epoch_end_exclusive: Epoch,
+}
+
+impl<'s, S: SubstateDatabase + ?Sized> TransactionTrackerReader<'s, S> {
+    pub fn new(substate_db: &'s S) -> Self {
+        Self { substate_db }
+    }
+
+    pub fn read_tracker(&self) -> TransactionTrackerSubstateV1 {
+        self.substate_db
+            .get_mapped::<SpreadPrefixKeyMapper, FieldSubstate<TransactionTrackerSubstate>>(
+                TRANSACTION_TRACKER.as_node_id(),
+                MAIN_BASE_PARTITION,
+                &TransactionTrackerField::TransactionTracker.into(),
+            )
+            .expect("Transaction tracker should exist")
+            .into_payload()
+            .into_v1()
+    }
+
+    /// Returns all partitions of the tracker, ordered from the earliest window of expiry epochs
+    /// to the latest.
+    pub fn partitions(&self) -> Vec<TrackedIntentPartition> {
+        let tracker = self.read_tracker();
+        let range = tracker.partition_range_start_inclusive..=tracker.partition_range_end_inclusive;
+        range
+            .clone()
+            .filter(|partition_number| *partition_number >= tracker.start_partition)
+            .chain(range.filter(|partition_number| *partition_number < tracker.start_partition))
+            .map(|partition_number| {
+                let (start, end) = tracker
+                    .expiry_epochs_of_partition(partition_number)
+                    .expect("Partition is within the tracker range");
+                TrackedIntentPartition {
+                    partition_number: PartitionNumber(partition_number),
+                    expiry_epoch_start_inclusive: start,
+                    expiry_epoch_end_exclusive: end,
+                }
+            })
+            .collect()
+    }
+
+    /// Resolves the status of an intent whose expiry epoch is known, e.g. from its header.
+    pub fn intent_status_with_expiry_epoch(
+        &self,
+        intent_hash: &IntentHash,
+        expiry_epoch: Epoch,
+    ) -> Option<TrackedIntent> {
+        let tracker = self.read_tracker();
+        let partition_number = tracker.partition_for_expiry_epoch(expiry_epoch)?;
+        self.read_intent_in_partition(&tracker, intent_hash, partition_number)
+    }
+
+    /// Resolves the status of an intent by searching all partitions of the tracker.
+    pub fn intent_status(&self, intent_hash: &IntentHash) -> Option<TrackedIntent> {
+        let tracker = self.read_tracker();
+        (tracker.partition_range_start_inclusive..=tracker.partition_range_end_inclusive).find_map(
+            |partition_number| {
+                self.read_intent_in_partition(&tracker, intent_hash, partition_number)
+            },
+        )
+    }
+
+    /// Lists the intents tracked in the given range of partition numbers. Partitions outside of
+    /// the tracker range are ignored.
+    pub fn list_intents(&self, partition_numbers: RangeInclusive<u8>) -> Vec<TrackedIntent> {
+        let tracker = self.read_tracker();
+        let mut intents = Vec::new();
+        for partition_number in partition_numbers {
+            let Some((start, end)) = tracker.expiry_epochs_of_partition(partition_number) else {
+                continue;
+            };
+            let entries = self
+                .substate_db
+                .list_mapped::<SpreadPrefixKeyMapper, TransactionStatusEntry, MapKey>(
+                    TRANSACTION_TRACKER.as_node_id(),
+                    PartitionNumber(partition_number),
+                );
+            for (substate_key, substate) in entries {
+                let Some(status) = substate.into_value() else {
+                    continue;
+                };
+                let hash: Hash = scrypto_decode(
+                    substate_key
+                        .for_map()
+                        .expect("Transaction tracker partitions are keyed by map keys"),
+                )
+                .expect("Transaction tracker keys are intent hashes");
+                intents.push(TrackedIntent {
+                    intent_hash: IntentHash::from_hash(hash),
+                    status: status.into_v1(),
+                    partition_number: PartitionNumber(partition_number),
+                    expiry_epoch_start_inclusive: start,
+                    expiry_epoch_end_exclusive: end,
+                });
+            }
+        }
+        intents
+    }
+
+    fn read_intent_in_partition(
+        &self,
+        tracker: &TransactionTrackerSubstateV1,
+        intent_hash: &IntentHash,
+        partition_number: u8,
+    ) -> Option<TrackedIntent> {
+        let (start, end) = tracker.expiry_epochs_of_partition(partition_number)?;
+        let status = self
+            .substate_db
+            .get_mapped::<SpreadPrefixKeyMapper, TransactionStatusEntry>(
+                TRANSACTION_TRACKER.as_node_id(),
+                PartitionNumber(partition_number),
+                &SubstateKey::Map(scrypto_encode(&intent_hash.into_hash()).unwrap()),
+            )?
+            .into_value()?;
+        Some(TrackedIntent {
+            intent_hash: *intent_hash,
+            status: status.into_v1(),
+            partition_number: PartitionNumber(partition_number),
+            expiry_epoch_start_inclusive: start,
+            expiry_epoch_end_exclusive: end,
+        })
+    }
+}
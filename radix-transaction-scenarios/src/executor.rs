@@ -254,9 +254,12 @@ where
                 &filter,
                 false,
             )?;
-            current_protocol_version = protocol_update_executor.protocol_update.into();
+            current_protocol_version = protocol_update_executor.protocol_update.protocol_version();
             (self.on_before_protocol_update_executed)(&protocol_update_executor);
-            protocol_update_executor.run_and_commit(&mut self.database);
+            protocol_update_executor.run_and_commit_with_vm_init(
+                &mut self.database,
+                VmInit::new(&self.scrypto_vm, self.native_vm_extension.clone()),
+            );
         }
 
         self.execute_scenarios_at_new_protocol_version(
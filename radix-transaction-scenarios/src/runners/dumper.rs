@@ -13,6 +13,10 @@ mod test {
     use radix_transactions::manifest::*;
     use std::{ffi::OsString, fs, path::*};
 
+    /// The latest protocol version whose scenarios have been run on a permanent network. The
+    /// summary hashes of the scenarios run at any later version are still allowed to change.
+    const LAST_DEPLOYED_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::Anemone;
+
     #[derive(Copy, Clone)]
     pub enum DumperMode {
         Write,
@@ -252,7 +256,7 @@ mod test {
                             .borrow_mut().take().unwrap().finalize().to_string()[0..16].to_string();
 
                         writeln!(&mut summary, "== SUMMARY HASHES ==").unwrap();
-                        if protocol_version == ProtocolVersion::LATEST {
+                        if protocol_version > LAST_DEPLOYED_PROTOCOL_VERSION {
                             writeln!(&mut summary, "These {protocol_version_display_name} hashes are permitted to change only until the scenario is deployed to a permanent network, else it can cause divergence.").unwrap();
                             writeln!(&mut summary, "State changes: {state_change_digest} (allowed to change if not deployed to any network)").unwrap();
                             writeln!(&mut summary, "Events       : {event_digest} (allowed to change if not deployed to any network)").unwrap();
@@ -1,5 +1,5 @@
 use crate::model::*;
-use crate::signing::Signer;
+use crate::signing::{Signer, SigningError};
 
 pub struct TransactionBuilder {
     manifest: Option<TransactionManifestV1>,
@@ -35,7 +35,14 @@ impl TransactionBuilder {
         self
     }
 
-    pub fn sign<S: Signer>(mut self, signer: &S) -> Self {
+    /// Encrypts the message for its decryptors, and attaches it to the transaction.
+    #[cfg(feature = "std")]
+    pub fn encrypted_message(self, message: crate::signing::EncryptedMessageBuilderV1) -> Self {
+        let encrypted_message = message.encrypt().expect("Message could be encrypted");
+        self.message(MessageV1::Encrypted(encrypted_message))
+    }
+
+    pub fn sign<S: Signer + ?Sized>(mut self, signer: &S) -> Self {
         let intent = self.transaction_intent();
         let prepared = intent.prepare().expect("Intent could be prepared");
         self.intent_signatures
@@ -43,7 +50,7 @@ impl TransactionBuilder {
         self
     }
 
-    pub fn multi_sign<S: Signer>(mut self, signers: &[&S]) -> Self {
+    pub fn multi_sign<S: Signer + ?Sized>(mut self, signers: &[&S]) -> Self {
         let intent = self.transaction_intent();
         let prepared = intent.prepare().expect("Intent could be prepared");
         for signer in signers {
@@ -53,12 +60,26 @@ impl TransactionBuilder {
         self
     }
 
+    /// As [`TransactionBuilder::multi_sign`], but returns an error if any signer fails to sign.
+    pub fn try_multi_sign<S: Signer + ?Sized>(
+        mut self,
+        signers: &[&S],
+    ) -> Result<Self, SigningError> {
+        let intent = self.transaction_intent();
+        let prepared = intent.prepare().expect("Intent could be prepared");
+        for signer in signers {
+            self.intent_signatures
+                .push(signer.try_sign_with_public_key(&prepared.intent_hash())?);
+        }
+        Ok(self)
+    }
+
     pub fn signer_signatures(mut self, sigs: Vec<SignatureWithPublicKeyV1>) -> Self {
         self.intent_signatures.extend(sigs);
         self
     }
 
-    pub fn notarize<S: Signer>(mut self, signer: &S) -> Self {
+    pub fn notarize<S: Signer + ?Sized>(mut self, signer: &S) -> Self {
         let signed_intent = self.signed_transaction_intent();
         let prepared = signed_intent
             .prepare()
@@ -71,6 +92,20 @@ impl TransactionBuilder {
         self
     }
 
+    /// As [`TransactionBuilder::notarize`], but returns an error if the notary fails to sign.
+    pub fn try_notarize<S: Signer + ?Sized>(mut self, signer: &S) -> Result<Self, SigningError> {
+        let signed_intent = self.signed_transaction_intent();
+        let prepared = signed_intent
+            .prepare()
+            .expect("Signed intent could be prepared");
+        self.notary_signature = Some(
+            signer
+                .try_sign_with_public_key(&prepared.signed_intent_hash())?
+                .signature(),
+        );
+        Ok(self)
+    }
+
     pub fn notary_signature(mut self, signature: SignatureV1) -> Self {
         self.notary_signature = Some(signature);
         self
@@ -122,7 +157,8 @@ mod tests {
 
     use super::*;
     use crate::builder::*;
-    use crate::internal_prelude::Secp256k1PrivateKey;
+    use crate::internal_prelude::{Ed25519PrivateKey, Secp256k1PrivateKey};
+    use crate::signing::DynSigner;
 
     #[test]
     fn notary_as_signatory() {
@@ -153,4 +189,73 @@ mod tests {
             true
         );
     }
+
+    #[test]
+    fn signers_of_different_types_can_be_mixed() {
+        let notary_private_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let signer1 = Secp256k1PrivateKey::from_u64(2).unwrap();
+        let signer2 = Ed25519PrivateKey::from_u64(3).unwrap();
+        let signers: [&dyn DynSigner; 2] = [&signer1, &signer2];
+
+        let transaction = TransactionBuilder::new()
+            .header(TransactionHeaderV1 {
+                network_id: NetworkDefinition::simulator().id,
+                start_epoch_inclusive: Epoch::zero(),
+                end_epoch_exclusive: Epoch::of(100),
+                nonce: 5,
+                notary_public_key: notary_private_key.public_key().into(),
+                notary_is_signatory: false,
+                tip_percentage: 5,
+            })
+            .manifest(ManifestBuilder::new().drop_auth_zone_proofs().build())
+            .multi_sign(&signers)
+            .notarize(&notary_private_key as &dyn DynSigner)
+            .build();
+
+        let prepared = transaction.prepare().unwrap();
+        let intent_hash = prepared.intent_hash();
+        assert_eq!(
+            transaction.signed_intent.intent_signatures.signatures,
+            vec![
+                IntentSignatureV1(signer1.sign_with_public_key(&intent_hash)),
+                IntentSignatureV1(signer2.sign_with_public_key(&intent_hash)),
+            ]
+        );
+    }
+
+    #[test]
+    fn encrypted_message_can_be_decrypted_by_decryptor() {
+        let notary_private_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let decryptor_private_key = Ed25519PrivateKey::from_u64(2).unwrap();
+
+        let transaction = TransactionBuilder::new()
+            .header(TransactionHeaderV1 {
+                network_id: NetworkDefinition::simulator().id,
+                start_epoch_inclusive: Epoch::zero(),
+                end_epoch_exclusive: Epoch::of(100),
+                nonce: 5,
+                notary_public_key: notary_private_key.public_key().into(),
+                notary_is_signatory: true,
+                tip_percentage: 5,
+            })
+            .manifest(ManifestBuilder::new().drop_auth_zone_proofs().build())
+            .encrypted_message(
+                crate::signing::EncryptedMessageBuilderV1::new_text("text/plain", "Hello!")
+                    .add_decryptor(decryptor_private_key.public_key()),
+            )
+            .notarize(&notary_private_key)
+            .build();
+
+        let MessageV1::Encrypted(encrypted_message) = transaction.signed_intent.intent.message
+        else {
+            panic!("Message should be encrypted");
+        };
+        assert_eq!(
+            encrypted_message
+                .decrypt(&decryptor_private_key.into())
+                .unwrap()
+                .message,
+            MessageContentsV1::String("Hello!".to_string())
+        );
+    }
 }
@@ -16,7 +16,12 @@ pub mod prelude {
     // Exports from this crate
     pub use crate::builder::*;
     pub use crate::model::*;
-    pub use crate::signing::{PrivateKey, Signer};
+    pub use crate::signing::{
+        DynSigner, EncryptedMessageBuilderV1, MessageDecryptionError, MessageEncryptionEntropy,
+        MessageEncryptionError, PrivateKey, Signer, SigningError,
+    };
+    #[cfg(feature = "std")]
+    pub use crate::signing::{ExternalSigner, ExternalSignerError};
 }
 
 // Extra things which this crate wants which upstream crates likely don't
@@ -166,6 +166,3 @@ impl AesWrapped128BitKey {
 //============================================================================
 
 pub type PreparedMessageV1 = SummarizedRawFullBody<MessageV1>;
-
-// TODO: Add tests with a canonical implementation of message encryption/decryption,
-// and corresponding test vectors for other implementers.
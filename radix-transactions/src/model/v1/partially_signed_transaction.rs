@@ -0,0 +1,423 @@
+use super::*;
+use crate::internal_prelude::*;
+use radix_engine_interface::blueprints::account::*;
+use radix_engine_interface::blueprints::identity::*;
+use radix_engine_interface::object_modules::metadata::*;
+use radix_engine_interface::object_modules::role_assignment::*;
+
+/// An intent, along with the intent signatures which have been collected for it so far.
+///
+/// This is a portable container for signing a transaction across a number of machines (eg the
+/// air-gapped signers of a multi-signature account): it can be passed from signer to signer, or
+/// copies signed in parallel can be merged, before it is finally notarized into a
+/// [`NotarizedTransactionV1`].
+///
+/// It is serialized with [`to_payload_bytes`](Self::to_payload_bytes) as a Manifest SBOR payload.
+#[derive(Debug, Clone, Eq, PartialEq, ManifestSbor)]
+pub struct PartiallySignedTransactionV1 {
+    pub intent: IntentV1,
+    pub intent_signatures: IntentSignaturesV1,
+    /// The hashes of the public keys which are expected to sign the intent.
+    ///
+    /// These are derived from the manifest where possible (see [`Self::required_signers_of`]),
+    /// and can be added to by the creator of the transaction.
+    pub required_signers: IndexSet<PublicKeyHash>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PartialSigningError {
+    PrepareError(PrepareError),
+    InvalidIntentSignature,
+    DuplicateSigner(PublicKey),
+    MismatchingIntents {
+        expected: IntentHash,
+        actual: IntentHash,
+    },
+    MissingSigners(Vec<PublicKeyHash>),
+    InvalidNotary {
+        expected: PublicKey,
+        actual: PublicKey,
+    },
+    /// The signer failed to sign, with the [`Debug`] representation of its [`SigningError`].
+    SigningError(String),
+    EncodeError(EncodeError),
+    DecodeError(DecodeError),
+}
+
+impl PartialSigningError {
+    fn signing_error(error: SigningError) -> Self {
+        Self::SigningError(format!("{:?}", error))
+    }
+}
+
+impl From<PrepareError> for PartialSigningError {
+    fn from(value: PrepareError) -> Self {
+        Self::PrepareError(value)
+    }
+}
+
+impl PartiallySignedTransactionV1 {
+    pub fn new(intent: IntentV1) -> Self {
+        let required_signers = Self::required_signers_of(&intent.instructions.0);
+        Self {
+            intent,
+            intent_signatures: IntentSignaturesV1 { signatures: vec![] },
+            required_signers,
+        }
+    }
+
+    /// Derives the signers which the manifest statically requires.
+    ///
+    /// Only the owners of preallocated accounts and identities can be derived without ledger
+    /// state: their owner role is a signature requirement on the public key hash which their
+    /// address is derived from. Calls to their owner-protected methods (eg account withdrawals
+    /// and proof creation, or metadata and role assignment updates) therefore require a signature
+    /// by that key - unless the entity has since been securified.
+    pub fn required_signers_of(instructions: &[InstructionV1]) -> IndexSet<PublicKeyHash> {
+        let mut required_signers = index_set_new();
+        for instruction in instructions {
+            let (address, method_name, is_owner_protected): (_, _, fn(&str) -> bool) =
+                match instruction {
+                    InstructionV1::CallMethod {
+                        address,
+                        method_name,
+                        ..
+                    } => (address, method_name, is_owner_protected_main_method),
+                    InstructionV1::CallMetadataMethod {
+                        address,
+                        method_name,
+                        ..
+                    } => (address, method_name, is_owner_protected_metadata_method),
+                    InstructionV1::CallRoleAssignmentMethod {
+                        address,
+                        method_name,
+                        ..
+                    } => (
+                        address,
+                        method_name,
+                        is_owner_protected_role_assignment_method,
+                    ),
+                    _ => continue,
+                };
+            let DynamicGlobalAddress::Static(address) = address else {
+                continue;
+            };
+            let Some(public_key_hash) = preallocated_owner_public_key_hash(&address) else {
+                continue;
+            };
+            if is_owner_protected(method_name) {
+                required_signers.insert(public_key_hash);
+            }
+        }
+        required_signers
+    }
+
+    pub fn intent_hash(&self) -> Result<IntentHash, PrepareError> {
+        Ok(self.intent.prepare()?.intent_hash())
+    }
+
+    /// The public keys which have signed the intent so far.
+    pub fn signers(&self) -> Result<Vec<PublicKey>, PartialSigningError> {
+        let intent_hash = self.intent_hash()?.into_hash();
+        self.intent_signatures
+            .signatures
+            .iter()
+            .map(|signature| {
+                recover(&intent_hash, &signature.0)
+                    .ok_or(PartialSigningError::InvalidIntentSignature)
+            })
+            .collect()
+    }
+
+    /// The required signers which haven't signed yet, taking into account that the notary may be
+    /// a signatory.
+    pub fn missing_signers(&self) -> Result<Vec<PublicKeyHash>, PartialSigningError> {
+        let mut signed = self
+            .signers()?
+            .into_iter()
+            .map(|public_key| public_key.get_hash())
+            .collect::<IndexSet<_>>();
+        let header = &self.intent.header;
+        if header.notary_is_signatory {
+            signed.insert(header.notary_public_key.get_hash());
+        }
+        Ok(self
+            .required_signers
+            .iter()
+            .filter(|public_key_hash| !signed.contains(*public_key_hash))
+            .cloned()
+            .collect())
+    }
+
+    pub fn sign<S: Signer>(&mut self, signer: &S) -> Result<(), PartialSigningError> {
+        let signature = signer
+            .try_sign_with_public_key(&self.intent_hash()?)
+            .map_err(PartialSigningError::signing_error)?;
+        self.add_signature(signature)
+    }
+
+    /// Adds a signature produced elsewhere, after checking that it is a valid signature of the
+    /// intent by a new signer.
+    pub fn add_signature(
+        &mut self,
+        signature: SignatureWithPublicKeyV1,
+    ) -> Result<(), PartialSigningError> {
+        let intent_hash = self.intent_hash()?.into_hash();
+        let public_key =
+            recover(&intent_hash, &signature).ok_or(PartialSigningError::InvalidIntentSignature)?;
+        if !verify(&intent_hash, &public_key, &signature.signature()) {
+            return Err(PartialSigningError::InvalidIntentSignature);
+        }
+        if self.signers()?.contains(&public_key) {
+            return Err(PartialSigningError::DuplicateSigner(public_key));
+        }
+        self.intent_signatures
+            .signatures
+            .push(IntentSignatureV1(signature));
+        Ok(())
+    }
+
+    /// Merges in the signatures and required signers of another copy of the same intent. Signers
+    /// who have signed both copies are only included once.
+    pub fn merge(&mut self, other: &Self) -> Result<(), PartialSigningError> {
+        let expected = self.intent_hash()?;
+        let actual = other.intent_hash()?;
+        if expected != actual {
+            return Err(PartialSigningError::MismatchingIntents { expected, actual });
+        }
+        let mut signers = self.signers()?.into_iter().collect::<IndexSet<_>>();
+        for (signature, signer) in other
+            .intent_signatures
+            .signatures
+            .iter()
+            .zip(other.signers()?)
+        {
+            if signers.insert(signer) {
+                self.intent_signatures.signatures.push(signature.clone());
+            }
+        }
+        self.required_signers
+            .extend(other.required_signers.iter().cloned());
+        Ok(())
+    }
+
+    /// Notarizes the transaction, failing if any of the required signers are missing.
+    pub fn notarize<S: Signer>(
+        &self,
+        notary: &S,
+    ) -> Result<NotarizedTransactionV1, PartialSigningError> {
+        let missing_signers = self.missing_signers()?;
+        if !missing_signers.is_empty() {
+            return Err(PartialSigningError::MissingSigners(missing_signers));
+        }
+        self.notarize_allowing_missing_signers(notary)
+    }
+
+    pub fn notarize_allowing_missing_signers<S: Signer>(
+        &self,
+        notary: &S,
+    ) -> Result<NotarizedTransactionV1, PartialSigningError> {
+        let expected = self.intent.header.notary_public_key;
+        let actual = notary.public_key();
+        if expected != actual {
+            return Err(PartialSigningError::InvalidNotary { expected, actual });
+        }
+        let signed_intent = SignedIntentV1 {
+            intent: self.intent.clone(),
+            intent_signatures: self.intent_signatures.clone(),
+        };
+        let signed_intent_hash = signed_intent.prepare()?.signed_intent_hash();
+        Ok(NotarizedTransactionV1 {
+            signed_intent,
+            notary_signature: NotarySignatureV1(
+                notary
+                    .try_sign_with_public_key(&signed_intent_hash)
+                    .map_err(PartialSigningError::signing_error)?
+                    .signature(),
+            ),
+        })
+    }
+
+    pub fn to_payload_bytes(&self) -> Result<Vec<u8>, PartialSigningError> {
+        manifest_encode(self).map_err(PartialSigningError::EncodeError)
+    }
+
+    pub fn from_payload_bytes(payload_bytes: &[u8]) -> Result<Self, PartialSigningError> {
+        manifest_decode(payload_bytes).map_err(PartialSigningError::DecodeError)
+    }
+}
+
+fn preallocated_owner_public_key_hash(address: &GlobalAddress) -> Option<PublicKeyHash> {
+    let node_id = address.as_node_id();
+    let hash_bytes = copy_u8_array(&node_id.as_bytes()[1..]);
+    match node_id.entity_type()? {
+        EntityType::GlobalVirtualSecp256k1Account | EntityType::GlobalVirtualSecp256k1Identity => {
+            Some(PublicKeyHash::Secp256k1(Secp256k1PublicKeyHash(hash_bytes)))
+        }
+        EntityType::GlobalVirtualEd25519Account | EntityType::GlobalVirtualEd25519Identity => {
+            Some(PublicKeyHash::Ed25519(Ed25519PublicKeyHash(hash_bytes)))
+        }
+        _ => None,
+    }
+}
+
+fn is_owner_protected_main_method(method_name: &str) -> bool {
+    // The deposit methods are excluded, as they may be permitted by the account's deposit rules,
+    // and identities have no owner-protected methods besides securify.
+    [
+        ACCOUNT_SECURIFY_IDENT,
+        ACCOUNT_LOCK_FEE_IDENT,
+        ACCOUNT_LOCK_CONTINGENT_FEE_IDENT,
+        ACCOUNT_WITHDRAW_IDENT,
+        ACCOUNT_WITHDRAW_NON_FUNGIBLES_IDENT,
+        ACCOUNT_LOCK_FEE_AND_WITHDRAW_IDENT,
+        ACCOUNT_LOCK_FEE_AND_WITHDRAW_NON_FUNGIBLES_IDENT,
+        ACCOUNT_CREATE_PROOF_OF_AMOUNT_IDENT,
+        ACCOUNT_CREATE_PROOF_OF_NON_FUNGIBLES_IDENT,
+        ACCOUNT_SET_DEFAULT_DEPOSIT_RULE_IDENT,
+        ACCOUNT_SET_RESOURCE_PREFERENCE_IDENT,
+        ACCOUNT_REMOVE_RESOURCE_PREFERENCE_IDENT,
+        ACCOUNT_BURN_IDENT,
+        ACCOUNT_BURN_NON_FUNGIBLES_IDENT,
+        ACCOUNT_ADD_AUTHORIZED_DEPOSITOR,
+        ACCOUNT_REMOVE_AUTHORIZED_DEPOSITOR,
+        IDENTITY_SECURIFY_IDENT,
+    ]
+    .contains(&method_name)
+}
+
+fn is_owner_protected_metadata_method(method_name: &str) -> bool {
+    [
+        METADATA_SET_IDENT,
+        METADATA_LOCK_IDENT,
+        METADATA_REMOVE_IDENT,
+    ]
+    .contains(&method_name)
+}
+
+fn is_owner_protected_role_assignment_method(method_name: &str) -> bool {
+    [
+        ROLE_ASSIGNMENT_SET_IDENT,
+        ROLE_ASSIGNMENT_SET_OWNER_IDENT,
+        ROLE_ASSIGNMENT_LOCK_OWNER_IDENT,
+    ]
+    .contains(&method_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::*;
+
+    fn create_partially_signed_transaction(
+        notary: &Secp256k1PrivateKey,
+        accounts: &[ComponentAddress],
+    ) -> PartiallySignedTransactionV1 {
+        let mut manifest_builder = ManifestBuilder::new();
+        for account in accounts {
+            manifest_builder = manifest_builder.withdraw_from_account(*account, XRD, 1);
+        }
+        let (instructions, blobs) = manifest_builder
+            .try_deposit_entire_worktop_or_abort(accounts[0], None)
+            .build()
+            .for_intent();
+        PartiallySignedTransactionV1::new(IntentV1 {
+            header: TransactionHeaderV1 {
+                network_id: NetworkDefinition::simulator().id,
+                start_epoch_inclusive: Epoch::zero(),
+                end_epoch_exclusive: Epoch::of(100),
+                nonce: 5,
+                notary_public_key: notary.public_key().into(),
+                notary_is_signatory: false,
+                tip_percentage: 0,
+            },
+            instructions,
+            blobs,
+            message: MessageV1::None,
+        })
+    }
+
+    #[test]
+    fn required_signers_are_derived_from_preallocated_accounts() {
+        let notary = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let signer1 = Secp256k1PrivateKey::from_u64(2).unwrap();
+        let signer2 = Ed25519PrivateKey::from_u64(3).unwrap();
+        let account1 = ComponentAddress::virtual_account_from_public_key(&signer1.public_key());
+        let account2 = ComponentAddress::virtual_account_from_public_key(&signer2.public_key());
+
+        let transaction = create_partially_signed_transaction(&notary, &[account1, account2]);
+
+        assert_eq!(
+            transaction.required_signers,
+            indexset!(
+                PublicKey::from(signer1.public_key()).get_hash(),
+                PublicKey::from(signer2.public_key()).get_hash(),
+            )
+        );
+    }
+
+    #[test]
+    fn signatures_collected_separately_can_be_merged_and_notarized() {
+        let notary = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let signer1 = Secp256k1PrivateKey::from_u64(2).unwrap();
+        let signer2 = Ed25519PrivateKey::from_u64(3).unwrap();
+        let account1 = ComponentAddress::virtual_account_from_public_key(&signer1.public_key());
+        let account2 = ComponentAddress::virtual_account_from_public_key(&signer2.public_key());
+        let transaction = create_partially_signed_transaction(&notary, &[account1, account2]);
+
+        // Each signer signs their own copy, as it's passed around as a payload
+        let payload = transaction.to_payload_bytes().unwrap();
+        let mut copy1 = PartiallySignedTransactionV1::from_payload_bytes(&payload).unwrap();
+        copy1.sign(&signer1).unwrap();
+        let mut copy2 = PartiallySignedTransactionV1::from_payload_bytes(&payload).unwrap();
+        copy2.sign(&signer2).unwrap();
+        assert_eq!(
+            copy1.notarize(&notary),
+            Err(PartialSigningError::MissingSigners(vec![PublicKey::from(
+                signer2.public_key()
+            )
+            .get_hash()]))
+        );
+
+        copy1.merge(&copy2).unwrap();
+        copy1.merge(&copy2).unwrap();
+        assert_eq!(copy1.intent_signatures.signatures.len(), 2);
+        assert_eq!(copy1.missing_signers(), Ok(vec![]));
+
+        let transaction = copy1.notarize(&notary).unwrap();
+        let validator = NotarizedTransactionValidator::new(ValidationConfig::simulator());
+        let validated = validator.validate(transaction.prepare().unwrap()).unwrap();
+        assert_eq!(validated.signer_keys.len(), 2);
+    }
+
+    #[test]
+    fn invalid_and_duplicate_signatures_are_rejected() {
+        let notary = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let signer = Secp256k1PrivateKey::from_u64(2).unwrap();
+        let account = ComponentAddress::virtual_account_from_public_key(&signer.public_key());
+        let mut transaction = create_partially_signed_transaction(&notary, &[account]);
+
+        transaction.sign(&signer).unwrap();
+        assert_eq!(
+            transaction.sign(&signer),
+            Err(PartialSigningError::DuplicateSigner(
+                signer.public_key().into()
+            ))
+        );
+        let other_signer = Ed25519PrivateKey::from_u64(3).unwrap();
+        assert_eq!(
+            transaction.add_signature(other_signer.sign_with_public_key(&hash("other"))),
+            Err(PartialSigningError::InvalidIntentSignature)
+        );
+
+        let other_transaction = create_partially_signed_transaction(&signer, &[account]);
+        assert!(matches!(
+            transaction.merge(&other_transaction),
+            Err(PartialSigningError::MismatchingIntents { .. })
+        ));
+        assert!(matches!(
+            transaction.notarize(&signer),
+            Err(PartialSigningError::InvalidNotary { .. })
+        ));
+    }
+}
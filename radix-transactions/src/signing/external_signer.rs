@@ -0,0 +1,544 @@
+//! A [`Signer`] which delegates signing to an external process, for keys which can't be held in
+//! memory (e.g. keys held by an HSM-fronting signing daemon).
+//!
+//! The signer talks to the external process over a line-based JSON protocol: every request is a
+//! single line containing a JSON object, and the external process must answer every request with
+//! a single line containing a JSON object. Keys and hashes are hex-encoded.
+//!
+//! When connecting, the public key of the external signer is requested:
+//! ```text
+//! > {"method":"public_key"}
+//! < {"curve":"secp256k1","public_key":"02..."}
+//! ```
+//!
+//! Every signature is then requested with:
+//! ```text
+//! > {"method":"sign","curve":"secp256k1","public_key":"02...","hash":"9f..."}
+//! < {"signature":"01..."}
+//! ```
+//!
+//! The `curve` is either `secp256k1` or `ed25519`. Secp256k1 signatures must be 65 byte
+//! recoverable signatures (the recovery id followed by the compact signature) and Ed25519
+//! signatures must be 64 bytes.
+//!
+//! The external process can reject any request by answering with `{"error":"<message>"}`, which
+//! is surfaced as [`ExternalSignerError::SignerError`]. If it doesn't answer within the read
+//! timeout, [`ExternalSignerError::TimedOut`] is returned, and the signer can't be used again.
+
+use crate::internal_prelude::*;
+use radix_common::prelude::IsHash;
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::Mutex;
+use std::time::Duration;
+
+#[derive(Debug)]
+pub enum ExternalSignerError {
+    IOError(std::io::Error),
+    ConnectionClosed,
+    TimedOut,
+    InvalidResponse(String),
+    SignerError(String),
+    UnsupportedCurve(String),
+    InvalidPublicKey,
+    InvalidSignature,
+}
+
+impl From<std::io::Error> for ExternalSignerError {
+    fn from(err: std::io::Error) -> Self {
+        Self::IOError(err)
+    }
+}
+
+struct ExternalSignerConnection {
+    writer: Box<dyn Write + Send>,
+    /// The lines read from the external signer, by a separate thread so that reads can time out.
+    /// An empty line means the external signer closed the connection.
+    lines: Receiver<std::io::Result<String>>,
+    read_timeout: Duration,
+    /// Once a request has timed out, a late response can't be told apart from the response to the
+    /// next request, so no more requests are made.
+    timed_out: bool,
+    child: Option<Child>,
+}
+
+impl ExternalSignerConnection {
+    fn new(
+        reader: impl BufRead + Send + 'static,
+        writer: impl Write + Send + 'static,
+        read_timeout: Duration,
+        child: Option<Child>,
+    ) -> Self {
+        Self {
+            writer: Box::new(writer),
+            lines: spawn_line_reader(reader),
+            read_timeout,
+            timed_out: false,
+            child,
+        }
+    }
+
+    fn request(&mut self, request: Value) -> Result<Value, ExternalSignerError> {
+        if self.timed_out {
+            return Err(ExternalSignerError::TimedOut);
+        }
+        writeln!(self.writer, "{}", request)?;
+        self.writer.flush()?;
+
+        let line = match self.lines.recv_timeout(self.read_timeout) {
+            Ok(line) => line?,
+            Err(RecvTimeoutError::Timeout) => {
+                self.timed_out = true;
+                return Err(ExternalSignerError::TimedOut);
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(ExternalSignerError::ConnectionClosed);
+            }
+        };
+        if line.is_empty() {
+            return Err(ExternalSignerError::ConnectionClosed);
+        }
+        let response: Value = serde_json::from_str(line.trim())
+            .map_err(|err| ExternalSignerError::InvalidResponse(err.to_string()))?;
+        match response.get("error") {
+            Some(error) => Err(ExternalSignerError::SignerError(match error.as_str() {
+                Some(message) => message.to_string(),
+                None => error.to_string(),
+            })),
+            None => Ok(response),
+        }
+    }
+}
+
+impl Drop for ExternalSignerConnection {
+    fn drop(&mut self) {
+        if let Some(child) = &mut self.child {
+            // Closing stdin signals the external process to exit, but a process which stopped
+            // answering may never read it
+            self.writer = Box::new(std::io::sink());
+            if self.timed_out {
+                let _ = child.kill();
+            }
+            let _ = child.wait();
+        }
+    }
+}
+
+fn spawn_line_reader(
+    mut reader: impl BufRead + Send + 'static,
+) -> Receiver<std::io::Result<String>> {
+    let (sender, receiver) = channel();
+    std::thread::spawn(move || loop {
+        let mut line = String::new();
+        let result = reader.read_line(&mut line);
+        let is_last = !matches!(result, Ok(length) if length > 0);
+        if sender.send(result.map(|_| line)).is_err() || is_last {
+            break;
+        }
+    });
+    receiver
+}
+
+/// Shuts the socket down when the signer is dropped, so that both the daemon and the thread
+/// reading from the socket see the connection close.
+#[cfg(unix)]
+struct UnixSocketWriter(std::os::unix::net::UnixStream);
+
+#[cfg(unix)]
+impl Write for UnixSocketWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.0.flush()
+    }
+}
+
+#[cfg(unix)]
+impl Drop for UnixSocketWriter {
+    fn drop(&mut self) {
+        let _ = self.0.shutdown(std::net::Shutdown::Both);
+    }
+}
+
+pub struct ExternalSigner {
+    public_key: PublicKey,
+    connection: Mutex<ExternalSignerConnection>,
+}
+
+impl ExternalSigner {
+    /// A read timeout which leaves time for signers which wait for the user to confirm signing.
+    pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(60);
+
+    /// Runs the given command in the system shell and talks to it over its stdin and stdout.
+    /// The stderr of the command is inherited, so it can be used for diagnostics.
+    ///
+    /// Every request fails with [`ExternalSignerError::TimedOut`] if the command doesn't answer
+    /// within the `read_timeout`, e.g. [`Self::DEFAULT_READ_TIMEOUT`].
+    pub fn spawn(command: &str, read_timeout: Duration) -> Result<Self, ExternalSignerError> {
+        #[cfg(windows)]
+        let shell_command = {
+            let mut shell_command = Command::new("cmd");
+            shell_command.arg("/C").arg(command);
+            shell_command
+        };
+        #[cfg(not(windows))]
+        let shell_command = {
+            let mut shell_command = Command::new("sh");
+            shell_command.arg("-c").arg(command);
+            shell_command
+        };
+        Self::from_command(shell_command, read_timeout)
+    }
+
+    pub fn from_command(
+        mut command: Command,
+        read_timeout: Duration,
+    ) -> Result<Self, ExternalSignerError> {
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()?;
+        let reader = BufReader::new(child.stdout.take().expect("stdout is piped"));
+        let writer = child.stdin.take().expect("stdin is piped");
+        Self::connect(ExternalSignerConnection::new(
+            reader,
+            writer,
+            read_timeout,
+            Some(child),
+        ))
+    }
+
+    /// Talks to a signing daemon listening on the Unix socket at the given path.
+    #[cfg(unix)]
+    pub fn connect_unix_socket(
+        path: impl AsRef<std::path::Path>,
+        read_timeout: Duration,
+    ) -> Result<Self, ExternalSignerError> {
+        let stream = std::os::unix::net::UnixStream::connect(path)?;
+        let reader = BufReader::new(stream.try_clone()?);
+        Self::from_streams(reader, UnixSocketWriter(stream), read_timeout)
+    }
+
+    /// Talks to an external signer over arbitrary streams. The reader is read from a separate
+    /// thread, which runs until the reader reaches its end.
+    pub fn from_streams(
+        reader: impl BufRead + Send + 'static,
+        writer: impl Write + Send + 'static,
+        read_timeout: Duration,
+    ) -> Result<Self, ExternalSignerError> {
+        Self::connect(ExternalSignerConnection::new(
+            reader,
+            writer,
+            read_timeout,
+            None,
+        ))
+    }
+
+    fn connect(mut connection: ExternalSignerConnection) -> Result<Self, ExternalSignerError> {
+        let response = connection.request(json!({ "method": "public_key" }))?;
+        let curve = get_string_field(&response, "curve")?;
+        let public_key_bytes = get_hex_field(&response, "public_key")?;
+        let public_key = match curve {
+            "secp256k1" => Secp256k1PublicKey::try_from(public_key_bytes.as_slice())
+                .map_err(|_| ExternalSignerError::InvalidPublicKey)?
+                .into(),
+            "ed25519" => Ed25519PublicKey::try_from(public_key_bytes.as_slice())
+                .map_err(|_| ExternalSignerError::InvalidPublicKey)?
+                .into(),
+            _ => return Err(ExternalSignerError::UnsupportedCurve(curve.to_string())),
+        };
+        Ok(Self {
+            public_key,
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// Requests a signature from the external signer, and checks that it's valid for the
+    /// signer's public key.
+    pub fn try_sign_without_public_key(
+        &self,
+        message_hash: &impl IsHash,
+    ) -> Result<SignatureV1, ExternalSignerError> {
+        let (curve, public_key) = match &self.public_key {
+            PublicKey::Secp256k1(public_key) => ("secp256k1", public_key.to_vec()),
+            PublicKey::Ed25519(public_key) => ("ed25519", public_key.to_vec()),
+        };
+        let response = self.connection.lock().unwrap().request(json!({
+            "method": "sign",
+            "curve": curve,
+            "public_key": hex::encode(public_key),
+            "hash": hex::encode(message_hash.as_slice()),
+        }))?;
+        let signature_bytes = get_hex_field(&response, "signature")?;
+        let signature: SignatureV1 = match &self.public_key {
+            PublicKey::Secp256k1(_) => Secp256k1Signature::try_from(signature_bytes.as_slice())
+                .map_err(|_| ExternalSignerError::InvalidSignature)?
+                .into(),
+            PublicKey::Ed25519(_) => Ed25519Signature::try_from(signature_bytes.as_slice())
+                .map_err(|_| ExternalSignerError::InvalidSignature)?
+                .into(),
+        };
+        if !verify(
+            &Hash(*message_hash.as_bytes()),
+            &self.public_key,
+            &signature,
+        ) {
+            return Err(ExternalSignerError::InvalidSignature);
+        }
+        Ok(signature)
+    }
+
+    pub fn try_sign_with_public_key(
+        &self,
+        message_hash: &impl IsHash,
+    ) -> Result<SignatureWithPublicKeyV1, ExternalSignerError> {
+        Ok(
+            match (
+                self.try_sign_without_public_key(message_hash)?,
+                &self.public_key,
+            ) {
+                (SignatureV1::Secp256k1(signature), _) => {
+                    SignatureWithPublicKeyV1::Secp256k1 { signature }
+                }
+                (SignatureV1::Ed25519(signature), PublicKey::Ed25519(public_key)) => {
+                    SignatureWithPublicKeyV1::Ed25519 {
+                        public_key: *public_key,
+                        signature,
+                    }
+                }
+                (SignatureV1::Ed25519(_), PublicKey::Secp256k1(_)) => {
+                    unreachable!("Signature is verified against the public key")
+                }
+            },
+        )
+    }
+}
+
+/// The infallible [`Signer`] methods panic if the external signer fails, so prefer
+/// [`Signer::try_sign_with_public_key`] or the `try_` methods of the [`ExternalSigner`] itself.
+impl Signer for ExternalSigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    fn sign_without_public_key(&self, message_hash: &impl IsHash) -> SignatureV1 {
+        self.try_sign_without_public_key(message_hash)
+            .unwrap_or_else(|err| panic!("External signer failed to sign: {:?}", err))
+    }
+
+    fn sign_with_public_key(&self, message_hash: &impl IsHash) -> SignatureWithPublicKeyV1 {
+        self.try_sign_with_public_key(message_hash)
+            .unwrap_or_else(|err| panic!("External signer failed to sign: {:?}", err))
+    }
+
+    fn try_sign_with_public_key(
+        &self,
+        message_hash: &impl IsHash,
+    ) -> Result<SignatureWithPublicKeyV1, SigningError> {
+        ExternalSigner::try_sign_with_public_key(self, message_hash)
+            .map_err(SigningError::ExternalSignerError)
+    }
+}
+
+fn get_string_field<'a>(response: &'a Value, field: &str) -> Result<&'a str, ExternalSignerError> {
+    response
+        .get(field)
+        .and_then(Value::as_str)
+        .ok_or_else(|| ExternalSignerError::InvalidResponse(format!("Missing field `{}`", field)))
+}
+
+fn get_hex_field(response: &Value, field: &str) -> Result<Vec<u8>, ExternalSignerError> {
+    hex::decode(get_string_field(response, field)?).map_err(|_| {
+        ExternalSignerError::InvalidResponse(format!("Field `{}` is not valid hex", field))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Condvar};
+
+    /// An in-process implementation of the protocol, which answers requests as they're written.
+    #[derive(Clone)]
+    struct InMemorySigningDaemon {
+        private_key: Arc<PrivateKey>,
+        request: Arc<Mutex<Vec<u8>>>,
+        responses: Arc<(Mutex<Vec<u8>>, Condvar)>,
+    }
+
+    impl InMemorySigningDaemon {
+        fn new(private_key: PrivateKey) -> Self {
+            Self {
+                private_key: Arc::new(private_key),
+                request: Default::default(),
+                responses: Default::default(),
+            }
+        }
+
+        fn respond(&self, request: &Value) -> Value {
+            let (curve, public_key) = match self.private_key.public_key() {
+                PublicKey::Secp256k1(public_key) => ("secp256k1", public_key.to_vec()),
+                PublicKey::Ed25519(public_key) => ("ed25519", public_key.to_vec()),
+            };
+            match request["method"].as_str() {
+                Some("public_key") => {
+                    json!({ "curve": curve, "public_key": hex::encode(public_key) })
+                }
+                Some("sign") => {
+                    let hash = Hash::try_from(
+                        hex::decode(request["hash"].as_str().unwrap())
+                            .unwrap()
+                            .as_slice(),
+                    )
+                    .unwrap();
+                    let signature = match self.private_key.sign_without_public_key(&hash) {
+                        SignatureV1::Secp256k1(signature) => signature.to_vec(),
+                        SignatureV1::Ed25519(signature) => signature.to_vec(),
+                    };
+                    json!({ "signature": hex::encode(signature) })
+                }
+                _ => json!({ "error": "Unknown method" }),
+            }
+        }
+    }
+
+    impl Write for InMemorySigningDaemon {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            let mut request = self.request.lock().unwrap();
+            for byte in buf {
+                if *byte == b'\n' {
+                    let response = self.respond(&serde_json::from_slice(&request).unwrap());
+                    let (responses, responded) = &*self.responses;
+                    let mut responses = responses.lock().unwrap();
+                    responses.extend(response.to_string().into_bytes());
+                    responses.push(b'\n');
+                    responded.notify_all();
+                    request.clear();
+                } else {
+                    request.push(*byte);
+                }
+            }
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl std::io::Read for InMemorySigningDaemon {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            // Like a pipe, blocks until there's something to read
+            let (responses, responded) = &*self.responses;
+            let mut responses = responded
+                .wait_while(responses.lock().unwrap(), |responses| responses.is_empty())
+                .unwrap();
+            let length = buf.len().min(responses.len());
+            buf[..length].copy_from_slice(&responses[..length]);
+            responses.drain(..length);
+            Ok(length)
+        }
+    }
+
+    fn external_signer_for(private_key: PrivateKey) -> ExternalSigner {
+        let daemon = InMemorySigningDaemon::new(private_key);
+        ExternalSigner::from_streams(
+            BufReader::new(daemon.clone()),
+            daemon,
+            ExternalSigner::DEFAULT_READ_TIMEOUT,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_external_secp256k1_signer_signs_like_the_private_key() {
+        let private_key = Secp256k1PrivateKey::from_u64(1).unwrap();
+        let signer = external_signer_for(Secp256k1PrivateKey::from_u64(1).unwrap().into());
+        let hash = hash("message");
+
+        assert_eq!(signer.public_key(), private_key.public_key().into());
+        assert_eq!(
+            signer.sign_with_public_key(&hash),
+            private_key.sign_with_public_key(&hash)
+        );
+    }
+
+    #[test]
+    fn test_external_ed25519_signer_signs_like_the_private_key() {
+        let private_key = Ed25519PrivateKey::from_u64(1).unwrap();
+        let signer = external_signer_for(Ed25519PrivateKey::from_u64(1).unwrap().into());
+        let hash = hash("message");
+
+        assert_eq!(signer.public_key(), private_key.public_key().into());
+        assert_eq!(
+            signer.sign_with_public_key(&hash),
+            private_key.sign_with_public_key(&hash)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_signer_errors_are_propagated() {
+        let result = ExternalSigner::spawn(
+            r#"read _; echo '{"error":"Signing key is locked"}'"#,
+            ExternalSigner::DEFAULT_READ_TIMEOUT,
+        );
+
+        assert!(matches!(
+            result,
+            Err(ExternalSignerError::SignerError(message)) if message == "Signing key is locked"
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_invalid_signatures_are_rejected() {
+        let public_key = Secp256k1PrivateKey::from_u64(1).unwrap().public_key();
+        let command = format!(
+            r#"read _; echo '{{"curve":"secp256k1","public_key":"{}"}}'; read _; echo '{{"signature":"{}"}}'"#,
+            hex::encode(public_key.to_vec()),
+            hex::encode([1u8; Secp256k1Signature::LENGTH]),
+        );
+        let signer = ExternalSigner::spawn(&command, ExternalSigner::DEFAULT_READ_TIMEOUT).unwrap();
+
+        assert!(matches!(
+            signer.try_sign_with_public_key(&hash("message")),
+            Err(ExternalSignerError::InvalidSignature)
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unresponsive_signers_time_out() {
+        let start = std::time::Instant::now();
+        let result = ExternalSigner::spawn("sleep 10", Duration::from_millis(100));
+
+        assert!(matches!(result, Err(ExternalSignerError::TimedOut)));
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_requests_fail_after_a_timeout() {
+        let public_key = Secp256k1PrivateKey::from_u64(1).unwrap().public_key();
+        let command = format!(
+            r#"read _; echo '{{"curve":"secp256k1","public_key":"{}"}}'; sleep 10"#,
+            hex::encode(public_key.to_vec()),
+        );
+        let signer = ExternalSigner::spawn(&command, Duration::from_millis(100)).unwrap();
+
+        assert!(matches!(
+            signer.try_sign_with_public_key(&hash("message")),
+            Err(ExternalSignerError::TimedOut)
+        ));
+        assert!(matches!(
+            signer.try_sign_with_public_key(&hash("message")),
+            Err(ExternalSignerError::TimedOut)
+        ));
+    }
+}
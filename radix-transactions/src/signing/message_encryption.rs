@@ -0,0 +1,481 @@
+use crate::internal_prelude::*;
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes128Gcm, Nonce};
+use aes_kw::KekAes256;
+use blake2::digest::consts::U32;
+use blake2::Blake2b;
+use hkdf::SimpleHkdf;
+
+/// The length of the 128-bit ephemeral AES-GCM key, which encrypts the message payload.
+pub const MESSAGE_AES_KEY_LENGTH: usize = 16;
+/// The length of the AES-GCM nonce, which prefixes the `AesGcmPayload`.
+pub const MESSAGE_AES_GCM_NONCE_LENGTH: usize = 12;
+/// The length of the AES-GCM authentication tag, which suffixes the `AesGcmPayload`.
+pub const MESSAGE_AES_GCM_TAG_LENGTH: usize = 16;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageEncryptionError {
+    NoDecryptors,
+    InvalidEphemeralPrivateKey(CurveType),
+    InvalidDecryptorPublicKey(PublicKey),
+    EncodeError(EncodeError),
+    EncryptionFailed,
+    KeyWrapFailed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessageDecryptionError {
+    NoDecryptorsForCurveType(CurveType),
+    MismatchingDecryptorCurves {
+        actual: CurveType,
+        expected: CurveType,
+    },
+    NotADecryptor(PublicKeyFingerprint),
+    InvalidEphemeralPublicKey,
+    KeyUnwrapFailed,
+    PayloadTooShort,
+    DecryptionFailed,
+    DecodeError(DecodeError),
+}
+
+/// The random values used to encrypt a message.
+///
+/// These should be freshly generated from a cryptographically secure source for every message -
+/// [`EncryptedMessageBuilderV1::encrypt`] does this for you. Providing them explicitly is mostly
+/// useful for creating test vectors.
+#[derive(Debug, Clone)]
+pub struct MessageEncryptionEntropy {
+    pub aes_key: [u8; MESSAGE_AES_KEY_LENGTH],
+    pub aes_gcm_nonce: [u8; MESSAGE_AES_GCM_NONCE_LENGTH],
+    pub ed25519_ephemeral_private_key: [u8; Ed25519PrivateKey::LENGTH],
+    pub secp256k1_ephemeral_private_key: [u8; Secp256k1PrivateKey::LENGTH],
+}
+
+impl MessageEncryptionEntropy {
+    #[cfg(feature = "std")]
+    pub fn generate() -> Self {
+        use rand::RngCore;
+
+        let mut rng = rand::rngs::OsRng;
+        let mut entropy = Self {
+            aes_key: [0u8; MESSAGE_AES_KEY_LENGTH],
+            aes_gcm_nonce: [0u8; MESSAGE_AES_GCM_NONCE_LENGTH],
+            ed25519_ephemeral_private_key: [0u8; Ed25519PrivateKey::LENGTH],
+            secp256k1_ephemeral_private_key: [0u8; Secp256k1PrivateKey::LENGTH],
+        };
+        rng.fill_bytes(&mut entropy.aes_key);
+        rng.fill_bytes(&mut entropy.aes_gcm_nonce);
+        rng.fill_bytes(&mut entropy.ed25519_ephemeral_private_key);
+        // A random 32 bytes is an invalid secp256k1 key with negligible probability
+        rng.fill_bytes(&mut entropy.secp256k1_ephemeral_private_key);
+        entropy
+    }
+}
+
+/// Creates an [`EncryptedMessageV1`] from a [`PlaintextMessageV1`], which can be decrypted by any
+/// of the added decryptors. See [`EncryptedMessageV1`] for details of the encryption scheme.
+#[derive(Debug, Clone)]
+pub struct EncryptedMessageBuilderV1 {
+    message: PlaintextMessageV1,
+    decryptors: IndexSet<PublicKey>,
+}
+
+impl EncryptedMessageBuilderV1 {
+    pub fn new(message: PlaintextMessageV1) -> Self {
+        Self {
+            message,
+            decryptors: index_set_new(),
+        }
+    }
+
+    pub fn new_text(mime_type: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(PlaintextMessageV1 {
+            mime_type: mime_type.into(),
+            message: MessageContentsV1::String(message.into()),
+        })
+    }
+
+    pub fn add_decryptor(mut self, public_key: impl Into<PublicKey>) -> Self {
+        self.decryptors.insert(public_key.into());
+        self
+    }
+
+    pub fn add_decryptors(mut self, public_keys: impl IntoIterator<Item = PublicKey>) -> Self {
+        self.decryptors.extend(public_keys);
+        self
+    }
+
+    /// Encrypts the message with freshly generated entropy.
+    #[cfg(feature = "std")]
+    pub fn encrypt(&self) -> Result<EncryptedMessageV1, MessageEncryptionError> {
+        self.encrypt_with_entropy(&MessageEncryptionEntropy::generate())
+    }
+
+    /// Encrypts the message deterministically with the given entropy.
+    ///
+    /// The entropy must never be re-used - [`encrypt`](Self::encrypt) should be preferred outside
+    /// of tests.
+    pub fn encrypt_with_entropy(
+        &self,
+        entropy: &MessageEncryptionEntropy,
+    ) -> Result<EncryptedMessageV1, MessageEncryptionError> {
+        if self.decryptors.is_empty() {
+            return Err(MessageEncryptionError::NoDecryptors);
+        }
+
+        let plaintext_payload =
+            manifest_encode(&self.message).map_err(MessageEncryptionError::EncodeError)?;
+        let cipher = Aes128Gcm::new_from_slice(&entropy.aes_key)
+            .map_err(|_| MessageEncryptionError::EncryptionFailed)?;
+        let cipher_text_and_tag = cipher
+            .encrypt(
+                Nonce::from_slice(&entropy.aes_gcm_nonce),
+                plaintext_payload.as_slice(),
+            )
+            .map_err(|_| MessageEncryptionError::EncryptionFailed)?;
+        let mut encrypted =
+            Vec::with_capacity(MESSAGE_AES_GCM_NONCE_LENGTH + cipher_text_and_tag.len());
+        encrypted.extend_from_slice(&entropy.aes_gcm_nonce);
+        encrypted.extend_from_slice(&cipher_text_and_tag);
+
+        let mut ed25519_decryptors = index_map_new();
+        let mut secp256k1_decryptors = index_map_new();
+        for public_key in self.decryptors.iter() {
+            match public_key {
+                PublicKey::Ed25519(_) => {
+                    ed25519_decryptors.insert(PublicKeyFingerprint::from(*public_key), public_key)
+                }
+                PublicKey::Secp256k1(_) => {
+                    secp256k1_decryptors.insert(PublicKeyFingerprint::from(*public_key), public_key)
+                }
+            };
+        }
+
+        let mut decryptors_by_curve = index_map_new();
+        if !ed25519_decryptors.is_empty() {
+            let ephemeral_private_key = Ed25519PrivateKey::from_bytes(
+                &entropy.ed25519_ephemeral_private_key,
+            )
+            .map_err(|_| MessageEncryptionError::InvalidEphemeralPrivateKey(CurveType::Ed25519))?;
+            let dh_ephemeral_public_key = ephemeral_private_key.public_key();
+            let decryptors = wrap_aes_key_for_decryptors(
+                &entropy.aes_key,
+                &PrivateKey::Ed25519(ephemeral_private_key),
+                ed25519_decryptors,
+            )?;
+            decryptors_by_curve.insert(
+                CurveType::Ed25519,
+                DecryptorsByCurve::Ed25519 {
+                    dh_ephemeral_public_key,
+                    decryptors,
+                },
+            );
+        }
+        if !secp256k1_decryptors.is_empty() {
+            let ephemeral_private_key =
+                Secp256k1PrivateKey::from_bytes(&entropy.secp256k1_ephemeral_private_key).map_err(
+                    |_| MessageEncryptionError::InvalidEphemeralPrivateKey(CurveType::Secp256k1),
+                )?;
+            let dh_ephemeral_public_key = ephemeral_private_key.public_key();
+            let decryptors = wrap_aes_key_for_decryptors(
+                &entropy.aes_key,
+                &PrivateKey::Secp256k1(ephemeral_private_key),
+                secp256k1_decryptors,
+            )?;
+            decryptors_by_curve.insert(
+                CurveType::Secp256k1,
+                DecryptorsByCurve::Secp256k1 {
+                    dh_ephemeral_public_key,
+                    decryptors,
+                },
+            );
+        }
+
+        Ok(EncryptedMessageV1 {
+            encrypted: AesGcmPayload(encrypted),
+            decryptors_by_curve,
+        })
+    }
+}
+
+impl EncryptedMessageV1 {
+    /// The fingerprints of the public keys which can decrypt this message.
+    pub fn decryptor_fingerprints(&self) -> Vec<PublicKeyFingerprint> {
+        self.decryptors_by_curve
+            .values()
+            .flat_map(|decryptors| match decryptors {
+                DecryptorsByCurve::Ed25519 { decryptors, .. }
+                | DecryptorsByCurve::Secp256k1 { decryptors, .. } => decryptors.keys().cloned(),
+            })
+            .collect()
+    }
+
+    pub fn is_decryptor(&self, public_key: &PublicKey) -> bool {
+        let fingerprint = PublicKeyFingerprint::from(*public_key);
+        match self.decryptors_by_curve.get(&curve_type_of(public_key)) {
+            Some(
+                DecryptorsByCurve::Ed25519 { decryptors, .. }
+                | DecryptorsByCurve::Secp256k1 { decryptors, .. },
+            ) => decryptors.contains_key(&fingerprint),
+            None => false,
+        }
+    }
+
+    pub fn decrypt(
+        &self,
+        private_key: &PrivateKey,
+    ) -> Result<PlaintextMessageV1, MessageDecryptionError> {
+        let public_key = private_key.public_key();
+        let curve_type = curve_type_of(&public_key);
+        let fingerprint = PublicKeyFingerprint::from(public_key);
+
+        let decryptors = self
+            .decryptors_by_curve
+            .get(&curve_type)
+            .ok_or(MessageDecryptionError::NoDecryptorsForCurveType(curve_type))?;
+        let (shared_secret, decryptors) = match (private_key, decryptors) {
+            (
+                PrivateKey::Ed25519(private_key),
+                DecryptorsByCurve::Ed25519 {
+                    dh_ephemeral_public_key,
+                    decryptors,
+                },
+            ) => (
+                private_key.diffie_hellman(dh_ephemeral_public_key),
+                decryptors,
+            ),
+            (
+                PrivateKey::Secp256k1(private_key),
+                DecryptorsByCurve::Secp256k1 {
+                    dh_ephemeral_public_key,
+                    decryptors,
+                },
+            ) => (
+                private_key.diffie_hellman(dh_ephemeral_public_key),
+                decryptors,
+            ),
+            _ => {
+                return Err(MessageDecryptionError::MismatchingDecryptorCurves {
+                    actual: decryptors.curve_type(),
+                    expected: curve_type,
+                })
+            }
+        };
+        let wrapped_key = decryptors
+            .get(&fingerprint)
+            .ok_or(MessageDecryptionError::NotADecryptor(fingerprint))?;
+        let shared_secret =
+            shared_secret.map_err(|_| MessageDecryptionError::InvalidEphemeralPublicKey)?;
+
+        let mut aes_key = [0u8; MESSAGE_AES_KEY_LENGTH];
+        KekAes256::from(derive_key_encrypting_key(&shared_secret))
+            .unwrap(&wrapped_key.0, &mut aes_key)
+            .map_err(|_| MessageDecryptionError::KeyUnwrapFailed)?;
+
+        let payload = &self.encrypted.0;
+        if payload.len() < MESSAGE_AES_GCM_NONCE_LENGTH + MESSAGE_AES_GCM_TAG_LENGTH {
+            return Err(MessageDecryptionError::PayloadTooShort);
+        }
+        let (nonce, cipher_text_and_tag) = payload.split_at(MESSAGE_AES_GCM_NONCE_LENGTH);
+        let plaintext_payload = Aes128Gcm::new_from_slice(&aes_key)
+            .map_err(|_| MessageDecryptionError::DecryptionFailed)?
+            .decrypt(Nonce::from_slice(nonce), cipher_text_and_tag)
+            .map_err(|_| MessageDecryptionError::DecryptionFailed)?;
+
+        manifest_decode(&plaintext_payload).map_err(MessageDecryptionError::DecodeError)
+    }
+}
+
+fn curve_type_of(public_key: &PublicKey) -> CurveType {
+    match public_key {
+        PublicKey::Ed25519(_) => CurveType::Ed25519,
+        PublicKey::Secp256k1(_) => CurveType::Secp256k1,
+    }
+}
+
+/// `KEK = HKDF(hash: Blake2b-256, secret: shared secret, salt: [], info: [], length: 256 bits)`
+fn derive_key_encrypting_key(shared_secret: &[u8; 32]) -> [u8; 32] {
+    let mut key_encrypting_key = [0u8; 32];
+    SimpleHkdf::<Blake2b<U32>>::new(Some(&[]), shared_secret)
+        .expand(&[], &mut key_encrypting_key)
+        .expect("32 bytes is a valid HKDF output length");
+    key_encrypting_key
+}
+
+fn wrap_aes_key_for_decryptors(
+    aes_key: &[u8; MESSAGE_AES_KEY_LENGTH],
+    ephemeral_private_key: &PrivateKey,
+    decryptors: IndexMap<PublicKeyFingerprint, &PublicKey>,
+) -> Result<IndexMap<PublicKeyFingerprint, AesWrapped128BitKey>, MessageEncryptionError> {
+    let mut wrapped_keys = index_map_new();
+    for (fingerprint, public_key) in decryptors {
+        let shared_secret = match (ephemeral_private_key, public_key) {
+            (PrivateKey::Ed25519(private_key), PublicKey::Ed25519(public_key)) => {
+                private_key.diffie_hellman(public_key)
+            }
+            (PrivateKey::Secp256k1(private_key), PublicKey::Secp256k1(public_key)) => {
+                private_key.diffie_hellman(public_key)
+            }
+            _ => unreachable!("Decryptors are grouped by curve type"),
+        }
+        .map_err(|_| MessageEncryptionError::InvalidDecryptorPublicKey(*public_key))?;
+
+        let mut wrapped_key = [0u8; AesWrapped128BitKey::LENGTH];
+        KekAes256::from(derive_key_encrypting_key(&shared_secret))
+            .wrap(aes_key, &mut wrapped_key)
+            .map_err(|_| MessageEncryptionError::KeyWrapFailed)?;
+        wrapped_keys.insert(fingerprint, AesWrapped128BitKey(wrapped_key));
+    }
+    Ok(wrapped_keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_entropy() -> MessageEncryptionEntropy {
+        MessageEncryptionEntropy {
+            aes_key: [0x11; MESSAGE_AES_KEY_LENGTH],
+            aes_gcm_nonce: [0x22; MESSAGE_AES_GCM_NONCE_LENGTH],
+            ed25519_ephemeral_private_key: [0x33; Ed25519PrivateKey::LENGTH],
+            secp256k1_ephemeral_private_key: [0x44; Secp256k1PrivateKey::LENGTH],
+        }
+    }
+
+    fn test_decryptors() -> Vec<PrivateKey> {
+        vec![
+            Ed25519PrivateKey::from_u64(1).unwrap().into(),
+            Ed25519PrivateKey::from_u64(2).unwrap().into(),
+            Secp256k1PrivateKey::from_u64(1).unwrap().into(),
+        ]
+    }
+
+    fn test_message_builder() -> EncryptedMessageBuilderV1 {
+        EncryptedMessageBuilderV1::new_text("text/plain", "Hello, Radix!").add_decryptors(
+            test_decryptors()
+                .iter()
+                .map(|private_key| private_key.public_key()),
+        )
+    }
+
+    #[test]
+    fn all_decryptors_can_decrypt_message() {
+        let encrypted_message = test_message_builder().encrypt().unwrap();
+
+        assert_eq!(encrypted_message.decryptor_fingerprints().len(), 3);
+        for private_key in test_decryptors() {
+            assert!(encrypted_message.is_decryptor(&private_key.public_key()));
+            assert_eq!(
+                encrypted_message.decrypt(&private_key).unwrap(),
+                PlaintextMessageV1 {
+                    mime_type: "text/plain".to_string(),
+                    message: MessageContentsV1::String("Hello, Radix!".to_string()),
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn encrypted_message_passes_validation() {
+        let encrypted_message = test_message_builder().encrypt().unwrap();
+
+        let validator = NotarizedTransactionValidator::new(ValidationConfig::simulator());
+        assert_eq!(
+            validator.validate_message_v1(&MessageV1::Encrypted(encrypted_message)),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn non_decryptor_cannot_decrypt_message() {
+        let encrypted_message = test_message_builder().encrypt().unwrap();
+
+        let ed25519_private_key: PrivateKey = Ed25519PrivateKey::from_u64(3).unwrap().into();
+        assert!(!encrypted_message.is_decryptor(&ed25519_private_key.public_key()));
+        assert_eq!(
+            encrypted_message.decrypt(&ed25519_private_key),
+            Err(MessageDecryptionError::NotADecryptor(
+                ed25519_private_key.public_key().into()
+            ))
+        );
+
+        let ed25519_only_message = EncryptedMessageBuilderV1::new_text("text/plain", "Hello!")
+            .add_decryptor(Ed25519PrivateKey::from_u64(1).unwrap().public_key())
+            .encrypt()
+            .unwrap();
+        assert_eq!(
+            ed25519_only_message.decrypt(&Secp256k1PrivateKey::from_u64(1).unwrap().into()),
+            Err(MessageDecryptionError::NoDecryptorsForCurveType(
+                CurveType::Secp256k1
+            ))
+        );
+    }
+
+    #[test]
+    fn tampered_message_cannot_be_decrypted() {
+        let mut encrypted_message = test_message_builder().encrypt().unwrap();
+        let last_byte = encrypted_message.encrypted.0.last_mut().unwrap();
+        *last_byte ^= 1;
+
+        assert_eq!(
+            encrypted_message.decrypt(&test_decryptors()[0]),
+            Err(MessageDecryptionError::DecryptionFailed)
+        );
+    }
+
+    #[test]
+    fn message_without_decryptors_cannot_be_encrypted() {
+        assert_eq!(
+            EncryptedMessageBuilderV1::new_text("text/plain", "Hello!")
+                .encrypt_with_entropy(&test_entropy()),
+            Err(MessageEncryptionError::NoDecryptors)
+        );
+    }
+
+    /// A test vector for other implementations of the scheme - the decryptors are the keys from
+    /// `Ed25519PrivateKey::from_u64(1)`, `Ed25519PrivateKey::from_u64(2)` and
+    /// `Secp256k1PrivateKey::from_u64(1)`.
+    #[test]
+    fn encryption_matches_test_vector() {
+        let encrypted_message = test_message_builder()
+            .encrypt_with_entropy(&test_entropy())
+            .unwrap();
+
+        assert_eq!(
+            hex::encode(&encrypted_message.encrypted.0),
+            "222222222222222222222222e4d45e1b8d2c49ec3869e926190b0b819303ca7b36b6d2ce1421904748c9c2b159b2db51dcfb410636fdb27ec795f77ab1"
+        );
+        assert_eq!(
+            encrypted_message.decryptors_by_curve,
+            indexmap!(
+                CurveType::Ed25519 => DecryptorsByCurve::Ed25519 {
+                    dh_ephemeral_public_key: Ed25519PublicKey::from_str(
+                        "17cb79fb2b4120f2b1ec65e4198d6e08b28e813feb01e4a400839b85e18080ce"
+                    )
+                    .unwrap(),
+                    decryptors: indexmap!(
+                        fingerprint("f91fa8df2486c9ea") => wrapped_key("26fe696c38c621ef5674431f15b532e740eadd8bb25f4f86"),
+                        fingerprint("b403cf0557293778") => wrapped_key("2f4950d8bc5aa580d4a52cdf0ed3fa6d2dfbeb432373aef4"),
+                    ),
+                },
+                CurveType::Secp256k1 => DecryptorsByCurve::Secp256k1 {
+                    dh_ephemeral_public_key: Secp256k1PublicKey::from_str(
+                        "032c0b7cf95324a07d05398b240174dc0c2be444d96b159aa6c7f7b1e668680991"
+                    )
+                    .unwrap(),
+                    decryptors: indexmap!(
+                        fingerprint("a243c1128c2fe737") => wrapped_key("4bfe108f7078c94a7e0a3e460b000229821844fb62137409"),
+                    ),
+                },
+            )
+        );
+    }
+
+    fn fingerprint(hex: &str) -> PublicKeyFingerprint {
+        PublicKeyFingerprint(copy_u8_array(&hex::decode(hex).unwrap()))
+    }
+
+    fn wrapped_key(hex: &str) -> AesWrapped128BitKey {
+        AesWrapped128BitKey(copy_u8_array(&hex::decode(hex).unwrap()))
+    }
+}
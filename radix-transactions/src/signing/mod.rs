@@ -1,3 +1,9 @@
+#[cfg(feature = "std")]
+mod external_signer;
+mod message_encryption;
 mod signer;
 
+#[cfg(feature = "std")]
+pub use external_signer::*;
+pub use message_encryption::*;
 pub use signer::*;
@@ -27,10 +27,80 @@ impl From<Ed25519PrivateKey> for PrivateKey {
     }
 }
 
+/// The reason a [`Signer`] which doesn't hold its key in memory failed to sign.
+#[derive(Debug)]
+pub enum SigningError {
+    #[cfg(feature = "std")]
+    ExternalSignerError(super::ExternalSignerError),
+}
+
 pub trait Signer {
     fn public_key(&self) -> PublicKey;
     fn sign_without_public_key(&self, message_hash: &impl IsHash) -> SignatureV1;
     fn sign_with_public_key(&self, message_hash: &impl IsHash) -> SignatureWithPublicKeyV1;
+
+    /// As [`Signer::sign_with_public_key`], but returns an error instead of panicking if the
+    /// signer fails. Signers which hold their key in memory never fail.
+    fn try_sign_with_public_key(
+        &self,
+        message_hash: &impl IsHash,
+    ) -> Result<SignatureWithPublicKeyV1, SigningError> {
+        Ok(self.sign_with_public_key(message_hash))
+    }
+}
+
+/// An object-safe version of [`Signer`], which is implemented for all signers, so that signers of
+/// different types can be used together, e.g. as a `&[&dyn DynSigner]`.
+pub trait DynSigner {
+    fn signer_public_key(&self) -> PublicKey;
+    fn sign_hash_without_public_key(&self, message_hash: &Hash) -> SignatureV1;
+    fn sign_hash_with_public_key(&self, message_hash: &Hash) -> SignatureWithPublicKeyV1;
+    fn try_sign_hash_with_public_key(
+        &self,
+        message_hash: &Hash,
+    ) -> Result<SignatureWithPublicKeyV1, SigningError>;
+}
+
+impl<S: Signer> DynSigner for S {
+    fn signer_public_key(&self) -> PublicKey {
+        Signer::public_key(self)
+    }
+
+    fn sign_hash_without_public_key(&self, message_hash: &Hash) -> SignatureV1 {
+        self.sign_without_public_key(message_hash)
+    }
+
+    fn sign_hash_with_public_key(&self, message_hash: &Hash) -> SignatureWithPublicKeyV1 {
+        self.sign_with_public_key(message_hash)
+    }
+
+    fn try_sign_hash_with_public_key(
+        &self,
+        message_hash: &Hash,
+    ) -> Result<SignatureWithPublicKeyV1, SigningError> {
+        self.try_sign_with_public_key(message_hash)
+    }
+}
+
+impl<'a> Signer for dyn DynSigner + 'a {
+    fn public_key(&self) -> PublicKey {
+        self.signer_public_key()
+    }
+
+    fn sign_without_public_key(&self, message_hash: &impl IsHash) -> SignatureV1 {
+        self.sign_hash_without_public_key(AsRef::<Hash>::as_ref(message_hash))
+    }
+
+    fn sign_with_public_key(&self, message_hash: &impl IsHash) -> SignatureWithPublicKeyV1 {
+        self.sign_hash_with_public_key(AsRef::<Hash>::as_ref(message_hash))
+    }
+
+    fn try_sign_with_public_key(
+        &self,
+        message_hash: &impl IsHash,
+    ) -> Result<SignatureWithPublicKeyV1, SigningError> {
+        self.try_sign_hash_with_public_key(AsRef::<Hash>::as_ref(message_hash))
+    }
 }
 
 impl Signer for Secp256k1PrivateKey {
@@ -28,6 +28,7 @@ impl CustomValue<NoCustomValueKind> for NoCustomValue {
 pub type BasicEncoder<'a> = VecEncoder<'a, NoCustomValueKind>;
 pub type BasicDecoder<'a> = VecDecoder<'a, NoCustomValueKind>;
 pub type BasicTraverser<'a> = VecTraverser<'a, NoCustomTraversal>;
+pub type BasicStreamingTraverser<I> = StreamingTraverser<I, NoCustomTraversal>;
 pub type BasicValue = Value<NoCustomValueKind, NoCustomValue>;
 pub type BasicValueKind = ValueKind<NoCustomValueKind>;
 pub type BasicEnumVariantValue = EnumVariantValue<NoCustomValueKind, NoCustomValue>;
@@ -163,6 +164,18 @@ pub fn basic_payload_traverser<'b>(buf: &'b [u8]) -> BasicTraverser<'b> {
     )
 }
 
+/// Creates a streaming payload traverser from the input
+pub fn basic_streaming_payload_traverser<I: StreamingInput>(
+    input: I,
+) -> BasicStreamingTraverser<I> {
+    BasicStreamingTraverser::new(
+        input,
+        BASIC_SBOR_V1_MAX_DEPTH,
+        ExpectedStart::PayloadPrefix(BASIC_SBOR_V1_PAYLOAD_PREFIX),
+        true,
+    )
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Sbor)]
 pub enum NoCustomTypeKind {}
 
@@ -231,6 +244,18 @@ impl CustomSchema for NoCustomSchema {
     }
 }
 
+impl RustCodegenCustomSchema for NoCustomSchema {
+    const SBOR_DERIVE: &'static str = "BasicSbor";
+    const ANY_TYPE: &'static str = "BasicValue";
+
+    fn custom_type_name(
+        custom_type_kind: &Self::CustomTypeKind<LocalTypeId>,
+        _: &TypeValidation<Self::CustomTypeValidation>,
+    ) -> String {
+        match *custom_type_kind {}
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
 pub enum NoCustomExtension {}
 
@@ -325,6 +350,19 @@ mod serde_serialization {
             unreachable!("No custom values exist")
         }
     }
+
+    impl DeserializableCustomExtension for NoCustomExtension {
+        type CustomValue = NoCustomValue;
+        type CustomDeserializationContext<'a> = ();
+
+        fn deserialize_custom_value(
+            _: &Self::CustomDeserializationContext<'_>,
+            _: Self::CustomValueKind,
+            _: &str,
+        ) -> Result<Self::CustomValue, String> {
+            unreachable!("No custom values exist")
+        }
+    }
 }
 
 #[cfg(test)]
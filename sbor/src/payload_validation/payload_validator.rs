@@ -125,7 +125,7 @@ pub fn validate_payload_against_schema<'s, E: ValidatableCustomExtension<T>, T>(
     }
 }
 
-fn validate_event_with_type<E: ValidatableCustomExtension<T>, T>(
+pub fn validate_event_with_type<E: ValidatableCustomExtension<T>, T>(
     schema: &Schema<E::CustomSchema>,
     event: &TypedTraversalEvent<E>,
     context: &T,
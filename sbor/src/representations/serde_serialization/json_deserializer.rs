@@ -0,0 +1,1167 @@
+use super::*;
+use crate::rust::prelude::*;
+use crate::traversal::*;
+use crate::*;
+use serde_json::{Map as JsonMap, Value as JsonValue};
+
+/// Allows the custom values of an extension to be parsed back from their JSON representation.
+pub trait DeserializableCustomExtension: CustomExtension {
+    type CustomValue: CustomValue<Self::CustomValueKind>
+        + for<'b> Encode<Self::CustomValueKind, VecEncoder<'b, Self::CustomValueKind>>;
+
+    type CustomDeserializationContext<'a>: Copy;
+
+    /// Parses a custom value from the string it is serialized as.
+    ///
+    /// All custom values are serialized as a JSON string - both in the value field of the
+    /// Programmatic representation, and in the Natural representation.
+    fn deserialize_custom_value(
+        context: &Self::CustomDeserializationContext<'_>,
+        custom_value_kind: Self::CustomValueKind,
+        value: &str,
+    ) -> Result<Self::CustomValue, String>;
+}
+
+pub enum DeserializationParameters<'s, 'a, E: DeserializableCustomExtension> {
+    Schemaless {
+        mode: SerializationMode,
+        custom_context: E::CustomDeserializationContext<'a>,
+        depth_limit: usize,
+    },
+    WithSchema {
+        mode: SerializationMode,
+        custom_context: E::CustomDeserializationContext<'a>,
+        schema: &'s Schema<E::CustomSchema>,
+        type_id: LocalTypeId,
+        depth_limit: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonDeserializationError {
+    /// The location of the offending JSON value, eg `$.fields[1].value`
+    pub path: String,
+    pub error: JsonDeserializationErrorKind,
+}
+
+impl fmt::Display for JsonDeserializationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {:?}", self.path, self.error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsonDeserializationErrorKind {
+    UnsupportedMode(SerializationMode),
+    UnexpectedJsonType {
+        expected: &'static str,
+        actual: &'static str,
+    },
+    MissingField(String),
+    UnexpectedField(String),
+    UnknownValueKind(String),
+    ValueKindDoesNotMatchType {
+        value_kind: String,
+        type_name: Option<String>,
+    },
+    MismatchingChildValueKind {
+        expected: String,
+        actual: String,
+    },
+    /// In the Natural representation, the value kind is taken from the schema. If the schema
+    /// doesn't pin down a single value kind (eg for `Any`), the value must be a tagged custom value
+    /// or a collection whose element kinds can be inferred.
+    CannotInferValueKind,
+    InvalidInteger {
+        value_kind: String,
+        value: String,
+    },
+    InvalidHex(String),
+    InvalidCustomValue {
+        value_kind: String,
+        error: String,
+    },
+    UnknownVariantId(u8),
+    UnknownVariantName(String),
+    MismatchingFieldCount {
+        expected: usize,
+        actual: usize,
+    },
+    DepthLimitExceeded(usize),
+    EncodeError(EncodeError),
+}
+
+/// Parses a value from the JSON produced by serializing it with the given parameters.
+///
+/// The Programmatic representation can be parsed with or without a schema, as it captures the
+/// value kind of every value. The Natural representation leaves out most value kinds, so they are
+/// taken from the schema, and can only be inferred without a schema for tagged values.
+///
+/// If a schema is provided, the value kinds, enum variants and field counts are checked against it,
+/// but type validations are not applied - use `validate_payload_against_schema` on the encoded
+/// payload for that.
+///
+/// Note that the Model representation is not supported.
+pub fn deserialize_value_from_json<E: DeserializableCustomExtension>(
+    json: &JsonValue,
+    parameters: &DeserializationParameters<'_, '_, E>,
+) -> Result<Value<E::CustomValueKind, E::CustomValue>, JsonDeserializationError> {
+    let (mut deserializer, type_id) = JsonDeserializer::new(parameters);
+    deserializer.deserialize_value(json, type_id, 0)
+}
+
+/// Parses a value from its JSON representation (see [`deserialize_value_from_json`]), and encodes
+/// it as a full SBOR payload.
+pub fn deserialize_payload_from_json<E: DeserializableCustomExtension>(
+    json: &JsonValue,
+    parameters: &DeserializationParameters<'_, '_, E>,
+) -> Result<Vec<u8>, JsonDeserializationError> {
+    let value = deserialize_value_from_json(json, parameters)?;
+    let depth_limit = match parameters {
+        DeserializationParameters::Schemaless { depth_limit, .. }
+        | DeserializationParameters::WithSchema { depth_limit, .. } => *depth_limit,
+    };
+    let mut buf = Vec::with_capacity(512);
+    VecEncoder::<E::CustomValueKind>::new(&mut buf, depth_limit)
+        .encode_payload(&value, E::PAYLOAD_PREFIX)
+        .map_err(|error| JsonDeserializationError {
+            path: "$".to_string(),
+            error: JsonDeserializationErrorKind::EncodeError(error),
+        })?;
+    Ok(buf)
+}
+
+enum JsonPathSegment {
+    Field(String),
+    Index(usize),
+}
+
+struct JsonDeserializer<'s, 'a, E: DeserializableCustomExtension> {
+    schema: &'s Schema<E::CustomSchema>,
+    mode: SerializationMode,
+    custom_context: E::CustomDeserializationContext<'a>,
+    depth_limit: usize,
+    path: Vec<JsonPathSegment>,
+}
+
+type DeserializedValue<E> = Value<
+    <E as CustomExtension>::CustomValueKind,
+    <E as DeserializableCustomExtension>::CustomValue,
+>;
+
+type JsonResult<T> = Result<T, JsonDeserializationError>;
+
+impl<'s, 'a, E: DeserializableCustomExtension> JsonDeserializer<'s, 'a, E> {
+    fn new(parameters: &DeserializationParameters<'s, 'a, E>) -> (Self, LocalTypeId) {
+        let (schema, mode, custom_context, type_id, depth_limit) = match parameters {
+            DeserializationParameters::Schemaless {
+                mode,
+                custom_context,
+                depth_limit,
+            } => (
+                E::CustomSchema::empty_schema(),
+                *mode,
+                *custom_context,
+                LocalTypeId::any(),
+                *depth_limit,
+            ),
+            DeserializationParameters::WithSchema {
+                mode,
+                custom_context,
+                schema,
+                type_id,
+                depth_limit,
+            } => (*schema, *mode, *custom_context, *type_id, *depth_limit),
+        };
+        let deserializer = Self {
+            schema,
+            mode,
+            custom_context,
+            depth_limit,
+            path: vec![],
+        };
+        (deserializer, type_id)
+    }
+
+    //====================
+    // Paths and errors
+    //====================
+
+    fn error<T>(&self, error: JsonDeserializationErrorKind) -> JsonResult<T> {
+        let mut path = String::from("$");
+        for segment in &self.path {
+            match segment {
+                JsonPathSegment::Field(field) => {
+                    path.push('.');
+                    path.push_str(field);
+                }
+                JsonPathSegment::Index(index) => {
+                    path.push_str(&format!("[{}]", index));
+                }
+            }
+        }
+        Err(JsonDeserializationError { path, error })
+    }
+
+    fn at<T>(
+        &mut self,
+        segment: JsonPathSegment,
+        f: impl FnOnce(&mut Self) -> JsonResult<T>,
+    ) -> JsonResult<T> {
+        self.path.push(segment);
+        let result = f(self);
+        self.path.pop();
+        result
+    }
+
+    fn at_field<T>(
+        &mut self,
+        field: &str,
+        f: impl FnOnce(&mut Self) -> JsonResult<T>,
+    ) -> JsonResult<T> {
+        self.at(JsonPathSegment::Field(field.to_string()), f)
+    }
+
+    fn at_index<T>(
+        &mut self,
+        index: usize,
+        f: impl FnOnce(&mut Self) -> JsonResult<T>,
+    ) -> JsonResult<T> {
+        self.at(JsonPathSegment::Index(index), f)
+    }
+
+    fn unexpected_json_type<T>(&self, expected: &'static str, actual: &JsonValue) -> JsonResult<T> {
+        let actual = match actual {
+            JsonValue::Null => "null",
+            JsonValue::Bool(_) => "boolean",
+            JsonValue::Number(_) => "number",
+            JsonValue::String(_) => "string",
+            JsonValue::Array(_) => "array",
+            JsonValue::Object(_) => "object",
+        };
+        self.error(JsonDeserializationErrorKind::UnexpectedJsonType { expected, actual })
+    }
+
+    //====================
+    // JSON accessors
+    //====================
+
+    fn expect_object<'j>(&self, json: &'j JsonValue) -> JsonResult<&'j JsonMap<String, JsonValue>> {
+        match json {
+            JsonValue::Object(object) => Ok(object),
+            _ => self.unexpected_json_type("object", json),
+        }
+    }
+
+    fn expect_array<'j>(&self, json: &'j JsonValue) -> JsonResult<&'j Vec<JsonValue>> {
+        match json {
+            JsonValue::Array(array) => Ok(array),
+            _ => self.unexpected_json_type("array", json),
+        }
+    }
+
+    fn expect_str<'j>(&self, json: &'j JsonValue) -> JsonResult<&'j str> {
+        match json {
+            JsonValue::String(string) => Ok(string),
+            _ => self.unexpected_json_type("string", json),
+        }
+    }
+
+    fn expect_field<'j>(
+        &self,
+        object: &'j JsonMap<String, JsonValue>,
+        field: &str,
+    ) -> JsonResult<&'j JsonValue> {
+        match object.get(field) {
+            Some(value) => Ok(value),
+            None => self.error(JsonDeserializationErrorKind::MissingField(
+                field.to_string(),
+            )),
+        }
+    }
+
+    fn parse_value_kind(&self, name: &str) -> JsonResult<ValueKind<E::CustomValueKind>> {
+        match (0..=u8::MAX)
+            .filter_map(ValueKind::<E::CustomValueKind>::from_u8)
+            .find(|value_kind| value_kind.to_string() == name)
+        {
+            Some(value_kind) => Ok(value_kind),
+            None => self.error(JsonDeserializationErrorKind::UnknownValueKind(
+                name.to_string(),
+            )),
+        }
+    }
+
+    fn parse_value_kind_field(
+        &mut self,
+        object: &JsonMap<String, JsonValue>,
+        field: &str,
+    ) -> JsonResult<ValueKind<E::CustomValueKind>> {
+        let json = self.expect_field(object, field)?;
+        self.at_field(field, |d| {
+            let name = d.expect_str(json)?;
+            d.parse_value_kind(name)
+        })
+    }
+
+    fn parse_integer<T: FromStr>(
+        &self,
+        value_kind: ValueKind<E::CustomValueKind>,
+        json: &JsonValue,
+    ) -> JsonResult<T> {
+        // Integers may be represented as JSON numbers or as strings, depending on the mode and
+        // their size - we accept either, as long as the value fits.
+        let string = match json {
+            JsonValue::Number(number) => number.to_string(),
+            JsonValue::String(string) => string.clone(),
+            _ => return self.unexpected_json_type("number or string", json),
+        };
+        match string.parse::<T>() {
+            Ok(value) => Ok(value),
+            Err(_) => self.error(JsonDeserializationErrorKind::InvalidInteger {
+                value_kind: value_kind.to_string(),
+                value: string,
+            }),
+        }
+    }
+
+    //====================
+    // Schema lookups
+    //====================
+
+    fn type_kind(&self, type_id: LocalTypeId) -> Option<&'s SchemaTypeKind<E::CustomSchema>> {
+        self.schema.resolve_type_kind(type_id)
+    }
+
+    /// Returns the value kind implied by the type, if there is a single one.
+    fn value_kind_from_type(&self, type_id: LocalTypeId) -> Option<ValueKind<E::CustomValueKind>> {
+        let type_kind = self.type_kind(type_id)?;
+        let value_kind = match type_kind {
+            TypeKind::Any => return None,
+            TypeKind::Bool => ValueKind::Bool,
+            TypeKind::I8 => ValueKind::I8,
+            TypeKind::I16 => ValueKind::I16,
+            TypeKind::I32 => ValueKind::I32,
+            TypeKind::I64 => ValueKind::I64,
+            TypeKind::I128 => ValueKind::I128,
+            TypeKind::U8 => ValueKind::U8,
+            TypeKind::U16 => ValueKind::U16,
+            TypeKind::U32 => ValueKind::U32,
+            TypeKind::U64 => ValueKind::U64,
+            TypeKind::U128 => ValueKind::U128,
+            TypeKind::String => ValueKind::String,
+            TypeKind::Array { .. } => ValueKind::Array,
+            TypeKind::Tuple { .. } => ValueKind::Tuple,
+            TypeKind::Enum { .. } => ValueKind::Enum,
+            TypeKind::Map { .. } => ValueKind::Map,
+            TypeKind::Custom(_) => {
+                let mut matching = (CUSTOM_VALUE_KIND_START..=u8::MAX)
+                    .filter_map(E::CustomValueKind::from_u8)
+                    .filter(|custom_value_kind| {
+                        E::custom_value_kind_matches_type_kind(
+                            self.schema,
+                            *custom_value_kind,
+                            type_kind,
+                        )
+                    });
+                let custom_value_kind = matching.next()?;
+                if matching.next().is_some() {
+                    return None;
+                }
+                ValueKind::Custom(custom_value_kind)
+            }
+        };
+        Some(value_kind)
+    }
+
+    fn check_value_kind_matches_type(
+        &self,
+        value_kind: ValueKind<E::CustomValueKind>,
+        type_id: LocalTypeId,
+    ) -> JsonResult<()> {
+        let Some(type_kind) = self.type_kind(type_id) else {
+            return Ok(());
+        };
+        if value_kind_matches_type_kind::<E>(self.schema, value_kind, type_kind) {
+            return Ok(());
+        }
+        self.error(JsonDeserializationErrorKind::ValueKindDoesNotMatchType {
+            value_kind: value_kind.to_string(),
+            type_name: self
+                .schema
+                .resolve_type_name_from_metadata(type_id)
+                .map(|name| name.to_string()),
+        })
+    }
+
+    fn field_types(&self, type_id: LocalTypeId, length: usize) -> JsonResult<Vec<LocalTypeId>> {
+        match self.type_kind(type_id) {
+            Some(TypeKind::Tuple { field_types }) => {
+                if field_types.len() != length {
+                    return self.error(JsonDeserializationErrorKind::MismatchingFieldCount {
+                        expected: field_types.len(),
+                        actual: length,
+                    });
+                }
+                Ok(field_types.clone())
+            }
+            _ => Ok(vec![LocalTypeId::any(); length]),
+        }
+    }
+
+    fn variant_field_types(
+        &self,
+        type_id: LocalTypeId,
+        variant_id: u8,
+        length: usize,
+    ) -> JsonResult<Vec<LocalTypeId>> {
+        match self.type_kind(type_id) {
+            Some(TypeKind::Enum { variants }) => {
+                let Some(field_types) = variants.get(&variant_id) else {
+                    return self.error(JsonDeserializationErrorKind::UnknownVariantId(variant_id));
+                };
+                if field_types.len() != length {
+                    return self.error(JsonDeserializationErrorKind::MismatchingFieldCount {
+                        expected: field_types.len(),
+                        actual: length,
+                    });
+                }
+                Ok(field_types.clone())
+            }
+            _ => Ok(vec![LocalTypeId::any(); length]),
+        }
+    }
+
+    fn element_type(&self, type_id: LocalTypeId) -> LocalTypeId {
+        match self.type_kind(type_id) {
+            Some(TypeKind::Array { element_type }) => *element_type,
+            _ => LocalTypeId::any(),
+        }
+    }
+
+    fn key_and_value_types(&self, type_id: LocalTypeId) -> (LocalTypeId, LocalTypeId) {
+        match self.type_kind(type_id) {
+            Some(TypeKind::Map {
+                key_type,
+                value_type,
+            }) => (*key_type, *value_type),
+            _ => (LocalTypeId::any(), LocalTypeId::any()),
+        }
+    }
+
+    /// Reads the variant of an enum value, which is given by its id or, failing that, by its name.
+    fn parse_variant_id(
+        &mut self,
+        object: &JsonMap<String, JsonValue>,
+        type_id: LocalTypeId,
+    ) -> JsonResult<u8> {
+        if let Some(json) = object.get("variant_id") {
+            return self.at_field("variant_id", |d| d.parse_integer::<u8>(ValueKind::U8, json));
+        }
+        let Some(json) = object.get("variant_name") else {
+            return self.error(JsonDeserializationErrorKind::MissingField(
+                "variant_id".to_string(),
+            ));
+        };
+        self.at_field("variant_name", |d| {
+            let variant_name = d.expect_str(json)?;
+            let variant_id = match d.schema.resolve_type_metadata(type_id) {
+                Some(TypeMetadata {
+                    child_names: Some(ChildNames::EnumVariants(variants)),
+                    ..
+                }) => variants
+                    .iter()
+                    .find(|(_, metadata)| metadata.get_name() == Some(variant_name))
+                    .map(|(variant_id, _)| *variant_id),
+                _ => None,
+            };
+            match variant_id {
+                Some(variant_id) => Ok(variant_id),
+                None => d.error(JsonDeserializationErrorKind::UnknownVariantName(
+                    variant_name.to_string(),
+                )),
+            }
+        })
+    }
+
+    fn variant_field_names(
+        &self,
+        type_id: LocalTypeId,
+        variant_id: u8,
+    ) -> Option<&'s [Cow<'static, str>]> {
+        match self.schema.resolve_type_metadata(type_id) {
+            Some(TypeMetadata {
+                child_names: Some(ChildNames::EnumVariants(variants)),
+                ..
+            }) => variants.get(&variant_id)?.get_field_names(),
+            _ => None,
+        }
+    }
+
+    /// Checks that all the children have the same value kind, and returns it.
+    fn common_value_kind<'v>(
+        &self,
+        expected: Option<ValueKind<E::CustomValueKind>>,
+        mut children: impl Iterator<Item = &'v DeserializedValue<E>>,
+    ) -> JsonResult<ValueKind<E::CustomValueKind>>
+    where
+        E::CustomValue: 'v,
+    {
+        let expected = match expected {
+            Some(expected) => expected,
+            None => match children.next() {
+                Some(first) => first.get_value_kind(),
+                None => return self.error(JsonDeserializationErrorKind::CannotInferValueKind),
+            },
+        };
+        for child in children {
+            let actual = child.get_value_kind();
+            if actual != expected {
+                return self.error(JsonDeserializationErrorKind::MismatchingChildValueKind {
+                    expected: expected.to_string(),
+                    actual: actual.to_string(),
+                });
+            }
+        }
+        Ok(expected)
+    }
+
+    //====================
+    // Values
+    //====================
+
+    fn deserialize_value(
+        &mut self,
+        json: &JsonValue,
+        type_id: LocalTypeId,
+        depth: usize,
+    ) -> JsonResult<DeserializedValue<E>> {
+        match self.mode {
+            SerializationMode::Programmatic => {
+                self.deserialize_programmatic_value(json, type_id, depth)
+            }
+            SerializationMode::Natural => self.deserialize_natural_value(json, type_id, depth),
+            SerializationMode::Model => {
+                self.error(JsonDeserializationErrorKind::UnsupportedMode(self.mode))
+            }
+        }
+    }
+
+    fn enter_container(&self, depth: usize) -> JsonResult<usize> {
+        let depth = depth + 1;
+        if depth > self.depth_limit {
+            return self.error(JsonDeserializationErrorKind::DepthLimitExceeded(
+                self.depth_limit,
+            ));
+        }
+        Ok(depth)
+    }
+
+    fn deserialize_programmatic_value(
+        &mut self,
+        json: &JsonValue,
+        type_id: LocalTypeId,
+        depth: usize,
+    ) -> JsonResult<DeserializedValue<E>> {
+        let object = self.expect_object(json)?;
+        let kind = self.expect_field(object, "kind")?;
+        let kind = self.at_field("kind", |d| d.expect_str(kind))?;
+        let value_kind = if kind == "Bytes" {
+            ValueKind::Array
+        } else {
+            self.at_field("kind", |d| d.parse_value_kind(kind))?
+        };
+        self.check_value_kind_matches_type(value_kind, type_id)?;
+
+        match value_kind {
+            ValueKind::Tuple => {
+                let depth = self.enter_container(depth)?;
+                let fields = self.expect_field(object, "fields")?;
+                let fields = self.at_field("fields", |d| {
+                    let fields = d.expect_array(fields)?;
+                    let field_types = d.field_types(type_id, fields.len())?;
+                    d.deserialize_programmatic_children(fields, &field_types, depth)
+                })?;
+                Ok(Value::Tuple { fields })
+            }
+            ValueKind::Enum => {
+                let depth = self.enter_container(depth)?;
+                let discriminator = self.parse_variant_id(object, type_id)?;
+                let fields = self.expect_field(object, "fields")?;
+                let fields = self.at_field("fields", |d| {
+                    let fields = d.expect_array(fields)?;
+                    let field_types =
+                        d.variant_field_types(type_id, discriminator, fields.len())?;
+                    d.deserialize_programmatic_children(fields, &field_types, depth)
+                })?;
+                Ok(Value::Enum {
+                    discriminator,
+                    fields,
+                })
+            }
+            ValueKind::Array => {
+                let depth = self.enter_container(depth)?;
+                let element_value_kind = self.parse_value_kind_field(object, "element_kind")?;
+                if let Some(hex) = object.get("hex") {
+                    return self.at_field("hex", |d| d.deserialize_bytes(element_value_kind, hex));
+                }
+                let element_type = self.element_type(type_id);
+                let elements = self.expect_field(object, "elements")?;
+                let elements = self.at_field("elements", |d| {
+                    let elements = d.expect_array(elements)?;
+                    let element_types = vec![element_type; elements.len()];
+                    let elements =
+                        d.deserialize_programmatic_children(elements, &element_types, depth)?;
+                    d.common_value_kind(Some(element_value_kind), elements.iter())?;
+                    Ok(elements)
+                })?;
+                Ok(Value::Array {
+                    element_value_kind,
+                    elements,
+                })
+            }
+            ValueKind::Map => {
+                let depth = self.enter_container(depth)?;
+                let key_value_kind = self.parse_value_kind_field(object, "key_kind")?;
+                let value_value_kind = self.parse_value_kind_field(object, "value_kind")?;
+                let (key_type, value_type) = self.key_and_value_types(type_id);
+                let entries = self.expect_field(object, "entries")?;
+                let entries = self.at_field("entries", |d| {
+                    let entries = d.expect_array(entries)?;
+                    let mut deserialized_entries = Vec::with_capacity(entries.len());
+                    for (index, entry) in entries.iter().enumerate() {
+                        deserialized_entries.push(d.at_index(index, |d| {
+                            d.deserialize_map_entry(entry, key_type, value_type, depth)
+                        })?);
+                    }
+                    d.common_value_kind(
+                        Some(key_value_kind),
+                        deserialized_entries.iter().map(|(key, _)| key),
+                    )?;
+                    d.common_value_kind(
+                        Some(value_value_kind),
+                        deserialized_entries.iter().map(|(_, value)| value),
+                    )?;
+                    Ok(deserialized_entries)
+                })?;
+                Ok(Value::Map {
+                    key_value_kind,
+                    value_value_kind,
+                    entries,
+                })
+            }
+            _ => {
+                let value = self.expect_field(object, "value")?;
+                self.at_field("value", |d| d.deserialize_terminal_value(value_kind, value))
+            }
+        }
+    }
+
+    fn deserialize_programmatic_children(
+        &mut self,
+        children: &[JsonValue],
+        child_types: &[LocalTypeId],
+        depth: usize,
+    ) -> JsonResult<Vec<DeserializedValue<E>>> {
+        let mut values = Vec::with_capacity(children.len());
+        for (index, (child, child_type)) in children.iter().zip(child_types).enumerate() {
+            values.push(self.at_index(index, |d| {
+                d.deserialize_programmatic_value(child, *child_type, depth)
+            })?);
+        }
+        Ok(values)
+    }
+
+    fn deserialize_map_entry(
+        &mut self,
+        entry: &JsonValue,
+        key_type: LocalTypeId,
+        value_type: LocalTypeId,
+        depth: usize,
+    ) -> JsonResult<(DeserializedValue<E>, DeserializedValue<E>)> {
+        let entry = self.expect_object(entry)?;
+        let key = self.expect_field(entry, "key")?;
+        let key = self.at_field("key", |d| d.deserialize_value(key, key_type, depth))?;
+        let value = self.expect_field(entry, "value")?;
+        let value = self.at_field("value", |d| d.deserialize_value(value, value_type, depth))?;
+        Ok((key, value))
+    }
+
+    fn deserialize_natural_value(
+        &mut self,
+        json: &JsonValue,
+        type_id: LocalTypeId,
+        depth: usize,
+    ) -> JsonResult<DeserializedValue<E>> {
+        let value_kind = match self.value_kind_from_type(type_id) {
+            Some(value_kind) => value_kind,
+            None => self.value_kind_from_tag(json)?,
+        };
+        self.check_value_kind_matches_type(value_kind, type_id)?;
+
+        match value_kind {
+            ValueKind::Tuple => {
+                let depth = self.enter_container(depth)?;
+                let field_names = self
+                    .schema
+                    .resolve_type_metadata(type_id)
+                    .and_then(|metadata| metadata.get_field_names());
+                let length = match json {
+                    JsonValue::Object(object) => object.len(),
+                    _ => self.expect_array(json)?.len(),
+                };
+                let field_types = self.field_types(type_id, length)?;
+                let fields =
+                    self.deserialize_natural_fields(json, &field_types, field_names, depth)?;
+                Ok(Value::Tuple { fields })
+            }
+            ValueKind::Enum => {
+                let depth = self.enter_container(depth)?;
+                let object = self.expect_object(json)?;
+                let discriminator = self.parse_variant_id(object, type_id)?;
+                let field_names = self.variant_field_names(type_id, discriminator);
+                let fields = match object.get("fields") {
+                    Some(fields) => self.at_field("fields", |d| {
+                        let length = match fields {
+                            JsonValue::Object(object) => object.len(),
+                            _ => d.expect_array(fields)?.len(),
+                        };
+                        let field_types = d.variant_field_types(type_id, discriminator, length)?;
+                        d.deserialize_natural_fields(fields, &field_types, field_names, depth)
+                    })?,
+                    None => {
+                        // Fields can be omitted for unit variants
+                        self.variant_field_types(type_id, discriminator, 0)?;
+                        vec![]
+                    }
+                };
+                Ok(Value::Enum {
+                    discriminator,
+                    fields,
+                })
+            }
+            ValueKind::Array => {
+                let depth = self.enter_container(depth)?;
+                let element_type = self.element_type(type_id);
+                let expected_element_value_kind = self.value_kind_from_type(element_type);
+                if let JsonValue::Object(object) = json {
+                    let hex = self.expect_field(object, "hex")?;
+                    return self.at_field("hex", |d| d.deserialize_bytes(ValueKind::U8, hex));
+                }
+                let elements = self.expect_array(json)?;
+                let mut deserialized_elements = Vec::with_capacity(elements.len());
+                for (index, element) in elements.iter().enumerate() {
+                    deserialized_elements.push(self.at_index(index, |d| {
+                        d.deserialize_natural_value(element, element_type, depth)
+                    })?);
+                }
+                let element_value_kind = self
+                    .common_value_kind(expected_element_value_kind, deserialized_elements.iter())?;
+                Ok(Value::Array {
+                    element_value_kind,
+                    elements: deserialized_elements,
+                })
+            }
+            ValueKind::Map => {
+                let depth = self.enter_container(depth)?;
+                let (key_type, value_type) = self.key_and_value_types(type_id);
+                let expected_key_value_kind = self.value_kind_from_type(key_type);
+                let expected_value_value_kind = self.value_kind_from_type(value_type);
+                let entries = match json {
+                    // Maps with string keys are represented as JSON objects
+                    JsonValue::Object(object) => {
+                        if let Some(key_value_kind) = expected_key_value_kind {
+                            if key_value_kind != ValueKind::String {
+                                return self.unexpected_json_type("array", json);
+                            }
+                        }
+                        let mut entries = Vec::with_capacity(object.len());
+                        for (key, value) in object {
+                            let value = self.at_field(key, |d| {
+                                d.deserialize_natural_value(value, value_type, depth)
+                            })?;
+                            entries.push((Value::String { value: key.clone() }, value));
+                        }
+                        entries
+                    }
+                    _ => {
+                        let array = self.expect_array(json)?;
+                        let mut entries = Vec::with_capacity(array.len());
+                        for (index, entry) in array.iter().enumerate() {
+                            entries.push(self.at_index(index, |d| {
+                                d.deserialize_map_entry(entry, key_type, value_type, depth)
+                            })?);
+                        }
+                        entries
+                    }
+                };
+                let key_value_kind = match (json, expected_key_value_kind) {
+                    (JsonValue::Object(_), _) => ValueKind::String,
+                    (_, expected) => {
+                        self.common_value_kind(expected, entries.iter().map(|(key, _)| key))?
+                    }
+                };
+                let value_value_kind = self.common_value_kind(
+                    expected_value_value_kind,
+                    entries.iter().map(|(_, value)| value),
+                )?;
+                Ok(Value::Map {
+                    key_value_kind,
+                    value_value_kind,
+                    entries,
+                })
+            }
+            ValueKind::Custom(_) => match json {
+                // Some custom values are tagged with their kind, even in the Natural representation
+                JsonValue::Object(object) => {
+                    let value = self.expect_field(object, "value")?;
+                    self.at_field("value", |d| d.deserialize_terminal_value(value_kind, value))
+                }
+                _ => self.deserialize_terminal_value(value_kind, json),
+            },
+            _ => self.deserialize_terminal_value(value_kind, json),
+        }
+    }
+
+    /// In the Natural representation, only custom values can carry their kind.
+    fn value_kind_from_tag(
+        &mut self,
+        json: &JsonValue,
+    ) -> JsonResult<ValueKind<E::CustomValueKind>> {
+        let JsonValue::Object(object) = json else {
+            return self.error(JsonDeserializationErrorKind::CannotInferValueKind);
+        };
+        let Some(kind) = object.get("kind") else {
+            return self.error(JsonDeserializationErrorKind::CannotInferValueKind);
+        };
+        self.at_field("kind", |d| {
+            let kind = d.expect_str(kind)?;
+            match d.parse_value_kind(kind)? {
+                value_kind @ ValueKind::Custom(_) => Ok(value_kind),
+                _ => d.error(JsonDeserializationErrorKind::CannotInferValueKind),
+            }
+        })
+    }
+
+    fn deserialize_natural_fields(
+        &mut self,
+        json: &JsonValue,
+        field_types: &[LocalTypeId],
+        field_names: Option<&[Cow<'static, str>]>,
+        depth: usize,
+    ) -> JsonResult<Vec<DeserializedValue<E>>> {
+        let mut fields = Vec::with_capacity(field_types.len());
+        match json {
+            // Fields with known names are represented as JSON objects
+            JsonValue::Object(object) => {
+                let Some(field_names) =
+                    field_names.filter(|names| names.len() == field_types.len())
+                else {
+                    return self.unexpected_json_type("array", json);
+                };
+                for (field_name, field_type) in field_names.iter().zip(field_types) {
+                    let field = self.expect_field(object, field_name)?;
+                    fields.push(self.at_field(field_name, |d| {
+                        d.deserialize_natural_value(field, *field_type, depth)
+                    })?);
+                }
+                if let Some(unexpected) = object
+                    .keys()
+                    .find(|key| !field_names.iter().any(|name| name == key.as_str()))
+                {
+                    return self.error(JsonDeserializationErrorKind::UnexpectedField(
+                        unexpected.clone(),
+                    ));
+                }
+            }
+            _ => {
+                let array = self.expect_array(json)?;
+                for (index, (field, field_type)) in array.iter().zip(field_types).enumerate() {
+                    fields.push(self.at_index(index, |d| {
+                        d.deserialize_natural_value(field, *field_type, depth)
+                    })?);
+                }
+            }
+        }
+        Ok(fields)
+    }
+
+    fn deserialize_bytes(
+        &mut self,
+        element_value_kind: ValueKind<E::CustomValueKind>,
+        json: &JsonValue,
+    ) -> JsonResult<DeserializedValue<E>> {
+        if element_value_kind != ValueKind::U8 {
+            return self.error(JsonDeserializationErrorKind::MismatchingChildValueKind {
+                expected: element_value_kind.to_string(),
+                actual: ValueKind::<E::CustomValueKind>::U8.to_string(),
+            });
+        }
+        let hex = self.expect_str(json)?;
+        let bytes = match hex::decode(hex) {
+            Ok(bytes) => bytes,
+            Err(_) => return self.error(JsonDeserializationErrorKind::InvalidHex(hex.to_string())),
+        };
+        Ok(Value::Array {
+            element_value_kind: ValueKind::U8,
+            elements: bytes.into_iter().map(|value| Value::U8 { value }).collect(),
+        })
+    }
+
+    fn deserialize_terminal_value(
+        &mut self,
+        value_kind: ValueKind<E::CustomValueKind>,
+        json: &JsonValue,
+    ) -> JsonResult<DeserializedValue<E>> {
+        let value = match value_kind {
+            ValueKind::Bool => match json {
+                JsonValue::Bool(value) => Value::Bool { value: *value },
+                _ => return self.unexpected_json_type("boolean", json),
+            },
+            ValueKind::I8 => Value::I8 {
+                value: self.parse_integer(value_kind, json)?,
+            },
+            ValueKind::I16 => Value::I16 {
+                value: self.parse_integer(value_kind, json)?,
+            },
+            ValueKind::I32 => Value::I32 {
+                value: self.parse_integer(value_kind, json)?,
+            },
+            ValueKind::I64 => Value::I64 {
+                value: self.parse_integer(value_kind, json)?,
+            },
+            ValueKind::I128 => Value::I128 {
+                value: self.parse_integer(value_kind, json)?,
+            },
+            ValueKind::U8 => Value::U8 {
+                value: self.parse_integer(value_kind, json)?,
+            },
+            ValueKind::U16 => Value::U16 {
+                value: self.parse_integer(value_kind, json)?,
+            },
+            ValueKind::U32 => Value::U32 {
+                value: self.parse_integer(value_kind, json)?,
+            },
+            ValueKind::U64 => Value::U64 {
+                value: self.parse_integer(value_kind, json)?,
+            },
+            ValueKind::U128 => Value::U128 {
+                value: self.parse_integer(value_kind, json)?,
+            },
+            ValueKind::String => Value::String {
+                value: self.expect_str(json)?.to_string(),
+            },
+            ValueKind::Custom(custom_value_kind) => {
+                let string = self.expect_str(json)?;
+                match E::deserialize_custom_value(&self.custom_context, custom_value_kind, string) {
+                    Ok(value) => Value::Custom { value },
+                    Err(error) => {
+                        return self.error(JsonDeserializationErrorKind::InvalidCustomValue {
+                            value_kind: value_kind.to_string(),
+                            error,
+                        })
+                    }
+                }
+            }
+            ValueKind::Tuple | ValueKind::Enum | ValueKind::Array | ValueKind::Map => {
+                unreachable!("Container values are not terminal values")
+            }
+        };
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "serde")] // Ensures that VS Code runs this module with the features serde tag!
+mod tests {
+    use super::*;
+    use radix_rust::ContextualSerialize;
+    use serde_json::{json, to_value};
+
+    type BasicDeserializationParameters<'s> =
+        DeserializationParameters<'s, 'static, NoCustomExtension>;
+
+    #[derive(Sbor, Debug, PartialEq, Eq, Hash)]
+    enum TestEnum {
+        UnitVariant,
+        SingleFieldVariant { field: u8 },
+        DoubleStructVariant { field1: u8, field2: u8 },
+    }
+
+    #[derive(Sbor, Debug, PartialEq, Eq)]
+    struct MyFieldStruct {
+        field1: u64,
+        field2: Vec<String>,
+    }
+
+    #[derive(BasicSbor, Debug, PartialEq, Eq)]
+    struct MyComplexTupleStruct(
+        Vec<u16>,
+        Vec<u8>,
+        IndexMap<TestEnum, MyFieldStruct>,
+        IndexMap<String, i32>,
+        TestEnum,
+        TestEnum,
+        MyFieldStruct,
+        Option<i128>,
+        (bool, i8, u32, String),
+    );
+
+    fn complex_value() -> MyComplexTupleStruct {
+        MyComplexTupleStruct(
+            vec![1, 2, 3],
+            vec![0x3a, 0x92],
+            indexmap! {
+                TestEnum::UnitVariant => MyFieldStruct { field1: 1, field2: vec!["hello".to_string()] },
+                TestEnum::DoubleStructVariant { field1: 1, field2: 2 } => MyFieldStruct { field1: 3, field2: vec![] },
+            },
+            indexmap! {
+                "hello".to_string() => -1,
+                "world".to_string() => 2,
+            },
+            TestEnum::UnitVariant,
+            TestEnum::SingleFieldVariant { field: 7 },
+            MyFieldStruct {
+                field1: u64::MAX,
+                field2: vec!["a".to_string(), "b".to_string()],
+            },
+            Some(-5),
+            (true, -3, 153, "x".to_string()),
+        )
+    }
+
+    fn assert_round_trips(payload: &[u8], mode: SerializationMode, with_schema: bool) {
+        let (type_id, schema) =
+            generate_full_schema_from_single_type::<MyComplexTupleStruct, NoCustomSchema>();
+        let raw = BasicRawPayload::new_from_valid_slice_with_checks(payload).unwrap();
+        let (json, parameters) = if with_schema {
+            (
+                to_value(raw.serializable(SerializationParameters::WithSchema {
+                    mode,
+                    custom_context: (),
+                    schema: schema.v1(),
+                    type_id,
+                    depth_limit: 64,
+                }))
+                .unwrap(),
+                BasicDeserializationParameters::WithSchema {
+                    mode,
+                    custom_context: (),
+                    schema: schema.v1(),
+                    type_id,
+                    depth_limit: 64,
+                },
+            )
+        } else {
+            (
+                to_value(raw.serializable(SerializationParameters::Schemaless {
+                    mode,
+                    custom_context: (),
+                    depth_limit: 64,
+                }))
+                .unwrap(),
+                BasicDeserializationParameters::Schemaless {
+                    mode,
+                    custom_context: (),
+                    depth_limit: 64,
+                },
+            )
+        };
+        let deserialized = deserialize_payload_from_json(&json, &parameters).unwrap();
+        assert_eq!(deserialized, payload);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")] // Workaround for VS Code "Run Test" feature
+    fn programmatic_json_round_trips_with_and_without_schema() {
+        let payload = basic_encode(&complex_value()).unwrap();
+        assert_round_trips(&payload, SerializationMode::Programmatic, true);
+        assert_round_trips(&payload, SerializationMode::Programmatic, false);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")] // Workaround for VS Code "Run Test" feature
+    fn natural_json_round_trips_with_schema() {
+        let payload = basic_encode(&complex_value()).unwrap();
+        assert_round_trips(&payload, SerializationMode::Natural, true);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")] // Workaround for VS Code "Run Test" feature
+    fn natural_json_can_be_parsed_by_variant_name() {
+        let (type_id, schema) = generate_full_schema_from_single_type::<TestEnum, NoCustomSchema>();
+        let json = json!({
+            "variant_name": "DoubleStructVariant",
+            "fields": { "field1": 3, "field2": 5 }
+        });
+        let payload = deserialize_payload_from_json(
+            &json,
+            &BasicDeserializationParameters::WithSchema {
+                mode: SerializationMode::Natural,
+                custom_context: (),
+                schema: schema.v1(),
+                type_id,
+                depth_limit: 64,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            basic_decode::<TestEnum>(&payload).unwrap(),
+            TestEnum::DoubleStructVariant {
+                field1: 3,
+                field2: 5
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")] // Workaround for VS Code "Run Test" feature
+    fn errors_include_the_json_path() {
+        let json = json!({
+            "kind": "Tuple",
+            "fields": [
+                { "kind": "U8", "value": "1" },
+                {
+                    "kind": "Array",
+                    "element_kind": "U16",
+                    "elements": [
+                        { "kind": "U16", "value": "1" },
+                        { "kind": "U16", "value": "70000" },
+                    ]
+                },
+            ]
+        });
+        let error = deserialize_value_from_json(
+            &json,
+            &BasicDeserializationParameters::Schemaless {
+                mode: SerializationMode::Programmatic,
+                custom_context: (),
+                depth_limit: 64,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(error.path, "$.fields[1].elements[1].value");
+        assert!(matches!(
+            error.error,
+            JsonDeserializationErrorKind::InvalidInteger { .. }
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")] // Workaround for VS Code "Run Test" feature
+    fn model_mode_is_rejected() {
+        let error = deserialize_value_from_json(
+            &json!([]),
+            &BasicDeserializationParameters::Schemaless {
+                mode: SerializationMode::Model,
+                custom_context: (),
+                depth_limit: 64,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.error,
+            JsonDeserializationErrorKind::UnsupportedMode(SerializationMode::Model)
+        );
+    }
+}
@@ -1,5 +1,7 @@
 //! This module is for representing SBOR via the serde serialization format.
 //! In particular, it's been optimised for serializing to JSON, but can also be serialized into other formats.
+//! Values in the Programmatic and Natural JSON representations can also be parsed back, see
+//! [`deserialize_value_from_json`] and [`deserialize_payload_from_json`].
 //!
 //! To use this module, you need to enable the `serde` feature.
 //!
@@ -24,11 +26,13 @@
 
 // Imports and Exports
 mod contextual_serialize;
+mod json_deserializer;
 mod serde_serializer;
 mod traits;
 mod value_map_aggregator;
 
 pub use contextual_serialize::*;
+pub use json_deserializer::*;
 pub use serde_serializer::*;
 pub use traits::*;
 pub use value_map_aggregator::*;
@@ -1,6 +1,7 @@
 mod custom_traits;
 mod describe;
 mod macros;
+mod rust_codegen;
 mod schema;
 mod schema_validation;
 mod type_aggregator;
@@ -11,6 +12,7 @@ mod well_known_types;
 pub use custom_traits::*;
 pub use describe::*;
 pub(crate) use macros::*;
+pub use rust_codegen::*;
 pub use schema::*;
 pub use schema_validation::*;
 pub use type_aggregator::*;
@@ -0,0 +1,754 @@
+use crate::rust::prelude::*;
+use crate::*;
+
+/// Extends a [`CustomSchema`] with the Rust types of its custom type kinds, so that Rust source
+/// can be generated from its schemas with a [`RustCodeGenerator`].
+pub trait RustCodegenCustomSchema: CustomSchema {
+    /// The derive macro implementing the SBOR traits of the custom extension, e.g. `BasicSbor`.
+    const SBOR_DERIVE: &'static str;
+
+    /// The Rust type which can hold a value of any type.
+    const ANY_TYPE: &'static str;
+
+    /// Returns the Rust type of a custom type kind with the given validation.
+    fn custom_type_name(
+        custom_type_kind: &Self::CustomTypeKind<LocalTypeId>,
+        type_validation: &TypeValidation<Self::CustomTypeValidation>,
+    ) -> String;
+
+    /// Returns the Rust type of a well known type, if the name in its metadata isn't usable as is.
+    fn well_known_type_name(_well_known_id: WellKnownTypeId) -> Option<&'static str> {
+        None
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RustCodegenError {
+    TypeNotFound(LocalTypeId),
+    /// An unnamed type (e.g. a tuple) contains itself, which can't be expressed in Rust.
+    UnnamedRecursiveType(LocalTypeId),
+}
+
+/// Generates Rust type definitions, with SBOR derives, from the types of a schema.
+///
+/// A definition is generated for each named struct or enum reachable from the root types. Other
+/// types are written inline: tuples, arrays as `Vec`, maps as `IndexMap`, `Option` and `Result`
+/// as themselves, and well known types by their name. The names come from the [`TypeMetadata`],
+/// with a numeric suffix added when distinct types share a name (e.g. instances of a generic
+/// type). Fields which would make a recursive type infinitely sized are boxed.
+///
+/// With the same derives in scope, the generated types describe themselves with a schema equal
+/// to the original one.
+pub struct RustCodeGenerator<'s, S: RustCodegenCustomSchema> {
+    schema: &'s SchemaV1<S>,
+    derives: Vec<String>,
+}
+
+pub fn generate_rust_types<S: RustCodegenCustomSchema>(
+    schema: &VersionedSchema<S>,
+    root_type_ids: &[LocalTypeId],
+) -> Result<String, RustCodegenError> {
+    RustCodeGenerator::new(schema).generate(root_type_ids)
+}
+
+impl<'s, S: RustCodegenCustomSchema> RustCodeGenerator<'s, S> {
+    pub fn new(schema: &'s VersionedSchema<S>) -> Self {
+        Self {
+            schema: schema.v1(),
+            derives: vec![
+                S::SBOR_DERIVE.to_string(),
+                "Debug".to_string(),
+                "Clone".to_string(),
+                "PartialEq".to_string(),
+                "Eq".to_string(),
+            ],
+        }
+    }
+
+    /// Replaces the derives added to each type, besides the SBOR derive which is always added.
+    pub fn with_derives<D: Into<String>>(mut self, derives: impl IntoIterator<Item = D>) -> Self {
+        self.derives.truncate(1);
+        self.derives.extend(derives.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn generate(&self, root_type_ids: &[LocalTypeId]) -> Result<String, RustCodegenError> {
+        let mut context = GenerationContext {
+            schema: self.schema,
+            definitions: index_map_new(),
+            reserved_names: [
+                "Option",
+                "Result",
+                "Vec",
+                "IndexMap",
+                "Box",
+                "String",
+                S::ANY_TYPE,
+            ]
+            .into_iter()
+            .map(ToString::to_string)
+            .collect(),
+            by_value_references: index_map_new(),
+        };
+
+        let mut visited = index_set_new();
+        for type_id in root_type_ids {
+            context.discover(*type_id, &mut visited)?;
+        }
+        context.assign_names();
+        context.resolve_by_value_references()?;
+
+        let mut output = String::new();
+        for index in context.definitions.keys() {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            context.write_definition(*index, &self.derives, &mut output)?;
+        }
+        Ok(output)
+    }
+}
+
+struct GenerationContext<'s, S: RustCodegenCustomSchema> {
+    schema: &'s SchemaV1<S>,
+    /// The schema local types which get a definition, with their assigned names.
+    definitions: IndexMap<usize, String>,
+    reserved_names: IndexSet<String>,
+    /// The defined types which each defined type contains without indirection.
+    by_value_references: IndexMap<usize, IndexSet<usize>>,
+}
+
+enum GenericEnum {
+    Option(LocalTypeId),
+    Result(LocalTypeId, LocalTypeId),
+}
+
+impl<'s, S: RustCodegenCustomSchema> GenerationContext<'s, S> {
+    fn type_kind(&self, type_id: LocalTypeId) -> Result<&'s SchemaTypeKind<S>, RustCodegenError> {
+        self.schema
+            .resolve_type_kind(type_id)
+            .ok_or(RustCodegenError::TypeNotFound(type_id))
+    }
+
+    fn type_metadata(&self, type_id: LocalTypeId) -> Result<&'s TypeMetadata, RustCodegenError> {
+        self.schema
+            .resolve_type_metadata(type_id)
+            .ok_or(RustCodegenError::TypeNotFound(type_id))
+    }
+
+    /// `Option` and `Result` are generic, so they are written inline with their type arguments.
+    fn as_generic_enum(
+        &self,
+        type_id: LocalTypeId,
+    ) -> Result<Option<GenericEnum>, RustCodegenError> {
+        let TypeKind::Enum { variants } = self.type_kind(type_id)? else {
+            return Ok(None);
+        };
+        let generic_enum = match (
+            self.type_metadata(type_id)?.get_name(),
+            variants.len(),
+            variants.get(&0).map(|fields| fields.as_slice()),
+            variants.get(&1).map(|fields| fields.as_slice()),
+        ) {
+            (Some("Option"), 2, Some([]), Some([some_type])) => {
+                Some(GenericEnum::Option(*some_type))
+            }
+            (Some("Result"), 2, Some([ok_type]), Some([err_type])) => {
+                Some(GenericEnum::Result(*ok_type, *err_type))
+            }
+            _ => None,
+        };
+        Ok(generic_enum)
+    }
+
+    fn is_defined(&self, type_id: LocalTypeId) -> Result<bool, RustCodegenError> {
+        let LocalTypeId::SchemaLocalIndex(_) = type_id else {
+            return Ok(false);
+        };
+        Ok(match self.type_kind(type_id)? {
+            TypeKind::Tuple { .. } => self.type_metadata(type_id)?.get_name().is_some(),
+            TypeKind::Enum { .. } => self.as_generic_enum(type_id)?.is_none(),
+            _ => false,
+        })
+    }
+
+    fn discover(
+        &mut self,
+        type_id: LocalTypeId,
+        visited: &mut IndexSet<LocalTypeId>,
+    ) -> Result<(), RustCodegenError> {
+        if !visited.insert(type_id) {
+            return Ok(());
+        }
+        if let LocalTypeId::WellKnown(well_known_id) = type_id {
+            let name = self.well_known_name(well_known_id)?;
+            self.reserved_names.insert(name);
+            return Ok(());
+        }
+        if self.is_defined(type_id)? {
+            let LocalTypeId::SchemaLocalIndex(index) = type_id else {
+                unreachable!("Only schema local types are defined")
+            };
+            self.definitions.insert(index, String::new());
+        }
+        match self.type_kind(type_id)? {
+            TypeKind::Array { element_type } => self.discover(*element_type, visited)?,
+            TypeKind::Tuple { field_types } => {
+                for field_type in field_types {
+                    self.discover(*field_type, visited)?;
+                }
+            }
+            TypeKind::Enum { variants } => {
+                for field_type in variants.values().flatten() {
+                    self.discover(*field_type, visited)?;
+                }
+            }
+            TypeKind::Map {
+                key_type,
+                value_type,
+            } => {
+                self.discover(*key_type, visited)?;
+                self.discover(*value_type, visited)?;
+            }
+            TypeKind::Custom(custom_type_kind) => {
+                let validation = self.type_validation(type_id)?;
+                let name = S::custom_type_name(custom_type_kind, validation);
+                self.reserved_names.insert(name);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn type_validation(
+        &self,
+        type_id: LocalTypeId,
+    ) -> Result<&'s TypeValidation<S::CustomTypeValidation>, RustCodegenError> {
+        self.schema
+            .resolve_type_validation(type_id)
+            .ok_or(RustCodegenError::TypeNotFound(type_id))
+    }
+
+    fn well_known_name(&self, well_known_id: WellKnownTypeId) -> Result<String, RustCodegenError> {
+        if let Some(name) = S::well_known_type_name(well_known_id) {
+            return Ok(name.to_string());
+        }
+        let type_id = LocalTypeId::WellKnown(well_known_id);
+        match self.type_metadata(type_id)?.get_name() {
+            Some(name) => Ok(name.to_string()),
+            None => self.inline_type(type_id, None, &mut vec![]),
+        }
+    }
+
+    fn assign_names(&mut self) {
+        let mut used_names = self.reserved_names.clone();
+        for (index, name) in self.definitions.iter_mut() {
+            let type_id = LocalTypeId::SchemaLocalIndex(*index);
+            let base_name = self
+                .schema
+                .resolve_type_name_from_metadata(type_id)
+                .map(|name| to_identifier(name, "Type"))
+                .filter(|name| !name.is_empty())
+                .unwrap_or_else(|| format!("Type{}", index));
+            let mut candidate = base_name.clone();
+            let mut suffix = 2usize;
+            while used_names.contains(&candidate) {
+                candidate = format!("{}{}", base_name, suffix);
+                suffix += 1;
+            }
+            used_names.insert(candidate.clone());
+            *name = candidate;
+        }
+    }
+
+    fn resolve_by_value_references(&mut self) -> Result<(), RustCodegenError> {
+        let indices: Vec<usize> = self.definitions.keys().cloned().collect();
+        for index in indices {
+            let mut references = index_set_new();
+            for field_type in self.field_types(index)? {
+                self.collect_by_value_references(*field_type, &mut references, &mut vec![])?;
+            }
+            self.by_value_references.insert(index, references);
+        }
+        Ok(())
+    }
+
+    fn field_types(&self, index: usize) -> Result<Vec<&'s LocalTypeId>, RustCodegenError> {
+        Ok(
+            match self.type_kind(LocalTypeId::SchemaLocalIndex(index))? {
+                TypeKind::Tuple { field_types } => field_types.iter().collect(),
+                TypeKind::Enum { variants } => variants.values().flatten().collect(),
+                _ => vec![],
+            },
+        )
+    }
+
+    fn collect_by_value_references(
+        &self,
+        type_id: LocalTypeId,
+        references: &mut IndexSet<usize>,
+        stack: &mut Vec<LocalTypeId>,
+    ) -> Result<(), RustCodegenError> {
+        let LocalTypeId::SchemaLocalIndex(index) = type_id else {
+            return Ok(());
+        };
+        if self.definitions.contains_key(&index) {
+            references.insert(index);
+            return Ok(());
+        }
+        if stack.contains(&type_id) {
+            return Err(RustCodegenError::UnnamedRecursiveType(type_id));
+        }
+        stack.push(type_id);
+        match (self.type_kind(type_id)?, self.as_generic_enum(type_id)?) {
+            (TypeKind::Tuple { field_types }, _) => {
+                for field_type in field_types {
+                    self.collect_by_value_references(*field_type, references, stack)?;
+                }
+            }
+            (_, Some(GenericEnum::Option(some_type))) => {
+                self.collect_by_value_references(some_type, references, stack)?;
+            }
+            (_, Some(GenericEnum::Result(ok_type, err_type))) => {
+                self.collect_by_value_references(ok_type, references, stack)?;
+                self.collect_by_value_references(err_type, references, stack)?;
+            }
+            // Arrays and maps store their elements on the heap
+            _ => {}
+        }
+        stack.pop();
+        Ok(())
+    }
+
+    /// Whether the `from` type contains the `to` type without indirection, directly or through
+    /// other types.
+    fn contains_by_value(&self, from: usize, to: usize) -> bool {
+        let mut visited = index_set_new();
+        let mut pending = vec![from];
+        while let Some(index) = pending.pop() {
+            let Some(references) = self.by_value_references.get(&index) else {
+                continue;
+            };
+            for reference in references {
+                if *reference == to {
+                    return true;
+                }
+                if visited.insert(*reference) {
+                    pending.push(*reference);
+                }
+            }
+        }
+        false
+    }
+
+    /// Returns the Rust type of a type used within the definition of `container`, or within a
+    /// heap allocation if `container` is `None`.
+    fn inline_type(
+        &self,
+        type_id: LocalTypeId,
+        container: Option<usize>,
+        stack: &mut Vec<LocalTypeId>,
+    ) -> Result<String, RustCodegenError> {
+        match type_id {
+            LocalTypeId::WellKnown(well_known_id) => {
+                if let Some(name) = S::well_known_type_name(well_known_id) {
+                    return Ok(name.to_string());
+                }
+                if let Some(name) = self.type_metadata(type_id)?.get_name() {
+                    return Ok(name.to_string());
+                }
+            }
+            LocalTypeId::SchemaLocalIndex(index) => {
+                if let Some(name) = self.definitions.get(&index) {
+                    return Ok(match container {
+                        Some(container) if self.contains_by_value(index, container) => {
+                            format!("Box<{}>", name)
+                        }
+                        _ => name.clone(),
+                    });
+                }
+            }
+        }
+
+        if stack.contains(&type_id) {
+            return Err(RustCodegenError::UnnamedRecursiveType(type_id));
+        }
+        stack.push(type_id);
+        let name = match self.type_kind(type_id)? {
+            TypeKind::Any => S::ANY_TYPE.to_string(),
+            TypeKind::Bool => "bool".to_string(),
+            TypeKind::I8 => "i8".to_string(),
+            TypeKind::I16 => "i16".to_string(),
+            TypeKind::I32 => "i32".to_string(),
+            TypeKind::I64 => "i64".to_string(),
+            TypeKind::I128 => "i128".to_string(),
+            TypeKind::U8 => "u8".to_string(),
+            TypeKind::U16 => "u16".to_string(),
+            TypeKind::U32 => "u32".to_string(),
+            TypeKind::U64 => "u64".to_string(),
+            TypeKind::U128 => "u128".to_string(),
+            TypeKind::String => "String".to_string(),
+            TypeKind::Array { element_type } => {
+                format!("Vec<{}>", self.inline_type(*element_type, None, stack)?)
+            }
+            TypeKind::Tuple { field_types } => match field_types.as_slice() {
+                [] => "()".to_string(),
+                [field_type] => format!("({},)", self.inline_type(*field_type, container, stack)?),
+                field_types => format!(
+                    "({})",
+                    field_types
+                        .iter()
+                        .map(|field_type| self.inline_type(*field_type, container, stack))
+                        .collect::<Result<Vec<_>, _>>()?
+                        .join(", ")
+                ),
+            },
+            TypeKind::Enum { .. } => match self.as_generic_enum(type_id)? {
+                Some(GenericEnum::Option(some_type)) => {
+                    format!("Option<{}>", self.inline_type(some_type, container, stack)?)
+                }
+                Some(GenericEnum::Result(ok_type, err_type)) => format!(
+                    "Result<{}, {}>",
+                    self.inline_type(ok_type, container, stack)?,
+                    self.inline_type(err_type, container, stack)?
+                ),
+                // Only reachable for an unnamed well known enum, which can still be held by value
+                // of any type.
+                None => S::ANY_TYPE.to_string(),
+            },
+            TypeKind::Map {
+                key_type,
+                value_type,
+            } => format!(
+                "IndexMap<{}, {}>",
+                self.inline_type(*key_type, None, stack)?,
+                self.inline_type(*value_type, None, stack)?
+            ),
+            TypeKind::Custom(custom_type_kind) => {
+                S::custom_type_name(custom_type_kind, self.type_validation(type_id)?)
+            }
+        };
+        stack.pop();
+        Ok(name)
+    }
+
+    fn write_definition(
+        &self,
+        index: usize,
+        derives: &[String],
+        output: &mut String,
+    ) -> Result<(), RustCodegenError> {
+        let type_id = LocalTypeId::SchemaLocalIndex(index);
+        let name = &self.definitions[&index];
+        let metadata = self.type_metadata(type_id)?;
+        output.push_str(&format!("#[derive({})]\n", derives.join(", ")));
+        match self.type_kind(type_id)? {
+            TypeKind::Tuple { field_types } => {
+                let fields = self.fields(index, field_types, metadata, "pub ")?;
+                let separator = if fields.starts_with(" {") { "" } else { ";" };
+                output.push_str(&format!("pub struct {}{}{}\n", name, fields, separator));
+            }
+            TypeKind::Enum { variants } => {
+                let explicit_discriminators = variants
+                    .keys()
+                    .enumerate()
+                    .any(|(position, discriminator)| position != *discriminator as usize);
+                output.push_str(&format!("pub enum {} {{\n", name));
+                for (discriminator, field_types) in variants {
+                    let variant_metadata = match &metadata.child_names {
+                        Some(ChildNames::EnumVariants(variants)) => variants.get(discriminator),
+                        _ => None,
+                    };
+                    let variant_name = variant_metadata
+                        .and_then(|metadata| metadata.get_name())
+                        .map(|name| to_identifier(name, "Variant"))
+                        .filter(|name| !name.is_empty())
+                        .unwrap_or_else(|| format!("Variant{}", discriminator));
+                    let fields = match variant_metadata {
+                        Some(variant_metadata) => {
+                            self.fields(index, field_types, variant_metadata, "")?
+                        }
+                        None => self.fields(index, field_types, &TypeMetadata::unnamed(), "")?,
+                    };
+                    if explicit_discriminators {
+                        output
+                            .push_str(&format!("    #[sbor(discriminator({}))]\n", discriminator));
+                    }
+                    output.push_str(&format!("    {}{},\n", variant_name, fields));
+                }
+                output.push_str("}\n");
+            }
+            _ => unreachable!("Only tuples and enums are defined"),
+        }
+        Ok(())
+    }
+
+    /// Returns the fields of a struct or enum variant, as written after its name.
+    fn fields(
+        &self,
+        container: usize,
+        field_types: &[LocalTypeId],
+        metadata: &TypeMetadata,
+        visibility: &str,
+    ) -> Result<String, RustCodegenError> {
+        let field_types = field_types
+            .iter()
+            .map(|field_type| self.inline_type(*field_type, Some(container), &mut vec![]))
+            .collect::<Result<Vec<_>, _>>()?;
+        let fields = match metadata
+            .get_field_names()
+            .filter(|field_names| field_names.len() == field_types.len())
+        {
+            Some([]) => " {}".to_string(),
+            Some(field_names) => {
+                let indent = if visibility.is_empty() {
+                    "        "
+                } else {
+                    "    "
+                };
+                let mut fields = " {\n".to_string();
+                for (field_name, field_type) in field_names.iter().zip(field_types) {
+                    fields.push_str(&format!(
+                        "{}{}{}: {},\n",
+                        indent,
+                        visibility,
+                        to_identifier(field_name, "field"),
+                        field_type
+                    ));
+                }
+                fields.push_str(&indent[4..]);
+                fields.push('}');
+                fields
+            }
+            None if field_types.is_empty() => String::new(),
+            None => format!(
+                "({})",
+                field_types
+                    .iter()
+                    .map(|field_type| format!("{}{}", visibility, field_type))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        Ok(fields)
+    }
+}
+
+const RUST_KEYWORDS: [&str; 50] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while",
+];
+
+/// Turns a name from the metadata into a Rust identifier, prefixing names which start with a
+/// digit with the given prefix.
+fn to_identifier(name: &str, digit_prefix: &str) -> String {
+    let identifier: String = name
+        .strip_prefix("r#")
+        .unwrap_or(name)
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+        .collect();
+    if identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("{}{}", digit_prefix, identifier)
+    } else if ["self", "Self", "crate", "super"].contains(&identifier.as_str()) {
+        format!("{}_", identifier)
+    } else if RUST_KEYWORDS.contains(&identifier.as_str()) {
+        format!("r#{}", identifier)
+    } else {
+        identifier
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod original {
+        use super::*;
+
+        #[derive(BasicSbor)]
+        pub struct Root {
+            pub tree: Tree,
+            pub pairs: BTreeMap<String, (u8, Option<Leaf>)>,
+            pub set: BTreeSet<u32>,
+            pub bytes: Vec<u8>,
+            pub result: Result<Unit, Empty>,
+            pub r#type: Kind,
+        }
+
+        #[derive(BasicSbor)]
+        pub enum Tree {
+            Leaf(Leaf),
+            Node {
+                left: Box<Tree>,
+                right: Option<Box<Tree>>,
+            },
+            Forest(Vec<Tree>),
+        }
+
+        #[derive(BasicSbor)]
+        pub struct Leaf(pub u64, pub i8);
+
+        #[derive(BasicSbor)]
+        pub struct Unit;
+
+        #[derive(BasicSbor)]
+        pub struct Empty {}
+
+        #[derive(BasicSbor)]
+        pub enum Kind {
+            #[sbor(discriminator(1))]
+            A,
+            #[sbor(discriminator(5))]
+            B(bool),
+        }
+
+        #[derive(BasicSbor)]
+        pub struct Wrapper<T> {
+            pub inner: T,
+        }
+
+        #[derive(BasicSbor)]
+        pub struct Generic {
+            pub first: Wrapper<u8>,
+            pub second: Wrapper<String>,
+            pub third: Wrapper<u8>,
+        }
+    }
+
+    macro_rules! generated_types {
+        ($($code:tt)*) => {
+            mod generated {
+                use super::*;
+
+                $($code)*
+            }
+
+            const GENERATED_CODE: &str = stringify!($($code)*);
+        };
+    }
+
+    generated_types! {
+        #[derive(BasicSbor, Debug, Clone, PartialEq, Eq)]
+        pub struct Root {
+            pub tree: Tree,
+            pub pairs: IndexMap<String, (u8, Option<Leaf>)>,
+            pub set: Vec<u32>,
+            pub bytes: Vec<u8>,
+            pub result: Result<Unit, Empty>,
+            pub r#type: Kind,
+        }
+
+        #[derive(BasicSbor, Debug, Clone, PartialEq, Eq)]
+        pub enum Tree {
+            Leaf(Leaf),
+            Node {
+                left: Box<Tree>,
+                right: Option<Box<Tree>>,
+            },
+            Forest(Vec<Tree>),
+        }
+
+        #[derive(BasicSbor, Debug, Clone, PartialEq, Eq)]
+        pub struct Leaf(pub u64, pub i8);
+
+        #[derive(BasicSbor, Debug, Clone, PartialEq, Eq)]
+        pub struct Unit;
+
+        #[derive(BasicSbor, Debug, Clone, PartialEq, Eq)]
+        pub struct Empty {}
+
+        #[derive(BasicSbor, Debug, Clone, PartialEq, Eq)]
+        pub enum Kind {
+            #[sbor(discriminator(1))]
+            A,
+            #[sbor(discriminator(5))]
+            B(bool),
+        }
+    }
+
+    fn without_whitespace(code: &str) -> String {
+        code.chars().filter(|c| !c.is_whitespace()).collect()
+    }
+
+    #[test]
+    fn test_generated_types_round_trip() {
+        let (type_id, schema) =
+            generate_full_schema_from_single_type::<original::Root, NoCustomSchema>();
+        let code = generate_rust_types(&schema, &[type_id]).unwrap();
+        assert_eq!(
+            without_whitespace(&code),
+            without_whitespace(GENERATED_CODE)
+        );
+
+        let (generated_type_id, generated_schema) =
+            generate_full_schema_from_single_type::<generated::Root, NoCustomSchema>();
+        assert_eq!(generated_type_id, type_id);
+        assert_eq!(generated_schema, schema);
+    }
+
+    #[test]
+    fn test_name_collisions_and_derives() {
+        let (type_id, schema) =
+            generate_full_schema_from_single_type::<original::Generic, NoCustomSchema>();
+        let code = RustCodeGenerator::new(&schema)
+            .with_derives(["Debug"])
+            .generate(&[type_id])
+            .unwrap();
+        assert_eq!(
+            code,
+            r#"#[derive(BasicSbor, Debug)]
+pub struct Generic {
+    pub first: Wrapper,
+    pub second: Wrapper2,
+    pub third: Wrapper,
+}
+
+#[derive(BasicSbor, Debug)]
+pub struct Wrapper {
+    pub inner: u8,
+}
+
+#[derive(BasicSbor, Debug)]
+pub struct Wrapper2 {
+    pub inner: String,
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_invalid_schemas() {
+        let (_, schema) = generate_full_schema_from_single_type::<original::Leaf, NoCustomSchema>();
+        let missing_type_id = LocalTypeId::SchemaLocalIndex(1);
+        assert_eq!(
+            generate_rust_types(&schema, &[missing_type_id]),
+            Err(RustCodegenError::TypeNotFound(missing_type_id))
+        );
+
+        let recursive_type_id = LocalTypeId::SchemaLocalIndex(1);
+        let schema: VersionedSchema<NoCustomSchema> = Schema {
+            type_kinds: vec![
+                TypeKind::Tuple {
+                    field_types: vec![recursive_type_id],
+                },
+                TypeKind::Array {
+                    element_type: recursive_type_id,
+                },
+            ],
+            type_metadata: vec![
+                TypeMetadata::no_child_names("Holder"),
+                TypeMetadata::unnamed(),
+            ],
+            type_validations: vec![TypeValidation::None, TypeValidation::None],
+        }
+        .into_versioned();
+        assert_eq!(
+            generate_rust_types(&schema, &[LocalTypeId::SchemaLocalIndex(0)]),
+            Err(RustCodegenError::UnnamedRecursiveType(recursive_type_id))
+        );
+    }
+}
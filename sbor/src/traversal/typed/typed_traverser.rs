@@ -512,7 +512,7 @@ impl<'s, E: CustomExtension> TypedTraverserState<'s, E> {
     }
 }
 
-fn value_kind_matches_type_kind<E: CustomExtension>(
+pub(crate) fn value_kind_matches_type_kind<E: CustomExtension>(
     schema: &Schema<E::CustomSchema>,
     value_kind: ValueKind<E::CustomValueKind>,
     type_kind: &SchemaTypeKind<E::CustomSchema>,
@@ -0,0 +1,775 @@
+use super::*;
+use crate::rust::prelude::*;
+use crate::value_kind::*;
+use crate::*;
+
+/// The default maximum length of the byte batches output by the [`StreamingTraverser`].
+pub const DEFAULT_STREAMING_MAX_BATCH_SIZE: usize = 64 * 1024;
+
+/// The number of bytes the [`StreamingTraverser`] requests from its input at a time.
+const READ_CHUNK_SIZE: usize = 8 * 1024;
+
+/// A source of bytes for the [`StreamingTraverser`].
+pub trait StreamingInput {
+    type Error;
+
+    /// Reads some bytes into the start of the (non-empty) buffer, and returns how many were read.
+    /// Returning `0` signals the end of the input.
+    fn read_into(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error>;
+}
+
+impl StreamingInput for &[u8] {
+    type Error = core::convert::Infallible;
+
+    fn read_into(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+        let length = buffer.len().min(self.len());
+        buffer[..length].copy_from_slice(&self[..length]);
+        *self = &self[length..];
+        Ok(length)
+    }
+}
+
+/// A [`StreamingInput`] over an iterator of chunks of the payload, which works without `std`.
+///
+/// This is useful when the payload arrives in parts, eg from pages in a database or packets
+/// from the network.
+pub struct ChunkedInput<I: Iterator>
+where
+    I::Item: AsRef<[u8]>,
+{
+    chunks: I,
+    current_chunk: Option<I::Item>,
+    current_chunk_offset: usize,
+}
+
+impl<I: Iterator> ChunkedInput<I>
+where
+    I::Item: AsRef<[u8]>,
+{
+    pub fn new(chunks: impl IntoIterator<IntoIter = I>) -> Self {
+        Self {
+            chunks: chunks.into_iter(),
+            current_chunk: None,
+            current_chunk_offset: 0,
+        }
+    }
+}
+
+impl<I: Iterator> StreamingInput for ChunkedInput<I>
+where
+    I::Item: AsRef<[u8]>,
+{
+    type Error = core::convert::Infallible;
+
+    fn read_into(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+        loop {
+            if let Some(chunk) = &self.current_chunk {
+                let remaining = &chunk.as_ref()[self.current_chunk_offset..];
+                if !remaining.is_empty() {
+                    let length = buffer.len().min(remaining.len());
+                    buffer[..length].copy_from_slice(&remaining[..length]);
+                    self.current_chunk_offset += length;
+                    return Ok(length);
+                }
+            }
+            match self.chunks.next() {
+                Some(chunk) => {
+                    self.current_chunk = Some(chunk);
+                    self.current_chunk_offset = 0;
+                }
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
+/// A [`StreamingInput`] over a [`std::io::Read`], eg a file or a socket.
+#[cfg(feature = "std")]
+pub struct IoReadInput<R: std::io::Read>(pub R);
+
+#[cfg(feature = "std")]
+impl<R: std::io::Read> StreamingInput for IoReadInput<R> {
+    type Error = std::io::Error;
+
+    fn read_into(&mut self, buffer: &mut [u8]) -> Result<usize, Self::Error> {
+        loop {
+            match self.0.read(buffer) {
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                result => return result,
+            }
+        }
+    }
+}
+
+/// The `StreamingTraverser` is the incremental counterpart of the [`VecTraverser`]: it pulls the
+/// payload from a [`StreamingInput`] as it goes, instead of requiring all of it as a slice.
+///
+/// It outputs the same events as the `VecTraverser`, except that the bytes of a `u8` array are
+/// output in (consecutive) batches of at most `max_batch_size` bytes, so the memory used is
+/// bounded by the largest string or custom value in the payload (and the batch size), rather than
+/// the size of the payload. The events borrow from an internal buffer, so they're only valid
+/// until the next call to the traverser.
+///
+/// If the input fails, the traverser outputs a `BufferUnderflow` error, and the input's error can
+/// be retrieved with [`Self::input_error`].
+///
+/// The caller is responsible for stopping calling `next_event` after an Error or End event.
+pub struct StreamingTraverser<I: StreamingInput, T: CustomTraversal> {
+    input: I,
+    input_error: Option<I::Error>,
+    input_ended: bool,
+    buffer: Vec<u8>,
+    /// The position in the buffer of the first byte which hasn't been traversed yet
+    buffer_offset: usize,
+    /// The offset in the payload of the start of the buffer
+    discarded_length: usize,
+    max_depth: usize,
+    max_batch_size: usize,
+    check_exact_end: bool,
+    container_stack: Vec<ContainerState<T>>,
+    next_event_override: NextEventOverride<T::CustomValueKind>,
+}
+
+impl<I: StreamingInput, T: CustomTraversal> StreamingTraverser<I, T> {
+    pub fn new(
+        input: I,
+        max_depth: usize,
+        expected_start: ExpectedStart<T::CustomValueKind>,
+        check_exact_end: bool,
+    ) -> Self {
+        Self {
+            input,
+            input_error: None,
+            input_ended: false,
+            buffer: Vec::new(),
+            buffer_offset: 0,
+            discarded_length: 0,
+            max_depth,
+            max_batch_size: DEFAULT_STREAMING_MAX_BATCH_SIZE,
+            check_exact_end,
+            container_stack: Vec::with_capacity(max_depth),
+            next_event_override: match expected_start {
+                ExpectedStart::PayloadPrefix(prefix) => NextEventOverride::ReadPrefix(prefix),
+                ExpectedStart::Value => NextEventOverride::ReadRootValue,
+                ExpectedStart::ValueBody(value_kind) => {
+                    NextEventOverride::ReadRootValueWithValueKind(value_kind)
+                }
+            },
+        }
+    }
+
+    /// Sets the maximum length of the byte batches output for `u8` arrays.
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        assert!(max_batch_size > 0);
+        self.max_batch_size = max_batch_size;
+        self
+    }
+
+    /// The error returned by the input, if it failed.
+    pub fn input_error(&self) -> Option<&I::Error> {
+        self.input_error.as_ref()
+    }
+
+    pub fn next_event(&mut self) -> LocatedTraversalEvent<'_, '_, T> {
+        match self.next_event_override {
+            NextEventOverride::ReadPrefix(expected_prefix) => {
+                self.next_event_override = NextEventOverride::ReadRootValue;
+                let start_offset = self.get_offset();
+                if let Err(error) = self.decode_from_buffer(|decoder| {
+                    decoder.read_and_check_payload_prefix(expected_prefix)
+                }) {
+                    return self.map_error(start_offset, error);
+                }
+                self.next_event()
+            }
+            NextEventOverride::ReadRootValue => {
+                self.next_event_override = NextEventOverride::None;
+                self.read_root_value(None)
+            }
+            NextEventOverride::ReadRootValueWithValueKind(value_kind) => {
+                self.next_event_override = NextEventOverride::None;
+                self.read_root_value(Some(value_kind))
+            }
+            // Byte arrays are read in batches while the array is incomplete
+            NextEventOverride::ReadBytes(_) | NextEventOverride::None => {
+                let parent = self.container_stack.last();
+                match parent {
+                    Some(parent) => {
+                        if parent.is_complete() {
+                            self.exit_container()
+                        } else if let ContainerHeader::Array(ArrayHeader {
+                            element_value_kind: ValueKind::U8,
+                            ..
+                        }) = parent.container_header
+                        {
+                            self.read_bytes_batch()
+                        } else {
+                            self.read_child_value()
+                        }
+                    }
+                    None => self.read_end(),
+                }
+            }
+        }
+    }
+
+    /// Skips the rest of the innermost container being traversed (eg the one which has just been
+    /// started), and returns its `ContainerEnd` event, or the first error encountered.
+    ///
+    /// The skipped values are still validated, but aren't output, and only a bounded part of them
+    /// is buffered at any time.
+    pub fn skip_container(&mut self) -> LocatedTraversalEvent<'_, '_, T> {
+        let depth = self.container_stack.len();
+        if depth == 0 {
+            return self.next_event();
+        }
+        // The result is rebuilt after the loop, as events borrow the traverser
+        let (event, start_offset, end_offset) = loop {
+            let next = self.next_event();
+            let location = (next.location.start_offset, next.location.end_offset);
+            match next.event {
+                TraversalEvent::ContainerEnd(header)
+                    if next.location.ancestor_path.len() < depth =>
+                {
+                    break (TraversalEvent::ContainerEnd(header), location.0, location.1);
+                }
+                TraversalEvent::DecodeError(error) => {
+                    break (TraversalEvent::DecodeError(error), location.0, location.1);
+                }
+                TraversalEvent::End => {
+                    break (TraversalEvent::End, location.0, location.1);
+                }
+                _ => {}
+            }
+        };
+        LocatedTraversalEvent {
+            event,
+            location: Location {
+                start_offset,
+                end_offset,
+                ancestor_path: &self.container_stack,
+            },
+        }
+    }
+
+    fn enter_container(
+        &mut self,
+        start_offset: usize,
+        container_header: ContainerHeader<T>,
+    ) -> LocatedTraversalEvent<'_, '_, T> {
+        let child_count = container_header.get_child_count();
+
+        self.container_stack.push(ContainerState {
+            container_header,
+            container_start_offset: start_offset,
+            container_child_count: child_count,
+            current_child_index: None,
+        });
+
+        // Check depth: either container stack overflows or children of this container will overflow.
+        if self.container_stack.len() > self.max_depth
+            || self.container_stack.len() == self.max_depth && child_count > 0
+        {
+            return self.map_error(start_offset, DecodeError::MaxDepthExceeded(self.max_depth));
+        }
+
+        LocatedTraversalEvent {
+            event: TraversalEvent::ContainerStart(container_header),
+            location: Location {
+                start_offset,
+                end_offset: self.get_offset(),
+                ancestor_path: &self.container_stack[0..self.container_stack.len() - 1],
+            },
+        }
+    }
+
+    fn exit_container(&mut self) -> LocatedTraversalEvent<'_, '_, T> {
+        let container = self.container_stack.pop().unwrap();
+        LocatedTraversalEvent {
+            event: TraversalEvent::ContainerEnd(container.container_header),
+            location: Location {
+                start_offset: container.container_start_offset,
+                end_offset: self.get_offset(),
+                ancestor_path: &self.container_stack,
+            },
+        }
+    }
+
+    fn read_root_value(
+        &mut self,
+        value_kind: Option<ValueKind<T::CustomValueKind>>,
+    ) -> LocatedTraversalEvent<'_, '_, T> {
+        let start_offset = self.get_offset();
+        let value_kind = match value_kind {
+            Some(value_kind) => value_kind,
+            None => match self.decode_from_buffer(|decoder| decoder.read_value_kind()) {
+                Ok(value_kind) => value_kind,
+                Err(error) => return self.map_error(start_offset, error),
+            },
+        };
+        self.next_value(start_offset, value_kind)
+    }
+
+    fn read_child_value(&mut self) -> LocatedTraversalEvent<'_, '_, T> {
+        let start_offset = self.get_offset();
+        let parent = self.container_stack.last_mut().unwrap();
+        parent.advance_current_child_index();
+        let value_kind = parent
+            .container_header
+            .get_implicit_child_value_kind(parent.current_child_index.unwrap());
+        let value_kind = match value_kind {
+            Some(value_kind) => value_kind,
+            None => match self.decode_from_buffer(|decoder| decoder.read_value_kind()) {
+                Ok(value_kind) => value_kind,
+                Err(error) => return self.map_error(start_offset, error),
+            },
+        };
+        self.next_value(start_offset, value_kind)
+    }
+
+    fn next_value(
+        &mut self,
+        start_offset: usize,
+        value_kind: ValueKind<T::CustomValueKind>,
+    ) -> LocatedTraversalEvent<'_, '_, T> {
+        let header = match value_kind {
+            ValueKind::Array => self.decode_from_buffer(|decoder| {
+                let element_value_kind = decoder.read_value_kind()?;
+                let length = decoder.read_size()?;
+                Ok(ContainerHeader::Array(ArrayHeader {
+                    element_value_kind,
+                    length,
+                }))
+            }),
+            ValueKind::Map => self.decode_from_buffer(|decoder| {
+                let key_value_kind = decoder.read_value_kind()?;
+                let value_value_kind = decoder.read_value_kind()?;
+                let length = decoder.read_size()?;
+                Ok(ContainerHeader::Map(MapHeader {
+                    key_value_kind,
+                    value_value_kind,
+                    length,
+                }))
+            }),
+            ValueKind::Enum => self.decode_from_buffer(|decoder| {
+                let variant = decoder.read_byte()?;
+                let length = decoder.read_size()?;
+                Ok(ContainerHeader::EnumVariant(EnumVariantHeader {
+                    variant,
+                    length,
+                }))
+            }),
+            ValueKind::Tuple => self.decode_from_buffer(|decoder| {
+                let length = decoder.read_size()?;
+                Ok(ContainerHeader::Tuple(TupleHeader { length }))
+            }),
+            _ => return self.read_terminal_value(start_offset, value_kind),
+        };
+        match header {
+            Ok(header) => self.enter_container(start_offset, header),
+            Err(error) => self.map_error(start_offset, error),
+        }
+    }
+
+    fn read_terminal_value(
+        &mut self,
+        start_offset: usize,
+        value_kind: ValueKind<T::CustomValueKind>,
+    ) -> LocatedTraversalEvent<'_, '_, T> {
+        // The value is first decoded to find its length (buffering as much as is needed), and then
+        // decoded again from the buffer, so that the event can borrow from it.
+        let length = match self.decode_from_buffer(|decoder| {
+            decode_terminal_value::<T>(decoder, value_kind)?;
+            Ok(decoder.get_offset())
+        }) {
+            Ok(length) => length,
+            Err(error) => return self.map_error(start_offset, error),
+        };
+        let end_offset = self.get_offset();
+        let value_bytes = &self.buffer[self.buffer_offset - length..self.buffer_offset];
+        let value = decode_terminal_value::<T>(
+            &mut VecDecoder::new(value_bytes, self.max_depth),
+            value_kind,
+        )
+        .expect("The value was successfully decoded from the same bytes");
+        LocatedTraversalEvent {
+            event: TraversalEvent::TerminalValue(value),
+            location: Location {
+                start_offset,
+                end_offset,
+                ancestor_path: &self.container_stack,
+            },
+        }
+    }
+
+    fn read_bytes_batch(&mut self) -> LocatedTraversalEvent<'_, '_, T> {
+        let start_offset = self.get_offset();
+        let parent = self.container_stack.last().unwrap();
+        let remaining_count = parent.container_child_count
+            - parent
+                .current_child_index
+                .map(|index| index + 1)
+                .unwrap_or(0);
+        let batch_size = remaining_count.min(self.max_batch_size);
+        if !self.fill(batch_size) {
+            let error = DecodeError::BufferUnderflow {
+                required: batch_size,
+                remaining: self.buffer.len() - self.buffer_offset,
+            };
+            return self.map_error(start_offset, error);
+        }
+        self.buffer_offset += batch_size;
+        self.container_stack
+            .last_mut()
+            .unwrap()
+            .advance_current_child_index_by(batch_size);
+        LocatedTraversalEvent {
+            event: TraversalEvent::TerminalValueBatch(TerminalValueBatchRef::U8(
+                &self.buffer[self.buffer_offset - batch_size..self.buffer_offset],
+            )),
+            location: Location {
+                start_offset,
+                end_offset: self.get_offset(),
+                ancestor_path: &self.container_stack,
+            },
+        }
+    }
+
+    fn read_end(&mut self) -> LocatedTraversalEvent<'_, '_, T> {
+        let offset = self.get_offset();
+        if self.check_exact_end && self.fill(1) {
+            let error = DecodeError::ExtraTrailingBytes(self.buffer.len() - self.buffer_offset);
+            return self.map_error(offset, error);
+        }
+        LocatedTraversalEvent {
+            event: TraversalEvent::End,
+            location: Location {
+                start_offset: offset,
+                end_offset: offset,
+                ancestor_path: &self.container_stack,
+            },
+        }
+    }
+
+    fn map_error(
+        &self,
+        start_offset: usize,
+        error: DecodeError,
+    ) -> LocatedTraversalEvent<'_, '_, T> {
+        LocatedTraversalEvent {
+            event: TraversalEvent::DecodeError(error),
+            location: Location {
+                start_offset,
+                end_offset: self.get_offset(),
+                ancestor_path: &self.container_stack,
+            },
+        }
+    }
+
+    /// The offset in the payload of the first byte which hasn't been traversed yet.
+    #[inline]
+    fn get_offset(&self) -> usize {
+        self.discarded_length + self.buffer_offset
+    }
+
+    /// Runs the decode function against the buffered bytes, reading more of the input whenever
+    /// it runs out of bytes, and then marks the bytes it read as traversed.
+    fn decode_from_buffer<V>(
+        &mut self,
+        decode: impl Fn(&mut VecDecoder<'_, T::CustomValueKind>) -> Result<V, DecodeError>,
+    ) -> Result<V, DecodeError> {
+        loop {
+            let mut decoder = VecDecoder::new(&self.buffer[self.buffer_offset..], self.max_depth);
+            let result = decode(&mut decoder);
+            let decoded_length = decoder.get_offset();
+            match result {
+                Ok(value) => {
+                    self.buffer_offset += decoded_length;
+                    return Ok(value);
+                }
+                Err(DecodeError::BufferUnderflow {
+                    required,
+                    remaining,
+                }) if !self.input_ended => {
+                    let buffered = self.buffer.len() - self.buffer_offset;
+                    self.fill(buffered - remaining + required);
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    /// Reads the input until at least `required` untraversed bytes are buffered, discarding the
+    /// traversed bytes first. Returns `false` if the input ended (or failed) before that.
+    fn fill(&mut self, required: usize) -> bool {
+        if self.buffer_offset > 0 {
+            self.buffer.drain(..self.buffer_offset);
+            self.discarded_length += self.buffer_offset;
+            self.buffer_offset = 0;
+        }
+        while self.buffer.len() < required {
+            if self.input_ended {
+                return false;
+            }
+            let length = self.buffer.len();
+            self.buffer.resize(length + READ_CHUNK_SIZE, 0);
+            match self.input.read_into(&mut self.buffer[length..]) {
+                Ok(0) => {
+                    self.buffer.truncate(length);
+                    self.input_ended = true;
+                }
+                Ok(read_length) => self.buffer.truncate(length + read_length),
+                Err(error) => {
+                    self.buffer.truncate(length);
+                    self.input_error = Some(error);
+                    self.input_ended = true;
+                }
+            }
+        }
+        true
+    }
+}
+
+fn decode_terminal_value<'de, T: CustomTraversal>(
+    decoder: &mut VecDecoder<'de, T::CustomValueKind>,
+    value_kind: ValueKind<T::CustomValueKind>,
+) -> Result<TerminalValueRef<'de, T>, DecodeError> {
+    Ok(match value_kind {
+        ValueKind::Bool => {
+            TerminalValueRef::Bool(bool::decode_body_with_value_kind(decoder, value_kind)?)
+        }
+        ValueKind::I8 => {
+            TerminalValueRef::I8(i8::decode_body_with_value_kind(decoder, value_kind)?)
+        }
+        ValueKind::I16 => {
+            TerminalValueRef::I16(i16::decode_body_with_value_kind(decoder, value_kind)?)
+        }
+        ValueKind::I32 => {
+            TerminalValueRef::I32(i32::decode_body_with_value_kind(decoder, value_kind)?)
+        }
+        ValueKind::I64 => {
+            TerminalValueRef::I64(i64::decode_body_with_value_kind(decoder, value_kind)?)
+        }
+        ValueKind::I128 => {
+            TerminalValueRef::I128(i128::decode_body_with_value_kind(decoder, value_kind)?)
+        }
+        ValueKind::U8 => {
+            TerminalValueRef::U8(u8::decode_body_with_value_kind(decoder, value_kind)?)
+        }
+        ValueKind::U16 => {
+            TerminalValueRef::U16(u16::decode_body_with_value_kind(decoder, value_kind)?)
+        }
+        ValueKind::U32 => {
+            TerminalValueRef::U32(u32::decode_body_with_value_kind(decoder, value_kind)?)
+        }
+        ValueKind::U64 => {
+            TerminalValueRef::U64(u64::decode_body_with_value_kind(decoder, value_kind)?)
+        }
+        ValueKind::U128 => {
+            TerminalValueRef::U128(u128::decode_body_with_value_kind(decoder, value_kind)?)
+        }
+        ValueKind::String => {
+            let size = decoder.read_size()?;
+            let bytes = decoder.read_slice_from_payload(size)?;
+            TerminalValueRef::String(
+                sbor::rust::str::from_utf8(bytes).map_err(|_| DecodeError::InvalidUtf8)?,
+            )
+        }
+        ValueKind::Custom(custom_value_kind) => {
+            TerminalValueRef::Custom(T::decode_custom_value_body(custom_value_kind, decoder)?)
+        }
+        ValueKind::Array | ValueKind::Map | ValueKind::Enum | ValueKind::Tuple => {
+            unreachable!("Containers aren't terminal values")
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Categorize, Encode)]
+    #[allow(dead_code)]
+    enum TestEnum {
+        A { x: u32 },
+        B(u32),
+        C,
+    }
+
+    fn test_payload() -> Vec<u8> {
+        basic_encode(&(
+            2u8,
+            vec![3u8; 1000],
+            (3u32, indexmap!(16u8 => "Hello".to_string())),
+            TestEnum::B(4u32),
+            Vec::<u8>::new(),
+            vec![vec![(-2i64, true)]],
+            "x".repeat(20000),
+        ))
+        .unwrap()
+    }
+
+    fn collect_events<I: StreamingInput>(
+        traverser: &mut BasicStreamingTraverser<I>,
+    ) -> Vec<String> {
+        let mut events = vec![];
+        loop {
+            let event = traverser.next_event();
+            let is_last = matches!(
+                event.event,
+                TraversalEvent::End | TraversalEvent::DecodeError(_)
+            );
+            events.push(format!("{:?}", event));
+            if is_last {
+                return events;
+            }
+        }
+    }
+
+    fn expected_events(payload: &[u8]) -> Vec<String> {
+        let mut traverser = basic_payload_traverser(payload);
+        let mut events = vec![];
+        loop {
+            let event = traverser.next_event();
+            let is_last = matches!(
+                event.event,
+                TraversalEvent::End | TraversalEvent::DecodeError(_)
+            );
+            events.push(format!("{:?}", event));
+            if is_last {
+                return events;
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_events_as_vec_traverser() {
+        let payload = test_payload();
+        let expected = expected_events(&payload);
+
+        let mut traverser = basic_streaming_payload_traverser(payload.as_slice());
+        assert_eq!(collect_events(&mut traverser), expected);
+
+        // The worst case, where every byte arrives separately
+        let mut traverser = basic_streaming_payload_traverser(ChunkedInput::new(payload.chunks(1)));
+        assert_eq!(collect_events(&mut traverser), expected);
+
+        let mut traverser =
+            basic_streaming_payload_traverser(IoReadInput(std::io::Cursor::new(payload.clone())));
+        assert_eq!(collect_events(&mut traverser), expected);
+    }
+
+    #[test]
+    fn test_byte_arrays_are_output_in_batches() {
+        let payload = basic_encode(&vec![7u8; 1000]).unwrap();
+        let mut traverser =
+            basic_streaming_payload_traverser(payload.as_slice()).with_max_batch_size(300);
+        assert!(matches!(
+            traverser.next_event().event,
+            TraversalEvent::ContainerStart(_)
+        ));
+        let mut batch_lengths = vec![];
+        loop {
+            let event = traverser.next_event();
+            match event.event {
+                TraversalEvent::TerminalValueBatch(TerminalValueBatchRef::U8(bytes)) => {
+                    assert!(bytes.iter().all(|byte| *byte == 7));
+                    assert_eq!(
+                        event.location.end_offset - event.location.start_offset,
+                        bytes.len()
+                    );
+                    batch_lengths.push(bytes.len());
+                }
+                TraversalEvent::ContainerEnd(_) => break,
+                event => panic!("Unexpected event {:?}", event),
+            }
+        }
+        assert_eq!(batch_lengths, vec![300, 300, 300, 100]);
+        assert!(matches!(traverser.next_event().event, TraversalEvent::End));
+    }
+
+    #[test]
+    fn test_skip_container() {
+        let payload = basic_encode(&(vec![(1u32, "a".repeat(100)); 100], 5u16)).unwrap();
+        let mut traverser = basic_streaming_payload_traverser(ChunkedInput::new(payload.chunks(7)));
+        assert!(matches!(
+            traverser.next_event().event,
+            TraversalEvent::ContainerStart(ContainerHeader::Tuple(_))
+        ));
+        let array_start = traverser.next_event();
+        assert!(matches!(
+            array_start.event,
+            TraversalEvent::ContainerStart(ContainerHeader::Array(_))
+        ));
+        let array_start_offset = array_start.location.start_offset;
+
+        let array_end = traverser.skip_container();
+        assert!(matches!(
+            array_end.event,
+            TraversalEvent::ContainerEnd(ContainerHeader::Array(ArrayHeader { length: 100, .. }))
+        ));
+        assert_eq!(array_end.location.start_offset, array_start_offset);
+        assert_eq!(array_end.location.ancestor_path.len(), 1);
+
+        assert_eq!(
+            traverser.next_event().event,
+            TraversalEvent::TerminalValue(TerminalValueRef::U16(5))
+        );
+        assert!(matches!(
+            traverser.next_event().event,
+            TraversalEvent::ContainerEnd(ContainerHeader::Tuple(_))
+        ));
+        assert!(matches!(traverser.next_event().event, TraversalEvent::End));
+    }
+
+    #[test]
+    fn test_errors() {
+        // Truncated payload
+        let payload = test_payload();
+        let truncated = &payload[..payload.len() - 10];
+        let expected = expected_events(truncated);
+        let mut traverser =
+            basic_streaming_payload_traverser(ChunkedInput::new(truncated.chunks(3)));
+        let events = collect_events(&mut traverser);
+        assert!(events.last().unwrap().contains("BufferUnderflow"));
+        assert_eq!(events.len(), expected.len());
+
+        // Trailing bytes
+        let mut payload = basic_encode(&(1u8, 2u8)).unwrap();
+        payload.push(0);
+        let mut traverser = basic_streaming_payload_traverser(payload.as_slice());
+        assert!(collect_events(&mut traverser)
+            .last()
+            .unwrap()
+            .contains("ExtraTrailingBytes(1)"));
+
+        // Depth limit
+        let payload = basic_encode(&vec![vec![vec![1u32]]]).unwrap();
+        let mut traverser = StreamingTraverser::<_, NoCustomTraversal>::new(
+            payload.as_slice(),
+            2,
+            ExpectedStart::PayloadPrefix(BASIC_SBOR_V1_PAYLOAD_PREFIX),
+            true,
+        );
+        assert!(collect_events(&mut traverser)
+            .last()
+            .unwrap()
+            .contains("MaxDepthExceeded(2)"));
+
+        // Failing input
+        struct FailingInput;
+        impl StreamingInput for FailingInput {
+            type Error = &'static str;
+
+            fn read_into(&mut self, _: &mut [u8]) -> Result<usize, Self::Error> {
+                Err("Disconnected")
+            }
+        }
+        let mut traverser = basic_streaming_payload_traverser(FailingInput);
+        assert!(matches!(
+            traverser.next_event().event,
+            TraversalEvent::DecodeError(DecodeError::BufferUnderflow { .. })
+        ));
+        assert_eq!(traverser.input_error(), Some(&"Disconnected"));
+    }
+}
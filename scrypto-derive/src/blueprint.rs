@@ -255,6 +255,40 @@ pub fn derive_sensible_identifier_from_path(path: &Path) -> Result<String> {
     }
 }
 
+/// Splits a collection type, e.g. `SortedIndex<u64, Order>`, into the name of the collection kind
+/// and its key and value types.
+fn parse_collection_type(collection_type: &Type) -> Result<(&'static str, &Type, &Type)> {
+    let error = || {
+        Error::new(
+            collection_type.span(),
+            "Collections must be of type `Index<K, V>` or `SortedIndex<K, V>`",
+        )
+    };
+
+    let Type::Path(type_path) = collection_type else {
+        return Err(error());
+    };
+    let segment = type_path.path.segments.last().ok_or_else(error)?;
+    let kind = if segment.ident == "Index" {
+        "Index"
+    } else if segment.ident == "SortedIndex" {
+        "SortedIndex"
+    } else {
+        return Err(error());
+    };
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return Err(error());
+    };
+    let mut types = arguments.args.iter().filter_map(|argument| match argument {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    });
+    match (types.next(), types.next(), types.next()) {
+        (Some(key_type), Some(value_type), None) => Ok((kind, key_type, value_type)),
+        _ => Err(error()),
+    }
+}
+
 pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
     trace!("handle_blueprint() starts");
 
@@ -378,6 +412,7 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
         const_statements
     };
 
+    let hook_fns = extract_hook_fns(bp_items)?;
     let generated_schema_info = generate_schema(bp_ident, bp_items, &mut dependency_exprs)?;
     let fn_idents = generated_schema_info.fn_idents;
     let method_idents = generated_schema_info.method_idents;
@@ -686,6 +721,38 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
         }
     }
 
+    // Collections are numbered in the order of declaration and exposed through accessors on the
+    // blueprint struct
+    let mut collection_accessors = Vec::<ImplItemMethod>::new();
+    let mut collection_schemas = Vec::<TokenStream>::new();
+    for attribute in &blueprint.attributes {
+        if attribute.path.is_ident("collections") {
+            let collections_inner = parse2::<ast::CollectionsInner>(attribute.tokens.clone())?;
+            for collection in collections_inner.collections {
+                let collection_index = u8::try_from(collection_accessors.len()).map_err(|_| {
+                    Error::new(collection.ident.span(), "Too many collections declared")
+                })?;
+                let (kind, key_type, value_type) =
+                    parse_collection_type(&collection.collection_type)?;
+                let ident = &collection.ident;
+                let collection_type = &collection.collection_type;
+                let variant = format_ident!("{}", kind);
+                collection_accessors.push(parse_quote! {
+                    pub fn #ident(&self) -> #collection_type {
+                        <#collection_type>::new(#collection_index)
+                    }
+                });
+                collection_schemas.push(quote! {
+                    BlueprintCollectionSchema::#variant(BlueprintKeyValueSchema {
+                        key: TypeRef::Static(aggregator.add_child_type_and_descendents::<#key_type>()),
+                        value: TypeRef::Static(aggregator.add_child_type_and_descendents::<#value_type>()),
+                        allow_ownership: false,
+                    })
+                });
+            }
+        }
+    }
+
     #[cfg(feature = "no-schema")]
     let output_schema = quote! {};
     #[cfg(not(feature = "no-schema"))]
@@ -734,10 +801,36 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
             }
         };
 
+        let collections = if collection_schemas.is_empty() {
+            quote! { Vec::new() }
+        } else {
+            quote! { vec![#(#collection_schemas),*] }
+        };
+
         let schema_ident = format_ident!("{}_schema", bp_ident);
         let fn_names = generated_schema_info.fn_names;
         let fn_schemas = generated_schema_info.fn_schemas;
 
+        let hooks = if hook_fns.is_empty() {
+            quote! { BlueprintHooksInit::default() }
+        } else {
+            let hook_variants = hook_fns.iter().map(|hook_fn| hook_fn.kind.variant_ident());
+            let hook_export_names = hook_fns
+                .iter()
+                .map(|hook_fn| hook_fn.kind.export_name(bp_ident));
+            quote! {
+                {
+                    let mut hooks = index_map_new();
+                    #(
+                        hooks.insert(BlueprintHook::#hook_variants, #hook_export_names.to_string());
+                    )*
+                    BlueprintHooksInit {
+                        hooks,
+                    }
+                }
+            }
+        };
+
         // Getting the event types and other named types from attribute
         let (event_type_names, event_type_paths, registered_type_names, registered_type_paths) = {
             let mut event_type_paths = BTreeMap::<String, Path>::new();
@@ -753,7 +846,8 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
                             ));
                         }
                     }
-                } else if attribute.path.is_ident("types") {
+                } else if attribute.path.is_ident("types") || attribute.path.is_ident("collections")
+                {
                 }
                 // None of the attributes to apply at the top-level of blueprint macros matched. So,
                 // we provide an error to the user that they're using an incorrect attribute macro
@@ -811,7 +905,7 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
 
                     let state = BlueprintStateSchemaInit {
                         fields,
-                        collections: Vec::new(),
+                        collections: #collections,
                     };
 
                     // Aggregate functions
@@ -859,7 +953,7 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
                         events,
                         types,
                         functions,
-                        hooks: BlueprintHooksInit::default(),
+                        hooks: #hooks,
                     }
                 };
 
@@ -892,6 +986,16 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
         }
     };
 
+    let output_collection_accessors = if collection_accessors.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            impl #bp_ident {
+                #(#collection_accessors)*
+            }
+        }
+    };
+
     let output_original_code = quote! {
         #[derive(::scrypto::prelude::ScryptoSbor)]
         pub struct #bp_ident #bp_fields #bp_semi_token
@@ -900,6 +1004,8 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
             #(#bp_items)*
         }
 
+        #output_collection_accessors
+
         impl ::scrypto::component::ComponentState for #bp_ident {
             const BLUEPRINT_NAME: &'static str = #bp_name;
         }
@@ -929,9 +1035,11 @@ pub fn handle_blueprint(input: TokenStream) -> Result<TokenStream> {
     let method_input_structs = generate_method_input_structs(bp_ident, bp_items)?;
 
     let functions = generate_dispatcher(bp_ident, bp_items)?;
+    let hook_functions = generate_hook_dispatcher(bp_ident, &hook_fns)?;
     let output_dispatcher = quote! {
         #(#method_input_structs)*
         #(#functions)*
+        #(#hook_functions)*
     };
 
     trace!("Generated dispatcher: \n{}", quote! { #output_dispatcher });
@@ -1367,6 +1475,247 @@ fn generate_dispatcher(bp_ident: &Ident, items: &[ImplItem]) -> Result<Vec<Token
     Ok(functions)
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HookKind {
+    OnDrop,
+    OnMove,
+}
+
+impl HookKind {
+    fn from_attribute(attribute: &Attribute) -> Option<Self> {
+        if attribute.path.is_ident("on_drop") {
+            Some(HookKind::OnDrop)
+        } else if attribute.path.is_ident("on_move") {
+            Some(HookKind::OnMove)
+        } else {
+            None
+        }
+    }
+
+    fn attribute_name(&self) -> &'static str {
+        match self {
+            HookKind::OnDrop => "on_drop",
+            HookKind::OnMove => "on_move",
+        }
+    }
+
+    fn variant_ident(&self) -> Ident {
+        match self {
+            HookKind::OnDrop => format_ident!("OnDrop"),
+            HookKind::OnMove => format_ident!("OnMove"),
+        }
+    }
+
+    fn export_name(&self, bp_ident: &Ident) -> String {
+        format!("{}_{}", bp_ident, self.attribute_name())
+    }
+
+    /// The names of the `OnDropInput` / `OnMoveInput` fields passed to the hook, in order.
+    fn input_fields(&self) -> Vec<Ident> {
+        match self {
+            HookKind::OnDrop => vec![],
+            HookKind::OnMove => vec![
+                format_ident!("is_moving_down"),
+                format_ident!("is_to_barrier"),
+                format_ident!("destination_blueprint_id"),
+            ],
+        }
+    }
+}
+
+struct HookFn {
+    kind: HookKind,
+    method: ImplItemMethod,
+}
+
+/// Finds the `#[on_drop]` and `#[on_move]` functions of the blueprint, strips the hook attributes
+/// and validates their signatures.
+///
+/// Hook functions are private to the blueprint: they're exported as blueprint hooks rather than as
+/// functions or methods, so the engine is the only caller.
+fn extract_hook_fns(items: &mut [ImplItem]) -> Result<Vec<HookFn>> {
+    let mut hook_fns = Vec::<HookFn>::new();
+
+    for item in items {
+        let m = match item {
+            ImplItem::Method(m) => m,
+            _ => continue,
+        };
+
+        let mut kind = None;
+        let mut attrs = Vec::new();
+        for attribute in m.attrs.drain(..) {
+            match HookKind::from_attribute(&attribute) {
+                Some(hook_kind) => {
+                    if kind.is_some() {
+                        return Err(Error::new(
+                            attribute.span(),
+                            "A function can only be registered as one hook",
+                        ));
+                    }
+                    if !attribute.tokens.is_empty() {
+                        return Err(Error::new(
+                            attribute.tokens.span(),
+                            "Hook attributes take no arguments",
+                        ));
+                    }
+                    kind = Some(hook_kind);
+                }
+                None => attrs.push(attribute),
+            }
+        }
+        m.attrs = attrs;
+
+        let kind = match kind {
+            Some(kind) => kind,
+            None => continue,
+        };
+
+        if !matches!(m.vis, Visibility::Inherited) {
+            return Err(Error::new(
+                m.vis.span(),
+                format!(
+                    "`#[{}]` hook functions must be private to the blueprint",
+                    kind.attribute_name()
+                ),
+            ));
+        }
+
+        let mut arg_count = 0;
+        for input in m.sig.inputs.iter() {
+            match input {
+                FnArg::Receiver(r) => {
+                    if r.reference.is_none() {
+                        return Err(Error::new(r.span(), "Function input `self` is not supported. Try replacing it with `&self`."));
+                    }
+                }
+                FnArg::Typed(_) => arg_count += 1,
+            }
+        }
+        let expected_args = kind.input_fields();
+        if arg_count != expected_args.len() {
+            let expected = if expected_args.is_empty() {
+                "no arguments".to_string()
+            } else {
+                format!(
+                    "arguments `{}`",
+                    expected_args
+                        .iter()
+                        .map(|ident| ident.to_string())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            };
+            return Err(Error::new(
+                m.sig.inputs.span(),
+                format!(
+                    "`#[{}]` hook functions take {} besides the optional receiver",
+                    kind.attribute_name(),
+                    expected
+                ),
+            ));
+        }
+
+        let returns_unit = match &m.sig.output {
+            ReturnType::Default => true,
+            ReturnType::Type(_, t) => {
+                matches!(t.as_ref(), Type::Tuple(tuple) if tuple.elems.is_empty())
+            }
+        };
+        if !returns_unit {
+            return Err(Error::new(
+                m.sig.output.span(),
+                "Hook functions must not return a value",
+            ));
+        }
+
+        if hook_fns.iter().any(|hook_fn| hook_fn.kind == kind) {
+            return Err(Error::new(
+                m.sig.ident.span(),
+                format!(
+                    "A `#[{}]` hook has already been declared",
+                    kind.attribute_name()
+                ),
+            ));
+        }
+
+        hook_fns.push(HookFn {
+            kind,
+            method: m.clone(),
+        });
+    }
+
+    Ok(hook_fns)
+}
+
+fn generate_hook_dispatcher(bp_ident: &Ident, hook_fns: &[HookFn]) -> Result<Vec<TokenStream>> {
+    let mut functions = Vec::new();
+
+    for HookFn { kind, method } in hook_fns {
+        let ident = &method.sig.ident;
+
+        let mut dispatch_args: Vec<Expr> = vec![];
+        let mut get_state: Option<Stmt> = None;
+        for input in method.sig.inputs.iter() {
+            if let FnArg::Receiver(r) = input {
+                if r.mutability.is_some() {
+                    dispatch_args.push(parse_quote! { state.deref_mut() });
+                    get_state = Some(parse_quote! {
+                        let mut state: DataRefMut<#bp_ident> = component_data.get_mut();
+                    });
+                } else {
+                    dispatch_args.push(parse_quote! { state.deref() });
+                    get_state = Some(parse_quote! {
+                        let state: DataRef<#bp_ident> = component_data.get();
+                    });
+                }
+            }
+        }
+        for field in kind.input_fields() {
+            dispatch_args.push(parse_quote! { input.#field });
+        }
+
+        let input_type: Path = match kind {
+            HookKind::OnDrop => parse_quote! { ::scrypto::blueprints::hooks::OnDropInput },
+            HookKind::OnMove => parse_quote! { ::scrypto::blueprints::hooks::OnMoveInput },
+        };
+
+        let mut stmts: Vec<Stmt> = vec![];
+        stmts.push(parse_quote! {
+            let input: #input_type = ::scrypto::data::scrypto::scrypto_decode(&::scrypto::engine::wasm_api::copy_buffer(args)).unwrap();
+        });
+        if let Some(stmt) = get_state {
+            stmts.push(parse_quote! {
+                let mut component_data = ::scrypto::runtime::ComponentStatePointer::new();
+            });
+            stmts.push(stmt);
+        }
+        stmts.push(parse_quote! {
+            let return_data: () = #bp_ident::#ident(#(#dispatch_args),*);
+        });
+        stmts.push(parse_quote! {
+            return ::scrypto::engine::wasm_api::forget_vec(::scrypto::data::scrypto::scrypto_encode(&return_data).unwrap());
+        });
+
+        let export_name = kind.export_name(bp_ident);
+        validate_type_name(&export_name, bp_ident.span())?;
+        let fn_ident = format_ident!("{}", export_name);
+        functions.push(quote! {
+            #[no_mangle]
+            pub extern "C" fn #fn_ident(args: ::scrypto::engine::wasm_api::Buffer) -> ::scrypto::engine::wasm_api::Slice {
+                use sbor::rust::ops::{Deref, DerefMut};
+
+                // Set up panic hook
+                ::scrypto::set_up_panic_hook();
+
+                #(#stmts)*
+            }
+        });
+    }
+
+    Ok(functions)
+}
+
 fn create_argument_ident(argument: &Pat, index: usize) -> Result<Ident> {
     Ok(match argument {
         // If we have a standard parameter name - use that
@@ -1828,6 +2177,40 @@ mod tests {
         assert!(matches!(handle_blueprint(input), Err(_)));
     }
 
+    #[test]
+    fn test_blueprint_hooks() {
+        let input = TokenStream::from_str(
+            "mod test { struct Test {} impl Test { #[on_drop] fn drop_hook(&self) {} #[on_move] fn move_hook(is_moving_down: bool, is_to_barrier: bool, destination_blueprint_id: Option<BlueprintId>) {} } }",
+        )
+        .unwrap();
+        let output = handle_blueprint(input).unwrap().to_string();
+
+        assert!(output.contains("pub extern \"C\" fn Test_on_drop"));
+        assert!(output.contains("pub extern \"C\" fn Test_on_move"));
+        assert!(output.contains(
+            "hooks . insert (BlueprintHook :: OnDrop , \"Test_on_drop\" . to_string ())"
+        ));
+        assert!(output.contains(
+            "hooks . insert (BlueprintHook :: OnMove , \"Test_on_move\" . to_string ())"
+        ));
+        assert!(!output.contains("# [on_drop]"));
+        assert!(!output.contains("fn Test_drop_hook"));
+    }
+
+    #[test]
+    fn test_invalid_blueprint_hooks_should_fail() {
+        for input in [
+            "mod test { struct Test {} impl Test { #[on_drop] pub fn drop_hook(&self) {} } }",
+            "mod test { struct Test {} impl Test { #[on_drop] fn drop_hook(&self, x: u32) {} } }",
+            "mod test { struct Test {} impl Test { #[on_drop] fn drop_hook(&self) -> u32 { 1 } } }",
+            "mod test { struct Test {} impl Test { #[on_move] fn move_hook(&self) {} } }",
+            "mod test { struct Test {} impl Test { #[on_drop] fn a() {} #[on_drop] fn b() {} } }",
+        ] {
+            let input = TokenStream::from_str(input).unwrap();
+            assert!(matches!(handle_blueprint(input), Err(_)));
+        }
+    }
+
     #[test]
     fn test_derive_sensible_identifier_from_path() {
         assert_eq!(
@@ -15,6 +15,13 @@ use proc_macro::TokenStream;
 /// This macro will derive the dispatcher method responsible for handling invocation
 /// according to Scrypto ABI.
 ///
+/// Private functions marked with `#[on_drop]` or `#[on_move]` are registered as blueprint
+/// hooks and invoked by the engine when an object of the blueprint is dropped or moved between
+/// call frames. An `#[on_drop]` hook takes no arguments; an `#[on_move]` hook takes
+/// `is_moving_down: bool, is_to_barrier: bool, destination_blueprint_id: Option<BlueprintId>`.
+/// Both may take `&self` or `&mut self` to access the object's state, and can panic to abort
+/// the transaction.
+///
 /// # Example
 /// ```ignore
 /// use scrypto::prelude::*;
@@ -216,8 +216,10 @@ where
         let id_allocator = IdAllocator::new(Self::DEFAULT_INTENT_HASH);
 
         // Determine if any protocol updates need to be run against the database.
-        self.protocol_executor
-            .commit_each_protocol_update(&mut self.database);
+        self.protocol_executor.commit_each_protocol_update_with_vm_init(
+            &mut self.database,
+            VmInit::new(&scrypto_vm, NoExtension),
+        );
 
         // If a flash is specified execute it.
         let database_updates = self.flash_database.database_updates();
@@ -245,6 +247,17 @@ where
                     .map(|v| scrypto_decode(v.as_slice()).unwrap())
                     .unwrap_or(VmBoot::babylon());
 
+                let system_version = database
+                    .get_substate(
+                        &db_partition_key,
+                        &SpreadPrefixKeyMapper::to_db_sort_key(&SubstateKey::Field(
+                            BOOT_LOADER_SYSTEM_SUBSTATE_FIELD_KEY,
+                        )),
+                    )
+                    .map(|v| scrypto_decode::<SystemBoot>(v.as_slice()).unwrap())
+                    .map(|system_boot| system_boot.system_version())
+                    .unwrap_or(SystemVersion::V1);
+
                 let transaction_runtime_module = TransactionRuntimeModule::new(
                     NetworkDefinition::simulator(),
                     Self::DEFAULT_INTENT_HASH,
@@ -253,7 +266,8 @@ where
                 let auth_module = AuthModule::new(AuthZoneParams {
                     initial_proofs: Default::default(),
                     virtual_resources: Default::default(),
-                });
+                })
+                .with_time_conditions(system_version.supports_time_conditions());
 
                 let limits_module = LimitsModule::from_params(LimitParameters::babylon_genesis());
 
@@ -32,12 +32,17 @@ use radix_engine_interface::blueprints::pool::{
 use radix_engine_interface::prelude::{dec, freeze_roles, rule};
 use radix_substate_store_impls::memory_db::InMemorySubstateDatabase;
 use radix_substate_store_impls::state_tree_support::StateTreeUpdatingDatabase;
+use radix_substate_store_impls::substate_snapshot::{
+    SubstateSnapshotError, SubstateSnapshotReader,
+};
 use radix_substate_store_interface::db_key_mapper::SpreadPrefixKeyMapper;
 use radix_substate_store_interface::db_key_mapper::{DatabaseKeyMapper, MappedSubstateDatabase};
 use radix_substate_store_interface::interface::{
     CommittableSubstateDatabase, DatabaseUpdate, ListableSubstateDatabase, SubstateDatabase,
 };
-use radix_substate_store_queries::query::{ResourceAccounter, StateTreeTraverser, VaultFinder};
+use radix_substate_store_queries::query::{
+    ResourceAccounter, StateTreeTraverser, TrackedIntent, TransactionTrackerReader, VaultFinder,
+};
 use radix_substate_store_queries::typed_native_events::to_typed_native_event;
 use radix_substate_store_queries::typed_substate_layout::*;
 use radix_transactions::validation::{
@@ -377,7 +382,10 @@ impl<E: NativeVmExtension, D: TestDatabase> LedgerSimulatorBuilder<E, D> {
 
         // Protocol Updates
         self.protocol_executor
-            .commit_each_protocol_update(&mut substate_db);
+            .commit_each_protocol_update_with_vm_init(
+                &mut substate_db,
+                VmInit::new(&scrypto_vm, self.custom_extension.clone()),
+            );
 
         // Note that 0 is not a valid private key
         let next_private_key = 100;
@@ -446,6 +454,39 @@ pub struct LedgerSimulatorSnapshot {
     with_receipt_substate_check: bool,
 }
 
+impl LedgerSimulatorSnapshot {
+    /// Creates a snapshot from a portable substate snapshot (e.g. one exported from a replayed
+    /// ledger), to be passed to [`LedgerSimulatorBuilder::build_from_snapshot`].
+    ///
+    /// A partial substate snapshot is imported on top of a freshly bootstrapped ledger.
+    pub fn from_substate_snapshot<R: std::io::Read>(
+        reader: SubstateSnapshotReader<R>,
+        with_kernel_trace: bool,
+    ) -> Result<Self, SubstateSnapshotError> {
+        let mut snapshot = if reader.header().is_partial {
+            LedgerSimulatorSnapshot {
+                with_kernel_trace,
+                ..LedgerSimulatorBuilder::new()
+                    .without_kernel_trace()
+                    .build()
+                    .create_snapshot()
+            }
+        } else {
+            LedgerSimulatorSnapshot {
+                database: InMemorySubstateDatabase::standard(),
+                next_private_key: 100,
+                next_transaction_nonce: 100,
+                collected_events: vec![],
+                xrd_free_credits_used: false,
+                with_kernel_trace,
+                with_receipt_substate_check: true,
+            }
+        };
+        reader.import_into(&mut snapshot.database)?;
+        Ok(snapshot)
+    }
+}
+
 impl<E: NativeVmExtension> LedgerSimulator<E, InMemorySubstateDatabase> {
     pub fn create_snapshot(&self) -> LedgerSimulatorSnapshot {
         LedgerSimulatorSnapshot {
@@ -784,6 +825,19 @@ impl<E: NativeVmExtension, D: TestDatabase> LedgerSimulator<E, D> {
         accounter.close().balances
     }
 
+    /// Returns the status of the given intent, if it's still tracked by the transaction tracker.
+    ///
+    /// Only notarized transactions are tracked, so intents executed with e.g. `execute_manifest`
+    /// won't be found.
+    pub fn get_intent_status(&self, intent_hash: &IntentHash) -> Option<TrackedIntent> {
+        TransactionTrackerReader::new(&self.database).intent_status(intent_hash)
+    }
+
+    /// Lists all intents currently tracked by the transaction tracker.
+    pub fn list_tracked_intents(&self) -> Vec<TrackedIntent> {
+        TransactionTrackerReader::new(&self.database).list_intents(0..=u8::MAX)
+    }
+
     pub fn component_state<T: ScryptoDecode>(&self, component_address: ComponentAddress) -> T {
         let node_id: &NodeId = component_address.as_node_id();
         let component_state = self
@@ -826,6 +880,59 @@ impl<E: NativeVmExtension, D: TestDatabase> LedgerSimulator<E, D> {
         reader.read_typed_kv_entry(kv_store_id.as_node_id(), key)
     }
 
+    /// Returns all entries of an index collection of a component, in the order of the database.
+    pub fn get_index_entries<K: ScryptoDecode, V: ScryptoDecode>(
+        &self,
+        component_address: ComponentAddress,
+        collection_index: CollectionIndex,
+    ) -> Vec<(K, V)> {
+        let reader = SystemDatabaseReader::new(self.substate_db());
+        reader
+            .collection_iter(
+                component_address.as_node_id(),
+                ModuleId::Main,
+                collection_index,
+            )
+            .unwrap()
+            .map(|(key, value)| {
+                let SubstateKey::Map(key) = key else {
+                    panic!("Collection {} is not an index", collection_index);
+                };
+                let value: IndexEntrySubstate<V> = scrypto_decode(&value).unwrap();
+                (scrypto_decode(&key).unwrap(), value.into_value())
+            })
+            .collect()
+    }
+
+    /// Returns all entries of a sorted index collection of a component in ascending order, along
+    /// with their sort keys.
+    pub fn get_sorted_index_entries<K: ScryptoDecode, V: ScryptoDecode>(
+        &self,
+        component_address: ComponentAddress,
+        collection_index: CollectionIndex,
+    ) -> Vec<(u16, K, V)> {
+        let reader = SystemDatabaseReader::new(self.substate_db());
+        reader
+            .collection_iter(
+                component_address.as_node_id(),
+                ModuleId::Main,
+                collection_index,
+            )
+            .unwrap()
+            .map(|(key, value)| {
+                let SubstateKey::Sorted((sort_key, key)) = key else {
+                    panic!("Collection {} is not a sorted index", collection_index);
+                };
+                let value: SortedIndexEntrySubstate<V> = scrypto_decode(&value).unwrap();
+                (
+                    u16::from_be_bytes(sort_key),
+                    scrypto_decode(&key).unwrap(),
+                    value.into_value(),
+                )
+            })
+            .collect()
+    }
+
     pub fn get_fungible_resource_total_supply(&self, resource: ResourceAddress) -> Decimal {
         let total_supply = self
             .substate_db()
@@ -1357,6 +1464,7 @@ impl<E: NativeVmExtension, D: TestDatabase> LedgerSimulator<E, D> {
                     enable_kernel_trace: execution_config.enable_kernel_trace,
                     enable_cost_breakdown: execution_config.enable_cost_breakdown,
                     execution_trace: execution_config.execution_trace,
+                    enable_auth_diagnostics: execution_config.enable_auth_diagnostics,
                     callback_init: vm_init,
                     system_overrides: execution_config.system_overrides.clone(),
                 },
@@ -1396,6 +1504,33 @@ impl<E: NativeVmExtension, D: TestDatabase> LedgerSimulator<E, D> {
         )
     }
 
+    /// Signs and notarizes the manifest with the given signers, which can be anything implementing
+    /// [`Signer`] (e.g. an [`ExternalSigner`]) and be of different types, and executes it as a
+    /// notarized transaction. Fails without executing anything if any of the signers fails to sign.
+    pub fn execute_notarized_manifest(
+        &mut self,
+        manifest: TransactionManifestV1,
+        signers: &[&dyn DynSigner],
+        notary: &dyn DynSigner,
+    ) -> Result<TransactionReceipt, SigningError> {
+        let network = NetworkDefinition::simulator();
+        let notarized_transaction = TransactionBuilder::new()
+            .header(TransactionHeaderV1 {
+                network_id: network.id,
+                start_epoch_inclusive: Epoch::zero(),
+                end_epoch_exclusive: Epoch::of(99),
+                nonce: self.next_transaction_nonce(),
+                notary_public_key: notary.public_key(),
+                notary_is_signatory: false,
+                tip_percentage: DEFAULT_TIP_PERCENTAGE,
+            })
+            .manifest(manifest)
+            .try_multi_sign(signers)?
+            .try_notarize(notary)?
+            .build();
+        Ok(self.execute_notarized_transaction(&notarized_transaction.to_raw().unwrap()))
+    }
+
     pub fn execute_system_transaction(
         &mut self,
         instructions: Vec<InstructionV1>,
@@ -1,7 +1,9 @@
 mod compile;
 mod inject_costing_err;
 mod ledger_simulator;
+mod staking_simulator;
 
 pub use compile::*;
 pub use inject_costing_err::*;
 pub use ledger_simulator::*;
+pub use staking_simulator::*;
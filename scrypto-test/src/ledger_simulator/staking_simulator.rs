@@ -0,0 +1,765 @@
+use crate::prelude::*;
+use radix_engine::system::system_db_reader::SystemDatabaseReader;
+use radix_engine_interface::api::ModuleId;
+use radix_engine_interface::blueprints::consensus_manager::{
+    ConsensusManagerNextRoundInput, EpochChangeOutcome, LeaderProposalHistory,
+    CONSENSUS_MANAGER_NEXT_ROUND_IDENT, VALIDATOR_UPDATE_FEE_IDENT,
+};
+use radix_substate_store_queries::typed_substate_layout::*;
+
+/// The private key seeds of the simulated validators (offset by their index) and of the staker.
+/// These are kept well away from the seeds handed out by [`LedgerSimulator::new_key_pair`].
+const VALIDATOR_KEY_SEED_OFFSET: u64 = 1_000_000;
+const STAKER_KEY_SEED: u64 = 999_999;
+
+/// Decides which of a validator's proposals are made and which are missed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProposalPattern {
+    /// The given proportion of proposals (between 0 and 1) is made, spread evenly across the
+    /// validator's turns as a leader.
+    Reliability(Decimal),
+    /// Proposals are made (`true`) or missed (`false`) following the given sequence, which is
+    /// repeated once exhausted.
+    Cycle(Vec<bool>),
+}
+
+impl ProposalPattern {
+    pub fn always_made() -> Self {
+        Self::Reliability(Decimal::ONE)
+    }
+
+    pub fn always_missed() -> Self {
+        Self::Reliability(Decimal::ZERO)
+    }
+
+    /// Panics if the pattern is a [`ProposalPattern::Reliability`] outside of 0 to 1 (inclusive).
+    pub fn validate(&self) {
+        if let ProposalPattern::Reliability(reliability) = self {
+            assert!(
+                *reliability >= Decimal::ZERO && *reliability <= Decimal::ONE,
+                "Reliability should be between 0 and 1, but is {}",
+                reliability
+            );
+        }
+    }
+
+    /// Returns whether the validator's proposal on its `turn`-th turn as a leader is made.
+    pub fn is_made(&self, turn: u64) -> bool {
+        match self {
+            ProposalPattern::Reliability(reliability) => {
+                let proposals_made_after = |turns: u64| {
+                    Decimal::from(turns)
+                        .checked_mul(*reliability)
+                        .and_then(|made| made.checked_floor())
+                        .expect("Reliability should have been validated to be at most 1")
+                };
+                proposals_made_after(turn + 1) > proposals_made_after(turn)
+            }
+            ProposalPattern::Cycle(sequence) => {
+                sequence.is_empty() || sequence[(turn % sequence.len() as u64) as usize]
+            }
+        }
+    }
+}
+
+/// The initial setup of a validator in a [`StakingSimulator`].
+#[derive(Debug, Clone)]
+pub struct SimulatedValidatorConfig {
+    /// The XRD staked to the validator at genesis, on behalf of the simulator's staker.
+    pub stake_xrd: Decimal,
+    pub fee_factor: Decimal,
+    pub proposal_pattern: ProposalPattern,
+}
+
+impl SimulatedValidatorConfig {
+    pub fn new(stake_xrd: Decimal) -> Self {
+        Self {
+            stake_xrd,
+            fee_factor: Decimal::ZERO,
+            proposal_pattern: ProposalPattern::always_made(),
+        }
+    }
+
+    pub fn with_fee_factor(mut self, fee_factor: Decimal) -> Self {
+        self.fee_factor = fee_factor;
+        self
+    }
+
+    pub fn with_proposal_pattern(mut self, proposal_pattern: ProposalPattern) -> Self {
+        proposal_pattern.validate();
+        self.proposal_pattern = proposal_pattern;
+        self
+    }
+}
+
+pub struct StakingSimulatorBuilder {
+    validators: Vec<SimulatedValidatorConfig>,
+    consensus_manager_config: ConsensusManagerConfig,
+    genesis_epoch: Epoch,
+    round_duration_millis: Option<u64>,
+    staker_xrd_balance: Decimal,
+}
+
+impl Default for StakingSimulatorBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StakingSimulatorBuilder {
+    pub fn new() -> Self {
+        Self {
+            validators: vec![],
+            consensus_manager_config: CustomGenesis::default_consensus_manager_config(),
+            genesis_epoch: Epoch::of(1),
+            round_duration_millis: None,
+            staker_xrd_balance: Decimal::ZERO,
+        }
+    }
+
+    /// Adds a validator, which is referred to by its index in the order of addition. All
+    /// validators need to fit into the active validator set at genesis.
+    pub fn with_validator(mut self, validator: SimulatedValidatorConfig) -> Self {
+        self.validators.push(validator);
+        self
+    }
+
+    pub fn with_consensus_manager_config(mut self, config: ConsensusManagerConfig) -> Self {
+        self.consensus_manager_config = config;
+        self
+    }
+
+    pub fn with_genesis_epoch(mut self, genesis_epoch: Epoch) -> Self {
+        self.genesis_epoch = genesis_epoch;
+        self
+    }
+
+    /// Sets how far the proposer timestamp moves on each round. By default, it's chosen so that
+    /// the epoch's target duration elapses in exactly its minimum number of rounds.
+    pub fn with_round_duration_millis(mut self, round_duration_millis: u64) -> Self {
+        self.round_duration_millis = Some(round_duration_millis);
+        self
+    }
+
+    /// Sets the unstaked XRD held by the staker's account at genesis, for use with
+    /// [`StakingSimulator::stake`].
+    pub fn with_staker_xrd_balance(mut self, staker_xrd_balance: Decimal) -> Self {
+        self.staker_xrd_balance = staker_xrd_balance;
+        self
+    }
+
+    pub fn build(self) -> StakingSimulator {
+        let staker_key = Secp256k1PrivateKey::from_u64(STAKER_KEY_SEED)
+            .unwrap()
+            .public_key();
+        let staker_account = ComponentAddress::virtual_account_from_public_key(&staker_key);
+        let validator_keys = (0..self.validators.len() as u64)
+            .map(|index| {
+                Secp256k1PrivateKey::from_u64(VALIDATOR_KEY_SEED_OFFSET + index)
+                    .unwrap()
+                    .public_key()
+            })
+            .collect::<Vec<_>>();
+
+        let genesis_validators = validator_keys
+            .iter()
+            .zip(&self.validators)
+            .map(|(key, config)| GenesisValidator {
+                fee_factor: config.fee_factor,
+                ..GenesisValidator::from(*key)
+            })
+            .collect();
+        let stake_allocations = validator_keys
+            .iter()
+            .zip(&self.validators)
+            .map(|(key, config)| {
+                (
+                    *key,
+                    vec![GenesisStakeAllocation {
+                        account_index: 0,
+                        xrd_amount: config.stake_xrd,
+                    }],
+                )
+            })
+            .collect();
+        let genesis = CustomGenesis {
+            genesis_data_chunks: vec![
+                GenesisDataChunk::Validators(genesis_validators),
+                GenesisDataChunk::Stakes {
+                    accounts: vec![staker_account],
+                    allocations: stake_allocations,
+                },
+                GenesisDataChunk::ResourceBalances {
+                    accounts: vec![staker_account],
+                    allocations: vec![(
+                        XRD,
+                        vec![GenesisResourceAllocation {
+                            account_index: 0,
+                            amount: self.staker_xrd_balance,
+                        }],
+                    )],
+                },
+            ],
+            genesis_epoch: self.genesis_epoch,
+            initial_config: self.consensus_manager_config.clone(),
+            initial_time_ms: 1,
+            initial_current_leader: Some(0),
+            faucet_supply: *DEFAULT_TESTING_FAUCET_SUPPLY,
+        };
+        let mut ledger = LedgerSimulatorBuilder::new()
+            .with_custom_genesis(genesis)
+            .without_kernel_trace()
+            .build();
+
+        let validators = validator_keys
+            .into_iter()
+            .zip(self.validators)
+            .map(|(key, config)| {
+                config.proposal_pattern.validate();
+                let address = ledger.get_active_validator_with_key(&key);
+                let initial_state = ledger.get_validator_info(address);
+                SimulatedValidator {
+                    key,
+                    address,
+                    proposal_pattern: config.proposal_pattern,
+                    proposal_turns: 0,
+                    last_stake_unit_redemption_value: stake_unit_redemption_value(
+                        &mut ledger,
+                        &initial_state,
+                    ),
+                }
+            })
+            .collect();
+
+        let epoch_change_condition = &self.consensus_manager_config.epoch_change_condition;
+        let round_duration_millis = self.round_duration_millis.unwrap_or(
+            epoch_change_condition.target_duration_millis
+                / epoch_change_condition.min_round_count.max(1),
+        );
+
+        StakingSimulator {
+            ledger,
+            validators,
+            staker_key,
+            staker_account,
+            round_duration_millis,
+            unbonding_claims: vec![],
+        }
+    }
+}
+
+struct SimulatedValidator {
+    key: Secp256k1PublicKey,
+    address: ComponentAddress,
+    proposal_pattern: ProposalPattern,
+    proposal_turns: u64,
+    last_stake_unit_redemption_value: Decimal,
+}
+
+/// A claim on unstaked XRD held by the staker, which becomes claimable at `claim_epoch`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnbondingClaim {
+    /// The index of the validator (in the order of configuration) the XRD was unstaked from.
+    pub validator_index: usize,
+    pub claim_nft_id: NonFungibleGlobalId,
+    pub claim_epoch: Epoch,
+    pub claim_amount: Decimal,
+}
+
+/// The outcome of a single epoch of a [`StakingSimulator`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StakingEpochReport {
+    /// The epoch which has just concluded.
+    pub epoch: Epoch,
+    /// The time elapsed since the previous epoch change, according to the proposer timestamps.
+    pub duration_millis: i64,
+    /// The reports of all validators, in the order of their configuration.
+    pub validators: Vec<ValidatorEpochReport>,
+}
+
+impl StakingEpochReport {
+    /// The total XRD emitted to all validators in the epoch, including validator fees.
+    pub fn total_emission_xrd(&self) -> Decimal {
+        self.validators
+            .iter()
+            .map(|validator| validator.emission_xrd())
+            .fold(Decimal::ZERO, |total, emission| {
+                total.checked_add(emission).unwrap()
+            })
+    }
+}
+
+/// The outcome of a single epoch for a single validator. All balances are captured after the
+/// epoch change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorEpochReport {
+    pub address: ComponentAddress,
+    /// Whether the validator was part of the active validator set in the concluded epoch. An
+    /// inactive validator doesn't propose, and gets neither emissions nor rewards.
+    pub was_active: bool,
+    pub proposals_made: u64,
+    pub proposals_missed: u64,
+    /// The emission added to the stake pool, after the reliability penalty and validator fee.
+    pub stake_pool_added_xrd: Decimal,
+    /// The part of the emission taken by the validator as its fee (as locked owner stake units).
+    pub validator_fee_xrd: Decimal,
+    /// The rewards from transaction fees and tips, added to the stake pool.
+    pub reward_xrd: Decimal,
+    pub fee_factor: Decimal,
+    pub stake_xrd: Decimal,
+    pub stake_unit_supply: Decimal,
+    /// The XRD redeemable for a single stake unit.
+    pub stake_unit_redemption_value: Decimal,
+    /// The relative change of the redemption value since the previous epoch, e.g. `0.001` for a
+    /// 0.1% increase.
+    pub stake_unit_redemption_value_growth: Decimal,
+    /// The staker's unbonding XRD from this validator which can already be claimed.
+    pub claimable_unbonding_xrd: Decimal,
+    /// The staker's unbonding XRD from this validator which can't be claimed yet.
+    pub pending_unbonding_xrd: Decimal,
+}
+
+impl ValidatorEpochReport {
+    /// The total XRD emitted to the validator, i.e. including its fee.
+    pub fn emission_xrd(&self) -> Decimal {
+        self.stake_pool_added_xrd
+            .checked_add(self.validator_fee_xrd)
+            .unwrap()
+    }
+}
+
+/// A harness for simulating the staking economics of a set of validators over many epochs,
+/// e.g. to verify APY calculations against real [`ConsensusManagerConfig`] parameters.
+///
+/// All validators are created at genesis, with their stake delegated by a single staker. Each
+/// epoch is run through the consensus manager's rounds, with leaders taking turns in the order
+/// of the active validator set, and each validator making or missing its proposals according to
+/// its [`ProposalPattern`].
+pub struct StakingSimulator {
+    ledger: DefaultLedgerSimulator,
+    validators: Vec<SimulatedValidator>,
+    staker_key: Secp256k1PublicKey,
+    staker_account: ComponentAddress,
+    round_duration_millis: u64,
+    unbonding_claims: Vec<UnbondingClaim>,
+}
+
+impl StakingSimulator {
+    pub fn ledger(&mut self) -> &mut DefaultLedgerSimulator {
+        &mut self.ledger
+    }
+
+    pub fn validator_address(&self, validator_index: usize) -> ComponentAddress {
+        self.validators[validator_index].address
+    }
+
+    pub fn staker_account(&self) -> ComponentAddress {
+        self.staker_account
+    }
+
+    pub fn unbonding_claims(&self) -> &[UnbondingClaim] {
+        &self.unbonding_claims
+    }
+
+    pub fn set_proposal_pattern(&mut self, validator_index: usize, pattern: ProposalPattern) {
+        pattern.validate();
+        self.validators[validator_index].proposal_pattern = pattern;
+    }
+
+    /// Returns the stake units of the given validator held by the staker.
+    pub fn staker_stake_units(&mut self, validator_index: usize) -> Decimal {
+        let validator = self
+            .ledger
+            .get_validator_info(self.validators[validator_index].address);
+        self.ledger
+            .get_component_balance(self.staker_account, validator.stake_unit_resource)
+    }
+
+    /// Stakes XRD from the staker's account to the given validator.
+    pub fn stake(&mut self, validator_index: usize, xrd_amount: Decimal) -> TransactionReceipt {
+        let validator_address = self.validators[validator_index].address;
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(self.staker_account, XRD, xrd_amount)
+            .take_all_from_worktop(XRD, "xrd")
+            .stake_validator(validator_address, "xrd")
+            .try_deposit_entire_worktop_or_abort(self.staker_account, None)
+            .build();
+        self.execute_as_staker(manifest)
+    }
+
+    /// Unstakes the staker's stake units from the given validator, recording the resulting
+    /// [`UnbondingClaim`].
+    pub fn unstake(&mut self, validator_index: usize, stake_units: Decimal) -> UnbondingClaim {
+        let validator_address = self.validators[validator_index].address;
+        let validator = self.ledger.get_validator_info(validator_address);
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(
+                self.staker_account,
+                validator.stake_unit_resource,
+                stake_units,
+            )
+            .take_all_from_worktop(validator.stake_unit_resource, "stake_units")
+            .unstake_validator(validator_address, "stake_units")
+            .try_deposit_entire_worktop_or_abort(self.staker_account, None)
+            .build();
+        self.execute_as_staker(manifest).expect_commit_success();
+
+        let claim_nft_id = self
+            .staker_claim_nft_ids(validator.claim_nft)
+            .into_iter()
+            .map(|id| NonFungibleGlobalId::new(validator.claim_nft, id))
+            .find(|id| {
+                !self
+                    .unbonding_claims
+                    .iter()
+                    .any(|claim| &claim.claim_nft_id == id)
+            })
+            .expect("Unstaking should create a claim NFT");
+        let data = self.ledger.get_non_fungible_data::<UnstakeData>(
+            validator.claim_nft,
+            claim_nft_id.local_id().clone(),
+        );
+        let claim = UnbondingClaim {
+            validator_index,
+            claim_nft_id,
+            claim_epoch: data.claim_epoch,
+            claim_amount: data.claim_amount,
+        };
+        self.unbonding_claims.push(claim.clone());
+        claim
+    }
+
+    /// Claims all of the staker's unbonding XRD from the given validator which is claimable in
+    /// the current epoch, returning the claimed amount.
+    pub fn claim_xrd(&mut self, validator_index: usize) -> Decimal {
+        let current_epoch = self.ledger.get_consensus_manager_state().epoch;
+        let (claimable, pending): (Vec<_>, Vec<_>) =
+            self.unbonding_claims.drain(..).partition(|claim| {
+                claim.validator_index == validator_index && claim.claim_epoch <= current_epoch
+            });
+        self.unbonding_claims = pending;
+        if claimable.is_empty() {
+            return Decimal::ZERO;
+        }
+
+        let validator_address = self.validators[validator_index].address;
+        let claim_nft = claimable[0].claim_nft_id.resource_address();
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_non_fungibles_from_account(
+                self.staker_account,
+                claim_nft,
+                claimable
+                    .iter()
+                    .map(|claim| claim.claim_nft_id.local_id().clone()),
+            )
+            .take_all_from_worktop(claim_nft, "claims")
+            .claim_xrd(validator_address, "claims")
+            .try_deposit_entire_worktop_or_abort(self.staker_account, None)
+            .build();
+        let receipt = self.execute_as_staker(manifest);
+        self.ledger
+            .extract_events_of_type::<ClaimXrdEvent>(receipt.expect_commit_success())
+            .into_iter()
+            .fold(Decimal::ZERO, |total, event| {
+                total.checked_add(event.claimed_xrd).unwrap()
+            })
+    }
+
+    /// Requests a change of the given validator's fee factor, as its owner. Note that increases
+    /// only take effect after the configured number of epochs.
+    pub fn update_fee(
+        &mut self,
+        validator_index: usize,
+        fee_factor: Decimal,
+    ) -> TransactionReceipt {
+        let validator = &self.validators[validator_index];
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_non_fungibles(
+                ComponentAddress::virtual_account_from_public_key(&validator.key),
+                VALIDATOR_OWNER_BADGE,
+                [NonFungibleLocalId::bytes(validator.address.as_node_id().0).unwrap()],
+            )
+            .call_method(
+                validator.address,
+                VALIDATOR_UPDATE_FEE_IDENT,
+                manifest_args!(fee_factor),
+            )
+            .build();
+        let proof = NonFungibleGlobalId::from_public_key(&validator.key);
+        self.ledger.execute_manifest(manifest, vec![proof])
+    }
+
+    /// Runs the rounds of the current epoch until the epoch changes, and reports its outcome.
+    pub fn advance_epoch(&mut self) -> StakingEpochReport {
+        let config = self.read_config();
+        let state = self.ledger.get_consensus_manager_state();
+        let leaders = self.read_active_validator_indices();
+        let epoch_start_millis = self.ledger.get_current_proposer_timestamp_ms();
+
+        let mut round = state.round;
+        let mut proposer_timestamp_ms = epoch_start_millis;
+        let mut gap_round_leaders = vec![];
+        let result = loop {
+            round = Round::of(round.number() + 1);
+            proposer_timestamp_ms += self.round_duration_millis as i64;
+            let leader = ((round.number() - 1) % leaders.len() as u64) as ValidatorIndex;
+            let is_made = match leaders[leader as usize] {
+                Some(validator_index) => {
+                    let validator = &mut self.validators[validator_index];
+                    let is_made = validator.proposal_pattern.is_made(validator.proposal_turns);
+                    validator.proposal_turns += 1;
+                    is_made
+                }
+                None => true,
+            };
+            let is_epoch_change = matches!(
+                config.epoch_change_condition.should_epoch_change(
+                    state.effective_epoch_start_milli,
+                    proposer_timestamp_ms,
+                    round
+                ),
+                EpochChangeOutcome::Change { .. }
+            );
+
+            // Missed proposals are reported as gap rounds of the next made proposal, unless the
+            // epoch ends on them, in which case the last one becomes a fallback round.
+            if !is_made && !is_epoch_change {
+                gap_round_leaders.push(leader);
+                continue;
+            }
+            let receipt = self.ledger.execute_system_transaction(
+                vec![InstructionV1::CallMethod {
+                    address: CONSENSUS_MANAGER.into(),
+                    method_name: CONSENSUS_MANAGER_NEXT_ROUND_IDENT.to_string(),
+                    args: to_manifest_value_and_unwrap!(&ConsensusManagerNextRoundInput {
+                        round,
+                        proposer_timestamp_ms,
+                        leader_proposal_history: LeaderProposalHistory {
+                            gap_round_leaders: core::mem::take(&mut gap_round_leaders),
+                            current_leader: leader,
+                            is_fallback: !is_made,
+                        },
+                    }),
+                }],
+                btreeset![AuthAddresses::validator_role()],
+                vec![],
+            );
+            if is_epoch_change {
+                break receipt.expect_commit_success().clone();
+            }
+            receipt.expect_commit_success();
+        };
+
+        let validators = (0..self.validators.len())
+            .map(|validator_index| self.report_validator(validator_index, &result, &leaders))
+            .collect();
+        StakingEpochReport {
+            epoch: state.epoch,
+            duration_millis: proposer_timestamp_ms - epoch_start_millis,
+            validators,
+        }
+    }
+
+    pub fn advance_epochs(&mut self, epochs: usize) -> Vec<StakingEpochReport> {
+        (0..epochs).map(|_| self.advance_epoch()).collect()
+    }
+
+    /// Annualizes the growth of the given validator's stake unit redemption value over the given
+    /// consecutive epochs, compounding it over `epochs_per_year` epochs.
+    pub fn annualized_yield(
+        reports: &[StakingEpochReport],
+        validator_index: usize,
+        epochs_per_year: u64,
+    ) -> Decimal {
+        let total_growth = reports
+            .iter()
+            .map(|report| {
+                PreciseDecimal::from(
+                    report.validators[validator_index].stake_unit_redemption_value_growth,
+                )
+            })
+            .fold(PreciseDecimal::ONE, |total, growth| {
+                total
+                    .checked_mul(PreciseDecimal::ONE.checked_add(growth).unwrap())
+                    .unwrap()
+            });
+        let growth_per_epoch = total_growth
+            .checked_nth_root(reports.len() as u32)
+            .expect("Reports should not be empty");
+        let annual_growth = growth_per_epoch
+            .checked_powi(epochs_per_year as i64)
+            .unwrap();
+        Decimal::try_from(annual_growth.checked_sub(PreciseDecimal::ONE).unwrap()).unwrap()
+    }
+
+    fn report_validator(
+        &mut self,
+        validator_index: usize,
+        result: &CommitResult,
+        leaders: &[Option<usize>],
+    ) -> ValidatorEpochReport {
+        let address = self.validators[validator_index].address;
+        let emission = self
+            .extract_validator_events::<ValidatorEmissionAppliedEvent>(result, address)
+            .pop();
+        let reward_xrd = self
+            .extract_validator_events::<ValidatorRewardAppliedEvent>(result, address)
+            .into_iter()
+            .fold(Decimal::ZERO, |total, event| {
+                total.checked_add(event.amount).unwrap()
+            });
+
+        let state = self.ledger.get_validator_info(address);
+        let stake_xrd = self
+            .ledger
+            .inspect_vault_balance(state.stake_xrd_vault_id.0)
+            .unwrap();
+        let stake_unit_supply = self
+            .ledger
+            .get_fungible_resource_total_supply(state.stake_unit_resource);
+        let redemption_value = stake_unit_redemption_value(&mut self.ledger, &state);
+        let validator = &mut self.validators[validator_index];
+        let redemption_value_growth = if validator.last_stake_unit_redemption_value.is_zero() {
+            Decimal::ZERO
+        } else {
+            redemption_value
+                .checked_div(validator.last_stake_unit_redemption_value)
+                .and_then(|ratio| ratio.checked_sub(Decimal::ONE))
+                .unwrap()
+        };
+        validator.last_stake_unit_redemption_value = redemption_value;
+
+        let current_epoch = result
+            .next_epoch()
+            .expect("Epoch should have changed")
+            .epoch;
+        let (mut claimable_unbonding_xrd, mut pending_unbonding_xrd) =
+            (Decimal::ZERO, Decimal::ZERO);
+        for claim in &self.unbonding_claims {
+            if claim.validator_index != validator_index {
+                continue;
+            }
+            let total = if claim.claim_epoch <= current_epoch {
+                &mut claimable_unbonding_xrd
+            } else {
+                &mut pending_unbonding_xrd
+            };
+            *total = total.checked_add(claim.claim_amount).unwrap();
+        }
+
+        ValidatorEpochReport {
+            address,
+            was_active: leaders.contains(&Some(validator_index)),
+            proposals_made: emission.as_ref().map_or(0, |event| event.proposals_made),
+            proposals_missed: emission.as_ref().map_or(0, |event| event.proposals_missed),
+            stake_pool_added_xrd: emission
+                .as_ref()
+                .map_or(Decimal::ZERO, |event| event.stake_pool_added_xrd),
+            validator_fee_xrd: emission
+                .as_ref()
+                .map_or(Decimal::ZERO, |event| event.validator_fee_xrd),
+            reward_xrd,
+            fee_factor: state.validator_fee_factor,
+            stake_xrd,
+            stake_unit_supply,
+            stake_unit_redemption_value: redemption_value,
+            stake_unit_redemption_value_growth: redemption_value_growth,
+            claimable_unbonding_xrd,
+            pending_unbonding_xrd,
+        }
+    }
+
+    fn extract_validator_events<T: ScryptoEvent>(
+        &self,
+        result: &CommitResult,
+        validator_address: ComponentAddress,
+    ) -> Vec<T> {
+        result
+            .application_events
+            .iter()
+            .filter(|(id, _data)| match &id.0 {
+                Emitter::Method(node_id, ModuleId::Main) => {
+                    node_id == validator_address.as_node_id()
+                }
+                _ => false,
+            })
+            .filter(|(id, _data)| self.ledger.is_event_name_equal::<T>(id))
+            .map(|(_id, data)| scrypto_decode::<T>(data).unwrap())
+            .collect()
+    }
+
+    /// Maps the indices of the active validator set (i.e. the leader indices) to the indices of
+    /// the simulated validators.
+    fn read_active_validator_indices(&self) -> Vec<Option<usize>> {
+        let reader = SystemDatabaseReader::new(self.ledger.substate_db());
+        let validator_set = reader
+            .read_typed_object_field::<ConsensusManagerCurrentValidatorSetFieldPayload>(
+                CONSENSUS_MANAGER.as_node_id(),
+                ModuleId::Main,
+                ConsensusManagerField::CurrentValidatorSet.field_index(),
+            )
+            .unwrap()
+            .fully_update_and_into_latest_version()
+            .validator_set;
+        validator_set
+            .validators_by_stake_desc
+            .keys()
+            .map(|address| {
+                self.validators
+                    .iter()
+                    .position(|validator| &validator.address == address)
+            })
+            .collect()
+    }
+
+    fn read_config(&self) -> ConsensusManagerConfig {
+        let reader = SystemDatabaseReader::new(self.ledger.substate_db());
+        reader
+            .read_typed_object_field::<ConsensusManagerConfigurationFieldPayload>(
+                CONSENSUS_MANAGER.as_node_id(),
+                ModuleId::Main,
+                ConsensusManagerField::Configuration.field_index(),
+            )
+            .unwrap()
+            .fully_update_and_into_latest_version()
+            .config
+    }
+
+    fn staker_claim_nft_ids(&mut self, claim_nft: ResourceAddress) -> Vec<NonFungibleLocalId> {
+        let vaults = self
+            .ledger
+            .get_component_vaults(self.staker_account, claim_nft);
+        let mut ids = vec![];
+        for vault in vaults {
+            if let Some((_, vault_ids)) = self.ledger.inspect_non_fungible_vault(vault) {
+                ids.extend(vault_ids);
+            }
+        }
+        ids
+    }
+
+    fn execute_as_staker(&mut self, manifest: TransactionManifestV1) -> TransactionReceipt {
+        let proof = NonFungibleGlobalId::from_public_key(&self.staker_key);
+        self.ledger.execute_manifest(manifest, vec![proof])
+    }
+}
+
+fn stake_unit_redemption_value(
+    ledger: &mut DefaultLedgerSimulator,
+    validator: &ValidatorSubstate,
+) -> Decimal {
+    let stake_xrd = ledger
+        .inspect_vault_balance(validator.stake_xrd_vault_id.0)
+        .unwrap();
+    let stake_unit_supply =
+        ledger.get_fungible_resource_total_supply(validator.stake_unit_resource);
+    if stake_unit_supply.is_zero() {
+        Decimal::ONE
+    } else {
+        stake_xrd.checked_div(stake_unit_supply).unwrap()
+    }
+}
@@ -196,6 +196,18 @@ impl<C: HasStub + HasMethods> Owned<C> {
     }
 }
 
+impl<C: ComponentState> Owned<C> {
+    /// Drops this object and returns its state.
+    ///
+    /// Only the blueprint of the object may drop it. If the blueprint declares an `#[on_drop]`
+    /// hook, the hook runs before the object is removed and may abort the transaction.
+    pub fn drop_object(self) -> C {
+        let node_id = self.0.handle().as_node_id().clone();
+        let mut fields = ScryptoVmV1Api::object_drop(node_id);
+        scrypto_decode(&fields.remove(0)).unwrap()
+    }
+}
+
 pub trait FnMapping<T> {
     fn to_mapping(self) -> Vec<(String, T)>;
 }
@@ -0,0 +1,136 @@
+use crate::engine::scrypto_env::ScryptoVmV1Api;
+use radix_common::data::scrypto::*;
+use radix_engine_interface::api::{CollectionIndex, ACTOR_STATE_SELF};
+use sbor::rust::marker::PhantomData;
+use sbor::rust::prelude::*;
+
+/// An iterable collection of key-value pairs which is part of the state of a component.
+///
+/// Unlike a [`KeyValueStore`](super::KeyValueStore), an index is not an object of its own but a
+/// collection declared through the `#[collections(...)]` attribute of the blueprint, and it can
+/// only be accessed from the methods of the component which owns it. Its keys can be scanned, but
+/// in no particular order.
+pub struct Index<K: ScryptoEncode + ScryptoDecode, V: ScryptoEncode + ScryptoDecode> {
+    collection_index: CollectionIndex,
+    key: PhantomData<K>,
+    value: PhantomData<V>,
+}
+
+impl<K: ScryptoEncode + ScryptoDecode, V: ScryptoEncode + ScryptoDecode> Index<K, V> {
+    /// Creates a handle to the index at the given collection index of the current component.
+    ///
+    /// This is used by the accessors generated by the `#[blueprint]` macro, which guarantee that
+    /// the collection at the index is an index of the right key and value types.
+    pub fn new(collection_index: CollectionIndex) -> Self {
+        Self {
+            collection_index,
+            key: PhantomData,
+            value: PhantomData,
+        }
+    }
+
+    /// Inserts a key-value pair into this index, overwriting the existing value if any.
+    pub fn insert(&self, key: K, value: V) {
+        ScryptoVmV1Api::actor_index_insert(
+            ACTOR_STATE_SELF,
+            self.collection_index,
+            scrypto_encode(&key).unwrap(),
+            scrypto_encode(&value).unwrap(),
+        );
+    }
+
+    /// Removes the entry of the given key from this index, returning its value if it existed.
+    pub fn remove(&self, key: &K) -> Option<V> {
+        ScryptoVmV1Api::actor_index_remove(
+            ACTOR_STATE_SELF,
+            self.collection_index,
+            scrypto_encode(key).unwrap(),
+        )
+        .map(|value| scrypto_decode(&value).unwrap())
+    }
+
+    /// Returns up to `limit` keys of this index.
+    pub fn scan_keys(&self, limit: u32) -> Vec<K> {
+        ScryptoVmV1Api::actor_index_scan_keys(ACTOR_STATE_SELF, self.collection_index, limit)
+            .into_iter()
+            .map(|key| scrypto_decode(&key).unwrap())
+            .collect()
+    }
+
+    /// Removes up to `limit` entries from this index and returns them.
+    pub fn drain(&self, limit: u32) -> Vec<(K, V)> {
+        ScryptoVmV1Api::actor_index_drain(ACTOR_STATE_SELF, self.collection_index, limit)
+            .into_iter()
+            .map(|(key, value)| {
+                (
+                    scrypto_decode(&key).unwrap(),
+                    scrypto_decode(&value).unwrap(),
+                )
+            })
+            .collect()
+    }
+}
+
+/// A collection of key-value pairs which is part of the state of a component and which is ordered
+/// by a `u16` sort key.
+///
+/// Entries with the same sort key are ordered by the SBOR encoding of their keys. Like an
+/// [`Index`], a sorted index is declared through the `#[collections(...)]` attribute of the
+/// blueprint and can only be accessed from the methods of the component which owns it.
+pub struct SortedIndex<K: ScryptoEncode + ScryptoDecode, V: ScryptoEncode + ScryptoDecode> {
+    collection_index: CollectionIndex,
+    key: PhantomData<K>,
+    value: PhantomData<V>,
+}
+
+impl<K: ScryptoEncode + ScryptoDecode, V: ScryptoEncode + ScryptoDecode> SortedIndex<K, V> {
+    /// Creates a handle to the sorted index at the given collection index of the current
+    /// component.
+    ///
+    /// This is used by the accessors generated by the `#[blueprint]` macro, which guarantee that
+    /// the collection at the index is a sorted index of the right key and value types.
+    pub fn new(collection_index: CollectionIndex) -> Self {
+        Self {
+            collection_index,
+            key: PhantomData,
+            value: PhantomData,
+        }
+    }
+
+    /// Inserts a key-value pair with the given sort key into this index, overwriting the existing
+    /// value of the same sort key and key if any.
+    pub fn insert(&self, sort_key: u16, key: K, value: V) {
+        ScryptoVmV1Api::actor_sorted_index_insert(
+            ACTOR_STATE_SELF,
+            self.collection_index,
+            (sort_key.to_be_bytes(), scrypto_encode(&key).unwrap()),
+            scrypto_encode(&value).unwrap(),
+        );
+    }
+
+    /// Removes the entry of the given sort key and key from this index, returning its value if it
+    /// existed.
+    pub fn remove(&self, sort_key: u16, key: &K) -> Option<V> {
+        ScryptoVmV1Api::actor_sorted_index_remove(
+            ACTOR_STATE_SELF,
+            self.collection_index,
+            &(sort_key.to_be_bytes(), scrypto_encode(key).unwrap()),
+        )
+        .map(|value| scrypto_decode(&value).unwrap())
+    }
+
+    /// Returns the first `limit` entries of this index in ascending order, along with their sort
+    /// keys.
+    pub fn scan(&self, limit: u32) -> Vec<(u16, K, V)> {
+        ScryptoVmV1Api::actor_sorted_index_scan(ACTOR_STATE_SELF, self.collection_index, limit)
+            .into_iter()
+            .map(|((sort_key, key), value)| {
+                (
+                    u16::from_be_bytes(sort_key),
+                    scrypto_decode(&key).unwrap(),
+                    scrypto_decode(&value).unwrap(),
+                )
+            })
+            .collect()
+    }
+}
@@ -5,11 +5,11 @@ use radix_common::math::Decimal;
 use radix_common::types::GlobalAddressReservation;
 use radix_engine_interface::api::actor_api::EventFlags;
 use radix_engine_interface::api::key_value_entry_api::KeyValueEntryHandle;
-use radix_engine_interface::api::{ActorRefHandle, FieldValue};
+use radix_engine_interface::api::{ActorRefHandle, ActorStateHandle, CollectionIndex, FieldValue};
 use radix_engine_interface::api::{AttachedModuleId, FieldIndex, LockFlags};
 use radix_engine_interface::types::PackageAddress;
 use radix_engine_interface::types::{BlueprintId, GlobalAddress};
-use radix_engine_interface::types::{Level, NodeId, SubstateHandle};
+use radix_engine_interface::types::{Level, NodeId, SortedKey, SubstateHandle};
 use sbor::rust::prelude::*;
 
 pub struct ScryptoVmV1Api;
@@ -73,6 +73,13 @@ impl ScryptoVmV1Api {
         GlobalAddress::try_from(bytes.as_slice()).unwrap()
     }
 
+    pub fn object_drop(object_id: NodeId) -> Vec<Vec<u8>> {
+        let bytes = copy_buffer(unsafe {
+            object::object_drop(object_id.as_bytes().as_ptr(), object_id.as_bytes().len())
+        });
+        scrypto_decode(&bytes).unwrap()
+    }
+
     pub fn object_instance_of(node_id: &NodeId, blueprint_id: &BlueprintId) -> bool {
         let rtn = unsafe {
             object::object_instance_of(
@@ -229,6 +236,113 @@ impl ScryptoVmV1Api {
         };
     }
 
+    pub fn actor_index_insert(
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) {
+        unsafe {
+            actor_index::actor_index_insert(
+                object_handle,
+                u32::from(collection_index),
+                key.as_ptr(),
+                key.len(),
+                value.as_ptr(),
+                value.len(),
+            )
+        };
+    }
+
+    pub fn actor_index_remove(
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        key: Vec<u8>,
+    ) -> Option<Vec<u8>> {
+        let removed = copy_buffer(unsafe {
+            actor_index::actor_index_remove(
+                object_handle,
+                u32::from(collection_index),
+                key.as_ptr(),
+                key.len(),
+            )
+        });
+        scrypto_decode(&removed).unwrap()
+    }
+
+    pub fn actor_index_scan_keys(
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        limit: u32,
+    ) -> Vec<Vec<u8>> {
+        let keys = copy_buffer(unsafe {
+            actor_index::actor_index_scan_keys(object_handle, u32::from(collection_index), limit)
+        });
+        scrypto_decode(&keys).unwrap()
+    }
+
+    pub fn actor_index_drain(
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        limit: u32,
+    ) -> Vec<(Vec<u8>, Vec<u8>)> {
+        let entries = copy_buffer(unsafe {
+            actor_index::actor_index_drain(object_handle, u32::from(collection_index), limit)
+        });
+        scrypto_decode(&entries).unwrap()
+    }
+
+    pub fn actor_sorted_index_insert(
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        sorted_key: SortedKey,
+        value: Vec<u8>,
+    ) {
+        let sorted_key = scrypto_encode(&sorted_key).unwrap();
+        unsafe {
+            actor_sorted_index::actor_sorted_index_insert(
+                object_handle,
+                u32::from(collection_index),
+                sorted_key.as_ptr(),
+                sorted_key.len(),
+                value.as_ptr(),
+                value.len(),
+            )
+        };
+    }
+
+    pub fn actor_sorted_index_remove(
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        sorted_key: &SortedKey,
+    ) -> Option<Vec<u8>> {
+        let sorted_key = scrypto_encode(sorted_key).unwrap();
+        let removed = copy_buffer(unsafe {
+            actor_sorted_index::actor_sorted_index_remove(
+                object_handle,
+                u32::from(collection_index),
+                sorted_key.as_ptr(),
+                sorted_key.len(),
+            )
+        });
+        scrypto_decode(&removed).unwrap()
+    }
+
+    pub fn actor_sorted_index_scan(
+        object_handle: ActorStateHandle,
+        collection_index: CollectionIndex,
+        limit: u32,
+    ) -> Vec<(SortedKey, Vec<u8>)> {
+        let entries = copy_buffer(unsafe {
+            actor_sorted_index::actor_sorted_index_scan(
+                object_handle,
+                u32::from(collection_index),
+                limit,
+            )
+        });
+        scrypto_decode(&entries).unwrap()
+    }
+
     pub fn field_entry_read(lock_handle: SubstateHandle) -> Vec<u8> {
         copy_buffer(unsafe { field_entry::field_entry_read(lock_handle) })
     }
@@ -90,6 +90,9 @@ pub mod object {
             address_id_len: usize,
         ) -> Buffer;
 
+        /// Drops an owned object of the calling blueprint, returning its field values
+        pub fn object_drop(obj_id_ptr: *const u8, obj_id_len: usize) -> Buffer;
+
         /// Check if an object is an instance of blueprint
         pub fn object_instance_of(
             obj_id_ptr: *const u8,
@@ -176,6 +179,79 @@ pub mod actor {
     }
 }
 
+/// API to manipulate the index collections of the current actor
+pub mod actor_index {
+    use radix_engine_interface::api::ActorStateHandle;
+    pub use radix_engine_interface::types::{Buffer, BufferId, Slice};
+
+    super::wasm_extern_c! {
+        /// Inserts an entry into an index of the current actor
+        pub fn actor_index_insert(
+            actor_state_handle: ActorStateHandle,
+            collection_index: u32,
+            key_ptr: *const u8,
+            key_len: usize,
+            value_ptr: *const u8,
+            value_len: usize,
+        );
+
+        /// Removes an entry from an index of the current actor
+        pub fn actor_index_remove(
+            actor_state_handle: ActorStateHandle,
+            collection_index: u32,
+            key_ptr: *const u8,
+            key_len: usize,
+        ) -> Buffer;
+
+        /// Scans up to `limit` keys of an index of the current actor
+        pub fn actor_index_scan_keys(
+            actor_state_handle: ActorStateHandle,
+            collection_index: u32,
+            limit: u32,
+        ) -> Buffer;
+
+        /// Removes and returns up to `limit` entries of an index of the current actor
+        pub fn actor_index_drain(
+            actor_state_handle: ActorStateHandle,
+            collection_index: u32,
+            limit: u32,
+        ) -> Buffer;
+    }
+}
+
+/// API to manipulate the sorted index collections of the current actor
+pub mod actor_sorted_index {
+    use radix_engine_interface::api::ActorStateHandle;
+    pub use radix_engine_interface::types::{Buffer, BufferId, Slice};
+
+    super::wasm_extern_c! {
+        /// Inserts an entry into a sorted index of the current actor
+        pub fn actor_sorted_index_insert(
+            actor_state_handle: ActorStateHandle,
+            collection_index: u32,
+            sorted_key_ptr: *const u8,
+            sorted_key_len: usize,
+            value_ptr: *const u8,
+            value_len: usize,
+        );
+
+        /// Removes an entry from a sorted index of the current actor
+        pub fn actor_sorted_index_remove(
+            actor_state_handle: ActorStateHandle,
+            collection_index: u32,
+            sorted_key_ptr: *const u8,
+            sorted_key_len: usize,
+        ) -> Buffer;
+
+        /// Scans up to `limit` entries of a sorted index of the current actor, in ascending order
+        pub fn actor_sorted_index_scan(
+            actor_state_handle: ActorStateHandle,
+            collection_index: u32,
+            limit: u32,
+        ) -> Buffer;
+    }
+}
+
 pub mod kv_store {
     pub use radix_engine_interface::types::{Buffer, BufferId, Slice};
 